@@ -0,0 +1,106 @@
+//
+// authentication_service/invites.rs
+//
+// This module provides the single-use invite codes admins issue so that new
+// staff members can register accounts with elevated roles. Only a SHA-256
+// hash of each code is stored.
+//
+
+use super::{generate_code, types::InviteCode, types::UserRole, AuthenticationService};
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use sha2::{Digest, Sha256};
+
+/// How long an invite code stays usable, in seconds
+const INVITE_CODE_LIFETIME_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Length of invite codes
+const INVITE_CODE_LENGTH: usize = 16;
+
+impl AuthenticationService {
+    // ==================== INVITE CODE OPERATIONS ====================
+
+    /// Issues a single-use invite code that lets someone register an account with a staff role
+    ///
+    /// # Arguments
+    /// * `created_by` - Username of the admin issuing the code
+    /// * `role` - Role the code grants; must be staff or admin
+    ///
+    /// # Returns
+    /// * `Result<InviteCode>` - The code to hand to the new staff member, or error if the
+    ///   role does not need an invite
+    pub fn create_invite_code(&self, created_by: &str, role: UserRole) -> Result<InviteCode> {
+        if !role.is_staff() {
+            bail!("Invite codes are only needed for staff and admin accounts");
+        }
+
+        let code = generate_code(INVITE_CODE_LENGTH);
+        let now = Utc::now().timestamp();
+        let expires_timestamp = now + INVITE_CODE_LIFETIME_SECONDS;
+        self.connection
+            .execute(
+                "INSERT INTO invite_codes (code_hash, role, created_by, created_timestamp, expires_timestamp) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![hash_invite_code(&code), role, created_by, now, expires_timestamp],
+            )
+            .context("Failed to insert invite code")?;
+
+        log::info!("{} issued an invite code for the {} role", created_by, role);
+        Ok(InviteCode {
+            code,
+            role,
+            expires_timestamp,
+        })
+    }
+
+    /// Finds the role an unused, unexpired invite code grants
+    ///
+    /// # Arguments
+    /// * `code` - The invite code given by the user
+    ///
+    /// # Returns
+    /// * `Result<Option<UserRole>>` - The role, or None if the code cannot be used
+    pub(super) fn invite_code_role(&self, code: &str) -> Result<Option<UserRole>> {
+        self.connection
+            .query_row(
+                "SELECT role FROM invite_codes WHERE code_hash = ?1 AND used_by IS NULL AND expires_timestamp > ?2",
+                params![hash_invite_code(code), Utc::now().timestamp()],
+                |row| row.get::<_, UserRole>(0),
+            )
+            .optional()
+            .context("Failed to query invite code")
+    }
+
+    /// Marks an invite code as used so it cannot register another account
+    ///
+    /// # Arguments
+    /// * `code` - The invite code given by the user
+    /// * `username` - Username of the account registered with the code
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub(super) fn redeem_invite_code(&self, code: &str, username: &str) -> Result<()> {
+        self.connection
+            .execute(
+                "UPDATE invite_codes SET used_by = ?1, used_timestamp = ?2 WHERE code_hash = ?3",
+                params![username, Utc::now().timestamp(), hash_invite_code(code)],
+            )
+            .context("Failed to redeem invite code")?;
+        log::info!("Invite code redeemed by username: {}", username);
+        Ok(())
+    }
+}
+
+/// Hashes an invite code for storage and lookup
+///
+/// Codes are long and random, so a fast unsalted hash is enough to keep them
+/// from being read back out of the database.
+///
+/// # Arguments
+/// * `code` - The invite code
+///
+/// # Returns
+/// * `String` - The hex-encoded SHA-256 digest of the normalized code
+fn hash_invite_code(code: &str) -> String {
+    hex::encode(Sha256::digest(code.trim().to_uppercase().as_bytes()))
+}
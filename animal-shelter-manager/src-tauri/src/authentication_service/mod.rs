@@ -3,24 +3,72 @@
 //
 // This module provides authentication-related functionality including
 // user registration, login/logout, and session management.
-// Passwords are securely hashed using bcrypt.
+// Passwords are securely hashed using Argon2id, with accounts hashed by
+// bcrypt before that being rehashed on their next successful login.
+// Sessions are persisted
+// so that a logged-in user stays logged in across app restarts until the
+// session expires or is left idle for too long.
 //
 
+mod invites;
+mod rate_limit;
 mod test;
+mod two_factor;
 pub mod types;
 
-use anyhow::{bail, Context, Result};
-use bcrypt::{hash, verify, DEFAULT_COST};
-use rusqlite::{params, Connection};
+use crate::database_service::{
+    add_column_if_missing,
+    maintenance::vacuum_and_analyze,
+    search::{match_patterns, match_score_sql, MAX_SEARCH_RESULTS},
+    types::{MaintenanceResult, SearchHit},
+    WEB_INTAKE_USERNAME,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::{
+    password_hash::{
+        rand_core::{OsRng, RngCore},
+        PasswordHash, PasswordHasher, PasswordVerifier, SaltString,
+    },
+    Argon2,
+};
+use chrono::Utc;
+use rate_limit::LoginRateLimiter;
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
 use std::path::Path;
-use types::{LoginResult, UserAuthentication, UserRole};
+use std::time::Instant;
+use two_factor::PendingTwoFactor;
+use types::{
+    HashAlgorithm, InviteCodeError, LoginResult, PasswordReset, SessionInfo, SessionPolicy,
+    UserAccount, UserAuthentication, UserRole, UserStatus,
+};
+use uuid::Uuid;
+
+/// How long a temporary password issued by staff stays usable, in seconds
+const TEMPORARY_PASSWORD_LIFETIME_SECONDS: i64 = 24 * 60 * 60;
+
+/// Characters temporary passwords and invite codes are made of, leaving out look-alikes
+/// such as 0 and O
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Length of temporary passwords
+const TEMPORARY_PASSWORD_LENGTH: usize = 10;
+
+/// Version of the data migrations applied by `migrate_data`, stored in the `user_version` pragma
+const DATA_VERSION: i64 = 1;
 
 /// Service for handling authentication operations in the animal shelter application
 pub struct AuthenticationService {
     /// Current logged-in username, None if no user is logged in
     current_user: Option<String>,
+    /// Token of the current user's persisted session, None if no user is logged in
+    session_token: Option<String>,
     /// SQLite database connection for authentication data
     connection: Connection,
+    /// Limits how quickly login attempts can be made for each username
+    login_rate_limiter: LoginRateLimiter,
+    /// Login waiting for its two-factor code, None if no login is waiting
+    pending_two_factor: Option<PendingTwoFactor>,
 }
 
 /// Represents the current user's information
@@ -62,15 +110,26 @@ impl AuthenticationService {
         };
 
         // Create service instance
-        let service = AuthenticationService {
+        let mut service = AuthenticationService {
             current_user: None,
+            session_token: None,
             connection,
+            login_rate_limiter: LoginRateLimiter::default(),
+            pending_two_factor: None,
         };
 
         // Initialize database tables
         service
             .initialize_tables()
             .context("Failed to initialize authentication database tables")?;
+        service
+            .migrate_data()
+            .context("Failed to migrate authentication data")?;
+
+        // Log the user of the last session back in; a failure here only means logging in again
+        if let Err(e) = service.restore_session() {
+            log::warn!("Failed to restore previous session: {}", e);
+        }
 
         log::info!(
             "Authentication service initialized successfully at path: {:?}",
@@ -92,18 +151,233 @@ impl AuthenticationService {
             CREATE TABLE IF NOT EXISTS user_authentication (
                 username TEXT PRIMARY KEY,
                 password_hash TEXT NOT NULL,
-                role TEXT NOT NULL
+                role TEXT NOT NULL,
+                hash_algorithm TEXT NOT NULL DEFAULT 'bcrypt',
+                created_timestamp INTEGER,
+                status TEXT NOT NULL DEFAULT 'active',
+                must_change_password INTEGER NOT NULL DEFAULT 0
             )
             ",
                 [],
             )
             .context("Failed to create user_authentication table")?;
 
+        // Accounts created before the algorithm was recorded were all hashed with bcrypt
+        add_column_if_missing(
+            &self.connection,
+            "user_authentication",
+            "hash_algorithm",
+            "TEXT NOT NULL DEFAULT 'bcrypt'",
+        )?;
+        add_column_if_missing(
+            &self.connection,
+            "user_authentication",
+            "created_timestamp",
+            "INTEGER",
+        )?;
+        add_column_if_missing(
+            &self.connection,
+            "user_authentication",
+            "status",
+            "TEXT NOT NULL DEFAULT 'active'",
+        )?;
+        add_column_if_missing(
+            &self.connection,
+            "user_authentication",
+            "must_change_password",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+
+        // Create sessions table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS sessions (
+                token TEXT PRIMARY KEY,
+                username TEXT NOT NULL,
+                remembered INTEGER NOT NULL,
+                created_timestamp INTEGER NOT NULL,
+                last_activity_timestamp INTEGER NOT NULL,
+                expires_timestamp INTEGER NOT NULL,
+                device_name TEXT
+            )
+            ",
+                [],
+            )
+            .context("Failed to create sessions table")?;
+        add_column_if_missing(&self.connection, "sessions", "device_name", "TEXT")?;
+
+        // Create password_resets table, holding each user's pending temporary password
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS password_resets (
+                username TEXT PRIMARY KEY,
+                password_hash TEXT NOT NULL,
+                expires_timestamp INTEGER NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create password_resets table")?;
+
+        // Create two_factor table, holding the TOTP secrets of enrolled accounts
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS two_factor (
+                username TEXT PRIMARY KEY,
+                secret TEXT NOT NULL,
+                enabled INTEGER NOT NULL,
+                last_used_step INTEGER
+            )
+            ",
+                [],
+            )
+            .context("Failed to create two_factor table")?;
+
+        // Create invite_codes table; only a hash of each code is stored
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS invite_codes (
+                code_hash TEXT PRIMARY KEY,
+                role TEXT NOT NULL,
+                created_by TEXT NOT NULL,
+                created_timestamp INTEGER NOT NULL,
+                expires_timestamp INTEGER NOT NULL,
+                used_by TEXT,
+                used_timestamp INTEGER
+            )
+            ",
+                [],
+            )
+            .context("Failed to create invite_codes table")?;
+
+        // Create session_policy table, holding at most one row
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS session_policy (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                lifetime_seconds INTEGER NOT NULL,
+                remembered_lifetime_seconds INTEGER NOT NULL,
+                idle_timeout_seconds INTEGER NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create session_policy table")?;
+
+        Ok(())
+    }
+
+    /// Applies one-off data migrations that have not yet run on this database,
+    /// tracked through SQLite's `user_version` pragma
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn migrate_data(&self) -> Result<()> {
+        let data_version: i64 = self
+            .connection
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .context("Failed to read authentication database user version")?;
+
+        if data_version < 1 {
+            self.promote_first_staff_to_admin()?;
+        }
+
+        self.connection
+            .pragma_update(None, "user_version", DATA_VERSION)
+            .context("Failed to update authentication database user version")?;
+        Ok(())
+    }
+
+    /// Gives the admin role to the oldest active staff account when there is no admin,
+    /// as on databases created before the admin role existed, so that someone can
+    /// issue invite codes and manage the other accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn promote_first_staff_to_admin(&self) -> Result<()> {
+        if self.count_admins()? > 0 {
+            return Ok(());
+        }
+        let username: Option<String> = self
+            .connection
+            .query_row(
+                "SELECT username FROM user_authentication WHERE role = ?1 AND status = ?2 ORDER BY rowid LIMIT 1",
+                params![UserRole::Staff, UserStatus::Active],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to find the oldest staff account")?;
+        if let Some(username) = username {
+            self.connection
+                .execute(
+                    "UPDATE user_authentication SET role = ?1 WHERE username = ?2",
+                    params![UserRole::Admin, username],
+                )
+                .context("Failed to promote staff account to admin")?;
+            log::info!(
+                "Promoted {} to admin, as no admin account existed",
+                username
+            );
+        }
         Ok(())
     }
 
     /// Registers a new user with the given credentials and logs them in
     ///
+    /// Anyone may sign up as a customer, and as an admin while there are no accounts
+    /// at all. Every other staff or admin account needs an unused invite code issued
+    /// by an admin, and is given the role the code grants.
+    ///
+    /// # Arguments
+    /// * `username` - Username for the new account
+    /// * `password` - Plain text password (will be hashed securely)
+    /// * `role` - Role asked for when signing up without an invite code
+    /// * `invite_code` - Invite code granting a staff role, if any
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn sign_up(
+        &mut self,
+        username: &str,
+        password: &str,
+        role: UserRole,
+        invite_code: Option<&str>,
+    ) -> Result<()> {
+        let invite_code = invite_code.map(str::trim).filter(|code| !code.is_empty());
+        if let Some(invite_code) = invite_code {
+            // The code decides the role, whatever was asked for
+            let Some(granted_role) = self.invite_code_role(invite_code)? else {
+                bail!(InviteCodeError::Invalid);
+            };
+            self.register_user(username, password, granted_role)?;
+            self.redeem_invite_code(invite_code, username)?;
+        } else {
+            // Only the very first account may make itself an admin
+            match role {
+                UserRole::Customer => {}
+                UserRole::Admin if self.count_users()? == 0 => {}
+                _ => bail!(InviteCodeError::Missing),
+            }
+            self.register_user(username, password, role)?;
+        }
+
+        // Automatically log in the user after successful registration
+        self.start_session(username, false)?;
+
+        log::info!(
+            "User account created and logged in successfully for username: {}",
+            username
+        );
+        Ok(())
+    }
+
+    /// Registers a new user with the given credentials, without logging them in
+    ///
     /// # Arguments
     /// * `username` - Username for the new account
     /// * `password` - Plain text password (will be hashed securely)
@@ -111,37 +385,38 @@ impl AuthenticationService {
     ///
     /// # Returns
     /// * `Result<()>` - Success or error
-    pub fn sign_up(&mut self, username: &str, password: &str, role: UserRole) -> Result<()> {
+    pub fn register_user(&self, username: &str, password: &str, role: UserRole) -> Result<()> {
         // Validate input parameters
         if username.trim().is_empty() {
             bail!("Username cannot be empty");
         }
-        if password.len() < 6 {
-            bail!("Password must be at least 6 characters long");
+        if username.trim() == WEB_INTAKE_USERNAME {
+            bail!("Username {} is reserved", WEB_INTAKE_USERNAME);
         }
-
-        // Hash the password securely
-        let password_hash = hash(password, DEFAULT_COST).context("Failed to hash password")?;
+        let password_hash = hash_password(password)?;
 
         // Create user authentication record
         let user_auth = UserAuthentication {
             username: username.to_string(),
             password_hash,
+            hash_algorithm: HashAlgorithm::Argon2id,
             role,
         };
 
         // Insert user into database
         self.insert_user(&user_auth)
-            .context("Failed to create user account")?;
-
-        // Automatically log in the user after successful registration
-        self.current_user = Some(username.to_string());
+            .context("Failed to create user account")
+    }
 
-        log::info!(
-            "User account created and logged in successfully for username: {}",
-            username
-        );
-        Ok(())
+    /// Checks whether an account exists for a username
+    ///
+    /// # Arguments
+    /// * `username` - Username to look up
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the account exists
+    pub fn user_exists(&self, username: &str) -> Result<bool> {
+        Ok(self.get_password_hash(username)?.is_some())
     }
 
     /// Attempts to log in a user with the given credentials
@@ -149,12 +424,31 @@ impl AuthenticationService {
     /// # Arguments
     /// * `username` - Username to log in
     /// * `password` - Plain text password to verify
+    /// * `remember_me` - Whether the session should last for days instead of hours
     ///
     /// # Returns
-    /// * `Result<LoginResult>` - Login result indicating success, invalid password, or user not found
-    pub fn log_in(&mut self, username: &str, password: &str) -> Result<LoginResult> {
+    /// * `Result<LoginResult>` - Login result indicating success, invalid password, user not found,
+    ///   too many attempts, or that a new password or two-factor code is needed to finish
+    pub fn log_in(
+        &mut self,
+        username: &str,
+        password: &str,
+        remember_me: bool,
+    ) -> Result<LoginResult> {
+        // A new login abandons any login still waiting for its two-factor code
+        self.pending_two_factor = None;
+
+        // Refuse attempts beyond the rate limit before doing any expensive work
+        if !self
+            .login_rate_limiter
+            .try_acquire(username, Instant::now())
+        {
+            log::warn!("Too many login attempts for username: {}", username);
+            return Ok(LoginResult::TooManyAttempts);
+        }
+
         // Retrieve password hash from database
-        let stored_hash = match self.get_password_hash(username)? {
+        let (stored_hash, hash_algorithm) = match self.get_password_hash(username)? {
             Some(hash) => hash,
             None => {
                 log::warn!("Login attempt for non-existent username: {}", username);
@@ -163,24 +457,219 @@ impl AuthenticationService {
         };
 
         // Verify password against stored hash
-        let password_valid = verify(password, &stored_hash).context("Failed to verify password")?;
+        let password_valid = verify_password(password, &stored_hash, hash_algorithm)?;
 
         if password_valid {
-            // Set current user on successful login
-            self.current_user = Some(username.to_string());
+            if self.get_user_status(username)? == Some(UserStatus::Suspended) {
+                log::warn!("Login attempt for suspended username: {}", username);
+                return Ok(LoginResult::Suspended);
+            }
+
+            // Move accounts off older algorithms while the plain text password is at hand
+            if hash_algorithm != HashAlgorithm::Argon2id {
+                if let Err(e) = self.update_password_hash(username, password) {
+                    log::warn!("Failed to rehash password for {}: {}", username, e);
+                }
+            }
+
+            // Remembering the password makes any pending temporary password unnecessary
+            self.delete_password_reset(username)?;
+
+            // Passwords chosen by an admin must be replaced before the account can be used
+            if self.must_change_password(username)? {
+                log::info!("Password change required for username: {}", username);
+                return Ok(LoginResult::PasswordChangeRequired);
+            }
+
+            if self.is_totp_enabled(username)? {
+                self.begin_two_factor(username, remember_me);
+                return Ok(LoginResult::TwoFactorRequired);
+            }
+
+            // Start a session for the user on successful login
+            self.start_session(username, remember_me)?;
             log::info!("User logged in successfully: {}", username);
             Ok(LoginResult::Success)
+        } else if self.matches_password_reset(username, password)? {
+            if self.get_user_status(username)? == Some(UserStatus::Suspended) {
+                log::warn!("Login attempt for suspended username: {}", username);
+                return Ok(LoginResult::Suspended);
+            }
+            log::info!("Temporary password used for username: {}", username);
+            Ok(LoginResult::PasswordChangeRequired)
         } else {
             log::warn!("Invalid password for username: {}", username);
             Ok(LoginResult::InvalidPassword)
         }
     }
 
-    /// Retrieves information about the current logged-in user
+    /// Issues a one-time temporary password for a user who forgot theirs,
+    /// replacing any temporary password issued before
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account to reset
+    /// * `issuer_role` - Role of the staff member issuing the reset; staff can only
+    ///   reset customers, while admins can reset anyone
+    ///
+    /// # Returns
+    /// * `Result<PasswordReset>` - The temporary password to hand to the user, or error if
+    ///   the account does not exist or the issuer may not reset it
+    pub fn create_password_reset(
+        &self,
+        username: &str,
+        issuer_role: &UserRole,
+    ) -> Result<PasswordReset> {
+        let role = self
+            .get_user_role(username)?
+            .context(format!("User {} not found", username))?;
+        match issuer_role {
+            UserRole::Admin => {}
+            UserRole::Staff if role == UserRole::Customer => {}
+            UserRole::Staff => bail!("Only an admin can reset the password of a staff account"),
+            UserRole::Customer => bail!("Only staff members can reset passwords"),
+        }
+
+        let temporary_password = generate_code(TEMPORARY_PASSWORD_LENGTH);
+        let password_hash = hash_password(&temporary_password)?;
+        let expires_timestamp = Utc::now().timestamp() + TEMPORARY_PASSWORD_LIFETIME_SECONDS;
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO password_resets (username, password_hash, expires_timestamp) VALUES (?1, ?2, ?3)",
+                params![username, password_hash, expires_timestamp],
+            )
+            .context("Failed to insert password reset")?;
+
+        log::info!("Issued temporary password for username: {}", username);
+        Ok(PasswordReset {
+            username: username.to_string(),
+            temporary_password,
+            expires_timestamp,
+        })
+    }
+
+    /// Sets a new password using a temporary password, then logs the user in
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account being reset
+    /// * `temporary_password` - The temporary password issued by staff
+    /// * `new_password` - Plain text password to replace the forgotten one
+    ///
+    /// # Returns
+    /// * `Result<LoginResult>` - Success, two-factor required if the account is enrolled,
+    ///   invalid password if the temporary password is wrong or expired, or too many attempts
+    pub fn complete_password_reset(
+        &mut self,
+        username: &str,
+        temporary_password: &str,
+        new_password: &str,
+    ) -> Result<LoginResult> {
+        if !self
+            .login_rate_limiter
+            .try_acquire(username, Instant::now())
+        {
+            log::warn!(
+                "Too many password reset attempts for username: {}",
+                username
+            );
+            return Ok(LoginResult::TooManyAttempts);
+        }
+        if !self.matches_password_reset(username, temporary_password)? {
+            log::warn!("Invalid temporary password for username: {}", username);
+            return Ok(LoginResult::InvalidPassword);
+        }
+
+        self.finish_password_change(username, new_password)
+    }
+
+    /// Sets a new password for an account that must change its password, then logs the
+    /// user in. Either the account's current password or a pending temporary password
+    /// is accepted as proof of identity.
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account
+    /// * `current_password` - The account's current or temporary password
+    /// * `new_password` - Plain text password to replace it with
+    ///
+    /// # Returns
+    /// * `Result<LoginResult>` - Success, two-factor required if the account is enrolled,
+    ///   invalid password if the current password is wrong, suspended, or too many attempts;
+    ///   error if the new password is invalid or the same as the current one
+    pub fn change_password(
+        &mut self,
+        username: &str,
+        current_password: &str,
+        new_password: &str,
+    ) -> Result<LoginResult> {
+        if !self
+            .login_rate_limiter
+            .try_acquire(username, Instant::now())
+        {
+            log::warn!(
+                "Too many password change attempts for username: {}",
+                username
+            );
+            return Ok(LoginResult::TooManyAttempts);
+        }
+
+        let Some((stored_hash, hash_algorithm)) = self.get_password_hash(username)? else {
+            log::warn!(
+                "Password change attempt for non-existent username: {}",
+                username
+            );
+            return Ok(LoginResult::UserNotFound);
+        };
+        if !verify_password(current_password, &stored_hash, hash_algorithm)?
+            && !self.matches_password_reset(username, current_password)?
+        {
+            log::warn!("Invalid current password for username: {}", username);
+            return Ok(LoginResult::InvalidPassword);
+        }
+        if new_password == current_password {
+            bail!("The new password must be different from the current one");
+        }
+
+        self.finish_password_change(username, new_password)
+    }
+
+    /// Replaces the password of a user who proved their identity, then logs them in
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account
+    /// * `new_password` - Plain text password to replace the old one
+    ///
+    /// # Returns
+    /// * `Result<LoginResult>` - Success, two-factor required if the account is enrolled,
+    ///   or suspended; error if the new password is invalid
+    fn finish_password_change(
+        &mut self,
+        username: &str,
+        new_password: &str,
+    ) -> Result<LoginResult> {
+        if self.get_user_status(username)? == Some(UserStatus::Suspended) {
+            log::warn!(
+                "Password change attempt for suspended username: {}",
+                username
+            );
+            return Ok(LoginResult::Suspended);
+        }
+
+        self.replace_password(username, new_password)?;
+        if self.is_totp_enabled(username)? {
+            self.begin_two_factor(username, false);
+            return Ok(LoginResult::TwoFactorRequired);
+        }
+        self.start_session(username, false)?;
+        log::info!("Password changed for username: {}", username);
+        Ok(LoginResult::Success)
+    }
+
+    /// Retrieves information about the current logged-in user, logging them out
+    /// first if their session has expired or been idle for too long
     ///
     /// # Returns
     /// * `Result<Option<CurrentUser>>` - Current user info if logged in, None otherwise
-    pub fn get_current_user(&self) -> Result<Option<CurrentUser>> {
+    pub fn get_current_user(&mut self) -> Result<Option<CurrentUser>> {
+        self.expire_inactive_session()?;
         match &self.current_user {
             Some(username) => {
                 // Get user role from database
@@ -201,41 +690,537 @@ impl AuthenticationService {
         }
     }
 
-    /// Logs out the current user
+    /// Records activity by the current user, keeping their session from idling out
     ///
     /// # Returns
-    /// * `()` - Always succeeds
-    pub fn log_out(&mut self) {
-        match &self.current_user {
+    /// * `Result<bool>` - True if the user is still logged in, false if their session has ended
+    pub fn refresh_session(&mut self) -> Result<bool> {
+        self.expire_inactive_session()?;
+        let Some(token) = &self.session_token else {
+            return Ok(false);
+        };
+
+        self.connection
+            .execute(
+                "UPDATE sessions SET last_activity_timestamp = ?1 WHERE token = ?2",
+                params![Utc::now().timestamp(), token],
+            )
+            .context("Failed to refresh session")?;
+        Ok(true)
+    }
+
+    /// Retrieves how long sessions last
+    ///
+    /// # Returns
+    /// * `Result<SessionPolicy>` - The configured policy, or the default one if never configured
+    pub fn get_session_policy(&self) -> Result<SessionPolicy> {
+        let policy = self
+            .connection
+            .query_row(
+                "SELECT lifetime_seconds, remembered_lifetime_seconds, idle_timeout_seconds FROM session_policy WHERE id = 1",
+                [],
+                |row| {
+                    Ok(SessionPolicy {
+                        lifetime_seconds: row.get(0)?,
+                        remembered_lifetime_seconds: row.get(1)?,
+                        idle_timeout_seconds: row.get(2)?,
+                    })
+                },
+            )
+            .optional()
+            .context("Failed to query session policy")?;
+        Ok(policy.unwrap_or_default())
+    }
+
+    /// Changes how long sessions last; sessions already started keep their lifetime
+    ///
+    /// # Arguments
+    /// * `policy` - The new policy
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or error if a duration is not positive
+    pub fn set_session_policy(&self, policy: &SessionPolicy) -> Result<()> {
+        if policy.lifetime_seconds <= 0
+            || policy.remembered_lifetime_seconds <= 0
+            || policy.idle_timeout_seconds <= 0
+        {
+            bail!("Session durations must be positive");
+        }
+
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO session_policy (id, lifetime_seconds, remembered_lifetime_seconds, idle_timeout_seconds) VALUES (1, ?1, ?2, ?3)",
+                params![
+                    policy.lifetime_seconds,
+                    policy.remembered_lifetime_seconds,
+                    policy.idle_timeout_seconds
+                ],
+            )
+            .context("Failed to update session policy")?;
+
+        log::info!("Updated session policy: {:?}", policy);
+        Ok(())
+    }
+
+    /// Lists the sessions that are still active, most recently used first
+    ///
+    /// # Returns
+    /// * `Result<Vec<SessionInfo>>` - The active sessions or error
+    pub fn list_sessions(&self) -> Result<Vec<SessionInfo>> {
+        self.delete_inactive_sessions()?;
+
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT token, username, remembered, created_timestamp, last_activity_timestamp, expires_timestamp, device_name FROM sessions ORDER BY last_activity_timestamp DESC",
+            )
+            .context("Failed to prepare query for sessions")?;
+
+        let sessions = statement
+            .query_map([], |row| {
+                let token: String = row.get(0)?;
+                Ok(SessionInfo {
+                    id: derive_session_id(&token),
+                    current: self.session_token.as_ref() == Some(&token),
+                    username: row.get(1)?,
+                    remembered: row.get(2)?,
+                    created_timestamp: row.get(3)?,
+                    last_activity_timestamp: row.get(4)?,
+                    expires_timestamp: row.get(5)?,
+                    device_name: row.get(6)?,
+                })
+            })
+            .context("Failed to execute query for sessions")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse session rows")?;
+
+        log::debug!("Retrieved {} active sessions", sessions.len());
+        Ok(sessions)
+    }
+
+    /// Ends a session, logging out whoever is using it the next time their app checks
+    ///
+    /// # Arguments
+    /// * `session_id` - ID of the session, as listed by `list_sessions`
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the session was found and ended, false if not found
+    pub fn terminate_session(&mut self, session_id: &str) -> Result<bool> {
+        let tokens = self
+            .connection
+            .prepare("SELECT token FROM sessions")
+            .context("Failed to prepare query for sessions")?
+            .query_map([], |row| row.get::<_, String>(0))
+            .context("Failed to execute query for sessions")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse session rows")?;
+        let Some(token) = tokens
+            .into_iter()
+            .find(|token| derive_session_id(token) == session_id)
+        else {
+            return Ok(false);
+        };
+
+        self.connection
+            .execute("DELETE FROM sessions WHERE token = ?1", params![token])
+            .context("Failed to delete session")?;
+        log::info!("Terminated session: {}", session_id);
+
+        // Ending the current session logs this app out right away
+        self.expire_inactive_session()?;
+        Ok(true)
+    }
+
+    /// Logs out the current user and invalidates their persisted session
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn log_out(&mut self) -> Result<()> {
+        self.end_session()?;
+        match self.current_user.take() {
             Some(username) => {
                 log::info!("User logged out: {}", username);
-                self.current_user = None;
             }
             None => {
                 log::warn!("No user was logged in to log out");
             }
         }
+        Ok(())
+    }
+
+    /// Lists every user account, ordered by username
+    ///
+    /// # Returns
+    /// * `Result<Vec<UserAccount>>` - The accounts or error
+    pub fn list_users(&self) -> Result<Vec<UserAccount>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT username, role, created_timestamp, status FROM user_authentication ORDER BY username",
+            )
+            .context("Failed to prepare query for users")?;
+
+        let users = statement
+            .query_map([], |row| {
+                Ok(UserAccount {
+                    username: row.get(0)?,
+                    role: row.get(1)?,
+                    created_timestamp: row.get(2)?,
+                    status: row.get(3)?,
+                })
+            })
+            .context("Failed to execute query for users")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse user rows")?;
+
+        log::debug!("Retrieved {} user accounts", users.len());
+        Ok(users)
+    }
+
+    /// Finds the user accounts whose username contains some text
+    ///
+    /// # Arguments
+    /// * `text` - The text to look for, ignoring case
+    /// * `limit` - Most accounts to return, capped at `MAX_SEARCH_RESULTS`
+    ///
+    /// # Returns
+    /// * `Result<Vec<SearchHit<UserAccount>>>` - The accounts found, best matches first, or error
+    pub fn search_users(&self, text: &str, limit: usize) -> Result<Vec<SearchHit<UserAccount>>> {
+        let query = format!(
+            "SELECT * FROM (SELECT username, role, created_timestamp, status, {} AS score FROM user_authentication) WHERE score > 0 ORDER BY score DESC, username LIMIT {}",
+            match_score_sql(&[("username", 3)]),
+            limit.min(MAX_SEARCH_RESULTS)
+        );
+        let mut statement = self
+            .connection
+            .prepare(&query)
+            .context("Failed to prepare user search")?;
+
+        let hits = statement
+            .query_map(rusqlite::params_from_iter(match_patterns(text)), |row| {
+                Ok(SearchHit {
+                    record: UserAccount {
+                        username: row.get(0)?,
+                        role: row.get(1)?,
+                        created_timestamp: row.get(2)?,
+                        status: row.get(3)?,
+                    },
+                    score: row.get(4)?,
+                })
+            })
+            .context("Failed to execute user search")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse user search row")?;
+        Ok(hits)
+    }
+
+    /// Changes the role of a user account
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account to change
+    /// * `role` - New role for the account
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or error if the account does not exist or is the last admin
+    pub fn change_user_role(&self, username: &str, role: UserRole) -> Result<()> {
+        let current_role = self
+            .get_user_role(username)?
+            .context(format!("User {} not found", username))?;
+
+        // Keep at least one account able to manage the others
+        if current_role == UserRole::Admin
+            && role != UserRole::Admin
+            && self.get_user_status(username)? == Some(UserStatus::Active)
+            && self.count_admins()? <= 1
+        {
+            bail!("Cannot remove the admin role from the last admin");
+        }
+
+        self.connection
+            .execute(
+                "UPDATE user_authentication SET role = ?1 WHERE username = ?2",
+                params![role, username],
+            )
+            .context("Failed to update user role")?;
+
+        log::info!(
+            "Changed role of {} from {} to {}",
+            username,
+            current_role,
+            role
+        );
+        Ok(())
+    }
+
+    /// Suspends a user account so it cannot log in, and ends all of its sessions
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account to suspend
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or error if the account does not exist or is the last active admin
+    pub fn suspend_user(&self, username: &str) -> Result<()> {
+        let role = self
+            .get_user_role(username)?
+            .context(format!("User {} not found", username))?;
+
+        // Keep at least one account able to manage the others
+        if role == UserRole::Admin
+            && self.get_user_status(username)? == Some(UserStatus::Active)
+            && self.count_admins()? <= 1
+        {
+            bail!("Cannot suspend the last active admin");
+        }
+
+        self.set_user_status(username, UserStatus::Suspended)?;
+        self.connection
+            .execute(
+                "DELETE FROM sessions WHERE username = ?1",
+                params![username],
+            )
+            .context("Failed to end sessions of suspended user")?;
+        self.delete_password_reset(username)?;
+
+        log::info!("Suspended user account: {}", username);
+        Ok(())
+    }
+
+    /// Reactivates a suspended user account so it can log in again
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account to reactivate
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or error if the account does not exist
+    pub fn reactivate_user(&self, username: &str) -> Result<()> {
+        self.set_user_status(username, UserStatus::Active)?;
+        log::info!("Reactivated user account: {}", username);
+        Ok(())
+    }
+
+    /// Deletes a customer account along with its sessions, pending password reset and
+    /// two-factor secret, once the customer's data has been erased
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account to delete
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the account was deleted, false if it did not exist
+    pub fn erase_user(&self, username: &str) -> Result<bool> {
+        match self.get_user_role(username)? {
+            None => return Ok(false),
+            Some(UserRole::Customer) => {}
+            Some(_) => bail!("Only customer accounts can be erased"),
+        }
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for erasing user")?;
+        for table in [
+            "sessions",
+            "password_resets",
+            "two_factor",
+            "user_authentication",
+        ] {
+            transaction
+                .execute(
+                    &format!("DELETE FROM {} WHERE username = ?1", table),
+                    params![username],
+                )
+                .context(format!("Failed to delete {} of erased user", table))?;
+        }
+        transaction
+            .commit()
+            .context("Failed to commit erasing user")?;
+
+        log::info!("Erased user account: {}", username);
+        Ok(true)
+    }
+
+    /// Creates an account on behalf of a user; the user must choose their own
+    /// password the first time they log in
+    ///
+    /// # Arguments
+    /// * `username` - Username for the new account
+    /// * `password` - Initial password to hand to the user
+    /// * `role` - Role to assign to the new user
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn create_user(&self, username: &str, password: &str, role: UserRole) -> Result<()> {
+        self.register_user(username, password, role)?;
+        self.set_must_change_password(username, true)?;
+
+        log::info!("Created user account for username: {}", username);
+        Ok(())
+    }
+
+    /// Replaces the password of a user account; the user must choose their own
+    /// password the next time they log in
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account to change
+    /// * `new_password` - Plain text password (will be hashed securely)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or error if the account does not exist or the password is invalid
+    pub fn reset_password(&self, username: &str, new_password: &str) -> Result<()> {
+        if !self.replace_password(username, new_password)? {
+            bail!("User {} not found", username);
+        }
+        self.set_must_change_password(username, true)?;
+
+        log::info!("Reset password for username: {}", username);
+        Ok(())
+    }
+
+    /// Compacts the authentication database and refreshes its query planner statistics
+    ///
+    /// # Returns
+    /// * `Result<MaintenanceResult>` - The size of the database before and after or error
+    pub fn run_maintenance(&self) -> Result<MaintenanceResult> {
+        vacuum_and_analyze(&self.connection, "authentication")
     }
 
     // ==================== PRIVATE DATABASE OPERATIONS ====================
 
+    /// Persists a new session for a user and makes them the current user,
+    /// replacing any session that was already active
+    ///
+    /// # Arguments
+    /// * `username` - The username to start the session for
+    /// * `remember_me` - Whether the session should last for days instead of hours
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn start_session(&mut self, username: &str, remember_me: bool) -> Result<()> {
+        self.end_session()?;
+
+        let policy = self.get_session_policy()?;
+        let token = Uuid::new_v4().to_string();
+        let now = Utc::now().timestamp();
+        let lifetime = if remember_me {
+            policy.remembered_lifetime_seconds
+        } else {
+            policy.lifetime_seconds
+        };
+        self.connection
+            .execute(
+                "INSERT INTO sessions (token, username, remembered, created_timestamp, last_activity_timestamp, expires_timestamp, device_name) VALUES (?1, ?2, ?3, ?4, ?4, ?5, ?6)",
+                params![token, username, remember_me, now, now + lifetime, device_name()],
+            )
+            .context("Failed to insert session")?;
+
+        self.current_user = Some(username.to_string());
+        self.session_token = Some(token);
+        log::debug!("Started session for username: {}", username);
+        Ok(())
+    }
+
+    /// Deletes the current persisted session, if any
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn end_session(&mut self) -> Result<()> {
+        if let Some(token) = self.session_token.take() {
+            self.connection
+                .execute("DELETE FROM sessions WHERE token = ?1", params![token])
+                .context("Failed to delete session")?;
+        }
+        Ok(())
+    }
+
+    /// Deletes sessions that have expired, and sessions that are not remembered
+    /// and have been idle for longer than the idle timeout
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn delete_inactive_sessions(&self) -> Result<()> {
+        let policy = self.get_session_policy()?;
+        self.connection
+            .execute(
+                "DELETE FROM sessions WHERE expires_timestamp <= ?1 OR (remembered = 0 AND last_activity_timestamp + ?2 <= ?1)",
+                params![Utc::now().timestamp(), policy.idle_timeout_seconds],
+            )
+            .context("Failed to delete inactive sessions")?;
+        Ok(())
+    }
+
+    /// Logs out the current user if their session is no longer active
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn expire_inactive_session(&mut self) -> Result<()> {
+        let Some(token) = &self.session_token else {
+            return Ok(());
+        };
+
+        self.delete_inactive_sessions()?;
+        let active = self
+            .connection
+            .query_row(
+                "SELECT 1 FROM sessions WHERE token = ?1",
+                params![token],
+                |_| Ok(()),
+            )
+            .optional()
+            .context("Failed to query session")?
+            .is_some();
+        if !active {
+            log::info!("Session expired for username: {:?}", self.current_user);
+            self.current_user = None;
+            self.session_token = None;
+        }
+        Ok(())
+    }
+
+    /// Removes inactive sessions and logs in the user of the most recent one left
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn restore_session(&mut self) -> Result<()> {
+        self.delete_inactive_sessions()?;
+
+        let session = self
+            .connection
+            .query_row(
+                "SELECT s.token, s.username FROM sessions s JOIN user_authentication u ON u.username = s.username ORDER BY s.created_timestamp DESC LIMIT 1",
+                [],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()
+            .context("Failed to query sessions")?;
+
+        if let Some((token, username)) = session {
+            log::info!("Restored session for username: {}", username);
+            self.current_user = Some(username);
+            self.session_token = Some(token);
+        }
+        Ok(())
+    }
+
     /// Retrieves the password hash for a specific username
     ///
     /// # Arguments
     /// * `username` - The username to look up
     ///
     /// # Returns
-    /// * `Result<Option<String>>` - Password hash if user exists, None if not found
-    fn get_password_hash(&self, username: &str) -> Result<Option<String>> {
+    /// * `Result<Option<(String, HashAlgorithm)>>` - Password hash and the algorithm it was made
+    ///   with if user exists, None if not found
+    fn get_password_hash(&self, username: &str) -> Result<Option<(String, HashAlgorithm)>> {
         let mut statement = self
             .connection
-            .prepare("SELECT password_hash FROM user_authentication WHERE username = ?1")
+            .prepare(
+                "SELECT password_hash, hash_algorithm FROM user_authentication WHERE username = ?1",
+            )
             .context("Failed to prepare query for password hash")?;
 
         let mut rows = statement
             .query_map(params![username], |row| {
                 let password_hash: String = row.get(0)?;
-                Ok(password_hash)
+                let hash_algorithm: HashAlgorithm = row.get(1)?;
+                Ok((password_hash, hash_algorithm))
             })
             .context("Failed to execute query for password hash")?;
 
@@ -252,6 +1237,94 @@ impl AuthenticationService {
         }
     }
 
+    /// Hashes a new password with Argon2id and stores it for a user
+    ///
+    /// # Arguments
+    /// * `username` - The username to update
+    /// * `password` - Plain text password to hash
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the user exists and was updated, false if not found
+    fn update_password_hash(&self, username: &str, password: &str) -> Result<bool> {
+        let password_hash = hash_password(password)?;
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE user_authentication SET password_hash = ?1, hash_algorithm = ?2 WHERE username = ?3",
+                params![password_hash, HashAlgorithm::Argon2id, username],
+            )
+            .context("Failed to update password hash")?;
+        Ok(rows_affected > 0)
+    }
+
+    /// Sets a new password for a user, discarding any pending temporary password
+    /// and ending the user's sessions other than the current one
+    ///
+    /// # Arguments
+    /// * `username` - The username to update
+    /// * `password` - Plain text password to hash
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the user exists and was updated, false if not found
+    fn replace_password(&self, username: &str, password: &str) -> Result<bool> {
+        if !self.update_password_hash(username, password)? {
+            return Ok(false);
+        }
+        self.delete_password_reset(username)?;
+        self.set_must_change_password(username, false)?;
+
+        // Sessions started with the old password must not outlive it
+        self.connection
+            .execute(
+                "DELETE FROM sessions WHERE username = ?1 AND token IS NOT ?2",
+                params![username, self.session_token],
+            )
+            .context("Failed to invalidate sessions")?;
+        Ok(true)
+    }
+
+    /// Checks a password against the user's pending temporary password, if it has not expired
+    ///
+    /// # Arguments
+    /// * `username` - The username to check
+    /// * `password` - Plain text password to verify
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the password is the user's valid temporary password
+    fn matches_password_reset(&self, username: &str, password: &str) -> Result<bool> {
+        let reset_hash = self
+            .connection
+            .query_row(
+                "SELECT password_hash FROM password_resets WHERE username = ?1 AND expires_timestamp > ?2",
+                params![username, Utc::now().timestamp()],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .context("Failed to query password reset")?;
+
+        match reset_hash {
+            Some(hash) => verify_password(password, &hash, HashAlgorithm::Argon2id),
+            None => Ok(false),
+        }
+    }
+
+    /// Deletes the user's pending temporary password, if any
+    ///
+    /// # Arguments
+    /// * `username` - The username whose temporary password to delete
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn delete_password_reset(&self, username: &str) -> Result<()> {
+        self.connection
+            .execute(
+                "DELETE FROM password_resets WHERE username = ?1",
+                params![username],
+            )
+            .context("Failed to delete password reset")?;
+        Ok(())
+    }
+
     /// Inserts a new user authentication record into the database
     ///
     /// # Arguments
@@ -263,8 +1336,14 @@ impl AuthenticationService {
         let rows_affected = self
             .connection
             .execute(
-                "INSERT INTO user_authentication (username, password_hash, role) VALUES (?1, ?2, ?3)",
-                params![user_auth.username, user_auth.password_hash, user_auth.role],
+                "INSERT INTO user_authentication (username, password_hash, role, hash_algorithm, created_timestamp) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    user_auth.username,
+                    user_auth.password_hash,
+                    user_auth.role,
+                    user_auth.hash_algorithm,
+                    Utc::now().timestamp()
+                ],
             )
             .context("Failed to insert user into database")?;
 
@@ -282,6 +1361,110 @@ impl AuthenticationService {
         }
     }
 
+    /// Retrieves whether a user account may log in
+    ///
+    /// # Arguments
+    /// * `username` - The username to look up
+    ///
+    /// # Returns
+    /// * `Result<Option<UserStatus>>` - The status if the user exists, None otherwise
+    fn get_user_status(&self, username: &str) -> Result<Option<UserStatus>> {
+        self.connection
+            .query_row(
+                "SELECT status FROM user_authentication WHERE username = ?1",
+                params![username],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to query user status")
+    }
+
+    /// Checks whether a user must replace their password before using their account
+    ///
+    /// # Arguments
+    /// * `username` - The username to look up
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the user must change their password
+    fn must_change_password(&self, username: &str) -> Result<bool> {
+        let must_change = self
+            .connection
+            .query_row(
+                "SELECT must_change_password FROM user_authentication WHERE username = ?1",
+                params![username],
+                |row| row.get::<_, bool>(0),
+            )
+            .optional()
+            .context("Failed to query password change flag")?;
+        Ok(must_change.unwrap_or(false))
+    }
+
+    /// Changes whether a user must replace their password before using their account
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account to change
+    /// * `must_change` - Whether the password must be changed
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn set_must_change_password(&self, username: &str, must_change: bool) -> Result<()> {
+        self.connection
+            .execute(
+                "UPDATE user_authentication SET must_change_password = ?1 WHERE username = ?2",
+                params![must_change, username],
+            )
+            .context("Failed to update password change flag")?;
+        Ok(())
+    }
+
+    /// Changes whether a user account may log in
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account to change
+    /// * `status` - New status for the account
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or error if the account does not exist
+    fn set_user_status(&self, username: &str, status: UserStatus) -> Result<()> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE user_authentication SET status = ?1 WHERE username = ?2",
+                params![status, username],
+            )
+            .context("Failed to update user status")?;
+        if rows_affected == 0 {
+            bail!("User {} not found", username);
+        }
+        Ok(())
+    }
+
+    /// Counts every account, whatever its role or status
+    ///
+    /// # Returns
+    /// * `Result<i64>` - Number of accounts or error
+    fn count_users(&self) -> Result<i64> {
+        self.connection
+            .query_row("SELECT COUNT(*) FROM user_authentication", [], |row| {
+                row.get(0)
+            })
+            .context("Failed to count users")
+    }
+
+    /// Counts the active accounts with the admin role
+    ///
+    /// # Returns
+    /// * `Result<i64>` - Number of active admins or error
+    fn count_admins(&self) -> Result<i64> {
+        self.connection
+            .query_row(
+                "SELECT COUNT(*) FROM user_authentication WHERE role = ?1 AND status = ?2",
+                params![UserRole::Admin, UserStatus::Active],
+                |row| row.get(0),
+            )
+            .context("Failed to count admins")
+    }
+
     /// Retrieves the role for a specific username
     ///
     /// # Arguments
@@ -315,3 +1498,92 @@ impl AuthenticationService {
         }
     }
 }
+
+/// Validates and hashes a plain text password with Argon2id
+///
+/// # Arguments
+/// * `password` - Plain text password to hash
+///
+/// # Returns
+/// * `Result<String>` - The hash in PHC string format, or error if the password is too short
+fn hash_password(password: &str) -> Result<String> {
+    if password.len() < 6 {
+        bail!("Password must be at least 6 characters long");
+    }
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| anyhow!("Failed to hash password: {}", e))
+}
+
+/// Derives the ID a session is listed under from its token, so listing sessions
+/// never reveals the tokens themselves
+///
+/// # Arguments
+/// * `token` - The session token
+///
+/// # Returns
+/// * `String` - The hex-encoded SHA-256 digest of the token
+fn derive_session_id(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Names the device the app runs on, for telling sessions apart
+///
+/// # Returns
+/// * `Option<String>` - The host name of the device, None if it cannot be determined
+fn device_name() -> Option<String> {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Generates a random code that is easy to read out and type
+///
+/// # Arguments
+/// * `length` - Number of characters in the code
+///
+/// # Returns
+/// * `String` - The code
+fn generate_code(length: usize) -> String {
+    (0..length)
+        .map(|_| {
+            let index = OsRng.next_u32() as usize % CODE_ALPHABET.len();
+            CODE_ALPHABET[index] as char
+        })
+        .collect()
+}
+
+/// Checks a plain text password against a stored hash
+///
+/// # Arguments
+/// * `password` - Plain text password to verify
+/// * `stored_hash` - The stored password hash
+/// * `hash_algorithm` - Algorithm the stored hash was made with
+///
+/// # Returns
+/// * `Result<bool>` - True if the password matches, or error if the hash is malformed
+fn verify_password(
+    password: &str,
+    stored_hash: &str,
+    hash_algorithm: HashAlgorithm,
+) -> Result<bool> {
+    match hash_algorithm {
+        HashAlgorithm::Bcrypt => {
+            bcrypt::verify(password, stored_hash).context("Failed to verify password")
+        }
+        HashAlgorithm::Argon2id => {
+            let parsed_hash = PasswordHash::new(stored_hash)
+                .map_err(|e| anyhow!("Failed to parse password hash: {}", e))?;
+            match Argon2::default().verify_password(password.as_bytes(), &parsed_hash) {
+                Ok(()) => Ok(true),
+                Err(argon2::password_hash::Error::Password) => Ok(false),
+                Err(e) => Err(anyhow!("Failed to verify password: {}", e)),
+            }
+        }
+    }
+}
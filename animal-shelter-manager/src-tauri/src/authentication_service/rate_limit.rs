@@ -0,0 +1,82 @@
+//
+// authentication_service/rate_limit.rs
+//
+// This module provides per-username rate limiting of login attempts,
+// so that a script cannot brute-force passwords or keep the CPU busy
+// verifying password hashes.
+//
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Number of login attempts a username can make in a burst
+const BUCKET_CAPACITY: f64 = 5.0;
+
+/// Time it takes to regain one login attempt
+const REFILL_INTERVAL: Duration = Duration::from_secs(12);
+
+/// Number of tracked usernames above which buckets that have refilled are forgotten
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Login attempts left for a username
+struct Bucket {
+    /// Attempts left, including partially regained ones
+    tokens: f64,
+    /// When the tokens were last topped up
+    last_refill: Instant,
+}
+
+impl Bucket {
+    /// Tops up the tokens regained since the last refill
+    ///
+    /// # Arguments
+    /// * `now` - The current time
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let regained = elapsed.as_secs_f64() / REFILL_INTERVAL.as_secs_f64();
+        self.tokens = (self.tokens + regained).min(BUCKET_CAPACITY);
+        self.last_refill = now;
+    }
+}
+
+/// Token bucket rate limiter for login attempts, keyed by username
+#[derive(Default)]
+pub struct LoginRateLimiter {
+    /// Buckets of the usernames that attempted to log in recently
+    buckets: HashMap<String, Bucket>,
+}
+
+impl LoginRateLimiter {
+    /// Takes one login attempt from the username's bucket
+    ///
+    /// # Arguments
+    /// * `username` - The username attempting to log in
+    /// * `now` - The current time
+    ///
+    /// # Returns
+    /// * `bool` - True if the attempt is allowed, false if the username is out of attempts
+    pub fn try_acquire(&mut self, username: &str, now: Instant) -> bool {
+        if self.buckets.len() > PRUNE_THRESHOLD {
+            self.buckets.retain(|_, bucket| {
+                bucket.refill(now);
+                bucket.tokens < BUCKET_CAPACITY
+            });
+        }
+
+        let bucket = self
+            .buckets
+            .entry(username.to_string())
+            .or_insert_with(|| Bucket {
+                tokens: BUCKET_CAPACITY,
+                last_refill: now,
+            });
+        bucket.refill(now);
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
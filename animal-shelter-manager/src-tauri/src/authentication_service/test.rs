@@ -7,11 +7,17 @@
 #[cfg(test)]
 mod authentication_service_tests {
     use super::super::{
-        types::{LoginResult, UserRole},
+        rate_limit::LoginRateLimiter,
+        types::{
+            HashAlgorithm, LoginResult, SessionPolicy, UserAuthentication, UserRole, UserStatus,
+        },
         AuthenticationService,
     };
+    use chrono::Utc;
     use std::fs;
     use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+    use totp_rs::{Algorithm, Secret, TOTP};
 
     /// Helper function to create a test authentication service with proper test artifacts directory
     ///
@@ -42,25 +48,44 @@ mod authentication_service_tests {
         let mut auth_service = create_test_auth_service("test_sign_up_valid_user");
 
         // Test successful sign up
-        let result = auth_service.sign_up("testuser", "password123", UserRole::Customer);
+        let result = auth_service.sign_up("testuser", "password123", UserRole::Customer, None);
         assert!(result.is_ok());
 
         // Verify user can now log in (implicit verification that user was created)
-        let login_result = auth_service.log_in("testuser", "password123");
+        let login_result = auth_service.log_in("testuser", "password123", false);
         assert!(login_result.is_ok());
         assert_eq!(login_result.unwrap(), LoginResult::Success);
     }
 
+    #[test]
+    fn test_register_user() {
+        let mut auth_service = create_test_auth_service("test_register_user");
+        assert!(!auth_service.user_exists("testuser").unwrap());
+
+        // Registering creates the account without logging anyone in
+        auth_service
+            .register_user("testuser", "password123", UserRole::Staff)
+            .unwrap();
+        assert!(auth_service.user_exists("testuser").unwrap());
+        assert!(auth_service.get_current_user().unwrap().is_none());
+        assert_eq!(
+            auth_service
+                .log_in("testuser", "password123", false)
+                .unwrap(),
+            LoginResult::Success
+        );
+    }
+
     #[test]
     fn test_sign_up_duplicate_user() {
         let mut auth_service = create_test_auth_service("test_sign_up_duplicate_user");
 
         // First sign up should succeed
-        let result1 = auth_service.sign_up("testuser", "password123", UserRole::Customer);
+        let result1 = auth_service.sign_up("testuser", "password123", UserRole::Customer, None);
         assert!(result1.is_ok());
 
         // Duplicate sign up should fail
-        let result2 = auth_service.sign_up("testuser", "password456", UserRole::Staff);
+        let result2 = auth_service.sign_up("testuser", "password456", UserRole::Customer, None);
         assert!(result2.is_err());
     }
 
@@ -69,7 +94,7 @@ mod authentication_service_tests {
         let mut auth_service = create_test_auth_service("test_sign_up_invalid_input");
 
         // Empty username should fail
-        let result1 = auth_service.sign_up("", "password123", UserRole::Customer);
+        let result1 = auth_service.sign_up("", "password123", UserRole::Customer, None);
         assert!(result1.is_err());
         assert!(result1
             .unwrap_err()
@@ -77,7 +102,7 @@ mod authentication_service_tests {
             .contains("Username cannot be empty"));
 
         // Whitespace-only username should fail
-        let result2 = auth_service.sign_up("   ", "password123", UserRole::Customer);
+        let result2 = auth_service.sign_up("   ", "password123", UserRole::Customer, None);
         assert!(result2.is_err());
         assert!(result2
             .unwrap_err()
@@ -85,12 +110,153 @@ mod authentication_service_tests {
             .contains("Username cannot be empty"));
 
         // Short password should fail
-        let result3 = auth_service.sign_up("testuser", "123", UserRole::Customer);
+        let result3 = auth_service.sign_up("testuser", "123", UserRole::Customer, None);
         assert!(result3.is_err());
         assert!(result3
             .unwrap_err()
             .to_string()
             .contains("Password must be at least 6 characters"));
+
+        // Requests from the website are filed under a username no account can take
+        let result4 = auth_service.sign_up("@website", "password123", UserRole::Customer, None);
+        assert!(result4.unwrap_err().to_string().contains("is reserved"));
+    }
+
+    #[test]
+    fn test_sign_up_with_invite_code() {
+        let mut auth_service = create_test_auth_service("test_sign_up_with_invite_code");
+
+        // Staff accounts cannot be registered without a valid invite for the role
+        assert!(auth_service
+            .sign_up("volunteer", "password123", UserRole::Staff, None)
+            .is_err());
+        assert!(auth_service
+            .sign_up(
+                "volunteer",
+                "password123",
+                UserRole::Staff,
+                Some("NOTACODE")
+            )
+            .is_err());
+        assert!(auth_service
+            .create_invite_code("manager", UserRole::Customer)
+            .is_err());
+        let staff_invite = auth_service
+            .create_invite_code("manager", UserRole::Staff)
+            .unwrap();
+
+        // The first account of a new shelter needs no invite to be an admin
+        auth_service
+            .sign_up("founder", "password123", UserRole::Admin, None)
+            .unwrap();
+        auth_service.log_out().unwrap();
+        assert!(auth_service
+            .sign_up("intruder", "password123", UserRole::Admin, None)
+            .is_err());
+
+        // The code decides the role, whatever is asked for, and is accepted regardless
+        // of case, but only once
+        auth_service
+            .sign_up(
+                "worker",
+                "password123",
+                UserRole::Admin,
+                Some(&staff_invite.code.to_lowercase()),
+            )
+            .unwrap();
+        auth_service.log_out().unwrap();
+        assert_eq!(
+            auth_service.get_user_role("worker").unwrap(),
+            Some(UserRole::Staff)
+        );
+        assert!(auth_service
+            .sign_up(
+                "another",
+                "password123",
+                UserRole::Staff,
+                Some(&staff_invite.code)
+            )
+            .is_err());
+
+        // Admin invites can be redeemed from the staff sign-up form
+        let admin_invite = auth_service
+            .create_invite_code("founder", UserRole::Admin)
+            .unwrap();
+        auth_service
+            .sign_up(
+                "deputy",
+                "password123",
+                UserRole::Staff,
+                Some(&admin_invite.code),
+            )
+            .unwrap();
+        assert_eq!(
+            auth_service.get_user_role("deputy").unwrap(),
+            Some(UserRole::Admin)
+        );
+
+        // Only the hash of a code is stored
+        let stored: i64 = auth_service
+            .connection
+            .query_row(
+                "SELECT COUNT(*) FROM invite_codes WHERE code_hash = ?1",
+                [&staff_invite.code],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(stored, 0);
+    }
+
+    #[test]
+    fn test_first_admin_on_existing_shelter() {
+        let mut auth_service = create_test_auth_service("test_first_admin_on_existing_shelter");
+        let db_path = PathBuf::from(
+            "test_artifacts/authentication_service/test_first_admin_on_existing_shelter/test_auth.db",
+        );
+        auth_service
+            .register_user("customer", "password123", UserRole::Customer)
+            .unwrap();
+        auth_service
+            .register_user("oldest", "password123", UserRole::Staff)
+            .unwrap();
+        auth_service
+            .register_user("newer", "password123", UserRole::Staff)
+            .unwrap();
+
+        // With accounts already registered, nobody may make themselves admin
+        assert!(auth_service
+            .sign_up("visitor", "password123", UserRole::Admin, None)
+            .is_err());
+
+        // Databases from before the admin role get the oldest staff account as admin
+        auth_service
+            .connection
+            .pragma_update(None, "user_version", 0)
+            .unwrap();
+        drop(auth_service);
+        let auth_service = AuthenticationService::new(&db_path).unwrap();
+        assert_eq!(
+            auth_service.get_user_role("oldest").unwrap(),
+            Some(UserRole::Admin)
+        );
+        assert_eq!(
+            auth_service.get_user_role("newer").unwrap(),
+            Some(UserRole::Staff)
+        );
+
+        // The migration runs once, so a demoted admin stays demoted
+        auth_service
+            .register_user("manager", "password123", UserRole::Admin)
+            .unwrap();
+        auth_service
+            .change_user_role("oldest", UserRole::Staff)
+            .unwrap();
+        drop(auth_service);
+        let auth_service = AuthenticationService::new(&db_path).unwrap();
+        assert_eq!(
+            auth_service.get_user_role("oldest").unwrap(),
+            Some(UserRole::Staff)
+        );
     }
 
     #[test]
@@ -99,11 +265,13 @@ mod authentication_service_tests {
 
         // Create a user first
         auth_service
-            .sign_up("testuser", "password123", UserRole::Customer)
+            .sign_up("testuser", "password123", UserRole::Customer, None)
             .unwrap();
 
         // Test successful login
-        let login_result = auth_service.log_in("testuser", "password123").unwrap();
+        let login_result = auth_service
+            .log_in("testuser", "password123", false)
+            .unwrap();
         assert_eq!(login_result, LoginResult::Success);
 
         // Verify user is now logged in
@@ -118,14 +286,16 @@ mod authentication_service_tests {
 
         // Create a user first (this will automatically log them in)
         auth_service
-            .sign_up("testuser", "password123", UserRole::Customer)
+            .sign_up("testuser", "password123", UserRole::Customer, None)
             .unwrap();
 
         // Log out the user first to test fresh login attempt
-        auth_service.log_out();
+        auth_service.log_out().unwrap();
 
         // Test login with wrong password
-        let login_result = auth_service.log_in("testuser", "wrongpassword").unwrap();
+        let login_result = auth_service
+            .log_in("testuser", "wrongpassword", false)
+            .unwrap();
         assert_eq!(login_result, LoginResult::InvalidPassword);
 
         // Verify no user is logged in
@@ -138,7 +308,9 @@ mod authentication_service_tests {
         let mut auth_service = create_test_auth_service("test_log_in_nonexistent_user");
 
         // Test login with non-existent username
-        let login_result = auth_service.log_in("nonexistent", "password123").unwrap();
+        let login_result = auth_service
+            .log_in("nonexistent", "password123", false)
+            .unwrap();
         assert_eq!(login_result, LoginResult::UserNotFound);
 
         // Verify no user is logged in
@@ -146,9 +318,238 @@ mod authentication_service_tests {
         assert!(current_user.is_none());
     }
 
+    #[test]
+    fn test_log_in_rehashes_bcrypt_password() {
+        let mut auth_service = create_test_auth_service("test_log_in_rehashes_bcrypt_password");
+
+        // An account from before the move to Argon2id
+        auth_service
+            .insert_user(&UserAuthentication {
+                username: "testuser".to_string(),
+                password_hash: bcrypt::hash("password123", 4).unwrap(),
+                hash_algorithm: HashAlgorithm::Bcrypt,
+                role: UserRole::Customer,
+            })
+            .unwrap();
+        assert_eq!(
+            auth_service
+                .log_in("testuser", "wrongpassword", false)
+                .unwrap(),
+            LoginResult::InvalidPassword
+        );
+        let (_, algorithm) = auth_service.get_password_hash("testuser").unwrap().unwrap();
+        assert_eq!(algorithm, HashAlgorithm::Bcrypt);
+
+        // A successful login upgrades the hash, and the password keeps working
+        assert_eq!(
+            auth_service
+                .log_in("testuser", "password123", false)
+                .unwrap(),
+            LoginResult::Success
+        );
+        let (hash, algorithm) = auth_service.get_password_hash("testuser").unwrap().unwrap();
+        assert_eq!(algorithm, HashAlgorithm::Argon2id);
+        assert!(hash.starts_with("$argon2id$"));
+        assert_eq!(
+            auth_service
+                .log_in("testuser", "password123", false)
+                .unwrap(),
+            LoginResult::Success
+        );
+    }
+
+    #[test]
+    fn test_password_reset() {
+        let mut auth_service = create_test_auth_service("test_password_reset");
+        auth_service
+            .register_user("customer", "password123", UserRole::Customer)
+            .unwrap();
+        auth_service
+            .register_user("worker", "password123", UserRole::Staff)
+            .unwrap();
+
+        // Staff can reset customers but not other staff
+        assert!(auth_service
+            .create_password_reset("worker", &UserRole::Staff)
+            .is_err());
+        assert!(auth_service
+            .create_password_reset("nobody", &UserRole::Admin)
+            .is_err());
+        let reset = auth_service
+            .create_password_reset("customer", &UserRole::Staff)
+            .unwrap();
+        assert_eq!(reset.temporary_password.len(), 10);
+
+        // The temporary password asks for a new password instead of logging in
+        assert_eq!(
+            auth_service
+                .log_in("customer", &reset.temporary_password, false)
+                .unwrap(),
+            LoginResult::PasswordChangeRequired
+        );
+        assert!(auth_service.get_current_user().unwrap().is_none());
+        assert_eq!(
+            auth_service
+                .complete_password_reset("customer", "WRONGCODE1", "newpassword")
+                .unwrap(),
+            LoginResult::InvalidPassword
+        );
+        assert_eq!(
+            auth_service
+                .complete_password_reset("customer", &reset.temporary_password, "newpassword")
+                .unwrap(),
+            LoginResult::Success
+        );
+        assert_eq!(
+            auth_service.get_current_user().unwrap().unwrap().username,
+            "customer"
+        );
+
+        // The temporary password only works once, and the new password replaces the old one
+        assert_eq!(
+            auth_service
+                .log_in("customer", &reset.temporary_password, false)
+                .unwrap(),
+            LoginResult::InvalidPassword
+        );
+        assert_eq!(
+            auth_service
+                .log_in("customer", "newpassword", false)
+                .unwrap(),
+            LoginResult::Success
+        );
+
+        // Expired temporary passwords are refused
+        let reset = auth_service
+            .create_password_reset("worker", &UserRole::Admin)
+            .unwrap();
+        auth_service
+            .connection
+            .execute("UPDATE password_resets SET expires_timestamp = 0", [])
+            .unwrap();
+        assert_eq!(
+            auth_service
+                .log_in("worker", &reset.temporary_password, false)
+                .unwrap(),
+            LoginResult::InvalidPassword
+        );
+    }
+
+    #[test]
+    fn test_two_factor_login() {
+        let mut auth_service = create_test_auth_service("test_two_factor_login");
+        auth_service
+            .register_user("worker", "password123", UserRole::Staff)
+            .unwrap();
+        auth_service
+            .register_user("customer", "password123", UserRole::Customer)
+            .unwrap();
+        assert!(auth_service.enroll_totp("customer").is_err());
+
+        // Enrollment only takes effect once confirmed with a code from the app
+        let enrollment = auth_service.enroll_totp("worker").unwrap();
+        assert!(enrollment.provisioning_uri.starts_with("otpauth://totp/"));
+        let totp = TOTP::new(
+            Algorithm::SHA1,
+            6,
+            0,
+            30,
+            Secret::Encoded(enrollment.secret).to_bytes().unwrap(),
+            None,
+            "worker".to_string(),
+        )
+        .unwrap();
+        let now = Utc::now().timestamp() as u64;
+        assert!(!auth_service.is_totp_enabled("worker").unwrap());
+        assert!(!auth_service
+            .confirm_totp_enrollment("worker", &totp.generate(now + 600))
+            .unwrap());
+        assert!(auth_service
+            .confirm_totp_enrollment("worker", &totp.generate(now))
+            .unwrap());
+        assert!(auth_service.is_totp_enabled("worker").unwrap());
+
+        // The password alone no longer logs in
+        assert_eq!(
+            auth_service.log_in("worker", "password123", false).unwrap(),
+            LoginResult::TwoFactorRequired
+        );
+        assert!(auth_service.get_current_user().unwrap().is_none());
+
+        // Codes cannot be reused
+        assert_eq!(
+            auth_service.verify_totp(&totp.generate(now)).unwrap(),
+            LoginResult::InvalidTwoFactorCode
+        );
+        assert_eq!(
+            auth_service.verify_totp(&totp.generate(now + 30)).unwrap(),
+            LoginResult::Success
+        );
+        assert_eq!(
+            auth_service.get_current_user().unwrap().unwrap().username,
+            "worker"
+        );
+        assert!(auth_service.verify_totp(&totp.generate(now + 30)).is_err());
+
+        // Disabling restores password-only login
+        auth_service.disable_totp("worker").unwrap();
+        assert_eq!(
+            auth_service.log_in("worker", "password123", false).unwrap(),
+            LoginResult::Success
+        );
+    }
+
+    #[test]
+    fn test_log_in_rate_limited() {
+        let mut auth_service = create_test_auth_service("test_log_in_rate_limited");
+        auth_service
+            .register_user("testuser", "password123", UserRole::Customer)
+            .unwrap();
+
+        // A burst of attempts is allowed, then further attempts are refused
+        for _ in 0..5 {
+            assert_eq!(
+                auth_service
+                    .log_in("testuser", "wrongpassword", false)
+                    .unwrap(),
+                LoginResult::InvalidPassword
+            );
+        }
+        assert_eq!(
+            auth_service
+                .log_in("testuser", "password123", false)
+                .unwrap(),
+            LoginResult::TooManyAttempts
+        );
+        assert!(auth_service.get_current_user().unwrap().is_none());
+
+        // Other usernames have their own limit
+        assert_eq!(
+            auth_service
+                .log_in("otheruser", "password123", false)
+                .unwrap(),
+            LoginResult::UserNotFound
+        );
+    }
+
+    #[test]
+    fn test_login_rate_limiter_refills() {
+        let mut limiter = LoginRateLimiter::default();
+        let start = Instant::now();
+        for _ in 0..5 {
+            assert!(limiter.try_acquire("testuser", start));
+        }
+        assert!(!limiter.try_acquire("testuser", start));
+
+        // One attempt is regained every 12 seconds
+        assert!(!limiter.try_acquire("testuser", start + Duration::from_secs(6)));
+        assert!(limiter.try_acquire("testuser", start + Duration::from_secs(12)));
+        assert!(!limiter.try_acquire("testuser", start + Duration::from_secs(12)));
+    }
+
     #[test]
     fn test_get_current_user_when_logged_out() {
-        let auth_service = create_test_auth_service("test_get_current_user_when_logged_out");
+        let mut auth_service = create_test_auth_service("test_get_current_user_when_logged_out");
 
         // Test when no user is logged in
         let current_user = auth_service.get_current_user().unwrap();
@@ -160,10 +561,20 @@ mod authentication_service_tests {
         let mut auth_service = create_test_auth_service("test_get_current_user_when_logged_in");
 
         // Create and login user
+        let invite = auth_service
+            .create_invite_code("manager", UserRole::Staff)
+            .unwrap();
         auth_service
-            .sign_up("testuser", "password123", UserRole::Staff)
+            .sign_up(
+                "testuser",
+                "password123",
+                UserRole::Staff,
+                Some(&invite.code),
+            )
+            .unwrap();
+        let login_result = auth_service
+            .log_in("testuser", "password123", false)
             .unwrap();
-        let login_result = auth_service.log_in("testuser", "password123").unwrap();
         assert_eq!(login_result, LoginResult::Success);
 
         // Test current user retrieval
@@ -178,9 +589,11 @@ mod authentication_service_tests {
 
         // Create and login user
         auth_service
-            .sign_up("testuser", "password123", UserRole::Customer)
+            .sign_up("testuser", "password123", UserRole::Customer, None)
+            .unwrap();
+        let login_result = auth_service
+            .log_in("testuser", "password123", false)
             .unwrap();
-        let login_result = auth_service.log_in("testuser", "password123").unwrap();
         assert_eq!(login_result, LoginResult::Success);
 
         // Verify user is logged in
@@ -188,7 +601,7 @@ mod authentication_service_tests {
         assert!(current_user_before.is_some());
 
         // Test logout
-        auth_service.log_out();
+        auth_service.log_out().unwrap();
 
         // Verify user is logged out
         let current_user_after = auth_service.get_current_user().unwrap();
@@ -200,10 +613,399 @@ mod authentication_service_tests {
         let mut auth_service = create_test_auth_service("test_log_out_when_already_logged_out");
 
         // Test logout when no user is logged in (should not panic)
-        auth_service.log_out();
+        auth_service.log_out().unwrap();
 
         // Verify still no user logged in
         let current_user = auth_service.get_current_user().unwrap();
         assert!(current_user.is_none());
     }
+
+    #[test]
+    fn test_session_persists_across_restarts() {
+        let mut auth_service = create_test_auth_service("test_session_persists_across_restarts");
+        let db_path =
+            PathBuf::from("test_artifacts/authentication_service/test_session_persists_across_restarts/test_auth.db");
+        auth_service
+            .register_user("testuser", "password123", UserRole::Staff)
+            .unwrap();
+        auth_service
+            .log_in("testuser", "password123", true)
+            .unwrap();
+        drop(auth_service);
+
+        // Restarting restores the logged-in user
+        let mut auth_service = AuthenticationService::new(&db_path).unwrap();
+        let current_user = auth_service.get_current_user().unwrap().unwrap();
+        assert_eq!(current_user.username, "testuser");
+
+        // Logging out invalidates the session for the next launch
+        auth_service.log_out().unwrap();
+        drop(auth_service);
+        let mut auth_service = AuthenticationService::new(&db_path).unwrap();
+        assert!(auth_service.get_current_user().unwrap().is_none());
+
+        // Expired sessions are not restored
+        auth_service
+            .log_in("testuser", "password123", false)
+            .unwrap();
+        auth_service
+            .connection
+            .execute("UPDATE sessions SET expires_timestamp = 0", [])
+            .unwrap();
+        drop(auth_service);
+        let mut auth_service = AuthenticationService::new(&db_path).unwrap();
+        assert!(auth_service.get_current_user().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_list_and_terminate_sessions() {
+        let mut auth_service = create_test_auth_service("test_list_and_terminate_sessions");
+        auth_service
+            .register_user("volunteer", "password123", UserRole::Staff)
+            .unwrap();
+        auth_service
+            .register_user("manager", "password123", UserRole::Admin)
+            .unwrap();
+
+        // A session left open elsewhere stays in the database after this app logs in again
+        auth_service
+            .log_in("volunteer", "password123", true)
+            .unwrap();
+        auth_service.session_token = None;
+        auth_service
+            .log_in("manager", "password123", false)
+            .unwrap();
+
+        let sessions = auth_service.list_sessions().unwrap();
+        assert_eq!(sessions.len(), 2);
+        let volunteer = sessions.iter().find(|s| s.username == "volunteer").unwrap();
+        assert!(volunteer.remembered);
+        assert!(!volunteer.current);
+        assert!(sessions
+            .iter()
+            .any(|s| s.username == "manager" && s.current));
+
+        // Listed IDs do not reveal session tokens
+        let tokens: i64 = auth_service
+            .connection
+            .query_row(
+                "SELECT COUNT(*) FROM sessions WHERE token = ?1",
+                [&volunteer.id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(tokens, 0);
+
+        assert!(auth_service.terminate_session(&volunteer.id).unwrap());
+        assert!(!auth_service.terminate_session(&volunteer.id).unwrap());
+        assert_eq!(auth_service.list_sessions().unwrap().len(), 1);
+
+        // Terminating the current session logs this app out
+        let current = auth_service.list_sessions().unwrap().remove(0);
+        assert!(auth_service.terminate_session(&current.id).unwrap());
+        assert!(auth_service.get_current_user().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_session_idle_timeout() {
+        let mut auth_service = create_test_auth_service("test_session_idle_timeout");
+        assert_eq!(
+            auth_service.get_session_policy().unwrap(),
+            SessionPolicy::default()
+        );
+        assert!(auth_service
+            .set_session_policy(&SessionPolicy {
+                idle_timeout_seconds: 0,
+                ..SessionPolicy::default()
+            })
+            .is_err());
+        auth_service
+            .set_session_policy(&SessionPolicy {
+                idle_timeout_seconds: 60,
+                ..SessionPolicy::default()
+            })
+            .unwrap();
+        assert_eq!(
+            auth_service
+                .get_session_policy()
+                .unwrap()
+                .idle_timeout_seconds,
+            60
+        );
+        auth_service
+            .register_user("testuser", "password123", UserRole::Staff)
+            .unwrap();
+        let idle_since = chrono::Utc::now().timestamp() - 120;
+
+        // Activity keeps the session alive, and idling ends it
+        auth_service
+            .log_in("testuser", "password123", false)
+            .unwrap();
+        assert!(auth_service.refresh_session().unwrap());
+        auth_service
+            .connection
+            .execute(
+                "UPDATE sessions SET last_activity_timestamp = ?1",
+                [idle_since],
+            )
+            .unwrap();
+        assert!(auth_service.get_current_user().unwrap().is_none());
+        assert!(!auth_service.refresh_session().unwrap());
+
+        // Remembered sessions do not idle out
+        auth_service
+            .log_in("testuser", "password123", true)
+            .unwrap();
+        auth_service
+            .connection
+            .execute(
+                "UPDATE sessions SET last_activity_timestamp = ?1",
+                [idle_since],
+            )
+            .unwrap();
+        assert!(auth_service.get_current_user().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_admin_role_management() {
+        let mut auth_service = create_test_auth_service("test_admin_role_management");
+
+        // The first admin can sign up; later admins must be granted the role
+        auth_service
+            .sign_up("manager", "password123", UserRole::Admin, None)
+            .unwrap();
+        assert!(auth_service
+            .sign_up("intruder", "password123", UserRole::Admin, None)
+            .is_err());
+        auth_service
+            .register_user("worker", "password123", UserRole::Customer)
+            .unwrap();
+
+        // Admins count as staff
+        assert!(UserRole::Admin.is_staff());
+        assert!(!UserRole::Customer.is_staff());
+
+        auth_service
+            .change_user_role("worker", UserRole::Staff)
+            .unwrap();
+        let users = auth_service.list_users().unwrap();
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0].username, "manager");
+        assert_eq!(users[0].role, UserRole::Admin);
+        assert_eq!(users[1].role, UserRole::Staff);
+        assert!(auth_service
+            .change_user_role("nobody", UserRole::Staff)
+            .is_err());
+
+        // The last admin cannot be demoted
+        assert!(auth_service
+            .change_user_role("manager", UserRole::Staff)
+            .is_err());
+        auth_service
+            .change_user_role("worker", UserRole::Admin)
+            .unwrap();
+        auth_service
+            .change_user_role("manager", UserRole::Staff)
+            .unwrap();
+
+        // Resetting a password replaces the old one, and the user must then choose their own
+        assert!(auth_service.reset_password("worker", "123").is_err());
+        assert!(auth_service
+            .reset_password("nobody", "newpassword")
+            .is_err());
+        auth_service
+            .reset_password("worker", "newpassword")
+            .unwrap();
+        assert_eq!(
+            auth_service.log_in("worker", "password123", false).unwrap(),
+            LoginResult::InvalidPassword
+        );
+        assert_eq!(
+            auth_service.log_in("worker", "newpassword", false).unwrap(),
+            LoginResult::PasswordChangeRequired
+        );
+    }
+
+    #[test]
+    fn test_search_users() {
+        let auth_service = create_test_auth_service("test_search_users");
+        for username in ["jimbob", "bobby", "bob", "alice"] {
+            auth_service
+                .register_user(username, "password123", UserRole::Customer)
+                .unwrap();
+        }
+
+        // The exact username ranks first, then usernames starting with the text
+        let hits = auth_service.search_users("BOB", 10).unwrap();
+        let found: Vec<(&str, i64)> = hits
+            .iter()
+            .map(|hit| (hit.record.username.as_str(), hit.score))
+            .collect();
+        assert_eq!(found, vec![("bob", 9), ("bobby", 6), ("jimbob", 3)]);
+        assert_eq!(auth_service.search_users("bob", 1).unwrap().len(), 1);
+        assert!(auth_service.search_users("carol", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_change_password_after_account_creation() {
+        let mut auth_service =
+            create_test_auth_service("test_change_password_after_account_creation");
+        auth_service
+            .create_user("newstaff", "initial123", UserRole::Staff)
+            .unwrap();
+
+        // The initial password only leads to choosing a new one, without logging in
+        assert_eq!(
+            auth_service
+                .log_in("newstaff", "initial123", false)
+                .unwrap(),
+            LoginResult::PasswordChangeRequired
+        );
+        assert!(auth_service.get_current_user().unwrap().is_none());
+
+        assert_eq!(
+            auth_service
+                .change_password("newstaff", "wrongpassword", "chosen123")
+                .unwrap(),
+            LoginResult::InvalidPassword
+        );
+        assert!(auth_service
+            .change_password("newstaff", "initial123", "initial123")
+            .is_err());
+        assert_eq!(
+            auth_service
+                .change_password("newstaff", "initial123", "chosen123")
+                .unwrap(),
+            LoginResult::Success
+        );
+        assert_eq!(
+            auth_service.get_current_user().unwrap().unwrap().username,
+            "newstaff"
+        );
+
+        // The chosen password works normally from then on
+        auth_service.log_out().unwrap();
+        assert_eq!(
+            auth_service.log_in("newstaff", "chosen123", false).unwrap(),
+            LoginResult::Success
+        );
+    }
+
+    #[test]
+    fn test_suspend_and_reactivate_user() {
+        let mut auth_service = create_test_auth_service("test_suspend_and_reactivate_user");
+        auth_service
+            .sign_up("manager", "password123", UserRole::Admin, None)
+            .unwrap();
+        auth_service
+            .register_user("customer", "password123", UserRole::Customer)
+            .unwrap();
+
+        // Accounts are listed with when they were created and whether they are active
+        let users = auth_service.list_users().unwrap();
+        assert!(users.iter().all(|u| u.status == UserStatus::Active));
+        assert!(users.iter().all(|u| u.created_timestamp.is_some()));
+
+        // The last active admin cannot be suspended
+        assert!(auth_service.suspend_user("manager").is_err());
+        assert!(auth_service.suspend_user("nobody").is_err());
+
+        // Suspended accounts cannot log in, even with the right password
+        auth_service.log_out().unwrap();
+        auth_service
+            .log_in("customer", "password123", false)
+            .unwrap();
+        auth_service.suspend_user("customer").unwrap();
+        assert_eq!(
+            auth_service
+                .log_in("customer", "password123", false)
+                .unwrap(),
+            LoginResult::Suspended
+        );
+        assert_eq!(
+            auth_service
+                .log_in("customer", "wrongpassword", false)
+                .unwrap(),
+            LoginResult::InvalidPassword
+        );
+        let customer = auth_service
+            .list_users()
+            .unwrap()
+            .into_iter()
+            .find(|u| u.username == "customer")
+            .unwrap();
+        assert_eq!(customer.status, UserStatus::Suspended);
+
+        // Suspending ends the account's sessions
+        let sessions: i64 = auth_service
+            .connection
+            .query_row(
+                "SELECT COUNT(*) FROM sessions WHERE username = 'customer'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(sessions, 0);
+
+        auth_service.reactivate_user("customer").unwrap();
+        assert_eq!(
+            auth_service
+                .log_in("customer", "password123", false)
+                .unwrap(),
+            LoginResult::Success
+        );
+    }
+
+    #[test]
+    fn test_erase_user() {
+        let mut auth_service = create_test_auth_service("test_erase_user");
+        auth_service
+            .sign_up("manager", "password123", UserRole::Admin, None)
+            .unwrap();
+        auth_service
+            .register_user("customer", "password123", UserRole::Customer)
+            .unwrap();
+
+        // Only customer accounts can be erased
+        assert!(auth_service.erase_user("manager").is_err());
+        assert!(!auth_service.erase_user("nobody").unwrap());
+
+        // Erasing deletes the account and ends its sessions
+        auth_service.log_out().unwrap();
+        auth_service
+            .log_in("customer", "password123", true)
+            .unwrap();
+        assert!(auth_service.erase_user("customer").unwrap());
+        let sessions: i64 = auth_service
+            .connection
+            .query_row(
+                "SELECT COUNT(*) FROM sessions WHERE username = 'customer'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(sessions, 0);
+        assert!(!auth_service.user_exists("customer").unwrap());
+        assert_eq!(
+            auth_service
+                .log_in("customer", "password123", false)
+                .unwrap(),
+            LoginResult::UserNotFound
+        );
+    }
+
+    #[test]
+    fn test_run_maintenance() {
+        let mut auth_service = create_test_auth_service("test_run_maintenance");
+        auth_service
+            .sign_up("testuser", "password123", UserRole::Customer, None)
+            .expect("Sign up should succeed");
+
+        let result = auth_service
+            .run_maintenance()
+            .expect("Maintenance should succeed");
+        assert_eq!(result.database, "authentication");
+        assert!(result.size_after_bytes > 0);
+        assert!(result.reclaimed_bytes >= 0);
+    }
 }
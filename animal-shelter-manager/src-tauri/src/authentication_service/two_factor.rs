@@ -0,0 +1,285 @@
+//
+// authentication_service/two_factor.rs
+//
+// This module provides optional two-factor authentication for staff accounts
+// using time-based one-time passwords (TOTP) from an authenticator app.
+//
+
+use super::{
+    types::{LoginResult, TotpEnrollment},
+    AuthenticationService,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use std::time::{Duration, Instant};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+/// Name shown for the account in authenticator apps
+const TOTP_ISSUER: &str = "Animal Shelter Manager";
+
+/// Length of a TOTP time step, in seconds
+const TOTP_STEP_SECONDS: u64 = 30;
+
+/// Number of time steps before and after the current one whose codes are accepted,
+/// to allow for clock drift
+const TOTP_SKEW_STEPS: i64 = 1;
+
+/// How long a login that passed the password check waits for its two-factor code
+const PENDING_LOGIN_LIFETIME: Duration = Duration::from_secs(5 * 60);
+
+/// A login whose password was verified and that waits for a two-factor code
+#[derive(Clone)]
+pub(super) struct PendingTwoFactor {
+    /// Username of the account logging in
+    username: String,
+    /// Whether the session should last for days instead of hours
+    remember_me: bool,
+    /// When the login stops waiting for its code
+    expires: Instant,
+}
+
+impl AuthenticationService {
+    // ==================== TWO-FACTOR AUTHENTICATION OPERATIONS ====================
+
+    /// Starts enrolling a staff account in two-factor authentication by generating a new secret.
+    /// The account is not protected until the enrollment is confirmed with a code.
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account to enroll
+    ///
+    /// # Returns
+    /// * `Result<TotpEnrollment>` - The secret and its provisioning URI for an authenticator app,
+    ///   or error if the account is not staff or is already enrolled
+    pub fn enroll_totp(&self, username: &str) -> Result<TotpEnrollment> {
+        let role = self
+            .get_user_role(username)?
+            .context(format!("User {} not found", username))?;
+        if !role.is_staff() {
+            bail!("Two-factor authentication is only available to staff accounts");
+        }
+        if self.is_totp_enabled(username)? {
+            bail!("Two-factor authentication is already enabled; disable it first");
+        }
+
+        let secret = Secret::generate_secret()
+            .to_bytes()
+            .map_err(|e| anyhow!("Failed to generate secret: {:?}", e))?;
+        let totp = build_totp(secret, username)?;
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO two_factor (username, secret, enabled, last_used_step) VALUES (?1, ?2, 0, NULL)",
+                params![username, totp.get_secret_base32()],
+            )
+            .context("Failed to store two-factor secret")?;
+
+        log::info!("Started two-factor enrollment for username: {}", username);
+        Ok(TotpEnrollment {
+            secret: totp.get_secret_base32(),
+            provisioning_uri: totp.get_url(),
+        })
+    }
+
+    /// Finishes enrolling an account in two-factor authentication
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account being enrolled
+    /// * `code` - Code shown by the authenticator app, proving it was set up correctly
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the code was correct and two-factor authentication is now
+    ///   enabled, false if the code was wrong
+    pub fn confirm_totp_enrollment(&self, username: &str, code: &str) -> Result<bool> {
+        if !self.check_totp_code(username, code)? {
+            return Ok(false);
+        }
+
+        self.connection
+            .execute(
+                "UPDATE two_factor SET enabled = 1 WHERE username = ?1",
+                params![username],
+            )
+            .context("Failed to enable two-factor authentication")?;
+        log::info!(
+            "Enabled two-factor authentication for username: {}",
+            username
+        );
+        Ok(true)
+    }
+
+    /// Turns off two-factor authentication for an account and forgets its secret
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn disable_totp(&self, username: &str) -> Result<()> {
+        self.connection
+            .execute(
+                "DELETE FROM two_factor WHERE username = ?1",
+                params![username],
+            )
+            .context("Failed to disable two-factor authentication")?;
+        log::info!(
+            "Disabled two-factor authentication for username: {}",
+            username
+        );
+        Ok(())
+    }
+
+    /// Checks whether an account has confirmed two-factor authentication
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if logging in requires a two-factor code
+    pub fn is_totp_enabled(&self, username: &str) -> Result<bool> {
+        let enabled = self
+            .connection
+            .query_row(
+                "SELECT enabled FROM two_factor WHERE username = ?1",
+                params![username],
+                |row| row.get::<_, bool>(0),
+            )
+            .optional()
+            .context("Failed to query two-factor status")?;
+        Ok(enabled.unwrap_or(false))
+    }
+
+    /// Finishes a login that is waiting for a two-factor code
+    ///
+    /// # Arguments
+    /// * `code` - Code shown by the authenticator app
+    ///
+    /// # Returns
+    /// * `Result<LoginResult>` - Success, an invalid two-factor code, or too many attempts;
+    ///   error if no login is waiting for a code
+    pub fn verify_totp(&mut self, code: &str) -> Result<LoginResult> {
+        let Some(pending) = self.pending_two_factor.clone() else {
+            bail!("No login is waiting for a two-factor code");
+        };
+        if pending.expires <= Instant::now() {
+            self.pending_two_factor = None;
+            bail!("The login timed out; please log in again");
+        }
+        if !self
+            .login_rate_limiter
+            .try_acquire(&pending.username, Instant::now())
+        {
+            log::warn!(
+                "Too many two-factor attempts for username: {}",
+                pending.username
+            );
+            return Ok(LoginResult::TooManyAttempts);
+        }
+
+        if !self.check_totp_code(&pending.username, code)? {
+            log::warn!("Invalid two-factor code for username: {}", pending.username);
+            return Ok(LoginResult::InvalidTwoFactorCode);
+        }
+
+        self.pending_two_factor = None;
+        self.start_session(&pending.username, pending.remember_me)?;
+        log::info!(
+            "User logged in successfully with two-factor authentication: {}",
+            pending.username
+        );
+        Ok(LoginResult::Success)
+    }
+
+    /// Holds a login whose password was verified until its two-factor code is given
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account logging in
+    /// * `remember_me` - Whether the session should last for days instead of hours
+    pub(super) fn begin_two_factor(&mut self, username: &str, remember_me: bool) {
+        self.pending_two_factor = Some(PendingTwoFactor {
+            username: username.to_string(),
+            remember_me,
+            expires: Instant::now() + PENDING_LOGIN_LIFETIME,
+        });
+        log::info!("Waiting for two-factor code for username: {}", username);
+    }
+
+    /// Checks a code against the account's secret, refusing codes that were already used
+    ///
+    /// # Arguments
+    /// * `username` - Username of the account
+    /// * `code` - Code shown by the authenticator app
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the code is valid and had not been used before
+    fn check_totp_code(&self, username: &str, code: &str) -> Result<bool> {
+        let stored = self
+            .connection
+            .query_row(
+                "SELECT secret, last_used_step FROM two_factor WHERE username = ?1",
+                params![username],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<i64>>(1)?)),
+            )
+            .optional()
+            .context("Failed to query two-factor secret")?;
+        let Some((secret, last_used_step)) = stored else {
+            return Ok(false);
+        };
+
+        let secret = Secret::Encoded(secret)
+            .to_bytes()
+            .map_err(|e| anyhow!("Failed to decode two-factor secret: {:?}", e))?;
+        let totp = build_totp(secret, username)?;
+        let Some(step) = matching_step(&totp, code, Utc::now().timestamp()) else {
+            return Ok(false);
+        };
+
+        // A code can only be used once, so an observed code cannot be replayed
+        if last_used_step.is_some_and(|last_used_step| step <= last_used_step) {
+            return Ok(false);
+        }
+        self.connection
+            .execute(
+                "UPDATE two_factor SET last_used_step = ?1 WHERE username = ?2",
+                params![step, username],
+            )
+            .context("Failed to record two-factor code use")?;
+        Ok(true)
+    }
+}
+
+/// Builds the TOTP generator for an account's secret
+///
+/// # Arguments
+/// * `secret` - The raw secret
+/// * `username` - Username of the account, shown in authenticator apps
+///
+/// # Returns
+/// * `Result<TOTP>` - The generator or error
+fn build_totp(secret: Vec<u8>, username: &str) -> Result<TOTP> {
+    TOTP::new(
+        Algorithm::SHA1,
+        6,
+        0,
+        TOTP_STEP_SECONDS,
+        secret,
+        Some(TOTP_ISSUER.to_string()),
+        username.to_string(),
+    )
+    .map_err(|e| anyhow!("Failed to create TOTP generator: {:?}", e))
+}
+
+/// Finds the time step, within the allowed clock drift, whose code matches
+///
+/// # Arguments
+/// * `totp` - The account's TOTP generator
+/// * `code` - Code shown by the authenticator app
+/// * `now` - The current timestamp
+///
+/// # Returns
+/// * `Option<i64>` - The matching time step, None if the code does not match
+fn matching_step(totp: &TOTP, code: &str, now: i64) -> Option<i64> {
+    let current_step = now / TOTP_STEP_SECONDS as i64;
+    (-TOTP_SKEW_STEPS..=TOTP_SKEW_STEPS)
+        .map(|offset| current_step + offset)
+        .find(|step| totp.generate((step * TOTP_STEP_SECONDS as i64) as u64) == code.trim())
+}
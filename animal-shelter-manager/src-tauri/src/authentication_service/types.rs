@@ -7,6 +7,7 @@
 
 use rusqlite::{types::FromSql, ToSql};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use strum::{Display, EnumString};
 
 /// User role in the system
@@ -14,12 +15,24 @@ use strum::{Display, EnumString};
 #[strum(serialize_all = "kebab-case")]
 #[serde(rename_all = "kebab-case")]
 pub enum UserRole {
+    /// Shelter manager with staff privileges who also manages user accounts
+    Admin,
     /// Staff member with elevated privileges
     Staff,
     /// Customer using the system
     Customer,
 }
 
+impl UserRole {
+    /// Whether the role grants staff privileges
+    ///
+    /// # Returns
+    /// * `bool` - True for staff members and admins
+    pub fn is_staff(&self) -> bool {
+        matches!(self, UserRole::Admin | UserRole::Staff)
+    }
+}
+
 /// Implement ToSql and FromSql for UserRole to store it as a string in the database
 impl ToSql for UserRole {
     fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
@@ -37,6 +50,62 @@ impl FromSql for UserRole {
     }
 }
 
+/// Whether an account may log in
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum UserStatus {
+    /// The account can log in
+    Active,
+    /// The account was suspended by an admin and cannot log in until reactivated
+    Suspended,
+}
+
+/// Implement ToSql and FromSql for UserStatus to store it as a string in the database
+impl ToSql for UserStatus {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for UserStatus {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
+/// Algorithm a stored password hash was made with
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum HashAlgorithm {
+    /// bcrypt, used by accounts created before the move to Argon2id
+    Bcrypt,
+    /// Argon2id, used for all new and changed passwords
+    Argon2id,
+}
+
+/// Implement ToSql and FromSql for HashAlgorithm to store it as a string in the database
+impl ToSql for HashAlgorithm {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for HashAlgorithm {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
 /// Result of a login attempt
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -47,6 +116,17 @@ pub enum LoginResult {
     InvalidPassword,
     /// Username does not exist in the system
     UserNotFound,
+    /// Too many login attempts were made for the username; try again later
+    TooManyAttempts,
+    /// A temporary password, or a password chosen by an admin, was used; a new password
+    /// must be set to finish logging in
+    PasswordChangeRequired,
+    /// The password was correct; a two-factor code must be verified to finish logging in
+    TwoFactorRequired,
+    /// The two-factor code was wrong, expired, or already used
+    InvalidTwoFactorCode,
+    /// The account was suspended by an admin
+    Suspended,
 }
 
 /// Represents user authentication data in the system
@@ -56,6 +136,124 @@ pub struct UserAuthentication {
     pub username: String,
     /// Hashed password for security
     pub password_hash: String,
+    /// Algorithm the password hash was made with
+    pub hash_algorithm: HashAlgorithm,
     /// User role in the system
     pub role: UserRole,
 }
+
+/// A user account as listed to admins, without its password hash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAccount {
+    /// Username of the account
+    pub username: String,
+    /// Role of the account
+    pub role: UserRole,
+    /// Timestamp when the account was created (None for accounts created before it was recorded)
+    pub created_timestamp: Option<i64>,
+    /// Whether the account may log in
+    pub status: UserStatus,
+}
+
+/// A session that is still active, as listed to admins
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionInfo {
+    /// Identifier of the session, derived from its token without revealing it
+    pub id: String,
+    /// Username of the account the session belongs to
+    pub username: String,
+    /// Whether the session lasts for days instead of hours
+    pub remembered: bool,
+    /// Timestamp when the session started
+    pub created_timestamp: i64,
+    /// Timestamp of the last activity in the session
+    pub last_activity_timestamp: i64,
+    /// Timestamp after which the session ends
+    pub expires_timestamp: i64,
+    /// Name of the device the session was started on, if known
+    pub device_name: Option<String>,
+    /// Whether this is the session of the app listing it
+    pub current: bool,
+}
+
+/// How long sessions last before the user must log in again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionPolicy {
+    /// Lifetime of a session, in seconds
+    pub lifetime_seconds: i64,
+    /// Lifetime of a session when the user asks to be remembered, in seconds
+    pub remembered_lifetime_seconds: i64,
+    /// Time without activity after which a session that is not remembered ends, in seconds
+    pub idle_timeout_seconds: i64,
+}
+
+impl Default for SessionPolicy {
+    fn default() -> Self {
+        SessionPolicy {
+            lifetime_seconds: 12 * 60 * 60,
+            remembered_lifetime_seconds: 30 * 24 * 60 * 60,
+            idle_timeout_seconds: 15 * 60,
+        }
+    }
+}
+
+/// A temporary password issued by staff for a user who forgot theirs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordReset {
+    /// Username of the account being reset
+    pub username: String,
+    /// One-time password to hand to the user; only its hash is stored
+    pub temporary_password: String,
+    /// Timestamp after which the temporary password no longer works
+    pub expires_timestamp: i64,
+}
+
+/// A single-use code an admin hands to a new staff member so they can register
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InviteCode {
+    /// The code to hand out; only its hash is stored
+    pub code: String,
+    /// Role an account registered with the code receives
+    pub role: UserRole,
+    /// Timestamp after which the code no longer works
+    pub expires_timestamp: i64,
+}
+
+/// Error returned when a staff account is registered without a usable invite code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InviteCodeError {
+    /// No invite code was given
+    Missing,
+    /// The invite code is invalid, expired, or already used
+    Invalid,
+}
+
+impl fmt::Display for InviteCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InviteCodeError::Missing => {
+                write!(f, "An invite code is required to register a staff account")
+            }
+            InviteCodeError::Invalid => {
+                write!(f, "The invite code is invalid, expired, or already used")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InviteCodeError {}
+
+/// A new two-factor secret to add to an authenticator app
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpEnrollment {
+    /// Base32 secret, for typing into the app by hand
+    pub secret: String,
+    /// `otpauth://` URI holding the secret, for showing as a QR code
+    pub provisioning_uri: String,
+}
@@ -0,0 +1,498 @@
+//
+// backup_service/cloud.rs
+//
+// This module uploads backups to S3-compatible storage, such as Amazon S3,
+// Backblaze B2 or MinIO, so a copy survives a break-in or fire at the shelter,
+// and downloads them again to restore from. Requests are signed with AWS
+// Signature Version 4 and retried while the storage is briefly unavailable.
+//
+
+use super::list_files;
+use super::types::Backup;
+use crate::settings_service::types::CloudBackupConfig;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::{Method, StatusCode, Url};
+use sha2::{Digest, Sha256};
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use tokio::fs;
+
+/// Name of the object uploaded last into every backup, describing it; a backup
+/// without one was not uploaded completely
+pub const MANIFEST_NAME: &str = "backup.json";
+
+/// How many times a request is tried before giving up
+const MAX_ATTEMPTS: u32 = 3;
+
+/// How long to wait before retrying a request, multiplied by the attempts made
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// How long a single request may take, long enough to upload a large database
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Name of the signing algorithm, as written in the authorization header
+const SIGNING_ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// Client for the bucket backups are uploaded to
+pub struct CloudStorage {
+    /// HTTP client sending the requests
+    client: reqwest::Client,
+    /// Where the bucket is and the credentials to reach it
+    config: CloudBackupConfig,
+}
+
+/// A request to the storage, as needed to sign it
+#[derive(Debug, Clone)]
+pub(super) struct SignedRequest<'a> {
+    /// HTTP method, such as PUT
+    pub method: &'a str,
+    /// Host the request is sent to, with the port if it is not the default
+    pub host: &'a str,
+    /// Path of the request, already percent-encoded
+    pub path: &'a str,
+    /// Query string of the request, in canonical form
+    pub query: &'a str,
+    /// Further headers to sign, with lowercase names
+    pub headers: &'a [(&'a str, &'a str)],
+    /// Hex-encoded SHA-256 digest of the body
+    pub payload_hash: &'a str,
+}
+
+impl CloudStorage {
+    /// Creates a client for the bucket set in the settings
+    ///
+    /// # Arguments
+    /// * `config` - Where the bucket is and the credentials to reach it
+    ///
+    /// # Returns
+    /// * `Result<CloudStorage>` - The client or error
+    pub fn new(config: CloudBackupConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("Failed to build cloud storage HTTP client")?;
+        Ok(CloudStorage { client, config })
+    }
+
+    /// Uploads every file of a backup, then its manifest
+    ///
+    /// # Arguments
+    /// * `backup` - The backup to upload
+    ///
+    /// # Returns
+    /// * `Result<usize>` - The number of files uploaded, not counting the manifest
+    pub async fn upload_backup(&self, backup: &Backup) -> Result<usize> {
+        let files = list_files(&backup.path).await?;
+        for file in &files {
+            let relative = file
+                .strip_prefix(&backup.path)
+                .context(format!("File is outside the backup: {:?}", file))?;
+            let key = object_key(&backup.name, relative)?;
+            let contents = fs::read(file)
+                .await
+                .context(format!("Failed to read file: {:?}", file))?;
+            self.put_object(&key, contents).await?;
+        }
+
+        let manifest = serde_json::to_vec_pretty(backup).context("Failed to write manifest")?;
+        self.put_object(&format!("{}/{}", backup.name, MANIFEST_NAME), manifest)
+            .await?;
+        log::info!(
+            "Uploaded backup {} with {} files to bucket {}",
+            backup.name,
+            files.len(),
+            self.config.bucket
+        );
+        Ok(files.len())
+    }
+
+    /// Lists the backups uploaded completely, oldest first
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - The names of the backups or error
+    pub async fn list_backups(&self) -> Result<Vec<String>> {
+        let manifest_suffix = format!("/{}", MANIFEST_NAME);
+        let mut names: Vec<String> = self
+            .list_objects("backup_")
+            .await?
+            .iter()
+            .filter_map(|key| key.strip_suffix(&manifest_suffix))
+            .filter(|name| !name.contains('/'))
+            .map(str::to_string)
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Downloads an uploaded backup into a directory
+    ///
+    /// # Arguments
+    /// * `name` - Name of the backup
+    /// * `destination` - Directory to download the backup into
+    ///
+    /// # Returns
+    /// * `Result<Backup>` - The downloaded backup, or error if it was not uploaded completely
+    pub async fn download_backup(&self, name: &str, destination: &Path) -> Result<Backup> {
+        safe_relative_path(name)?;
+        let prefix = format!("{}/", name);
+        let manifest_key = format!("{}{}", prefix, MANIFEST_NAME);
+        let keys = self.list_objects(&prefix).await?;
+        if !keys.contains(&manifest_key) {
+            bail!("No complete backup named {} is in the cloud", name);
+        }
+
+        for key in keys.iter().filter(|key| **key != manifest_key) {
+            let path = destination.join(safe_relative_path(&key[prefix.len()..])?);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .await
+                    .context(format!("Failed to create directory: {:?}", parent))?;
+            }
+            let contents = self.get_object(key).await?;
+            fs::write(&path, contents)
+                .await
+                .context(format!("Failed to write file: {:?}", path))?;
+        }
+
+        let mut backup: Backup = serde_json::from_slice(&self.get_object(&manifest_key).await?)
+            .context(format!("Failed to read manifest of backup {}", name))?;
+        backup.path = destination.to_path_buf();
+        log::info!(
+            "Downloaded backup {} from bucket {}",
+            name,
+            self.config.bucket
+        );
+        Ok(backup)
+    }
+
+    /// Stores an object in the bucket, replacing any with the same key
+    async fn put_object(&self, key: &str, contents: Vec<u8>) -> Result<()> {
+        self.send(Method::PUT, key, &[], contents).await?;
+        Ok(())
+    }
+
+    /// Reads an object from the bucket
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>> {
+        let response = self.send(Method::GET, key, &[], Vec::new()).await?;
+        Ok(response
+            .bytes()
+            .await
+            .context(format!("Failed to download {}", key))?
+            .to_vec())
+    }
+
+    /// Lists the keys of the objects in the bucket that start with a prefix
+    async fn list_objects(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut continuation_token: Option<String> = None;
+        loop {
+            let mut query = vec![
+                ("list-type", "2".to_string()),
+                ("prefix", prefix.to_string()),
+            ];
+            if let Some(token) = &continuation_token {
+                query.push(("continuation-token", token.clone()));
+            }
+            let body = self
+                .send(Method::GET, "", &query, Vec::new())
+                .await?
+                .text()
+                .await
+                .context("Failed to read the list of objects")?;
+
+            keys.extend(xml_values(&body, "Key"));
+            if xml_values(&body, "IsTruncated").first().map(String::as_str) != Some("true") {
+                return Ok(keys);
+            }
+            continuation_token = xml_values(&body, "NextContinuationToken")
+                .into_iter()
+                .next();
+            if continuation_token.is_none() {
+                bail!("The list of objects is cut short without a way to continue it");
+            }
+        }
+    }
+
+    /// Sends a signed request for an object, or for the bucket if the key is empty,
+    /// retrying when the storage cannot be reached or is briefly unavailable
+    ///
+    /// # Arguments
+    /// * `method` - The HTTP method
+    /// * `key` - Key of the object, or empty for the bucket
+    /// * `query` - Query parameters
+    /// * `body` - The body to send
+    ///
+    /// # Returns
+    /// * `Result<reqwest::Response>` - The successful response, or error once the
+    ///   attempts run out or the storage refuses the request
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        query: &[(&str, String)],
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response> {
+        let mut path = format!("/{}", encode_uri(&self.config.bucket, false));
+        if !key.is_empty() {
+            path.push('/');
+            path.push_str(&encode_uri(key, false));
+        }
+        let query = canonical_query(query);
+        let mut url = format!("{}{}", self.config.endpoint, path);
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query);
+        }
+        let url = Url::parse(&url).context(format!("Invalid cloud storage address: {}", url))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => bail!("The cloud storage address has no host"),
+        };
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let mut attempt = 1;
+        loop {
+            let request = SignedRequest {
+                method: method.as_str(),
+                host: &host,
+                path: &path,
+                query: &query,
+                headers: &[],
+                payload_hash: &payload_hash,
+            };
+            let mut builder = self
+                .client
+                .request(method.clone(), url.clone())
+                .body(body.clone());
+            for (name, value) in sign_request(&request, &self.config, Utc::now()) {
+                builder = builder.header(name, value);
+            }
+
+            let failure = match builder.send().await {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) => {
+                    let status = response.status();
+                    let code = xml_values(&response.text().await.unwrap_or_default(), "Code")
+                        .into_iter()
+                        .next()
+                        .unwrap_or_default();
+                    let failure = format!(
+                        "Cloud storage refused {} {} with status {} {}",
+                        method, path, status, code
+                    );
+                    if !is_retryable(status) {
+                        bail!(failure.trim_end().to_string());
+                    }
+                    failure
+                }
+                Err(e) => format!("Failed to reach cloud storage: {}", e),
+            };
+            if attempt >= MAX_ATTEMPTS {
+                bail!(
+                    "{} (gave up after {} attempts)",
+                    failure.trim_end(),
+                    attempt
+                );
+            }
+            log::warn!("{}; retrying", failure.trim_end());
+            tokio::time::sleep(RETRY_DELAY * attempt).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Signs a request with AWS Signature Version 4
+///
+/// # Arguments
+/// * `request` - The request to sign
+/// * `config` - The region and credentials to sign with
+/// * `now` - The time of the request
+///
+/// # Returns
+/// * `Vec<(String, String)>` - The date, payload hash and authorization headers to send
+pub(super) fn sign_request(
+    request: &SignedRequest,
+    config: &CloudBackupConfig,
+    now: DateTime<Utc>,
+) -> Vec<(String, String)> {
+    let timestamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+
+    let mut headers: Vec<(&str, &str)> = vec![
+        ("host", request.host),
+        ("x-amz-content-sha256", request.payload_hash),
+        ("x-amz-date", &timestamp),
+    ];
+    headers.extend_from_slice(request.headers);
+    headers.sort();
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        request.method,
+        request.path,
+        request.query,
+        canonical_headers,
+        signed_headers,
+        request.payload_hash
+    );
+    let scope = format!("{}/{}/s3/aws4_request", date, config.region);
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}",
+        SIGNING_ALGORITHM,
+        timestamp,
+        scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let secret = format!("AWS4{}", config.secret_access_key);
+    let signing_key = [date.as_str(), &config.region, "s3", "aws4_request"]
+        .iter()
+        .fold(secret.into_bytes(), |key, part| hmac_sha256(&key, part));
+    let signature = hex::encode(hmac_sha256(&signing_key, &string_to_sign));
+
+    vec![
+        ("x-amz-date".to_string(), timestamp.clone()),
+        (
+            "x-amz-content-sha256".to_string(),
+            request.payload_hash.to_string(),
+        ),
+        (
+            "authorization".to_string(),
+            format!(
+                "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+                SIGNING_ALGORITHM, config.access_key_id, scope, signed_headers, signature
+            ),
+        ),
+    ]
+}
+
+/// Computes the HMAC-SHA256 of a message
+fn hmac_sha256(key: &[u8], message: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(message.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encodes text for a path or query string, keeping only the characters
+/// RFC 3986 leaves unreserved
+///
+/// # Arguments
+/// * `text` - The text to encode
+/// * `encode_slash` - Whether to encode slashes too, as query values need
+///
+/// # Returns
+/// * `String` - The encoded text
+pub(super) fn encode_uri(text: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Writes query parameters in canonical form: encoded and sorted by name
+fn canonical_query(query: &[(&str, String)]) -> String {
+    let mut parameters: Vec<(String, String)> = query
+        .iter()
+        .map(|(name, value)| (encode_uri(name, true), encode_uri(value, true)))
+        .collect();
+    parameters.sort();
+    parameters
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Whether a failed request is worth retrying: throttling and server errors pass,
+/// while refused credentials or missing objects do not
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+/// Builds the key of a file of a backup, using forward slashes on every platform
+fn object_key(backup_name: &str, relative: &Path) -> Result<String> {
+    let mut parts = vec![backup_name.to_string()];
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => parts.push(
+                part.to_str()
+                    .context(format!("File name is not valid UTF-8: {:?}", relative))?
+                    .to_string(),
+            ),
+            _ => bail!("Unexpected path in backup: {:?}", relative),
+        }
+    }
+    Ok(parts.join("/"))
+}
+
+/// Turns part of a downloaded key into a relative path, refusing any that would
+/// leave the directory it is downloaded into
+///
+/// # Arguments
+/// * `key` - The key, or the part of it below the backup
+///
+/// # Returns
+/// * `Result<PathBuf>` - The relative path, or error if the key is unsafe
+pub(super) fn safe_relative_path(key: &str) -> Result<PathBuf> {
+    let mut path = PathBuf::new();
+    for part in key.split('/') {
+        match Path::new(part).components().collect::<Vec<_>>().as_slice() {
+            [Component::Normal(name)] if name.to_str() == Some(part) => path.push(part),
+            _ => bail!("Unsafe object key in cloud backup: {:?}", key),
+        }
+    }
+    Ok(path)
+}
+
+/// Gets the text of every element with a tag in an XML response, such as the keys
+/// in a list of objects
+///
+/// # Arguments
+/// * `xml` - The response body
+/// * `tag` - Name of the elements
+///
+/// # Returns
+/// * `Vec<String>` - The unescaped text of the elements, in order
+pub(super) fn xml_values(xml: &str, tag: &str) -> Vec<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let mut values = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        rest = &rest[start + open.len()..];
+        let Some(end) = rest.find(&close) else {
+            break;
+        };
+        values.push(
+            rest[..end]
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&apos;", "'")
+                .replace("&amp;", "&"),
+        );
+        rest = &rest[end + close.len()..];
+    }
+    values
+}
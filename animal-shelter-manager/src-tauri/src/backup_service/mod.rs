@@ -0,0 +1,284 @@
+//
+// backup_service/mod.rs
+//
+// This module makes backups of the application's data. Each backup is a
+// directory under the backup directory holding a consistent copy of every
+// database, written with SQLite's VACUUM INTO so it can be taken while the
+// application is running, and a copy of every uploaded file. Backups can also
+// be uploaded to S3-compatible storage off site, and restored from.
+//
+
+use crate::file_service::BACKUP_DIRECTORY;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OpenFlags};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use types::Backup;
+
+pub mod cloud;
+mod test;
+pub mod types;
+
+/// Name of the directory within a backup that holds the uploaded files
+pub const BACKUP_FILES_DIRECTORY: &str = "files";
+
+/// Service for making backups of the databases and uploaded files
+pub struct BackupService {
+    /// Root directory where all application files are stored
+    root_path: PathBuf,
+}
+
+impl BackupService {
+    /// Creates a new BackupService instance for a root directory
+    ///
+    /// # Arguments
+    /// * `root_path` - The directory holding the databases and uploaded files
+    ///
+    /// # Returns
+    /// * `BackupService` - New BackupService instance
+    pub fn new<P: AsRef<Path>>(root_path: P) -> Self {
+        BackupService {
+            root_path: root_path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Backs up the databases and uploaded files into a new backup directory
+    ///
+    /// Databases that do not exist yet are skipped. If anything fails, the partly
+    /// written backup is removed.
+    ///
+    /// # Arguments
+    /// * `database_file_names` - Names of the database files in the root directory
+    /// * `uploaded_files` - Paths of the uploaded files, all within the root directory
+    /// * `now` - The current time, which names the backup
+    ///
+    /// # Returns
+    /// * `Result<Backup>` - The backup made or error
+    pub async fn create_backup(
+        &self,
+        database_file_names: &[&str],
+        uploaded_files: &[PathBuf],
+        now: DateTime<Utc>,
+    ) -> Result<Backup> {
+        let name = format!("backup_{}", now.format("%Y-%m-%d_%H-%M-%S"));
+        let path = self.root_path.join(BACKUP_DIRECTORY).join(&name);
+        if fs::try_exists(&path).await.unwrap_or(false) {
+            bail!("A backup named {} already exists", name);
+        }
+        fs::create_dir_all(&path)
+            .await
+            .context(format!("Failed to create backup directory: {:?}", path))?;
+
+        let mut backup = Backup {
+            name,
+            path,
+            created_timestamp: now.timestamp(),
+            database_count: 0,
+            file_count: 0,
+            bytes: 0,
+        };
+        match self
+            .copy_into_backup(&mut backup, database_file_names, uploaded_files)
+            .await
+        {
+            Ok(()) => {
+                log::info!(
+                    "Created backup {} with {} databases and {} files",
+                    backup.name,
+                    backup.database_count,
+                    backup.file_count
+                );
+                Ok(backup)
+            }
+            Err(e) => {
+                if let Err(remove_error) = fs::remove_dir_all(&backup.path).await {
+                    log::warn!(
+                        "Failed to remove incomplete backup {:?}: {}",
+                        backup.path,
+                        remove_error
+                    );
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Copies the databases and uploaded files into a backup directory
+    ///
+    /// # Arguments
+    /// * `backup` - The backup being made, whose counts are updated
+    /// * `database_file_names` - Names of the database files in the root directory
+    /// * `uploaded_files` - Paths of the uploaded files, all within the root directory
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    async fn copy_into_backup(
+        &self,
+        backup: &mut Backup,
+        database_file_names: &[&str],
+        uploaded_files: &[PathBuf],
+    ) -> Result<()> {
+        for file_name in database_file_names {
+            let source = self.root_path.join(file_name);
+            if !fs::try_exists(&source).await.unwrap_or(false) {
+                continue;
+            }
+            let destination = backup.path.join(file_name);
+            let written = destination.clone();
+            tokio::task::spawn_blocking(move || copy_database(&source, &written))
+                .await
+                .context("Database backup did not complete")??;
+            backup.database_count += 1;
+            backup.bytes += file_size(&destination).await?;
+        }
+
+        let files_path = backup.path.join(BACKUP_FILES_DIRECTORY);
+        for file in uploaded_files {
+            let relative = file
+                .strip_prefix(&self.root_path)
+                .context(format!("File is outside the root directory: {:?}", file))?;
+            let destination = files_path.join(relative);
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)
+                    .await
+                    .context(format!("Failed to create directory: {:?}", parent))?;
+            }
+            backup.bytes += fs::copy(file, &destination)
+                .await
+                .context(format!("Failed to copy file into backup: {:?}", file))?;
+            backup.file_count += 1;
+        }
+        Ok(())
+    }
+
+    /// Replaces the databases and uploaded files with those of a backup
+    ///
+    /// The application must not be running. Uploaded files that are not in the backup
+    /// are kept, so nothing added since is lost.
+    ///
+    /// # Arguments
+    /// * `backup_path` - Directory of the backup
+    /// * `database_file_names` - Names of the database files in the root directory
+    ///
+    /// # Returns
+    /// * `Result<usize>` - The number of databases and files restored or error
+    pub async fn restore_backup(
+        &self,
+        backup_path: &Path,
+        database_file_names: &[&str],
+    ) -> Result<usize> {
+        let mut restored = 0;
+        for file_name in database_file_names {
+            let source = backup_path.join(file_name);
+            if !fs::try_exists(&source).await.unwrap_or(false) {
+                continue;
+            }
+            // The journal of the replaced database must not be applied to the restored one
+            let destination = self.root_path.join(file_name);
+            for suffix in ["-wal", "-shm"] {
+                let journal = self.root_path.join(format!("{}{}", file_name, suffix));
+                if fs::try_exists(&journal).await.unwrap_or(false) {
+                    fs::remove_file(&journal)
+                        .await
+                        .context(format!("Failed to remove journal: {:?}", journal))?;
+                }
+            }
+            fs::copy(&source, &destination)
+                .await
+                .context(format!("Failed to restore database: {:?}", destination))?;
+            restored += 1;
+        }
+
+        let files_path = backup_path.join(BACKUP_FILES_DIRECTORY);
+        if fs::try_exists(&files_path).await.unwrap_or(false) {
+            for file in list_files(&files_path).await? {
+                let relative = file
+                    .strip_prefix(&files_path)
+                    .context(format!("File is outside the backup: {:?}", file))?;
+                let destination = self.root_path.join(relative);
+                if let Some(parent) = destination.parent() {
+                    fs::create_dir_all(parent)
+                        .await
+                        .context(format!("Failed to create directory: {:?}", parent))?;
+                }
+                fs::copy(&file, &destination)
+                    .await
+                    .context(format!("Failed to restore file: {:?}", destination))?;
+                restored += 1;
+            }
+        }
+
+        log::info!(
+            "Restored {} databases and files from {:?}",
+            restored,
+            backup_path
+        );
+        Ok(restored)
+    }
+}
+
+/// Lists every file in a directory and the directories within it, in a stable order
+///
+/// # Arguments
+/// * `directory` - The directory to list
+///
+/// # Returns
+/// * `Result<Vec<PathBuf>>` - Paths of the files or error
+async fn list_files(directory: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut directories = vec![directory.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        let mut entries = fs::read_dir(&directory)
+            .await
+            .context(format!("Failed to read directory: {:?}", directory))?;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .context(format!("Failed to read directory: {:?}", directory))?
+        {
+            let file_type = entry.file_type().await?;
+            if file_type.is_dir() {
+                directories.push(entry.path());
+            } else if file_type.is_file() {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Writes a consistent copy of a SQLite database, even while other connections use it
+///
+/// # Arguments
+/// * `source` - Path of the database
+/// * `destination` - Path of the copy, which must not exist
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+fn copy_database(source: &Path, destination: &Path) -> Result<()> {
+    let connection = Connection::open_with_flags(source, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .context(format!("Failed to open database: {:?}", source))?;
+    connection
+        .execute(
+            "VACUUM INTO ?1",
+            [destination.to_string_lossy().into_owned()],
+        )
+        .context(format!("Failed to back up database: {:?}", source))?;
+    Ok(())
+}
+
+/// Gets the size of a file
+///
+/// # Arguments
+/// * `path` - Path of the file
+///
+/// # Returns
+/// * `Result<u64>` - The size in bytes or error
+async fn file_size(path: &Path) -> Result<u64> {
+    Ok(fs::metadata(path)
+        .await
+        .context(format!("Failed to read metadata: {:?}", path))?
+        .len())
+}
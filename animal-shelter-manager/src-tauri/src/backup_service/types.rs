@@ -0,0 +1,26 @@
+//
+// backup_service/types.rs
+//
+// This module contains the type definitions for backups.
+//
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// A backup of the databases and uploaded files
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Backup {
+    /// Name of the backup directory, made from the time of the backup
+    pub name: String,
+    /// Path of the backup directory
+    pub path: PathBuf,
+    /// Timestamp when the backup was made
+    pub created_timestamp: i64,
+    /// Number of databases copied
+    pub database_count: usize,
+    /// Number of uploaded files copied
+    pub file_count: usize,
+    /// Total size of the backup, in bytes
+    pub bytes: u64,
+}
@@ -0,0 +1,417 @@
+//
+// cli/mod.rs
+//
+// This module runs maintenance tasks from the command line, without starting
+// the GUI, so they can be scripted on the shelter's server: backups, imports
+// and exports, password resets and integrity checks. The tasks work on the
+// same databases and files as the application, which can keep running while
+// they do, except for restoring a backup.
+//
+
+use crate::authentication_service::AuthenticationService;
+use crate::backup_service::{cloud::CloudStorage, types::Backup, BackupService};
+use crate::database_service::{
+    types::{Animal, Changelog},
+    DatabaseService,
+};
+use crate::export_service;
+use crate::file_service::{FileService, BACKUP_DIRECTORY};
+use crate::settings_service::SettingsService;
+use crate::{
+    add_file_problems, AUTHENTICATION_DATABASE_FILE, DATABASE_FILE_NAMES, SETTINGS_DATABASE_FILE,
+    SHELTER_DATABASE_FILE,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::Utc;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use types::{CliArgs, CliCommand};
+
+mod test;
+pub mod types;
+
+/// Flag that makes the application run a task from the command line instead of
+/// starting the GUI; it must be the first argument
+pub const CLI_FLAG: &str = "--cli";
+
+/// Identifier of the application, as set in tauri.conf.json, which names its data directory
+const APP_IDENTIFIER: &str = "com.sen201.animal-shelter-manager";
+
+/// Exit code of a task that succeeded
+const EXIT_SUCCESS: i32 = 0;
+
+/// Exit code of a task that failed
+const EXIT_FAILURE: i32 = 1;
+
+/// Exit code when the arguments cannot be understood
+const EXIT_USAGE: i32 = 2;
+
+/// Exit code of an integrity check that found problems
+const EXIT_PROBLEMS_FOUND: i32 = 3;
+
+/// How to use the command line interface
+const USAGE: &str = "\
+Usage: animal-shelter-manager --cli [--data-dir <directory>] <command> [arguments]
+
+Commands:
+  backup                                 Back up the databases and uploaded files, uploading
+                                         the backup if cloud backups are set up
+  list-cloud-backups                     List the backups in cloud storage
+  restore-cloud-backup <name>            Back up the current data, then replace it with a
+                                         backup from cloud storage; close the application first
+  check-integrity                        Check the database and uploaded files, printing
+                                         the report as JSON; exits with 3 if problems are found
+  export-changes <file> [--since <ts>]   Export the changes made at or after a Unix timestamp
+  import-changes <file>                  Import a changelog exported by another installation
+  export-animals <file>                  Export every animal as an Excel workbook
+  import-animals <file>                  Create the animals listed in a JSON array
+  reset-password <username>              Set a new password, read from standard input, which
+                                         the user must change when they next log in
+  reactivate-user <username>             Let a suspended user log in again
+  help                                   Print this message
+
+Options:
+  --data-dir <directory>                 Work on the data in this directory instead of the
+                                         application's own";
+
+/// Runs a task from the command line
+///
+/// # Arguments
+/// * `args` - The arguments given after the CLI flag
+///
+/// # Returns
+/// * `i32` - The exit code: 0 on success, 1 if the task failed, 2 if the arguments
+///   cannot be understood, and 3 if an integrity check found problems
+pub fn run(args: &[String]) -> i32 {
+    #[cfg(windows)]
+    attach_parent_console();
+
+    let args = match parse_args(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{:#}\n\n{}", e, USAGE);
+            return EXIT_USAGE;
+        }
+    };
+    if args.command == CliCommand::Help {
+        println!("{}", USAGE);
+        return EXIT_SUCCESS;
+    }
+
+    let Some(data_dir) = args.data_dir.or_else(default_data_dir) else {
+        eprintln!("Error: Failed to find the application data directory; pass --data-dir");
+        return EXIT_FAILURE;
+    };
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Error: Failed to start the runtime: {}", e);
+            return EXIT_FAILURE;
+        }
+    };
+    match runtime.block_on(run_command(&data_dir, args.command)) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            EXIT_FAILURE
+        }
+    }
+}
+
+/// Reads the arguments given after the CLI flag
+///
+/// # Arguments
+/// * `args` - The arguments given after the CLI flag
+///
+/// # Returns
+/// * `Result<CliArgs>` - The options and task, or error describing what is wrong
+pub fn parse_args(args: &[String]) -> Result<CliArgs> {
+    let mut data_dir = None;
+    let mut since_timestamp = None;
+    let mut positional = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--data-dir" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--data-dir needs a directory"))?;
+                data_dir = Some(PathBuf::from(value));
+            }
+            "--since" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--since needs a timestamp"))?;
+                let timestamp = value
+                    .parse()
+                    .map_err(|_| anyhow!("--since needs a Unix timestamp, not {:?}", value))?;
+                since_timestamp = Some(timestamp);
+            }
+            "-h" | "--help" => positional.insert(0, "help"),
+            option if option.starts_with("--") => bail!("Unknown option {}", option),
+            value => positional.push(value),
+        }
+    }
+
+    let Some((&name, operands)) = positional.split_first() else {
+        bail!("No command given");
+    };
+    let operand = |description: &str| -> Result<String> {
+        match operands {
+            [operand] => Ok(operand.to_string()),
+            [] => bail!("{} needs {}", name, description),
+            _ => bail!("{} takes only {}", name, description),
+        }
+    };
+    let no_operands = || -> Result<()> {
+        if !operands.is_empty() {
+            bail!("{} takes no arguments", name);
+        }
+        Ok(())
+    };
+    if since_timestamp.is_some() && name != "export-changes" {
+        bail!("--since only applies to export-changes");
+    }
+
+    let command = match name {
+        "help" => CliCommand::Help,
+        "backup" => {
+            no_operands()?;
+            CliCommand::Backup
+        }
+        "list-cloud-backups" => {
+            no_operands()?;
+            CliCommand::ListCloudBackups
+        }
+        "restore-cloud-backup" => CliCommand::RestoreCloudBackup {
+            name: operand("a backup name")?,
+        },
+        "check-integrity" => {
+            no_operands()?;
+            CliCommand::CheckIntegrity
+        }
+        "export-changes" => CliCommand::ExportChanges {
+            path: operand("a file")?.into(),
+            since_timestamp,
+        },
+        "import-changes" => CliCommand::ImportChanges {
+            path: operand("a file")?.into(),
+        },
+        "export-animals" => CliCommand::ExportAnimals {
+            path: operand("a file")?.into(),
+        },
+        "import-animals" => CliCommand::ImportAnimals {
+            path: operand("a file")?.into(),
+        },
+        "reset-password" => CliCommand::ResetPassword {
+            username: operand("a username")?,
+        },
+        "reactivate-user" => CliCommand::ReactivateUser {
+            username: operand("a username")?,
+        },
+        unknown => bail!("Unknown command {}", unknown),
+    };
+    Ok(CliArgs { data_dir, command })
+}
+
+/// Runs a task on the data in a directory
+///
+/// # Arguments
+/// * `data_dir` - Directory holding the databases and uploaded files
+/// * `command` - The task to run
+///
+/// # Returns
+/// * `Result<i32>` - The exit code or error if the task failed
+async fn run_command(data_dir: &Path, command: CliCommand) -> Result<i32> {
+    if !data_dir.is_dir() {
+        bail!("Data directory does not exist: {:?}", data_dir);
+    }
+
+    match command {
+        CliCommand::Help => println!("{}", USAGE),
+        CliCommand::Backup => {
+            let backup = back_up(data_dir).await?;
+            if let Some(storage) = open_cloud_storage(data_dir)? {
+                let uploaded = storage.upload_backup(&backup).await?;
+                println!(
+                    "Uploaded {} files of {} to cloud storage",
+                    uploaded, backup.name
+                );
+            }
+        }
+        CliCommand::ListCloudBackups => {
+            let storage = open_cloud_storage(data_dir)?
+                .ok_or_else(|| anyhow!("Cloud backups are not set up"))?;
+            for name in storage.list_backups().await? {
+                println!("{}", name);
+            }
+        }
+        CliCommand::RestoreCloudBackup { name } => {
+            let storage = open_cloud_storage(data_dir)?
+                .ok_or_else(|| anyhow!("Cloud backups are not set up"))?;
+            if !storage.list_backups().await?.contains(&name) {
+                bail!("No backup named {} in cloud storage", name);
+            }
+            back_up(data_dir).await?;
+
+            // A backup made on this installation may still be here, sparing the download
+            let backup_path = data_dir.join(BACKUP_DIRECTORY).join(&name);
+            if !backup_path.is_dir() {
+                storage.download_backup(&name, &backup_path).await?;
+                println!("Downloaded {} to {}", name, backup_path.display());
+            }
+            let restored = BackupService::new(data_dir)
+                .restore_backup(&backup_path, &DATABASE_FILE_NAMES)
+                .await?;
+            println!("Restored {} databases and files from {}", restored, name);
+        }
+        CliCommand::CheckIntegrity => {
+            let database_service = open_database(data_dir)?;
+            let mut report = database_service.check_integrity()?;
+            let image_paths = database_service.query_image_paths()?;
+            let file_service = FileService::new(data_dir)?;
+            add_file_problems(&mut report, &image_paths, &file_service).await?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            if !report.healthy {
+                return Ok(EXIT_PROBLEMS_FOUND);
+            }
+        }
+        CliCommand::ExportChanges {
+            path,
+            since_timestamp,
+        } => {
+            let changelog = open_database(data_dir)?
+                .export_changes(since_timestamp.unwrap_or(0), Utc::now().timestamp())?;
+            write_file(&path, serde_json::to_vec_pretty(&changelog)?).await?;
+            println!("Exported changes to {}", path.display());
+        }
+        CliCommand::ImportChanges { path } => {
+            let changelog: Changelog = serde_json::from_slice(&read_file(&path).await?)
+                .context(format!("Failed to read changes: {:?}", path))?;
+            let result = open_database(data_dir)?.import_changes(&changelog)?;
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+        CliCommand::ExportAnimals { path } => {
+            let animals = open_database(data_dir)?.query_animals(None)?;
+            let locale = SettingsService::new(data_dir.join(SETTINGS_DATABASE_FILE))?.locale()?;
+            let contents = export_service::render_animals_xlsx(&animals, locale)?;
+            write_file(&path, contents).await?;
+            println!("Exported {} animals to {}", animals.len(), path.display());
+        }
+        CliCommand::ImportAnimals { path } => {
+            let animals: Vec<Animal> = serde_json::from_slice(&read_file(&path).await?)
+                .context(format!("Failed to read animals: {:?}", path))?;
+            let (ids, _) = open_database(data_dir)?.insert_animals_batch(&animals)?;
+            println!("Created {} animals", ids.len());
+            for id in ids {
+                println!("{}", id);
+            }
+        }
+        CliCommand::ResetPassword { username } => {
+            let authentication_service = open_authentication(data_dir)?;
+            eprintln!("New password for {}:", username);
+            let mut password = String::new();
+            std::io::stdin()
+                .lock()
+                .read_line(&mut password)
+                .context("Failed to read the new password")?;
+            let password = password.trim_end_matches(['\r', '\n']);
+            if password.is_empty() {
+                bail!("No password given");
+            }
+            authentication_service.reset_password(&username, password)?;
+            println!(
+                "Reset the password of {}; they must change it when they next log in",
+                username
+            );
+        }
+        CliCommand::ReactivateUser { username } => {
+            open_authentication(data_dir)?.reactivate_user(&username)?;
+            println!("Reactivated {}", username);
+        }
+    }
+    Ok(EXIT_SUCCESS)
+}
+
+/// Backs up the databases and uploaded files in a data directory, printing where to
+///
+/// # Arguments
+/// * `data_dir` - Directory holding the databases and uploaded files
+///
+/// # Returns
+/// * `Result<Backup>` - The backup made or error
+async fn back_up(data_dir: &Path) -> Result<Backup> {
+    let file_service = FileService::new(data_dir)?;
+    let uploaded_files = file_service.list_uploaded_files().await?;
+    let backup = BackupService::new(data_dir)
+        .create_backup(&DATABASE_FILE_NAMES, &uploaded_files, Utc::now())
+        .await?;
+    println!(
+        "Backed up {} databases and {} files ({} bytes) to {}",
+        backup.database_count,
+        backup.file_count,
+        backup.bytes,
+        backup.path.display()
+    );
+    Ok(backup)
+}
+
+/// Connects to the cloud storage set in the settings of a data directory
+///
+/// # Returns
+/// * `Result<Option<CloudStorage>>` - The storage, or None if cloud backups are not set up
+fn open_cloud_storage(data_dir: &Path) -> Result<Option<CloudStorage>> {
+    SettingsService::new(data_dir.join(SETTINGS_DATABASE_FILE))?
+        .cloud_backup_config()?
+        .map(CloudStorage::new)
+        .transpose()
+}
+
+/// Opens the shelter database in a data directory
+fn open_database(data_dir: &Path) -> Result<DatabaseService> {
+    DatabaseService::new(data_dir.join(SHELTER_DATABASE_FILE))
+}
+
+/// Opens the authentication database in a data directory
+fn open_authentication(data_dir: &Path) -> Result<AuthenticationService> {
+    AuthenticationService::new(data_dir.join(AUTHENTICATION_DATABASE_FILE))
+}
+
+/// Reads a file given on the command line
+async fn read_file(path: &Path) -> Result<Vec<u8>> {
+    tokio::fs::read(path)
+        .await
+        .context(format!("Failed to read file: {:?}", path))
+}
+
+/// Writes a file given on the command line
+async fn write_file(path: &Path, contents: Vec<u8>) -> Result<()> {
+    tokio::fs::write(path, contents)
+        .await
+        .context(format!("Failed to write file: {:?}", path))
+}
+
+/// Finds the data directory the application uses, as Tauri resolves it
+///
+/// # Returns
+/// * `Option<PathBuf>` - The directory, or None if the platform has no data directory
+fn default_data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_IDENTIFIER))
+}
+
+/// Writes output to the console the application was started from, since release builds
+/// on Windows have no console of their own
+#[cfg(windows)]
+fn attach_parent_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    // SAFETY: AttachConsole takes no pointers; it fails harmlessly if the process
+    // already has a console or its parent has none
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
@@ -0,0 +1,104 @@
+//
+// cli/test.rs
+//
+// This file contains unit tests for reading command line arguments.
+//
+
+#[cfg(test)]
+mod cli_tests {
+    use crate::cli::{
+        parse_args,
+        types::{CliArgs, CliCommand},
+    };
+    use std::path::PathBuf;
+
+    /// Helper function to read arguments written as a single string
+    ///
+    /// # Arguments
+    /// * `line` - The arguments, separated by spaces
+    ///
+    /// # Returns
+    /// * `anyhow::Result<CliArgs>` - The options and task, or error
+    fn parse(line: &str) -> anyhow::Result<CliArgs> {
+        let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+        parse_args(&args)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse("backup").unwrap(),
+            CliArgs {
+                data_dir: None,
+                command: CliCommand::Backup,
+            }
+        );
+        assert_eq!(
+            parse("--data-dir /srv/shelter export-changes out.json --since 1700000000").unwrap(),
+            CliArgs {
+                data_dir: Some(PathBuf::from("/srv/shelter")),
+                command: CliCommand::ExportChanges {
+                    path: PathBuf::from("out.json"),
+                    since_timestamp: Some(1_700_000_000),
+                },
+            }
+        );
+        assert_eq!(
+            parse("reset-password staffer").unwrap().command,
+            CliCommand::ResetPassword {
+                username: "staffer".to_string(),
+            }
+        );
+        assert_eq!(parse("--help").unwrap().command, CliCommand::Help);
+        assert_eq!(
+            parse("restore-cloud-backup backup_2025-03-04_05-06-07")
+                .unwrap()
+                .command,
+            CliCommand::RestoreCloudBackup {
+                name: "backup_2025-03-04_05-06-07".to_string(),
+            }
+        );
+        assert_eq!(
+            parse("check-integrity --data-dir data").unwrap().data_dir,
+            Some(PathBuf::from("data"))
+        );
+    }
+
+    #[test]
+    fn test_parse_args_rejects_bad_arguments() {
+        for (line, expected) in [
+            ("", "No command given"),
+            ("restore", "Unknown command restore"),
+            ("backup --force", "Unknown option --force"),
+            ("backup extra", "backup takes no arguments"),
+            ("import-changes", "import-changes needs a file"),
+            (
+                "restore-cloud-backup",
+                "restore-cloud-backup needs a backup name",
+            ),
+            (
+                "list-cloud-backups all",
+                "list-cloud-backups takes no arguments",
+            ),
+            (
+                "reactivate-user a b",
+                "reactivate-user takes only a username",
+            ),
+            (
+                "export-changes out.json --since yesterday",
+                "Unix timestamp",
+            ),
+            ("export-animals out.xlsx --since 0", "--since only applies"),
+            ("backup --data-dir", "--data-dir needs a directory"),
+        ] {
+            let error = parse(line).unwrap_err().to_string();
+            assert!(
+                error.contains(expected),
+                "{:?} gave {:?}, expected {:?}",
+                line,
+                error,
+                expected
+            );
+        }
+    }
+}
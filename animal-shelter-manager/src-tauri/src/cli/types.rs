@@ -0,0 +1,70 @@
+//
+// cli/types.rs
+//
+// This module contains the type definitions for the command line interface.
+//
+
+use std::path::PathBuf;
+
+/// Arguments given after the CLI flag
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliArgs {
+    /// Directory holding the databases and uploaded files, or None for the one the
+    /// application uses
+    pub data_dir: Option<PathBuf>,
+    /// The task to run
+    pub command: CliCommand,
+}
+
+/// A maintenance task that can be run from the command line
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    /// Print how to use the command line interface
+    Help,
+    /// Back up the databases and uploaded files, and upload the backup if cloud backups
+    /// are set up
+    Backup,
+    /// List the backups in cloud storage
+    ListCloudBackups,
+    /// Replace the databases and uploaded files with a backup from cloud storage, after
+    /// backing up the current ones
+    RestoreCloudBackup {
+        /// Name of the backup, such as "backup_2025-03-04_05-06-07"
+        name: String,
+    },
+    /// Check the database and uploaded files for corruption and dangling references
+    CheckIntegrity,
+    /// Export the changes made since a time as a changelog file
+    ExportChanges {
+        /// Where to write the changelog
+        path: PathBuf,
+        /// Only export changes made at or after this timestamp, or everything if None
+        since_timestamp: Option<i64>,
+    },
+    /// Import a changelog file exported by another installation
+    ImportChanges {
+        /// The changelog to read
+        path: PathBuf,
+    },
+    /// Export every animal as an Excel workbook
+    ExportAnimals {
+        /// Where to write the workbook
+        path: PathBuf,
+    },
+    /// Create the animals listed in a JSON file
+    ImportAnimals {
+        /// The JSON file to read
+        path: PathBuf,
+    },
+    /// Set a new password for a user, read from standard input, which they must change
+    /// when they next log in
+    ResetPassword {
+        /// Username of the account
+        username: String,
+    },
+    /// Let a suspended user log in again
+    ReactivateUser {
+        /// Username of the account
+        username: String,
+    },
+}
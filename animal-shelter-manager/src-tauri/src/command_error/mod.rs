@@ -0,0 +1,200 @@
+//
+// command_error/mod.rs
+//
+// This module defines the error every command returns to the frontend. Each
+// error carries a kind the frontend can react to, such as showing the invalid
+// fields of a form, along with a message describing what went wrong. When an
+// error is sent to the frontend it also gets a summary in the locale chosen in
+// the settings, with the key of that summary.
+//
+
+use crate::database_service::{types::FieldError, validation::ValidationError};
+use crate::i18n::{self, types::MessageKey};
+use rusqlite::ffi;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+mod test;
+
+/// Error returned by a command, serialized as an object with a `kind`, the `key` and
+/// localized `summary` of that kind, and a `message`
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandError {
+    /// The record the command works on, or one it refers to, does not exist
+    NotFound {
+        /// Description of the error
+        message: String,
+    },
+    /// The submitted record has invalid fields
+    ValidationFailed {
+        /// Description of the error
+        message: String,
+        /// The invalid fields and what is wrong with each of them
+        field_errors: Vec<FieldError>,
+    },
+    /// The user is not logged in, or not allowed to do this
+    PermissionDenied {
+        /// Description of the error
+        message: String,
+    },
+    /// The command clashes with the current state, such as a name that is already taken
+    Conflict {
+        /// Description of the error
+        message: String,
+    },
+    /// A file could not be read or written
+    Io {
+        /// Description of the error
+        message: String,
+    },
+    /// A database or other service failed
+    Database {
+        /// Description of the error
+        message: String,
+    },
+}
+
+impl CommandError {
+    /// Creates an error for a record that does not exist
+    pub fn not_found(message: impl Into<String>) -> Self {
+        CommandError::NotFound {
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error for an action the user is not allowed to take
+    pub fn permission_denied(message: impl Into<String>) -> Self {
+        CommandError::PermissionDenied {
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error for a command that clashes with the current state
+    pub fn conflict(message: impl Into<String>) -> Self {
+        CommandError::Conflict {
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error from the failure of a service, telling its kind from the cause
+    ///
+    /// Validation errors keep their invalid fields, unique constraint violations are
+    /// conflicts, foreign key violations mean a referenced record does not exist, and
+    /// file system errors are I/O errors. Anything else is a database error.
+    ///
+    /// # Arguments
+    /// * `context` - What the command was doing, such as "Failed to create animal"
+    /// * `error` - The error the service returned
+    ///
+    /// # Returns
+    /// * `CommandError` - The error, with a message made of the context and the error
+    pub fn from_service(context: impl fmt::Display, error: impl Into<anyhow::Error>) -> Self {
+        let error = error.into();
+        let message = format!("{}: {:#}", context, error);
+
+        for cause in error.chain() {
+            if let Some(validation) = cause.downcast_ref::<ValidationError>() {
+                return CommandError::ValidationFailed {
+                    message,
+                    field_errors: validation.fields.clone(),
+                };
+            }
+            if let Some(rusqlite::Error::SqliteFailure(failure, _)) =
+                cause.downcast_ref::<rusqlite::Error>()
+            {
+                match failure.extended_code {
+                    ffi::SQLITE_CONSTRAINT_UNIQUE | ffi::SQLITE_CONSTRAINT_PRIMARYKEY => {
+                        return CommandError::Conflict { message };
+                    }
+                    ffi::SQLITE_CONSTRAINT_FOREIGNKEY => {
+                        return CommandError::NotFound { message };
+                    }
+                    _ => {}
+                }
+            }
+            if cause.is::<std::io::Error>() {
+                return CommandError::Io { message };
+            }
+        }
+        CommandError::Database { message }
+    }
+
+    /// Gets the kind of the error, as the frontend names it
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CommandError::NotFound { .. } => "not-found",
+            CommandError::ValidationFailed { .. } => "validation-failed",
+            CommandError::PermissionDenied { .. } => "permission-denied",
+            CommandError::Conflict { .. } => "conflict",
+            CommandError::Io { .. } => "io",
+            CommandError::Database { .. } => "database",
+        }
+    }
+
+    /// Gets the key of the summary of the error
+    pub fn summary_key(&self) -> MessageKey {
+        match self {
+            CommandError::NotFound { .. } => MessageKey::ErrorNotFound,
+            CommandError::ValidationFailed { .. } => MessageKey::ErrorValidationFailed,
+            CommandError::PermissionDenied { .. } => MessageKey::ErrorPermissionDenied,
+            CommandError::Conflict { .. } => MessageKey::ErrorConflict,
+            CommandError::Io { .. } => MessageKey::ErrorIo,
+            CommandError::Database { .. } => MessageKey::ErrorDatabase,
+        }
+    }
+
+    /// Gets the description of the error
+    pub fn message(&self) -> &str {
+        match self {
+            CommandError::NotFound { message }
+            | CommandError::ValidationFailed { message, .. }
+            | CommandError::PermissionDenied { message }
+            | CommandError::Conflict { message }
+            | CommandError::Io { message }
+            | CommandError::Database { message } => message,
+        }
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// A command error as sent to the frontend
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializedCommandError<'a> {
+    /// Kind of the error
+    kind: &'static str,
+    /// Key of the summary
+    key: MessageKey,
+    /// Summary of the error in the current locale
+    summary: &'static str,
+    /// Description of the error
+    message: &'a str,
+    /// The invalid fields, for validation errors only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    field_errors: Option<&'a [FieldError]>,
+}
+
+impl Serialize for CommandError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let key = self.summary_key();
+        let field_errors = match self {
+            CommandError::ValidationFailed { field_errors, .. } => Some(field_errors.as_slice()),
+            _ => None,
+        };
+        SerializedCommandError {
+            kind: self.kind(),
+            key,
+            summary: i18n::text(key, i18n::current_locale()),
+            message: self.message(),
+            field_errors,
+        }
+        .serialize(serializer)
+    }
+}
@@ -0,0 +1,127 @@
+//
+// command_error/test.rs
+//
+// This file contains unit tests for telling the kind of a command error from
+// the error a service returned.
+//
+
+#[cfg(test)]
+mod command_error_tests {
+    use crate::command_error::CommandError;
+    use crate::database_service::{types::FieldError, validation::ValidationError};
+    use anyhow::{anyhow, Context};
+    use rusqlite::Connection;
+
+    /// Helper function to create an in-memory database with a parent and a child table
+    ///
+    /// # Returns
+    /// * `Connection` - A connection with foreign keys enforced
+    fn create_test_connection() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "PRAGMA foreign_keys = ON;
+             CREATE TABLE parents (id TEXT PRIMARY KEY, name TEXT UNIQUE);
+             CREATE TABLE children (id TEXT PRIMARY KEY, parent_id TEXT REFERENCES parents(id));
+             INSERT INTO parents (id, name) VALUES ('p1', 'Rex');",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_validation_errors_keep_their_fields() {
+        let fields = vec![FieldError {
+            field: "name".to_string(),
+            message: "is required".to_string(),
+        }];
+        let error = Err::<(), _>(ValidationError {
+            fields: fields.clone(),
+        })
+        .context("Checking animal")
+        .unwrap_err();
+
+        let command_error = CommandError::from_service("Failed to create animal", error);
+        assert_eq!(
+            command_error,
+            CommandError::ValidationFailed {
+                message:
+                    "Failed to create animal: Checking animal: Invalid fields: name is required"
+                        .to_string(),
+                field_errors: fields,
+            }
+        );
+
+        let json = serde_json::to_value(&command_error).unwrap();
+        assert_eq!(json["kind"], "validation-failed");
+        assert_eq!(json["fieldErrors"][0]["field"], "name");
+        assert_eq!(json["fieldErrors"][0]["message"], "is required");
+    }
+
+    #[test]
+    fn test_constraint_violations() {
+        let conn = create_test_connection();
+
+        let duplicate = conn
+            .execute("INSERT INTO parents (id, name) VALUES ('p2', 'Rex')", [])
+            .unwrap_err();
+        assert!(matches!(
+            CommandError::from_service("Failed to add parent", duplicate),
+            CommandError::Conflict { .. }
+        ));
+
+        let same_id = conn
+            .execute("INSERT INTO parents (id, name) VALUES ('p1', 'Max')", [])
+            .unwrap_err();
+        assert!(matches!(
+            CommandError::from_service("Failed to add parent", same_id),
+            CommandError::Conflict { .. }
+        ));
+
+        let missing_parent = conn
+            .execute(
+                "INSERT INTO children (id, parent_id) VALUES ('c1', 'p9')",
+                [],
+            )
+            .unwrap_err();
+        assert!(matches!(
+            CommandError::from_service("Failed to add child", missing_parent),
+            CommandError::NotFound { .. }
+        ));
+
+        let bad_sql = conn
+            .execute("INSERT INTO missing VALUES (1)", [])
+            .unwrap_err();
+        assert!(matches!(
+            CommandError::from_service("Failed to add record", bad_sql),
+            CommandError::Database { .. }
+        ));
+    }
+
+    #[test]
+    fn test_other_errors() {
+        let io_error = std::fs::read("test_artifacts/command_error/does_not_exist").unwrap_err();
+        let command_error = CommandError::from_service("Failed to read file", io_error);
+        assert!(matches!(command_error, CommandError::Io { .. }));
+        assert!(command_error.message().starts_with("Failed to read file: "));
+
+        let command_error = CommandError::from_service("Failed to get animal", anyhow!("Locked"));
+        assert_eq!(
+            command_error,
+            CommandError::Database {
+                message: "Failed to get animal: Locked".to_string()
+            }
+        );
+        assert_eq!(command_error.to_string(), "Failed to get animal: Locked");
+
+        let json = serde_json::to_value(CommandError::permission_denied("Not logged in")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "kind": "permission-denied",
+                "key": "error-permission-denied",
+                "summary": "You are not allowed to do this",
+                "message": "Not logged in",
+            })
+        );
+    }
+}
@@ -0,0 +1,322 @@
+//
+// database_service/appointments.rs
+//
+// This module provides operations for meet-and-greet appointments: customers
+// book a slot to meet an animal, and staff confirm or cancel it. An animal or
+// customer cannot be booked twice for overlapping slots.
+//
+
+use super::types::{
+    AnimalStatus, Appointment, AppointmentStatus, CancelAppointmentResult,
+    ConfirmAppointmentResult, Notification, RequestAppointmentResult,
+};
+use super::validation;
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension, Row};
+use uuid::Uuid;
+
+/// Length of a meet-and-greet appointment, in seconds
+const APPOINTMENT_LENGTH_SECONDS: i64 = 30 * 60;
+
+/// Columns selected when reading an appointment, in the order expected by `appointment_from_row`
+const APPOINTMENT_COLUMNS: &str =
+    "p.id, p.animal_id, a.name, p.username, p.slot_timestamp, p.status, p.created_timestamp";
+
+/// Builds an appointment from a row selected with `APPOINTMENT_COLUMNS`
+fn appointment_from_row(row: &Row) -> rusqlite::Result<Appointment> {
+    Ok(Appointment {
+        id: row.get(0)?,
+        animal_id: row.get(1)?,
+        animal_name: row.get(2)?,
+        username: row.get(3)?,
+        slot_timestamp: row.get(4)?,
+        status: row.get(5)?,
+        created_timestamp: row.get(6)?,
+    })
+}
+
+impl DatabaseService {
+    // ==================== APPOINTMENTS TABLE OPERATIONS ====================
+
+    /// Books a meet-and-greet appointment for a customer, waiting for staff to confirm it
+    ///
+    /// Fails with a `ValidationError` if the slot is not in the future.
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to meet
+    /// * `username` - The username of the customer booking the appointment
+    /// * `slot_timestamp` - Timestamp when the appointment starts
+    ///
+    /// # Returns
+    /// * `Result<RequestAppointmentResult>` - Requested, or why the slot could not be booked
+    pub fn request_appointment(
+        &self,
+        animal_id: &str,
+        username: &str,
+        slot_timestamp: i64,
+    ) -> Result<RequestAppointmentResult> {
+        validation::ensure_valid(validation::validate_appointment_slot(slot_timestamp))?;
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for appointment booking")?;
+
+        let status: Option<AnimalStatus> = transaction
+            .query_row(
+                "SELECT status FROM animals WHERE id = ?1",
+                params![animal_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to read animal status")?;
+        match status {
+            None => return Ok(RequestAppointmentResult::AnimalNotFound),
+            Some(AnimalStatus::Available | AnimalStatus::Requested) => {}
+            Some(_) => return Ok(RequestAppointmentResult::AnimalNotAvailable),
+        }
+
+        // Neither the animal nor the customer can be in two places at once
+        let overlapping: i64 = transaction
+            .query_row(
+                "SELECT COUNT(*) FROM appointments WHERE (animal_id = ?1 OR username = ?2) AND status != ?3 AND slot_timestamp > ?4 - ?5 AND slot_timestamp < ?4 + ?5",
+                params![
+                    animal_id,
+                    username,
+                    AppointmentStatus::Cancelled,
+                    slot_timestamp,
+                    APPOINTMENT_LENGTH_SECONDS
+                ],
+                |row| row.get(0),
+            )
+            .context("Failed to count overlapping appointments")?;
+        if overlapping > 0 {
+            return Ok(RequestAppointmentResult::SlotTaken);
+        }
+
+        let id = Uuid::new_v4().to_string();
+        transaction
+            .execute(
+                "INSERT INTO appointments (id, animal_id, username, slot_timestamp, status, created_timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    id,
+                    animal_id,
+                    username,
+                    slot_timestamp,
+                    AppointmentStatus::Requested,
+                    Utc::now().timestamp()
+                ],
+            )
+            .context("Failed to insert appointment into database")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit appointment booking")?;
+
+        log::info!(
+            "Booked appointment {} with animal ID: {} for user: {}",
+            id,
+            animal_id,
+            username
+        );
+        Ok(RequestAppointmentResult::Requested)
+    }
+
+    /// Retrieves the appointments a customer has booked
+    ///
+    /// # Arguments
+    /// * `username` - The username of the customer
+    ///
+    /// # Returns
+    /// * `Result<Vec<Appointment>>` - Appointments, latest slot first
+    pub fn query_appointments_by_username(&self, username: &str) -> Result<Vec<Appointment>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM appointments p JOIN animals a ON a.id = p.animal_id WHERE p.username = ?1 ORDER BY p.slot_timestamp DESC",
+                APPOINTMENT_COLUMNS
+            ))
+            .context("Failed to prepare query for appointments by username")?;
+
+        let appointments = statement
+            .query_map(params![username], appointment_from_row)
+            .context("Failed to execute query for appointments by username")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse appointment row")?;
+
+        log::debug!(
+            "Retrieved {} appointments for user: {}",
+            appointments.len(),
+            username
+        );
+        Ok(appointments)
+    }
+
+    /// Retrieves the appointments starting during a period, for staff to plan the visits
+    ///
+    /// # Arguments
+    /// * `from_timestamp` - Start of the period, inclusive
+    /// * `to_timestamp` - End of the period, exclusive
+    ///
+    /// # Returns
+    /// * `Result<Vec<Appointment>>` - Appointments, earliest slot first
+    pub fn query_appointments(
+        &self,
+        from_timestamp: i64,
+        to_timestamp: i64,
+    ) -> Result<Vec<Appointment>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM appointments p JOIN animals a ON a.id = p.animal_id WHERE p.slot_timestamp >= ?1 AND p.slot_timestamp < ?2 ORDER BY p.slot_timestamp ASC",
+                APPOINTMENT_COLUMNS
+            ))
+            .context("Failed to prepare query for appointments")?;
+
+        let appointments = statement
+            .query_map(params![from_timestamp, to_timestamp], appointment_from_row)
+            .context("Failed to execute query for appointments")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse appointment row")?;
+
+        log::debug!("Retrieved {} appointments", appointments.len());
+        Ok(appointments)
+    }
+
+    /// Confirms a requested appointment and notifies the customer
+    ///
+    /// # Arguments
+    /// * `appointment_id` - The ID of the appointment
+    ///
+    /// # Returns
+    /// * `Result<(ConfirmAppointmentResult, Vec<Notification>)>` - Confirmed, or why the
+    ///   appointment could not be confirmed, with the notification created for the customer
+    pub fn confirm_appointment(
+        &self,
+        appointment_id: &str,
+    ) -> Result<(ConfirmAppointmentResult, Vec<Notification>)> {
+        let appointment = match self.query_appointment_by_id(appointment_id)? {
+            None => return Ok((ConfirmAppointmentResult::NotFound, Vec::new())),
+            Some(appointment) if appointment.status != AppointmentStatus::Requested => {
+                return Ok((ConfirmAppointmentResult::NotRequested, Vec::new()))
+            }
+            Some(appointment) => appointment,
+        };
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for appointment confirmation")?;
+        transaction
+            .execute(
+                "UPDATE appointments SET status = ?2 WHERE id = ?1",
+                params![appointment_id, AppointmentStatus::Confirmed],
+            )
+            .context("Failed to confirm appointment")?;
+        let notification = self.insert_notification(
+            &appointment.username,
+            &format!(
+                "Your meet-and-greet with {} on {} is confirmed",
+                appointment.animal_name,
+                format_slot(appointment.slot_timestamp)
+            ),
+            Some(&appointment.animal_id),
+        )?;
+        transaction
+            .commit()
+            .context("Failed to commit appointment confirmation")?;
+
+        log::info!("Confirmed appointment with ID: {}", appointment_id);
+        Ok((ConfirmAppointmentResult::Confirmed, vec![notification]))
+    }
+
+    /// Cancels an appointment, notifying the customer when staff cancel it
+    ///
+    /// # Arguments
+    /// * `appointment_id` - The ID of the appointment
+    /// * `username` - The customer cancelling their own appointment, or None when staff cancel it
+    ///
+    /// # Returns
+    /// * `Result<(CancelAppointmentResult, Vec<Notification>)>` - Cancelled, or why the
+    ///   appointment could not be cancelled, with the notification created for the customer
+    pub fn cancel_appointment(
+        &self,
+        appointment_id: &str,
+        username: Option<&str>,
+    ) -> Result<(CancelAppointmentResult, Vec<Notification>)> {
+        let appointment = match self.query_appointment_by_id(appointment_id)? {
+            Some(appointment) if username.is_none_or(|name| name == appointment.username) => {
+                appointment
+            }
+            _ => return Ok((CancelAppointmentResult::NotFound, Vec::new())),
+        };
+        if appointment.status == AppointmentStatus::Cancelled {
+            return Ok((CancelAppointmentResult::AlreadyCancelled, Vec::new()));
+        }
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for appointment cancellation")?;
+        transaction
+            .execute(
+                "UPDATE appointments SET status = ?2 WHERE id = ?1",
+                params![appointment_id, AppointmentStatus::Cancelled],
+            )
+            .context("Failed to cancel appointment")?;
+        let mut notifications = Vec::new();
+        if username.is_none() {
+            notifications.push(self.insert_notification(
+                &appointment.username,
+                &format!(
+                    "Your meet-and-greet with {} on {} was cancelled",
+                    appointment.animal_name,
+                    format_slot(appointment.slot_timestamp)
+                ),
+                Some(&appointment.animal_id),
+            )?);
+        }
+        transaction
+            .commit()
+            .context("Failed to commit appointment cancellation")?;
+
+        log::info!("Cancelled appointment with ID: {}", appointment_id);
+        Ok((CancelAppointmentResult::Cancelled, notifications))
+    }
+
+    /// Retrieves an appointment by its ID
+    ///
+    /// # Arguments
+    /// * `appointment_id` - The ID of the appointment
+    ///
+    /// # Returns
+    /// * `Result<Option<Appointment>>` - The appointment, or None if not found
+    fn query_appointment_by_id(&self, appointment_id: &str) -> Result<Option<Appointment>> {
+        self.connection
+            .query_row(
+                &format!(
+                    "SELECT {} FROM appointments p JOIN animals a ON a.id = p.animal_id WHERE p.id = ?1",
+                    APPOINTMENT_COLUMNS
+                ),
+                params![appointment_id],
+                appointment_from_row,
+            )
+            .optional()
+            .context("Failed to query appointment by ID")
+    }
+}
+
+/// Formats the start of an appointment slot, such as 2025-03-14 14:30 UTC
+///
+/// # Arguments
+/// * `timestamp` - The timestamp in seconds
+///
+/// # Returns
+/// * `String` - The date and time in UTC
+pub(super) fn format_slot(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|date| date.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_default()
+}
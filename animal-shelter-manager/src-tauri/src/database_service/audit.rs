@@ -0,0 +1,135 @@
+//
+// database_service/audit.rs
+//
+// This module provides operations for the audit log, which records what staff
+// members do to animals and adoption requests so the team can follow each
+// other's work in the activity feed.
+//
+
+use super::types::{AuditAction, AuditEntry};
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::params;
+use uuid::Uuid;
+
+/// Entries returned for each page of the activity feed, unless more or fewer are asked for
+pub const DEFAULT_ACTIVITY_FEED_LIMIT: usize = 50;
+
+/// Most entries returned for a page of the activity feed
+pub const MAX_ACTIVITY_FEED_LIMIT: usize = 200;
+
+impl DatabaseService {
+    // ==================== AUDIT LOG OPERATIONS ====================
+
+    /// Records something a staff member did
+    ///
+    /// The animal concerned, and its name, are looked up in the animals and adoption
+    /// requests and then in the trash, and kept with the entry so it still reads well
+    /// once they are gone.
+    ///
+    /// # Arguments
+    /// * `username` - Username of the staff member who acted
+    /// * `action` - What they did
+    /// * `record_id` - ID of the animal or adoption request they acted on
+    ///
+    /// # Returns
+    /// * `Result<AuditEntry>` - The stored entry or error
+    pub fn insert_audit_entry(
+        &self,
+        username: &str,
+        action: AuditAction,
+        record_id: &str,
+    ) -> Result<AuditEntry> {
+        let animal_id: Option<String> = if action.is_about_request() {
+            self.connection
+                .query_row(
+                    "SELECT COALESCE((SELECT animal_id FROM adoption_requests WHERE id = ?1), (SELECT animal_id FROM trashed_adoption_requests WHERE id = ?1))",
+                    params![record_id],
+                    |row| row.get(0),
+                )
+                .context("Failed to look up animal of adoption request")?
+        } else {
+            Some(record_id.to_string())
+        };
+        let animal_name: Option<String> = self
+            .connection
+            .query_row(
+                "SELECT COALESCE((SELECT name FROM animals WHERE id = ?1), (SELECT name FROM trashed_animals WHERE id = ?1))",
+                params![animal_id],
+                |row| row.get(0),
+            )
+            .context("Failed to look up name of animal")?;
+
+        let entry = AuditEntry {
+            id: Uuid::new_v4().to_string(),
+            username: username.to_string(),
+            action,
+            record_id: record_id.to_string(),
+            animal_id,
+            animal_name,
+            timestamp: Utc::now().timestamp(),
+        };
+
+        self.connection
+            .execute(
+                "INSERT INTO audit_log (id, username, action, record_id, animal_id, animal_name, timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    entry.id,
+                    entry.username,
+                    entry.action,
+                    entry.record_id,
+                    entry.animal_id,
+                    entry.animal_name,
+                    entry.timestamp
+                ],
+            )
+            .context("Failed to insert audit log entry into database")?;
+
+        log::debug!("Recorded {} of {} by {}", action, record_id, username);
+        Ok(entry)
+    }
+
+    /// Retrieves a page of the audit log, most recent first
+    ///
+    /// # Arguments
+    /// * `offset` - Number of the most recent entries to skip
+    /// * `limit` - Most entries to return
+    ///
+    /// # Returns
+    /// * `Result<(Vec<AuditEntry>, i64)>` - The entries of the page and the number of entries in the whole log, or error
+    pub fn query_audit_log(&self, offset: usize, limit: usize) -> Result<(Vec<AuditEntry>, i64)> {
+        let total = self
+            .connection
+            .query_row("SELECT COUNT(*) FROM audit_log", [], |row| row.get(0))
+            .context("Failed to count audit log entries")?;
+
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, username, action, record_id, animal_id, animal_name, timestamp FROM audit_log ORDER BY timestamp DESC, rowid DESC LIMIT ?1 OFFSET ?2",
+            )
+            .context("Failed to prepare query for audit log")?;
+
+        let entry_iter = statement
+            .query_map(params![limit as i64, offset as i64], |row| {
+                Ok(AuditEntry {
+                    id: row.get(0)?,
+                    username: row.get(1)?,
+                    action: row.get(2)?,
+                    record_id: row.get(3)?,
+                    animal_id: row.get(4)?,
+                    animal_name: row.get(5)?,
+                    timestamp: row.get(6)?,
+                })
+            })
+            .context("Failed to execute query for audit log")?;
+
+        let mut entries = Vec::new();
+        for entry in entry_iter {
+            entries.push(entry.context("Failed to parse audit log row")?);
+        }
+
+        Ok((entries, total))
+    }
+}
@@ -0,0 +1,207 @@
+//
+// database_service/banned_adopters.rs
+//
+// This module provides operations for managing the people staff should not
+// let adopt, and for checking adoption requests against them.
+//
+
+use super::types::BannedAdopter;
+use super::validation;
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use rusqlite::params;
+use uuid::Uuid;
+
+impl DatabaseService {
+    // ==================== BANNED_ADOPTERS TABLE OPERATIONS ====================
+
+    /// Retrieves every banned adopter, most recently added first
+    ///
+    /// # Returns
+    /// * `Result<Vec<BannedAdopter>>` - List of banned adopters or error
+    pub fn query_banned_adopters(&self) -> Result<Vec<BannedAdopter>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, name_pattern, email_pattern, tel_number_pattern, reason, added_by, created_timestamp FROM banned_adopters ORDER BY created_timestamp DESC",
+            )
+            .context("Failed to prepare query for banned adopters")?;
+
+        let banned_iter = statement
+            .query_map([], |row| {
+                Ok(BannedAdopter {
+                    id: row.get(0)?,
+                    name_pattern: row.get(1)?,
+                    email_pattern: row.get(2)?,
+                    tel_number_pattern: row.get(3)?,
+                    reason: row.get(4)?,
+                    added_by: row.get(5)?,
+                    created_timestamp: row.get(6)?,
+                })
+            })
+            .context("Failed to execute query for banned adopters")?;
+
+        let mut banned = Vec::new();
+        for entry in banned_iter {
+            banned.push(entry.context("Failed to parse banned adopter row")?);
+        }
+
+        log::debug!("Retrieved {} banned adopters", banned.len());
+        Ok(banned)
+    }
+
+    /// Inserts a new banned adopter into the database
+    ///
+    /// # Arguments
+    /// * `banned` - The entry to insert; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<String>` - The ID of the entry, or a validation error if it has no pattern or no reason
+    pub fn insert_banned_adopter(&self, banned: &BannedAdopter) -> Result<String> {
+        validation::ensure_valid(validation::validate_banned_adopter(banned))?;
+        let name_pattern = normalize_pattern(&banned.name_pattern);
+        let email_pattern = normalize_pattern(&banned.email_pattern);
+        let tel_number_pattern = normalize_pattern(&banned.tel_number_pattern);
+
+        let id = if banned.id.trim().is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            banned.id.clone()
+        };
+
+        self.connection
+            .execute(
+                "INSERT INTO banned_adopters (id, name_pattern, email_pattern, tel_number_pattern, reason, added_by, created_timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    id,
+                    name_pattern,
+                    email_pattern,
+                    tel_number_pattern,
+                    banned.reason.trim(),
+                    banned.added_by,
+                    banned.created_timestamp
+                ],
+            )
+            .context("Failed to insert banned adopter into database")?;
+
+        log::info!("{} added banned adopter with ID: {}", banned.added_by, id);
+        Ok(id)
+    }
+
+    /// Deletes a banned adopter from the database by ID
+    ///
+    /// # Arguments
+    /// * `banned_id` - The ID of the entry to delete
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the entry was found and deleted, false if not found
+    pub fn delete_banned_adopter(&self, banned_id: &str) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "DELETE FROM banned_adopters WHERE id = ?1",
+                params![banned_id],
+            )
+            .context("Failed to delete banned adopter from database")?;
+
+        if rows_affected == 1 {
+            log::info!("Removed banned adopter with ID: {}", banned_id);
+        }
+        Ok(rows_affected == 1)
+    }
+
+    /// Finds the banned adopters whose patterns match the contact details of an adoption request
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the adoption request to check
+    ///
+    /// # Returns
+    /// * `Result<Vec<BannedAdopter>>` - The matching entries, empty if the requester is not
+    ///   banned, or error if the request does not exist
+    pub fn query_banned_adopter_matches(&self, request_id: &str) -> Result<Vec<BannedAdopter>> {
+        let Some(request) = self.query_adoption_request_by_id(request_id)? else {
+            bail!("Adoption request with ID {} not found", request_id);
+        };
+
+        let tel_digits = digits(&request.tel_number);
+        let matches: Vec<BannedAdopter> = self
+            .query_banned_adopters()?
+            .into_iter()
+            .filter(|banned| {
+                banned
+                    .name_pattern
+                    .as_deref()
+                    .is_some_and(|pattern| matches_pattern(pattern, &request.name))
+                    || banned
+                        .email_pattern
+                        .as_deref()
+                        .is_some_and(|pattern| matches_pattern(pattern, &request.email))
+                    || banned
+                        .tel_number_pattern
+                        .as_deref()
+                        .is_some_and(|pattern| matches_pattern(&digits(pattern), &tel_digits))
+            })
+            .collect();
+
+        if !matches.is_empty() {
+            log::warn!(
+                "Adoption request {} matches {} banned adopters",
+                request_id,
+                matches.len()
+            );
+        }
+        Ok(matches)
+    }
+}
+
+/// Trims a pattern, treating a blank one as absent
+fn normalize_pattern(pattern: &Option<String>) -> Option<String> {
+    pattern
+        .as_deref()
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .map(str::to_string)
+}
+
+/// Keeps only the digits of a telephone number or pattern, and its wildcards
+fn digits(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '*')
+        .collect()
+}
+
+/// Checks a value against a pattern case-insensitively, where `*` matches any run of characters
+///
+/// # Arguments
+/// * `pattern` - The pattern to match
+/// * `value` - The value to check
+///
+/// # Returns
+/// * `bool` - True if the whole value matches the pattern
+fn matches_pattern(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    let value = value.trim().to_lowercase();
+    if pattern.is_empty() || value.is_empty() {
+        return false;
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if parts.len() == 1 {
+        return value == first;
+    }
+    if !value.starts_with(first) || value.len() < first.len() + last.len() {
+        return false;
+    }
+
+    // Find the middle parts in order between the fixed start and end
+    let mut rest = &value[first.len()..value.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    value.ends_with(last)
+}
@@ -0,0 +1,226 @@
+//
+// database_service/calendar.rs
+//
+// This module gathers the dated events of the shelter, such as vaccinations
+// coming due, animals due back from foster care, meet-and-greet appointments and
+// spay/neuter surgeries, so they can be shown on a calendar.
+//
+
+use super::types::{AnimalStatus, AppointmentStatus, CalendarEvent, CalendarEventKind};
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use rusqlite::params;
+
+impl DatabaseService {
+    // ==================== CALENDAR OPERATIONS ====================
+
+    /// Lists the events due within a period, in the order they are due
+    ///
+    /// Only the latest dose of each vaccine counts, and only animals still in the
+    /// care of the shelter have vaccinations due. Foster placements count until they
+    /// are ended, appointments unless they were cancelled and surgeries until they
+    /// are performed.
+    ///
+    /// # Arguments
+    /// * `from_timestamp` - Start of the period, inclusive
+    /// * `to_timestamp` - End of the period, exclusive
+    ///
+    /// # Returns
+    /// * `Result<Vec<CalendarEvent>>` - The events or error
+    pub fn query_calendar_events(
+        &self,
+        from_timestamp: i64,
+        to_timestamp: i64,
+    ) -> Result<Vec<CalendarEvent>> {
+        if from_timestamp >= to_timestamp {
+            bail!("The calendar period must end after it starts");
+        }
+
+        let mut events = self.query_vaccinations_due(from_timestamp, to_timestamp)?;
+        events.extend(self.query_foster_returns_due(from_timestamp, to_timestamp)?);
+        events.extend(self.query_appointments_booked(from_timestamp, to_timestamp)?);
+        events.extend(self.query_surgeries_booked(from_timestamp, to_timestamp)?);
+        events.sort_by(|a, b| {
+            a.timestamp
+                .cmp(&b.timestamp)
+                .then_with(|| a.animal_name.cmp(&b.animal_name))
+        });
+        Ok(events)
+    }
+
+    /// Lists the vaccinations whose next dose is due within a period
+    ///
+    /// # Arguments
+    /// * `from_timestamp` - Start of the period, inclusive
+    /// * `to_timestamp` - End of the period, exclusive
+    ///
+    /// # Returns
+    /// * `Result<Vec<CalendarEvent>>` - The events or error
+    fn query_vaccinations_due(
+        &self,
+        from_timestamp: i64,
+        to_timestamp: i64,
+    ) -> Result<Vec<CalendarEvent>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT v.id, v.animal_id, a.name, v.vaccine, v.due_timestamp FROM vaccinations v JOIN animals a ON a.id = v.animal_id
+                 WHERE v.due_timestamp >= ?1 AND v.due_timestamp < ?2 AND a.status NOT IN (?3, ?4, ?5)
+                 AND NOT EXISTS (
+                     SELECT 1 FROM vaccinations later WHERE later.animal_id = v.animal_id
+                     AND later.vaccine = v.vaccine COLLATE NOCASE
+                     AND later.administered_timestamp > v.administered_timestamp
+                 )",
+            )
+            .context("Failed to prepare query for vaccinations due")?;
+
+        let events = statement
+            .query_map(
+                params![
+                    from_timestamp,
+                    to_timestamp,
+                    AnimalStatus::Adopted,
+                    AnimalStatus::PassedAway,
+                    AnimalStatus::Transferred
+                ],
+                |row| {
+                    Ok(CalendarEvent {
+                        kind: CalendarEventKind::VaccinationDue,
+                        record_id: row.get(0)?,
+                        animal_id: row.get(1)?,
+                        animal_name: row.get(2)?,
+                        subject: row.get(3)?,
+                        timestamp: row.get(4)?,
+                    })
+                },
+            )
+            .context("Failed to execute query for vaccinations due")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse vaccination due row")?;
+
+        Ok(events)
+    }
+
+    /// Lists the foster placements that are due to end within a period
+    ///
+    /// # Arguments
+    /// * `from_timestamp` - Start of the period, inclusive
+    /// * `to_timestamp` - End of the period, exclusive
+    ///
+    /// # Returns
+    /// * `Result<Vec<CalendarEvent>>` - The events or error
+    fn query_foster_returns_due(
+        &self,
+        from_timestamp: i64,
+        to_timestamp: i64,
+    ) -> Result<Vec<CalendarEvent>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT f.id, f.animal_id, a.name, f.caregiver_name, f.end_timestamp FROM foster_placements f JOIN animals a ON a.id = f.animal_id
+                 WHERE f.ended_timestamp IS NULL AND f.end_timestamp >= ?1 AND f.end_timestamp < ?2",
+            )
+            .context("Failed to prepare query for foster returns due")?;
+
+        let events = statement
+            .query_map(params![from_timestamp, to_timestamp], |row| {
+                Ok(CalendarEvent {
+                    kind: CalendarEventKind::FosterReturnDue,
+                    record_id: row.get(0)?,
+                    animal_id: row.get(1)?,
+                    animal_name: row.get(2)?,
+                    subject: row.get(3)?,
+                    timestamp: row.get(4)?,
+                })
+            })
+            .context("Failed to execute query for foster returns due")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse foster return due row")?;
+
+        Ok(events)
+    }
+
+    /// Lists the meet-and-greet appointments booked within a period that were not
+    /// cancelled
+    ///
+    /// # Arguments
+    /// * `from_timestamp` - Start of the period, inclusive
+    /// * `to_timestamp` - End of the period, exclusive
+    ///
+    /// # Returns
+    /// * `Result<Vec<CalendarEvent>>` - The events or error
+    fn query_appointments_booked(
+        &self,
+        from_timestamp: i64,
+        to_timestamp: i64,
+    ) -> Result<Vec<CalendarEvent>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT p.id, p.animal_id, a.name, p.username, p.slot_timestamp FROM appointments p JOIN animals a ON a.id = p.animal_id
+                 WHERE p.status <> ?3 AND p.slot_timestamp >= ?1 AND p.slot_timestamp < ?2",
+            )
+            .context("Failed to prepare query for appointments booked")?;
+
+        let events = statement
+            .query_map(
+                params![from_timestamp, to_timestamp, AppointmentStatus::Cancelled],
+                |row| {
+                    Ok(CalendarEvent {
+                        kind: CalendarEventKind::Appointment,
+                        record_id: row.get(0)?,
+                        animal_id: row.get(1)?,
+                        animal_name: row.get(2)?,
+                        subject: row.get(3)?,
+                        timestamp: row.get(4)?,
+                    })
+                },
+            )
+            .context("Failed to execute query for appointments booked")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse appointment row")?;
+
+        Ok(events)
+    }
+
+    /// Lists the spay and neuter surgeries booked within a period that have not been
+    /// performed yet
+    ///
+    /// # Arguments
+    /// * `from_timestamp` - Start of the period, inclusive
+    /// * `to_timestamp` - End of the period, exclusive
+    ///
+    /// # Returns
+    /// * `Result<Vec<CalendarEvent>>` - The events or error
+    fn query_surgeries_booked(
+        &self,
+        from_timestamp: i64,
+        to_timestamp: i64,
+    ) -> Result<Vec<CalendarEvent>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT s.id, s.animal_id, a.name, COALESCE(c.name, ''), s.scheduled_timestamp FROM surgeries s JOIN animals a ON a.id = s.animal_id
+                 LEFT JOIN contacts c ON c.id = s.contact_id
+                 WHERE s.completed_timestamp IS NULL AND s.scheduled_timestamp >= ?1 AND s.scheduled_timestamp < ?2",
+            )
+            .context("Failed to prepare query for surgeries booked")?;
+
+        let events = statement
+            .query_map(params![from_timestamp, to_timestamp], |row| {
+                Ok(CalendarEvent {
+                    kind: CalendarEventKind::Surgery,
+                    record_id: row.get(0)?,
+                    animal_id: row.get(1)?,
+                    animal_name: row.get(2)?,
+                    subject: row.get(3)?,
+                    timestamp: row.get(4)?,
+                })
+            })
+            .context("Failed to execute query for surgeries booked")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse surgery row")?;
+
+        Ok(events)
+    }
+}
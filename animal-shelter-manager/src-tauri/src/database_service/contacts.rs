@@ -0,0 +1,264 @@
+//
+// database_service/contacts.rs
+//
+// This module provides operations for the directory of vets, rescues,
+// transport partners and donors the shelter works with, and for linking them
+// to the vaccinations and transfers they took part in.
+//
+
+use super::types::{Contact, ContactKind};
+use super::validation;
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use rusqlite::{params, OptionalExtension, Row};
+use uuid::Uuid;
+
+/// Columns selected when reading a contact, in the order expected by `contact_from_row`
+const CONTACT_COLUMNS: &str = "id, name, kind, tel_number, email, address, notes";
+
+/// Builds a contact from a row selected with `CONTACT_COLUMNS`
+fn contact_from_row(row: &Row) -> rusqlite::Result<Contact> {
+    Ok(Contact {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        kind: row.get(2)?,
+        tel_number: row.get(3)?,
+        email: row.get(4)?,
+        address: row.get(5)?,
+        notes: row.get(6)?,
+    })
+}
+
+impl DatabaseService {
+    // ==================== CONTACTS TABLE OPERATIONS ====================
+
+    /// Inserts a new contact into the database
+    ///
+    /// Fails with a `ValidationError` listing the invalid fields if the contact does not validate.
+    ///
+    /// # Arguments
+    /// * `contact` - The contact to insert; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<String>` - The ID of the inserted contact or error
+    pub fn insert_contact(&self, contact: &Contact) -> Result<String> {
+        validation::ensure_valid(validation::validate_contact(contact))?;
+
+        let id = if contact.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            contact.id.clone()
+        };
+
+        self.connection
+            .execute(
+                "INSERT INTO contacts (id, name, kind, tel_number, email, address, notes) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    id,
+                    contact.name.trim(),
+                    contact.kind,
+                    contact.tel_number.trim(),
+                    contact.email.trim(),
+                    contact.address.trim(),
+                    contact.notes.trim()
+                ],
+            )
+            .context("Failed to insert contact into database")?;
+
+        log::info!("Successfully inserted contact with ID: {}", id);
+        Ok(id)
+    }
+
+    /// Retrieves the contacts in the directory
+    ///
+    /// # Arguments
+    /// * `kind` - Only retrieve contacts of this kind, or every contact if None
+    ///
+    /// # Returns
+    /// * `Result<Vec<Contact>>` - Contacts ordered by name, or error
+    pub fn query_contacts(&self, kind: Option<ContactKind>) -> Result<Vec<Contact>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM contacts WHERE ?1 IS NULL OR kind = ?1 ORDER BY name COLLATE NOCASE",
+                CONTACT_COLUMNS
+            ))
+            .context("Failed to prepare query for contacts")?;
+
+        let contacts = statement
+            .query_map(params![kind], contact_from_row)
+            .context("Failed to execute query for contacts")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse contact row")?;
+
+        log::debug!("Retrieved {} contacts", contacts.len());
+        Ok(contacts)
+    }
+
+    /// Retrieves a contact by its ID
+    ///
+    /// # Arguments
+    /// * `contact_id` - The ID of the contact
+    ///
+    /// # Returns
+    /// * `Result<Option<Contact>>` - The contact, or None if not found
+    pub fn query_contact_by_id(&self, contact_id: &str) -> Result<Option<Contact>> {
+        self.connection
+            .query_row(
+                &format!("SELECT {} FROM contacts WHERE id = ?1", CONTACT_COLUMNS),
+                params![contact_id],
+                contact_from_row,
+            )
+            .optional()
+            .context("Failed to read contact")
+    }
+
+    /// Updates the details of a contact
+    ///
+    /// Fails with a `ValidationError` listing the invalid fields if the contact does not validate.
+    ///
+    /// # Arguments
+    /// * `contact` - The updated contact information
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the contact was found and updated, false if not found
+    pub fn update_contact(&self, contact: &Contact) -> Result<bool> {
+        validation::ensure_valid(validation::validate_contact(contact))?;
+
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE contacts SET name = ?2, kind = ?3, tel_number = ?4, email = ?5, address = ?6, notes = ?7 WHERE id = ?1",
+                params![
+                    contact.id,
+                    contact.name.trim(),
+                    contact.kind,
+                    contact.tel_number.trim(),
+                    contact.email.trim(),
+                    contact.address.trim(),
+                    contact.notes.trim()
+                ],
+            )
+            .context("Failed to update contact in database")?;
+
+        log::info!(
+            "Updated contact with ID: {}, found: {}",
+            contact.id,
+            rows_affected == 1
+        );
+        Ok(rows_affected == 1)
+    }
+
+    /// Deletes a contact, unlinking the vaccinations, transfers, surgeries and donations it
+    /// took part in
+    ///
+    /// # Arguments
+    /// * `contact_id` - The ID of the contact to delete
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the contact was found and deleted, false if not found
+    pub fn delete_contact(&self, contact_id: &str) -> Result<bool> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for contact deletion")?;
+
+        transaction
+            .execute(
+                "UPDATE vaccinations SET contact_id = NULL WHERE contact_id = ?1",
+                params![contact_id],
+            )
+            .context("Failed to unlink vaccinations from contact")?;
+        transaction
+            .execute(
+                "UPDATE transfers SET contact_id = NULL WHERE contact_id = ?1",
+                params![contact_id],
+            )
+            .context("Failed to unlink transfers from contact")?;
+        transaction
+            .execute(
+                "UPDATE surgeries SET contact_id = NULL WHERE contact_id = ?1",
+                params![contact_id],
+            )
+            .context("Failed to unlink surgeries from contact")?;
+        transaction
+            .execute(
+                "UPDATE donations SET contact_id = NULL WHERE contact_id = ?1",
+                params![contact_id],
+            )
+            .context("Failed to unlink donations from contact")?;
+        let rows_affected = transaction
+            .execute("DELETE FROM contacts WHERE id = ?1", params![contact_id])
+            .context("Failed to delete contact from database")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit contact deletion")?;
+        log::info!(
+            "Deleted contact with ID: {}, found: {}",
+            contact_id,
+            rows_affected == 1
+        );
+        Ok(rows_affected == 1)
+    }
+
+    /// Links a recorded vaccination to the veterinarian who gave it
+    ///
+    /// # Arguments
+    /// * `vaccination_id` - The ID of the vaccination
+    /// * `contact_id` - The ID of the contact, or None to unlink the vaccination
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the vaccination was found and linked, false if not found
+    pub fn link_vaccination_to_contact(
+        &self,
+        vaccination_id: &str,
+        contact_id: Option<&str>,
+    ) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE vaccinations SET contact_id = ?2 WHERE id = ?1",
+                params![vaccination_id, contact_id],
+            )
+            .context("Failed to link vaccination to contact")?;
+
+        log::info!(
+            "Linked vaccination with ID: {} to contact: {:?}, found: {}",
+            vaccination_id,
+            contact_id,
+            rows_affected == 1
+        );
+        Ok(rows_affected == 1)
+    }
+
+    /// Links a recorded transfer to the contact at the partner organization or transport partner
+    ///
+    /// # Arguments
+    /// * `transfer_id` - The ID of the transfer
+    /// * `contact_id` - The ID of the contact, or None to unlink the transfer
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the transfer was found and linked, false if not found
+    pub fn link_transfer_to_contact(
+        &self,
+        transfer_id: &str,
+        contact_id: Option<&str>,
+    ) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE transfers SET contact_id = ?2 WHERE id = ?1",
+                params![transfer_id, contact_id],
+            )
+            .context("Failed to link transfer to contact")?;
+
+        log::info!(
+            "Linked transfer with ID: {} to contact: {:?}, found: {}",
+            transfer_id,
+            contact_id,
+            rows_affected == 1
+        );
+        Ok(rows_affected == 1)
+    }
+}
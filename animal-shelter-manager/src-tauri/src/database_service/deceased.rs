@@ -0,0 +1,167 @@
+//
+// database_service/deceased.rs
+//
+// This module provides operations for recording how animals passed away or
+// were euthanized while in the shelter's care, for legal record-keeping.
+//
+
+use super::types::{AnimalStatus, DeceasedRecord, RecordDeathResult, RequestStatus};
+use super::validation;
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use uuid::Uuid;
+
+impl DatabaseService {
+    // ==================== DECEASED_RECORDS TABLE OPERATIONS ====================
+
+    /// Retrieves the record of how a specific animal passed away
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to retrieve the record for
+    ///
+    /// # Returns
+    /// * `Result<Option<DeceasedRecord>>` - The record, or None if the animal has not passed away
+    pub fn query_deceased_record_by_animal_id(
+        &self,
+        animal_id: &str,
+    ) -> Result<Option<DeceasedRecord>> {
+        self.connection
+            .query_row(
+                "SELECT id, animal_id, death_timestamp, cause, authorized_by, notes FROM deceased_records WHERE animal_id = ?1",
+                params![animal_id],
+                |row| {
+                    Ok(DeceasedRecord {
+                        id: row.get(0)?,
+                        animal_id: row.get(1)?,
+                        death_timestamp: row.get(2)?,
+                        cause: row.get(3)?,
+                        authorized_by: row.get(4)?,
+                        notes: row.get(5)?,
+                    })
+                },
+            )
+            .optional()
+            .context("Failed to query deceased record by animal ID")
+    }
+
+    /// Records that an animal in the shelter's care passed away and marks it as passed away
+    ///
+    /// Its ongoing foster placement and kennel assignment end, and its pending adoption
+    /// requests are rejected. Fails with a `ValidationError` listing the invalid fields if
+    /// the record does not validate.
+    ///
+    /// # Arguments
+    /// * `record` - The record to store; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<RecordDeathResult>` - Success, or why the death could not be recorded
+    pub fn record_death(&self, record: &DeceasedRecord) -> Result<RecordDeathResult> {
+        validation::ensure_valid(validation::validate_deceased_record(record))?;
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for deceased record")?;
+
+        // Only animals the shelter is still caring for can be recorded here
+        let status: Option<AnimalStatus> = transaction
+            .query_row(
+                "SELECT status FROM animals WHERE id = ?1",
+                params![record.animal_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to read animal status")?;
+        match status {
+            None => return Ok(RecordDeathResult::AnimalNotFound),
+            Some(AnimalStatus::Available | AnimalStatus::Requested | AnimalStatus::Fostered) => {}
+            Some(AnimalStatus::PassedAway) => return Ok(RecordDeathResult::AlreadyDeceased),
+            Some(_) => return Ok(RecordDeathResult::AnimalNotInCare),
+        }
+
+        let id = if record.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            record.id.clone()
+        };
+
+        transaction
+            .execute(
+                "INSERT INTO deceased_records (id, animal_id, death_timestamp, cause, authorized_by, notes) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    id,
+                    record.animal_id,
+                    record.death_timestamp,
+                    record.cause.trim(),
+                    record.authorized_by.trim(),
+                    record.notes.trim()
+                ],
+            )
+            .context("Failed to insert deceased record into database")?;
+
+        transaction
+            .execute(
+                "UPDATE animals SET status = ?2, version = version + 1 WHERE id = ?1",
+                params![record.animal_id, AnimalStatus::PassedAway],
+            )
+            .context("Failed to mark animal as passed away")?;
+
+        // The animal no longer occupies a foster home or a kennel
+        transaction
+            .execute(
+                "UPDATE foster_placements SET ended_timestamp = ?2 WHERE animal_id = ?1 AND ended_timestamp IS NULL",
+                params![record.animal_id, record.death_timestamp],
+            )
+            .context("Failed to end foster placement of animal")?;
+        transaction
+            .execute(
+                "UPDATE kennel_assignments SET removed_timestamp = ?2 WHERE animal_id = ?1 AND removed_timestamp IS NULL",
+                params![record.animal_id, record.death_timestamp],
+            )
+            .context("Failed to end kennel assignment of animal")?;
+
+        // Nobody can adopt the animal any more
+        let rejected_ids = transaction
+            .prepare("SELECT id FROM adoption_requests WHERE animal_id = ?1 AND status = ?2")
+            .context("Failed to prepare query for pending adoption requests")?
+            .query_map(params![record.animal_id, RequestStatus::Pending], |row| {
+                row.get::<_, String>(0)
+            })
+            .context("Failed to execute query for pending adoption requests")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse adoption request row")?;
+        transaction
+            .execute(
+                "UPDATE adoption_requests SET status = ?3 WHERE animal_id = ?1 AND status = ?2",
+                params![
+                    record.animal_id,
+                    RequestStatus::Pending,
+                    RequestStatus::Rejected
+                ],
+            )
+            .context("Failed to reject pending adoption requests")?;
+        // Their applicants get the usual rejection email, and webhooks hear of it
+        let now = Utc::now().timestamp();
+        for rejected_id in &rejected_ids {
+            self.queue_request_status_change(
+                rejected_id,
+                &record.animal_id,
+                &RequestStatus::Rejected,
+                now,
+            )?;
+        }
+
+        transaction
+            .commit()
+            .context("Failed to commit deceased record")?;
+
+        log::info!(
+            "Recorded death of animal with ID: {} and rejected {} pending request(s)",
+            record.animal_id,
+            rejected_ids.len()
+        );
+        Ok(RecordDeathResult::Success)
+    }
+}
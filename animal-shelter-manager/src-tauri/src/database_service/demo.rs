@@ -0,0 +1,419 @@
+//
+// database_service/demo.rs
+//
+// This module provides realistic demo data for UI development, screenshots and
+// training new staff, so none of that needs to happen on real shelter records.
+//
+
+use super::types::{
+    AdoptionRequest, Animal, AnimalStatus, ApprovalRequirements, DemoDataSummary, RequestStatus,
+};
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, Utc};
+use rusqlite::params;
+
+/// Username of the demo staff account
+pub const DEMO_STAFF_USERNAME: &str = "demo.staff";
+
+/// Password shared by every demo account
+pub const DEMO_PASSWORD: &str = "demo1234";
+
+/// Number of seconds in a day, for timestamps relative to now
+const DAY_SECONDS: i64 = 24 * 60 * 60;
+
+/// Number of days between an approved demo request being filed and the adoption
+const DAYS_TO_ADOPTION: i64 = 5;
+
+/// A customer who files demo adoption requests
+pub struct DemoCustomer {
+    /// Username of the customer's account
+    pub username: &'static str,
+    /// Full name of the customer
+    pub name: &'static str,
+    /// Telephone number of the customer
+    pub tel_number: &'static str,
+    /// Address of the customer
+    pub address: &'static str,
+    /// Occupation of the customer
+    pub occupation: &'static str,
+    /// Annual income of the customer
+    pub annual_income: &'static str,
+    /// Number of people in the customer's household
+    pub num_people: i32,
+    /// Number of children in the customer's household
+    pub num_children: i32,
+    /// Country of residence of the customer
+    pub country: &'static str,
+}
+
+/// An animal admitted to the demo shelter
+struct DemoAnimal {
+    /// Name of the animal
+    name: &'static str,
+    /// Species of the animal
+    specie: &'static str,
+    /// Breed of the animal
+    breed: &'static str,
+    /// Sex of the animal
+    sex: &'static str,
+    /// Age of the animal in years
+    age: i32,
+    /// Birth month of the animal, from 1 to 12
+    birth_month: i32,
+    /// Whether the animal is neutered
+    neutered: bool,
+    /// Number of days since the animal was admitted
+    admitted_days_ago: i64,
+    /// Appearance description of the animal
+    appearance: &'static str,
+    /// Bio & Characteristics of the animal
+    bio: &'static str,
+}
+
+/// An adoption request filed in the demo
+struct DemoRequest {
+    /// Name of the requested animal
+    animal_name: &'static str,
+    /// Username of the customer filing the request
+    username: &'static str,
+    /// Number of days since the request was filed
+    filed_days_ago: i64,
+    /// Whether the request is approved
+    approved: bool,
+}
+
+/// Customers who file the demo adoption requests
+pub const DEMO_CUSTOMERS: &[DemoCustomer] = &[
+    DemoCustomer {
+        username: "alice.demo",
+        name: "Alice Tan",
+        tel_number: "081-234-5678",
+        address: "12 Sukhumvit Road, Bangkok",
+        occupation: "Teacher",
+        annual_income: "42000",
+        num_people: 3,
+        num_children: 1,
+        country: "Thailand",
+    },
+    DemoCustomer {
+        username: "bob.demo",
+        name: "Bob Nguyen",
+        tel_number: "+84 90 123 4567",
+        address: "45 Le Loi Street, Ho Chi Minh City",
+        occupation: "Nurse",
+        annual_income: "38000",
+        num_people: 2,
+        num_children: 0,
+        country: "Vietnam",
+    },
+    DemoCustomer {
+        username: "carol.demo",
+        name: "Carol Smith",
+        tel_number: "+44 20 7946 0958",
+        address: "8 Baker Street, London",
+        occupation: "Architect",
+        annual_income: "65000",
+        num_people: 4,
+        num_children: 2,
+        country: "United Kingdom",
+    },
+    DemoCustomer {
+        username: "dave.demo",
+        name: "Dave Suzuki",
+        tel_number: "+81 3 1234 5678",
+        address: "3-1 Shibuya, Tokyo",
+        occupation: "Chef",
+        annual_income: "47000",
+        num_people: 1,
+        num_children: 0,
+        country: "Japan",
+    },
+];
+
+/// Animals admitted to the demo shelter
+const DEMO_ANIMALS: &[DemoAnimal] = &[
+    DemoAnimal {
+        name: "Luna",
+        specie: "Dog",
+        breed: "Golden Retriever",
+        sex: "Female",
+        age: 3,
+        birth_month: 4,
+        neutered: true,
+        admitted_days_ago: 40,
+        appearance: "Golden coat with a white patch on her chest",
+        bio: "Gentle and patient, Luna loves children and long walks.",
+    },
+    DemoAnimal {
+        name: "Milo",
+        specie: "Cat",
+        breed: "Siamese",
+        sex: "Male",
+        age: 2,
+        birth_month: 9,
+        neutered: true,
+        admitted_days_ago: 25,
+        appearance: "Cream body with dark brown points and blue eyes",
+        bio: "Talkative and curious, Milo follows people from room to room.",
+    },
+    DemoAnimal {
+        name: "Bella",
+        specie: "Dog",
+        breed: "Beagle",
+        sex: "Female",
+        age: 5,
+        birth_month: 1,
+        neutered: true,
+        admitted_days_ago: 90,
+        appearance: "Tricolor coat with long floppy ears",
+        bio: "Bella is food-motivated and quick to learn new tricks.",
+    },
+    DemoAnimal {
+        name: "Oliver",
+        specie: "Cat",
+        breed: "Maine Coon",
+        sex: "Male",
+        age: 4,
+        birth_month: 6,
+        neutered: true,
+        admitted_days_ago: 60,
+        appearance: "Long brown tabby coat with tufted ears",
+        bio: "A calm giant who enjoys being brushed and napping in the sun.",
+    },
+    DemoAnimal {
+        name: "Max",
+        specie: "Dog",
+        breed: "German Shepherd",
+        sex: "Male",
+        age: 6,
+        birth_month: 11,
+        neutered: false,
+        admitted_days_ago: 15,
+        appearance: "Black and tan coat with upright ears",
+        bio: "Loyal and protective, Max would suit an experienced owner.",
+    },
+    DemoAnimal {
+        name: "Coco",
+        specie: "Rabbit",
+        breed: "Holland Lop",
+        sex: "Female",
+        age: 1,
+        birth_month: 3,
+        neutered: false,
+        admitted_days_ago: 10,
+        appearance: "Chocolate fur with lop ears",
+        bio: "Coco is shy at first but loves fresh greens and gentle strokes.",
+    },
+    DemoAnimal {
+        name: "Simba",
+        specie: "Cat",
+        breed: "Domestic Shorthair",
+        sex: "Male",
+        age: 1,
+        birth_month: 7,
+        neutered: true,
+        admitted_days_ago: 5,
+        appearance: "Orange tabby with amber eyes",
+        bio: "Playful and bold, Simba gets along well with other cats.",
+    },
+    DemoAnimal {
+        name: "Daisy",
+        specie: "Dog",
+        breed: "Poodle",
+        sex: "Female",
+        age: 8,
+        birth_month: 2,
+        neutered: true,
+        admitted_days_ago: 120,
+        appearance: "Curly apricot coat",
+        bio: "A senior lady who prefers quiet homes and short strolls.",
+    },
+    DemoAnimal {
+        name: "Rocky",
+        specie: "Dog",
+        breed: "Labrador Retriever",
+        sex: "Male",
+        age: 2,
+        birth_month: 5,
+        neutered: true,
+        admitted_days_ago: 30,
+        appearance: "Short black coat and a thick otter tail",
+        bio: "Rocky loves swimming and fetching anything you throw.",
+    },
+    DemoAnimal {
+        name: "Nala",
+        specie: "Cat",
+        breed: "Persian",
+        sex: "Female",
+        age: 3,
+        birth_month: 8,
+        neutered: true,
+        admitted_days_ago: 45,
+        appearance: "Long white coat with copper eyes",
+        bio: "Nala enjoys a quiet lap and needs daily grooming.",
+    },
+];
+
+/// Adoption requests filed in the demo; approving a request rejects the other
+/// pending requests for the same animal
+const DEMO_REQUESTS: &[DemoRequest] = &[
+    DemoRequest {
+        animal_name: "Luna",
+        username: "alice.demo",
+        filed_days_ago: 6,
+        approved: false,
+    },
+    DemoRequest {
+        animal_name: "Luna",
+        username: "bob.demo",
+        filed_days_ago: 3,
+        approved: false,
+    },
+    DemoRequest {
+        animal_name: "Milo",
+        username: "carol.demo",
+        filed_days_ago: 2,
+        approved: false,
+    },
+    DemoRequest {
+        animal_name: "Bella",
+        username: "alice.demo",
+        filed_days_ago: 20,
+        approved: true,
+    },
+    DemoRequest {
+        animal_name: "Bella",
+        username: "dave.demo",
+        filed_days_ago: 18,
+        approved: false,
+    },
+    DemoRequest {
+        animal_name: "Oliver",
+        username: "dave.demo",
+        filed_days_ago: 12,
+        approved: true,
+    },
+    DemoRequest {
+        animal_name: "Rocky",
+        username: "bob.demo",
+        filed_days_ago: 1,
+        approved: false,
+    },
+];
+
+impl DatabaseService {
+    // ==================== DEMO DATA OPERATIONS ====================
+
+    /// Fills an empty database with demo animals and adoption requests from the demo
+    /// customers, approving some of the requests so adopted animals show up as well
+    ///
+    /// # Returns
+    /// * `Result<DemoDataSummary>` - How many records were created, or an error if the
+    ///   database already holds animals
+    pub fn seed_demo_data(&self) -> Result<DemoDataSummary> {
+        let has_animals: bool = self
+            .connection
+            .query_row("SELECT EXISTS (SELECT 1 FROM animals)", [], |row| {
+                row.get(0)
+            })
+            .context("Failed to check for existing animals")?;
+        if has_animals {
+            bail!("Demo data can only be seeded into an empty database");
+        }
+
+        let now = Utc::now().timestamp();
+        let this_year = Utc::now().year();
+
+        for (index, demo) in DEMO_ANIMALS.iter().enumerate() {
+            self.insert_animal(&Animal {
+                id: format!("demo-animal-{}", index + 1),
+                name: demo.name.to_string(),
+                specie: demo.specie.to_string(),
+                breed: demo.breed.to_string(),
+                sex: demo.sex.to_string(),
+                birth_month: Some(demo.birth_month),
+                birth_year: Some(this_year - demo.age),
+                neutered: demo.neutered,
+                admission_timestamp: now - demo.admitted_days_ago * DAY_SECONDS,
+                status: AnimalStatus::Available,
+                image_path: None,
+                appearance: demo.appearance.to_string(),
+                bio: demo.bio.to_string(),
+                version: 1,
+                microchip_number: None,
+                location_id: None,
+                good_with_kids: None,
+                good_with_dogs: None,
+                good_with_cats: None,
+                special_needs: None,
+                size: None,
+                color: None,
+            })
+            .with_context(|| format!("Failed to insert demo animal {}", demo.name))?;
+        }
+
+        let mut approved = Vec::new();
+        for (index, demo) in DEMO_REQUESTS.iter().enumerate() {
+            let Some(animal_index) = DEMO_ANIMALS
+                .iter()
+                .position(|animal| animal.name == demo.animal_name)
+            else {
+                bail!("Demo request refers to unknown animal {}", demo.animal_name);
+            };
+            let Some(customer) = DEMO_CUSTOMERS
+                .iter()
+                .find(|customer| customer.username == demo.username)
+            else {
+                bail!("Demo request refers to unknown customer {}", demo.username);
+            };
+
+            let id = format!("demo-request-{}", index + 1);
+            self.insert_adoption_request(&AdoptionRequest {
+                id: id.clone(),
+                animal_id: format!("demo-animal-{}", animal_index + 1),
+                username: customer.username.to_string(),
+                name: customer.name.to_string(),
+                email: format!("{}@example.com", customer.username),
+                tel_number: customer.tel_number.to_string(),
+                address: customer.address.to_string(),
+                occupation: customer.occupation.to_string(),
+                annual_income: customer.annual_income.to_string(),
+                num_people: customer.num_people,
+                num_children: customer.num_children,
+                request_timestamp: now - demo.filed_days_ago * DAY_SECONDS,
+                adoption_timestamp: 0,
+                status: RequestStatus::Pending,
+                country: customer.country.to_string(),
+                co_applicant: None,
+            })
+            .with_context(|| format!("Failed to insert demo request {}", id))?;
+            if demo.approved {
+                approved.push((id, demo.filed_days_ago));
+            }
+        }
+
+        for (id, filed_days_ago) in &approved {
+            self.approve_adoption_request(id, true, None, &ApprovalRequirements::default())
+                .with_context(|| format!("Failed to approve demo request {}", id))?;
+
+            // Make the adoption look like it happened a few days after the request
+            self.connection
+                .execute(
+                    "UPDATE adoption_requests SET adoption_timestamp = ?2 WHERE id = ?1",
+                    params![id, now - (filed_days_ago - DAYS_TO_ADOPTION) * DAY_SECONDS],
+                )
+                .context("Failed to backdate demo adoption")?;
+        }
+
+        log::info!(
+            "Seeded demo data with {} animals and {} adoption requests",
+            DEMO_ANIMALS.len(),
+            DEMO_REQUESTS.len()
+        );
+        Ok(DemoDataSummary {
+            animal_count: DEMO_ANIMALS.len(),
+            request_count: DEMO_REQUESTS.len(),
+        })
+    }
+}
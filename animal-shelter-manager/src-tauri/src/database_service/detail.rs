@@ -0,0 +1,67 @@
+//
+// database_service/detail.rs
+//
+// This module provides a combined view of an animal and its related records
+// for the animal detail page.
+//
+
+use super::types::{AdoptionRequestView, AnimalDetail, NoteCategory};
+use super::DatabaseService;
+use anyhow::Result;
+use chrono::{Datelike, Utc};
+
+impl DatabaseService {
+    // ==================== ANIMAL DETAIL OPERATIONS ====================
+
+    /// Retrieves an animal together with its adoption requests, latest medical note and age,
+    /// and for staff, how it passed away
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to retrieve
+    /// * `include_internal` - Whether the latest medical note may be a staff-only note, and
+    ///   whether to include the deceased record
+    ///
+    /// # Returns
+    /// * `Result<Option<AnimalDetail>>` - The animal's details or None if not found
+    pub fn query_animal_detail(
+        &self,
+        animal_id: &str,
+        include_internal: bool,
+    ) -> Result<Option<AnimalDetail>> {
+        let Some(animal) = self.query_animal_by_id(animal_id)? else {
+            return Ok(None);
+        };
+
+        let adoption_requests = self
+            .query_adoption_requests_by_animal_id(animal_id)?
+            .into_iter()
+            .map(|request| AdoptionRequestView::Full(Box::new(request)))
+            .collect();
+        let latest_medical_note = self
+            .query_animal_notes_by_animal_id(animal_id, include_internal)?
+            .into_iter()
+            .find(|note| note.category == NoteCategory::Medical);
+
+        let now = Utc::now();
+        let age_months = match (animal.birth_month, animal.birth_year) {
+            (Some(month), Some(year)) => {
+                Some((now.year() - year) * 12 + now.month() as i32 - month)
+            }
+            _ => None,
+        };
+
+        let deceased_record = if include_internal {
+            self.query_deceased_record_by_animal_id(animal_id)?
+        } else {
+            None
+        };
+
+        Ok(Some(AnimalDetail {
+            animal,
+            adoption_requests,
+            latest_medical_note,
+            age_months,
+            deceased_record,
+        }))
+    }
+}
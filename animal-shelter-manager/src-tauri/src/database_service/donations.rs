@@ -0,0 +1,233 @@
+//
+// database_service/donations.rs
+//
+// This module provides operations for the donations made to the shelter, the
+// numbered receipts issued for them and the yearly statements donors use for
+// their tax returns. Receipts are numbered from a sequence of their own, kept
+// alongside those of the invoices and receipts for adoption fees.
+//
+
+use super::payments::next_document_number;
+use super::statistics::month_bounds;
+use super::types::{Donation, DonorStatement};
+use super::validation;
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use uuid::Uuid;
+
+/// Name of the sequence donation receipts are numbered from
+const DONATION_RECEIPT_SEQUENCE: &str = "donation-receipt";
+
+/// Columns selected when reading a donation, in the order expected by `donation_from_row`
+const DONATION_COLUMNS: &str =
+    "id, contact_id, donor_name, amount, donation_timestamp, notes, receipt_number";
+
+/// Builds a donation from a row selected with `DONATION_COLUMNS`
+fn donation_from_row(row: &Row) -> rusqlite::Result<Donation> {
+    Ok(Donation {
+        id: row.get(0)?,
+        contact_id: row.get(1)?,
+        donor_name: row.get(2)?,
+        amount: row.get(3)?,
+        donation_timestamp: row.get(4)?,
+        notes: row.get(5)?,
+        receipt_number: row.get(6)?,
+    })
+}
+
+/// Reads a donation by its ID
+///
+/// # Arguments
+/// * `connection` - The connection, or transaction, to read with
+/// * `donation_id` - The ID of the donation
+///
+/// # Returns
+/// * `Result<Option<Donation>>` - The donation, or None if not found
+fn read_donation(connection: &Connection, donation_id: &str) -> Result<Option<Donation>> {
+    connection
+        .query_row(
+            &format!("SELECT {} FROM donations WHERE id = ?1", DONATION_COLUMNS),
+            params![donation_id],
+            donation_from_row,
+        )
+        .optional()
+        .context("Failed to read donation")
+}
+
+impl DatabaseService {
+    // ==================== DONATIONS TABLE OPERATIONS ====================
+
+    /// Records a donation made by a donor in the contact directory
+    ///
+    /// The donor's name is copied from their contact, and the donation is recorded
+    /// without a receipt number, whatever it holds. Fails with a `ValidationError` if
+    /// the amount is not positive or the date is not valid.
+    ///
+    /// # Arguments
+    /// * `donation` - The donation to record; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<Option<String>>` - The ID of the recorded donation, or None if it has no
+    ///   donor or the donor's contact does not exist
+    pub fn insert_donation(&self, donation: &Donation) -> Result<Option<String>> {
+        validation::ensure_valid(validation::validate_donation(donation))?;
+        let Some(contact_id) = &donation.contact_id else {
+            return Ok(None);
+        };
+        let Some(donor) = self.query_contact_by_id(contact_id)? else {
+            return Ok(None);
+        };
+
+        let id = if donation.id.trim().is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            donation.id.clone()
+        };
+        self.connection
+            .execute(
+                "INSERT INTO donations (id, contact_id, donor_name, amount, donation_timestamp, notes, receipt_number) VALUES (?1, ?2, ?3, ?4, ?5, ?6, NULL)",
+                params![
+                    id,
+                    contact_id,
+                    donor.name,
+                    donation.amount,
+                    donation.donation_timestamp,
+                    donation.notes.trim()
+                ],
+            )
+            .context("Failed to insert donation into database")?;
+
+        log::info!(
+            "Recorded donation {} from contact with ID: {}",
+            id,
+            contact_id
+        );
+        Ok(Some(id))
+    }
+
+    /// Retrieves the donations made by a donor
+    ///
+    /// # Arguments
+    /// * `contact_id` - The ID of the donor's contact
+    ///
+    /// # Returns
+    /// * `Result<Vec<Donation>>` - Donations, most recent first
+    pub fn query_donations(&self, contact_id: &str) -> Result<Vec<Donation>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM donations WHERE contact_id = ?1 ORDER BY donation_timestamp DESC",
+                DONATION_COLUMNS
+            ))
+            .context("Failed to prepare query for donations")?;
+        let donations = statement
+            .query_map(params![contact_id], donation_from_row)
+            .context("Failed to execute query for donations")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse donation row")?;
+
+        log::debug!(
+            "Retrieved {} donations from contact: {}",
+            donations.len(),
+            contact_id
+        );
+        Ok(donations)
+    }
+
+    /// Numbers the receipt of a donation, taking the next donation receipt number
+    ///
+    /// A donation keeps the number it was given, so generating its receipt again prints
+    /// the same number.
+    ///
+    /// # Arguments
+    /// * `donation_id` - The ID of the donation
+    ///
+    /// # Returns
+    /// * `Result<Option<Donation>>` - The donation with its receipt number, or None if not
+    ///   found
+    pub fn issue_donation_receipt(&self, donation_id: &str) -> Result<Option<Donation>> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for donation receipt")?;
+
+        let Some(donation) = read_donation(&transaction, donation_id)? else {
+            return Ok(None);
+        };
+        if donation.receipt_number.is_some() {
+            return Ok(Some(donation));
+        }
+
+        let number = next_document_number(&transaction, DONATION_RECEIPT_SEQUENCE)?;
+        transaction
+            .execute(
+                "UPDATE donations SET receipt_number = ?2 WHERE id = ?1",
+                params![donation_id, number],
+            )
+            .context("Failed to number donation receipt")?;
+        let donation = read_donation(&transaction, donation_id)?;
+
+        transaction
+            .commit()
+            .context("Failed to commit donation receipt number")?;
+
+        log::info!(
+            "Issued donation receipt {} for donation with ID: {}",
+            Donation::format_receipt_number(number),
+            donation_id
+        );
+        Ok(donation)
+    }
+
+    /// Sums the donations a donor made during a year
+    ///
+    /// # Arguments
+    /// * `contact_id` - The ID of the donor's contact
+    /// * `year` - The year to sum the donations of
+    ///
+    /// # Returns
+    /// * `Result<Option<DonorStatement>>` - The donations of the year and their total, or
+    ///   None if the donor's contact does not exist
+    pub fn query_donor_statement(
+        &self,
+        contact_id: &str,
+        year: i32,
+    ) -> Result<Option<DonorStatement>> {
+        let Some(donor) = self.query_contact_by_id(contact_id)? else {
+            return Ok(None);
+        };
+        let (from_timestamp, _) = month_bounds(year, 1)?;
+        let (_, to_timestamp) = month_bounds(year, 12)?;
+
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM donations WHERE contact_id = ?1 AND donation_timestamp >= ?2 AND donation_timestamp < ?3 ORDER BY donation_timestamp",
+                DONATION_COLUMNS
+            ))
+            .context("Failed to prepare query for donor statement")?;
+        let donations = statement
+            .query_map(
+                params![contact_id, from_timestamp, to_timestamp],
+                donation_from_row,
+            )
+            .context("Failed to execute query for donor statement")?
+            .collect::<rusqlite::Result<Vec<Donation>>>()
+            .context("Failed to parse donation row")?;
+        let total = donations.iter().map(|donation| donation.amount).sum();
+
+        log::debug!(
+            "Summed {} donations from contact {} in {}",
+            donations.len(),
+            contact_id,
+            year
+        );
+        Ok(Some(DonorStatement {
+            donor,
+            year,
+            donations,
+            total,
+        }))
+    }
+}
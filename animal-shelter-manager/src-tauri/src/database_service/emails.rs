@@ -0,0 +1,212 @@
+//
+// database_service/emails.rs
+//
+// This module queues the emails telling applicants their adoption request was
+// approved or rejected, and keeps a log of every attempt to send them.
+//
+
+use super::types::{EmailMessage, EmailStatus, RequestStatus};
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use rusqlite::{params, OptionalExtension, Row};
+use uuid::Uuid;
+
+/// Columns selected when reading an email, in the order expected by `email_from_row`
+const EMAIL_COLUMNS: &str = "id, request_id, request_status, recipient, recipient_name, animal_name, subject, body, status, attempts, error, created_timestamp, attempted_timestamp";
+
+impl DatabaseService {
+    // ==================== EMAILS TABLE OPERATIONS ====================
+
+    /// Retrieves the send log of every email, most recent first
+    ///
+    /// # Returns
+    /// * `Result<Vec<EmailMessage>>` - List of emails or error
+    pub fn query_emails(&self) -> Result<Vec<EmailMessage>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM emails ORDER BY created_timestamp DESC",
+                EMAIL_COLUMNS
+            ))
+            .context("Failed to prepare query for emails")?;
+
+        let emails = statement
+            .query_map([], email_from_row)
+            .context("Failed to execute query for emails")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse email row")?;
+
+        log::debug!("Retrieved {} emails", emails.len());
+        Ok(emails)
+    }
+
+    /// Retrieves a specific email by its ID
+    ///
+    /// # Arguments
+    /// * `email_id` - The ID of the email to retrieve
+    ///
+    /// # Returns
+    /// * `Result<Option<EmailMessage>>` - The email if found, None if not found, or error
+    pub fn query_email_by_id(&self, email_id: &str) -> Result<Option<EmailMessage>> {
+        self.connection
+            .query_row(
+                &format!("SELECT {} FROM emails WHERE id = ?1", EMAIL_COLUMNS),
+                params![email_id],
+                email_from_row,
+            )
+            .optional()
+            .context(format!("Failed to query email with ID: {}", email_id))
+    }
+
+    /// Retrieves the emails waiting to be sent, oldest first
+    ///
+    /// # Returns
+    /// * `Result<Vec<EmailMessage>>` - The pending emails or error
+    pub fn query_pending_emails(&self) -> Result<Vec<EmailMessage>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM emails WHERE status = ?1 ORDER BY created_timestamp",
+                EMAIL_COLUMNS
+            ))
+            .context("Failed to prepare query for pending emails")?;
+
+        let emails = statement
+            .query_map(params![EmailStatus::Pending], email_from_row)
+            .context("Failed to execute query for pending emails")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse pending email row")?;
+
+        Ok(emails)
+    }
+
+    /// Records the outcome of an attempt to send an email
+    ///
+    /// Failed emails are not retried automatically, since the usual causes, such as
+    /// wrong SMTP settings or a mistyped address, need fixing first; staff resend them.
+    ///
+    /// # Arguments
+    /// * `email_id` - The ID of the email that was attempted
+    /// * `subject` - The subject the email was sent with, None to keep the last one
+    /// * `body` - The body the email was sent with, None to keep the last one
+    /// * `error` - Why the attempt failed, or None if the email was sent
+    /// * `attempted_timestamp` - The timestamp of the attempt
+    ///
+    /// # Returns
+    /// * `Result<EmailStatus>` - The status of the email after the attempt
+    pub fn record_email_attempt(
+        &self,
+        email_id: &str,
+        subject: Option<&str>,
+        body: Option<&str>,
+        error: Option<&str>,
+        attempted_timestamp: i64,
+    ) -> Result<EmailStatus> {
+        let status = match error {
+            None => EmailStatus::Sent,
+            Some(_) => EmailStatus::Failed,
+        };
+
+        self.connection
+            .execute(
+                "UPDATE emails SET subject = COALESCE(?2, subject), body = COALESCE(?3, body), status = ?4, attempts = attempts + 1, error = ?5, attempted_timestamp = ?6 WHERE id = ?1",
+                params![email_id, subject, body, status, error, attempted_timestamp],
+            )
+            .context("Failed to record email attempt")?;
+
+        log::debug!("Email {} is {}", email_id, status);
+        Ok(status)
+    }
+
+    /// Queues the email telling an applicant their adoption request was approved or
+    /// rejected, to be sent by the email dispatcher; runs within the caller's
+    /// transaction, if any
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the adoption request
+    /// * `status` - The new status of the request
+    /// * `timestamp` - The timestamp of the change
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if an email was queued, false if the status does not
+    ///   call for one or the applicant has no email address
+    pub(super) fn queue_status_email(
+        &self,
+        request_id: &str,
+        status: &RequestStatus,
+        timestamp: i64,
+    ) -> Result<bool> {
+        if !matches!(status, RequestStatus::Approved | RequestStatus::Rejected) {
+            return Ok(false);
+        }
+
+        let applicant: Option<(String, String, String)> = self
+            .connection
+            .query_row(
+                "SELECT r.email, r.name, a.name FROM adoption_requests r JOIN animals a ON a.id = r.animal_id WHERE r.id = ?1",
+                params![request_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()
+            .context("Failed to read applicant of adoption request")?;
+        let Some((recipient, recipient_name, animal_name)) = applicant else {
+            return Ok(false);
+        };
+        let recipient = recipient.trim();
+        if recipient.is_empty() {
+            log::debug!(
+                "Adoption request {} has no email address, no email queued",
+                request_id
+            );
+            return Ok(false);
+        }
+
+        self.connection
+            .execute(
+                "INSERT INTO emails (id, request_id, request_status, recipient, recipient_name, animal_name, status, attempts, created_timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, ?8)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    request_id,
+                    status,
+                    recipient,
+                    recipient_name,
+                    animal_name,
+                    EmailStatus::Pending,
+                    timestamp
+                ],
+            )
+            .context("Failed to queue email")?;
+
+        log::debug!(
+            "Queued {} email for adoption request {}",
+            status,
+            request_id
+        );
+        Ok(true)
+    }
+}
+
+/// Builds an EmailMessage from a row selected with `EMAIL_COLUMNS`
+///
+/// # Arguments
+/// * `row` - The row to read
+///
+/// # Returns
+/// * `rusqlite::Result<EmailMessage>` - The email or error
+fn email_from_row(row: &Row) -> rusqlite::Result<EmailMessage> {
+    Ok(EmailMessage {
+        id: row.get(0)?,
+        request_id: row.get(1)?,
+        request_status: row.get(2)?,
+        recipient: row.get(3)?,
+        recipient_name: row.get(4)?,
+        animal_name: row.get(5)?,
+        subject: row.get(6)?,
+        body: row.get(7)?,
+        status: row.get(8)?,
+        attempts: row.get(9)?,
+        error: row.get(10)?,
+        created_timestamp: row.get(11)?,
+        attempted_timestamp: row.get(12)?,
+    })
+}
@@ -0,0 +1,212 @@
+//
+// database_service/expenses.rs
+//
+// This module provides operations for recording what the shelter spends money
+// on, and for the budget report summing the expenses and donations month by
+// month.
+//
+
+use super::statistics::month_bounds;
+use super::types::{BudgetReport, CategoryAmount, Expense, MonthlyExpenses};
+use super::validation;
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use rusqlite::{params, Row};
+use uuid::Uuid;
+
+/// Columns selected when reading an expense, in the order expected by `expense_from_row`
+const EXPENSE_COLUMNS: &str = "id, category, amount, expense_timestamp, vendor, animal_id";
+
+/// Builds an expense from a row selected with `EXPENSE_COLUMNS`
+fn expense_from_row(row: &Row) -> rusqlite::Result<Expense> {
+    Ok(Expense {
+        id: row.get(0)?,
+        category: row.get(1)?,
+        amount: row.get(2)?,
+        expense_timestamp: row.get(3)?,
+        vendor: row.get(4)?,
+        animal_id: row.get(5)?,
+    })
+}
+
+impl DatabaseService {
+    // ==================== EXPENSES TABLE OPERATIONS ====================
+
+    /// Inserts a new expense into the database
+    ///
+    /// Fails with a `ValidationError` listing the invalid fields if the expense does not validate.
+    ///
+    /// # Arguments
+    /// * `expense` - The expense to insert; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<String>` - The ID of the inserted expense or error
+    pub fn insert_expense(&self, expense: &Expense) -> Result<String> {
+        validation::ensure_valid(validation::validate_expense(expense))?;
+
+        let id = if expense.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            expense.id.clone()
+        };
+
+        self.connection
+            .execute(
+                "INSERT INTO expenses (id, category, amount, expense_timestamp, vendor, animal_id) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    id,
+                    expense.category,
+                    expense.amount,
+                    expense.expense_timestamp,
+                    expense.vendor.trim(),
+                    expense.animal_id
+                ],
+            )
+            .context("Failed to insert expense into database")?;
+
+        log::info!("Successfully inserted expense with ID: {}", id);
+        Ok(id)
+    }
+
+    /// Retrieves the expenses made during a period
+    ///
+    /// # Arguments
+    /// * `from_timestamp` - Start of the period, inclusive
+    /// * `to_timestamp` - End of the period, exclusive
+    ///
+    /// # Returns
+    /// * `Result<Vec<Expense>>` - Expenses, most recent first
+    pub fn query_expenses(&self, from_timestamp: i64, to_timestamp: i64) -> Result<Vec<Expense>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM expenses WHERE expense_timestamp >= ?1 AND expense_timestamp < ?2 ORDER BY expense_timestamp DESC",
+                EXPENSE_COLUMNS
+            ))
+            .context("Failed to prepare query for expenses")?;
+
+        let expenses = statement
+            .query_map(params![from_timestamp, to_timestamp], expense_from_row)
+            .context("Failed to execute query for expenses")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse expense row")?;
+
+        log::debug!("Retrieved {} expenses", expenses.len());
+        Ok(expenses)
+    }
+
+    /// Updates an expense
+    ///
+    /// Fails with a `ValidationError` listing the invalid fields if the expense does not validate.
+    ///
+    /// # Arguments
+    /// * `expense` - The updated expense information
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the expense was found and updated, false if not found
+    pub fn update_expense(&self, expense: &Expense) -> Result<bool> {
+        validation::ensure_valid(validation::validate_expense(expense))?;
+
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE expenses SET category = ?2, amount = ?3, expense_timestamp = ?4, vendor = ?5, animal_id = ?6 WHERE id = ?1",
+                params![
+                    expense.id,
+                    expense.category,
+                    expense.amount,
+                    expense.expense_timestamp,
+                    expense.vendor.trim(),
+                    expense.animal_id
+                ],
+            )
+            .context("Failed to update expense in database")?;
+
+        log::info!(
+            "Updated expense with ID: {}, found: {}",
+            expense.id,
+            rows_affected == 1
+        );
+        Ok(rows_affected == 1)
+    }
+
+    /// Deletes an expense recorded by mistake
+    ///
+    /// # Arguments
+    /// * `expense_id` - The ID of the expense to delete
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the expense was found and deleted, false if not found
+    pub fn delete_expense(&self, expense_id: &str) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute("DELETE FROM expenses WHERE id = ?1", params![expense_id])
+            .context("Failed to delete expense from database")?;
+
+        log::info!(
+            "Deleted expense with ID: {}, found: {}",
+            expense_id,
+            rows_affected == 1
+        );
+        Ok(rows_affected == 1)
+    }
+
+    /// Computes how much the shelter spent during each month of a year, by category,
+    /// against the donations it received
+    ///
+    /// # Arguments
+    /// * `year` - The year to report on
+    ///
+    /// # Returns
+    /// * `Result<BudgetReport>` - Expenses and donations of each of the twelve months, or error
+    pub fn query_budget_report(&self, year: i32) -> Result<BudgetReport> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT category, SUM(amount) FROM expenses WHERE expense_timestamp >= ?1 AND expense_timestamp < ?2 GROUP BY category ORDER BY SUM(amount) DESC, category",
+            )
+            .context("Failed to prepare query for budget report")?;
+        let mut donations_statement = self
+            .connection
+            .prepare(
+                "SELECT COALESCE(SUM(amount), 0) FROM donations WHERE donation_timestamp >= ?1 AND donation_timestamp < ?2",
+            )
+            .context("Failed to prepare query for donations of budget report")?;
+
+        let mut months = Vec::new();
+        for month in 1..=12 {
+            let (start_timestamp, end_timestamp) = month_bounds(year, month)?;
+            let by_category = statement
+                .query_map(params![start_timestamp, end_timestamp], |row| {
+                    Ok(CategoryAmount {
+                        category: row.get(0)?,
+                        amount: row.get(1)?,
+                    })
+                })
+                .context("Failed to execute query for budget report")?
+                .collect::<rusqlite::Result<Vec<_>>>()
+                .context("Failed to parse budget report row")?;
+            let donations: i64 = donations_statement
+                .query_row(params![start_timestamp, end_timestamp], |row| row.get(0))
+                .context("Failed to execute query for donations of budget report")?;
+            let total: i64 = by_category.iter().map(|entry| entry.amount).sum();
+            months.push(MonthlyExpenses {
+                month,
+                total,
+                by_category,
+                donations,
+                net: donations - total,
+            });
+        }
+
+        let total: i64 = months.iter().map(|month| month.total).sum();
+        let donation_total: i64 = months.iter().map(|month| month.donations).sum();
+        Ok(BudgetReport {
+            year,
+            months,
+            total,
+            donation_total,
+            net: donation_total - total,
+        })
+    }
+}
@@ -0,0 +1,109 @@
+//
+// database_service/favorites.rs
+//
+// This module provides operations for managing the animals customers have
+// shortlisted as favorites.
+//
+
+use super::types::AnimalSummary;
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::params;
+
+impl DatabaseService {
+    // ==================== FAVORITES TABLE OPERATIONS ====================
+
+    /// Retrieves summary information for the animals a user has favorited, most recent first
+    ///
+    /// # Arguments
+    /// * `username` - The user name to retrieve favorites for
+    ///
+    /// # Returns
+    /// * `Result<Vec<AnimalSummary>>` - List of favorited animal summaries or error
+    pub fn query_favorites_by_username(&self, username: &str) -> Result<Vec<AnimalSummary>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT a.id, a.name, a.specie, a.breed, a.sex, a.admission_timestamp, a.status, a.image_path FROM favorites f JOIN animals a ON a.id = f.animal_id WHERE f.username = ?1 ORDER BY f.created_timestamp DESC",
+            )
+            .context("Failed to prepare query for favorites by user name")?;
+
+        let animal_iter = statement
+            .query_map(params![username], |row| {
+                Ok(AnimalSummary {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    specie: row.get(2)?,
+                    breed: row.get(3)?,
+                    sex: row.get(4)?,
+                    admission_timestamp: row.get(5)?,
+                    status: row.get(6)?,
+                    image_path: row.get(7)?,
+                })
+            })
+            .context("Failed to execute query for favorites by user name")?;
+
+        let mut animals = Vec::new();
+        for animal in animal_iter {
+            animals.push(animal.context("Failed to parse animal row")?);
+        }
+
+        log::debug!(
+            "Retrieved {} favorites for user name: {}",
+            animals.len(),
+            username
+        );
+        Ok(animals)
+    }
+
+    /// Adds an animal to a user's favorites
+    ///
+    /// # Arguments
+    /// * `username` - The user name favoriting the animal
+    /// * `animal_id` - The ID of the animal to favorite
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the favorite was added, false if it already existed
+    pub fn insert_favorite(&self, username: &str, animal_id: &str) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "INSERT OR IGNORE INTO favorites (username, animal_id, created_timestamp) VALUES (?1, ?2, ?3)",
+                params![username, animal_id, Utc::now().timestamp()],
+            )
+            .context("Failed to insert favorite into database")?;
+
+        if rows_affected == 1 {
+            log::info!("User {} favorited animal with ID: {}", username, animal_id);
+        }
+        Ok(rows_affected == 1)
+    }
+
+    /// Removes an animal from a user's favorites
+    ///
+    /// # Arguments
+    /// * `username` - The user name the favorite belongs to
+    /// * `animal_id` - The ID of the favorited animal
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the favorite was found and removed, false if not found
+    pub fn delete_favorite(&self, username: &str, animal_id: &str) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "DELETE FROM favorites WHERE username = ?1 AND animal_id = ?2",
+                params![username, animal_id],
+            )
+            .context("Failed to delete favorite from database")?;
+
+        if rows_affected == 1 {
+            log::info!(
+                "User {} removed animal with ID: {} from favorites",
+                username,
+                animal_id
+            );
+        }
+        Ok(rows_affected == 1)
+    }
+}
@@ -0,0 +1,229 @@
+//
+// database_service/fosters.rs
+//
+// This module provides operations for managing foster placements,
+// keeping the status of fostered animals in sync with their placements.
+//
+
+use super::types::{AnimalStatus, FosterPlacement, StartFosterResult};
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use uuid::Uuid;
+
+impl DatabaseService {
+    // ==================== FOSTER_PLACEMENTS TABLE OPERATIONS ====================
+
+    /// Retrieves all foster placements for a specific animal, most recent first
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to retrieve placements for
+    ///
+    /// # Returns
+    /// * `Result<Vec<FosterPlacement>>` - List of foster placements or error
+    pub fn query_foster_placements_by_animal_id(
+        &self,
+        animal_id: &str,
+    ) -> Result<Vec<FosterPlacement>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, animal_id, caregiver_name, caregiver_email, caregiver_tel_number, start_timestamp, end_timestamp, ended_timestamp FROM foster_placements WHERE animal_id = ?1 ORDER BY start_timestamp DESC",
+            )
+            .context("Failed to prepare query for foster placements by animal ID")?;
+
+        let placement_iter = statement
+            .query_map(params![animal_id], |row| {
+                Ok(FosterPlacement {
+                    id: row.get(0)?,
+                    animal_id: row.get(1)?,
+                    caregiver_name: row.get(2)?,
+                    caregiver_email: row.get(3)?,
+                    caregiver_tel_number: row.get(4)?,
+                    start_timestamp: row.get(5)?,
+                    end_timestamp: row.get(6)?,
+                    ended_timestamp: row.get(7)?,
+                })
+            })
+            .context("Failed to execute query for foster placements by animal ID")?;
+
+        let mut placements = Vec::new();
+        for placement in placement_iter {
+            placements.push(placement.context("Failed to parse foster placement row")?);
+        }
+
+        log::debug!(
+            "Retrieved {} foster placements for animal ID: {}",
+            placements.len(),
+            animal_id
+        );
+        Ok(placements)
+    }
+
+    /// Starts a foster placement and marks the animal as fostered
+    ///
+    /// # Arguments
+    /// * `placement` - The placement to start; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<StartFosterResult>` - Success, or why the placement could not be started
+    pub fn start_foster_placement(&self, placement: &FosterPlacement) -> Result<StartFosterResult> {
+        if placement.end_timestamp < placement.start_timestamp {
+            bail!("Foster placement cannot end before it starts");
+        }
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for foster placement")?;
+
+        // Only animals that are available can go into foster care
+        let status: Option<AnimalStatus> = transaction
+            .query_row(
+                "SELECT status FROM animals WHERE id = ?1",
+                params![placement.animal_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to read animal status")?;
+        match status {
+            None => return Ok(StartFosterResult::AnimalNotFound),
+            Some(AnimalStatus::Available) => {}
+            Some(_) => return Ok(StartFosterResult::AnimalNotAvailable),
+        }
+
+        let id = if placement.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            placement.id.clone()
+        };
+
+        transaction
+            .execute(
+                "INSERT INTO foster_placements (id, animal_id, caregiver_name, caregiver_email, caregiver_tel_number, start_timestamp, end_timestamp, ended_timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, NULL)",
+                params![
+                    id,
+                    placement.animal_id,
+                    placement.caregiver_name,
+                    placement.caregiver_email,
+                    placement.caregiver_tel_number,
+                    placement.start_timestamp,
+                    placement.end_timestamp
+                ],
+            )
+            .context("Failed to insert foster placement into database")?;
+
+        transaction
+            .execute(
+                "UPDATE animals SET status = ?2, version = version + 1 WHERE id = ?1",
+                params![placement.animal_id, AnimalStatus::Fostered],
+            )
+            .context("Failed to mark animal as fostered")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit foster placement")?;
+
+        log::info!(
+            "Started foster placement {} for animal with ID: {}",
+            id,
+            placement.animal_id
+        );
+        Ok(StartFosterResult::Success)
+    }
+
+    /// Moves the planned end of an ongoing foster placement
+    ///
+    /// # Arguments
+    /// * `placement_id` - The ID of the placement to extend
+    /// * `end_timestamp` - The new planned end of the placement
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if an ongoing placement was found and extended, false if not found
+    pub fn extend_foster_placement(&self, placement_id: &str, end_timestamp: i64) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE foster_placements SET end_timestamp = ?2 WHERE id = ?1 AND ended_timestamp IS NULL AND start_timestamp <= ?2",
+                params![placement_id, end_timestamp],
+            )
+            .context("Failed to extend foster placement")?;
+
+        match rows_affected {
+            1 => {
+                log::info!("Extended foster placement with ID: {}", placement_id);
+                Ok(true)
+            }
+            0 => {
+                log::warn!(
+                    "No ongoing foster placement found with ID: {} ending after its start",
+                    placement_id
+                );
+                Ok(false)
+            }
+            _ => {
+                bail!(
+                    "Unexpected number of rows affected when extending foster placement: {}",
+                    rows_affected
+                );
+            }
+        }
+    }
+
+    /// Ends an ongoing foster placement and makes the animal available again
+    ///
+    /// # Arguments
+    /// * `placement_id` - The ID of the placement to end
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if an ongoing placement was found and ended, false if not found
+    pub fn end_foster_placement(&self, placement_id: &str) -> Result<bool> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for ending foster placement")?;
+
+        let animal_id: Option<String> = transaction
+            .query_row(
+                "SELECT animal_id FROM foster_placements WHERE id = ?1 AND ended_timestamp IS NULL",
+                params![placement_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to read foster placement")?;
+        let Some(animal_id) = animal_id else {
+            log::warn!(
+                "No ongoing foster placement found with ID: {}",
+                placement_id
+            );
+            return Ok(false);
+        };
+
+        transaction
+            .execute(
+                "UPDATE foster_placements SET ended_timestamp = ?2 WHERE id = ?1",
+                params![placement_id, Utc::now().timestamp()],
+            )
+            .context("Failed to end foster placement")?;
+
+        // Only animals still in foster care go back to available
+        transaction
+            .execute(
+                "UPDATE animals SET status = ?2, version = version + 1 WHERE id = ?1 AND status = ?3",
+                params![animal_id, AnimalStatus::Available, AnimalStatus::Fostered],
+            )
+            .context("Failed to mark animal as available")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit ending of foster placement")?;
+
+        log::info!(
+            "Ended foster placement {} for animal with ID: {}",
+            placement_id,
+            animal_id
+        );
+        Ok(true)
+    }
+}
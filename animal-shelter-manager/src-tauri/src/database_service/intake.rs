@@ -0,0 +1,181 @@
+//
+// database_service/intake.rs
+//
+// This module provides operations for recording how animals come into the shelter
+// and querying the intake history of each animal.
+//
+
+use super::types::{AnimalStatus, IntakeRecord, IntakeType, StrayHold};
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use rusqlite::params;
+use uuid::Uuid;
+
+impl DatabaseService {
+    // ==================== INTAKE_RECORDS TABLE OPERATIONS ====================
+
+    /// Retrieves the intake history of a specific animal, most recent first
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to retrieve intake records for
+    ///
+    /// # Returns
+    /// * `Result<Vec<IntakeRecord>>` - List of intake records or error
+    pub fn query_intake_records_by_animal_id(&self, animal_id: &str) -> Result<Vec<IntakeRecord>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, animal_id, intake_type, source, condition, staff_username, intake_timestamp, hold_until_timestamp FROM intake_records WHERE animal_id = ?1 ORDER BY intake_timestamp DESC",
+            )
+            .context("Failed to prepare query for intake records by animal ID")?;
+
+        let record_iter = statement
+            .query_map(params![animal_id], |row| {
+                Ok(IntakeRecord {
+                    id: row.get(0)?,
+                    animal_id: row.get(1)?,
+                    intake_type: row.get(2)?,
+                    source: row.get(3)?,
+                    condition: row.get(4)?,
+                    staff_username: row.get(5)?,
+                    intake_timestamp: row.get(6)?,
+                    hold_until_timestamp: row.get(7)?,
+                })
+            })
+            .context("Failed to execute query for intake records by animal ID")?;
+
+        let mut records = Vec::new();
+        for record in record_iter {
+            records.push(record.context("Failed to parse intake record row")?);
+        }
+
+        log::debug!(
+            "Retrieved {} intake records for animal ID: {}",
+            records.len(),
+            animal_id
+        );
+        Ok(records)
+    }
+
+    /// Inserts a new intake record into the database
+    ///
+    /// A stray is put on hold for the given number of days from its intake, during which
+    /// it cannot be adopted; any hold given with the record is ignored.
+    ///
+    /// # Arguments
+    /// * `record` - The intake record to insert; an ID is generated if it is empty
+    /// * `stray_hold_days` - Days a stray must be held before adoption, or 0 for no hold
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn insert_intake_record(&self, record: &IntakeRecord, stray_hold_days: u32) -> Result<()> {
+        if record.staff_username.trim().is_empty() {
+            bail!("Intake record must name the staff member who took the animal in");
+        }
+
+        let id = if record.id.trim().is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            record.id.clone()
+        };
+        let hold_until_timestamp = (record.intake_type == IntakeType::Stray && stray_hold_days > 0)
+            .then(|| record.intake_timestamp + i64::from(stray_hold_days) * 86_400);
+
+        self.connection
+            .execute(
+                "INSERT INTO intake_records (id, animal_id, intake_type, source, condition, staff_username, intake_timestamp, hold_until_timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    id,
+                    record.animal_id,
+                    record.intake_type,
+                    record.source,
+                    record.condition,
+                    record.staff_username,
+                    record.intake_timestamp,
+                    hold_until_timestamp
+                ],
+            )
+            .context("Failed to insert intake record into database")?;
+
+        log::info!(
+            "Recorded intake {} for animal with ID: {}",
+            id,
+            record.animal_id
+        );
+        Ok(())
+    }
+
+    /// Finds when the hold on a stray ends, if it is still on hold
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal
+    ///
+    /// # Returns
+    /// * `Result<Option<i64>>` - When the latest hold ends, or None if the animal is not on hold
+    pub fn query_stray_hold_until(&self, animal_id: &str) -> Result<Option<i64>> {
+        let hold_until: Option<i64> = self
+            .connection
+            .query_row(
+                "SELECT MAX(hold_until_timestamp) FROM intake_records WHERE animal_id = ?1",
+                params![animal_id],
+                |row| row.get(0),
+            )
+            .context("Failed to query stray hold of animal")?;
+        Ok(hold_until.filter(|&hold_until| hold_until > Utc::now().timestamp()))
+    }
+
+    /// Retrieves the strays whose hold ends within a time range, ending soonest first
+    ///
+    /// Animals that have since been adopted, passed away or been transferred are left out.
+    ///
+    /// # Arguments
+    /// * `from_timestamp` - Start of the range, inclusive
+    /// * `to_timestamp` - End of the range, exclusive
+    ///
+    /// # Returns
+    /// * `Result<Vec<StrayHold>>` - The strays coming off hold, or error
+    pub fn query_stray_holds_ending(
+        &self,
+        from_timestamp: i64,
+        to_timestamp: i64,
+    ) -> Result<Vec<StrayHold>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT a.id, a.name, a.specie, i.intake_timestamp, MAX(i.hold_until_timestamp) AS hold_until
+                 FROM intake_records i JOIN animals a ON a.id = i.animal_id
+                 WHERE a.status NOT IN (?3, ?4, ?5)
+                 GROUP BY a.id
+                 HAVING hold_until >= ?1 AND hold_until < ?2
+                 ORDER BY hold_until ASC",
+            )
+            .context("Failed to prepare query for stray holds")?;
+
+        let holds = statement
+            .query_map(
+                params![
+                    from_timestamp,
+                    to_timestamp,
+                    AnimalStatus::Adopted,
+                    AnimalStatus::PassedAway,
+                    AnimalStatus::Transferred
+                ],
+                |row| {
+                    Ok(StrayHold {
+                        animal_id: row.get(0)?,
+                        animal_name: row.get(1)?,
+                        specie: row.get(2)?,
+                        intake_timestamp: row.get(3)?,
+                        hold_until_timestamp: row.get(4)?,
+                    })
+                },
+            )
+            .context("Failed to execute query for stray holds")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse stray hold row")?;
+
+        log::debug!("Retrieved {} strays coming off hold", holds.len());
+        Ok(holds)
+    }
+}
@@ -0,0 +1,153 @@
+//
+// database_service/integrity.rs
+//
+// This module provides checks that find corruption and dangling references
+// in the database, so staff can repair them.
+//
+
+use super::types::{IntegrityReport, OrphanedRecord};
+use super::{DatabaseService, ANIMAL_DEPENDENT_TABLES};
+use anyhow::{Context, Result};
+use rusqlite::params;
+
+impl DatabaseService {
+    // ==================== INTEGRITY CHECKS ====================
+
+    /// Checks the database file for corruption and records for references to missing animals
+    ///
+    /// Image files are not checked here, so the returned report has no missing images or
+    /// unreferenced files.
+    ///
+    /// # Returns
+    /// * `Result<IntegrityReport>` - The problems found or error
+    pub fn check_integrity(&self) -> Result<IntegrityReport> {
+        // SQLite reports a single "ok" row when the database is intact
+        let mut statement = self
+            .connection
+            .prepare("PRAGMA integrity_check")
+            .context("Failed to prepare integrity check")?;
+        let message_iter = statement
+            .query_map([], |row| row.get::<_, String>(0))
+            .context("Failed to run integrity check")?;
+        let mut integrity_errors = Vec::new();
+        for message in message_iter {
+            let message = message.context("Failed to parse integrity check row")?;
+            if message != "ok" {
+                integrity_errors.push(message);
+            }
+        }
+
+        // Find records pointing at animals that no longer exist
+        let mut orphaned_records = Vec::new();
+        for (table, description) in ANIMAL_DEPENDENT_TABLES {
+            let mut statement = self
+                .connection
+                .prepare(&format!(
+                    "SELECT id, animal_id FROM {} WHERE animal_id NOT IN (SELECT id FROM animals)",
+                    table
+                ))
+                .context(format!(
+                    "Failed to prepare query for orphaned {}s",
+                    description
+                ))?;
+            let record_iter = statement
+                .query_map([], |row| {
+                    Ok(OrphanedRecord {
+                        table: table.to_string(),
+                        id: row.get(0)?,
+                        animal_id: row.get(1)?,
+                    })
+                })
+                .context(format!(
+                    "Failed to execute query for orphaned {}s",
+                    description
+                ))?;
+            for record in record_iter {
+                orphaned_records.push(record.context("Failed to parse orphaned record row")?);
+            }
+        }
+
+        log::info!(
+            "Integrity check found {} integrity error(s) and {} orphaned record(s)",
+            integrity_errors.len(),
+            orphaned_records.len()
+        );
+        Ok(IntegrityReport {
+            healthy: integrity_errors.is_empty() && orphaned_records.is_empty(),
+            integrity_errors,
+            orphaned_records,
+            missing_images: Vec::new(),
+            unreferenced_files: Vec::new(),
+        })
+    }
+
+    /// Retrieves the image paths of animals in the shelter and in the trash
+    ///
+    /// # Returns
+    /// * `Result<Vec<(String, String)>>` - Pairs of animal ID and image path or error
+    pub fn query_image_paths(&self) -> Result<Vec<(String, String)>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, image_path FROM animals WHERE image_path IS NOT NULL UNION ALL SELECT id, image_path FROM trashed_animals WHERE image_path IS NOT NULL",
+            )
+            .context("Failed to prepare query for image paths")?;
+
+        let path_iter = statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .context("Failed to execute query for image paths")?;
+
+        let mut paths = Vec::new();
+        for path in path_iter {
+            paths.push(path.context("Failed to parse image path row")?);
+        }
+        Ok(paths)
+    }
+
+    /// Checks whether any animal, including those in the trash, still uses an image
+    ///
+    /// # Arguments
+    /// * `image_path` - Path of the image
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if an animal uses the image, or error
+    pub fn is_image_path_in_use(&self, image_path: &str) -> Result<bool> {
+        self.connection
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM animals WHERE image_path = ?1) OR EXISTS(SELECT 1 FROM trashed_animals WHERE image_path = ?1)",
+                params![image_path],
+                |row| row.get(0),
+            )
+            .context("Failed to check whether image is in use")
+    }
+
+    /// Points the animals using an image at its new path, after the file was moved
+    ///
+    /// # Arguments
+    /// * `changed_paths` - Pairs of old and new image path
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or error if any animal could not be updated, in which case none are
+    pub fn replace_image_paths(&self, changed_paths: &[(String, String)]) -> Result<()> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to start transaction for image paths")?;
+        for (old_path, new_path) in changed_paths {
+            for table in ["animals", "trashed_animals"] {
+                transaction
+                    .execute(
+                        &format!("UPDATE {} SET image_path = ?2 WHERE image_path = ?1", table),
+                        params![old_path, new_path],
+                    )
+                    .context("Failed to update image path")?;
+            }
+        }
+        transaction
+            .commit()
+            .context("Failed to commit image paths")?;
+
+        log::info!("Updated {} image paths", changed_paths.len());
+        Ok(())
+    }
+}
@@ -0,0 +1,302 @@
+//
+// database_service/inventory.rs
+//
+// This module provides operations for tracking the supplies kept in stock, such
+// as food and medicine, so shortages are noticed before they run out.
+//
+
+use super::types::{AdjustStockResult, InventoryAdjustment, InventoryItem};
+use super::validation;
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension, Row};
+use uuid::Uuid;
+
+/// Columns selected when reading an inventory item, in the order expected by `item_from_row`
+const ITEM_COLUMNS: &str = "id, name, unit, quantity_on_hand, reorder_threshold";
+
+/// Builds an inventory item from a row selected with `ITEM_COLUMNS`
+fn item_from_row(row: &Row) -> rusqlite::Result<InventoryItem> {
+    Ok(InventoryItem {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        unit: row.get(2)?,
+        quantity_on_hand: row.get(3)?,
+        reorder_threshold: row.get(4)?,
+    })
+}
+
+impl DatabaseService {
+    // ==================== INVENTORY_ITEMS TABLE OPERATIONS ====================
+
+    /// Inserts a new inventory item into the database
+    ///
+    /// Fails with a `ValidationError` listing the invalid fields if the item does not validate.
+    ///
+    /// # Arguments
+    /// * `item` - The item to insert, with its starting stock; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<String>` - The ID of the inserted item or error
+    pub fn insert_inventory_item(&self, item: &InventoryItem) -> Result<String> {
+        validation::ensure_valid(validation::validate_inventory_item(item))?;
+
+        let id = if item.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            item.id.clone()
+        };
+
+        self.connection
+            .execute(
+                "INSERT INTO inventory_items (id, name, unit, quantity_on_hand, reorder_threshold) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    id,
+                    item.name.trim(),
+                    item.unit.trim(),
+                    item.quantity_on_hand,
+                    item.reorder_threshold
+                ],
+            )
+            .context("Failed to insert inventory item into database")?;
+
+        log::info!("Successfully inserted inventory item with ID: {}", id);
+        Ok(id)
+    }
+
+    /// Retrieves every inventory item
+    ///
+    /// # Returns
+    /// * `Result<Vec<InventoryItem>>` - Items ordered by name, or error
+    pub fn query_inventory_items(&self) -> Result<Vec<InventoryItem>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM inventory_items ORDER BY name",
+                ITEM_COLUMNS
+            ))
+            .context("Failed to prepare query for inventory items")?;
+
+        let items = statement
+            .query_map([], item_from_row)
+            .context("Failed to execute query for inventory items")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse inventory item row")?;
+
+        log::debug!("Retrieved {} inventory items", items.len());
+        Ok(items)
+    }
+
+    /// Retrieves the inventory items whose stock is at or below their reorder threshold
+    ///
+    /// # Returns
+    /// * `Result<Vec<InventoryItem>>` - Items to reorder, the most depleted first
+    pub fn query_low_stock_items(&self) -> Result<Vec<InventoryItem>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM inventory_items WHERE quantity_on_hand <= reorder_threshold ORDER BY quantity_on_hand - reorder_threshold ASC, name",
+                ITEM_COLUMNS
+            ))
+            .context("Failed to prepare query for low stock items")?;
+
+        let items = statement
+            .query_map([], item_from_row)
+            .context("Failed to execute query for low stock items")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse inventory item row")?;
+
+        log::debug!("Retrieved {} low stock items", items.len());
+        Ok(items)
+    }
+
+    /// Updates the name, unit and reorder threshold of an inventory item
+    ///
+    /// The quantity on hand is left unchanged; it only changes through `adjust_stock`, so
+    /// every change is recorded.
+    ///
+    /// # Arguments
+    /// * `item` - The updated item information
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the item was found and updated, false if not found
+    pub fn update_inventory_item(&self, item: &InventoryItem) -> Result<bool> {
+        validation::ensure_valid(validation::validate_inventory_item(item))?;
+
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE inventory_items SET name = ?2, unit = ?3, reorder_threshold = ?4 WHERE id = ?1",
+                params![
+                    item.id,
+                    item.name.trim(),
+                    item.unit.trim(),
+                    item.reorder_threshold
+                ],
+            )
+            .context("Failed to update inventory item in database")?;
+
+        log::info!(
+            "Updated inventory item with ID: {}, found: {}",
+            item.id,
+            rows_affected == 1
+        );
+        Ok(rows_affected == 1)
+    }
+
+    /// Deletes an inventory item that is no longer stocked, together with its adjustments
+    ///
+    /// # Arguments
+    /// * `item_id` - The ID of the item to delete
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the item was found and deleted, false if not found
+    pub fn delete_inventory_item(&self, item_id: &str) -> Result<bool> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for inventory item deletion")?;
+
+        transaction
+            .execute(
+                "DELETE FROM inventory_adjustments WHERE item_id = ?1",
+                params![item_id],
+            )
+            .context("Failed to delete adjustments of inventory item")?;
+        let rows_affected = transaction
+            .execute(
+                "DELETE FROM inventory_items WHERE id = ?1",
+                params![item_id],
+            )
+            .context("Failed to delete inventory item from database")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit inventory item deletion")?;
+        log::info!(
+            "Deleted inventory item with ID: {}, found: {}",
+            item_id,
+            rows_affected == 1
+        );
+        Ok(rows_affected == 1)
+    }
+
+    // ==================== INVENTORY_ADJUSTMENTS TABLE OPERATIONS ====================
+
+    /// Adds to or takes from the stock of an inventory item and records why
+    ///
+    /// Fails with a `ValidationError` if the change is zero or no reason is given.
+    ///
+    /// # Arguments
+    /// * `item_id` - The ID of the item to adjust
+    /// * `quantity_change` - Quantity to add, negative to take stock away
+    /// * `reason` - Why the stock changed
+    /// * `username` - Username of the staff member adjusting the stock
+    ///
+    /// # Returns
+    /// * `Result<AdjustStockResult>` - The adjusted item, or why the stock could not be adjusted
+    pub fn adjust_stock(
+        &self,
+        item_id: &str,
+        quantity_change: f64,
+        reason: &str,
+        username: &str,
+    ) -> Result<AdjustStockResult> {
+        validation::ensure_valid(validation::validate_stock_adjustment(
+            quantity_change,
+            reason,
+        ))?;
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for stock adjustment")?;
+
+        let item = transaction
+            .query_row(
+                &format!("SELECT {} FROM inventory_items WHERE id = ?1", ITEM_COLUMNS),
+                params![item_id],
+                item_from_row,
+            )
+            .optional()
+            .context("Failed to read inventory item")?;
+        let mut item = match item {
+            None => return Ok(AdjustStockResult::NotFound),
+            Some(item) if item.quantity_on_hand + quantity_change < 0.0 => {
+                return Ok(AdjustStockResult::InsufficientStock(item))
+            }
+            Some(item) => item,
+        };
+        item.quantity_on_hand += quantity_change;
+
+        transaction
+            .execute(
+                "UPDATE inventory_items SET quantity_on_hand = ?2 WHERE id = ?1",
+                params![item_id, item.quantity_on_hand],
+            )
+            .context("Failed to update stock of inventory item")?;
+        transaction
+            .execute(
+                "INSERT INTO inventory_adjustments (id, item_id, quantity_change, reason, username, timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    item_id,
+                    quantity_change,
+                    reason.trim(),
+                    username,
+                    Utc::now().timestamp()
+                ],
+            )
+            .context("Failed to insert inventory adjustment into database")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit stock adjustment")?;
+
+        log::info!(
+            "Adjusted stock of inventory item with ID: {} by {}",
+            item_id,
+            quantity_change
+        );
+        Ok(AdjustStockResult::Adjusted(item))
+    }
+
+    /// Retrieves the changes made to the stock of an inventory item
+    ///
+    /// # Arguments
+    /// * `item_id` - The ID of the item to retrieve the adjustments for
+    ///
+    /// # Returns
+    /// * `Result<Vec<InventoryAdjustment>>` - Adjustments, most recent first
+    pub fn query_inventory_adjustments(&self, item_id: &str) -> Result<Vec<InventoryAdjustment>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, item_id, quantity_change, reason, username, timestamp FROM inventory_adjustments WHERE item_id = ?1 ORDER BY timestamp DESC, rowid DESC",
+            )
+            .context("Failed to prepare query for inventory adjustments")?;
+
+        let adjustments = statement
+            .query_map(params![item_id], |row| {
+                Ok(InventoryAdjustment {
+                    id: row.get(0)?,
+                    item_id: row.get(1)?,
+                    quantity_change: row.get(2)?,
+                    reason: row.get(3)?,
+                    username: row.get(4)?,
+                    timestamp: row.get(5)?,
+                })
+            })
+            .context("Failed to execute query for inventory adjustments")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse inventory adjustment row")?;
+
+        log::debug!(
+            "Retrieved {} adjustments for inventory item: {}",
+            adjustments.len(),
+            item_id
+        );
+        Ok(adjustments)
+    }
+}
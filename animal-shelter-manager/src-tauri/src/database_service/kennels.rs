@@ -0,0 +1,333 @@
+//
+// database_service/kennels.rs
+//
+// This module provides operations for managing kennels and the assignment
+// of animals to them, enforcing kennel capacity and species restrictions, and
+// reports how full the kennels are.
+//
+
+use super::types::{
+    AnimalStatus, AssignKennelResult, CapacityReport, Kennel, KennelOccupancy, SpecieCapacity,
+};
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+impl DatabaseService {
+    // ==================== KENNELS TABLE OPERATIONS ====================
+
+    /// Inserts a new kennel into the database
+    ///
+    /// # Arguments
+    /// * `kennel` - The kennel to insert; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn insert_kennel(&self, kennel: &Kennel) -> Result<()> {
+        if kennel.capacity < 1 {
+            bail!("Kennel capacity must be at least 1");
+        }
+
+        let id = if kennel.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            kennel.id.clone()
+        };
+
+        self.connection
+            .execute(
+                "INSERT INTO kennels (id, name, specie_restriction, capacity) VALUES (?1, ?2, ?3, ?4)",
+                params![id, kennel.name, kennel.specie_restriction, kennel.capacity],
+            )
+            .context("Failed to insert kennel into database")?;
+
+        log::info!("Successfully inserted kennel with ID: {}", id);
+        Ok(())
+    }
+
+    /// Retrieves every kennel together with the animals currently assigned to it
+    ///
+    /// # Returns
+    /// * `Result<Vec<KennelOccupancy>>` - Occupancy of each kennel ordered by name, or error
+    pub fn query_kennel_occupancy(&self) -> Result<Vec<KennelOccupancy>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT id, name, specie_restriction, capacity FROM kennels ORDER BY name")
+            .context("Failed to prepare query for kennels")?;
+
+        let kennel_iter = statement
+            .query_map([], |row| {
+                Ok(Kennel {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    specie_restriction: row.get(2)?,
+                    capacity: row.get(3)?,
+                })
+            })
+            .context("Failed to execute query for kennels")?;
+
+        let mut occupancy = Vec::new();
+        for kennel in kennel_iter {
+            let kennel = kennel.context("Failed to parse kennel row")?;
+            let animal_ids = self.query_kennel_animal_ids(&kennel.id)?;
+            occupancy.push(KennelOccupancy { kennel, animal_ids });
+        }
+
+        log::debug!("Retrieved occupancy of {} kennels", occupancy.len());
+        Ok(occupancy)
+    }
+
+    /// Compares the capacity of the kennels with the number of animals at the shelter and
+    /// in foster care, overall and for each species
+    ///
+    /// Animals count against the capacity while they are available or requested; fostered
+    /// animals are reported separately since they live with their caregivers.
+    ///
+    /// # Returns
+    /// * `Result<CapacityReport>` - Capacities, counts and utilization, or error
+    pub fn query_capacity_report(&self) -> Result<CapacityReport> {
+        let mut species: BTreeMap<String, SpecieCapacity> = BTreeMap::new();
+        let mut shared_capacity = 0;
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT specie_restriction, SUM(capacity) FROM kennels GROUP BY specie_restriction",
+            )
+            .context("Failed to prepare query for kennel capacity")?;
+        let capacities = statement
+            .query_map([], |row| {
+                Ok((row.get::<_, Option<String>>(0)?, row.get::<_, i64>(1)?))
+            })
+            .context("Failed to execute query for kennel capacity")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse kennel capacity row")?;
+        for (restriction, capacity) in capacities {
+            match restriction {
+                Some(specie) => specie_entry(&mut species, specie).dedicated_capacity = capacity,
+                None => shared_capacity = capacity,
+            }
+        }
+
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT specie, COUNT(*) FROM animals WHERE status IN (?1, ?2) GROUP BY specie",
+            )
+            .context("Failed to prepare query for animals at the shelter")?;
+        let counts = statement
+            .query_map(
+                params![AnimalStatus::Available, AnimalStatus::Requested],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .context("Failed to execute query for animals at the shelter")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse animals at the shelter row")?;
+        for (specie, count) in counts {
+            specie_entry(&mut species, specie).in_shelter_count = count;
+        }
+
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT a.specie, COUNT(*) FROM foster_placements f JOIN animals a ON a.id = f.animal_id WHERE f.ended_timestamp IS NULL GROUP BY a.specie",
+            )
+            .context("Failed to prepare query for animals in foster care")?;
+        let counts = statement
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })
+            .context("Failed to execute query for animals in foster care")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse animals in foster care row")?;
+        for (specie, count) in counts {
+            specie_entry(&mut species, specie).foster_count = count;
+        }
+
+        let mut species: Vec<SpecieCapacity> = species.into_values().collect();
+        for specie in &mut species {
+            specie.utilization_percent = utilization_percent(
+                specie.in_shelter_count,
+                specie.dedicated_capacity + shared_capacity,
+            );
+        }
+        let total_capacity =
+            shared_capacity + species.iter().map(|s| s.dedicated_capacity).sum::<i64>();
+        let in_shelter_count = species.iter().map(|s| s.in_shelter_count).sum();
+        Ok(CapacityReport {
+            total_capacity,
+            shared_capacity,
+            in_shelter_count,
+            foster_count: species.iter().map(|s| s.foster_count).sum(),
+            utilization_percent: utilization_percent(in_shelter_count, total_capacity),
+            species,
+        })
+    }
+
+    /// Retrieves the IDs of the animals currently assigned to a kennel
+    ///
+    /// # Arguments
+    /// * `kennel_id` - The ID of the kennel
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - IDs of the assigned animals or error
+    fn query_kennel_animal_ids(&self, kennel_id: &str) -> Result<Vec<String>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT animal_id FROM kennel_assignments WHERE kennel_id = ?1 AND removed_timestamp IS NULL ORDER BY assigned_timestamp",
+            )
+            .context("Failed to prepare query for kennel assignments")?;
+
+        let id_iter = statement
+            .query_map(params![kennel_id], |row| row.get(0))
+            .context("Failed to execute query for kennel assignments")?;
+
+        let mut animal_ids = Vec::new();
+        for id in id_iter {
+            animal_ids.push(id.context("Failed to parse kennel assignment row")?);
+        }
+        Ok(animal_ids)
+    }
+
+    // ==================== KENNEL_ASSIGNMENTS TABLE OPERATIONS ====================
+
+    /// Assigns an animal to a kennel, moving it out of its current kennel if it has one
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to assign
+    /// * `kennel_id` - The ID of the kennel to assign the animal to
+    ///
+    /// # Returns
+    /// * `Result<AssignKennelResult>` - Success, or why the assignment was refused
+    pub fn assign_animal_to_kennel(
+        &self,
+        animal_id: &str,
+        kennel_id: &str,
+    ) -> Result<AssignKennelResult> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for kennel assignment")?;
+
+        let specie: Option<String> = transaction
+            .query_row(
+                "SELECT specie FROM animals WHERE id = ?1",
+                params![animal_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to read animal species")?;
+        let Some(specie) = specie else {
+            return Ok(AssignKennelResult::AnimalNotFound);
+        };
+
+        let kennel: Option<(Option<String>, i64)> = transaction
+            .query_row(
+                "SELECT specie_restriction, capacity FROM kennels WHERE id = ?1",
+                params![kennel_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .context("Failed to read kennel")?;
+        let Some((specie_restriction, capacity)) = kennel else {
+            return Ok(AssignKennelResult::KennelNotFound);
+        };
+
+        if specie_restriction.is_some_and(|restriction| restriction != specie) {
+            return Ok(AssignKennelResult::SpecieNotAllowed);
+        }
+
+        // Count the other animals in the kennel, so re-assigning an animal to its own kennel succeeds
+        let occupied: i64 = transaction
+            .query_row(
+                "SELECT COUNT(*) FROM kennel_assignments WHERE kennel_id = ?1 AND animal_id != ?2 AND removed_timestamp IS NULL",
+                params![kennel_id, animal_id],
+                |row| row.get(0),
+            )
+            .context("Failed to count animals in kennel")?;
+        if occupied >= capacity {
+            return Ok(AssignKennelResult::KennelFull);
+        }
+
+        // Close the current assignment, then open the new one
+        let now = Utc::now().timestamp();
+        transaction
+            .execute(
+                "UPDATE kennel_assignments SET removed_timestamp = ?2 WHERE animal_id = ?1 AND removed_timestamp IS NULL",
+                params![animal_id, now],
+            )
+            .context("Failed to end current kennel assignment")?;
+        transaction
+            .execute(
+                "INSERT INTO kennel_assignments (id, animal_id, kennel_id, assigned_timestamp, removed_timestamp) VALUES (?1, ?2, ?3, ?4, NULL)",
+                params![Uuid::new_v4().to_string(), animal_id, kennel_id, now],
+            )
+            .context("Failed to insert kennel assignment into database")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit kennel assignment")?;
+
+        log::info!(
+            "Assigned animal with ID: {} to kennel with ID: {}",
+            animal_id,
+            kennel_id
+        );
+        Ok(AssignKennelResult::Success)
+    }
+
+    /// Removes an animal from the kennel it is currently assigned to
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to remove
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the animal was in a kennel and was removed, false otherwise
+    pub fn remove_animal_from_kennel(&self, animal_id: &str) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE kennel_assignments SET removed_timestamp = ?2 WHERE animal_id = ?1 AND removed_timestamp IS NULL",
+                params![animal_id, Utc::now().timestamp()],
+            )
+            .context("Failed to remove animal from kennel")?;
+
+        if rows_affected == 0 {
+            log::warn!("Animal with ID: {} is not assigned to a kennel", animal_id);
+            return Ok(false);
+        }
+        log::info!("Removed animal with ID: {} from its kennel", animal_id);
+        Ok(true)
+    }
+}
+
+/// Computes how full a capacity is
+///
+/// # Arguments
+/// * `count` - The number of animals housed
+/// * `capacity` - The number of animals that can be housed
+///
+/// # Returns
+/// * `Option<f64>` - The count as a percentage of the capacity, or None without capacity
+fn utilization_percent(count: i64, capacity: i64) -> Option<f64> {
+    (capacity > 0).then(|| count as f64 * 100.0 / capacity as f64)
+}
+
+/// Gets the capacity of a species in a report being built, adding it if it is new
+fn specie_entry(
+    species: &mut BTreeMap<String, SpecieCapacity>,
+    specie: String,
+) -> &mut SpecieCapacity {
+    species
+        .entry(specie.clone())
+        .or_insert_with(|| SpecieCapacity {
+            specie,
+            dedicated_capacity: 0,
+            in_shelter_count: 0,
+            foster_count: 0,
+            utilization_percent: None,
+        })
+}
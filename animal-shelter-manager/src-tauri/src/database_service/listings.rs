@@ -0,0 +1,56 @@
+//
+// database_service/listings.rs
+//
+// This module selects the animals listed in the public listings feed, which
+// the organization's website and adoption aggregators read.
+//
+
+use super::types::{Animal, AnimalStatus, NoteCategory};
+use super::{animal_from_row, DatabaseService, ANIMAL_COLUMNS};
+use anyhow::{Context, Result};
+use rusqlite::params;
+
+impl DatabaseService {
+    // ==================== LISTINGS OPERATIONS ====================
+
+    /// Retrieves the animals available for adoption, longest waiting first
+    ///
+    /// An animal is flagged when staff have written a staff-only behavior or medical
+    /// note about it, such as a bite history or a condition still being treated.
+    ///
+    /// # Arguments
+    /// * `exclude_flagged` - Whether to leave out flagged animals
+    ///
+    /// # Returns
+    /// * `Result<Vec<Animal>>` - The animals to list or error
+    pub fn query_listed_animals(&self, exclude_flagged: bool) -> Result<Vec<Animal>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM animals WHERE status = ?1
+                 AND NOT (?2 AND id IN (
+                     SELECT animal_id FROM animal_notes WHERE internal = 1 AND category IN (?3, ?4)
+                 ))
+                 ORDER BY admission_timestamp, name",
+                ANIMAL_COLUMNS
+            ))
+            .context("Failed to prepare query for listed animals")?;
+
+        let animals = statement
+            .query_map(
+                params![
+                    AnimalStatus::Available,
+                    exclude_flagged,
+                    NoteCategory::Behavior,
+                    NoteCategory::Medical
+                ],
+                animal_from_row,
+            )
+            .context("Failed to execute query for listed animals")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse listed animal row")?;
+
+        log::debug!("Retrieved {} listed animals", animals.len());
+        Ok(animals)
+    }
+}
@@ -0,0 +1,153 @@
+//
+// database_service/locations.rs
+//
+// This module provides operations for managing the sites run by the
+// organization, which animals are housed at.
+//
+
+use super::types::Location;
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use rusqlite::params;
+use uuid::Uuid;
+
+impl DatabaseService {
+    // ==================== LOCATIONS TABLE OPERATIONS ====================
+
+    /// Inserts a new location into the database
+    ///
+    /// # Arguments
+    /// * `location` - The location to insert; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<String>` - The ID of the inserted location or error
+    pub fn insert_location(&self, location: &Location) -> Result<String> {
+        if location.name.trim().is_empty() {
+            bail!("Location name must not be empty");
+        }
+
+        let id = if location.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            location.id.clone()
+        };
+
+        self.connection
+            .execute(
+                "INSERT INTO locations (id, name, address) VALUES (?1, ?2, ?3)",
+                params![id, location.name.trim(), location.address],
+            )
+            .context("Failed to insert location into database")?;
+
+        log::info!("Successfully inserted location with ID: {}", id);
+        Ok(id)
+    }
+
+    /// Retrieves every location
+    ///
+    /// # Returns
+    /// * `Result<Vec<Location>>` - Locations ordered by name, or error
+    pub fn query_locations(&self) -> Result<Vec<Location>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT id, name, address FROM locations ORDER BY name")
+            .context("Failed to prepare query for locations")?;
+
+        let location_iter = statement
+            .query_map([], |row| {
+                Ok(Location {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    address: row.get(2)?,
+                })
+            })
+            .context("Failed to execute query for locations")?;
+
+        let mut locations = Vec::new();
+        for location in location_iter {
+            locations.push(location.context("Failed to parse location row")?);
+        }
+
+        log::debug!("Retrieved {} locations", locations.len());
+        Ok(locations)
+    }
+
+    /// Updates the name and address of a location
+    ///
+    /// # Arguments
+    /// * `location` - The updated location information
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the location was found and updated, false if not found
+    pub fn update_location(&self, location: &Location) -> Result<bool> {
+        if location.name.trim().is_empty() {
+            bail!("Location name must not be empty");
+        }
+
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE locations SET name = ?2, address = ?3 WHERE id = ?1",
+                params![location.id, location.name.trim(), location.address],
+            )
+            .context("Failed to update location in database")?;
+
+        log::info!(
+            "Updated location with ID: {}, found: {}",
+            location.id,
+            rows_affected == 1
+        );
+        Ok(rows_affected == 1)
+    }
+
+    /// Deletes a location that no longer houses any animals
+    ///
+    /// Animals in the trash forget the deleted location, so they can still be restored.
+    ///
+    /// # Arguments
+    /// * `location_id` - The ID of the location to delete
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the location was found and deleted, false if not found
+    pub fn delete_location(&self, location_id: &str) -> Result<bool> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for location deletion")?;
+
+        let animal_count: i64 = transaction
+            .query_row(
+                "SELECT COUNT(*) FROM animals WHERE location_id = ?1",
+                params![location_id],
+                |row| row.get(0),
+            )
+            .context("Failed to count animals at location")?;
+        if animal_count > 0 {
+            bail!(
+                "Cannot delete location with ID {} because it houses {} animal(s)",
+                location_id,
+                animal_count
+            );
+        }
+
+        transaction
+            .execute(
+                "UPDATE trashed_animals SET location_id = NULL WHERE location_id = ?1",
+                params![location_id],
+            )
+            .context("Failed to clear location of animals in trash")?;
+        let rows_affected = transaction
+            .execute("DELETE FROM locations WHERE id = ?1", params![location_id])
+            .context("Failed to delete location from database")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit location deletion")?;
+        log::info!(
+            "Deleted location with ID: {}, found: {}",
+            location_id,
+            rows_affected == 1
+        );
+        Ok(rows_affected == 1)
+    }
+}
@@ -0,0 +1,96 @@
+//
+// database_service/maintenance.rs
+//
+// This module provides upkeep of SQLite database files: rebuilding them to
+// reclaim the space left by deleted rows and refreshing query planner statistics.
+//
+
+use super::types::MaintenanceResult;
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection};
+
+impl DatabaseService {
+    // ==================== MAINTENANCE OPERATIONS ====================
+
+    /// Compacts the shelter database, refreshes its query planner statistics and
+    /// records when it ran
+    ///
+    /// # Returns
+    /// * `Result<MaintenanceResult>` - The size of the database before and after or error
+    pub fn run_maintenance(&self) -> Result<MaintenanceResult> {
+        let result = vacuum_and_analyze(&self.connection, "animal_shelter")?;
+        self.connection
+            .execute(
+                "INSERT INTO maintenance_runs (run_timestamp) VALUES (?1)",
+                params![Utc::now().timestamp()],
+            )
+            .context("Failed to record maintenance run")?;
+        Ok(result)
+    }
+
+    /// Queries when maintenance last ran on the shelter database
+    ///
+    /// # Returns
+    /// * `Result<Option<i64>>` - The Unix timestamp of the last run, `None` if it never
+    ///   ran, or error
+    pub fn query_last_maintenance_timestamp(&self) -> Result<Option<i64>> {
+        self.connection
+            .query_row(
+                "SELECT MAX(run_timestamp) FROM maintenance_runs",
+                [],
+                |row| row.get(0),
+            )
+            .context("Failed to query last maintenance run")
+    }
+}
+
+/// Runs VACUUM and ANALYZE on a database and measures the space reclaimed
+///
+/// # Arguments
+/// * `connection` - Connection to the database, which must not be inside a transaction
+/// * `database` - Name of the database to report
+///
+/// # Returns
+/// * `Result<MaintenanceResult>` - The size of the database before and after or error
+pub fn vacuum_and_analyze(connection: &Connection, database: &str) -> Result<MaintenanceResult> {
+    let size_before_bytes = database_size(connection)?;
+    connection
+        .execute_batch("VACUUM; ANALYZE;")
+        .context(format!(
+            "Failed to vacuum and analyze {} database",
+            database
+        ))?;
+    let size_after_bytes = database_size(connection)?;
+
+    let result = MaintenanceResult {
+        database: database.to_string(),
+        size_before_bytes,
+        size_after_bytes,
+        reclaimed_bytes: (size_before_bytes - size_after_bytes).max(0),
+    };
+    log::info!(
+        "Maintained {} database, reclaiming {} bytes",
+        database,
+        result.reclaimed_bytes
+    );
+    Ok(result)
+}
+
+/// Computes the size of a database from its page count and page size
+///
+/// # Arguments
+/// * `connection` - Connection to the database
+///
+/// # Returns
+/// * `Result<i64>` - The size of the database in bytes or error
+fn database_size(connection: &Connection) -> Result<i64> {
+    let page_count: i64 = connection
+        .query_row("PRAGMA page_count", [], |row| row.get(0))
+        .context("Failed to read database page count")?;
+    let page_size: i64 = connection
+        .query_row("PRAGMA page_size", [], |row| row.get(0))
+        .context("Failed to read database page size")?;
+    Ok(page_count * page_size)
+}
@@ -6,15 +6,92 @@
 // The database is powered by SQLite.
 //
 
+mod appointments;
+pub mod audit;
+mod banned_adopters;
+mod calendar;
+mod contacts;
+mod deceased;
+pub mod demo;
+mod detail;
+mod donations;
+mod emails;
+mod expenses;
+mod favorites;
+mod fosters;
+mod intake;
+mod integrity;
+mod inventory;
+mod kennels;
+mod listings;
+mod locations;
+pub mod maintenance;
+mod notes;
+mod notifications;
+mod payments;
+mod privacy;
+mod profiles;
+mod relationships;
+mod reminders;
+mod request_checks;
+mod returns;
+pub mod screening;
+pub mod search;
+mod statistics;
+mod surgeries;
+mod sync;
 mod test;
+mod transfers;
+mod trash;
+mod trials;
 pub mod types;
+mod vaccinations;
+pub mod validation;
+mod waitlist;
+mod webhooks;
 
 use anyhow::{bail, Context, Result};
 use chrono::{Datelike, Duration, Utc};
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension, Row, ToSql};
 use std::collections::HashMap;
 use std::path::Path;
-use types::{AdoptionRequest, Animal, AnimalSummary, FilterCriteria, FilterValue};
+use types::{
+    AdoptionRequest, AdoptionRequestFilters, AdoptionRequestSummary, Animal, AnimalAdoptionRequest,
+    AnimalStatus, AnimalSummary, ApprovalRequirements, ApproveRequestResult, CoApplicant,
+    CreateRequestResult, FilterCriteria, FilterValue, Notification, RequestStatus,
+    UpdateAnimalResult, WebhookEvent,
+};
+use uuid::Uuid;
+
+/// Username adoption requests received from the public website are filed under, since
+/// their applicants have no account; no account can be registered with it
+pub const WEB_INTAKE_USERNAME: &str = "@website";
+
+/// Number of minor currency units, such as satang or cents, in one unit of the currency
+pub const MINOR_UNITS_PER_UNIT: i64 = 100;
+
+/// Version of the data migrations applied by `migrate_data`, stored in the `user_version` pragma
+pub const DATA_VERSION: i64 = 3;
+
+/// Tables holding records that reference an animal, with a description for messages,
+/// in the order they must be deleted when the animal is deleted
+const ANIMAL_DEPENDENT_TABLES: &[(&str, &str)] = &[
+    ("foster_placements", "foster placement"),
+    ("intake_records", "intake record"),
+    ("kennel_assignments", "kennel assignment"),
+    ("animal_notes", "note"),
+    ("vaccinations", "vaccination"),
+    ("adoption_returns", "adoption return"),
+    ("trial_adoptions", "trial adoption"),
+    ("deceased_records", "deceased record"),
+    ("appointments", "appointment"),
+    ("surgeries", "surgery"),
+    ("transfers", "transfer"),
+    ("adoption_requests", "adoption request"),
+];
+
+/// Columns selected when reading a complete animal, in the order expected by `animal_from_row`
+const ANIMAL_COLUMNS: &str = "id, name, specie, breed, sex, birth_month, birth_year, neutered, admission_timestamp, status, image_path, appearance, bio, version, microchip_number, location_id, good_with_kids, good_with_dogs, good_with_cats, special_needs, size, color";
 
 /// Service for handling database operations in the animal shelter application
 pub struct DatabaseService {
@@ -37,6 +114,11 @@ impl DatabaseService {
             db_path.as_ref()
         ))?;
 
+        // Enforce foreign key constraints, which SQLite leaves disabled by default
+        connection
+            .pragma_update(None, "foreign_keys", true)
+            .context("Failed to enable foreign key enforcement")?;
+
         // Create service instance
         let service = DatabaseService { connection };
 
@@ -58,6 +140,20 @@ impl DatabaseService {
     /// # Returns
     /// * `Result<()>` - Success or error
     fn initialize_tables(&self) -> Result<()> {
+        // Create locations table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS locations (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                address TEXT NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create locations table")?;
+
         // Create animals table
         self.connection
             .execute(
@@ -75,13 +171,55 @@ impl DatabaseService {
                 status TEXT NOT NULL,
                 image_path TEXT,
                 appearance TEXT NOT NULL,
-                bio TEXT NOT NULL
+                bio TEXT NOT NULL,
+                version INTEGER NOT NULL DEFAULT 1,
+                microchip_number TEXT,
+                location_id TEXT REFERENCES locations (id),
+                updated_timestamp INTEGER NOT NULL DEFAULT 0,
+                good_with_kids BOOLEAN,
+                good_with_dogs BOOLEAN,
+                good_with_cats BOOLEAN,
+                special_needs BOOLEAN,
+                size TEXT,
+                color TEXT
             )
             ",
                 [],
             )
             .context("Failed to create animals table")?;
 
+        // Add columns introduced after the original schema to existing databases
+        add_column_if_missing(
+            &self.connection,
+            "animals",
+            "version",
+            "INTEGER NOT NULL DEFAULT 1",
+        )?;
+        add_column_if_missing(&self.connection, "animals", "microchip_number", "TEXT")?;
+        add_column_if_missing(
+            &self.connection,
+            "animals",
+            "location_id",
+            "TEXT REFERENCES locations (id)",
+        )?;
+        add_column_if_missing(
+            &self.connection,
+            "animals",
+            "updated_timestamp",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+        for column in [
+            "good_with_kids",
+            "good_with_dogs",
+            "good_with_cats",
+            "special_needs",
+        ] {
+            add_column_if_missing(&self.connection, "animals", column, "BOOLEAN")?;
+        }
+        for column in ["size", "color"] {
+            add_column_if_missing(&self.connection, "animals", column, "TEXT")?;
+        }
+
         // Create adoption_requests table
         self.connection
             .execute(
@@ -102,193 +240,1111 @@ impl DatabaseService {
                 adoption_timestamp INTEGER NOT NULL,
                 status TEXT NOT NULL,
                 country TEXT NOT NULL,
+                updated_timestamp INTEGER NOT NULL DEFAULT 0,
+                co_applicant_name TEXT,
+                co_applicant_relationship TEXT,
+                co_applicant_contact TEXT,
                 FOREIGN KEY (animal_id) REFERENCES animals (id)
             )
             ",
                 [],
             )
             .context("Failed to create adoption_requests table")?;
+        add_column_if_missing(
+            &self.connection,
+            "adoption_requests",
+            "updated_timestamp",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+        for column in [
+            "co_applicant_name",
+            "co_applicant_relationship",
+            "co_applicant_contact",
+        ] {
+            add_column_if_missing(&self.connection, "adoption_requests", column, "TEXT")?;
+        }
 
-        log::debug!("Database tables initialized successfully");
-        Ok(())
-    }
+        // Create foster_placements table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS foster_placements (
+                id TEXT PRIMARY KEY,
+                animal_id TEXT NOT NULL,
+                caregiver_name TEXT NOT NULL,
+                caregiver_email TEXT NOT NULL,
+                caregiver_tel_number TEXT NOT NULL,
+                start_timestamp INTEGER NOT NULL,
+                end_timestamp INTEGER NOT NULL,
+                ended_timestamp INTEGER,
+                FOREIGN KEY (animal_id) REFERENCES animals (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create foster_placements table")?;
 
-    // ==================== ANIMALS TABLE OPERATIONS ====================
+        // Create kennels table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS kennels (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                specie_restriction TEXT,
+                capacity INTEGER NOT NULL CHECK (capacity > 0)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create kennels table")?;
 
-    /// Retrieves summary information for all animals in the database, with optional filtering
-    ///
-    /// # Arguments
-    /// * `filters` - Optional map of filter criteria and values
-    ///
-    /// # Returns
-    /// * `Result<Vec<AnimalSummary>>` - List of animal summaries or error
-    pub fn query_animals(
-        &self,
-        filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
-    ) -> Result<Vec<AnimalSummary>> {
-        let mut query = "SELECT id, name, specie, breed, sex, admission_timestamp, status, image_path FROM animals".to_string();
-        let mut where_clauses: Vec<String> = Vec::new();
-        let mut params: Vec<rusqlite::types::Value> = Vec::new();
-
-        if let Some(filters_map) = filters {
-            if !filters_map.is_empty() {
-                for (criteria, value_option) in filters_map {
-                    // Renamed value to value_option
-                    if let Some(value) = value_option {
-                        // Added unwrap for Option<FilterValue>
-                        match criteria {
-                            FilterCriteria::Status => {
-                                if let FilterValue::ChooseMany(stati) = value {
-                                    if stati.is_empty() {
-                                        where_clauses.push("1=0".to_string()); // No matches if empty list
-                                    } else {
-                                        let placeholders: Vec<_> =
-                                            stati.iter().map(|_| "?").collect();
-                                        where_clauses.push(format!(
-                                            "status IN ({})",
-                                            placeholders.join(",")
-                                        ));
-                                        for s in stati {
-                                            params.push(rusqlite::types::Value::from(s));
-                                        }
-                                    }
-                                }
-                            }
-                            FilterCriteria::Sex => {
-                                if let FilterValue::ChooseMany(sexes) = value {
-                                    if sexes.is_empty() {
-                                        where_clauses.push("1=0".to_string()); // No matches if empty list
-                                    } else {
-                                        let placeholders: Vec<_> =
-                                            sexes.iter().map(|_| "?").collect();
-                                        where_clauses
-                                            .push(format!("sex IN ({})", placeholders.join(",")));
-                                        for s in sexes {
-                                            params.push(rusqlite::types::Value::from(s));
-                                        }
-                                    }
-                                }
-                            }
-                            FilterCriteria::SpeciesAndBreeds => {
-                                if let FilterValue::NestedChooseMany(species_map) = value {
-                                    if species_map.is_empty() {
-                                        where_clauses.push("1=0".to_string()); // No matches if empty map
-                                    } else {
-                                        let mut species_clauses = Vec::new();
-                                        for (specie, breeds) in species_map {
-                                            if !breeds.is_empty() {
-                                                let breed_placeholders: Vec<_> =
-                                                    breeds.iter().map(|_| "?").collect();
-                                                species_clauses.push(format!(
-                                                    "(specie = ? AND breed IN ({}))",
-                                                    breed_placeholders.join(",")
-                                                ));
-                                                params.push(rusqlite::types::Value::from(specie));
-                                                for b in breeds {
-                                                    params.push(rusqlite::types::Value::from(b));
-                                                }
-                                            }
-                                        }
-                                        if species_clauses.is_empty() {
-                                            where_clauses.push("1=0".to_string());
-                                        // No matches if all nested breed lists are empty
-                                        } else {
-                                            where_clauses.push(format!(
-                                                "({})",
-                                                species_clauses.join(" OR ")
-                                            ));
-                                        }
-                                    }
-                                }
-                            }
-                            FilterCriteria::AdmissionDate => {
-                                if let FilterValue::ChooseOne(date_option) = value {
-                                    if date_option == "all_time" {
-                                        continue;
-                                    }
-                                    let now = Utc::now();
-                                    let start_of_period = match date_option.as_str() {
-                                        "today" => now.date_naive().and_hms_opt(0, 0, 0).unwrap(),
-                                        "this_week" => {
-                                            let weekday = now.weekday();
-                                            let days_to_subtract = weekday.num_days_from_monday();
-                                            now.date_naive()
-                                                .checked_sub_signed(Duration::days(
-                                                    days_to_subtract as i64,
-                                                ))
-                                                .unwrap()
-                                                .and_hms_opt(0, 0, 0)
-                                                .unwrap()
-                                        }
-                                        "this_month" => now
-                                            .date_naive()
-                                            .with_day(1)
-                                            .unwrap()
-                                            .and_hms_opt(0, 0, 0)
-                                            .unwrap(),
-                                        "this_year" => now
-                                            .date_naive()
-                                            .with_ordinal(1)
-                                            .unwrap()
-                                            .and_hms_opt(0, 0, 0)
-                                            .unwrap(),
-                                        _ => continue,
-                                    };
-                                    let start_timestamp = start_of_period.and_utc().timestamp();
-                                    where_clauses.push("admission_timestamp >= ?".to_string());
-                                    params.push(rusqlite::types::Value::Integer(start_timestamp));
-                                }
-                            }
-                            FilterCriteria::AdoptionDate => {
-                                if let FilterValue::ChooseOne(date_option) = value {
-                                    if date_option == "all_time" {
-                                        continue;
-                                    }
-                                    let now = Utc::now();
-                                    let start_of_period = match date_option.as_str() {
-                                        "today" => now.date_naive().and_hms_opt(0, 0, 0).unwrap(),
-                                        "this_week" => {
-                                            let weekday = now.weekday();
-                                            let days_to_subtract = weekday.num_days_from_monday();
-                                            now.date_naive()
-                                                .checked_sub_signed(Duration::days(
-                                                    days_to_subtract as i64,
-                                                ))
-                                                .unwrap()
-                                                .and_hms_opt(0, 0, 0)
-                                                .unwrap()
-                                        }
-                                        "this_month" => now
-                                            .date_naive()
-                                            .with_day(1)
-                                            .unwrap()
-                                            .and_hms_opt(0, 0, 0)
-                                            .unwrap(),
-                                        "this_year" => now
-                                            .date_naive()
-                                            .with_ordinal(1)
-                                            .unwrap()
-                                            .and_hms_opt(0, 0, 0)
-                                            .unwrap(),
-                                        _ => continue,
-                                    };
-                                    let start_timestamp = start_of_period.and_utc().timestamp();
-                                    where_clauses.push(
-                                        "EXISTS (SELECT 1 FROM adoption_requests ar WHERE ar.animal_id = animals.id AND ar.status = 'approved' AND ar.adoption_timestamp >= ?)".to_string()
-                                    );
-                                    params.push(rusqlite::types::Value::Integer(start_timestamp));
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+        // Create kennel_assignments table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS kennel_assignments (
+                id TEXT PRIMARY KEY,
+                animal_id TEXT NOT NULL,
+                kennel_id TEXT NOT NULL,
+                assigned_timestamp INTEGER NOT NULL,
+                removed_timestamp INTEGER,
+                FOREIGN KEY (animal_id) REFERENCES animals (id),
+                FOREIGN KEY (kennel_id) REFERENCES kennels (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create kennel_assignments table")?;
+
+        // Create intake_records table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS intake_records (
+                id TEXT PRIMARY KEY,
+                animal_id TEXT NOT NULL,
+                intake_type TEXT NOT NULL,
+                source TEXT NOT NULL,
+                condition TEXT NOT NULL,
+                staff_username TEXT NOT NULL,
+                intake_timestamp INTEGER NOT NULL,
+                hold_until_timestamp INTEGER,
+                FOREIGN KEY (animal_id) REFERENCES animals (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create intake_records table")?;
+        add_column_if_missing(
+            &self.connection,
+            "intake_records",
+            "hold_until_timestamp",
+            "INTEGER",
+        )?;
+
+        // Create contacts table for the vets, rescues and transport partners the shelter works with
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS contacts (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                tel_number TEXT NOT NULL,
+                email TEXT NOT NULL,
+                address TEXT NOT NULL,
+                notes TEXT NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create contacts table")?;
+
+        // Create transfers table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS transfers (
+                id TEXT PRIMARY KEY,
+                animal_id TEXT NOT NULL,
+                direction TEXT NOT NULL,
+                from_location_id TEXT,
+                to_location_id TEXT,
+                partner_organization TEXT,
+                reason TEXT NOT NULL,
+                staff_username TEXT NOT NULL,
+                transfer_timestamp INTEGER NOT NULL,
+                contact_id TEXT,
+                FOREIGN KEY (animal_id) REFERENCES animals (id),
+                FOREIGN KEY (from_location_id) REFERENCES locations (id),
+                FOREIGN KEY (to_location_id) REFERENCES locations (id),
+                FOREIGN KEY (contact_id) REFERENCES contacts (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create transfers table")?;
+        add_column_if_missing(
+            &self.connection,
+            "transfers",
+            "contact_id",
+            "TEXT REFERENCES contacts (id)",
+        )?;
+
+        // Create animal_notes table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS animal_notes (
+                id TEXT PRIMARY KEY,
+                animal_id TEXT NOT NULL,
+                author_username TEXT NOT NULL,
+                category TEXT NOT NULL,
+                content TEXT NOT NULL,
+                internal BOOLEAN NOT NULL,
+                created_timestamp INTEGER NOT NULL,
+                FOREIGN KEY (animal_id) REFERENCES animals (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create animal_notes table")?;
+
+        // Create vaccinations table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS vaccinations (
+                id TEXT PRIMARY KEY,
+                animal_id TEXT NOT NULL,
+                vaccine TEXT NOT NULL,
+                administered_timestamp INTEGER NOT NULL,
+                due_timestamp INTEGER,
+                recorded_by TEXT NOT NULL,
+                contact_id TEXT,
+                FOREIGN KEY (animal_id) REFERENCES animals (id),
+                FOREIGN KEY (contact_id) REFERENCES contacts (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create vaccinations table")?;
+        add_column_if_missing(
+            &self.connection,
+            "vaccinations",
+            "contact_id",
+            "TEXT REFERENCES contacts (id)",
+        )?;
+
+        // Create adoption_returns table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS adoption_returns (
+                id TEXT PRIMARY KEY,
+                adoption_request_id TEXT NOT NULL UNIQUE,
+                animal_id TEXT NOT NULL,
+                return_timestamp INTEGER NOT NULL,
+                reason TEXT NOT NULL,
+                FOREIGN KEY (adoption_request_id) REFERENCES adoption_requests (id),
+                FOREIGN KEY (animal_id) REFERENCES animals (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create adoption_returns table")?;
+
+        // Create trial_adoptions table, holding the trial period of adoptions approved on trial
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS trial_adoptions (
+                id TEXT PRIMARY KEY,
+                adoption_request_id TEXT NOT NULL UNIQUE,
+                animal_id TEXT NOT NULL,
+                started_timestamp INTEGER NOT NULL,
+                end_timestamp INTEGER NOT NULL,
+                FOREIGN KEY (adoption_request_id) REFERENCES adoption_requests (id),
+                FOREIGN KEY (animal_id) REFERENCES animals (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create trial_adoptions table")?;
+
+        // Create deceased_records table; an animal passes away at most once
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS deceased_records (
+                id TEXT PRIMARY KEY,
+                animal_id TEXT NOT NULL UNIQUE,
+                death_timestamp INTEGER NOT NULL,
+                cause TEXT NOT NULL,
+                authorized_by TEXT NOT NULL,
+                notes TEXT NOT NULL,
+                FOREIGN KEY (animal_id) REFERENCES animals (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create deceased_records table")?;
+
+        // Create animal_relationships table; each bond is stored once with the smaller ID first
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS animal_relationships (
+                animal_id TEXT NOT NULL,
+                bonded_animal_id TEXT NOT NULL,
+                PRIMARY KEY (animal_id, bonded_animal_id),
+                CHECK (animal_id < bonded_animal_id),
+                FOREIGN KEY (animal_id) REFERENCES animals (id),
+                FOREIGN KEY (bonded_animal_id) REFERENCES animals (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create animal_relationships table")?;
+
+        // Create waitlist table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS waitlist (
+                id TEXT PRIMARY KEY,
+                username TEXT NOT NULL,
+                specie TEXT NOT NULL,
+                breed TEXT,
+                created_timestamp INTEGER NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create waitlist table")?;
+
+        // Create notifications table; animal_id is informational and outlives the animal
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS notifications (
+                id TEXT PRIMARY KEY,
+                username TEXT NOT NULL,
+                message TEXT NOT NULL,
+                animal_id TEXT,
+                created_timestamp INTEGER NOT NULL,
+                read BOOLEAN NOT NULL DEFAULT 0
+            )
+            ",
+                [],
+            )
+            .context("Failed to create notifications table")?;
+
+        // Create favorites table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS favorites (
+                username TEXT NOT NULL,
+                animal_id TEXT NOT NULL,
+                created_timestamp INTEGER NOT NULL,
+                PRIMARY KEY (username, animal_id),
+                FOREIGN KEY (animal_id) REFERENCES animals (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create favorites table")?;
+
+        // Create banned adopters table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS banned_adopters (
+                id TEXT PRIMARY KEY,
+                name_pattern TEXT,
+                email_pattern TEXT,
+                tel_number_pattern TEXT,
+                reason TEXT NOT NULL,
+                added_by TEXT NOT NULL,
+                created_timestamp INTEGER NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create banned adopters table")?;
+
+        // Create user profiles table
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS user_profiles (
+                username TEXT PRIMARY KEY,
+                full_name TEXT NOT NULL,
+                email TEXT NOT NULL,
+                tel_number TEXT NOT NULL,
+                address TEXT NOT NULL,
+                country TEXT NOT NULL,
+                updated_timestamp INTEGER NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create user profiles table")?;
+
+        // Create trash tables; deleted records keep their columns until restored or purged
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS trashed_animals (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                specie TEXT NOT NULL,
+                breed TEXT NOT NULL,
+                sex TEXT NOT NULL,
+                birth_month INTEGER,
+                birth_year INTEGER,
+                neutered BOOLEAN NOT NULL,
+                admission_timestamp INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                image_path TEXT,
+                appearance TEXT NOT NULL,
+                bio TEXT NOT NULL,
+                version INTEGER NOT NULL DEFAULT 1,
+                microchip_number TEXT,
+                location_id TEXT,
+                good_with_kids BOOLEAN,
+                good_with_dogs BOOLEAN,
+                good_with_cats BOOLEAN,
+                special_needs BOOLEAN,
+                size TEXT,
+                color TEXT,
+                deleted_timestamp INTEGER NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create trashed_animals table")?;
+        add_column_if_missing(&self.connection, "trashed_animals", "location_id", "TEXT")?;
+        for column in [
+            "good_with_kids",
+            "good_with_dogs",
+            "good_with_cats",
+            "special_needs",
+        ] {
+            add_column_if_missing(&self.connection, "trashed_animals", column, "BOOLEAN")?;
+        }
+        for column in ["size", "color"] {
+            add_column_if_missing(&self.connection, "trashed_animals", column, "TEXT")?;
+        }
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS trashed_adoption_requests (
+                id TEXT PRIMARY KEY,
+                animal_id TEXT NOT NULL,
+                username TEXT NOT NULL,
+                name TEXT NOT NULL,
+                email TEXT NOT NULL,
+                tel_number TEXT NOT NULL,
+                address TEXT NOT NULL,
+                occupation TEXT NOT NULL,
+                annual_income TEXT NOT NULL,
+                num_people INTEGER NOT NULL,
+                num_children INTEGER NOT NULL,
+                request_timestamp INTEGER NOT NULL,
+                adoption_timestamp INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                country TEXT NOT NULL,
+                co_applicant_name TEXT,
+                co_applicant_relationship TEXT,
+                co_applicant_contact TEXT,
+                deleted_timestamp INTEGER NOT NULL,
+                deleted_with_animal BOOLEAN NOT NULL DEFAULT 0
+            )
+            ",
+                [],
+            )
+            .context("Failed to create trashed_adoption_requests table")?;
+        for column in [
+            "co_applicant_name",
+            "co_applicant_relationship",
+            "co_applicant_contact",
+        ] {
+            add_column_if_missing(
+                &self.connection,
+                "trashed_adoption_requests",
+                column,
+                "TEXT",
+            )?;
+        }
+
+        // The other records of trashed animals are kept as JSON, one row per record
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS trashed_animal_records (
+                animal_id TEXT NOT NULL,
+                table_name TEXT NOT NULL,
+                record TEXT NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create trashed_animal_records table")?;
+
+        // Create webhook tables; deliveries double as the log of every attempt
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS webhooks (
+                id TEXT PRIMARY KEY,
+                url TEXT NOT NULL,
+                secret TEXT NOT NULL,
+                event_types TEXT NOT NULL,
+                created_timestamp INTEGER NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create webhooks table")?;
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS webhook_deliveries (
+                id TEXT PRIMARY KEY,
+                webhook_id TEXT NOT NULL,
+                event TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                status TEXT NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                response_code INTEGER,
+                error TEXT,
+                created_timestamp INTEGER NOT NULL,
+                attempted_timestamp INTEGER,
+                next_attempt_timestamp INTEGER NOT NULL,
+                FOREIGN KEY (webhook_id) REFERENCES webhooks (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create webhook_deliveries table")?;
+
+        // Create sent_reminders table, so each reminder is only given once per record
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS sent_reminders (
+                rule TEXT NOT NULL,
+                record_key TEXT NOT NULL,
+                sent_timestamp INTEGER NOT NULL,
+                PRIMARY KEY (rule, record_key)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create sent_reminders table")?;
+
+        // Create emails table, the send log of emails to applicants
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS emails (
+                id TEXT PRIMARY KEY,
+                request_id TEXT NOT NULL,
+                request_status TEXT NOT NULL,
+                recipient TEXT NOT NULL,
+                recipient_name TEXT NOT NULL,
+                animal_name TEXT NOT NULL,
+                subject TEXT,
+                body TEXT,
+                status TEXT NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                error TEXT,
+                created_timestamp INTEGER NOT NULL,
+                attempted_timestamp INTEGER
+            )
+            ",
+                [],
+            )
+            .context("Failed to create emails table")?;
+
+        // Create erasure_requests table, holding customers' requests to erase their data
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS erasure_requests (
+                id TEXT PRIMARY KEY,
+                username TEXT NOT NULL,
+                status TEXT NOT NULL,
+                requested_timestamp INTEGER NOT NULL,
+                reviewed_by TEXT,
+                reviewed_timestamp INTEGER
+            )
+            ",
+                [],
+            )
+            .context("Failed to create erasure_requests table")?;
+
+        // Create screening_scores table, kept apart from adoption_requests so scoring a
+        // request does not count as a change to sync
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS screening_scores (
+                request_id TEXT PRIMARY KEY,
+                score INTEGER NOT NULL,
+                scored_timestamp INTEGER NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create screening_scores table")?;
+
+        // Create audit_log table, recording what staff members did for the activity feed
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS audit_log (
+                id TEXT PRIMARY KEY,
+                username TEXT NOT NULL,
+                action TEXT NOT NULL,
+                record_id TEXT NOT NULL,
+                animal_id TEXT,
+                animal_name TEXT,
+                timestamp INTEGER NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create audit_log table")?;
+
+        // Create inventory_items table for the supplies kept in stock
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS inventory_items (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                unit TEXT NOT NULL,
+                quantity_on_hand REAL NOT NULL,
+                reorder_threshold REAL NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create inventory_items table")?;
+
+        // Create inventory_adjustments table, recording each change to the stock of an item
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS inventory_adjustments (
+                id TEXT PRIMARY KEY,
+                item_id TEXT NOT NULL,
+                quantity_change REAL NOT NULL,
+                reason TEXT NOT NULL,
+                username TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                FOREIGN KEY (item_id) REFERENCES inventory_items (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create inventory_adjustments table")?;
+
+        // Create expenses table; an expense may be linked to the animal it was spent on.
+        // Like payments and donations, its amount is stored in minor currency units, such
+        // as satang, so that sums are exact
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS expenses (
+                id TEXT PRIMARY KEY,
+                category TEXT NOT NULL,
+                amount INTEGER NOT NULL,
+                expense_timestamp INTEGER NOT NULL,
+                vendor TEXT NOT NULL,
+                animal_id TEXT,
+                FOREIGN KEY (animal_id) REFERENCES animals (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create expenses table")?;
+
+        // Create appointments table for the times customers book to meet an animal
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS appointments (
+                id TEXT PRIMARY KEY,
+                animal_id TEXT NOT NULL,
+                username TEXT NOT NULL,
+                slot_timestamp INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                created_timestamp INTEGER NOT NULL,
+                FOREIGN KEY (animal_id) REFERENCES animals (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create appointments table")?;
+
+        // Create the surgeries table for spay and neuter surgeries, linked to the
+        // veterinarian performing them
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS surgeries (
+                id TEXT PRIMARY KEY,
+                animal_id TEXT NOT NULL,
+                scheduled_timestamp INTEGER NOT NULL,
+                contact_id TEXT,
+                completed_timestamp INTEGER,
+                notes TEXT NOT NULL,
+                FOREIGN KEY (animal_id) REFERENCES animals (id),
+                FOREIGN KEY (contact_id) REFERENCES contacts (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create surgeries table")?;
+
+        // Create the request_interviews table for phone interviews with applicants; like
+        // screening_scores it has no foreign key, so the interviews of a request moved to
+        // the trash are kept and come back with it when it is restored
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS request_interviews (
+                id TEXT PRIMARY KEY,
+                request_id TEXT NOT NULL,
+                interviewer TEXT NOT NULL,
+                interview_timestamp INTEGER NOT NULL,
+                notes TEXT NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create request_interviews table")?;
+
+        // Create the reference_checks table for the references applicants give, kept
+        // through the trash in the same way
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS reference_checks (
+                id TEXT PRIMARY KEY,
+                request_id TEXT NOT NULL,
+                referee_name TEXT NOT NULL,
+                referee_contact TEXT NOT NULL,
+                outcome TEXT NOT NULL,
+                notes TEXT NOT NULL,
+                checked_by TEXT NOT NULL,
+                checked_timestamp INTEGER NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create reference_checks table")?;
+
+        // Create the payments table for the fees charged for adoption requests. Payments
+        // are accounting records, so they have no foreign key and are kept even once their
+        // request is purged from the trash
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS payments (
+                id TEXT PRIMARY KEY,
+                request_id TEXT NOT NULL,
+                amount INTEGER NOT NULL,
+                description TEXT NOT NULL,
+                created_timestamp INTEGER NOT NULL,
+                paid_timestamp INTEGER,
+                invoice_number INTEGER UNIQUE,
+                receipt_number INTEGER UNIQUE
+            )
+            ",
+                [],
+            )
+            .context("Failed to create payments table")?;
+
+        // Create the donations table for the gifts donors make to the shelter. The donor's
+        // name is kept with each donation so its receipt can still be printed once the
+        // donor's contact is deleted
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS donations (
+                id TEXT PRIMARY KEY,
+                contact_id TEXT,
+                donor_name TEXT NOT NULL,
+                amount INTEGER NOT NULL,
+                donation_timestamp INTEGER NOT NULL,
+                notes TEXT NOT NULL,
+                receipt_number INTEGER UNIQUE,
+                FOREIGN KEY (contact_id) REFERENCES contacts (id)
+            )
+            ",
+                [],
+            )
+            .context("Failed to create donations table")?;
+
+        // Create the document_sequences table holding the last number issued for each kind
+        // of payment or donation document, so a number is never issued twice
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS document_sequences (
+                name TEXT PRIMARY KEY,
+                last_number INTEGER NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create document_sequences table")?;
+
+        // Create the maintenance_runs table, the log of when the database was last
+        // compacted, so scheduled maintenance knows when it is due
+        self.connection
+            .execute(
+                "
+            CREATE TABLE IF NOT EXISTS maintenance_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_timestamp INTEGER NOT NULL
+            )
+            ",
+                [],
+            )
+            .context("Failed to create maintenance_runs table")?;
+
+        // Bring the data of existing databases up to date
+        self.migrate_data()
+            .context("Failed to migrate existing database data")?;
+
+        // Create indexes for common lookups, on new and existing databases alike
+        self.create_indexes()
+            .context("Failed to create database indexes")?;
+
+        // Track when synced records change, so they can be exported to other installations
+        self.create_change_tracking_triggers()
+            .context("Failed to create change tracking triggers")?;
+
+        log::debug!("Database tables initialized successfully");
+        Ok(())
+    }
+
+    /// Reads the version of the data migrations applied to the database, which also
+    /// checks the database can be queried
+    ///
+    /// # Returns
+    /// * `Result<i64>` - The version, equal to `DATA_VERSION` once migrations have run
+    pub fn schema_version(&self) -> Result<i64> {
+        self.connection
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .context("Failed to read database user version")
+    }
+
+    /// Applies one-off data migrations that have not yet run on this database,
+    /// tracked through SQLite's `user_version` pragma
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn migrate_data(&self) -> Result<()> {
+        let data_version = self.schema_version()?;
+
+        if data_version < 1 {
+            self.migrate_numeric_ids_to_uuids()?;
+        }
+        if data_version < 2 {
+            self.migrate_unknown_birth_dates()?;
+        }
+        if data_version < 3 {
+            self.migrate_amounts_to_minor_units()?;
+        }
+
+        self.connection
+            .pragma_update(None, "user_version", DATA_VERSION)
+            .context("Failed to update database user version")?;
+        Ok(())
+    }
+
+    /// Replaces the 0 that earlier versions stored for an unknown birth month or year
+    /// with NULL, in both live and trashed animals
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn migrate_unknown_birth_dates(&self) -> Result<()> {
+        self.connection
+            .execute_batch(
+                "
+            UPDATE animals SET birth_month = NULL WHERE birth_month = 0;
+            UPDATE animals SET birth_year = NULL WHERE birth_year = 0;
+            UPDATE trashed_animals SET birth_month = NULL WHERE birth_month = 0;
+            UPDATE trashed_animals SET birth_year = NULL WHERE birth_year = 0;
+            ",
+            )
+            .context("Failed to clear unknown birth dates")?;
+        Ok(())
+    }
+
+    /// Converts the amounts of money earlier versions stored as REAL major units into
+    /// INTEGER minor units, rounding to the nearest one
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn migrate_amounts_to_minor_units(&self) -> Result<()> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for amount migration")?;
+
+        // SQLite cannot change the type of a column, so the amounts are copied into a new one
+        for table in ["expenses", "payments", "donations"] {
+            transaction
+                .execute_batch(&format!(
+                    "
+            ALTER TABLE {0} RENAME COLUMN amount TO amount_major;
+            ALTER TABLE {0} ADD COLUMN amount INTEGER NOT NULL DEFAULT 0;
+            UPDATE {0} SET amount = CAST(ROUND(amount_major * {1}) AS INTEGER);
+            ALTER TABLE {0} DROP COLUMN amount_major;
+            ",
+                    table, MINOR_UNITS_PER_UNIT
+                ))
+                .context(format!("Failed to migrate amounts in {}", table))?;
+        }
+
+        transaction
+            .commit()
+            .context("Failed to commit amount migration")?;
+        Ok(())
+    }
+
+    /// Replaces the sequential numeric IDs generated by earlier versions with UUIDs,
+    /// updating the adoption requests that reference migrated animals
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn migrate_numeric_ids_to_uuids(&self) -> Result<()> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for ID migration")?;
+
+        // Check foreign keys at commit time, since animals and their requests change in separate statements
+        transaction
+            .pragma_update(None, "defer_foreign_keys", true)
+            .context("Failed to defer foreign key checks")?;
+
+        for table in ["animals", "adoption_requests"] {
+            let numeric_ids: Vec<String> = {
+                let mut statement = transaction
+                    .prepare(&format!(
+                        "SELECT id FROM {} WHERE id <> '' AND id NOT GLOB '*[^0-9]*'",
+                        table
+                    ))
+                    .context(format!("Failed to query numeric IDs in {}", table))?;
+                let ids = statement
+                    .query_map([], |row| row.get(0))
+                    .context(format!("Failed to query numeric IDs in {}", table))?
+                    .collect::<rusqlite::Result<Vec<String>>>()
+                    .context(format!("Failed to parse numeric IDs in {}", table))?;
+                ids
+            };
+
+            for old_id in &numeric_ids {
+                let new_id = Uuid::new_v4().to_string();
+                transaction
+                    .execute(
+                        &format!("UPDATE {} SET id = ?2 WHERE id = ?1", table),
+                        params![old_id, new_id],
+                    )
+                    .context(format!("Failed to migrate ID {} in {}", old_id, table))?;
+                if table == "animals" {
+                    transaction
+                        .execute(
+                            "UPDATE adoption_requests SET animal_id = ?2 WHERE animal_id = ?1",
+                            params![old_id, new_id],
+                        )
+                        .context(format!(
+                            "Failed to update adoption requests for animal ID {}",
+                            old_id
+                        ))?;
+                }
+            }
+
+            if !numeric_ids.is_empty() {
+                log::info!(
+                    "Migrated {} numeric IDs in {} to UUIDs",
+                    numeric_ids.len(),
+                    table
+                );
+            }
+        }
+
+        transaction
+            .commit()
+            .context("Failed to commit ID migration")?;
+        Ok(())
+    }
+
+    /// Creates the indexes used by animal filters and adoption request lookups
+    /// if they don't exist
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn create_indexes(&self) -> Result<()> {
+        self.connection
+            .execute_batch(
+                "
+            CREATE INDEX IF NOT EXISTS idx_animals_status ON animals (status);
+            CREATE INDEX IF NOT EXISTS idx_animals_sex ON animals (sex);
+            CREATE INDEX IF NOT EXISTS idx_animals_specie_breed ON animals (specie, breed);
+            CREATE INDEX IF NOT EXISTS idx_animals_admission_timestamp ON animals (admission_timestamp);
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_animals_microchip_number ON animals (microchip_number);
+            CREATE INDEX IF NOT EXISTS idx_animals_location_id ON animals (location_id);
+            CREATE INDEX IF NOT EXISTS idx_adoption_requests_animal_id
+                ON adoption_requests (animal_id, status, adoption_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_adoption_requests_username
+                ON adoption_requests (username, animal_id, status);
+            CREATE INDEX IF NOT EXISTS idx_foster_placements_animal_id
+                ON foster_placements (animal_id);
+            CREATE INDEX IF NOT EXISTS idx_adoption_returns_animal_id
+                ON adoption_returns (animal_id, return_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_trial_adoptions_animal_id
+                ON trial_adoptions (animal_id);
+            CREATE INDEX IF NOT EXISTS idx_animal_notes_animal_id
+                ON animal_notes (animal_id, created_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_vaccinations_animal_id
+                ON vaccinations (animal_id, administered_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_intake_records_animal_id
+                ON intake_records (animal_id, intake_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_animals_updated_timestamp
+                ON animals (updated_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_adoption_requests_updated_timestamp
+                ON adoption_requests (updated_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_transfers_animal_id
+                ON transfers (animal_id, transfer_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_animal_relationships_bonded_animal_id
+                ON animal_relationships (bonded_animal_id);
+            CREATE INDEX IF NOT EXISTS idx_waitlist_specie_breed ON waitlist (specie, breed);
+            CREATE INDEX IF NOT EXISTS idx_waitlist_username ON waitlist (username);
+            CREATE INDEX IF NOT EXISTS idx_favorites_animal_id ON favorites (animal_id);
+            CREATE INDEX IF NOT EXISTS idx_trashed_animals_deleted_timestamp
+                ON trashed_animals (deleted_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_trashed_adoption_requests_animal_id
+                ON trashed_adoption_requests (animal_id);
+            CREATE INDEX IF NOT EXISTS idx_trashed_animal_records_animal_id
+                ON trashed_animal_records (animal_id);
+            CREATE INDEX IF NOT EXISTS idx_notifications_username
+                ON notifications (username, created_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_erasure_requests_status
+                ON erasure_requests (status, requested_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_audit_log_timestamp
+                ON audit_log (timestamp);
+            CREATE INDEX IF NOT EXISTS idx_inventory_adjustments_item_id
+                ON inventory_adjustments (item_id, timestamp);
+            CREATE INDEX IF NOT EXISTS idx_expenses_expense_timestamp
+                ON expenses (expense_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_appointments_animal_id
+                ON appointments (animal_id, slot_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_appointments_slot_timestamp
+                ON appointments (slot_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_surgeries_animal_id
+                ON surgeries (animal_id, completed_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_request_interviews_request_id
+                ON request_interviews (request_id);
+            CREATE INDEX IF NOT EXISTS idx_reference_checks_request_id
+                ON reference_checks (request_id);
+            CREATE INDEX IF NOT EXISTS idx_payments_request_id
+                ON payments (request_id, created_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_donations_contact_id
+                ON donations (contact_id, donation_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_kennel_assignments_animal_id
+                ON kennel_assignments (animal_id, removed_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_kennel_assignments_kennel_id
+                ON kennel_assignments (kennel_id, removed_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook_id
+                ON webhook_deliveries (webhook_id, created_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_status
+                ON webhook_deliveries (status, next_attempt_timestamp);
+            CREATE INDEX IF NOT EXISTS idx_emails_status
+                ON emails (status, created_timestamp);
+            ",
+            )
+            .context("Failed to create indexes")?;
+        Ok(())
+    }
+
+    /// Creates the triggers stamping animals and adoption requests with the time they last
+    /// changed, unless the statement sets the timestamp itself
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn create_change_tracking_triggers(&self) -> Result<()> {
+        for table in ["animals", "adoption_requests"] {
+            self.connection
+                .execute_batch(&format!(
+                    "
+            CREATE TRIGGER IF NOT EXISTS {0}_stamp_insert AFTER INSERT ON {0}
+                WHEN NEW.updated_timestamp = 0
+            BEGIN
+                UPDATE {0} SET updated_timestamp = CAST(strftime('%s', 'now') AS INTEGER)
+                    WHERE id = NEW.id;
+            END;
+            CREATE TRIGGER IF NOT EXISTS {0}_stamp_update AFTER UPDATE ON {0}
+                WHEN NEW.updated_timestamp = OLD.updated_timestamp
+            BEGIN
+                UPDATE {0} SET updated_timestamp = CAST(strftime('%s', 'now') AS INTEGER)
+                    WHERE id = NEW.id;
+            END;
+            ",
+                    table
+                ))
+                .context(format!("Failed to create triggers on {}", table))?;
         }
+        Ok(())
+    }
 
-        if !where_clauses.is_empty() {
-            query.push_str(" WHERE ");
-            query.push_str(&where_clauses.join(" AND "));
+    /// Runs a query returning a single text column and collects its values
+    ///
+    /// # Arguments
+    /// * `query` - The query to run; must select exactly one text column
+    /// * `params` - The values for the query's placeholders
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - The selected values or error
+    fn query_distinct_values(&self, query: &str, params: &[&dyn ToSql]) -> Result<Vec<String>> {
+        let mut statement = self.connection.prepare(query)?;
+        let value_iter = statement.query_map(params, |row| row.get(0))?;
+
+        let mut values = Vec::new();
+        for value in value_iter {
+            values.push(value?);
         }
+        Ok(values)
+    }
+
+    // ==================== ANIMALS TABLE OPERATIONS ====================
+
+    /// Retrieves summary information for all animals in the database, with optional filtering
+    ///
+    /// # Arguments
+    /// * `filters` - Optional map of filter criteria and values
+    ///
+    /// # Returns
+    /// * `Result<Vec<AnimalSummary>>` - List of animal summaries or error
+    pub fn query_animals(
+        &self,
+        filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
+    ) -> Result<Vec<AnimalSummary>> {
+        let (where_clause, params) = animal_filter_clause(filters);
+        let query = format!(
+            "SELECT id, name, specie, breed, sex, admission_timestamp, status, image_path FROM animals{}",
+            where_clause
+        );
 
         let mut statement = self
             .connection
@@ -319,6 +1375,66 @@ impl DatabaseService {
         Ok(animals)
     }
 
+    /// Counts the animals in the database, with optional filtering
+    ///
+    /// # Arguments
+    /// * `filters` - Optional map of filter criteria and values, as for `query_animals`
+    ///
+    /// # Returns
+    /// * `Result<i64>` - The number of matching animals or error
+    pub fn count_animals(
+        &self,
+        filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
+    ) -> Result<i64> {
+        let (where_clause, params) = animal_filter_clause(filters);
+        let query = format!("SELECT COUNT(*) FROM animals{}", where_clause);
+
+        self.connection
+            .query_row(&query, rusqlite::params_from_iter(params.iter()), |row| {
+                row.get(0)
+            })
+            .context(format!("Failed to count animals: {}", query))
+    }
+
+    /// Retrieves the distinct species of the animals in the database
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - Sorted list of species or error
+    pub fn query_distinct_species(&self) -> Result<Vec<String>> {
+        self.query_distinct_values(
+            "SELECT DISTINCT specie FROM animals ORDER BY specie",
+            params![],
+        )
+        .context("Failed to query distinct species")
+    }
+
+    /// Retrieves the distinct colors recorded for the animals in the database
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - Sorted list of colors or error
+    pub fn query_distinct_colors(&self) -> Result<Vec<String>> {
+        self.query_distinct_values(
+            "SELECT DISTINCT color FROM animals WHERE color IS NOT NULL ORDER BY color",
+            params![],
+        )
+        .context("Failed to query distinct colors")
+    }
+
+    /// Retrieves the distinct breeds of the animals of a species in the database
+    ///
+    /// # Arguments
+    /// * `specie` - The species to retrieve breeds for
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - Sorted list of breeds or error
+    pub fn query_distinct_breeds(&self, specie: &str) -> Result<Vec<String>> {
+        self.query_distinct_values(
+            "SELECT DISTINCT breed FROM animals WHERE specie = ?1 ORDER BY breed",
+            params![specie],
+        )
+        .context(format!("Failed to query distinct breeds of {}", specie))
+    }
+
     /// Retrieves complete information for a specific animal by ID
     ///
     /// # Arguments
@@ -327,28 +1443,16 @@ impl DatabaseService {
     /// # Returns
     /// * `Result<Option<Animal>>` - Complete animal information or None if not found
     pub fn query_animal_by_id(&self, animal_id: &str) -> Result<Option<Animal>> {
-        let mut statement = self.connection.prepare(
-            "SELECT id, name, specie, breed, sex, birth_month, birth_year, neutered, admission_timestamp, status, image_path, appearance, bio FROM animals WHERE id = ?1"
-        ).context("Failed to prepare query for animal by ID")?;
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM animals WHERE id = ?1",
+                ANIMAL_COLUMNS
+            ))
+            .context("Failed to prepare query for animal by ID")?;
 
         let mut rows = statement
-            .query_map(params![animal_id], |row| {
-                Ok(Animal {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    specie: row.get(2)?,
-                    breed: row.get(3)?,
-                    sex: row.get(4)?,
-                    birth_month: row.get(5)?,
-                    birth_year: row.get(6)?,
-                    neutered: row.get(7)?,
-                    admission_timestamp: row.get(8)?,
-                    status: row.get(9)?,
-                    image_path: row.get(10)?,
-                    appearance: row.get(11)?,
-                    bio: row.get(12)?,
-                })
-            })
+            .query_map(params![animal_id], animal_from_row)
             .context("Failed to execute query for animal by ID")?;
 
         match rows.next() {
@@ -364,30 +1468,122 @@ impl DatabaseService {
         }
     }
 
-    /// Inserts a new animal into the database
+    /// Retrieves complete information for the animal with a specific microchip number
+    ///
+    /// # Arguments
+    /// * `microchip_number` - The microchip number to look up
+    ///
+    /// # Returns
+    /// * `Result<Option<Animal>>` - Complete animal information or None if no animal has the microchip
+    pub fn query_animal_by_microchip(&self, microchip_number: &str) -> Result<Option<Animal>> {
+        let microchip_number = microchip_number.trim();
+        let animal = self
+            .connection
+            .query_row(
+                &format!(
+                    "SELECT {} FROM animals WHERE microchip_number = ?1",
+                    ANIMAL_COLUMNS
+                ),
+                params![microchip_number],
+                animal_from_row,
+            )
+            .optional()
+            .context("Failed to query animal by microchip number")?;
+
+        log::debug!(
+            "Microchip {} lookup found animal: {}",
+            microchip_number,
+            animal.is_some()
+        );
+        Ok(animal)
+    }
+
+    /// Inserts a new animal into the database and notifies customers waiting for its species or breed
+    ///
+    /// Fails with a `ValidationError` listing the invalid fields if the animal does not validate.
+    ///
+    /// # Arguments
+    /// * `animal` - The animal information to insert
+    ///
+    /// # Returns
+    /// * `Result<Vec<Notification>>` - Notifications created for matching waitlist entries, or error
+    pub fn insert_animal(&self, animal: &Animal) -> Result<Vec<Notification>> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for animal insertion")?;
+
+        let id = self.insert_animal_row(animal)?;
+        let notifications = self
+            .notify_waitlist(&id, animal)
+            .context("Failed to notify waitlist of new animal")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit animal insertion")?;
+
+        log::info!("Successfully inserted animal with ID: {}", id);
+        Ok(notifications)
+    }
+
+    /// Inserts several animals in a single transaction; if any insert fails, none are kept
+    ///
+    /// # Arguments
+    /// * `animals` - The animals to insert
+    ///
+    /// # Returns
+    /// * `Result<(Vec<String>, Vec<Notification>)>` - The IDs of the inserted animals in input order,
+    ///   and the notifications created for matching waitlist entries, or error
+    pub fn insert_animals_batch(
+        &self,
+        animals: &[Animal],
+    ) -> Result<(Vec<String>, Vec<Notification>)> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for batch animal insertion")?;
+
+        let mut ids = Vec::with_capacity(animals.len());
+        let mut notifications = Vec::new();
+        for (index, animal) in animals.iter().enumerate() {
+            let id = self
+                .insert_animal_row(animal)
+                .with_context(|| format!("Failed to insert animal at position {}", index))?;
+            notifications.extend(
+                self.notify_waitlist(&id, animal)
+                    .context("Failed to notify waitlist of new animal")?,
+            );
+            ids.push(id);
+        }
+
+        transaction
+            .commit()
+            .context("Failed to commit batch animal insertion")?;
+
+        log::info!("Successfully inserted batch of {} animals", ids.len());
+        Ok((ids, notifications))
+    }
+
+    /// Inserts the row for a new animal and queues its webhook event, without committing
+    /// or notifying waiting users
     ///
     /// # Arguments
     /// * `animal` - The animal information to insert
     ///
     /// # Returns
-    /// * `Result<()>` - Success or error
-    pub fn insert_animal(&self, animal: &Animal) -> Result<()> {
+    /// * `Result<String>` - The ID of the inserted animal, generated if it was empty
+    fn insert_animal_row(&self, animal: &Animal) -> Result<String> {
+        validation::ensure_valid(validation::validate_animal(animal))?;
+        validation::ensure_valid(validation::validate_initial_status(&animal.status))?;
+
         // Auto-generate ID if not provided (or empty)
         let id = if animal.id.trim().is_empty() {
-            let max_id: i64 = self
-                .connection
-                .query_row(
-                    "SELECT COALESCE(MAX(CAST(id AS INTEGER)), 0) FROM animals",
-                    [],
-                    |row| row.get(0),
-                )
-                .context("Failed to query max animal ID")?;
-            (max_id + 1).to_string()
+            Uuid::new_v4().to_string()
         } else {
             animal.id.clone()
         };
         let rows_affected = self.connection.execute(
-            "INSERT INTO animals (id, name, specie, breed, sex, birth_month, birth_year, neutered, admission_timestamp, status, image_path, appearance, bio) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            "INSERT INTO animals (id, name, specie, breed, sex, birth_month, birth_year, neutered, admission_timestamp, status, image_path, appearance, bio, microchip_number, location_id, good_with_kids, good_with_dogs, good_with_cats, special_needs, size, color) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)",
             params![
                 id,
                 animal.name,
@@ -401,13 +1597,28 @@ impl DatabaseService {
                 animal.status,
                 animal.image_path,
                 animal.appearance,
-                animal.bio
+                animal.bio,
+                normalize_microchip_number(&animal.microchip_number),
+                animal.location_id,
+                animal.good_with_kids,
+                animal.good_with_dogs,
+                animal.good_with_cats,
+                animal.special_needs,
+                animal.size,
+                normalize_color(&animal.color)
             ]
         ).context("Failed to insert animal into database")?;
 
         if rows_affected == 1 {
-            log::info!("Successfully inserted animal with ID: {}", id);
-            Ok(())
+            self.queue_webhook_event(
+                WebhookEvent::AnimalCreated,
+                &Animal {
+                    id: id.clone(),
+                    ..animal.clone()
+                },
+                Utc::now().timestamp(),
+            )?;
+            Ok(id)
         } else {
             bail!(
                 "Unexpected number of rows affected when inserting animal: {}",
@@ -418,14 +1629,41 @@ impl DatabaseService {
 
     /// Updates an existing animal in the database
     ///
+    /// The update only applies if the caller's version matches the stored version,
+    /// so concurrent edits are reported as conflicts instead of silently overwriting each other.
+    /// Fails with a `ValidationError` listing the invalid fields if the animal does not validate,
+    /// or if the edit marks the animal as passed away, which `record_death` does instead.
+    ///
     /// # Arguments
-    /// * `animal` - The updated animal information
+    /// * `animal` - The updated animal information, carrying the version it was read at
     ///
     /// # Returns
-    /// * `Result<bool>` - True if animal was found and updated, false if not found
-    pub fn update_animal(&self, animal: &Animal) -> Result<bool> {
-        let rows_affected = self.connection.execute(
-            "UPDATE animals SET name = ?2, specie = ?3, breed = ?4, sex = ?5, birth_month = ?6, birth_year = ?7, neutered = ?8, admission_timestamp = ?9, status = ?10, image_path = ?11, appearance = ?12, bio = ?13 WHERE id = ?1",
+    /// * `Result<UpdateAnimalResult>` - The updated record, a conflict with the current record, or not found
+    pub fn update_animal(&self, animal: &Animal) -> Result<UpdateAnimalResult> {
+        validation::ensure_valid(validation::validate_animal(animal))?;
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for animal update")?;
+
+        let previous_status: Option<AnimalStatus> = transaction
+            .query_row(
+                "SELECT status FROM animals WHERE id = ?1",
+                params![animal.id],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to read previous animal status")?;
+        if let Some(previous_status) = &previous_status {
+            validation::ensure_valid(validation::validate_status_change(
+                previous_status,
+                &animal.status,
+            ))?;
+        }
+
+        let rows_affected = transaction.execute(
+            "UPDATE animals SET name = ?2, specie = ?3, breed = ?4, sex = ?5, birth_month = ?6, birth_year = ?7, neutered = ?8, admission_timestamp = ?9, status = ?10, image_path = ?11, appearance = ?12, bio = ?13, microchip_number = ?15, location_id = ?16, good_with_kids = ?17, good_with_dogs = ?18, good_with_cats = ?19, special_needs = ?20, size = ?21, color = ?22, version = version + 1 WHERE id = ?1 AND version = ?14",
             params![
                 animal.id,
                 animal.name,
@@ -439,18 +1677,51 @@ impl DatabaseService {
                 animal.status,
                 animal.image_path,
                 animal.appearance,
-                animal.bio
+                animal.bio,
+                animal.version,
+                normalize_microchip_number(&animal.microchip_number),
+                animal.location_id,
+                animal.good_with_kids,
+                animal.good_with_dogs,
+                animal.good_with_cats,
+                animal.special_needs,
+                animal.size,
+                normalize_color(&animal.color)
             ]
         ).context("Failed to update animal in database")?;
 
-        match rows_affected {
-            1 => {
+        // Read back the current record to return it to the caller
+        let current = self.query_animal_by_id(&animal.id)?;
+
+        match (rows_affected, current) {
+            (1, Some(updated)) => {
+                if updated.status == AnimalStatus::Adopted
+                    && previous_status != Some(AnimalStatus::Adopted)
+                {
+                    self.queue_webhook_event(
+                        WebhookEvent::AnimalAdopted,
+                        &updated,
+                        Utc::now().timestamp(),
+                    )?;
+                }
+                transaction
+                    .commit()
+                    .context("Failed to commit animal update")?;
                 log::info!("Successfully updated animal with ID: {}", animal.id);
-                Ok(true)
+                Ok(UpdateAnimalResult::Updated(updated))
             }
-            0 => {
+            (0, Some(current)) => {
+                log::warn!(
+                    "Version conflict updating animal with ID: {} (expected {}, found {})",
+                    animal.id,
+                    animal.version,
+                    current.version
+                );
+                Ok(UpdateAnimalResult::Conflict(current))
+            }
+            (0, None) => {
                 log::warn!("No animal found with ID: {} for update", animal.id);
-                Ok(false)
+                Ok(UpdateAnimalResult::NotFound)
             }
             _ => {
                 bail!(
@@ -461,34 +1732,84 @@ impl DatabaseService {
         }
     }
 
+    /// Points an animal at a new image, bumping its version so that forms opened before
+    /// the change report a conflict instead of putting the old image back
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal
+    /// * `image_path` - Path of the new image, or None to remove the image
+    ///
+    /// # Returns
+    /// * `Result<Option<(Animal, Option<String>)>>` - The updated animal with the path of its
+    ///   previous image, or None if no animal has the ID
+    pub fn set_animal_image(
+        &self,
+        animal_id: &str,
+        image_path: Option<&str>,
+    ) -> Result<Option<(Animal, Option<String>)>> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for animal image")?;
+
+        let previous_image_path: Option<Option<String>> = transaction
+            .query_row(
+                "SELECT image_path FROM animals WHERE id = ?1",
+                params![animal_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to read previous animal image")?;
+        let Some(previous_image_path) = previous_image_path else {
+            log::warn!("No animal found with ID: {} for image update", animal_id);
+            return Ok(None);
+        };
+
+        transaction
+            .execute(
+                "UPDATE animals SET image_path = ?2, version = version + 1 WHERE id = ?1",
+                params![animal_id, image_path],
+            )
+            .context("Failed to update animal image")?;
+        let updated = self
+            .query_animal_by_id(animal_id)?
+            .context("Animal disappeared while updating its image")?;
+        transaction
+            .commit()
+            .context("Failed to commit animal image")?;
+
+        log::info!("Updated image of animal with ID: {}", animal_id);
+        Ok(Some((updated, previous_image_path)))
+    }
+
     /// Deletes an animal from the database by ID
     ///
+    /// Adoption requests and other records reference animals through foreign keys, so an
+    /// animal with such records can only be deleted when `cascade` is set, in which case
+    /// they are deleted along with it in a single transaction. The animal and its adoption
+    /// requests are kept in the trash until restored or purged.
+    ///
     /// # Arguments
     /// * `animal_id` - The ID of the animal to delete
+    /// * `cascade` - Whether to also delete the adoption requests and other records of the animal
     ///
     /// # Returns
     /// * `Result<bool>` - True if animal was found and deleted, false if not found
-    pub fn delete_animal(&self, animal_id: &str) -> Result<bool> {
-        let rows_affected = self
+    pub fn delete_animal(&self, animal_id: &str, cascade: bool) -> Result<bool> {
+        let transaction = self
             .connection
-            .execute("DELETE FROM animals WHERE id = ?1", params![animal_id])
-            .context("Failed to delete animal from database")?;
+            .unchecked_transaction()
+            .context("Failed to begin transaction for animal deletion")?;
 
-        match rows_affected {
-            1 => {
-                log::info!("Successfully deleted animal with ID: {}", animal_id);
-                Ok(true)
-            }
-            0 => {
-                log::warn!("No animal found with ID: {} for deletion", animal_id);
-                Ok(false)
-            }
-            _ => {
-                bail!(
-                    "Unexpected number of rows affected when deleting animal: {}",
-                    rows_affected
-                );
-            }
+        if remove_animal(&transaction, animal_id, cascade, Utc::now().timestamp())? {
+            transaction
+                .commit()
+                .context("Failed to commit animal deletion")?;
+            log::info!("Successfully deleted animal with ID: {}", animal_id);
+            Ok(true)
+        } else {
+            log::warn!("No animal found with ID: {} for deletion", animal_id);
+            Ok(false)
         }
     }
 
@@ -507,46 +1828,190 @@ impl DatabaseService {
     ) -> Result<Vec<AdoptionRequest>> {
         // SQL query to select adoption requests by animal ID
         let query =
-                "SELECT id, animal_id, username, name, email, tel_number, address, occupation, annual_income, num_people, num_children, request_timestamp, adoption_timestamp, status, country FROM adoption_requests WHERE animal_id = ?1"
+                "SELECT id, animal_id, username, name, email, tel_number, address, occupation, annual_income, num_people, num_children, request_timestamp, adoption_timestamp, status, country, co_applicant_name, co_applicant_relationship, co_applicant_contact FROM adoption_requests WHERE animal_id = ?1"
                     .to_string();
 
         let mut statement = self.connection.prepare(&query).context(format!(
-            "Failed to prepare query for adoption requests by animal ID: {}",
+            "Failed to prepare query for adoption requests by animal ID: {}",
+            query
+        ))?;
+
+        let request_iter = statement
+            .query_map(rusqlite::params![animal_id], |row| {
+                Ok(AdoptionRequest {
+                    id: row.get(0)?,
+                    animal_id: row.get(1)?,
+                    username: row.get(2)?,
+                    name: row.get(3)?,
+                    email: row.get(4)?,
+                    tel_number: row.get(5)?,
+                    address: row.get(6)?,
+                    occupation: row.get(7)?,
+                    annual_income: row.get(8)?,
+                    num_people: row.get(9)?,
+                    num_children: row.get(10)?,
+                    request_timestamp: row.get(11)?,
+                    adoption_timestamp: row.get(12)?,
+                    status: row.get(13)?,
+                    country: row.get(14)?,
+                    co_applicant: co_applicant_from_row(row, 15)?,
+                })
+            })
+            .context("Failed to execute query for adoption requests by animal ID")?;
+
+        let mut requests = Vec::new();
+        for request in request_iter {
+            requests.push(request.context("Failed to parse adoption request row")?);
+        }
+
+        log::debug!(
+            "Retrieved {} adoption requests for animal ID: {}",
+            requests.len(),
+            animal_id
+        );
+        Ok(requests)
+    }
+
+    /// Retrieves summary information for adoption requests matching the given filters, newest first
+    ///
+    /// # Arguments
+    /// * `filters` - The status, submission date range and animal species to filter by
+    ///
+    /// # Returns
+    /// * `Result<Vec<AdoptionRequestSummary>>` - List of adoption request summaries or error
+    pub fn query_adoption_requests(
+        &self,
+        filters: &AdoptionRequestFilters,
+    ) -> Result<Vec<AdoptionRequestSummary>> {
+        let mut query = "SELECT r.id, r.animal_id, a.name, a.specie, r.username, r.name, r.email, r.request_timestamp, r.status, s.score FROM adoption_requests r JOIN animals a ON a.id = r.animal_id LEFT JOIN screening_scores s ON s.request_id = r.id".to_string();
+        let (where_clauses, params) = adoption_request_filter_clauses(filters);
+        if !where_clauses.is_empty() {
+            query.push_str(" WHERE ");
+            query.push_str(&where_clauses.join(" AND "));
+        }
+        query.push_str(" ORDER BY r.request_timestamp DESC");
+
+        let mut statement = self.connection.prepare(&query).context(format!(
+            "Failed to prepare query for adoption requests: {}",
+            query
+        ))?;
+
+        let request_iter = statement
+            .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+                Ok(AdoptionRequestSummary {
+                    id: row.get(0)?,
+                    animal_id: row.get(1)?,
+                    animal_name: row.get(2)?,
+                    animal_specie: row.get(3)?,
+                    username: row.get(4)?,
+                    name: row.get(5)?,
+                    email: row.get(6)?,
+                    request_timestamp: row.get(7)?,
+                    status: row.get(8)?,
+                    screening_score: row.get(9)?,
+                })
+            })
+            .context("Failed to execute query for adoption requests")?;
+
+        let mut requests = Vec::new();
+        for request in request_iter {
+            requests.push(request.context("Failed to parse adoption request row")?);
+        }
+
+        log::debug!(
+            "Retrieved {} adoption requests from database",
+            requests.len()
+        );
+        Ok(requests)
+    }
+
+    /// Retrieves adoption requests joined with the animals they are for, in a single query
+    ///
+    /// # Arguments
+    /// * `filters` - Optional map of filter criteria and values applied to the animals
+    /// * `status` - Only include requests with this status, if given
+    ///
+    /// # Returns
+    /// * `Result<Vec<AnimalAdoptionRequest>>` - Requests with their animal summaries, oldest first, or error
+    pub fn query_adoption_requests_with_animals(
+        &self,
+        filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
+        status: Option<RequestStatus>,
+    ) -> Result<Vec<AnimalAdoptionRequest>> {
+        let (where_clause, mut params) = animal_filter_clause(filters);
+        let mut query = format!(
+            "SELECT {} FROM adoption_requests r JOIN (SELECT * FROM animals{}) a ON a.id = r.animal_id",
+            ANIMAL_ADOPTION_REQUEST_COLUMNS, where_clause
+        );
+        if let Some(status) = status {
+            query.push_str(" WHERE r.status = ?");
+            params.push(rusqlite::types::Value::from(status.to_string()));
+        }
+        query.push_str(" ORDER BY r.request_timestamp");
+
+        let mut statement = self.connection.prepare(&query).context(format!(
+            "Failed to prepare query for adoption requests with animals: {}",
+            query
+        ))?;
+
+        let request_iter = statement
+            .query_map(
+                rusqlite::params_from_iter(params.iter()),
+                animal_adoption_request_from_row,
+            )
+            .context("Failed to execute query for adoption requests with animals")?;
+
+        let mut requests = Vec::new();
+        for request in request_iter {
+            requests.push(request.context("Failed to parse adoption request row")?);
+        }
+
+        log::debug!(
+            "Retrieved {} adoption requests with animals from database",
+            requests.len()
+        );
+        Ok(requests)
+    }
+
+    /// Retrieves complete adoption requests joined with the animals they are for, with
+    /// the same filters as the adoption requests list, for exports that need every field
+    ///
+    /// # Arguments
+    /// * `filters` - The status, submission date range and animal species to filter by
+    ///
+    /// # Returns
+    /// * `Result<Vec<AnimalAdoptionRequest>>` - Requests with their animal summaries, most recent first, or error
+    pub fn query_adoption_request_details(
+        &self,
+        filters: &AdoptionRequestFilters,
+    ) -> Result<Vec<AnimalAdoptionRequest>> {
+        let mut query = format!(
+            "SELECT {} FROM adoption_requests r JOIN animals a ON a.id = r.animal_id",
+            ANIMAL_ADOPTION_REQUEST_COLUMNS
+        );
+        let (where_clauses, params) = adoption_request_filter_clauses(filters);
+        if !where_clauses.is_empty() {
+            query.push_str(" WHERE ");
+            query.push_str(&where_clauses.join(" AND "));
+        }
+        query.push_str(" ORDER BY r.request_timestamp DESC");
+
+        let mut statement = self.connection.prepare(&query).context(format!(
+            "Failed to prepare query for adoption request details: {}",
             query
         ))?;
 
         let request_iter = statement
-            .query_map(rusqlite::params![animal_id], |row| {
-                Ok(AdoptionRequest {
-                    id: row.get(0)?,
-                    animal_id: row.get(1)?,
-                    username: row.get(2)?,
-                    name: row.get(3)?,
-                    email: row.get(4)?,
-                    tel_number: row.get(5)?,
-                    address: row.get(6)?,
-                    occupation: row.get(7)?,
-                    annual_income: row.get(8)?,
-                    num_people: row.get(9)?,
-                    num_children: row.get(10)?,
-                    request_timestamp: row.get(11)?,
-                    adoption_timestamp: row.get(12)?,
-                    status: row.get(13)?,
-                    country: row.get(14)?,
-                })
-            })
-            .context("Failed to execute query for adoption requests by animal ID")?;
+            .query_map(
+                rusqlite::params_from_iter(params.iter()),
+                animal_adoption_request_from_row,
+            )
+            .context("Failed to execute query for adoption request details")?;
 
         let mut requests = Vec::new();
         for request in request_iter {
             requests.push(request.context("Failed to parse adoption request row")?);
         }
-
-        log::debug!(
-            "Retrieved {} adoption requests for animal ID: {}",
-            requests.len(),
-            animal_id
-        );
         Ok(requests)
     }
 
@@ -563,7 +2028,7 @@ impl DatabaseService {
     ) -> Result<Vec<AdoptionRequest>> {
         // SQL query to select adoption requests by username
         let query =
-                "SELECT id, animal_id, username, name, email, tel_number, address, occupation, annual_income, num_people, num_children, request_timestamp, adoption_timestamp, status, country FROM adoption_requests WHERE username = ?1"
+                "SELECT id, animal_id, username, name, email, tel_number, address, occupation, annual_income, num_people, num_children, request_timestamp, adoption_timestamp, status, country, co_applicant_name, co_applicant_relationship, co_applicant_contact FROM adoption_requests WHERE username = ?1"
                     .to_string();
 
         let mut statement = self.connection.prepare(&query).context(format!(
@@ -589,6 +2054,7 @@ impl DatabaseService {
                     adoption_timestamp: row.get(12)?,
                     status: row.get(13)?,
                     country: row.get(14)?,
+                    co_applicant: co_applicant_from_row(row, 15)?,
                 })
             })
             .context("Failed to execute query for adoption requests by user name")?;
@@ -606,6 +2072,35 @@ impl DatabaseService {
         Ok(requests)
     }
 
+    /// Counts the adoption requests that are still pending review
+    ///
+    /// # Arguments
+    /// * `location_id` - Only count requests for animals at this location, if given
+    ///
+    /// # Returns
+    /// * `Result<i64>` - The number of pending adoption requests or error
+    pub fn count_pending_adoption_requests(&self, location_id: Option<&str>) -> Result<i64> {
+        self.connection
+            .query_row(
+                "SELECT COUNT(*) FROM adoption_requests WHERE status = ?1 AND (?2 IS NULL OR animal_id IN (SELECT id FROM animals WHERE location_id = ?2))",
+                params![RequestStatus::Pending, location_id],
+                |row| row.get(0),
+            )
+            .context("Failed to count pending adoption requests")
+    }
+
+    /// Retrieves the distinct countries of the requesters in the database
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - Sorted list of countries or error
+    pub fn query_distinct_countries(&self) -> Result<Vec<String>> {
+        self.query_distinct_values(
+            "SELECT DISTINCT country FROM adoption_requests ORDER BY country",
+            params![],
+        )
+        .context("Failed to query distinct countries")
+    }
+
     /// Retrieves complete information for a specific adoption request by ID
     ///
     /// # Arguments
@@ -619,7 +2114,7 @@ impl DatabaseService {
     ) -> Result<Option<AdoptionRequest>> {
         // Prepare the SQL statement
         let mut statement = self.connection.prepare(
-                "SELECT id, animal_id, username, name, email, tel_number, address, occupation, annual_income, num_people, num_children, request_timestamp, adoption_timestamp, status, country FROM adoption_requests WHERE id = ?1"
+                "SELECT id, animal_id, username, name, email, tel_number, address, occupation, annual_income, num_people, num_children, request_timestamp, adoption_timestamp, status, country, co_applicant_name, co_applicant_relationship, co_applicant_contact FROM adoption_requests WHERE id = ?1"
             ).context("Failed to prepare query for adoption request by ID")?;
         let mut rows = statement
             .query_map(params![request_id], |row| {
@@ -639,6 +2134,7 @@ impl DatabaseService {
                     adoption_timestamp: row.get(12)?,
                     status: row.get(13)?,
                     country: row.get(14)?,
+                    co_applicant: co_applicant_from_row(row, 15)?,
                 })
             })
             .context("Failed to execute query for adoption request by ID")?;
@@ -658,30 +2154,63 @@ impl DatabaseService {
 
     /// Inserts a new adoption request into the database
     ///
+    /// A user may only have one pending request per animal, so the check for an
+    /// existing pending request and the insert run in the same transaction. Fails with a
+    /// `ValidationError` listing the invalid fields if the request does not validate.
+    ///
     /// # Arguments
     /// * `request` - The adoption request information to insert
     ///
     /// # Returns
-    /// * `Result<()>` - Success or error
-    pub fn insert_adoption_request(&self, request: &AdoptionRequest) -> Result<()> {
-        // Auto-generate ID if not provided (or empty)
-        let id = if request.id.trim().is_empty() {
-            let max_id: i64 = self
-                .connection
+    /// * `Result<CreateRequestResult>` - Whether the request was stored or rejected as a duplicate
+    pub fn insert_adoption_request(
+        &self,
+        request: &AdoptionRequest,
+    ) -> Result<CreateRequestResult> {
+        validation::ensure_valid(validation::validate_adoption_request(request))?;
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for adoption request insertion")?;
+
+        // Reject the request if the user already has a pending request for this animal;
+        // applicants from the website are told apart by their email address
+        if request.status == RequestStatus::Pending {
+            let pending_count: i64 = transaction
                 .query_row(
-                    "SELECT COALESCE(MAX(CAST(id AS INTEGER)), 0) FROM adoption_requests",
-                    [],
+                    "SELECT COUNT(*) FROM adoption_requests WHERE animal_id = ?1 AND username = ?2 AND status = ?3 AND (username <> ?4 OR lower(trim(email)) = lower(trim(?5)))",
+                    params![
+                        request.animal_id,
+                        request.username,
+                        RequestStatus::Pending,
+                        WEB_INTAKE_USERNAME,
+                        request.email
+                    ],
                     |row| row.get(0),
                 )
-                .context("Failed to query max adoption request ID")?;
-            (max_id + 1).to_string()
+                .context("Failed to check for existing pending adoption requests")?;
+
+            if pending_count > 0 {
+                log::warn!(
+                    "User {} already has a pending adoption request for animal ID: {}",
+                    request.username,
+                    request.animal_id
+                );
+                return Ok(CreateRequestResult::DuplicatePendingRequest);
+            }
+        }
+
+        // Auto-generate ID if not provided (or empty)
+        let id = if request.id.trim().is_empty() {
+            Uuid::new_v4().to_string()
         } else {
             request.id.clone()
         };
 
         // Number of rows affected by the insert operation
-        let rows_affected = self.connection.execute(
-            "INSERT INTO adoption_requests (id, animal_id, username, name, email, tel_number, address, occupation, annual_income, num_people, num_children, request_timestamp, adoption_timestamp, status, country) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+        let rows_affected = transaction.execute(
+            "INSERT INTO adoption_requests (id, animal_id, username, name, email, tel_number, address, occupation, annual_income, num_people, num_children, request_timestamp, adoption_timestamp, status, country, co_applicant_name, co_applicant_relationship, co_applicant_contact) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
             params![
                 id,
                 request.animal_id,
@@ -697,13 +2226,25 @@ impl DatabaseService {
                 request.request_timestamp,
                 request.adoption_timestamp,
                 request.status,
-                request.country
+                request.country,
+                request.co_applicant.as_ref().map(|co| co.name.trim()),
+                request.co_applicant.as_ref().map(|co| co.relationship.trim()),
+                request.co_applicant.as_ref().map(|co| co.contact.trim())
             ]
         ).context("Failed to insert adoption request into database")?;
 
         if rows_affected == 1 {
+            self.queue_request_status_change(
+                &id,
+                &request.animal_id,
+                &request.status,
+                Utc::now().timestamp(),
+            )?;
+            transaction
+                .commit()
+                .context("Failed to commit adoption request insertion")?;
             log::info!("Successfully inserted adoption request with ID: {}", id);
-            Ok(())
+            Ok(CreateRequestResult::Success)
         } else {
             bail!(
                 "Unexpected number of rows affected when inserting adoption request: {}",
@@ -714,15 +2255,39 @@ impl DatabaseService {
 
     /// Updates an existing adoption request in the database
     ///
+    /// Fails with a `ValidationError` listing the invalid fields if the request does not validate.
+    ///
     /// # Arguments
     /// * `request` - The updated adoption request information
     ///
     /// # Returns
     /// * `Result<bool>` - True if request was found and updated, false if not found
     pub fn update_adoption_request(&self, request: &AdoptionRequest) -> Result<bool> {
+        validation::ensure_valid(validation::validate_adoption_request(request))?;
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for adoption request update")?;
+
+        let previous_status: Option<RequestStatus> = transaction
+            .query_row(
+                "SELECT status FROM adoption_requests WHERE id = ?1",
+                params![request.id],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to read previous adoption request status")?;
+        if let Some(previous_status) = &previous_status {
+            validation::ensure_valid(validation::validate_request_status_change(
+                previous_status,
+                &request.status,
+            ))?;
+        }
+
         // Number of rows affected by the update operation
-        let rows_affected = self.connection.execute(
-            "UPDATE adoption_requests SET animal_id = ?2, username = ?3, name = ?4, email = ?5, tel_number = ?6, address = ?7, occupation = ?8, annual_income = ?9, num_people = ?10, num_children = ?11, request_timestamp = ?12, adoption_timestamp = ?13, status = ?14, country = ?15 WHERE id = ?1",
+        let rows_affected = transaction.execute(
+            "UPDATE adoption_requests SET animal_id = ?2, username = ?3, name = ?4, email = ?5, tel_number = ?6, address = ?7, occupation = ?8, annual_income = ?9, num_people = ?10, num_children = ?11, request_timestamp = ?12, adoption_timestamp = ?13, status = ?14, country = ?15, co_applicant_name = ?16, co_applicant_relationship = ?17, co_applicant_contact = ?18 WHERE id = ?1",
             params![
                 request.id,
                 request.animal_id,
@@ -738,12 +2303,26 @@ impl DatabaseService {
                 request.request_timestamp,
                 request.adoption_timestamp,
                 request.status,
-                request.country
+                request.country,
+                request.co_applicant.as_ref().map(|co| co.name.trim()),
+                request.co_applicant.as_ref().map(|co| co.relationship.trim()),
+                request.co_applicant.as_ref().map(|co| co.contact.trim())
             ]
         ).context("Failed to update adoption request in database")?;
 
         match rows_affected {
             1 => {
+                if previous_status.as_ref() != Some(&request.status) {
+                    self.queue_request_status_change(
+                        &request.id,
+                        &request.animal_id,
+                        &request.status,
+                        Utc::now().timestamp(),
+                    )?;
+                }
+                transaction
+                    .commit()
+                    .context("Failed to commit adoption request update")?;
                 log::info!(
                     "Successfully updated adoption request with ID: {}",
                     request.id
@@ -766,7 +2345,171 @@ impl DatabaseService {
         }
     }
 
-    /// Deletes an adoption request from the database by ID
+    /// Approves a pending adoption request, marks the animal as adopted,
+    /// and rejects every other pending request for the animal
+    ///
+    /// Approval is refused while the animal is a stray on hold, or while a check required by
+    /// `requirements` has not been passed, and unless `allow_split_bond`
+    /// is set, when the animal has bonded partners that the same user has not also
+    /// requested or adopted. When a trial end
+    /// is given, the request and the animal are put on trial until the adoption is
+    /// finalized with `finalize_trial_adoption` or the animal is returned; fails with a
+    /// `ValidationError` if the end is not in the future.
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the adoption request to approve
+    /// * `allow_split_bond` - Whether to approve even though bonded partners are left behind
+    /// * `trial_end_timestamp` - When the trial period ends, or None to adopt right away
+    /// * `requirements` - The interview and reference checks required before approval
+    ///
+    /// # Returns
+    /// * `Result<ApproveRequestResult>` - Approved or trial started, or why the request was
+    ///   not approved
+    pub fn approve_adoption_request(
+        &self,
+        request_id: &str,
+        allow_split_bond: bool,
+        trial_end_timestamp: Option<i64>,
+        requirements: &ApprovalRequirements,
+    ) -> Result<ApproveRequestResult> {
+        if let Some(end_timestamp) = trial_end_timestamp {
+            validation::ensure_valid(validation::validate_trial_end(end_timestamp))?;
+        }
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for adoption approval")?;
+
+        let request = self.query_adoption_request_by_id(request_id)?;
+        let Some(request) = request else {
+            return Ok(ApproveRequestResult::NotFound);
+        };
+        if request.status != RequestStatus::Pending {
+            return Ok(ApproveRequestResult::NotPending);
+        }
+
+        // Strays cannot be adopted until the hold required by law is over
+        if self.query_stray_hold_until(&request.animal_id)?.is_some() {
+            return Ok(ApproveRequestResult::OnStrayHold);
+        }
+
+        // The interview and references admins require must be on record
+        if let Some(unmet) = self.query_unmet_approval_requirement(request_id, requirements)? {
+            return Ok(unmet);
+        }
+
+        // Warn about bonded partners this user is not also adopting
+        if !allow_split_bond {
+            let left_behind =
+                self.query_bonded_animals_left_behind(&request.animal_id, &request.username)?;
+            if !left_behind.is_empty() {
+                return Ok(ApproveRequestResult::BondedPairSplit(left_behind));
+            }
+        }
+
+        // Remember which requests are rejected, to notify webhooks about them
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id FROM adoption_requests WHERE animal_id = ?1 AND id != ?2 AND status = ?3",
+            )
+            .context("Failed to prepare query for other pending adoption requests")?;
+        let rejected_ids = statement
+            .query_map(
+                params![request.animal_id, request_id, RequestStatus::Pending],
+                |row| row.get::<_, String>(0),
+            )
+            .context("Failed to execute query for other pending adoption requests")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse adoption request row")?;
+
+        let now = Utc::now().timestamp();
+        let (request_status, animal_status) = match trial_end_timestamp {
+            Some(end_timestamp) => {
+                transaction
+                    .execute(
+                        "UPDATE adoption_requests SET status = ?2 WHERE id = ?1",
+                        params![request_id, RequestStatus::TrialAdoption],
+                    )
+                    .context("Failed to start trial adoption")?;
+                transaction
+                    .execute(
+                        "INSERT INTO trial_adoptions (id, adoption_request_id, animal_id, started_timestamp, end_timestamp) VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![
+                            Uuid::new_v4().to_string(),
+                            request_id,
+                            request.animal_id,
+                            now,
+                            end_timestamp
+                        ],
+                    )
+                    .context("Failed to insert trial adoption into database")?;
+                (RequestStatus::TrialAdoption, AnimalStatus::TrialAdoption)
+            }
+            None => {
+                transaction
+                    .execute(
+                        "UPDATE adoption_requests SET status = ?2, adoption_timestamp = ?3 WHERE id = ?1",
+                        params![request_id, RequestStatus::Approved, now],
+                    )
+                    .context("Failed to approve adoption request")?;
+                (RequestStatus::Approved, AnimalStatus::Adopted)
+            }
+        };
+
+        let rejected = transaction
+            .execute(
+                "UPDATE adoption_requests SET status = ?3 WHERE animal_id = ?1 AND id != ?2 AND status = ?4",
+                params![
+                    request.animal_id,
+                    request_id,
+                    RequestStatus::Rejected,
+                    RequestStatus::Pending
+                ],
+            )
+            .context("Failed to reject other adoption requests")?;
+
+        transaction
+            .execute(
+                "UPDATE animals SET status = ?2, version = version + 1 WHERE id = ?1",
+                params![request.animal_id, animal_status],
+            )
+            .context("Failed to mark animal as adopted")?;
+
+        self.queue_request_status_change(request_id, &request.animal_id, &request_status, now)?;
+        for rejected_id in &rejected_ids {
+            self.queue_request_status_change(
+                rejected_id,
+                &request.animal_id,
+                &RequestStatus::Rejected,
+                now,
+            )?;
+        }
+        if animal_status == AnimalStatus::Adopted {
+            if let Some(animal) = self.query_animal_by_id(&request.animal_id)? {
+                self.queue_webhook_event(WebhookEvent::AnimalAdopted, &animal, now)?;
+            }
+        }
+
+        transaction
+            .commit()
+            .context("Failed to commit adoption approval")?;
+
+        log::info!(
+            "Approved adoption request with ID: {} as {} and rejected {} other request(s)",
+            request_id,
+            request_status,
+            rejected
+        );
+        Ok(match request_status {
+            RequestStatus::TrialAdoption => ApproveRequestResult::TrialStarted,
+            _ => ApproveRequestResult::Approved,
+        })
+    }
+
+    /// Deletes an adoption request from the database by ID, keeping it in the trash
+    /// until restored or purged
     ///
     /// # Arguments
     /// * `request_id` - The ID of the adoption request to delete
@@ -774,35 +2517,645 @@ impl DatabaseService {
     /// # Returns
     /// * `Result<bool>` - True if request was found and deleted, false if not found
     pub fn delete_adoption_request(&self, request_id: &str) -> Result<bool> {
-        let rows_affected = self
+        let transaction = self
             .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for adoption request deletion")?;
+
+        if remove_adoption_request(&transaction, request_id, Utc::now().timestamp())? {
+            transaction
+                .commit()
+                .context("Failed to commit adoption request deletion")?;
+            log::info!(
+                "Successfully deleted adoption request with ID: {}",
+                request_id
+            );
+            Ok(true)
+        } else {
+            log::warn!(
+                "No adoption request found with ID: {} for deletion",
+                request_id
+            );
+            Ok(false)
+        }
+    }
+}
+
+/// Adds a column to an existing table if the table does not have it yet
+///
+/// # Arguments
+/// * `connection` - Connection to the database holding the table
+/// * `table` - Name of the table to alter
+/// * `column` - Name of the column to add
+/// * `definition` - SQL type and constraints of the column
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn add_column_if_missing(
+    connection: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    let mut statement = connection
+        .prepare(&format!("PRAGMA table_info({})", table))
+        .context(format!("Failed to read columns of table {}", table))?;
+    let column_exists = statement
+        .query_map([], |row| row.get::<_, String>(1))
+        .context(format!("Failed to read columns of table {}", table))?
+        .filter_map(|name| name.ok())
+        .any(|name| name == column);
+
+    if !column_exists {
+        connection
             .execute(
-                "DELETE FROM adoption_requests WHERE id = ?1",
-                params![request_id],
+                &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+                [],
             )
-            .context("Failed to delete adoption request from database")?;
+            .context(format!(
+                "Failed to add column {} to table {}",
+                column, table
+            ))?;
+        log::info!("Added column {} to table {}", column, table);
+    }
+    Ok(())
+}
 
-        match rows_affected {
-            1 => {
-                log::info!(
-                    "Successfully deleted adoption request with ID: {}",
-                    request_id
-                );
-                Ok(true)
-            }
-            0 => {
-                log::warn!(
-                    "No adoption request found with ID: {} for deletion",
-                    request_id
-                );
-                Ok(false)
-            }
-            _ => {
-                bail!(
-                    "Unexpected number of rows affected when deleting adoption request: {}",
-                    rows_affected
-                );
+/// Deletes an animal and, if `cascade` is set, the records that reference it, keeping the
+/// animal and its records in the trash
+///
+/// # Arguments
+/// * `connection` - The connection or transaction performing the deletion
+/// * `animal_id` - The ID of the animal to delete
+/// * `cascade` - Whether to also delete the adoption requests and other records of the animal
+/// * `deleted_timestamp` - The timestamp of the deletion
+///
+/// # Returns
+/// * `Result<bool>` - True if animal was found and deleted, false if not found
+fn remove_animal(
+    connection: &Connection,
+    animal_id: &str,
+    cascade: bool,
+    deleted_timestamp: i64,
+) -> Result<bool> {
+    // Keep the animal and its records in the trash so the deletion can be undone
+    trash::copy_animal_to_trash(connection, animal_id, deleted_timestamp)?;
+
+    // Bonds and favorites only describe animals in the shelter, so they never block a deletion
+    connection
+        .execute(
+            "DELETE FROM animal_relationships WHERE animal_id = ?1 OR bonded_animal_id = ?1",
+            params![animal_id],
+        )
+        .context("Failed to delete bonds of animal")?;
+    connection
+        .execute(
+            "DELETE FROM favorites WHERE animal_id = ?1",
+            params![animal_id],
+        )
+        .context("Failed to delete favorites of animal")?;
+
+    // Expenses stay in the books after the animal they were spent on is deleted
+    connection
+        .execute(
+            "UPDATE expenses SET animal_id = NULL WHERE animal_id = ?1",
+            params![animal_id],
+        )
+        .context("Failed to unlink expenses of animal")?;
+
+    // Remove or refuse on the records that reference the animal, dependents first
+    for (table, description) in ANIMAL_DEPENDENT_TABLES {
+        let record_count: i64 = connection
+            .query_row(
+                &format!("SELECT COUNT(*) FROM {} WHERE animal_id = ?1", table),
+                params![animal_id],
+                |row| row.get(0),
+            )
+            .context(format!("Failed to count {}s for animal", description))?;
+
+        if record_count == 0 {
+            continue;
+        }
+        if !cascade {
+            bail!(
+                "Cannot delete animal with ID {} because it has {} {}(s)",
+                animal_id,
+                record_count,
+                description
+            );
+        }
+
+        connection
+            .execute(
+                &format!("DELETE FROM {} WHERE animal_id = ?1", table),
+                params![animal_id],
+            )
+            .context(format!("Failed to delete {}s for animal", description))?;
+        log::info!(
+            "Deleted {} {}(s) for animal with ID: {}",
+            record_count,
+            description,
+            animal_id
+        );
+    }
+
+    let rows_affected = connection
+        .execute("DELETE FROM animals WHERE id = ?1", params![animal_id])
+        .context("Failed to delete animal from database")?;
+
+    match rows_affected {
+        1 => Ok(true),
+        0 => Ok(false),
+        _ => {
+            bail!(
+                "Unexpected number of rows affected when deleting animal: {}",
+                rows_affected
+            );
+        }
+    }
+}
+
+/// Deletes an adoption request, keeping it in the trash
+///
+/// # Arguments
+/// * `connection` - The connection or transaction performing the deletion
+/// * `request_id` - The ID of the adoption request to delete
+/// * `deleted_timestamp` - The timestamp of the deletion
+///
+/// # Returns
+/// * `Result<bool>` - True if request was found and deleted, false if not found
+fn remove_adoption_request(
+    connection: &Connection,
+    request_id: &str,
+    deleted_timestamp: i64,
+) -> Result<bool> {
+    // Keep the request in the trash so the deletion can be undone
+    trash::copy_adoption_request_to_trash(connection, request_id, deleted_timestamp)?;
+
+    let rows_affected = connection
+        .execute(
+            "DELETE FROM adoption_requests WHERE id = ?1",
+            params![request_id],
+        )
+        .context("Failed to delete adoption request from database")?;
+
+    match rows_affected {
+        1 => Ok(true),
+        0 => Ok(false),
+        _ => {
+            bail!(
+                "Unexpected number of rows affected when deleting adoption request: {}",
+                rows_affected
+            );
+        }
+    }
+}
+
+/// Builds an animal from a row selected with `ANIMAL_COLUMNS`
+///
+/// # Arguments
+/// * `row` - The row to read
+///
+/// # Returns
+/// * `rusqlite::Result<Animal>` - The animal or a column error
+fn animal_from_row(row: &Row<'_>) -> rusqlite::Result<Animal> {
+    Ok(Animal {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        specie: row.get(2)?,
+        breed: row.get(3)?,
+        sex: row.get(4)?,
+        birth_month: row.get(5)?,
+        birth_year: row.get(6)?,
+        neutered: row.get(7)?,
+        admission_timestamp: row.get(8)?,
+        status: row.get(9)?,
+        image_path: row.get(10)?,
+        appearance: row.get(11)?,
+        bio: row.get(12)?,
+        version: row.get(13)?,
+        microchip_number: row.get(14)?,
+        location_id: row.get(15)?,
+        good_with_kids: row.get(16)?,
+        good_with_dogs: row.get(17)?,
+        good_with_cats: row.get(18)?,
+        special_needs: row.get(19)?,
+        size: row.get(20)?,
+        color: row.get(21)?,
+    })
+}
+
+/// Trims a microchip number, treating a blank number as no microchip
+///
+/// # Arguments
+/// * `microchip_number` - The microchip number entered for an animal
+///
+/// # Returns
+/// * `Option<&str>` - The trimmed microchip number, or None if it is missing or blank
+fn normalize_microchip_number(microchip_number: &Option<String>) -> Option<&str> {
+    microchip_number
+        .as_deref()
+        .map(str::trim)
+        .filter(|number| !number.is_empty())
+}
+
+/// Trims a color, treating a blank color as not recorded
+///
+/// # Arguments
+/// * `color` - The color entered for an animal
+///
+/// # Returns
+/// * `Option<&str>` - The trimmed color, or None if it is missing or blank
+fn normalize_color(color: &Option<String>) -> Option<&str> {
+    color
+        .as_deref()
+        .map(str::trim)
+        .filter(|color| !color.is_empty())
+}
+
+/// Columns selected by the queries joining adoption requests `r` with their animals `a`,
+/// in the order `animal_adoption_request_from_row` reads them
+const ANIMAL_ADOPTION_REQUEST_COLUMNS: &str = "r.id, r.animal_id, r.username, r.name, r.email, r.tel_number, r.address, r.occupation, r.annual_income, r.num_people, r.num_children, r.request_timestamp, r.adoption_timestamp, r.status, r.country, a.name, a.specie, a.breed, a.sex, a.admission_timestamp, a.status, a.image_path, r.co_applicant_name, r.co_applicant_relationship, r.co_applicant_contact";
+
+/// Reads an adoption request and its animal from a row selected with `ANIMAL_ADOPTION_REQUEST_COLUMNS`
+fn animal_adoption_request_from_row(
+    row: &rusqlite::Row,
+) -> rusqlite::Result<AnimalAdoptionRequest> {
+    Ok(AnimalAdoptionRequest {
+        animal: AnimalSummary {
+            id: row.get(1)?,
+            name: row.get(15)?,
+            specie: row.get(16)?,
+            breed: row.get(17)?,
+            sex: row.get(18)?,
+            admission_timestamp: row.get(19)?,
+            status: row.get(20)?,
+            image_path: row.get(21)?,
+        },
+        request: AdoptionRequest {
+            id: row.get(0)?,
+            animal_id: row.get(1)?,
+            username: row.get(2)?,
+            name: row.get(3)?,
+            email: row.get(4)?,
+            tel_number: row.get(5)?,
+            address: row.get(6)?,
+            occupation: row.get(7)?,
+            annual_income: row.get(8)?,
+            num_people: row.get(9)?,
+            num_children: row.get(10)?,
+            request_timestamp: row.get(11)?,
+            adoption_timestamp: row.get(12)?,
+            status: row.get(13)?,
+            country: row.get(14)?,
+            co_applicant: co_applicant_from_row(row, 22)?,
+        },
+    })
+}
+
+/// Reads the co-applicant of an adoption request from its name, relationship and contact columns
+///
+/// # Arguments
+/// * `row` - The row to read
+/// * `first_column` - Index of the co-applicant name; the relationship and contact follow it
+///
+/// # Returns
+/// * `rusqlite::Result<Option<CoApplicant>>` - The co-applicant, or None if the requester applied alone
+fn co_applicant_from_row(
+    row: &Row<'_>,
+    first_column: usize,
+) -> rusqlite::Result<Option<CoApplicant>> {
+    let Some(name) = row.get::<_, Option<String>>(first_column)? else {
+        return Ok(None);
+    };
+    Ok(Some(CoApplicant {
+        name,
+        relationship: row
+            .get::<_, Option<String>>(first_column + 1)?
+            .unwrap_or_default(),
+        contact: row
+            .get::<_, Option<String>>(first_column + 2)?
+            .unwrap_or_default(),
+    }))
+}
+
+/// Builds the conditions and their parameters for filtering adoption requests, for a query
+/// joining the requests as `r` with their animals as `a`
+///
+/// # Arguments
+/// * `filters` - The status, submission date range and animal species to filter by
+///
+/// # Returns
+/// * `(Vec<&str>, Vec<rusqlite::types::Value>)` - The conditions, to be joined with AND, and
+///   the values for their placeholders
+fn adoption_request_filter_clauses(
+    filters: &AdoptionRequestFilters,
+) -> (Vec<&'static str>, Vec<rusqlite::types::Value>) {
+    let mut where_clauses: Vec<&str> = Vec::new();
+    let mut params: Vec<rusqlite::types::Value> = Vec::new();
+
+    if let Some(status) = &filters.status {
+        where_clauses.push("r.status = ?");
+        params.push(rusqlite::types::Value::from(status.to_string()));
+    }
+    if let Some(from_timestamp) = filters.from_timestamp {
+        where_clauses.push("r.request_timestamp >= ?");
+        params.push(rusqlite::types::Value::Integer(from_timestamp));
+    }
+    if let Some(to_timestamp) = filters.to_timestamp {
+        where_clauses.push("r.request_timestamp <= ?");
+        params.push(rusqlite::types::Value::Integer(to_timestamp));
+    }
+    if let Some(specie) = &filters.specie {
+        where_clauses.push("a.specie = ?");
+        params.push(rusqlite::types::Value::from(specie.clone()));
+    }
+    (where_clauses, params)
+}
+
+/// Builds the WHERE clause and its parameters for filtering animals
+///
+/// # Arguments
+/// * `filters` - Optional map of filter criteria and values
+///
+/// # Returns
+/// * `(String, Vec<rusqlite::types::Value>)` - The clause, starting with " WHERE " or empty if
+///   nothing is filtered, and the values for its placeholders
+fn animal_filter_clause(
+    filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
+) -> (String, Vec<rusqlite::types::Value>) {
+    let mut where_clauses: Vec<String> = Vec::new();
+    let mut params: Vec<rusqlite::types::Value> = Vec::new();
+
+    if let Some(filters_map) = filters {
+        if !filters_map.is_empty() {
+            for (criteria, value_option) in filters_map {
+                // Renamed value to value_option
+                if let Some(value) = value_option {
+                    // Added unwrap for Option<FilterValue>
+                    match criteria {
+                        FilterCriteria::Status => {
+                            if let FilterValue::ChooseMany(stati) = value {
+                                if stati.is_empty() {
+                                    where_clauses.push("1=0".to_string()); // No matches if empty list
+                                } else {
+                                    let placeholders: Vec<_> = stati.iter().map(|_| "?").collect();
+                                    where_clauses
+                                        .push(format!("status IN ({})", placeholders.join(",")));
+                                    for s in stati {
+                                        params.push(rusqlite::types::Value::from(s));
+                                    }
+                                }
+                            }
+                        }
+                        FilterCriteria::Sex => {
+                            if let FilterValue::ChooseMany(sexes) = value {
+                                if sexes.is_empty() {
+                                    where_clauses.push("1=0".to_string()); // No matches if empty list
+                                } else {
+                                    let placeholders: Vec<_> = sexes.iter().map(|_| "?").collect();
+                                    where_clauses
+                                        .push(format!("sex IN ({})", placeholders.join(",")));
+                                    for s in sexes {
+                                        params.push(rusqlite::types::Value::from(s));
+                                    }
+                                }
+                            }
+                        }
+                        FilterCriteria::SpeciesAndBreeds => {
+                            if let FilterValue::NestedChooseMany(species_map) = value {
+                                if species_map.is_empty() {
+                                    where_clauses.push("1=0".to_string()); // No matches if empty map
+                                } else {
+                                    let mut species_clauses = Vec::new();
+                                    for (specie, breeds) in species_map {
+                                        if !breeds.is_empty() {
+                                            let breed_placeholders: Vec<_> =
+                                                breeds.iter().map(|_| "?").collect();
+                                            species_clauses.push(format!(
+                                                "(specie = ? AND breed IN ({}))",
+                                                breed_placeholders.join(",")
+                                            ));
+                                            params.push(rusqlite::types::Value::from(specie));
+                                            for b in breeds {
+                                                params.push(rusqlite::types::Value::from(b));
+                                            }
+                                        }
+                                    }
+                                    if species_clauses.is_empty() {
+                                        where_clauses.push("1=0".to_string());
+                                    // No matches if all nested breed lists are empty
+                                    } else {
+                                        where_clauses
+                                            .push(format!("({})", species_clauses.join(" OR ")));
+                                    }
+                                }
+                            }
+                        }
+                        FilterCriteria::AdmissionDate => {
+                            if let FilterValue::ChooseOne(date_option) = value {
+                                if date_option == "all_time" {
+                                    continue;
+                                }
+                                let now = Utc::now();
+                                let start_of_period = match date_option.as_str() {
+                                    "today" => now.date_naive().and_hms_opt(0, 0, 0).unwrap(),
+                                    "this_week" => {
+                                        let weekday = now.weekday();
+                                        let days_to_subtract = weekday.num_days_from_monday();
+                                        now.date_naive()
+                                            .checked_sub_signed(Duration::days(
+                                                days_to_subtract as i64,
+                                            ))
+                                            .unwrap()
+                                            .and_hms_opt(0, 0, 0)
+                                            .unwrap()
+                                    }
+                                    "this_month" => now
+                                        .date_naive()
+                                        .with_day(1)
+                                        .unwrap()
+                                        .and_hms_opt(0, 0, 0)
+                                        .unwrap(),
+                                    "this_year" => now
+                                        .date_naive()
+                                        .with_ordinal(1)
+                                        .unwrap()
+                                        .and_hms_opt(0, 0, 0)
+                                        .unwrap(),
+                                    _ => continue,
+                                };
+                                let start_timestamp = start_of_period.and_utc().timestamp();
+                                where_clauses.push("admission_timestamp >= ?".to_string());
+                                params.push(rusqlite::types::Value::Integer(start_timestamp));
+                            }
+                        }
+                        FilterCriteria::AdoptionDate => {
+                            if let FilterValue::ChooseOne(date_option) = value {
+                                if date_option == "all_time" {
+                                    continue;
+                                }
+                                let now = Utc::now();
+                                let start_of_period = match date_option.as_str() {
+                                    "today" => now.date_naive().and_hms_opt(0, 0, 0).unwrap(),
+                                    "this_week" => {
+                                        let weekday = now.weekday();
+                                        let days_to_subtract = weekday.num_days_from_monday();
+                                        now.date_naive()
+                                            .checked_sub_signed(Duration::days(
+                                                days_to_subtract as i64,
+                                            ))
+                                            .unwrap()
+                                            .and_hms_opt(0, 0, 0)
+                                            .unwrap()
+                                    }
+                                    "this_month" => now
+                                        .date_naive()
+                                        .with_day(1)
+                                        .unwrap()
+                                        .and_hms_opt(0, 0, 0)
+                                        .unwrap(),
+                                    "this_year" => now
+                                        .date_naive()
+                                        .with_ordinal(1)
+                                        .unwrap()
+                                        .and_hms_opt(0, 0, 0)
+                                        .unwrap(),
+                                    _ => continue,
+                                };
+                                let start_timestamp = start_of_period.and_utc().timestamp();
+                                where_clauses.push(
+                                    "EXISTS (SELECT 1 FROM adoption_requests ar WHERE ar.animal_id = animals.id AND ar.status = 'approved' AND ar.adoption_timestamp >= ?)".to_string()
+                                );
+                                params.push(rusqlite::types::Value::Integer(start_timestamp));
+                            }
+                        }
+                        FilterCriteria::Location => {
+                            if let FilterValue::ChooseMany(location_ids) = value {
+                                if location_ids.is_empty() {
+                                    where_clauses.push("1=0".to_string()); // No matches if empty list
+                                } else {
+                                    let placeholders: Vec<_> =
+                                        location_ids.iter().map(|_| "?").collect();
+                                    where_clauses.push(format!(
+                                        "location_id IN ({})",
+                                        placeholders.join(",")
+                                    ));
+                                    for id in location_ids {
+                                        params.push(rusqlite::types::Value::from(id));
+                                    }
+                                }
+                            }
+                        }
+                        FilterCriteria::Neutered => {
+                            if let FilterValue::ChooseOne(neutered_option) = value {
+                                match neutered_option.as_str() {
+                                    "yes" => where_clauses.push("neutered = 1".to_string()),
+                                    "no" => where_clauses.push("neutered = 0".to_string()),
+                                    _ => continue,
+                                }
+                            }
+                        }
+                        FilterCriteria::HasImage => {
+                            if let FilterValue::ChooseOne(image_option) = value {
+                                match image_option.as_str() {
+                                    "yes" => where_clauses.push(
+                                        "(image_path IS NOT NULL AND image_path != '')".to_string(),
+                                    ),
+                                    "no" => where_clauses.push(
+                                        "(image_path IS NULL OR image_path = '')".to_string(),
+                                    ),
+                                    _ => continue,
+                                }
+                            }
+                        }
+                        FilterCriteria::Age => {
+                            if let FilterValue::ChooseOne(age_option) = value {
+                                // Compare months since year 0, treating an unknown birth month
+                                // as the current one so the age is counted in whole years
+                                let now = Utc::now();
+                                let current_month = now.year() * 12 + now.month() as i32;
+                                let birth = "birth_year * 12 + COALESCE(birth_month, ?)";
+                                let (clause, bounds): (String, Vec<i32>) = match age_option.as_str()
+                                {
+                                    "young" => (format!("{} > ?", birth), vec![current_month - 12]),
+                                    "adult" => (
+                                        format!("{0} <= ? AND {0} > ?", birth),
+                                        vec![current_month - 12, current_month - 96],
+                                    ),
+                                    "senior" => {
+                                        (format!("{} <= ?", birth), vec![current_month - 96])
+                                    }
+                                    "unknown" => ("birth_year IS NULL".to_string(), Vec::new()),
+                                    _ => continue,
+                                };
+                                where_clauses.push(format!("({})", clause));
+                                for bound in bounds {
+                                    params
+                                        .push(rusqlite::types::Value::Integer(now.month() as i64));
+                                    params.push(rusqlite::types::Value::Integer(bound as i64));
+                                }
+                            }
+                        }
+                        FilterCriteria::Size => {
+                            if let FilterValue::ChooseMany(sizes) = value {
+                                if sizes.is_empty() {
+                                    where_clauses.push("1=0".to_string()); // No matches if empty list
+                                } else {
+                                    let placeholders: Vec<_> = sizes.iter().map(|_| "?").collect();
+                                    where_clauses
+                                        .push(format!("size IN ({})", placeholders.join(",")));
+                                    for s in sizes {
+                                        params.push(rusqlite::types::Value::from(s));
+                                    }
+                                }
+                            }
+                        }
+                        FilterCriteria::Color => {
+                            if let FilterValue::ChooseMany(colors) = value {
+                                if colors.is_empty() {
+                                    where_clauses.push("1=0".to_string()); // No matches if empty list
+                                } else {
+                                    let placeholders: Vec<_> = colors.iter().map(|_| "?").collect();
+                                    // Visitors say "brown" as often as "Brown"
+                                    where_clauses.push(format!(
+                                        "color COLLATE NOCASE IN ({})",
+                                        placeholders.join(",")
+                                    ));
+                                    for c in colors {
+                                        params.push(rusqlite::types::Value::from(c));
+                                    }
+                                }
+                            }
+                        }
+                        FilterCriteria::GoodWithKids
+                        | FilterCriteria::GoodWithDogs
+                        | FilterCriteria::GoodWithCats
+                        | FilterCriteria::SpecialNeeds => {
+                            if let FilterValue::ChooseOne(flag_option) = value {
+                                let column = match criteria {
+                                    FilterCriteria::GoodWithKids => "good_with_kids",
+                                    FilterCriteria::GoodWithDogs => "good_with_dogs",
+                                    FilterCriteria::GoodWithCats => "good_with_cats",
+                                    _ => "special_needs",
+                                };
+                                match flag_option.as_str() {
+                                    "yes" => where_clauses.push(format!("{} = 1", column)),
+                                    "no" => where_clauses.push(format!("{} = 0", column)),
+                                    "unknown" => where_clauses.push(format!("{} IS NULL", column)),
+                                    _ => continue,
+                                }
+                            }
+                        }
+                    }
+                }
             }
         }
     }
+
+    if where_clauses.is_empty() {
+        (String::new(), params)
+    } else {
+        (format!(" WHERE {}", where_clauses.join(" AND ")), params)
+    }
 }
@@ -0,0 +1,130 @@
+//
+// database_service/notes.rs
+//
+// This module provides operations for managing staff notes about animals.
+//
+
+use super::types::AnimalNote;
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use rusqlite::params;
+use uuid::Uuid;
+
+impl DatabaseService {
+    // ==================== ANIMAL_NOTES TABLE OPERATIONS ====================
+
+    /// Retrieves the notes about a specific animal, most recent first
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to retrieve notes for
+    /// * `include_internal` - Whether to include notes that are for staff only
+    ///
+    /// # Returns
+    /// * `Result<Vec<AnimalNote>>` - List of notes or error
+    pub fn query_animal_notes_by_animal_id(
+        &self,
+        animal_id: &str,
+        include_internal: bool,
+    ) -> Result<Vec<AnimalNote>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, animal_id, author_username, category, content, internal, created_timestamp FROM animal_notes WHERE animal_id = ?1 AND (?2 OR NOT internal) ORDER BY created_timestamp DESC",
+            )
+            .context("Failed to prepare query for animal notes by animal ID")?;
+
+        let note_iter = statement
+            .query_map(params![animal_id, include_internal], |row| {
+                Ok(AnimalNote {
+                    id: row.get(0)?,
+                    animal_id: row.get(1)?,
+                    author_username: row.get(2)?,
+                    category: row.get(3)?,
+                    content: row.get(4)?,
+                    internal: row.get(5)?,
+                    created_timestamp: row.get(6)?,
+                })
+            })
+            .context("Failed to execute query for animal notes by animal ID")?;
+
+        let mut notes = Vec::new();
+        for note in note_iter {
+            notes.push(note.context("Failed to parse animal note row")?);
+        }
+
+        log::debug!(
+            "Retrieved {} notes for animal ID: {}",
+            notes.len(),
+            animal_id
+        );
+        Ok(notes)
+    }
+
+    /// Inserts a new note about an animal into the database
+    ///
+    /// # Arguments
+    /// * `note` - The note to insert; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn insert_animal_note(&self, note: &AnimalNote) -> Result<()> {
+        if note.content.trim().is_empty() {
+            bail!("Note cannot be empty");
+        }
+
+        let id = if note.id.trim().is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            note.id.clone()
+        };
+
+        self.connection
+            .execute(
+                "INSERT INTO animal_notes (id, animal_id, author_username, category, content, internal, created_timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    id,
+                    note.animal_id,
+                    note.author_username,
+                    note.category,
+                    note.content.trim(),
+                    note.internal,
+                    note.created_timestamp
+                ],
+            )
+            .context("Failed to insert animal note into database")?;
+
+        log::info!("Added note {} for animal with ID: {}", id, note.animal_id);
+        Ok(())
+    }
+
+    /// Deletes a note about an animal from the database by ID
+    ///
+    /// # Arguments
+    /// * `note_id` - The ID of the note to delete
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the note was found and deleted, false if not found
+    pub fn delete_animal_note(&self, note_id: &str) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute("DELETE FROM animal_notes WHERE id = ?1", params![note_id])
+            .context("Failed to delete animal note from database")?;
+
+        match rows_affected {
+            1 => {
+                log::info!("Successfully deleted note with ID: {}", note_id);
+                Ok(true)
+            }
+            0 => {
+                log::warn!("No note found with ID: {} for deletion", note_id);
+                Ok(false)
+            }
+            _ => {
+                bail!(
+                    "Unexpected number of rows affected when deleting note: {}",
+                    rows_affected
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//
+// database_service/notifications.rs
+//
+// This module provides operations for managing in-app notifications for users.
+//
+
+use super::types::Notification;
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::params;
+use uuid::Uuid;
+
+impl DatabaseService {
+    // ==================== NOTIFICATIONS TABLE OPERATIONS ====================
+
+    /// Retrieves the notifications for a specific user, most recent first
+    ///
+    /// # Arguments
+    /// * `username` - The username to retrieve notifications for
+    ///
+    /// # Returns
+    /// * `Result<Vec<Notification>>` - List of notifications or error
+    pub fn query_notifications_by_username(&self, username: &str) -> Result<Vec<Notification>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, username, message, animal_id, created_timestamp, read FROM notifications WHERE username = ?1 ORDER BY created_timestamp DESC",
+            )
+            .context("Failed to prepare query for notifications by username")?;
+
+        let notification_iter = statement
+            .query_map(params![username], |row| {
+                Ok(Notification {
+                    id: row.get(0)?,
+                    username: row.get(1)?,
+                    message: row.get(2)?,
+                    animal_id: row.get(3)?,
+                    created_timestamp: row.get(4)?,
+                    read: row.get(5)?,
+                })
+            })
+            .context("Failed to execute query for notifications by username")?;
+
+        let mut notifications = Vec::new();
+        for notification in notification_iter {
+            notifications.push(notification.context("Failed to parse notification row")?);
+        }
+
+        log::debug!(
+            "Retrieved {} notifications for user: {}",
+            notifications.len(),
+            username
+        );
+        Ok(notifications)
+    }
+
+    /// Creates a new unread notification for a user
+    ///
+    /// # Arguments
+    /// * `username` - The username of the user to notify
+    /// * `message` - Text of the notification
+    /// * `animal_id` - ID of the animal the notification is about, if any
+    ///
+    /// # Returns
+    /// * `Result<Notification>` - The stored notification or error
+    pub fn insert_notification(
+        &self,
+        username: &str,
+        message: &str,
+        animal_id: Option<&str>,
+    ) -> Result<Notification> {
+        let notification = Notification {
+            id: Uuid::new_v4().to_string(),
+            username: username.to_string(),
+            message: message.to_string(),
+            animal_id: animal_id.map(str::to_string),
+            created_timestamp: Utc::now().timestamp(),
+            read: false,
+        };
+
+        self.connection
+            .execute(
+                "INSERT INTO notifications (id, username, message, animal_id, created_timestamp, read) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    notification.id,
+                    notification.username,
+                    notification.message,
+                    notification.animal_id,
+                    notification.created_timestamp,
+                    notification.read
+                ],
+            )
+            .context("Failed to insert notification into database")?;
+
+        log::debug!("Created notification for user: {}", username);
+        Ok(notification)
+    }
+
+    /// Marks a notification of a user as read
+    ///
+    /// # Arguments
+    /// * `notification_id` - The ID of the notification to mark
+    /// * `username` - The username of the user the notification must belong to
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the notification was found and marked, false if not found
+    pub fn mark_notification_read(&self, notification_id: &str, username: &str) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE notifications SET read = 1 WHERE id = ?1 AND username = ?2",
+                params![notification_id, username],
+            )
+            .context("Failed to mark notification as read")?;
+
+        Ok(rows_affected == 1)
+    }
+}
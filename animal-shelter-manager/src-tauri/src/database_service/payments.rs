@@ -0,0 +1,246 @@
+//
+// database_service/payments.rs
+//
+// This module provides operations for the fees charged for adoption requests,
+// and for numbering the invoices and receipts issued for them. Each kind of
+// document is numbered from its own sequence, stored in the database, so a
+// number is never issued twice.
+//
+
+use super::types::{Payment, PaymentDocument, RecordPaymentResult};
+use super::validation;
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use uuid::Uuid;
+
+/// Columns selected when reading a payment, in the order expected by `payment_from_row`
+const PAYMENT_COLUMNS: &str = "id, request_id, amount, description, created_timestamp, paid_timestamp, invoice_number, receipt_number";
+
+/// Builds a payment from a row selected with `PAYMENT_COLUMNS`
+fn payment_from_row(row: &Row) -> rusqlite::Result<Payment> {
+    Ok(Payment {
+        id: row.get(0)?,
+        request_id: row.get(1)?,
+        amount: row.get(2)?,
+        description: row.get(3)?,
+        created_timestamp: row.get(4)?,
+        paid_timestamp: row.get(5)?,
+        invoice_number: row.get(6)?,
+        receipt_number: row.get(7)?,
+    })
+}
+
+/// Reads a payment by its ID
+///
+/// # Arguments
+/// * `connection` - The connection, or transaction, to read with
+/// * `payment_id` - The ID of the payment
+///
+/// # Returns
+/// * `Result<Option<Payment>>` - The payment, or None if not found
+fn read_payment(connection: &Connection, payment_id: &str) -> Result<Option<Payment>> {
+    connection
+        .query_row(
+            &format!("SELECT {} FROM payments WHERE id = ?1", PAYMENT_COLUMNS),
+            params![payment_id],
+            payment_from_row,
+        )
+        .optional()
+        .context("Failed to read payment")
+}
+
+/// Takes the next number of a sequence of documents, starting it at 1 the first time
+/// it is used
+///
+/// # Arguments
+/// * `connection` - The transaction numbering the document
+/// * `sequence` - Name of the sequence, such as invoice
+///
+/// # Returns
+/// * `Result<i64>` - The number, never issued before in the sequence
+pub(super) fn next_document_number(connection: &Connection, sequence: &str) -> Result<i64> {
+    connection
+        .query_row(
+            "INSERT INTO document_sequences (name, last_number) VALUES (?1, 1) ON CONFLICT (name) DO UPDATE SET last_number = last_number + 1 RETURNING last_number",
+            params![sequence],
+            |row| row.get(0),
+        )
+        .context(format!("Failed to take next {} number", sequence))
+}
+
+impl DatabaseService {
+    // ==================== PAYMENTS TABLE OPERATIONS ====================
+
+    /// Retrieves the fees charged for an adoption request
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the adoption request
+    ///
+    /// # Returns
+    /// * `Result<Vec<Payment>>` - Payments, in the order they were charged
+    pub fn query_payments(&self, request_id: &str) -> Result<Vec<Payment>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM payments WHERE request_id = ?1 ORDER BY created_timestamp, rowid",
+                PAYMENT_COLUMNS
+            ))
+            .context("Failed to prepare query for payments")?;
+        let payments = statement
+            .query_map(params![request_id], payment_from_row)
+            .context("Failed to execute query for payments")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse payment row")?;
+
+        log::debug!(
+            "Retrieved {} payments for adoption request: {}",
+            payments.len(),
+            request_id
+        );
+        Ok(payments)
+    }
+
+    /// Retrieves a payment by its ID
+    ///
+    /// # Arguments
+    /// * `payment_id` - The ID of the payment
+    ///
+    /// # Returns
+    /// * `Result<Option<Payment>>` - The payment, or None if not found
+    pub fn query_payment_by_id(&self, payment_id: &str) -> Result<Option<Payment>> {
+        read_payment(&self.connection, payment_id)
+    }
+
+    /// Charges a fee for an adoption request
+    ///
+    /// The payment is recorded unpaid and without document numbers, whatever it holds.
+    /// Fails with a `ValidationError` if the amount is not positive or the description
+    /// is empty.
+    ///
+    /// # Arguments
+    /// * `payment` - The fee to charge; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<RecordPaymentResult>` - Recorded, or RequestNotFound
+    pub fn insert_payment(&self, payment: &Payment) -> Result<RecordPaymentResult> {
+        validation::ensure_valid(validation::validate_payment(payment))?;
+        if !self.adoption_request_exists(&payment.request_id)? {
+            return Ok(RecordPaymentResult::RequestNotFound);
+        }
+
+        let id = if payment.id.trim().is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            payment.id.clone()
+        };
+        self.connection
+            .execute(
+                "INSERT INTO payments (id, request_id, amount, description, created_timestamp, paid_timestamp, invoice_number, receipt_number) VALUES (?1, ?2, ?3, ?4, ?5, NULL, NULL, NULL)",
+                params![
+                    id,
+                    payment.request_id,
+                    payment.amount,
+                    payment.description.trim(),
+                    payment.created_timestamp
+                ],
+            )
+            .context("Failed to insert payment into database")?;
+
+        log::info!(
+            "Recorded payment {} for adoption request with ID: {}",
+            id,
+            payment.request_id
+        );
+        Ok(RecordPaymentResult::Recorded)
+    }
+
+    /// Records that a fee was paid; a payment already marked paid keeps its original date
+    ///
+    /// # Arguments
+    /// * `payment_id` - The ID of the payment
+    /// * `paid_timestamp` - Timestamp when the fee was paid
+    ///
+    /// # Returns
+    /// * `Result<Option<Payment>>` - The updated payment, or None if not found
+    pub fn mark_payment_paid(
+        &self,
+        payment_id: &str,
+        paid_timestamp: i64,
+    ) -> Result<Option<Payment>> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE payments SET paid_timestamp = COALESCE(paid_timestamp, ?2) WHERE id = ?1",
+                params![payment_id, paid_timestamp],
+            )
+            .context("Failed to mark payment as paid")?;
+        if rows_affected == 0 {
+            log::warn!("No payment found with ID: {} to mark as paid", payment_id);
+            return Ok(None);
+        }
+
+        log::info!("Marked payment with ID: {} as paid", payment_id);
+        read_payment(&self.connection, payment_id)
+    }
+
+    /// Numbers a document issued for a payment, taking the next number of its sequence
+    ///
+    /// A payment keeps the number it was given, so generating its document again prints
+    /// the same number. Fails if a receipt is asked for a payment that was not paid.
+    ///
+    /// # Arguments
+    /// * `payment_id` - The ID of the payment
+    /// * `document` - The kind of document to number
+    ///
+    /// # Returns
+    /// * `Result<Option<Payment>>` - The payment with the document's number, or None if
+    ///   not found
+    pub fn issue_payment_document(
+        &self,
+        payment_id: &str,
+        document: PaymentDocument,
+    ) -> Result<Option<Payment>> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for payment document")?;
+
+        let Some(payment) = read_payment(&transaction, payment_id)? else {
+            return Ok(None);
+        };
+        let (number, column) = match document {
+            PaymentDocument::Invoice => (payment.invoice_number, "invoice_number"),
+            PaymentDocument::Receipt => {
+                if payment.paid_timestamp.is_none() {
+                    bail!("Payment with ID {} has not been paid", payment_id);
+                }
+                (payment.receipt_number, "receipt_number")
+            }
+        };
+        if number.is_some() {
+            return Ok(Some(payment));
+        }
+
+        let number = next_document_number(&transaction, &document.to_string())?;
+        transaction
+            .execute(
+                &format!("UPDATE payments SET {} = ?2 WHERE id = ?1", column),
+                params![payment_id, number],
+            )
+            .context("Failed to number payment document")?;
+        let payment = read_payment(&transaction, payment_id)?;
+
+        transaction
+            .commit()
+            .context("Failed to commit payment document number")?;
+
+        log::info!(
+            "Issued {} {} for payment with ID: {}",
+            document,
+            document.format_number(number),
+            payment_id
+        );
+        Ok(payment)
+    }
+}
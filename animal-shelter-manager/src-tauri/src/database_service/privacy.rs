@@ -0,0 +1,370 @@
+//
+// database_service/privacy.rs
+//
+// This module provides operations for customers' rights over their personal
+// data: exporting everything stored about them, and asking for it to be erased.
+// Erasure waits for an admin's approval, and anonymizes historical adoption
+// records instead of deleting them, so the shelter's statistics still add up.
+//
+
+use super::types::{
+    AnimalStatus, CreateErasureResult, EmailStatus, ErasureRequest, ErasureRequestStatus,
+    RequestStatus, ReviewErasureResult, UserDataExport,
+};
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension, Row};
+use uuid::Uuid;
+
+/// Name left on adoption requests and emails in place of the requester's name
+pub const ERASED_NAME: &str = "Erased";
+
+/// Columns selected by the erasure request queries, in the order `erasure_request_from_row` reads them
+const ERASURE_REQUEST_COLUMNS: &str =
+    "id, username, status, requested_timestamp, reviewed_by, reviewed_timestamp";
+
+/// Reads an erasure request from a row selected with `ERASURE_REQUEST_COLUMNS`
+fn erasure_request_from_row(row: &Row) -> rusqlite::Result<ErasureRequest> {
+    Ok(ErasureRequest {
+        id: row.get(0)?,
+        username: row.get(1)?,
+        status: row.get(2)?,
+        requested_timestamp: row.get(3)?,
+        reviewed_by: row.get(4)?,
+        reviewed_timestamp: row.get(5)?,
+    })
+}
+
+impl DatabaseService {
+    // ==================== DATA EXPORT OPERATIONS ====================
+
+    /// Gathers everything stored about a customer: their profile, adoption requests,
+    /// favorites and appointments
+    ///
+    /// # Arguments
+    /// * `username` - The user name to export the data of
+    ///
+    /// # Returns
+    /// * `Result<UserDataExport>` - The customer's data or error
+    pub fn query_user_data_export(&self, username: &str) -> Result<UserDataExport> {
+        Ok(UserDataExport {
+            username: username.to_string(),
+            exported_timestamp: Utc::now().timestamp(),
+            profile: self.query_profile(username)?,
+            adoption_requests: self.query_adoption_requests_by_username(username)?,
+            favorites: self.query_favorites_by_username(username)?,
+            appointments: self.query_appointments_by_username(username)?,
+        })
+    }
+
+    // ==================== ERASURE REQUESTS TABLE OPERATIONS ====================
+
+    /// Retrieves erasure requests, oldest first
+    ///
+    /// # Arguments
+    /// * `status` - Only retrieve requests with this status, or all requests if None
+    ///
+    /// # Returns
+    /// * `Result<Vec<ErasureRequest>>` - List of erasure requests or error
+    pub fn query_erasure_requests(
+        &self,
+        status: Option<ErasureRequestStatus>,
+    ) -> Result<Vec<ErasureRequest>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM erasure_requests WHERE ?1 IS NULL OR status = ?1 ORDER BY requested_timestamp, id",
+                ERASURE_REQUEST_COLUMNS
+            ))
+            .context("Failed to prepare query for erasure requests")?;
+
+        let request_iter = statement
+            .query_map(params![status], erasure_request_from_row)
+            .context("Failed to execute query for erasure requests")?;
+
+        let mut requests = Vec::new();
+        for request in request_iter {
+            requests.push(request.context("Failed to parse erasure request row")?);
+        }
+        Ok(requests)
+    }
+
+    /// Retrieves a specific erasure request by ID
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the erasure request to retrieve
+    ///
+    /// # Returns
+    /// * `Result<Option<ErasureRequest>>` - The erasure request or None if not found
+    pub fn query_erasure_request_by_id(&self, request_id: &str) -> Result<Option<ErasureRequest>> {
+        self.connection
+            .query_row(
+                &format!(
+                    "SELECT {} FROM erasure_requests WHERE id = ?1",
+                    ERASURE_REQUEST_COLUMNS
+                ),
+                params![request_id],
+                erasure_request_from_row,
+            )
+            .optional()
+            .context("Failed to query erasure request")
+    }
+
+    /// Stores a customer's request to have their personal data erased, for an admin to review
+    ///
+    /// # Arguments
+    /// * `username` - The user name of the customer asking for erasure
+    ///
+    /// # Returns
+    /// * `Result<CreateErasureResult>` - The stored request, or the one already waiting for review
+    pub fn insert_erasure_request(&self, username: &str) -> Result<CreateErasureResult> {
+        let pending = self
+            .connection
+            .query_row(
+                &format!(
+                    "SELECT {} FROM erasure_requests WHERE username = ?1 AND status = ?2",
+                    ERASURE_REQUEST_COLUMNS
+                ),
+                params![username, ErasureRequestStatus::Pending],
+                erasure_request_from_row,
+            )
+            .optional()
+            .context("Failed to check for a pending erasure request")?;
+        if let Some(pending) = pending {
+            return Ok(CreateErasureResult::AlreadyPending(pending));
+        }
+
+        let request = ErasureRequest {
+            id: Uuid::new_v4().to_string(),
+            username: username.to_string(),
+            status: ErasureRequestStatus::Pending,
+            requested_timestamp: Utc::now().timestamp(),
+            reviewed_by: None,
+            reviewed_timestamp: None,
+        };
+        self.connection
+            .execute(
+                "INSERT INTO erasure_requests (id, username, status, requested_timestamp) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    request.id,
+                    request.username,
+                    request.status,
+                    request.requested_timestamp
+                ],
+            )
+            .context("Failed to insert erasure request into database")?;
+
+        log::info!(
+            "User {} asked for their data to be erased, request ID: {}",
+            username,
+            request.id
+        );
+        Ok(CreateErasureResult::Created(request))
+    }
+
+    /// Approves an erasure request and erases the customer's personal data
+    ///
+    /// The customer's profile, favorites, waitlist entries and notifications are deleted.
+    /// Their adoption requests, including trashed ones, are kept for the shelter's records
+    /// but anonymized: the personal details are cleared and the username is replaced, and
+    /// pending requests are rejected. The erasure request itself keeps the username, as
+    /// the record that the data was erased.
+    ///
+    /// The customer's account is not touched; it is held by the authentication service.
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the erasure request to approve
+    /// * `reviewer` - The user name of the admin approving it
+    ///
+    /// # Returns
+    /// * `Result<ReviewErasureResult>` - The approved request, or why it could not be approved
+    pub fn approve_erasure_request(
+        &self,
+        request_id: &str,
+        reviewer: &str,
+    ) -> Result<ReviewErasureResult> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for erasure")?;
+
+        let request = match self.query_erasure_request_by_id(request_id)? {
+            None => return Ok(ReviewErasureResult::NotFound),
+            Some(request) if request.status != ErasureRequestStatus::Pending => {
+                return Ok(ReviewErasureResult::AlreadyReviewed(request))
+            }
+            Some(request) => request,
+        };
+        let username = &request.username;
+        let erased_username = format!("erased-{}", request.id);
+
+        // Emails waiting to be sent would reach the customer after the erasure, so drop
+        // them, and clear the recipient of those already sent
+        transaction
+            .execute(
+                "DELETE FROM emails WHERE status = ?2 AND request_id IN (SELECT id FROM adoption_requests WHERE username = ?1)",
+                params![username, EmailStatus::Pending],
+            )
+            .context("Failed to delete pending emails")?;
+        transaction
+            .execute(
+                "UPDATE emails SET recipient = '', recipient_name = ?2, body = NULL WHERE request_id IN (SELECT id FROM adoption_requests WHERE username = ?1 UNION SELECT id FROM trashed_adoption_requests WHERE username = ?1)",
+                params![username, ERASED_NAME],
+            )
+            .context("Failed to anonymize emails")?;
+
+        // Interview notes and referees describe the customer, so they go entirely
+        for table in ["request_interviews", "reference_checks"] {
+            transaction
+                .execute(
+                    &format!(
+                        "DELETE FROM {} WHERE request_id IN (SELECT id FROM adoption_requests WHERE username = ?1 UNION SELECT id FROM trashed_adoption_requests WHERE username = ?1)",
+                        table
+                    ),
+                    params![username],
+                )
+                .context(format!("Failed to delete {} of user", table))?;
+        }
+
+        // Remember the animals with a pending request, which may have no other applicant left
+        let requested_animal_ids: Vec<String> = {
+            let mut statement = transaction
+                .prepare(
+                    "SELECT DISTINCT animal_id FROM adoption_requests WHERE username = ?1 AND status = ?2",
+                )
+                .context("Failed to prepare query for pending adoption requests")?;
+            let ids = statement
+                .query_map(params![username, RequestStatus::Pending], |row| row.get(0))
+                .context("Failed to query pending adoption requests")?
+                .collect::<rusqlite::Result<Vec<String>>>()
+                .context("Failed to parse pending adoption request row")?;
+            ids
+        };
+
+        let anonymized_requests = transaction
+            .execute(
+                "UPDATE adoption_requests SET username = ?2, name = ?3, email = '', tel_number = '', address = '', occupation = '', annual_income = '', num_people = 0, num_children = 0, country = '', co_applicant_name = NULL, co_applicant_relationship = NULL, co_applicant_contact = NULL, status = CASE WHEN status = ?4 THEN ?5 ELSE status END WHERE username = ?1",
+                params![
+                    username,
+                    erased_username,
+                    ERASED_NAME,
+                    RequestStatus::Pending,
+                    RequestStatus::Rejected
+                ],
+            )
+            .context("Failed to anonymize adoption requests")?;
+        transaction
+            .execute(
+                "UPDATE trashed_adoption_requests SET username = ?2, name = ?3, email = '', tel_number = '', address = '', occupation = '', annual_income = '', num_people = 0, num_children = 0, country = '', co_applicant_name = NULL, co_applicant_relationship = NULL, co_applicant_contact = NULL WHERE username = ?1",
+                params![username, erased_username, ERASED_NAME],
+            )
+            .context("Failed to anonymize trashed adoption requests")?;
+
+        // Animals left without a pending request are available again
+        for animal_id in &requested_animal_ids {
+            transaction
+                .execute(
+                    "UPDATE animals SET status = ?2, version = version + 1 WHERE id = ?1 AND status = ?3 AND NOT EXISTS (SELECT 1 FROM adoption_requests WHERE animal_id = ?1 AND status = ?4)",
+                    params![
+                        animal_id,
+                        AnimalStatus::Available,
+                        AnimalStatus::Requested,
+                        RequestStatus::Pending
+                    ],
+                )
+                .context("Failed to mark animal as available")?;
+        }
+
+        for table in [
+            "user_profiles",
+            "favorites",
+            "waitlist",
+            "notifications",
+            "appointments",
+        ] {
+            transaction
+                .execute(
+                    &format!("DELETE FROM {} WHERE username = ?1", table),
+                    params![username],
+                )
+                .context(format!("Failed to delete {} of user", table))?;
+        }
+
+        let reviewed_timestamp = Utc::now().timestamp();
+        transaction
+            .execute(
+                "UPDATE erasure_requests SET status = ?2, reviewed_by = ?3, reviewed_timestamp = ?4 WHERE id = ?1",
+                params![
+                    request.id,
+                    ErasureRequestStatus::Approved,
+                    reviewer,
+                    reviewed_timestamp
+                ],
+            )
+            .context("Failed to approve erasure request")?;
+
+        transaction.commit().context("Failed to commit erasure")?;
+
+        log::info!(
+            "Erased the data of user {} ({} adoption requests anonymized), approved by {}",
+            username,
+            anonymized_requests,
+            reviewer
+        );
+        Ok(ReviewErasureResult::Reviewed(ErasureRequest {
+            status: ErasureRequestStatus::Approved,
+            reviewed_by: Some(reviewer.to_string()),
+            reviewed_timestamp: Some(reviewed_timestamp),
+            ..request
+        }))
+    }
+
+    /// Rejects an erasure request, leaving the customer's data as it is
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the erasure request to reject
+    /// * `reviewer` - The user name of the admin rejecting it
+    ///
+    /// # Returns
+    /// * `Result<ReviewErasureResult>` - The rejected request, or why it could not be rejected
+    pub fn reject_erasure_request(
+        &self,
+        request_id: &str,
+        reviewer: &str,
+    ) -> Result<ReviewErasureResult> {
+        let request = match self.query_erasure_request_by_id(request_id)? {
+            None => return Ok(ReviewErasureResult::NotFound),
+            Some(request) if request.status != ErasureRequestStatus::Pending => {
+                return Ok(ReviewErasureResult::AlreadyReviewed(request))
+            }
+            Some(request) => request,
+        };
+
+        let reviewed_timestamp = Utc::now().timestamp();
+        self.connection
+            .execute(
+                "UPDATE erasure_requests SET status = ?2, reviewed_by = ?3, reviewed_timestamp = ?4 WHERE id = ?1",
+                params![
+                    request.id,
+                    ErasureRequestStatus::Rejected,
+                    reviewer,
+                    reviewed_timestamp
+                ],
+            )
+            .context("Failed to reject erasure request")?;
+
+        log::info!(
+            "Rejected erasure request {} of user {}, reviewed by {}",
+            request.id,
+            request.username,
+            reviewer
+        );
+        Ok(ReviewErasureResult::Reviewed(ErasureRequest {
+            status: ErasureRequestStatus::Rejected,
+            reviewed_by: Some(reviewer.to_string()),
+            reviewed_timestamp: Some(reviewed_timestamp),
+            ..request
+        }))
+    }
+}
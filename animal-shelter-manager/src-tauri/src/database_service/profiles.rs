@@ -0,0 +1,101 @@
+//
+// database_service/profiles.rs
+//
+// This module provides operations for managing the contact details users save
+// in their profile, and for filling adoption requests in from them.
+//
+
+use super::types::{AdoptionRequest, UserProfile};
+use super::validation;
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use rusqlite::{params, OptionalExtension};
+
+impl DatabaseService {
+    // ==================== USER PROFILE TABLE OPERATIONS ====================
+
+    /// Retrieves the profile of a user
+    ///
+    /// # Arguments
+    /// * `username` - The user name to retrieve the profile for
+    ///
+    /// # Returns
+    /// * `Result<Option<UserProfile>>` - The profile, None if the user has not saved one
+    pub fn query_profile(&self, username: &str) -> Result<Option<UserProfile>> {
+        self.connection
+            .query_row(
+                "SELECT username, full_name, email, tel_number, address, country, updated_timestamp FROM user_profiles WHERE username = ?1",
+                params![username],
+                |row| {
+                    Ok(UserProfile {
+                        username: row.get(0)?,
+                        full_name: row.get(1)?,
+                        email: row.get(2)?,
+                        tel_number: row.get(3)?,
+                        address: row.get(4)?,
+                        country: row.get(5)?,
+                        updated_timestamp: row.get(6)?,
+                    })
+                },
+            )
+            .optional()
+            .context("Failed to query user profile")
+    }
+
+    /// Creates or replaces the profile of a user
+    ///
+    /// # Arguments
+    /// * `profile` - The profile to store
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or error if the profile is invalid or storing it fails
+    pub fn upsert_profile(&self, profile: &UserProfile) -> Result<()> {
+        validation::ensure_valid(validation::validate_profile(profile))?;
+
+        self.connection
+            .execute(
+                "INSERT INTO user_profiles (username, full_name, email, tel_number, address, country, updated_timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT(username) DO UPDATE SET full_name = excluded.full_name, email = excluded.email, tel_number = excluded.tel_number, address = excluded.address, country = excluded.country, updated_timestamp = excluded.updated_timestamp",
+                params![
+                    profile.username,
+                    profile.full_name,
+                    profile.email,
+                    profile.tel_number,
+                    profile.address,
+                    profile.country,
+                    profile.updated_timestamp,
+                ],
+            )
+            .context("Failed to store user profile")?;
+
+        log::info!("Saved profile for user name: {}", profile.username);
+        Ok(())
+    }
+
+    /// Fills the contact details of an adoption request from the profile of its user.
+    /// Fields left blank in the profile keep the values already in the request.
+    ///
+    /// # Arguments
+    /// * `request` - The adoption request to fill in; its username selects the profile
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if a profile was found and applied, false if the user has none
+    pub fn fill_request_from_profile(&self, request: &mut AdoptionRequest) -> Result<bool> {
+        let Some(profile) = self.query_profile(&request.username)? else {
+            return Ok(false);
+        };
+
+        for (field, value) in [
+            (&mut request.name, profile.full_name),
+            (&mut request.email, profile.email),
+            (&mut request.tel_number, profile.tel_number),
+            (&mut request.address, profile.address),
+            (&mut request.country, profile.country),
+        ] {
+            if !value.trim().is_empty() {
+                *field = value;
+            }
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,165 @@
+//
+// database_service/relationships.rs
+//
+// This module provides operations for managing bonded animals that should be
+// adopted together.
+//
+
+use super::types::{AnimalSummary, RequestStatus};
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use rusqlite::params;
+
+impl DatabaseService {
+    // ==================== ANIMAL_RELATIONSHIPS TABLE OPERATIONS ====================
+
+    /// Retrieves summary information for the animals bonded to a specific animal
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to retrieve bonded animals for
+    ///
+    /// # Returns
+    /// * `Result<Vec<AnimalSummary>>` - List of bonded animal summaries or error
+    pub fn query_bonded_animals(&self, animal_id: &str) -> Result<Vec<AnimalSummary>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, name, specie, breed, sex, admission_timestamp, status, image_path FROM animals WHERE id IN (
+                    SELECT bonded_animal_id FROM animal_relationships WHERE animal_id = ?1
+                    UNION
+                    SELECT animal_id FROM animal_relationships WHERE bonded_animal_id = ?1
+                ) ORDER BY name",
+            )
+            .context("Failed to prepare query for bonded animals")?;
+
+        let animal_iter = statement
+            .query_map(params![animal_id], |row| {
+                Ok(AnimalSummary {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    specie: row.get(2)?,
+                    breed: row.get(3)?,
+                    sex: row.get(4)?,
+                    admission_timestamp: row.get(5)?,
+                    status: row.get(6)?,
+                    image_path: row.get(7)?,
+                })
+            })
+            .context("Failed to execute query for bonded animals")?;
+
+        let mut animals = Vec::new();
+        for animal in animal_iter {
+            animals.push(animal.context("Failed to parse animal row")?);
+        }
+        Ok(animals)
+    }
+
+    /// Records that two animals are bonded and should be adopted together
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of one animal
+    /// * `other_animal_id` - The ID of the other animal
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the bond was created, false if it already existed
+    pub fn link_bonded_animals(&self, animal_id: &str, other_animal_id: &str) -> Result<bool> {
+        if animal_id == other_animal_id {
+            bail!("An animal cannot be bonded to itself");
+        }
+        let (first, second) = ordered_pair(animal_id, other_animal_id);
+
+        let rows_affected = self
+            .connection
+            .execute(
+                "INSERT OR IGNORE INTO animal_relationships (animal_id, bonded_animal_id) VALUES (?1, ?2)",
+                params![first, second],
+            )
+            .context("Failed to insert bond into database")?;
+
+        if rows_affected == 1 {
+            log::info!("Bonded animals with IDs: {} and {}", first, second);
+        }
+        Ok(rows_affected == 1)
+    }
+
+    /// Removes the bond between two animals
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of one animal
+    /// * `other_animal_id` - The ID of the other animal
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the bond was found and removed, false if not found
+    pub fn unlink_bonded_animals(&self, animal_id: &str, other_animal_id: &str) -> Result<bool> {
+        let (first, second) = ordered_pair(animal_id, other_animal_id);
+
+        let rows_affected = self
+            .connection
+            .execute(
+                "DELETE FROM animal_relationships WHERE animal_id = ?1 AND bonded_animal_id = ?2",
+                params![first, second],
+            )
+            .context("Failed to delete bond from database")?;
+
+        if rows_affected == 1 {
+            log::info!(
+                "Removed bond between animals with IDs: {} and {}",
+                first,
+                second
+            );
+        }
+        Ok(rows_affected == 1)
+    }
+
+    /// Finds the bonded partners of an animal that a user has not also requested or adopted
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal being adopted
+    /// * `username` - The username of the adopter
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - IDs of the bonded animals that would be left behind
+    pub(super) fn query_bonded_animals_left_behind(
+        &self,
+        animal_id: &str,
+        username: &str,
+    ) -> Result<Vec<String>> {
+        let mut left_behind = Vec::new();
+        for partner in self.query_bonded_animals(animal_id)? {
+            let requested: bool = self
+                .connection
+                .query_row(
+                    "SELECT EXISTS (SELECT 1 FROM adoption_requests WHERE animal_id = ?1 AND username = ?2 AND status IN (?3, ?4, ?5))",
+                    params![
+                        partner.id,
+                        username,
+                        RequestStatus::Pending,
+                        RequestStatus::Approved,
+                        RequestStatus::TrialAdoption
+                    ],
+                    |row| row.get(0),
+                )
+                .context("Failed to check adoption requests for bonded animal")?;
+            if !requested {
+                left_behind.push(partner.id);
+            }
+        }
+        Ok(left_behind)
+    }
+}
+
+/// Orders two animal IDs the way bonds are stored, smaller ID first
+///
+/// # Arguments
+/// * `a` - The ID of one animal
+/// * `b` - The ID of the other animal
+///
+/// # Returns
+/// * `(&str, &str)` - The two IDs in stored order
+fn ordered_pair<'a>(a: &'a str, b: &'a str) -> (&'a str, &'a str) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
@@ -0,0 +1,385 @@
+//
+// database_service/reminders.rs
+//
+// This module evaluates the rules of the reminder scheduler: vaccinations that
+// are due, available animals that have stayed at the shelter a long time,
+// requests left pending, and upcoming meet-and-greet appointments. Each reminder
+// is given once per record, as a notification to every staff member, or to the
+// customer for their own appointments.
+//
+
+use super::appointments::format_slot;
+use super::types::{
+    AnimalStatus, AnimalSummary, AppointmentStatus, LongStayAnimal, Notification, ReminderRule,
+    ReminderThresholds, RequestStatus,
+};
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use rusqlite::params;
+
+/// Number of seconds in a day
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Number of seconds in an hour
+const SECONDS_PER_HOUR: i64 = 60 * 60;
+
+/// A reminder that is due, before it is given
+struct DueReminder {
+    /// The rule the reminder comes from
+    rule: ReminderRule,
+    /// Identifies the record the reminder is about, so it is only given once
+    record_key: String,
+    /// ID of the animal the reminder is about
+    animal_id: String,
+    /// Text of the notification
+    message: String,
+    /// The user to notify, or None to notify every staff member
+    recipient: Option<String>,
+}
+
+impl DatabaseService {
+    // ==================== REMINDER RULES ====================
+
+    /// Creates a notification for every staff member, or for the customer concerned, about
+    /// each reminder that has come due and was not given before
+    ///
+    /// # Arguments
+    /// * `thresholds` - When each rule applies, or None for rules that are turned off
+    /// * `usernames` - The staff members to notify
+    /// * `now` - The current timestamp
+    ///
+    /// # Returns
+    /// * `Result<Vec<Notification>>` - The notifications created
+    pub fn create_due_reminders(
+        &self,
+        thresholds: &ReminderThresholds,
+        usernames: &[String],
+        now: i64,
+    ) -> Result<Vec<Notification>> {
+        let mut due = Vec::new();
+        if let Some(days) = thresholds.vaccination_days {
+            due.extend(
+                self.due_vaccination_reminders(now + i64::from(days) * SECONDS_PER_DAY, now)?,
+            );
+        }
+        if let Some(days) = thresholds.length_of_stay_days {
+            due.extend(self.length_of_stay_reminders(days, now)?);
+        }
+        if let Some(days) = thresholds.pending_request_days {
+            due.extend(self.pending_request_reminders(days, now)?);
+        }
+        if let Some(hours) = thresholds.appointment_hours {
+            due.extend(self.appointment_reminders(hours, now)?);
+        }
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for reminders")?;
+
+        let mut notifications = Vec::new();
+        for reminder in due {
+            let recipients = match &reminder.recipient {
+                Some(username) => std::slice::from_ref(username),
+                None => usernames,
+            };
+            // Keep reminders for later if there is nobody to give them to
+            if recipients.is_empty() {
+                continue;
+            }
+            let is_new = transaction
+                .execute(
+                    "INSERT OR IGNORE INTO sent_reminders (rule, record_key, sent_timestamp) VALUES (?1, ?2, ?3)",
+                    params![reminder.rule, reminder.record_key, now],
+                )
+                .context("Failed to record reminder")?
+                == 1;
+            if !is_new {
+                continue;
+            }
+            for username in recipients {
+                notifications.push(self.insert_notification(
+                    username,
+                    &reminder.message,
+                    Some(&reminder.animal_id),
+                )?);
+            }
+        }
+
+        transaction.commit().context("Failed to commit reminders")?;
+
+        if !notifications.is_empty() {
+            log::info!("Created {} reminder notification(s)", notifications.len());
+        }
+        Ok(notifications)
+    }
+
+    /// Finds the available animals that have been in the shelter's care for at least a
+    /// number of days, so they can be given more attention
+    ///
+    /// # Arguments
+    /// * `days` - The number of days
+    /// * `now` - The current timestamp
+    ///
+    /// # Returns
+    /// * `Result<Vec<LongStayAnimal>>` - The animals with their days in care, longest
+    ///   staying first, or error
+    pub fn query_long_stay_animals(&self, days: u32, now: i64) -> Result<Vec<LongStayAnimal>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, name, specie, breed, sex, admission_timestamp, status, image_path, (?1 - admission_timestamp) / ?2 FROM animals WHERE status = ?3 AND admission_timestamp <= ?4 ORDER BY admission_timestamp, name",
+            )
+            .context("Failed to prepare query for long-staying animals")?;
+
+        let animals = statement
+            .query_map(
+                params![
+                    now,
+                    SECONDS_PER_DAY,
+                    AnimalStatus::Available,
+                    now - i64::from(days) * SECONDS_PER_DAY
+                ],
+                |row| {
+                    Ok(LongStayAnimal {
+                        animal: AnimalSummary {
+                            id: row.get(0)?,
+                            name: row.get(1)?,
+                            specie: row.get(2)?,
+                            breed: row.get(3)?,
+                            sex: row.get(4)?,
+                            admission_timestamp: row.get(5)?,
+                            status: row.get(6)?,
+                            image_path: row.get(7)?,
+                        },
+                        days_in_care: row.get(8)?,
+                    })
+                },
+            )
+            .context("Failed to execute query for long-staying animals")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse long-staying animal row")?;
+        Ok(animals)
+    }
+
+    /// Finds the latest dose of each vaccine due by a time, for animals in the shelter's care
+    ///
+    /// # Arguments
+    /// * `due_by` - Doses due at or before this timestamp are reminded of
+    /// * `now` - The current timestamp, to tell overdue doses apart
+    ///
+    /// # Returns
+    /// * `Result<Vec<DueReminder>>` - The reminders or error
+    fn due_vaccination_reminders(&self, due_by: i64, now: i64) -> Result<Vec<DueReminder>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT v.id, v.animal_id, a.name, v.vaccine, v.due_timestamp FROM vaccinations v JOIN animals a ON a.id = v.animal_id
+                 WHERE v.due_timestamp <= ?1 AND a.status NOT IN (?2, ?3, ?4)
+                 AND NOT EXISTS (
+                     SELECT 1 FROM vaccinations later WHERE later.animal_id = v.animal_id
+                     AND later.vaccine = v.vaccine COLLATE NOCASE
+                     AND later.administered_timestamp > v.administered_timestamp
+                 )",
+            )
+            .context("Failed to prepare query for due vaccinations")?;
+
+        let rows = statement
+            .query_map(
+                params![
+                    due_by,
+                    AnimalStatus::Adopted,
+                    AnimalStatus::PassedAway,
+                    AnimalStatus::Transferred
+                ],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, i64>(4)?,
+                    ))
+                },
+            )
+            .context("Failed to execute query for due vaccinations")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse due vaccination row")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, animal_id, name, vaccine, due_timestamp)| {
+                let verb = if due_timestamp < now { "was" } else { "is" };
+                DueReminder {
+                    rule: ReminderRule::VaccinationDue,
+                    record_key: id,
+                    animal_id,
+                    message: format!(
+                        "{}'s {} vaccination {} due on {}",
+                        name,
+                        vaccine,
+                        verb,
+                        format_date(due_timestamp)
+                    ),
+                    recipient: None,
+                }
+            })
+            .collect())
+    }
+
+    /// Finds the available animals waiting at the shelter for longer than a number of days
+    ///
+    /// # Arguments
+    /// * `days` - The number of days
+    /// * `now` - The current timestamp
+    ///
+    /// # Returns
+    /// * `Result<Vec<DueReminder>>` - The reminders or error
+    fn length_of_stay_reminders(&self, days: u32, now: i64) -> Result<Vec<DueReminder>> {
+        // Keyed by admission, so an animal that returns is reminded of again
+        Ok(self
+            .query_long_stay_animals(days, now)?
+            .into_iter()
+            .map(
+                |LongStayAnimal {
+                     animal,
+                     days_in_care,
+                 }| DueReminder {
+                    rule: ReminderRule::LengthOfStay,
+                    record_key: format!("{}:{}", animal.id, animal.admission_timestamp),
+                    message: format!(
+                        "{} has been at the shelter for {} days",
+                        animal.name, days_in_care
+                    ),
+                    animal_id: animal.id,
+                    recipient: None,
+                },
+            )
+            .collect())
+    }
+
+    /// Finds the adoption requests pending for longer than a number of days
+    ///
+    /// # Arguments
+    /// * `days` - The number of days
+    /// * `now` - The current timestamp
+    ///
+    /// # Returns
+    /// * `Result<Vec<DueReminder>>` - The reminders or error
+    fn pending_request_reminders(&self, days: u32, now: i64) -> Result<Vec<DueReminder>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT r.id, r.animal_id, r.name, a.name, r.request_timestamp FROM adoption_requests r JOIN animals a ON a.id = r.animal_id WHERE r.status = ?1 AND r.request_timestamp <= ?2",
+            )
+            .context("Failed to prepare query for long-pending requests")?;
+
+        let rows = statement
+            .query_map(
+                params![
+                    RequestStatus::Pending,
+                    now - i64::from(days) * SECONDS_PER_DAY
+                ],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, i64>(4)?,
+                    ))
+                },
+            )
+            .context("Failed to execute query for long-pending requests")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse long-pending request row")?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(id, animal_id, applicant, animal_name, request_timestamp)| DueReminder {
+                    rule: ReminderRule::PendingRequest,
+                    record_key: id,
+                    animal_id,
+                    message: format!(
+                        "{}'s request to adopt {} has been pending for {} days",
+                        applicant,
+                        animal_name,
+                        (now - request_timestamp) / SECONDS_PER_DAY
+                    ),
+                    recipient: None,
+                },
+            )
+            .collect())
+    }
+
+    /// Finds the confirmed appointments starting within a number of hours, to remind the
+    /// customers who booked them
+    ///
+    /// # Arguments
+    /// * `hours` - The number of hours
+    /// * `now` - The current timestamp
+    ///
+    /// # Returns
+    /// * `Result<Vec<DueReminder>>` - The reminders or error
+    fn appointment_reminders(&self, hours: u32, now: i64) -> Result<Vec<DueReminder>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT p.id, p.animal_id, a.name, p.username, p.slot_timestamp FROM appointments p JOIN animals a ON a.id = p.animal_id WHERE p.status = ?1 AND p.slot_timestamp > ?2 AND p.slot_timestamp <= ?3",
+            )
+            .context("Failed to prepare query for upcoming appointments")?;
+
+        let rows = statement
+            .query_map(
+                params![
+                    AppointmentStatus::Confirmed,
+                    now,
+                    now + i64::from(hours) * SECONDS_PER_HOUR
+                ],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, i64>(4)?,
+                    ))
+                },
+            )
+            .context("Failed to execute query for upcoming appointments")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse upcoming appointment row")?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(id, animal_id, animal_name, username, slot_timestamp)| DueReminder {
+                    rule: ReminderRule::AppointmentUpcoming,
+                    record_key: id,
+                    animal_id,
+                    message: format!(
+                        "Reminder: your meet-and-greet with {} is on {}",
+                        animal_name,
+                        format_slot(slot_timestamp)
+                    ),
+                    recipient: Some(username),
+                },
+            )
+            .collect())
+    }
+}
+
+/// Formats a timestamp as a date, such as 2025-03-14
+///
+/// # Arguments
+/// * `timestamp` - The timestamp in seconds
+///
+/// # Returns
+/// * `String` - The date in UTC
+fn format_date(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
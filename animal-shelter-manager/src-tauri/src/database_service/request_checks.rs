@@ -0,0 +1,299 @@
+//
+// database_service/request_checks.rs
+//
+// This module provides operations for recording the phone interviews held with
+// applicants and the references they gave, which admins can require before an
+// adoption request is approved.
+//
+
+use super::types::{
+    ApprovalRequirements, ApproveRequestResult, RecordCheckResult, ReferenceCheck,
+    ReferenceOutcome, RequestChecks, RequestInterview,
+};
+use super::validation;
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, Row};
+use uuid::Uuid;
+
+/// Columns selected when reading a reference check, in the order expected by
+/// `reference_check_from_row`
+const REFERENCE_CHECK_COLUMNS: &str =
+    "id, request_id, referee_name, referee_contact, outcome, notes, checked_by, checked_timestamp";
+
+/// Builds a reference check from a row selected with `REFERENCE_CHECK_COLUMNS`
+fn reference_check_from_row(row: &Row) -> rusqlite::Result<ReferenceCheck> {
+    Ok(ReferenceCheck {
+        id: row.get(0)?,
+        request_id: row.get(1)?,
+        referee_name: row.get(2)?,
+        referee_contact: row.get(3)?,
+        outcome: row.get(4)?,
+        notes: row.get(5)?,
+        checked_by: row.get(6)?,
+        checked_timestamp: row.get(7)?,
+    })
+}
+
+impl DatabaseService {
+    // ==================== REQUEST_INTERVIEWS AND REFERENCE_CHECKS TABLE OPERATIONS ====================
+
+    /// Retrieves the interviews and reference checks recorded for an adoption request
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the adoption request
+    ///
+    /// # Returns
+    /// * `Result<RequestChecks>` - Interviews, latest first, and reference checks
+    pub fn query_request_checks(&self, request_id: &str) -> Result<RequestChecks> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, request_id, interviewer, interview_timestamp, notes FROM request_interviews WHERE request_id = ?1 ORDER BY interview_timestamp DESC",
+            )
+            .context("Failed to prepare query for request interviews")?;
+        let interviews = statement
+            .query_map(params![request_id], |row| {
+                Ok(RequestInterview {
+                    id: row.get(0)?,
+                    request_id: row.get(1)?,
+                    interviewer: row.get(2)?,
+                    interview_timestamp: row.get(3)?,
+                    notes: row.get(4)?,
+                })
+            })
+            .context("Failed to execute query for request interviews")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse request interview row")?;
+
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM reference_checks WHERE request_id = ?1 ORDER BY rowid",
+                REFERENCE_CHECK_COLUMNS
+            ))
+            .context("Failed to prepare query for reference checks")?;
+        let reference_checks = statement
+            .query_map(params![request_id], reference_check_from_row)
+            .context("Failed to execute query for reference checks")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse reference check row")?;
+
+        log::debug!(
+            "Retrieved {} interviews and {} reference checks for adoption request: {}",
+            interviews.len(),
+            reference_checks.len(),
+            request_id
+        );
+        Ok(RequestChecks {
+            interviews,
+            reference_checks,
+        })
+    }
+
+    /// Records a phone interview held with the applicant of an adoption request
+    ///
+    /// Fails with a `ValidationError` if the notes are empty or the date is not valid.
+    ///
+    /// # Arguments
+    /// * `interview` - The interview to record; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<RecordCheckResult>` - Recorded, or RequestNotFound
+    pub fn insert_request_interview(
+        &self,
+        interview: &RequestInterview,
+    ) -> Result<RecordCheckResult> {
+        validation::ensure_valid(validation::validate_request_interview(interview))?;
+        if !self.adoption_request_exists(&interview.request_id)? {
+            return Ok(RecordCheckResult::RequestNotFound);
+        }
+
+        let id = if interview.id.trim().is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            interview.id.clone()
+        };
+        self.connection
+            .execute(
+                "INSERT INTO request_interviews (id, request_id, interviewer, interview_timestamp, notes) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    id,
+                    interview.request_id,
+                    interview.interviewer,
+                    interview.interview_timestamp,
+                    interview.notes.trim()
+                ],
+            )
+            .context("Failed to insert request interview into database")?;
+
+        log::info!(
+            "Recorded interview {} for adoption request with ID: {}",
+            id,
+            interview.request_id
+        );
+        Ok(RecordCheckResult::Recorded)
+    }
+
+    /// Records a reference given by the applicant of an adoption request
+    ///
+    /// Fails with a `ValidationError` if the referee has no name, cannot be contacted at
+    /// the given email address or telephone number, or the date is not valid.
+    ///
+    /// # Arguments
+    /// * `check` - The reference check to record; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<RecordCheckResult>` - Recorded, or RequestNotFound
+    pub fn insert_reference_check(&self, check: &ReferenceCheck) -> Result<RecordCheckResult> {
+        validation::ensure_valid(validation::validate_reference_check(check))?;
+        if !self.adoption_request_exists(&check.request_id)? {
+            return Ok(RecordCheckResult::RequestNotFound);
+        }
+
+        let id = if check.id.trim().is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            check.id.clone()
+        };
+        self.connection
+            .execute(
+                "INSERT INTO reference_checks (id, request_id, referee_name, referee_contact, outcome, notes, checked_by, checked_timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    id,
+                    check.request_id,
+                    check.referee_name.trim(),
+                    check.referee_contact.trim(),
+                    check.outcome,
+                    check.notes.trim(),
+                    check.checked_by,
+                    check.checked_timestamp
+                ],
+            )
+            .context("Failed to insert reference check into database")?;
+
+        log::info!(
+            "Recorded reference check {} for adoption request with ID: {}",
+            id,
+            check.request_id
+        );
+        Ok(RecordCheckResult::Recorded)
+    }
+
+    /// Records the outcome of a reference check, such as once the referee was reached
+    ///
+    /// # Arguments
+    /// * `check_id` - The ID of the reference check
+    /// * `outcome` - What the referee said about the applicant
+    /// * `notes` - Anything worth knowing about what the referee said
+    /// * `checked_by` - Username of the staff member recording the outcome
+    ///
+    /// # Returns
+    /// * `Result<Option<ReferenceCheck>>` - The updated reference check, or None if not found
+    pub fn update_reference_check_outcome(
+        &self,
+        check_id: &str,
+        outcome: ReferenceOutcome,
+        notes: &str,
+        checked_by: &str,
+    ) -> Result<Option<ReferenceCheck>> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "UPDATE reference_checks SET outcome = ?2, notes = ?3, checked_by = ?4, checked_timestamp = ?5 WHERE id = ?1",
+                params![
+                    check_id,
+                    outcome,
+                    notes.trim(),
+                    checked_by,
+                    Utc::now().timestamp()
+                ],
+            )
+            .context("Failed to update reference check")?;
+        if rows_affected == 0 {
+            log::warn!("No reference check found with ID: {} to update", check_id);
+            return Ok(None);
+        }
+
+        let check = self
+            .connection
+            .query_row(
+                &format!(
+                    "SELECT {} FROM reference_checks WHERE id = ?1",
+                    REFERENCE_CHECK_COLUMNS
+                ),
+                params![check_id],
+                reference_check_from_row,
+            )
+            .context("Failed to read updated reference check")?;
+
+        log::info!(
+            "Recorded outcome {} for reference check with ID: {}",
+            outcome,
+            check_id
+        );
+        Ok(Some(check))
+    }
+
+    /// Finds the first check an adoption request still has to pass before it can be approved
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the adoption request
+    /// * `requirements` - The checks required before approval
+    ///
+    /// # Returns
+    /// * `Result<Option<ApproveRequestResult>>` - Why the request cannot be approved yet, or
+    ///   None if every required check was passed
+    pub(super) fn query_unmet_approval_requirement(
+        &self,
+        request_id: &str,
+        requirements: &ApprovalRequirements,
+    ) -> Result<Option<ApproveRequestResult>> {
+        if requirements.interview {
+            let interviewed: bool = self
+                .connection
+                .query_row(
+                    "SELECT EXISTS (SELECT 1 FROM request_interviews WHERE request_id = ?1)",
+                    params![request_id],
+                    |row| row.get(0),
+                )
+                .context("Failed to check interviews of adoption request")?;
+            if !interviewed {
+                return Ok(Some(ApproveRequestResult::InterviewRequired));
+            }
+        }
+
+        if requirements.reference_check {
+            let referenced: bool = self
+                .connection
+                .query_row(
+                    "SELECT EXISTS (SELECT 1 FROM reference_checks WHERE request_id = ?1 AND outcome = ?2)",
+                    params![request_id, ReferenceOutcome::Positive],
+                    |row| row.get(0),
+                )
+                .context("Failed to check references of adoption request")?;
+            if !referenced {
+                return Ok(Some(ApproveRequestResult::ReferenceCheckRequired));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Checks whether an adoption request exists
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the adoption request
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the request exists
+    pub(super) fn adoption_request_exists(&self, request_id: &str) -> Result<bool> {
+        self.connection
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM adoption_requests WHERE id = ?1)",
+                params![request_id],
+                |row| row.get(0),
+            )
+            .context("Failed to check adoption request exists")
+    }
+}
@@ -0,0 +1,162 @@
+//
+// database_service/returns.rs
+//
+// This module provides operations for processing adopted animals that come back
+// to the shelter, keeping the original adoption request as history.
+//
+
+use super::types::{
+    AdoptionReturn, AnimalStatus, ProcessReturnResult, RequestStatus, WebhookEvent,
+};
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use uuid::Uuid;
+
+impl DatabaseService {
+    // ==================== ADOPTION_RETURNS TABLE OPERATIONS ====================
+
+    /// Retrieves the returns of a specific animal, most recent first
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to retrieve returns for
+    ///
+    /// # Returns
+    /// * `Result<Vec<AdoptionReturn>>` - List of returns or error
+    pub fn query_adoption_returns_by_animal_id(
+        &self,
+        animal_id: &str,
+    ) -> Result<Vec<AdoptionReturn>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, adoption_request_id, animal_id, return_timestamp, reason FROM adoption_returns WHERE animal_id = ?1 ORDER BY return_timestamp DESC",
+            )
+            .context("Failed to prepare query for adoption returns by animal ID")?;
+
+        let return_iter = statement
+            .query_map(params![animal_id], |row| {
+                Ok(AdoptionReturn {
+                    id: row.get(0)?,
+                    adoption_request_id: row.get(1)?,
+                    animal_id: row.get(2)?,
+                    return_timestamp: row.get(3)?,
+                    reason: row.get(4)?,
+                })
+            })
+            .context("Failed to execute query for adoption returns by animal ID")?;
+
+        let mut returns = Vec::new();
+        for adoption_return in return_iter {
+            returns.push(adoption_return.context("Failed to parse adoption return row")?);
+        }
+
+        log::debug!(
+            "Retrieved {} adoption returns for animal ID: {}",
+            returns.len(),
+            animal_id
+        );
+        Ok(returns)
+    }
+
+    /// Records the return of an adopted animal and makes the animal available again
+    ///
+    /// The approved adoption request is left untouched so the adoption stays in the history.
+    /// A request still on trial is rejected instead, since the adoption never became final.
+    ///
+    /// # Arguments
+    /// * `adoption_request_id` - The ID of the approved or trial adoption request being returned
+    /// * `reason` - Why the animal was returned
+    ///
+    /// # Returns
+    /// * `Result<ProcessReturnResult>` - Success, or why the return could not be processed
+    pub fn process_return(
+        &self,
+        adoption_request_id: &str,
+        reason: &str,
+    ) -> Result<ProcessReturnResult> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for adoption return")?;
+
+        let request: Option<(String, RequestStatus)> = transaction
+            .query_row(
+                "SELECT animal_id, status FROM adoption_requests WHERE id = ?1",
+                params![adoption_request_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .context("Failed to read adoption request")?;
+        let (animal_id, status) = match request {
+            None => return Ok(ProcessReturnResult::RequestNotFound),
+            Some((_, RequestStatus::Pending | RequestStatus::Rejected)) => {
+                return Ok(ProcessReturnResult::RequestNotApproved)
+            }
+            Some(request) => request,
+        };
+
+        let already_returned: bool = transaction
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM adoption_returns WHERE adoption_request_id = ?1)",
+                params![adoption_request_id],
+                |row| row.get(0),
+            )
+            .context("Failed to check for an existing adoption return")?;
+        if already_returned {
+            return Ok(ProcessReturnResult::AlreadyReturned);
+        }
+
+        let now = Utc::now().timestamp();
+        transaction
+            .execute(
+                "INSERT INTO adoption_returns (id, adoption_request_id, animal_id, return_timestamp, reason) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    adoption_request_id,
+                    animal_id,
+                    now,
+                    reason.trim()
+                ],
+            )
+            .context("Failed to insert adoption return into database")?;
+
+        transaction
+            .execute(
+                "UPDATE animals SET status = ?2, version = version + 1 WHERE id = ?1",
+                params![animal_id, AnimalStatus::Available],
+            )
+            .context("Failed to mark returned animal as available")?;
+
+        // End the trial without telling the adopter their request was rejected
+        if status == RequestStatus::TrialAdoption {
+            transaction
+                .execute(
+                    "UPDATE adoption_requests SET status = ?2 WHERE id = ?1",
+                    params![adoption_request_id, RequestStatus::Rejected],
+                )
+                .context("Failed to end trial adoption")?;
+            self.queue_webhook_event(
+                WebhookEvent::RequestStatusChanged,
+                &serde_json::json!({
+                    "id": adoption_request_id,
+                    "animalId": animal_id,
+                    "status": RequestStatus::Rejected,
+                }),
+                now,
+            )?;
+        }
+
+        transaction
+            .commit()
+            .context("Failed to commit adoption return")?;
+
+        log::info!(
+            "Processed return of animal with ID: {} from adoption request: {}",
+            animal_id,
+            adoption_request_id
+        );
+        Ok(ProcessReturnResult::Success)
+    }
+}
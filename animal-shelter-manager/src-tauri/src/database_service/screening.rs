@@ -0,0 +1,153 @@
+//
+// database_service/screening.rs
+//
+// This module provides operations for scoring adoption requests, so staff can
+// triage long queues by looking at the most promising requests first. How each
+// part of a request counts is configured in the settings; scores are stored
+// alongside the requests and recomputed when a request or the configuration
+// changes.
+//
+
+use super::types::{AdoptionRequest, AdoptionRequestFilters, ScreeningConfig};
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection};
+
+/// Reads an annual income entered as free text, such as "50,000", "$50000" or "50k"
+///
+/// Anything after a decimal point is ignored.
+///
+/// # Arguments
+/// * `text` - The income as the applicant entered it
+///
+/// # Returns
+/// * `Option<u64>` - The income, or None if the text holds no number
+pub fn parse_income(text: &str) -> Option<u64> {
+    let text = text.trim().to_lowercase();
+    let whole = text.split('.').next().unwrap_or_default();
+    let digits: String = whole.chars().filter(char::is_ascii_digit).collect();
+    let income: u64 = digits.parse().ok()?;
+    if text.ends_with('k') {
+        income.checked_mul(1000)
+    } else {
+        Some(income)
+    }
+}
+
+/// Computes the screening score of an adoption request
+///
+/// # Arguments
+/// * `request` - The adoption request to score
+/// * `prior_returns` - Number of animals the applicant adopted before and returned
+/// * `config` - How each part of the request counts
+///
+/// # Returns
+/// * `i64` - The score; higher scores are more promising
+pub fn screening_score(
+    request: &AdoptionRequest,
+    prior_returns: i64,
+    config: &ScreeningConfig,
+) -> i64 {
+    let income_points = parse_income(&request.annual_income)
+        .and_then(|income| {
+            config
+                .income_bands
+                .iter()
+                .filter(|band| income >= band.min_income)
+                .max_by_key(|band| band.min_income)
+        })
+        .map_or(0, |band| band.points);
+
+    i64::from(request.num_people) * config.points_per_person
+        + i64::from(request.num_children) * config.points_per_child
+        + income_points
+        + prior_returns * config.points_per_return
+}
+
+/// Scores an adoption request and stores its score
+///
+/// # Arguments
+/// * `connection` - The connection or transaction to work in
+/// * `request` - The adoption request to score
+/// * `config` - How each part of the request counts
+///
+/// # Returns
+/// * `Result<i64>` - The score or error
+fn store_screening_score(
+    connection: &Connection,
+    request: &AdoptionRequest,
+    config: &ScreeningConfig,
+) -> Result<i64> {
+    // Returns of animals adopted through the applicant's other requests
+    let prior_returns: i64 = connection
+        .query_row(
+            "SELECT COUNT(*) FROM adoption_returns ret JOIN adoption_requests r ON r.id = ret.adoption_request_id WHERE r.username = ?1 AND r.id <> ?2",
+            params![request.username, request.id],
+            |row| row.get(0),
+        )
+        .context("Failed to count prior returns of applicant")?;
+
+    let score = screening_score(request, prior_returns, config);
+    connection
+        .execute(
+            "INSERT INTO screening_scores (request_id, score, scored_timestamp) VALUES (?1, ?2, ?3)
+             ON CONFLICT (request_id) DO UPDATE SET score = excluded.score, scored_timestamp = excluded.scored_timestamp",
+            params![request.id, score, Utc::now().timestamp()],
+        )
+        .context("Failed to store screening score")?;
+    Ok(score)
+}
+
+impl DatabaseService {
+    // ==================== SCREENING OPERATIONS ====================
+
+    /// Scores an adoption request and stores its score
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the adoption request to score
+    /// * `config` - How each part of the request counts
+    ///
+    /// # Returns
+    /// * `Result<Option<i64>>` - The score, None if the request was not found, or error
+    pub fn score_adoption_request(
+        &self,
+        request_id: &str,
+        config: &ScreeningConfig,
+    ) -> Result<Option<i64>> {
+        let Some(request) = self.query_adoption_request_by_id(request_id)? else {
+            return Ok(None);
+        };
+        let score = store_screening_score(&self.connection, &request, config)?;
+        log::debug!("Scored adoption request {} at {}", request_id, score);
+        Ok(Some(score))
+    }
+
+    /// Scores every adoption request again, such as after the configuration changed
+    ///
+    /// # Arguments
+    /// * `config` - How each part of the request counts
+    ///
+    /// # Returns
+    /// * `Result<usize>` - Number of requests scored or error
+    pub fn recompute_screening_scores(&self, config: &ScreeningConfig) -> Result<usize> {
+        let requests = self.query_adoption_request_details(&AdoptionRequestFilters::default())?;
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for screening scores")?;
+        for detail in &requests {
+            store_screening_score(&transaction, &detail.request, config)?;
+        }
+        transaction
+            .commit()
+            .context("Failed to commit screening scores")?;
+
+        log::info!(
+            "Recomputed screening scores of {} adoption requests",
+            requests.len()
+        );
+        Ok(requests.len())
+    }
+}
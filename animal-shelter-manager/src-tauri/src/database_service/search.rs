@@ -0,0 +1,200 @@
+//
+// database_service/search.rs
+//
+// This module provides the free-text search behind the global search bar. The
+// text is matched in SQL against animal names, breeds and bios, and against the
+// applicant names, usernames and emails of adoption requests, together with the
+// structured filters of the list views. Each match is scored by which field it
+// was found in and whether it matched the whole field, the start of a word, or
+// anywhere within it.
+//
+
+use super::types::{
+    AdoptionRequestFilters, AdoptionRequestSummary, AnimalSummary, FilterCriteria, FilterValue,
+    SearchHit,
+};
+use super::{adoption_request_filter_clauses, animal_filter_clause, DatabaseService};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+
+/// Records returned for each kind of record searched, unless more or fewer are asked for
+pub const DEFAULT_SEARCH_RESULTS: usize = 20;
+
+/// Most records returned for each kind of record searched
+pub const MAX_SEARCH_RESULTS: usize = 100;
+
+impl DatabaseService {
+    // ==================== SEARCH OPERATIONS ====================
+
+    /// Finds the animals whose name, breed or bio contains some text, among those
+    /// matching the filters
+    ///
+    /// # Arguments
+    /// * `text` - The text to look for, ignoring case
+    /// * `filters` - Optional map of filter criteria and values
+    /// * `limit` - Most animals to return, capped at `MAX_SEARCH_RESULTS`
+    ///
+    /// # Returns
+    /// * `Result<Vec<SearchHit<AnimalSummary>>>` - The animals found, best matches first, or error
+    pub fn search_animals(
+        &self,
+        text: &str,
+        filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
+        limit: usize,
+    ) -> Result<Vec<SearchHit<AnimalSummary>>> {
+        let (where_clause, filter_params) = animal_filter_clause(filters);
+        let query = format!(
+            "SELECT * FROM (SELECT id, name, specie, breed, sex, admission_timestamp, status, image_path, {} AS score FROM animals{}) WHERE score > 0 ORDER BY score DESC, name LIMIT {}",
+            match_score_sql(&[("name", 3), ("breed", 2), ("bio", 1)]),
+            where_clause,
+            limit.min(MAX_SEARCH_RESULTS)
+        );
+        let mut params = match_patterns(text);
+        params.extend(filter_params);
+
+        let mut statement = self
+            .connection
+            .prepare(&query)
+            .context(format!("Failed to prepare animal search: {}", query))?;
+        let hits = statement
+            .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+                Ok(SearchHit {
+                    record: AnimalSummary {
+                        id: row.get(0)?,
+                        name: row.get(1)?,
+                        specie: row.get(2)?,
+                        breed: row.get(3)?,
+                        sex: row.get(4)?,
+                        admission_timestamp: row.get(5)?,
+                        status: row.get(6)?,
+                        image_path: row.get(7)?,
+                    },
+                    score: row.get(8)?,
+                })
+            })
+            .context("Failed to execute animal search")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse animal search row")?;
+
+        log::debug!("Animal search found {} animals", hits.len());
+        Ok(hits)
+    }
+
+    /// Finds the adoption requests whose applicant name, username or email contains some
+    /// text, among those matching the filters
+    ///
+    /// # Arguments
+    /// * `text` - The text to look for, ignoring case
+    /// * `filters` - The status, submission date range and animal species to filter by
+    /// * `limit` - Most requests to return, capped at `MAX_SEARCH_RESULTS`
+    ///
+    /// # Returns
+    /// * `Result<Vec<SearchHit<AdoptionRequestSummary>>>` - The requests found, best matches
+    ///   first, or error
+    pub fn search_adoption_requests(
+        &self,
+        text: &str,
+        filters: &AdoptionRequestFilters,
+        limit: usize,
+    ) -> Result<Vec<SearchHit<AdoptionRequestSummary>>> {
+        let (where_clauses, filter_params) = adoption_request_filter_clauses(filters);
+        let where_clause = if where_clauses.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", where_clauses.join(" AND "))
+        };
+        let query = format!(
+            "SELECT * FROM (SELECT r.id, r.animal_id, a.name AS animal_name, a.specie, r.username, r.name, r.email, r.request_timestamp, r.status, s.score AS screening_score, {} AS score FROM adoption_requests r JOIN animals a ON a.id = r.animal_id LEFT JOIN screening_scores s ON s.request_id = r.id{}) WHERE score > 0 ORDER BY score DESC, request_timestamp DESC LIMIT {}",
+            match_score_sql(&[("r.name", 3), ("r.username", 3), ("r.email", 2)]),
+            where_clause,
+            limit.min(MAX_SEARCH_RESULTS)
+        );
+        let mut params = match_patterns(text);
+        params.extend(filter_params);
+
+        let mut statement = self.connection.prepare(&query).context(format!(
+            "Failed to prepare adoption request search: {}",
+            query
+        ))?;
+        let hits = statement
+            .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+                Ok(SearchHit {
+                    record: AdoptionRequestSummary {
+                        id: row.get(0)?,
+                        animal_id: row.get(1)?,
+                        animal_name: row.get(2)?,
+                        animal_specie: row.get(3)?,
+                        username: row.get(4)?,
+                        name: row.get(5)?,
+                        email: row.get(6)?,
+                        request_timestamp: row.get(7)?,
+                        status: row.get(8)?,
+                        screening_score: row.get(9)?,
+                    },
+                    score: row.get(10)?,
+                })
+            })
+            .context("Failed to execute adoption request search")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse adoption request search row")?;
+
+        log::debug!("Adoption request search found {} requests", hits.len());
+        Ok(hits)
+    }
+}
+
+/// Builds an SQL expression scoring how well text matches some columns, using the
+/// patterns from `match_patterns` as parameters 1 to 4
+///
+/// A column scores three times its weight when it is the text, twice its weight when a
+/// word in it starts with the text, and its weight when it contains the text anywhere;
+/// the best scoring column counts. Rows scoring 0 do not match.
+///
+/// # Arguments
+/// * `columns` - The columns to match, with their weights; must be trusted column names
+///
+/// # Returns
+/// * `String` - The expression
+pub fn match_score_sql(columns: &[(&str, i64)]) -> String {
+    let scores: Vec<String> = columns
+        .iter()
+        .map(|(column, weight)| {
+            format!(
+                "CASE WHEN {0} LIKE ?1 ESCAPE '\\' THEN {1} WHEN {0} LIKE ?2 ESCAPE '\\' OR {0} LIKE ?3 ESCAPE '\\' THEN {2} WHEN {0} LIKE ?4 ESCAPE '\\' THEN {3} ELSE 0 END",
+                column,
+                weight * 3,
+                weight * 2,
+                weight
+            )
+        })
+        .collect();
+    match scores.as_slice() {
+        [score] => score.clone(),
+        _ => format!("max({})", scores.join(", ")),
+    }
+}
+
+/// Builds the LIKE patterns `match_score_sql` matches against: the whole text, the text
+/// at the start, the text at the start of a later word, and the text anywhere
+///
+/// # Arguments
+/// * `text` - The text to look for; LIKE wildcards in it are matched literally
+///
+/// # Returns
+/// * `Vec<rusqlite::types::Value>` - The four patterns
+pub fn match_patterns(text: &str) -> Vec<rusqlite::types::Value> {
+    let escaped = text
+        .trim()
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    [
+        escaped.clone(),
+        format!("{}%", escaped),
+        format!("% {}%", escaped),
+        format!("%{}%", escaped),
+    ]
+    .into_iter()
+    .map(rusqlite::types::Value::from)
+    .collect()
+}
@@ -0,0 +1,471 @@
+//
+// database_service/statistics.rs
+//
+// This module provides aggregate queries over the animals and adoption requests
+// tables, used to build the shelter statistics dashboard and its charts.
+//
+
+use super::types::{
+    CategoryCount, MonthlyCount, MonthlyReport, ShelterStatistics, TrendGranularity, TrendPoint,
+};
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc};
+use rusqlite::params;
+use std::collections::HashMap;
+
+/// Number of months covered by the adoptions per month statistic
+const ADOPTION_HISTORY_MONTHS: i32 = 12;
+
+/// Number of seconds in a day, used to convert timestamps into days
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Most periods an adoption trend can be split into, so a long range is asked for
+/// by week or month rather than by day
+const MAX_TREND_PERIODS: usize = 1_000;
+
+impl DatabaseService {
+    // ==================== STATISTICS OPERATIONS ====================
+
+    /// Computes aggregate statistics about the shelter
+    ///
+    /// # Arguments
+    /// * `location_id` - Only count animals at this location, and their requests, if given
+    ///
+    /// # Returns
+    /// * `Result<ShelterStatistics>` - Counts by status and species, adoptions per month,
+    ///   average length of stay, and pending request count, or error
+    pub fn query_shelter_statistics(&self, location_id: Option<&str>) -> Result<ShelterStatistics> {
+        let animals_by_status = self
+            .query_category_counts("status", location_id)
+            .context("Failed to count animals by status")?;
+        let animals_by_specie = self
+            .query_category_counts("specie", location_id)
+            .context("Failed to count animals by species")?;
+        let adoptions_per_month = self
+            .query_adoptions_per_month(location_id)
+            .context("Failed to count adoptions per month")?;
+
+        // Length of stay runs from admission until the approved adoption
+        let average_length_of_stay_days: Option<f64> = self
+            .connection
+            .query_row(
+                "SELECT AVG(ar.adoption_timestamp - a.admission_timestamp)
+                 FROM adoption_requests ar
+                 JOIN animals a ON a.id = ar.animal_id
+                 WHERE ar.status = 'approved' AND ar.adoption_timestamp > 0
+                   AND (?1 IS NULL OR a.location_id = ?1)",
+                params![location_id],
+                |row| row.get(0),
+            )
+            .context("Failed to compute average length of stay")?;
+
+        let pending_request_count = self.count_pending_adoption_requests(location_id)?;
+
+        Ok(ShelterStatistics {
+            animals_by_status,
+            animals_by_specie,
+            adoptions_per_month,
+            average_length_of_stay_days: average_length_of_stay_days
+                .map(|seconds| seconds / SECONDS_PER_DAY),
+            pending_request_count,
+        })
+    }
+
+    /// Computes the activity of the shelter during a calendar month
+    ///
+    /// # Arguments
+    /// * `year` - The year of the month to report on
+    /// * `month` - The month to report on, from 1 to 12
+    /// * `location_id` - Only count animals at this location, and their records, if given
+    ///
+    /// # Returns
+    /// * `Result<MonthlyReport>` - Admission, intake, adoption, return and death counts and
+    ///   the revenue for the month, or error
+    pub fn query_monthly_report(
+        &self,
+        year: i32,
+        month: u32,
+        location_id: Option<&str>,
+    ) -> Result<MonthlyReport> {
+        let (start_timestamp, end_timestamp) = month_bounds(year, month)?;
+        let count = |query: &str| -> Result<i64> {
+            self.connection
+                .query_row(
+                    query,
+                    params![start_timestamp, end_timestamp, location_id],
+                    |row| row.get(0),
+                )
+                .context(format!("Failed to execute monthly report query: {}", query))
+        };
+
+        let admission_count = count(
+            "SELECT COUNT(*) FROM animals WHERE admission_timestamp >= ?1 AND admission_timestamp < ?2 AND (?3 IS NULL OR location_id = ?3)",
+        )?;
+        let adoption_count = count(
+            "SELECT COUNT(*) FROM adoption_requests ar JOIN animals a ON a.id = ar.animal_id WHERE ar.status = 'approved' AND ar.adoption_timestamp >= ?1 AND ar.adoption_timestamp < ?2 AND (?3 IS NULL OR a.location_id = ?3)",
+        )?;
+        let return_count = count(
+            "SELECT COUNT(*) FROM adoption_returns r JOIN animals a ON a.id = r.animal_id WHERE r.return_timestamp >= ?1 AND r.return_timestamp < ?2 AND (?3 IS NULL OR a.location_id = ?3)",
+        )?;
+
+        let category_counts = |query: &str| -> Result<Vec<CategoryCount>> {
+            let mut statement = self
+                .connection
+                .prepare(query)
+                .context(format!("Failed to prepare monthly report query: {}", query))?;
+            let count_iter = statement
+                .query_map(
+                    params![start_timestamp, end_timestamp, location_id],
+                    |row| {
+                        Ok(CategoryCount {
+                            label: row.get(0)?,
+                            count: row.get(1)?,
+                        })
+                    },
+                )
+                .context(format!("Failed to execute monthly report query: {}", query))?;
+            let mut counts = Vec::new();
+            for count in count_iter {
+                counts.push(count.context("Failed to parse category count row")?);
+            }
+            Ok(counts)
+        };
+        let intakes_by_type = category_counts(
+            "SELECT i.intake_type, COUNT(*) FROM intake_records i
+             JOIN animals a ON a.id = i.animal_id
+             WHERE i.intake_timestamp >= ?1 AND i.intake_timestamp < ?2
+               AND (?3 IS NULL OR a.location_id = ?3)
+             GROUP BY i.intake_type ORDER BY i.intake_type",
+        )?;
+        // The cause tells natural deaths and euthanasia apart
+        let deaths_by_cause = category_counts(
+            "SELECT d.cause, COUNT(*) FROM deceased_records d
+             JOIN animals a ON a.id = d.animal_id
+             WHERE d.death_timestamp >= ?1 AND d.death_timestamp < ?2
+               AND (?3 IS NULL OR a.location_id = ?3)
+             GROUP BY d.cause ORDER BY d.cause",
+        )?;
+
+        let sum = |query: &str| -> Result<i64> {
+            self.connection
+                .query_row(
+                    query,
+                    params![start_timestamp, end_timestamp, location_id],
+                    |row| row.get(0),
+                )
+                .context(format!("Failed to execute monthly report query: {}", query))
+        };
+        // Fees stay in the books after their request or animal is deleted
+        let fee_revenue = sum("SELECT COALESCE(SUM(p.amount), 0) FROM payments p
+             LEFT JOIN adoption_requests ar ON ar.id = p.request_id
+             LEFT JOIN animals a ON a.id = ar.animal_id
+             WHERE p.paid_timestamp >= ?1 AND p.paid_timestamp < ?2
+               AND (?3 IS NULL OR a.location_id = ?3)")?;
+        // Donations are made to the whole organization, not to one of its locations
+        let donation_revenue = sum("SELECT COALESCE(SUM(amount), 0) FROM donations
+             WHERE donation_timestamp >= ?1 AND donation_timestamp < ?2 AND ?3 IS NULL")?;
+
+        Ok(MonthlyReport {
+            year,
+            month,
+            admission_count,
+            intakes_by_type,
+            adoption_count,
+            return_count,
+            death_count: deaths_by_cause.iter().map(|c| c.count).sum(),
+            deaths_by_cause,
+            fee_revenue,
+            donation_revenue,
+        })
+    }
+
+    /// Computes the activity of the shelter during each month of a year
+    ///
+    /// # Arguments
+    /// * `year` - The year to report on
+    /// * `location_id` - Only count animals at this location, and their records, if given
+    ///
+    /// # Returns
+    /// * `Result<Vec<MonthlyReport>>` - One report per month from January to December, or error
+    pub fn query_annual_report(
+        &self,
+        year: i32,
+        location_id: Option<&str>,
+    ) -> Result<Vec<MonthlyReport>> {
+        (1..=12)
+            .map(|month| self.query_monthly_report(year, month, location_id))
+            .collect()
+    }
+
+    /// Counts the animals admitted and the adoptions approved during each period of a
+    /// time range, so they can be drawn as a chart
+    ///
+    /// # Arguments
+    /// * `granularity` - Whether to count by day, week or month
+    /// * `from_timestamp` - Start of the range
+    /// * `to_timestamp` - End of the range, excluded
+    /// * `location_id` - Only count animals at this location, and their adoptions, if given
+    ///
+    /// # Returns
+    /// * `Result<Vec<TrendPoint>>` - One point for every period overlapping the range,
+    ///   oldest first, including periods with nothing in them, or error if the range is
+    ///   empty or split into too many periods
+    pub fn query_adoption_trends(
+        &self,
+        granularity: TrendGranularity,
+        from_timestamp: i64,
+        to_timestamp: i64,
+        location_id: Option<&str>,
+    ) -> Result<Vec<TrendPoint>> {
+        if from_timestamp >= to_timestamp {
+            bail!("The start of the range must be before its end");
+        }
+
+        // List every period first, so periods with nothing in them report zero
+        let mut period_start = period_start_of(granularity, timestamp_date(from_timestamp)?);
+        let mut points = Vec::new();
+        loop {
+            let period_start_timestamp = period_start
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+                .timestamp();
+            if period_start_timestamp >= to_timestamp {
+                break;
+            }
+            if points.len() == MAX_TREND_PERIODS {
+                bail!(
+                    "The range covers more than {} periods; count by a longer period",
+                    MAX_TREND_PERIODS
+                );
+            }
+            points.push(TrendPoint {
+                period_start_timestamp,
+                intake_count: 0,
+                adoption_count: 0,
+            });
+            period_start = next_period_start(granularity, period_start)?;
+        }
+
+        // The same expression buckets both kinds of event by the start of their period
+        let period_of = |column: &str| match granularity {
+            TrendGranularity::Day => format!("date({}, 'unixepoch')", column),
+            TrendGranularity::Week => {
+                format!("date({}, 'unixepoch', 'weekday 0', '-6 days')", column)
+            }
+            TrendGranularity::Month => format!("date({}, 'unixepoch', 'start of month')", column),
+        };
+        let query = format!(
+            "SELECT CAST(strftime('%s', period) AS INTEGER), SUM(intake), SUM(adoption)
+             FROM (
+                 SELECT {} AS period, 1 AS intake, 0 AS adoption
+                 FROM animals
+                 WHERE admission_timestamp >= ?1 AND admission_timestamp < ?2
+                   AND (?3 IS NULL OR location_id = ?3)
+                 UNION ALL
+                 SELECT {}, 0, 1
+                 FROM adoption_requests ar
+                 JOIN animals a ON a.id = ar.animal_id
+                 WHERE ar.status = 'approved'
+                   AND ar.adoption_timestamp >= ?1 AND ar.adoption_timestamp < ?2
+                   AND (?3 IS NULL OR a.location_id = ?3)
+             )
+             GROUP BY period",
+            period_of("admission_timestamp"),
+            period_of("ar.adoption_timestamp")
+        );
+        let mut statement = self
+            .connection
+            .prepare(&query)
+            .context("Failed to prepare query for adoption trends")?;
+        let row_iter = statement
+            .query_map(params![from_timestamp, to_timestamp, location_id], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })
+            .context("Failed to execute query for adoption trends")?;
+        let mut counts = HashMap::new();
+        for row in row_iter {
+            let (period_start, intakes, adoptions) =
+                row.context("Failed to parse adoption trend row")?;
+            counts.insert(period_start, (intakes, adoptions));
+        }
+
+        for point in &mut points {
+            if let Some(&(intakes, adoptions)) = counts.get(&point.period_start_timestamp) {
+                point.intake_count = intakes;
+                point.adoption_count = adoptions;
+            }
+        }
+        Ok(points)
+    }
+
+    /// Counts animals grouped by the given column
+    ///
+    /// # Arguments
+    /// * `column` - The animals column to group by; must be a trusted column name
+    /// * `location_id` - Only count animals at this location, if given
+    ///
+    /// # Returns
+    /// * `Result<Vec<CategoryCount>>` - Counts ordered by label, or error
+    fn query_category_counts(
+        &self,
+        column: &str,
+        location_id: Option<&str>,
+    ) -> Result<Vec<CategoryCount>> {
+        let query = format!(
+            "SELECT {0}, COUNT(*) FROM animals WHERE (?1 IS NULL OR location_id = ?1) GROUP BY {0} ORDER BY {0}",
+            column
+        );
+        let mut statement = self
+            .connection
+            .prepare(&query)
+            .context(format!("Failed to prepare query: {}", query))?;
+
+        let count_iter = statement
+            .query_map(params![location_id], |row| {
+                Ok(CategoryCount {
+                    label: row.get(0)?,
+                    count: row.get(1)?,
+                })
+            })
+            .context("Failed to execute category count query")?;
+
+        let mut counts = Vec::new();
+        for count in count_iter {
+            counts.push(count.context("Failed to parse category count row")?);
+        }
+        Ok(counts)
+    }
+
+    /// Counts approved adoptions for each of the last twelve months, including the current one
+    ///
+    /// # Arguments
+    /// * `location_id` - Only count adoptions of animals at this location, if given
+    ///
+    /// # Returns
+    /// * `Result<Vec<MonthlyCount>>` - One entry per month from oldest to newest, or error
+    fn query_adoptions_per_month(&self, location_id: Option<&str>) -> Result<Vec<MonthlyCount>> {
+        // Build the list of months, oldest first, so months without adoptions report zero
+        let today = Utc::now().date_naive();
+        let mut months: Vec<MonthlyCount> = (0..ADOPTION_HISTORY_MONTHS)
+            .rev()
+            .map(|offset| {
+                let index = today.year() * 12 + today.month0() as i32 - offset;
+                MonthlyCount {
+                    year: index.div_euclid(12),
+                    month: index.rem_euclid(12) as u32 + 1,
+                    count: 0,
+                }
+            })
+            .collect();
+
+        let start_timestamp = NaiveDate::from_ymd_opt(months[0].year, months[0].month, 1)
+            .context("Failed to compute start of adoption history")?
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp();
+
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT CAST(strftime('%Y', ar.adoption_timestamp, 'unixepoch') AS INTEGER),
+                        CAST(strftime('%m', ar.adoption_timestamp, 'unixepoch') AS INTEGER),
+                        COUNT(*)
+                 FROM adoption_requests ar
+                 JOIN animals a ON a.id = ar.animal_id
+                 WHERE ar.status = 'approved' AND ar.adoption_timestamp >= ?1
+                   AND (?2 IS NULL OR a.location_id = ?2)
+                 GROUP BY 1, 2",
+            )
+            .context("Failed to prepare query for adoptions per month")?;
+
+        let row_iter = statement
+            .query_map(params![start_timestamp, location_id], |row| {
+                Ok((
+                    row.get::<_, i32>(0)?,
+                    row.get::<_, u32>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })
+            .context("Failed to execute query for adoptions per month")?;
+
+        for row in row_iter {
+            let (year, month, count) = row.context("Failed to parse adoptions per month row")?;
+            if let Some(entry) = months
+                .iter_mut()
+                .find(|entry| entry.year == year && entry.month == month)
+            {
+                entry.count = count;
+            }
+        }
+
+        Ok(months)
+    }
+}
+
+/// Gets the date a timestamp falls on, in UTC
+fn timestamp_date(timestamp: i64) -> Result<NaiveDate> {
+    Ok(DateTime::from_timestamp(timestamp, 0)
+        .context(format!("Invalid timestamp {}", timestamp))?
+        .date_naive())
+}
+
+/// Gets the first day of the period a date falls in
+///
+/// # Arguments
+/// * `granularity` - The length of the periods
+/// * `date` - The date
+///
+/// # Returns
+/// * `NaiveDate` - The date itself, the Monday of its week, or the first of its month
+fn period_start_of(granularity: TrendGranularity, date: NaiveDate) -> NaiveDate {
+    match granularity {
+        TrendGranularity::Day => date,
+        TrendGranularity::Week => date - Days::new(date.weekday().num_days_from_monday().into()),
+        TrendGranularity::Month => date.with_day(1).unwrap(),
+    }
+}
+
+/// Gets the first day of the period after the one starting on a date
+fn next_period_start(granularity: TrendGranularity, period_start: NaiveDate) -> Result<NaiveDate> {
+    match granularity {
+        TrendGranularity::Day => period_start.checked_add_days(Days::new(1)),
+        TrendGranularity::Week => period_start.checked_add_days(Days::new(7)),
+        TrendGranularity::Month => period_start.checked_add_months(Months::new(1)),
+    }
+    .context("The range ends too far in the future")
+}
+
+/// Computes the timestamps bounding a calendar month
+///
+/// # Arguments
+/// * `year` - The year of the month
+/// * `month` - The month, from 1 to 12
+///
+/// # Returns
+/// * `Result<(i64, i64)>` - The start of the month and the start of the next month
+pub(super) fn month_bounds(year: i32, month: u32) -> Result<(i64, i64)> {
+    if !(1..=12).contains(&month) {
+        bail!("Month must be between 1 and 12, got {}", month);
+    }
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    let start_of = |year: i32, month: u32| -> Result<i64> {
+        Ok(NaiveDate::from_ymd_opt(year, month, 1)
+            .context(format!("Invalid month {}-{}", year, month))?
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp())
+    };
+    Ok((start_of(year, month)?, start_of(next_year, next_month)?))
+}
@@ -0,0 +1,245 @@
+//
+// database_service/surgeries.rs
+//
+// This module provides operations for booking spay and neuter surgeries, and
+// for recording them as performed, which marks the animal as neutered so the
+// flag stays accurate without editing the animal by hand.
+//
+
+use super::types::{CompleteSurgeryResult, ScheduleSurgeryResult, Surgery};
+use super::validation;
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use rusqlite::{params, OptionalExtension, Row};
+use uuid::Uuid;
+
+/// Columns selected when reading a surgery, in the order expected by `surgery_from_row`
+const SURGERY_COLUMNS: &str = "s.id, s.animal_id, a.name, s.scheduled_timestamp, s.contact_id, s.completed_timestamp, s.notes";
+
+/// Builds a surgery from a row selected with `SURGERY_COLUMNS`
+fn surgery_from_row(row: &Row) -> rusqlite::Result<Surgery> {
+    Ok(Surgery {
+        id: row.get(0)?,
+        animal_id: row.get(1)?,
+        animal_name: row.get(2)?,
+        scheduled_timestamp: row.get(3)?,
+        contact_id: row.get(4)?,
+        completed_timestamp: row.get(5)?,
+        notes: row.get(6)?,
+    })
+}
+
+impl DatabaseService {
+    // ==================== SURGERIES TABLE OPERATIONS ====================
+
+    /// Books a spay or neuter surgery for an animal that is not neutered yet
+    ///
+    /// Fails with a `ValidationError` if the scheduled date is not valid.
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal having the surgery
+    /// * `scheduled_timestamp` - Timestamp when the surgery is booked for
+    /// * `contact_id` - The ID of the veterinarian performing the surgery, if known
+    /// * `notes` - Anything worth knowing about the surgery
+    ///
+    /// # Returns
+    /// * `Result<ScheduleSurgeryResult>` - Scheduled, or why the surgery could not be booked
+    pub fn schedule_surgery(
+        &self,
+        animal_id: &str,
+        scheduled_timestamp: i64,
+        contact_id: Option<&str>,
+        notes: &str,
+    ) -> Result<ScheduleSurgeryResult> {
+        validation::ensure_valid(validation::validate_surgery_schedule(scheduled_timestamp))?;
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for surgery booking")?;
+
+        let neutered: Option<bool> = transaction
+            .query_row(
+                "SELECT neutered FROM animals WHERE id = ?1",
+                params![animal_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to read neutered flag of animal")?;
+        match neutered {
+            None => return Ok(ScheduleSurgeryResult::AnimalNotFound),
+            Some(true) => return Ok(ScheduleSurgeryResult::AlreadyNeutered),
+            Some(false) => {}
+        }
+
+        let pending: i64 = transaction
+            .query_row(
+                "SELECT COUNT(*) FROM surgeries WHERE animal_id = ?1 AND completed_timestamp IS NULL",
+                params![animal_id],
+                |row| row.get(0),
+            )
+            .context("Failed to count pending surgeries of animal")?;
+        if pending > 0 {
+            return Ok(ScheduleSurgeryResult::AlreadyScheduled);
+        }
+
+        let id = Uuid::new_v4().to_string();
+        transaction
+            .execute(
+                "INSERT INTO surgeries (id, animal_id, scheduled_timestamp, contact_id, completed_timestamp, notes) VALUES (?1, ?2, ?3, ?4, NULL, ?5)",
+                params![id, animal_id, scheduled_timestamp, contact_id, notes.trim()],
+            )
+            .context("Failed to insert surgery into database")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit surgery booking")?;
+
+        log::info!("Scheduled surgery {} for animal with ID: {}", id, animal_id);
+        Ok(ScheduleSurgeryResult::Scheduled)
+    }
+
+    /// Retrieves the surgeries booked for a specific animal
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to retrieve the surgeries for
+    ///
+    /// # Returns
+    /// * `Result<Vec<Surgery>>` - Surgeries, latest booking first
+    pub fn query_surgeries_by_animal_id(&self, animal_id: &str) -> Result<Vec<Surgery>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM surgeries s JOIN animals a ON a.id = s.animal_id WHERE s.animal_id = ?1 ORDER BY s.scheduled_timestamp DESC",
+                SURGERY_COLUMNS
+            ))
+            .context("Failed to prepare query for surgeries by animal ID")?;
+
+        let surgeries = statement
+            .query_map(params![animal_id], surgery_from_row)
+            .context("Failed to execute query for surgeries by animal ID")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse surgery row")?;
+
+        log::debug!(
+            "Retrieved {} surgeries for animal: {}",
+            surgeries.len(),
+            animal_id
+        );
+        Ok(surgeries)
+    }
+
+    /// Retrieves the surgeries that have not been performed yet, for staff to plan them
+    ///
+    /// # Returns
+    /// * `Result<Vec<Surgery>>` - Surgeries, earliest booking first so overdue ones lead
+    pub fn query_upcoming_surgeries(&self) -> Result<Vec<Surgery>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM surgeries s JOIN animals a ON a.id = s.animal_id WHERE s.completed_timestamp IS NULL ORDER BY s.scheduled_timestamp ASC, a.name",
+                SURGERY_COLUMNS
+            ))
+            .context("Failed to prepare query for upcoming surgeries")?;
+
+        let surgeries = statement
+            .query_map([], surgery_from_row)
+            .context("Failed to execute query for upcoming surgeries")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse surgery row")?;
+
+        log::debug!("Retrieved {} upcoming surgeries", surgeries.len());
+        Ok(surgeries)
+    }
+
+    /// Records that a surgery was performed and marks the animal as neutered
+    ///
+    /// Fails with a `ValidationError` if the completion date is not valid or lies in the future.
+    ///
+    /// # Arguments
+    /// * `surgery_id` - The ID of the surgery
+    /// * `completed_timestamp` - Timestamp when the surgery was performed
+    ///
+    /// # Returns
+    /// * `Result<CompleteSurgeryResult>` - The completed surgery, or why it could not be recorded
+    pub fn complete_surgery(
+        &self,
+        surgery_id: &str,
+        completed_timestamp: i64,
+    ) -> Result<CompleteSurgeryResult> {
+        validation::ensure_valid(validation::validate_surgery_completion(completed_timestamp))?;
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for surgery completion")?;
+
+        let surgery = transaction
+            .query_row(
+                &format!(
+                    "SELECT {} FROM surgeries s JOIN animals a ON a.id = s.animal_id WHERE s.id = ?1",
+                    SURGERY_COLUMNS
+                ),
+                params![surgery_id],
+                surgery_from_row,
+            )
+            .optional()
+            .context("Failed to read surgery")?;
+        let mut surgery = match surgery {
+            None => return Ok(CompleteSurgeryResult::NotFound),
+            Some(surgery) if surgery.completed_timestamp.is_some() => {
+                return Ok(CompleteSurgeryResult::AlreadyCompleted)
+            }
+            Some(surgery) => surgery,
+        };
+        surgery.completed_timestamp = Some(completed_timestamp);
+
+        transaction
+            .execute(
+                "UPDATE surgeries SET completed_timestamp = ?2 WHERE id = ?1",
+                params![surgery_id, completed_timestamp],
+            )
+            .context("Failed to record surgery as performed")?;
+        transaction
+            .execute(
+                "UPDATE animals SET neutered = 1, version = version + 1 WHERE id = ?1",
+                params![surgery.animal_id],
+            )
+            .context("Failed to mark animal as neutered")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit surgery completion")?;
+
+        log::info!(
+            "Completed surgery {} and marked animal with ID: {} as neutered",
+            surgery_id,
+            surgery.animal_id
+        );
+        Ok(CompleteSurgeryResult::Completed(surgery))
+    }
+
+    /// Cancels a surgery that has not been performed yet
+    ///
+    /// # Arguments
+    /// * `surgery_id` - The ID of the surgery to cancel
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if a surgery still to come was found and cancelled, false if not
+    pub fn cancel_surgery(&self, surgery_id: &str) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "DELETE FROM surgeries WHERE id = ?1 AND completed_timestamp IS NULL",
+                params![surgery_id],
+            )
+            .context("Failed to cancel surgery")?;
+
+        log::info!(
+            "Cancelled surgery with ID: {}, found: {}",
+            surgery_id,
+            rows_affected == 1
+        );
+        Ok(rows_affected == 1)
+    }
+}
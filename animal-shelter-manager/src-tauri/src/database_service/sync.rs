@@ -0,0 +1,400 @@
+//
+// database_service/sync.rs
+//
+// This module provides operations for keeping installations on different machines
+// in sync, by exporting the animals and adoption requests that changed on one
+// machine and importing them on another, where the most recent change wins.
+//
+
+use super::types::{
+    AdoptionRequest, ChangedRecord, Changelog, DeletedRecord, ImportChangesResult, Location,
+    TrashItemType,
+};
+use super::{
+    animal_from_row, co_applicant_from_row, remove_adoption_request, remove_animal, validation,
+    DatabaseService, ANIMAL_COLUMNS,
+};
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+
+impl DatabaseService {
+    // ==================== SYNC OPERATIONS ====================
+
+    /// Collects the changes made since a given time into a changelog
+    ///
+    /// Deletions are only known while the deleted records are in the trash, so
+    /// installations should sync more often than the trash is purged.
+    ///
+    /// # Arguments
+    /// * `since_timestamp` - Only include changes made at or after this timestamp
+    /// * `exported_timestamp` - The current timestamp, recorded in the changelog
+    ///
+    /// # Returns
+    /// * `Result<Changelog>` - The changes or error
+    pub fn export_changes(
+        &self,
+        since_timestamp: i64,
+        exported_timestamp: i64,
+    ) -> Result<Changelog> {
+        let locations = self.query_locations()?;
+
+        let animals = {
+            let mut statement = self
+                .connection
+                .prepare(&format!(
+                    "SELECT {}, updated_timestamp FROM animals WHERE updated_timestamp >= ?1 ORDER BY updated_timestamp",
+                    ANIMAL_COLUMNS
+                ))
+                .context("Failed to prepare query for changed animals")?;
+            let animal_iter = statement
+                .query_map(params![since_timestamp], |row| {
+                    Ok(ChangedRecord {
+                        record: animal_from_row(row)?,
+                        updated_timestamp: row.get(22)?,
+                    })
+                })
+                .context("Failed to execute query for changed animals")?;
+
+            let mut animals = Vec::new();
+            for animal in animal_iter {
+                animals.push(animal.context("Failed to parse changed animal row")?);
+            }
+            animals
+        };
+
+        let adoption_requests = {
+            let mut statement = self
+                .connection
+                .prepare(
+                    "SELECT id, animal_id, username, name, email, tel_number, address, occupation, annual_income, num_people, num_children, request_timestamp, adoption_timestamp, status, country, updated_timestamp, co_applicant_name, co_applicant_relationship, co_applicant_contact FROM adoption_requests WHERE updated_timestamp >= ?1 ORDER BY updated_timestamp",
+                )
+                .context("Failed to prepare query for changed adoption requests")?;
+            let request_iter = statement
+                .query_map(params![since_timestamp], |row| {
+                    Ok(ChangedRecord {
+                        record: AdoptionRequest {
+                            id: row.get(0)?,
+                            animal_id: row.get(1)?,
+                            username: row.get(2)?,
+                            name: row.get(3)?,
+                            email: row.get(4)?,
+                            tel_number: row.get(5)?,
+                            address: row.get(6)?,
+                            occupation: row.get(7)?,
+                            annual_income: row.get(8)?,
+                            num_people: row.get(9)?,
+                            num_children: row.get(10)?,
+                            request_timestamp: row.get(11)?,
+                            adoption_timestamp: row.get(12)?,
+                            status: row.get(13)?,
+                            country: row.get(14)?,
+                            co_applicant: co_applicant_from_row(row, 16)?,
+                        },
+                        updated_timestamp: row.get(15)?,
+                    })
+                })
+                .context("Failed to execute query for changed adoption requests")?;
+
+            let mut requests = Vec::new();
+            for request in request_iter {
+                requests.push(request.context("Failed to parse changed adoption request row")?);
+            }
+            requests
+        };
+
+        // Adoption requests deleted along with their animal go away with the animal
+        let deletions = {
+            let mut statement = self
+                .connection
+                .prepare(
+                    "SELECT 0, id, deleted_timestamp FROM trashed_animals WHERE deleted_timestamp >= ?1 UNION ALL SELECT 1, id, deleted_timestamp FROM trashed_adoption_requests WHERE deleted_timestamp >= ?1 AND deleted_with_animal = 0 ORDER BY 3",
+                )
+                .context("Failed to prepare query for deletions")?;
+            let deletion_iter = statement
+                .query_map(params![since_timestamp], |row| {
+                    let kind: i64 = row.get(0)?;
+                    Ok(DeletedRecord {
+                        item_type: match kind {
+                            0 => TrashItemType::Animal,
+                            _ => TrashItemType::AdoptionRequest,
+                        },
+                        id: row.get(1)?,
+                        deleted_timestamp: row.get(2)?,
+                    })
+                })
+                .context("Failed to execute query for deletions")?;
+
+            let mut deletions = Vec::new();
+            for deletion in deletion_iter {
+                deletions.push(deletion.context("Failed to parse deletion row")?);
+            }
+            deletions
+        };
+
+        log::info!(
+            "Exported {} animal(s), {} adoption request(s) and {} deletion(s) changed since {}",
+            animals.len(),
+            adoption_requests.len(),
+            deletions.len(),
+            since_timestamp
+        );
+        Ok(Changelog {
+            exported_timestamp,
+            since_timestamp,
+            locations,
+            animals,
+            adoption_requests,
+            deletions,
+        })
+    }
+
+    /// Applies a changelog exported by another installation
+    ///
+    /// Each change only replaces the local record if it is more recent than the last local
+    /// change or deletion of that record. Everything is applied in a single transaction,
+    /// so an invalid changelog leaves the database untouched.
+    ///
+    /// # Arguments
+    /// * `changelog` - The changes to apply
+    ///
+    /// # Returns
+    /// * `Result<ImportChangesResult>` - How many changes were applied, skipped and deleted, or error
+    pub fn import_changes(&self, changelog: &Changelog) -> Result<ImportChangesResult> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for importing changes")?;
+
+        let mut result = ImportChangesResult {
+            applied_count: 0,
+            skipped_count: 0,
+            deleted_count: 0,
+        };
+
+        for location in &changelog.locations {
+            upsert_location(&transaction, location)?;
+        }
+
+        for change in &changelog.animals {
+            let animal = &change.record;
+            validation::ensure_valid(validation::validate_animal(animal))
+                .context(format!("Invalid animal with ID {}", animal.id))?;
+            if !is_newer_than_local(
+                &transaction,
+                "animals",
+                "trashed_animals",
+                &animal.id,
+                change.updated_timestamp,
+            )? {
+                result.skipped_count += 1;
+                continue;
+            }
+
+            transaction
+                .execute(
+                    "INSERT INTO animals (id, name, specie, breed, sex, birth_month, birth_year, neutered, admission_timestamp, status, image_path, appearance, bio, version, microchip_number, location_id, updated_timestamp, good_with_kids, good_with_dogs, good_with_cats, special_needs, size, color) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23) ON CONFLICT (id) DO UPDATE SET name = excluded.name, specie = excluded.specie, breed = excluded.breed, sex = excluded.sex, birth_month = excluded.birth_month, birth_year = excluded.birth_year, neutered = excluded.neutered, admission_timestamp = excluded.admission_timestamp, status = excluded.status, image_path = excluded.image_path, appearance = excluded.appearance, bio = excluded.bio, version = animals.version + 1, microchip_number = excluded.microchip_number, location_id = excluded.location_id, updated_timestamp = excluded.updated_timestamp, good_with_kids = excluded.good_with_kids, good_with_dogs = excluded.good_with_dogs, good_with_cats = excluded.good_with_cats, special_needs = excluded.special_needs, size = excluded.size, color = excluded.color",
+                    params![
+                        animal.id,
+                        animal.name,
+                        animal.specie,
+                        animal.breed,
+                        animal.sex,
+                        animal.birth_month,
+                        animal.birth_year,
+                        animal.neutered,
+                        animal.admission_timestamp,
+                        animal.status,
+                        animal.image_path,
+                        animal.appearance,
+                        animal.bio,
+                        animal.version,
+                        animal.microchip_number,
+                        animal.location_id,
+                        change.updated_timestamp,
+                        animal.good_with_kids,
+                        animal.good_with_dogs,
+                        animal.good_with_cats,
+                        animal.special_needs,
+                        animal.size,
+                        animal.color
+                    ],
+                )
+                .context(format!("Failed to import animal with ID {}", animal.id))?;
+            transaction
+                .execute(
+                    "DELETE FROM trashed_animals WHERE id = ?1",
+                    params![animal.id],
+                )
+                .context("Failed to remove imported animal from trash")?;
+            transaction
+                .execute(
+                    "DELETE FROM trashed_animal_records WHERE animal_id = ?1",
+                    params![animal.id],
+                )
+                .context("Failed to remove records of imported animal from trash")?;
+            result.applied_count += 1;
+        }
+
+        for change in &changelog.adoption_requests {
+            let request = &change.record;
+            validation::ensure_valid(validation::validate_adoption_request(request))
+                .context(format!("Invalid adoption request with ID {}", request.id))?;
+
+            // Requests for animals no longer in the shelter here cannot be stored
+            let animal_exists: bool = transaction
+                .query_row(
+                    "SELECT EXISTS (SELECT 1 FROM animals WHERE id = ?1)",
+                    params![request.animal_id],
+                    |row| row.get(0),
+                )
+                .context("Failed to check animal of imported adoption request")?;
+            if !animal_exists
+                || !is_newer_than_local(
+                    &transaction,
+                    "adoption_requests",
+                    "trashed_adoption_requests",
+                    &request.id,
+                    change.updated_timestamp,
+                )?
+            {
+                result.skipped_count += 1;
+                continue;
+            }
+
+            transaction
+                .execute(
+                    "INSERT INTO adoption_requests (id, animal_id, username, name, email, tel_number, address, occupation, annual_income, num_people, num_children, request_timestamp, adoption_timestamp, status, country, updated_timestamp, co_applicant_name, co_applicant_relationship, co_applicant_contact) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19) ON CONFLICT (id) DO UPDATE SET animal_id = excluded.animal_id, username = excluded.username, name = excluded.name, email = excluded.email, tel_number = excluded.tel_number, address = excluded.address, occupation = excluded.occupation, annual_income = excluded.annual_income, num_people = excluded.num_people, num_children = excluded.num_children, request_timestamp = excluded.request_timestamp, adoption_timestamp = excluded.adoption_timestamp, status = excluded.status, country = excluded.country, updated_timestamp = excluded.updated_timestamp, co_applicant_name = excluded.co_applicant_name, co_applicant_relationship = excluded.co_applicant_relationship, co_applicant_contact = excluded.co_applicant_contact",
+                    params![
+                        request.id,
+                        request.animal_id,
+                        request.username,
+                        request.name,
+                        request.email,
+                        request.tel_number,
+                        request.address,
+                        request.occupation,
+                        request.annual_income,
+                        request.num_people,
+                        request.num_children,
+                        request.request_timestamp,
+                        request.adoption_timestamp,
+                        request.status,
+                        request.country,
+                        change.updated_timestamp,
+                        request.co_applicant.as_ref().map(|co| &co.name),
+                        request.co_applicant.as_ref().map(|co| &co.relationship),
+                        request.co_applicant.as_ref().map(|co| &co.contact)
+                    ],
+                )
+                .context(format!(
+                    "Failed to import adoption request with ID {}",
+                    request.id
+                ))?;
+            transaction
+                .execute(
+                    "DELETE FROM trashed_adoption_requests WHERE id = ?1",
+                    params![request.id],
+                )
+                .context("Failed to remove imported adoption request from trash")?;
+            result.applied_count += 1;
+        }
+
+        for deletion in &changelog.deletions {
+            let table = match deletion.item_type {
+                TrashItemType::Animal => "animals",
+                TrashItemType::AdoptionRequest => "adoption_requests",
+            };
+            let updated_timestamp: Option<i64> = transaction
+                .query_row(
+                    &format!("SELECT updated_timestamp FROM {} WHERE id = ?1", table),
+                    params![deletion.id],
+                    |row| row.get(0),
+                )
+                .optional()
+                .context(format!("Failed to read last change of deleted {}", table))?;
+
+            // Keep records that are already gone, or that changed here after the deletion
+            match updated_timestamp {
+                None => continue,
+                Some(updated_timestamp) if updated_timestamp > deletion.deleted_timestamp => {
+                    result.skipped_count += 1;
+                    continue;
+                }
+                Some(_) => {}
+            }
+
+            let deleted = match deletion.item_type {
+                TrashItemType::Animal => {
+                    remove_animal(&transaction, &deletion.id, true, deletion.deleted_timestamp)?
+                }
+                TrashItemType::AdoptionRequest => {
+                    remove_adoption_request(&transaction, &deletion.id, deletion.deleted_timestamp)?
+                }
+            };
+            if deleted {
+                result.deleted_count += 1;
+            }
+        }
+
+        transaction
+            .commit()
+            .context("Failed to commit imported changes")?;
+
+        log::info!(
+            "Imported changes: {} applied, {} skipped, {} deleted",
+            result.applied_count,
+            result.skipped_count,
+            result.deleted_count
+        );
+        Ok(result)
+    }
+}
+
+/// Creates a location, or updates it if it already exists
+///
+/// # Arguments
+/// * `connection` - The connection or transaction performing the import
+/// * `location` - The location to store
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+fn upsert_location(connection: &Connection, location: &Location) -> Result<()> {
+    connection
+        .execute(
+            "INSERT INTO locations (id, name, address) VALUES (?1, ?2, ?3) ON CONFLICT (id) DO UPDATE SET name = excluded.name, address = excluded.address",
+            params![location.id, location.name, location.address],
+        )
+        .context(format!("Failed to import location with ID {}", location.id))?;
+    Ok(())
+}
+
+/// Checks whether an incoming change is more recent than the local record and its deletion
+///
+/// # Arguments
+/// * `connection` - The connection or transaction performing the import
+/// * `table` - The table holding the record
+/// * `trash_table` - The table holding the record once it is deleted
+/// * `id` - The ID of the record
+/// * `updated_timestamp` - Timestamp of the incoming change
+///
+/// # Returns
+/// * `Result<bool>` - True if the incoming change should replace the local record
+fn is_newer_than_local(
+    connection: &Connection,
+    table: &str,
+    trash_table: &str,
+    id: &str,
+    updated_timestamp: i64,
+) -> Result<bool> {
+    let local_timestamp: Option<i64> = connection
+        .query_row(
+            &format!(
+                "SELECT MAX(timestamp) FROM (SELECT updated_timestamp AS timestamp FROM {} WHERE id = ?1 UNION ALL SELECT deleted_timestamp FROM {} WHERE id = ?1)",
+                table, trash_table
+            ),
+            params![id],
+            |row| row.get(0),
+        )
+        .context(format!("Failed to read last local change in {}", table))?;
+    Ok(local_timestamp.is_none_or(|local_timestamp| updated_timestamp > local_timestamp))
+}
@@ -7,12 +7,29 @@
 #[cfg(test)]
 mod database_service_tests {
     use super::super::{
+        privacy::ERASED_NAME,
+        screening::{parse_income, screening_score},
         types::{
-            AdoptionRequest, Animal, AnimalStatus, FilterCriteria, FilterValue, RequestStatus,
+            AdjustStockResult, AdoptionRequest, AdoptionRequestFilters, AdoptionRequestView,
+            Animal, AnimalNote, AnimalSize, AnimalStatus, AppointmentStatus, ApprovalRequirements,
+            ApproveRequestResult, AssignKennelResult, AuditAction, BannedAdopter,
+            CalendarEventKind, CancelAppointmentResult, CoApplicant, CompleteSurgeryResult,
+            ConfirmAppointmentResult, Contact, ContactKind, CreateErasureResult,
+            CreateRequestResult, DeceasedRecord, Donation, EmailStatus, ErasureRequestStatus,
+            Expense, ExpenseCategory, FilterCriteria, FilterValue, FinalizeTrialResult,
+            FosterPlacement, IncomeBand, IntakeRecord, IntakeType, InventoryItem,
+            JoinWaitlistResult, Kennel, Location, NoteCategory, Payment, PaymentDocument,
+            ProcessReturnResult, RecordCheckResult, RecordDeathResult, RecordPaymentResult,
+            RecordTransferResult, ReferenceCheck, ReferenceOutcome, RequestAppointmentResult,
+            RequestInterview, RequestStatus, ReviewErasureResult, ScheduleSurgeryResult,
+            ScreeningConfig, StartFosterResult, Transfer, TransferDirection, TrashItemType,
+            TrendGranularity, TrendPoint, UpdateAnimalResult, UserProfile, Vaccination,
+            WaitlistEntry, Webhook, WebhookDeliveryStatus, WebhookEvent,
         },
-        DatabaseService,
+        validation::{validate_adoption_request, validate_animal, ValidationError},
+        DatabaseService, DATA_VERSION,
     };
-    use chrono::Utc;
+    use chrono::{Datelike, Utc};
     use std::collections::HashMap;
     use std::fs;
     use std::path::PathBuf;
@@ -63,6 +80,15 @@ mod database_service_tests {
             image_path: Some("/test/images/buddy.jpg".to_string()),
             appearance: "Golden coat with friendly eyes".to_string(),
             bio: "Buddy is a friendly and energetic dog who loves playing fetch and going on walks. He gets along well with children and other pets.".to_string(),
+            version: 1,
+            microchip_number: None,
+            location_id: None,
+            good_with_kids: None,
+            good_with_dogs: None,
+            good_with_cats: None,
+            special_needs: None,
+            size: None,
+            color: None,
         }
     }
 
@@ -91,6 +117,87 @@ mod database_service_tests {
             adoption_timestamp: 0,
             status: RequestStatus::Pending,
             country: "Thailand".to_string(),
+            co_applicant: None,
+        }
+    }
+
+    /// Helper function to create a sample foster placement for testing
+    ///
+    /// # Arguments
+    /// * `id` - Unique identifier for the placement
+    /// * `animal_id` - ID of the animal being fostered
+    ///
+    /// # Returns
+    /// * `FosterPlacement` - Sample foster placement lasting thirty days
+    fn sample_placement(id: &str, animal_id: &str) -> FosterPlacement {
+        let now = Utc::now().timestamp();
+        FosterPlacement {
+            id: id.to_string(),
+            animal_id: animal_id.to_string(),
+            caregiver_name: "Somchai Jaidee".to_string(),
+            caregiver_email: "somchai@example.com".to_string(),
+            caregiver_tel_number: "0987654321".to_string(),
+            start_timestamp: now,
+            end_timestamp: now + 30 * 86_400,
+            ended_timestamp: None,
+        }
+    }
+
+    /// Helper function to create a sample phone interview for testing
+    ///
+    /// # Arguments
+    /// * `request_id` - ID of the adoption request the interview is about
+    ///
+    /// # Returns
+    /// * `RequestInterview` - Sample interview held an hour ago
+    fn sample_interview(request_id: &str) -> RequestInterview {
+        RequestInterview {
+            id: String::new(),
+            request_id: request_id.to_string(),
+            interviewer: "manager".to_string(),
+            interview_timestamp: Utc::now().timestamp() - 3600,
+            notes: "Has a fenced garden and works from home".to_string(),
+        }
+    }
+
+    /// Helper function to create a sample reference check for testing
+    ///
+    /// # Arguments
+    /// * `request_id` - ID of the adoption request the reference was given for
+    /// * `outcome` - What the referee said
+    ///
+    /// # Returns
+    /// * `ReferenceCheck` - Sample reference check recorded now
+    fn sample_reference_check(request_id: &str, outcome: ReferenceOutcome) -> ReferenceCheck {
+        ReferenceCheck {
+            id: String::new(),
+            request_id: request_id.to_string(),
+            referee_name: "Dr. Somchai".to_string(),
+            referee_contact: "somchai@example.com".to_string(),
+            outcome,
+            notes: String::new(),
+            checked_by: "manager".to_string(),
+            checked_timestamp: Utc::now().timestamp(),
+        }
+    }
+
+    /// Helper function to create a sample adoption fee for testing
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the adoption request the fee is charged for
+    ///
+    /// # Returns
+    /// * `Payment` - Sample unpaid adoption fee
+    fn sample_payment(request_id: &str) -> Payment {
+        Payment {
+            id: String::new(),
+            request_id: request_id.to_string(),
+            amount: 15000,
+            description: "Adoption fee".to_string(),
+            created_timestamp: Utc::now().timestamp(),
+            paid_timestamp: None,
+            invoice_number: None,
+            receipt_number: None,
         }
     }
 
@@ -138,10 +245,11 @@ mod database_service_tests {
         animal.appearance = "Updated golden coat with wise eyes".to_string();
         animal.bio = "Updated bio: Buddy is now a mature and well-trained dog.".to_string();
         let updated = db.update_animal(&animal).unwrap();
-        assert!(updated);
+        assert!(matches!(updated, UpdateAnimalResult::Updated(ref a) if a.version == 2));
 
         let found = db.query_animal_by_id("a1").unwrap().unwrap();
         assert_eq!(found.name, "Updated Buddy");
+        assert_eq!(found.version, 2);
         assert_eq!(found.status, AnimalStatus::Adopted);
         assert_eq!(
             found.image_path,
@@ -153,23 +261,83 @@ mod database_service_tests {
             "Updated bio: Buddy is now a mature and well-trained dog."
         );
 
+        // The list view gets the status and photo for its badges and thumbnails
+        let summaries = db.query_animals(None).unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].status, AnimalStatus::Adopted);
+        assert_eq!(
+            summaries[0].image_path.as_deref(),
+            Some("/test/images/updated_buddy.jpg")
+        );
+
         // Test update non-existent
         let fake_animal = sample_animal("fake");
         let not_updated = db.update_animal(&fake_animal).unwrap();
-        assert!(!not_updated);
+        assert!(matches!(not_updated, UpdateAnimalResult::NotFound));
 
         // Test delete
-        let deleted = db.delete_animal("a1").unwrap();
+        let deleted = db.delete_animal("a1", false).unwrap();
         assert!(deleted);
 
         let not_found = db.query_animal_by_id("a1").unwrap();
         assert!(not_found.is_none());
 
         // Test delete non-existent
-        let not_deleted = db.delete_animal("nonexistent").unwrap();
+        let not_deleted = db.delete_animal("nonexistent", false).unwrap();
         assert!(!not_deleted);
     }
 
+    #[test]
+    fn test_animals_validation() {
+        let db = create_test_db("test_animals_validation");
+        assert!(validate_animal(&sample_animal("a1")).is_empty());
+
+        // Every invalid field is reported, and nothing is stored
+        let mut animal = sample_animal("a1");
+        animal.name = "  ".to_string();
+        animal.birth_month = Some(99);
+        animal.admission_timestamp = Utc::now().timestamp() + 86400 * 7;
+        let fields: Vec<String> = validate_animal(&animal)
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert_eq!(fields, vec!["name", "birthMonth", "admissionTimestamp"]);
+
+        let error = db.insert_animal(&animal).unwrap_err();
+        let validation_error = error.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(validation_error.fields.len(), 3);
+        assert!(db.query_animal_by_id("a1").unwrap().is_none());
+
+        // Updates are checked too
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        let mut update = sample_animal("a1");
+        update.birth_year = Some(1800);
+        assert!(db.update_animal(&update).is_err());
+    }
+
+    #[test]
+    fn test_animals_generated_ids() {
+        let db = create_test_db("test_animals_generated_ids");
+
+        // Animals inserted without an ID should get distinct UUIDs
+        db.insert_animal(&sample_animal("")).unwrap();
+        db.insert_animal(&sample_animal("")).unwrap();
+        let animals = db.query_animals(None).unwrap();
+        assert_eq!(animals.len(), 2);
+        assert_ne!(animals[0].id, animals[1].id);
+        for animal in &animals {
+            assert!(uuid::Uuid::parse_str(&animal.id).is_ok());
+        }
+
+        // Requests inserted without an ID should get a UUID as well
+        db.insert_adoption_request(&sample_request("", &animals[0].id))
+            .unwrap();
+        let requests = db
+            .query_adoption_requests_by_animal_id(&animals[0].id)
+            .unwrap();
+        assert!(uuid::Uuid::parse_str(&requests[0].id).is_ok());
+    }
+
     #[test]
     fn test_animals_duplicate_insert() {
         let db = create_test_db("test_animals_duplicate_insert");
@@ -183,6 +351,103 @@ mod database_service_tests {
         assert!(duplicate_result.is_err());
     }
 
+    #[test]
+    fn test_animals_batch_insert() {
+        let db = create_test_db("test_animals_batch_insert");
+
+        // IDs are returned in input order, generated where missing
+        let (ids, _) = db
+            .insert_animals_batch(&[sample_animal("a1"), sample_animal("")])
+            .unwrap();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], "a1");
+        assert!(uuid::Uuid::parse_str(&ids[1]).is_ok());
+        assert_eq!(db.query_animals(None).unwrap().len(), 2);
+
+        // A failing animal rolls back the whole batch
+        let result = db.insert_animals_batch(&[sample_animal("a2"), sample_animal("a1")]);
+        assert!(result.is_err());
+        assert!(db.query_animal_by_id("a2").unwrap().is_none());
+        assert_eq!(db.query_animals(None).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_animal_detail() {
+        let db = create_test_db("test_animal_detail");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+
+        let now = Utc::now().timestamp();
+        let note = |id: &str, category: NoteCategory, internal: bool, offset: i64| AnimalNote {
+            id: id.to_string(),
+            animal_id: "a1".to_string(),
+            author_username: "staff".to_string(),
+            category,
+            content: format!("Note {}", id),
+            internal,
+            created_timestamp: now + offset,
+        };
+        db.insert_animal_note(&note("n1", NoteCategory::Medical, false, 0))
+            .unwrap();
+        db.insert_animal_note(&note("n2", NoteCategory::Medical, true, 10))
+            .unwrap();
+        db.insert_animal_note(&note("n3", NoteCategory::General, false, 20))
+            .unwrap();
+
+        // Staff see the latest medical note, even if it is internal
+        let detail = db.query_animal_detail("a1", true).unwrap().unwrap();
+        assert_eq!(detail.animal.id, "a1");
+        assert_eq!(detail.adoption_requests.len(), 1);
+        assert_eq!(detail.latest_medical_note.unwrap().id, "n2");
+        assert!(detail.age_months.unwrap() >= 12 * 4);
+
+        // Customers only see public notes
+        let detail = db.query_animal_detail("a1", false).unwrap().unwrap();
+        assert_eq!(detail.latest_medical_note.unwrap().id, "n1");
+
+        // Unknown birth dates have no age
+        let mut unknown = sample_animal("a2");
+        unknown.birth_month = None;
+        db.insert_animal(&unknown).unwrap();
+        let detail = db.query_animal_detail("a2", true).unwrap().unwrap();
+        assert!(detail.age_months.is_none());
+        assert!(detail.latest_medical_note.is_none());
+
+        assert!(db.query_animal_detail("missing", true).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_animals_update_conflict() {
+        let db = create_test_db("test_animals_update_conflict");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+
+        // Two staff members read the same version of the animal
+        let mut first_edit = db.query_animal_by_id("a1").unwrap().unwrap();
+        let mut second_edit = first_edit.clone();
+
+        // The first edit succeeds and bumps the version
+        first_edit.name = "Max".to_string();
+        let result = db.update_animal(&first_edit).unwrap();
+        assert!(matches!(result, UpdateAnimalResult::Updated(ref a) if a.version == 2));
+
+        // The second edit is stale and gets the current record back instead of overwriting
+        second_edit.name = "Rocky".to_string();
+        let result = db.update_animal(&second_edit).unwrap();
+        match result {
+            UpdateAnimalResult::Conflict(current) => {
+                assert_eq!(current.name, "Max");
+                assert_eq!(current.version, 2);
+            }
+            other => panic!("Expected a conflict, got {:?}", other),
+        }
+
+        // Retrying with the fresh version succeeds
+        second_edit.version = 2;
+        let result = db.update_animal(&second_edit).unwrap();
+        assert!(matches!(result, UpdateAnimalResult::Updated(ref a) if a.name == "Rocky"));
+    }
+
     #[test]
     fn test_animals_multiple_records() {
         let db = create_test_db("test_animals_multiple_records");
@@ -344,6 +609,369 @@ mod database_service_tests {
         assert!(animals.iter().any(|a| a.id == "a4"));
     }
 
+    #[test]
+    fn test_animals_filter_neutered_and_image() {
+        let db = create_test_db("test_animals_filter_neutered_and_image");
+        let mut intact = sample_animal("a2");
+        intact.neutered = false;
+        let mut no_image = sample_animal("a3");
+        no_image.image_path = None;
+        let mut blank_image = sample_animal("a4");
+        blank_image.image_path = Some(String::new());
+        for animal in [sample_animal("a1"), intact, no_image, blank_image] {
+            db.insert_animal(&animal).unwrap();
+        }
+
+        let query = |criteria: FilterCriteria, option: &str| {
+            let mut filters = HashMap::new();
+            filters.insert(criteria, Some(FilterValue::ChooseOne(option.to_string())));
+            let mut ids: Vec<String> = db
+                .query_animals(Some(filters))
+                .unwrap()
+                .into_iter()
+                .map(|a| a.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(query(FilterCriteria::Neutered, "no"), vec!["a2"]);
+        assert_eq!(query(FilterCriteria::Neutered, "yes").len(), 3);
+        assert_eq!(query(FilterCriteria::Neutered, "all").len(), 4);
+        assert_eq!(query(FilterCriteria::HasImage, "no"), vec!["a3", "a4"]);
+        assert_eq!(query(FilterCriteria::HasImage, "yes"), vec!["a1", "a2"]);
+    }
+
+    #[test]
+    fn test_animals_filter_age() {
+        let db = create_test_db("test_animals_filter_age");
+        let this_year = Utc::now().year();
+        let mut puppy = sample_animal("a1");
+        puppy.birth_month = None;
+        puppy.birth_year = Some(this_year);
+        let adult = sample_animal("a2");
+        let mut senior = sample_animal("a3");
+        senior.birth_month = None;
+        senior.birth_year = Some(this_year - 10);
+        let mut unknown = sample_animal("a4");
+        unknown.birth_month = None;
+        unknown.birth_year = None;
+        let mut month_only = sample_animal("a5");
+        month_only.birth_year = None;
+        for animal in [puppy, adult, senior, unknown, month_only] {
+            db.insert_animal(&animal).unwrap();
+        }
+
+        let query = |option: &str| {
+            let mut filters = HashMap::new();
+            filters.insert(
+                FilterCriteria::Age,
+                Some(FilterValue::ChooseOne(option.to_string())),
+            );
+            let mut ids: Vec<String> = db
+                .query_animals(Some(filters))
+                .unwrap()
+                .into_iter()
+                .map(|a| a.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(query("young"), vec!["a1"]);
+        assert_eq!(query("adult"), vec!["a2"]);
+        assert_eq!(query("senior"), vec!["a3"]);
+        assert_eq!(query("unknown"), vec!["a4", "a5"]);
+        assert_eq!(query("all").len(), 5);
+    }
+
+    #[test]
+    fn test_animals_filter_size_and_color() {
+        let db = create_test_db("test_animals_filter_size_and_color");
+        let mut small_brown = sample_animal("a1");
+        small_brown.size = Some(AnimalSize::Small);
+        small_brown.color = Some("  Brown ".to_string());
+        let mut large_black = sample_animal("a2");
+        large_black.size = Some(AnimalSize::Large);
+        large_black.color = Some("Black".to_string());
+        let mut blank_color = sample_animal("a3");
+        blank_color.color = Some(" ".to_string());
+        for animal in [small_brown, large_black, blank_color] {
+            db.insert_animal(&animal).unwrap();
+        }
+
+        let stored = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(stored.size, Some(AnimalSize::Small));
+        assert_eq!(stored.color.as_deref(), Some("Brown"));
+        assert_eq!(db.query_animal_by_id("a3").unwrap().unwrap().color, None);
+        assert_eq!(db.query_distinct_colors().unwrap(), vec!["Black", "Brown"]);
+
+        let query = |criteria: FilterCriteria, options: &[&str]| {
+            let mut filters = HashMap::new();
+            filters.insert(
+                criteria,
+                Some(FilterValue::ChooseMany(
+                    options.iter().map(|option| option.to_string()).collect(),
+                )),
+            );
+            let mut ids: Vec<String> = db
+                .query_animals(Some(filters))
+                .unwrap()
+                .into_iter()
+                .map(|a| a.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(query(FilterCriteria::Size, &["small"]), vec!["a1"]);
+        assert_eq!(
+            query(FilterCriteria::Size, &["small", "large"]),
+            vec!["a1", "a2"]
+        );
+        assert!(query(FilterCriteria::Size, &[]).is_empty());
+        assert_eq!(query(FilterCriteria::Color, &["brown"]), vec!["a1"]);
+        assert_eq!(
+            query(FilterCriteria::Color, &["Brown", "BLACK"]),
+            vec!["a1", "a2"]
+        );
+    }
+
+    #[test]
+    fn test_animals_filter_compatibility() {
+        let db = create_test_db("test_animals_filter_compatibility");
+        let mut family_dog = sample_animal("a1");
+        family_dog.good_with_kids = Some(true);
+        family_dog.good_with_dogs = Some(true);
+        family_dog.good_with_cats = Some(false);
+        let mut needy = sample_animal("a2");
+        needy.good_with_kids = Some(false);
+        needy.special_needs = Some(true);
+        for animal in [family_dog, needy, sample_animal("a3")] {
+            db.insert_animal(&animal).unwrap();
+        }
+
+        let stored = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(stored.good_with_kids, Some(true));
+        assert_eq!(stored.good_with_cats, Some(false));
+        assert_eq!(stored.special_needs, None);
+
+        let query = |criteria: FilterCriteria, option: &str| {
+            let mut filters = HashMap::new();
+            filters.insert(criteria, Some(FilterValue::ChooseOne(option.to_string())));
+            let mut ids: Vec<String> = db
+                .query_animals(Some(filters))
+                .unwrap()
+                .into_iter()
+                .map(|a| a.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(query(FilterCriteria::GoodWithKids, "yes"), vec!["a1"]);
+        assert_eq!(query(FilterCriteria::GoodWithKids, "no"), vec!["a2"]);
+        assert_eq!(query(FilterCriteria::GoodWithKids, "unknown"), vec!["a3"]);
+        assert_eq!(query(FilterCriteria::GoodWithDogs, "yes"), vec!["a1"]);
+        assert_eq!(query(FilterCriteria::GoodWithCats, "no"), vec!["a1"]);
+        assert_eq!(query(FilterCriteria::SpecialNeeds, "yes"), vec!["a2"]);
+        assert_eq!(query(FilterCriteria::SpecialNeeds, "all").len(), 3);
+
+        // Clearing a flag back to unknown is stored as such
+        let mut updated = stored.clone();
+        updated.good_with_kids = None;
+        assert!(matches!(
+            db.update_animal(&updated).unwrap(),
+            UpdateAnimalResult::Updated(_)
+        ));
+        assert_eq!(
+            db.query_animal_by_id("a1").unwrap().unwrap().good_with_kids,
+            None
+        );
+    }
+
+    #[test]
+    fn test_distinct_values() {
+        let db = create_test_db("test_distinct_values");
+        let mut beagle = sample_animal("a2");
+        beagle.breed = "Beagle".to_string();
+        let mut cat = sample_animal("a3");
+        cat.specie = "Cat".to_string();
+        cat.breed = "Siamese".to_string();
+        for animal in [sample_animal("a1"), beagle, cat, sample_animal("a4")] {
+            db.insert_animal(&animal).unwrap();
+        }
+        let mut request = sample_request("r2", "a2");
+        request.country = "Japan".to_string();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+        db.insert_adoption_request(&request).unwrap();
+        db.insert_adoption_request(&sample_request("r3", "a3"))
+            .unwrap();
+
+        assert_eq!(db.query_distinct_species().unwrap(), vec!["Cat", "Dog"]);
+        assert_eq!(
+            db.query_distinct_breeds("Dog").unwrap(),
+            vec!["Beagle", "Golden Retriever"]
+        );
+        assert!(db.query_distinct_breeds("Horse").unwrap().is_empty());
+        assert_eq!(
+            db.query_distinct_countries().unwrap(),
+            vec!["Japan", "Thailand"]
+        );
+    }
+
+    #[test]
+    fn test_animal_and_pending_request_counts() {
+        let db = create_test_db("test_animal_and_pending_request_counts");
+        let mut cat = sample_animal("a2");
+        cat.specie = "Cat".to_string();
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_animal(&cat).unwrap();
+
+        // Counts apply the same filters as the animal listing
+        assert_eq!(db.count_animals(None).unwrap(), 2);
+        let mut filters = HashMap::new();
+        filters.insert(
+            FilterCriteria::SpeciesAndBreeds,
+            Some(FilterValue::NestedChooseMany(HashMap::from([(
+                "Cat".to_string(),
+                vec!["Golden Retriever".to_string()],
+            )]))),
+        );
+        assert_eq!(db.count_animals(Some(filters.clone())).unwrap(), 1);
+        assert_eq!(
+            db.count_animals(Some(filters.clone())).unwrap() as usize,
+            db.query_animals(Some(filters)).unwrap().len()
+        );
+
+        // Only pending requests are counted
+        assert_eq!(db.count_pending_adoption_requests(None).unwrap(), 0);
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+        let mut rejected = sample_request("r2", "a2");
+        rejected.status = RequestStatus::Rejected;
+        db.insert_adoption_request(&rejected).unwrap();
+        assert_eq!(db.count_pending_adoption_requests(None).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_migrates_existing_database() {
+        // Create a database with the original animals schema
+        let mut db_path = PathBuf::from("test_artifacts/database_service");
+        db_path.push("test_migrates_existing_database");
+        fs::create_dir_all(&db_path).expect("Failed to create test artifacts directory");
+        db_path.push("test.db");
+        let _ = fs::remove_file(&db_path);
+        {
+            let connection = rusqlite::Connection::open(&db_path).unwrap();
+            connection
+                .execute_batch(
+                    "
+                CREATE TABLE animals (
+                    id TEXT PRIMARY KEY, name TEXT NOT NULL, specie TEXT NOT NULL,
+                    breed TEXT NOT NULL, sex TEXT NOT NULL, birth_month INTEGER,
+                    birth_year INTEGER, neutered BOOLEAN NOT NULL,
+                    admission_timestamp INTEGER NOT NULL, status TEXT NOT NULL,
+                    image_path TEXT, appearance TEXT NOT NULL, bio TEXT NOT NULL
+                );
+                CREATE TABLE adoption_requests (
+                    id TEXT PRIMARY KEY, animal_id TEXT NOT NULL, username TEXT NOT NULL,
+                    name TEXT NOT NULL, email TEXT NOT NULL, tel_number TEXT NOT NULL,
+                    address TEXT NOT NULL, occupation TEXT NOT NULL,
+                    annual_income TEXT NOT NULL, num_people INTEGER NOT NULL,
+                    num_children INTEGER NOT NULL, request_timestamp INTEGER NOT NULL,
+                    adoption_timestamp INTEGER NOT NULL, status TEXT NOT NULL,
+                    country TEXT NOT NULL,
+                    FOREIGN KEY (animal_id) REFERENCES animals (id)
+                );
+                CREATE TABLE payments (
+                    id TEXT PRIMARY KEY, request_id TEXT NOT NULL, amount REAL NOT NULL,
+                    description TEXT NOT NULL, created_timestamp INTEGER NOT NULL,
+                    paid_timestamp INTEGER, invoice_number INTEGER UNIQUE,
+                    receipt_number INTEGER UNIQUE
+                );
+                INSERT INTO payments VALUES ('p1', 'r1', 150.5, 'Adoption fee', 0, NULL, NULL, NULL);
+                INSERT INTO animals VALUES ('a1', 'Buddy', 'Dog', 'Beagle', 'Male', NULL, NULL, 1, 0, 'available', NULL, '', '');
+                INSERT INTO animals VALUES ('7', 'Lucy', 'Cat', 'Siamese', 'Female', NULL, NULL, 1, 0, 'requested', NULL, '', '');
+                INSERT INTO animals VALUES ('a2', 'Rex', 'Dog', 'Beagle', 'Male', 0, 0, 1, 0, 'available', NULL, '', '');
+                INSERT INTO adoption_requests VALUES ('3', '7', 'JiraPit', 'Jira Pit', 'jira.pit@gmail.com', '0123456789', 'Bangkok', 'Engineer', '50000', 2, 0, 0, 0, 'pending', 'Thailand');
+                ",
+                )
+                .unwrap();
+        }
+
+        // Opening the database with the service should migrate it in place
+        let db = DatabaseService::new(&db_path).unwrap();
+        let animal = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(animal.name, "Buddy");
+        assert_eq!(animal.version, 1);
+
+        // Zero birth dates from older versions become unknown
+        let rex = db.query_animal_by_id("a2").unwrap().unwrap();
+        assert_eq!(rex.birth_month, None);
+        assert_eq!(rex.birth_year, None);
+
+        // Numeric IDs should be replaced with UUIDs, keeping requests linked to their animal
+        assert!(db.query_animal_by_id("7").unwrap().is_none());
+        let lucy = db
+            .query_animals(None)
+            .unwrap()
+            .into_iter()
+            .find(|a| a.name == "Lucy")
+            .unwrap();
+        assert!(uuid::Uuid::parse_str(&lucy.id).is_ok());
+        let requests = db.query_adoption_requests_by_animal_id(&lucy.id).unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(uuid::Uuid::parse_str(&requests[0].id).is_ok());
+
+        // Amounts of money become whole minor units
+        assert_eq!(db.query_payments("r1").unwrap()[0].amount, 15050);
+
+        assert_eq!(db.schema_version().unwrap(), DATA_VERSION);
+
+        // Reopening the database should not migrate again
+        drop(db);
+        let db = DatabaseService::new(&db_path).unwrap();
+        assert!(db.query_animal_by_id(&lucy.id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_animals_microchip_lookup() {
+        let db = create_test_db("test_animals_microchip_lookup");
+
+        let mut chipped = sample_animal("a1");
+        chipped.microchip_number = Some(" 985112003456789 ".to_string());
+        db.insert_animal(&chipped).unwrap();
+
+        // Animals without a chip, or with a blank one, do not clash with each other
+        db.insert_animal(&sample_animal("a2")).unwrap();
+        let mut blank = sample_animal("a3");
+        blank.microchip_number = Some("  ".to_string());
+        db.insert_animal(&blank).unwrap();
+        assert_eq!(
+            db.query_animal_by_id("a3")
+                .unwrap()
+                .unwrap()
+                .microchip_number,
+            None
+        );
+
+        // Lookup ignores surrounding whitespace from the scanner
+        let found = db
+            .query_animal_by_microchip("985112003456789\n")
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, "a1");
+        assert_eq!(found.microchip_number.as_deref(), Some("985112003456789"));
+        assert!(db.query_animal_by_microchip("000").unwrap().is_none());
+
+        // The same chip cannot be registered twice
+        let mut duplicate = sample_animal("a4");
+        duplicate.microchip_number = Some("985112003456789".to_string());
+        assert!(db.insert_animal(&duplicate).is_err());
+    }
+
     // ==================== ADOPTION REQUESTS TESTS ====================
 
     #[test]
@@ -370,18 +998,22 @@ mod database_service_tests {
         let not_found = db.query_adoption_request_by_id("nonexistent").unwrap();
         assert!(not_found.is_none());
 
-        // Test update
+        // Test update; approval goes through approve_adoption_request instead
         request.name = "Non Prajogo".to_string();
-        request.status = RequestStatus::Approved;
-        request.adoption_timestamp = Utc::now().timestamp();
         request.country = "Indonesia".to_string();
+        for status in [RequestStatus::Approved, RequestStatus::TrialAdoption] {
+            request.status = status;
+            let error = db.update_adoption_request(&request).unwrap_err();
+            let validation = error.downcast_ref::<ValidationError>().unwrap();
+            assert_eq!(validation.fields[0].field, "status");
+        }
+        request.status = RequestStatus::Rejected;
         let updated = db.update_adoption_request(&request).unwrap();
         assert!(updated);
 
         let found = db.query_adoption_request_by_id("r1").unwrap().unwrap();
         assert_eq!(found.name, "Non Prajogo");
-        assert_eq!(found.status, RequestStatus::Approved);
-        assert_ne!(found.adoption_timestamp, 0);
+        assert_eq!(found.status, RequestStatus::Rejected);
         assert_eq!(found.country, "Indonesia");
 
         // Test update non-existent
@@ -401,6 +1033,81 @@ mod database_service_tests {
         assert!(!not_deleted);
     }
 
+    #[test]
+    fn test_requests_validation() {
+        let db = create_test_db("test_requests_validation");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        assert!(validate_adoption_request(&sample_request("r1", "a1")).is_empty());
+
+        let mut request = sample_request("r1", "a1");
+        request.email = "not-an-email".to_string();
+        request.tel_number = "call me".to_string();
+        request.num_people = 1;
+        request.num_children = 2;
+        let fields: Vec<String> = validate_adoption_request(&request)
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert_eq!(fields, vec!["email", "telNumber", "numChildren"]);
+        assert!(db.insert_adoption_request(&request).is_err());
+        assert!(db.query_adoption_request_by_id("r1").unwrap().is_none());
+
+        // Common telephone number formats are accepted
+        let mut request = sample_request("r1", "a1");
+        request.tel_number = "+66 (2) 123-4567".to_string();
+        assert!(validate_adoption_request(&request).is_empty());
+    }
+
+    #[test]
+    fn test_requests_co_applicant() {
+        let db = create_test_db("test_requests_co_applicant");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+
+        // Every co-applicant field is required, and the contact must be reachable
+        let mut request = sample_request("r1", "a1");
+        request.co_applicant = Some(CoApplicant {
+            name: " ".to_string(),
+            relationship: "Spouse".to_string(),
+            contact: "somewhere".to_string(),
+        });
+        let fields: Vec<String> = validate_adoption_request(&request)
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert_eq!(fields, vec!["coApplicantName", "coApplicantContact"]);
+
+        let co_applicant = CoApplicant {
+            name: "Mali Pit".to_string(),
+            relationship: "Spouse".to_string(),
+            contact: "mali.pit@gmail.com".to_string(),
+        };
+        request.co_applicant = Some(CoApplicant {
+            name: " Mali Pit ".to_string(),
+            ..co_applicant.clone()
+        });
+        db.insert_adoption_request(&request).unwrap();
+
+        let stored = db.query_adoption_request_by_id("r1").unwrap().unwrap();
+        assert_eq!(stored.co_applicant, Some(co_applicant.clone()));
+        assert_eq!(stored.applicant_names(), "Jira Pit & Mali Pit");
+        assert_eq!(
+            db.query_adoption_requests_by_animal_id("a1").unwrap()[0].co_applicant,
+            Some(co_applicant.clone())
+        );
+        let details = db
+            .query_adoption_request_details(&AdoptionRequestFilters::default())
+            .unwrap();
+        assert_eq!(details[0].request.co_applicant, Some(co_applicant));
+
+        // Removing the co-applicant leaves the requester applying alone
+        let mut alone = stored.clone();
+        alone.co_applicant = None;
+        assert!(db.update_adoption_request(&alone).unwrap());
+        let stored = db.query_adoption_request_by_id("r1").unwrap().unwrap();
+        assert_eq!(stored.co_applicant, None);
+        assert_eq!(stored.applicant_names(), "Jira Pit");
+    }
+
     #[test]
     fn test_requests_duplicate_insert() {
         let db = create_test_db("test_requests_duplicate_insert");
@@ -412,11 +1119,41 @@ mod database_service_tests {
         // First insert should succeed
         db.insert_adoption_request(&request).unwrap();
 
-        // Duplicate insert should fail
-        let duplicate_result = db.insert_adoption_request(&request);
+        // Duplicate ID insert should fail, even from a different user
+        let mut duplicate = request.clone();
+        duplicate.username = "NonPrajogo".to_string();
+        let duplicate_result = db.insert_adoption_request(&duplicate);
         assert!(duplicate_result.is_err());
     }
 
+    #[test]
+    fn test_requests_duplicate_pending() {
+        let db = create_test_db("test_requests_duplicate_pending");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+
+        // First pending request should succeed
+        let result = db
+            .insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+        assert_eq!(result, CreateRequestResult::Success);
+
+        // Second pending request from the same user for the same animal should be rejected
+        let result = db
+            .insert_adoption_request(&sample_request("r2", "a1"))
+            .unwrap();
+        assert_eq!(result, CreateRequestResult::DuplicatePendingRequest);
+        assert!(db.query_adoption_request_by_id("r2").unwrap().is_none());
+
+        // Once the first request is no longer pending, the user may apply again
+        let mut request = db.query_adoption_request_by_id("r1").unwrap().unwrap();
+        request.status = RequestStatus::Rejected;
+        db.update_adoption_request(&request).unwrap();
+        let result = db
+            .insert_adoption_request(&sample_request("r2", "a1"))
+            .unwrap();
+        assert_eq!(result, CreateRequestResult::Success);
+    }
+
     #[test]
     fn test_requests_foreign_key() {
         let db = create_test_db("test_requests_foreign_key");
@@ -427,6 +1164,245 @@ mod database_service_tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_delete_animal_with_requests() {
+        let db = create_test_db("test_delete_animal_with_requests");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+
+        // Deleting without cascade should be blocked and leave everything intact
+        let result = db.delete_animal("a1", false);
+        assert!(result.is_err());
+        assert!(db.query_animal_by_id("a1").unwrap().is_some());
+        assert!(db.query_adoption_request_by_id("r1").unwrap().is_some());
+
+        // Deleting with cascade should remove the animal and its requests
+        let deleted = db.delete_animal("a1", true).unwrap();
+        assert!(deleted);
+        assert!(db.query_animal_by_id("a1").unwrap().is_none());
+        assert!(db.query_adoption_request_by_id("r1").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_trash_restore_and_purge() {
+        let db = create_test_db("test_trash_restore_and_purge");
+        let mut animal = sample_animal("a2");
+        animal.image_path = None;
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_animal(&animal).unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+        db.insert_adoption_request(&sample_request("r2", "a2"))
+            .unwrap();
+
+        // Deleted records are listed in the trash, without requests deleted with their animal
+        assert!(db.delete_adoption_request("r2").unwrap());
+        assert!(db.delete_animal("a1", true).unwrap());
+        let trash = db.query_trash().unwrap();
+        assert_eq!(trash.len(), 2);
+        assert!(trash
+            .iter()
+            .any(|item| item.item_type == TrashItemType::Animal && item.id == "a1"));
+        assert!(trash
+            .iter()
+            .any(|item| item.item_type == TrashItemType::AdoptionRequest && item.id == "r2"));
+
+        // Restoring the animal brings back its requests
+        assert!(db.restore_from_trash(TrashItemType::Animal, "a1").unwrap());
+        assert!(db.query_animal_by_id("a1").unwrap().is_some());
+        assert!(db.query_adoption_request_by_id("r1").unwrap().is_some());
+        assert!(!db.restore_from_trash(TrashItemType::Animal, "a1").unwrap());
+
+        // A request cannot be restored once its animal is gone
+        assert!(db.delete_animal("a2", true).unwrap());
+        assert!(db
+            .restore_from_trash(TrashItemType::AdoptionRequest, "r2")
+            .is_err());
+
+        // Purging only removes expired records and reports the images to delete
+        assert!(db.delete_animal("a1", true).unwrap());
+        let (purged, image_paths) = db.purge_expired_trash(Utc::now().timestamp()).unwrap();
+        assert_eq!(purged, 0);
+        assert!(image_paths.is_empty());
+
+        let later = Utc::now().timestamp() + 31 * 24 * 60 * 60;
+        let (purged, image_paths) = db.purge_expired_trash(later).unwrap();
+        assert_eq!(purged, 4);
+        assert_eq!(image_paths, vec![sample_animal("a1").image_path.unwrap()]);
+        assert!(db.query_trash().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_trash_keeps_animal_history() {
+        let db = create_test_db("test_trash_keeps_animal_history");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_contact(&Contact {
+            id: "vet".to_string(),
+            name: "Riverside Clinic".to_string(),
+            kind: ContactKind::Veterinarian,
+            tel_number: "0812345678".to_string(),
+            email: String::new(),
+            address: String::new(),
+            notes: String::new(),
+        })
+        .unwrap();
+        db.insert_vaccination(&Vaccination {
+            id: "v1".to_string(),
+            animal_id: "a1".to_string(),
+            vaccine: "Rabies".to_string(),
+            administered_timestamp: 1_700_000_000,
+            due_timestamp: Some(1_731_536_000),
+            recorded_by: "staff".to_string(),
+            contact_id: Some("vet".to_string()),
+        })
+        .unwrap();
+        db.insert_animal_note(&AnimalNote {
+            id: "n1".to_string(),
+            animal_id: "a1".to_string(),
+            author_username: "staff".to_string(),
+            category: NoteCategory::Medical,
+            content: "Allergic to chicken".to_string(),
+            internal: true,
+            created_timestamp: 1_700_000_000,
+        })
+        .unwrap();
+
+        // The animal's records leave with it, and come back when it is restored
+        assert!(db.delete_animal("a1", true).unwrap());
+        assert!(db.query_vaccinations_by_animal_id("a1").unwrap().is_empty());
+        assert!(db.delete_contact("vet").unwrap());
+        assert!(db.restore_from_trash(TrashItemType::Animal, "a1").unwrap());
+
+        let vaccinations = db.query_vaccinations_by_animal_id("a1").unwrap();
+        assert_eq!(vaccinations.len(), 1);
+        assert_eq!(vaccinations[0].vaccine, "Rabies");
+        assert_eq!(vaccinations[0].due_timestamp, Some(1_731_536_000));
+        // The contact was deleted in the meantime, so the vaccination no longer points to it
+        assert_eq!(vaccinations[0].contact_id, None);
+        let notes = db.query_animal_notes_by_animal_id("a1", true).unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].content, "Allergic to chicken");
+        assert!(notes[0].internal);
+
+        // Records of purged animals are purged with them
+        assert!(db.delete_animal("a1", true).unwrap());
+        let later = Utc::now().timestamp() + 31 * 24 * 60 * 60;
+        db.purge_expired_trash(later).unwrap();
+        let remaining: i64 = db
+            .connection
+            .query_row("SELECT COUNT(*) FROM trashed_animal_records", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(remaining, 0);
+    }
+
+    #[test]
+    fn test_query_adoption_requests_with_filters() {
+        let db = create_test_db("test_query_adoption_requests_with_filters");
+        let now = Utc::now().timestamp();
+        let mut cat = sample_animal("a2");
+        cat.specie = "Cat".to_string();
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_animal(&cat).unwrap();
+
+        let mut old_request = sample_request("r1", "a1");
+        old_request.request_timestamp = now - 86400 * 10;
+        let mut approved = sample_request("r2", "a2");
+        approved.status = RequestStatus::Approved;
+        db.insert_adoption_request(&old_request).unwrap();
+        db.insert_adoption_request(&approved).unwrap();
+        db.insert_adoption_request(&sample_request("r3", "a2"))
+            .unwrap();
+
+        // No filters lists everything, newest first, with the animal's details
+        let all = db
+            .query_adoption_requests(&AdoptionRequestFilters::default())
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[2].id, "r1");
+        assert_eq!(all[2].animal_name, "Buddy");
+        assert_eq!(all[2].animal_specie, "Dog");
+
+        let by_status = db
+            .query_adoption_requests(&AdoptionRequestFilters {
+                status: Some(RequestStatus::Pending),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(by_status.len(), 2);
+
+        let recent_cats = db
+            .query_adoption_requests(&AdoptionRequestFilters {
+                status: Some(RequestStatus::Pending),
+                from_timestamp: Some(now - 86400),
+                to_timestamp: Some(now + 86400),
+                specie: Some("Cat".to_string()),
+            })
+            .unwrap();
+        assert_eq!(recent_cats.len(), 1);
+        assert_eq!(recent_cats[0].id, "r3");
+
+        let old = db
+            .query_adoption_requests(&AdoptionRequestFilters {
+                to_timestamp: Some(now - 86400),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(old.len(), 1);
+        assert_eq!(old[0].id, "r1");
+    }
+
+    #[test]
+    fn test_query_adoption_requests_with_animals() {
+        let db = create_test_db("test_query_adoption_requests_with_animals");
+        let mut cat = sample_animal("a2");
+        cat.name = "Lucy".to_string();
+        cat.sex = "Female".to_string();
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_animal(&cat).unwrap();
+
+        let mut rejected = sample_request("r3", "a1");
+        rejected.status = RequestStatus::Rejected;
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+        db.insert_adoption_request(&sample_request("r2", "a2"))
+            .unwrap();
+        db.insert_adoption_request(&rejected).unwrap();
+
+        // Each request comes with its animal's summary
+        let pending = db
+            .query_adoption_requests_with_animals(None, Some(RequestStatus::Pending))
+            .unwrap();
+        assert_eq!(pending.len(), 2);
+        for entry in &pending {
+            assert_eq!(entry.animal.id, entry.request.animal_id);
+        }
+        let lucy = pending.iter().find(|e| e.request.id == "r2").unwrap();
+        assert_eq!(lucy.animal.name, "Lucy");
+        assert_eq!(
+            lucy.animal.image_path.as_deref(),
+            Some("/test/images/buddy.jpg")
+        );
+
+        // Animal filters apply to the joined animals, even where column names overlap
+        let mut filters = HashMap::new();
+        filters.insert(
+            FilterCriteria::Sex,
+            Some(FilterValue::ChooseMany(vec!["Male".to_string()])),
+        );
+        filters.insert(
+            FilterCriteria::Status,
+            Some(FilterValue::ChooseMany(vec!["available".to_string()])),
+        );
+        let males = db
+            .query_adoption_requests_with_animals(Some(filters), None)
+            .unwrap();
+        assert_eq!(males.len(), 2);
+        assert!(males.iter().all(|e| e.animal.id == "a1"));
+    }
+
     #[test]
     fn test_query_adoption_requests_by_animal_id() {
         let db = create_test_db("test_query_adoption_requests_by_animal_id");
@@ -437,7 +1413,8 @@ mod database_service_tests {
         db.insert_animal(&animal2).unwrap();
 
         let request1 = sample_request("r1", "a1");
-        let request2 = sample_request("r2", "a1"); // Another request for animal1
+        let mut request2 = sample_request("r2", "a1"); // Another request for animal1
+        request2.username = "NonPrajogo".to_string();
         let request3 = sample_request("r3", "a2");
 
         db.insert_adoption_request(&request1).unwrap();
@@ -464,4 +1441,2945 @@ mod database_service_tests {
             .unwrap();
         assert_eq!(requests_for_nonexistent.len(), 0);
     }
+
+    #[test]
+    fn test_redacted_adoption_request() {
+        let mut request = sample_request("r1", "a1");
+        request.status = RequestStatus::Approved;
+        request.adoption_timestamp = request.request_timestamp + 100;
+
+        let redacted = request.redacted();
+        assert_eq!(redacted.id, "r1");
+        assert_eq!(redacted.animal_id, "a1");
+        assert_eq!(redacted.status, RequestStatus::Approved);
+        assert_eq!(redacted.adoption_timestamp, request.adoption_timestamp);
+
+        // The redacted view carries none of the requester's personal details
+        let json = serde_json::to_value(AdoptionRequestView::Redacted(redacted)).unwrap();
+        let fields: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                "adoptionTimestamp",
+                "animalId",
+                "id",
+                "requestTimestamp",
+                "status"
+            ]
+        );
+
+        // The full view serializes exactly like the request itself
+        let full =
+            serde_json::to_value(AdoptionRequestView::Full(Box::new(request.clone()))).unwrap();
+        assert_eq!(full, serde_json::to_value(&request).unwrap());
+    }
+
+    #[test]
+    fn test_process_return() {
+        let db = create_test_db("test_process_return");
+        let mut animal = sample_animal("a1");
+        animal.status = AnimalStatus::Adopted;
+        db.insert_animal(&animal).unwrap();
+
+        let pending = sample_request("r1", "a1");
+        db.insert_adoption_request(&pending).unwrap();
+        assert_eq!(
+            db.process_return("r1", "Allergies").unwrap(),
+            ProcessReturnResult::RequestNotApproved
+        );
+        assert_eq!(
+            db.process_return("missing", "Allergies").unwrap(),
+            ProcessReturnResult::RequestNotFound
+        );
+
+        db.approve_adoption_request("r1", false, None, &ApprovalRequirements::default())
+            .unwrap();
+
+        // Returning reopens the animal and keeps the adoption as history
+        assert_eq!(
+            db.process_return("r1", " Landlord does not allow pets ")
+                .unwrap(),
+            ProcessReturnResult::Success
+        );
+        let animal = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(animal.status, AnimalStatus::Available);
+        let request = db.query_adoption_request_by_id("r1").unwrap().unwrap();
+        assert_eq!(request.status, RequestStatus::Approved);
+
+        let returns = db.query_adoption_returns_by_animal_id("a1").unwrap();
+        assert_eq!(returns.len(), 1);
+        assert_eq!(returns[0].adoption_request_id, "r1");
+        assert_eq!(returns[0].reason, "Landlord does not allow pets");
+
+        // The same adoption cannot be returned twice
+        assert_eq!(
+            db.process_return("r1", "Again").unwrap(),
+            ProcessReturnResult::AlreadyReturned
+        );
+
+        // Returns are removed along with the animal on a cascading delete
+        assert!(db.delete_animal("a1", true).unwrap());
+        assert!(db
+            .query_adoption_returns_by_animal_id("a1")
+            .unwrap()
+            .is_empty());
+    }
+
+    // ==================== ANIMAL NOTES TESTS ====================
+
+    #[test]
+    fn test_animal_notes() {
+        let db = create_test_db("test_animal_notes");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        let now = Utc::now().timestamp();
+
+        let public_note = AnimalNote {
+            id: "n1".to_string(),
+            animal_id: "a1".to_string(),
+            author_username: "staff1".to_string(),
+            category: NoteCategory::Behavior,
+            content: "Loves belly rubs".to_string(),
+            internal: false,
+            created_timestamp: now - 60,
+        };
+        let internal_note = AnimalNote {
+            id: "n2".to_string(),
+            category: NoteCategory::Medical,
+            content: "Watch for limping on the left hind leg".to_string(),
+            internal: true,
+            created_timestamp: now,
+            ..public_note.clone()
+        };
+        db.insert_animal_note(&public_note).unwrap();
+        db.insert_animal_note(&internal_note).unwrap();
+
+        // Staff see every note, most recent first
+        let notes = db.query_animal_notes_by_animal_id("a1", true).unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].id, "n2");
+        assert_eq!(notes[0].category, NoteCategory::Medical);
+
+        // Internal notes are hidden from everyone else
+        let notes = db.query_animal_notes_by_animal_id("a1", false).unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].id, "n1");
+
+        // Blank notes are rejected
+        let mut blank = public_note.clone();
+        blank.id = "n3".to_string();
+        blank.content = "   ".to_string();
+        assert!(db.insert_animal_note(&blank).is_err());
+
+        assert!(db.delete_animal_note("n2").unwrap());
+        assert!(!db.delete_animal_note("n2").unwrap());
+        assert_eq!(
+            db.query_animal_notes_by_animal_id("a1", true)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    // ==================== FOSTER PLACEMENTS TESTS ====================
+
+    #[test]
+    fn test_foster_placements() {
+        let db = create_test_db("test_foster_placements");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+
+        // Unknown animals cannot be fostered
+        let result = db
+            .start_foster_placement(&sample_placement("f0", "missing"))
+            .unwrap();
+        assert_eq!(result, StartFosterResult::AnimalNotFound);
+
+        // Starting a placement marks the animal as fostered
+        let placement = sample_placement("f1", "a1");
+        let result = db.start_foster_placement(&placement).unwrap();
+        assert_eq!(result, StartFosterResult::Success);
+        let animal = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(animal.status, AnimalStatus::Fostered);
+        assert_eq!(animal.version, 2);
+
+        // Editing the animal can neither end the placement nor start one
+        let mut edited = animal.clone();
+        edited.status = AnimalStatus::Available;
+        let error = db.update_animal(&edited).unwrap_err();
+        let validation = error.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(validation.fields[0].field, "status");
+        let mut fostered = sample_animal("a9");
+        fostered.status = AnimalStatus::Fostered;
+        assert!(db.insert_animal(&fostered).is_err());
+
+        // A fostered animal cannot be placed again
+        let result = db
+            .start_foster_placement(&sample_placement("f2", "a1"))
+            .unwrap();
+        assert_eq!(result, StartFosterResult::AnimalNotAvailable);
+
+        // Extending moves the planned end, but never before the start
+        let new_end = placement.end_timestamp + 14 * 86_400;
+        assert!(db.extend_foster_placement("f1", new_end).unwrap());
+        assert!(!db
+            .extend_foster_placement("f1", placement.start_timestamp - 1)
+            .unwrap());
+
+        // Ending the placement makes the animal available again
+        assert!(db.end_foster_placement("f1").unwrap());
+        assert!(!db.end_foster_placement("f1").unwrap());
+        assert!(!db.extend_foster_placement("f1", new_end + 1).unwrap());
+        let animal = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(animal.status, AnimalStatus::Available);
+
+        let placements = db.query_foster_placements_by_animal_id("a1").unwrap();
+        assert_eq!(placements.len(), 1);
+        assert_eq!(placements[0].end_timestamp, new_end);
+        assert!(placements[0].ended_timestamp.is_some());
+
+        // Placements are kept as history unless the deletion cascades
+        assert!(db.delete_animal("a1", false).is_err());
+        assert!(db.delete_animal("a1", true).unwrap());
+        assert!(db
+            .query_foster_placements_by_animal_id("a1")
+            .unwrap()
+            .is_empty());
+    }
+
+    // ==================== DECEASED RECORDS TESTS ====================
+
+    #[test]
+    fn test_record_death() {
+        let db = create_test_db("test_record_death");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+        db.start_foster_placement(&sample_placement("f1", "a1"))
+            .unwrap();
+
+        // Editing the animal cannot skip the record
+        let mut animal = db.query_animal_by_id("a1").unwrap().unwrap();
+        animal.status = AnimalStatus::PassedAway;
+        let error = db.update_animal(&animal).unwrap_err();
+        let validation = error.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(validation.fields[0].field, "status");
+
+        // The cause and who authorized it are required
+        let mut record = DeceasedRecord {
+            id: String::new(),
+            animal_id: "a1".to_string(),
+            death_timestamp: Utc::now().timestamp(),
+            cause: " ".to_string(),
+            authorized_by: String::new(),
+            notes: String::new(),
+        };
+        let error = db.record_death(&record).unwrap_err();
+        let validation = error.downcast_ref::<ValidationError>().unwrap();
+        let fields: Vec<&str> = validation.fields.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["cause", "authorizedBy"]);
+
+        record.cause = " Kidney failure ".to_string();
+        record.authorized_by = "Dr. Somchai".to_string();
+        record.animal_id = "missing".to_string();
+        assert_eq!(
+            db.record_death(&record).unwrap(),
+            RecordDeathResult::AnimalNotFound
+        );
+
+        // Recording the death ends the placement and closes the animal's requests,
+        // emailing their applicants
+        record.animal_id = "a1".to_string();
+        assert_eq!(
+            db.record_death(&record).unwrap(),
+            RecordDeathResult::Success
+        );
+        let animal = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(animal.status, AnimalStatus::PassedAway);
+        let request = db.query_adoption_request_by_id("r1").unwrap().unwrap();
+        assert_eq!(request.status, RequestStatus::Rejected);
+        let emails = db.query_emails().unwrap();
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].request_id, "r1");
+        assert_eq!(emails[0].request_status, RequestStatus::Rejected);
+        let placements = db.query_foster_placements_by_animal_id("a1").unwrap();
+        assert!(placements[0].ended_timestamp.is_some());
+        assert_eq!(
+            db.record_death(&record).unwrap(),
+            RecordDeathResult::AlreadyDeceased
+        );
+
+        // Editing the animal cannot bring it back, nor can it be created as passed away
+        let mut revived = animal.clone();
+        revived.status = AnimalStatus::Available;
+        let error = db.update_animal(&revived).unwrap_err();
+        let validation = error.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(validation.fields[0].field, "status");
+        let mut deceased = sample_animal("a3");
+        deceased.status = AnimalStatus::PassedAway;
+        let error = db.insert_animal(&deceased).unwrap_err();
+        let validation = error.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(validation.fields[0].field, "status");
+
+        // Staff see the record on the animal's detail, others do not
+        let detail = db.query_animal_detail("a1", true).unwrap().unwrap();
+        let deceased = detail.deceased_record.unwrap();
+        assert_eq!(deceased.cause, "Kidney failure");
+        assert_eq!(deceased.authorized_by, "Dr. Somchai");
+        let detail = db.query_animal_detail("a1", false).unwrap().unwrap();
+        assert!(detail.deceased_record.is_none());
+
+        // Animals no longer in the shelter's care cannot be recorded
+        let mut adopted = sample_animal("a2");
+        adopted.status = AnimalStatus::Adopted;
+        db.insert_animal(&adopted).unwrap();
+        record.animal_id = "a2".to_string();
+        assert_eq!(
+            db.record_death(&record).unwrap(),
+            RecordDeathResult::AnimalNotInCare
+        );
+    }
+
+    // ==================== INTAKE RECORDS TESTS ====================
+
+    #[test]
+    fn test_intake_records() {
+        let db = create_test_db("test_intake_records");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        let now = Utc::now().timestamp();
+
+        let stray = IntakeRecord {
+            id: String::new(),
+            animal_id: "a1".to_string(),
+            intake_type: IntakeType::Stray,
+            source: "Found near Chatuchak Market".to_string(),
+            condition: "Thin, minor cut on left paw".to_string(),
+            staff_username: "staff1".to_string(),
+            intake_timestamp: now - 86_400,
+            hold_until_timestamp: None,
+        };
+        db.insert_intake_record(&stray, 5).unwrap();
+
+        // The same animal can come back in later through a different route
+        let mut surrender = stray.clone();
+        surrender.intake_type = IntakeType::OwnerSurrender;
+        surrender.source = "Owner moving abroad".to_string();
+        surrender.intake_timestamp = now;
+        db.insert_intake_record(&surrender, 5).unwrap();
+
+        let history = db.query_intake_records_by_animal_id("a1").unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].intake_type, IntakeType::OwnerSurrender);
+        assert_eq!(history[1].intake_type, IntakeType::Stray);
+        assert!(!history[0].id.is_empty());
+
+        // Only strays are put on hold
+        let hold_until = now + 4 * 86_400;
+        assert_eq!(history[0].hold_until_timestamp, None);
+        assert_eq!(history[1].hold_until_timestamp, Some(hold_until));
+
+        // A staff member is required, and the animal must exist
+        let mut anonymous = stray.clone();
+        anonymous.staff_username = " ".to_string();
+        assert!(db.insert_intake_record(&anonymous, 5).is_err());
+        let mut unknown = stray.clone();
+        unknown.animal_id = "missing".to_string();
+        assert!(db.insert_intake_record(&unknown, 5).is_err());
+    }
+
+    #[test]
+    fn test_stray_hold() {
+        let db = create_test_db("test_stray_hold");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_animal(&sample_animal("a2")).unwrap();
+        db.insert_animal(&sample_animal("a3")).unwrap();
+        let now = Utc::now().timestamp();
+
+        let stray = IntakeRecord {
+            id: String::new(),
+            animal_id: "a1".to_string(),
+            intake_type: IntakeType::Stray,
+            source: "Found near Lumphini Park".to_string(),
+            condition: "Healthy".to_string(),
+            staff_username: "staff1".to_string(),
+            intake_timestamp: now - 86_400,
+            hold_until_timestamp: None,
+        };
+        db.insert_intake_record(&stray, 3).unwrap();
+        let mut long_hold = stray.clone();
+        long_hold.animal_id = "a2".to_string();
+        db.insert_intake_record(&long_hold, 30).unwrap();
+        let mut expired = stray.clone();
+        expired.animal_id = "a3".to_string();
+        expired.intake_timestamp = now - 10 * 86_400;
+        db.insert_intake_record(&expired, 3).unwrap();
+
+        // A stray cannot be adopted before its hold is over
+        assert_eq!(
+            db.query_stray_hold_until("a1").unwrap(),
+            Some(now + 2 * 86_400)
+        );
+        assert_eq!(db.query_stray_hold_until("a3").unwrap(), None);
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+        db.insert_adoption_request(&sample_request("r3", "a3"))
+            .unwrap();
+        assert_eq!(
+            db.approve_adoption_request("r1", false, None, &ApprovalRequirements::default())
+                .unwrap(),
+            ApproveRequestResult::OnStrayHold
+        );
+        let request = db.query_adoption_request_by_id("r1").unwrap().unwrap();
+        assert_eq!(request.status, RequestStatus::Pending);
+        assert_eq!(
+            db.approve_adoption_request("r3", false, None, &ApprovalRequirements::default())
+                .unwrap(),
+            ApproveRequestResult::Approved
+        );
+
+        // Only the holds ending within the range are reported
+        let holds = db.query_stray_holds_ending(now, now + 7 * 86_400).unwrap();
+        assert_eq!(holds.len(), 1);
+        assert_eq!(holds[0].animal_id, "a1");
+        assert_eq!(holds[0].intake_timestamp, now - 86_400);
+        assert_eq!(holds[0].hold_until_timestamp, now + 2 * 86_400);
+    }
+
+    // ==================== KENNELS TESTS ====================
+
+    #[test]
+    fn test_kennel_assignments() {
+        let db = create_test_db("test_kennel_assignments");
+        for id in ["a1", "a2"] {
+            db.insert_animal(&sample_animal(id)).unwrap();
+        }
+        let mut cat = sample_animal("c1");
+        cat.specie = "Cat".to_string();
+        db.insert_animal(&cat).unwrap();
+
+        let dog_run = Kennel {
+            id: "k1".to_string(),
+            name: "Dog Run".to_string(),
+            specie_restriction: Some("Dog".to_string()),
+            capacity: 1,
+        };
+        let shared = Kennel {
+            id: "k2".to_string(),
+            name: "Shared Room".to_string(),
+            specie_restriction: None,
+            capacity: 2,
+        };
+        db.insert_kennel(&dog_run).unwrap();
+        db.insert_kennel(&shared).unwrap();
+
+        // Zero capacity kennels are rejected
+        let mut empty = shared.clone();
+        empty.id = "k3".to_string();
+        empty.name = "Closet".to_string();
+        empty.capacity = 0;
+        assert!(db.insert_kennel(&empty).is_err());
+
+        // Refusals for unknown records, species restrictions, and capacity
+        assert_eq!(
+            db.assign_animal_to_kennel("missing", "k1").unwrap(),
+            AssignKennelResult::AnimalNotFound
+        );
+        assert_eq!(
+            db.assign_animal_to_kennel("a1", "missing").unwrap(),
+            AssignKennelResult::KennelNotFound
+        );
+        assert_eq!(
+            db.assign_animal_to_kennel("c1", "k1").unwrap(),
+            AssignKennelResult::SpecieNotAllowed
+        );
+        assert_eq!(
+            db.assign_animal_to_kennel("a1", "k1").unwrap(),
+            AssignKennelResult::Success
+        );
+        assert_eq!(
+            db.assign_animal_to_kennel("a2", "k1").unwrap(),
+            AssignKennelResult::KennelFull
+        );
+
+        // Re-assigning an animal to its own full kennel is allowed
+        assert_eq!(
+            db.assign_animal_to_kennel("a1", "k1").unwrap(),
+            AssignKennelResult::Success
+        );
+
+        // Moving an animal frees its previous kennel
+        assert_eq!(
+            db.assign_animal_to_kennel("a1", "k2").unwrap(),
+            AssignKennelResult::Success
+        );
+        assert_eq!(
+            db.assign_animal_to_kennel("a2", "k1").unwrap(),
+            AssignKennelResult::Success
+        );
+
+        let occupancy = db.query_kennel_occupancy().unwrap();
+        assert_eq!(occupancy.len(), 2);
+        assert_eq!(occupancy[0].kennel.name, "Dog Run");
+        assert_eq!(occupancy[0].animal_ids, vec!["a2".to_string()]);
+        assert_eq!(occupancy[1].animal_ids, vec!["a1".to_string()]);
+
+        // Removing an animal empties its kennel
+        assert!(db.remove_animal_from_kennel("a1").unwrap());
+        assert!(!db.remove_animal_from_kennel("a1").unwrap());
+        let occupancy = db.query_kennel_occupancy().unwrap();
+        assert!(occupancy[1].animal_ids.is_empty());
+    }
+
+    #[test]
+    fn test_capacity_report() {
+        let db = create_test_db("test_capacity_report");
+
+        // Without kennels there is nothing to be full
+        let report = db.query_capacity_report().unwrap();
+        assert_eq!(report.total_capacity, 0);
+        assert_eq!(report.utilization_percent, None);
+        assert!(report.species.is_empty());
+
+        for (id, specie, capacity) in [
+            ("k1", Some("Dog"), 2),
+            ("k2", Some("Cat"), 3),
+            ("k3", None, 2),
+        ] {
+            db.insert_kennel(&Kennel {
+                id: id.to_string(),
+                name: id.to_string(),
+                specie_restriction: specie.map(String::from),
+                capacity,
+            })
+            .unwrap();
+        }
+        for id in ["a1", "a2", "a3"] {
+            db.insert_animal(&sample_animal(id)).unwrap();
+        }
+        let mut adopted = sample_animal("a4");
+        adopted.status = AnimalStatus::Adopted;
+        db.insert_animal(&adopted).unwrap();
+        let mut cat = sample_animal("c1");
+        cat.specie = "Cat".to_string();
+        db.insert_animal(&cat).unwrap();
+        let mut rabbit = sample_animal("r1");
+        rabbit.specie = "Rabbit".to_string();
+        rabbit.status = AnimalStatus::Requested;
+        db.insert_animal(&rabbit).unwrap();
+        db.start_foster_placement(&sample_placement("f1", "a3"))
+            .unwrap();
+
+        let report = db.query_capacity_report().unwrap();
+        assert_eq!(report.total_capacity, 7);
+        assert_eq!(report.shared_capacity, 2);
+        assert_eq!(report.in_shelter_count, 4);
+        assert_eq!(report.foster_count, 1);
+        assert_eq!(report.utilization_percent, Some(400.0 / 7.0));
+
+        let species: Vec<(&str, i64, i64, i64, Option<f64>)> = report
+            .species
+            .iter()
+            .map(|s| {
+                (
+                    s.specie.as_str(),
+                    s.dedicated_capacity,
+                    s.in_shelter_count,
+                    s.foster_count,
+                    s.utilization_percent,
+                )
+            })
+            .collect();
+        // Species without kennels of their own can still use the shared ones
+        assert_eq!(
+            species,
+            vec![
+                ("Cat", 3, 1, 0, Some(20.0)),
+                ("Dog", 2, 2, 1, Some(50.0)),
+                ("Rabbit", 0, 1, 0, Some(50.0)),
+            ]
+        );
+    }
+
+    // ==================== FAVORITES TESTS ====================
+
+    #[test]
+    fn test_locations() {
+        let db = create_test_db("test_locations");
+        let north = db
+            .insert_location(&Location {
+                id: String::new(),
+                name: "North Site".to_string(),
+                address: "1 North Road".to_string(),
+            })
+            .unwrap();
+        let south = db
+            .insert_location(&Location {
+                id: "south".to_string(),
+                name: "South Site".to_string(),
+                address: "2 South Road".to_string(),
+            })
+            .unwrap();
+        let locations = db.query_locations().unwrap();
+        assert_eq!(locations.len(), 2);
+        assert_eq!(locations[0].id, north);
+
+        let mut north_animal = sample_animal("a1");
+        north_animal.location_id = Some(north.clone());
+        let mut south_animal = sample_animal("a2");
+        south_animal.location_id = Some(south.clone());
+        south_animal.specie = "Cat".to_string();
+        db.insert_animal(&north_animal).unwrap();
+        db.insert_animal(&south_animal).unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a2"))
+            .unwrap();
+        assert_eq!(
+            db.query_animal_by_id("a1").unwrap().unwrap().location_id,
+            Some(north.clone())
+        );
+
+        // Animals can be filtered by location
+        let mut filters = HashMap::new();
+        filters.insert(
+            FilterCriteria::Location,
+            Some(FilterValue::ChooseMany(vec![south.clone()])),
+        );
+        let animals = db.query_animals(Some(filters)).unwrap();
+        assert_eq!(animals.len(), 1);
+        assert_eq!(animals[0].id, "a2");
+
+        // Statistics are scoped to a location when one is given
+        let statistics = db.query_shelter_statistics(Some(&north)).unwrap();
+        assert_eq!(statistics.animals_by_specie.len(), 1);
+        assert_eq!(statistics.animals_by_specie[0].label, "Dog");
+        assert_eq!(statistics.pending_request_count, 0);
+        let statistics = db.query_shelter_statistics(Some(&south)).unwrap();
+        assert_eq!(statistics.pending_request_count, 1);
+        let statistics = db.query_shelter_statistics(None).unwrap();
+        assert_eq!(statistics.animals_by_specie.len(), 2);
+
+        // A location housing animals cannot be deleted
+        assert!(db.delete_location(&north).is_err());
+        db.delete_animal("a1", true).unwrap();
+        assert!(db.delete_location(&north).unwrap());
+        assert!(!db.delete_location(&north).unwrap());
+        assert!(db.restore_from_trash(TrashItemType::Animal, "a1").unwrap());
+        assert_eq!(
+            db.query_animal_by_id("a1").unwrap().unwrap().location_id,
+            None
+        );
+    }
+
+    #[test]
+    fn test_inventory() {
+        let db = create_test_db("test_inventory");
+        let mut food = InventoryItem {
+            id: "food".to_string(),
+            name: " Dry dog food ".to_string(),
+            unit: "kg".to_string(),
+            quantity_on_hand: 40.0,
+            reorder_threshold: 15.0,
+        };
+        db.insert_inventory_item(&food).unwrap();
+        db.insert_inventory_item(&InventoryItem {
+            id: "dewormer".to_string(),
+            name: "Dewormer".to_string(),
+            unit: "tablets".to_string(),
+            quantity_on_hand: 10.0,
+            reorder_threshold: 20.0,
+        })
+        .unwrap();
+
+        // Negative quantities and blank units are rejected
+        let error = db
+            .insert_inventory_item(&InventoryItem {
+                id: String::new(),
+                name: "Litter".to_string(),
+                unit: " ".to_string(),
+                quantity_on_hand: -1.0,
+                reorder_threshold: 0.0,
+            })
+            .unwrap_err();
+        let validation = error.downcast_ref::<ValidationError>().unwrap();
+        let fields: Vec<&str> = validation.fields.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["unit", "quantityOnHand"]);
+
+        let items = db.query_inventory_items().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].name, "Dry dog food");
+        let low_stock = db.query_low_stock_items().unwrap();
+        assert_eq!(low_stock.len(), 1);
+        assert_eq!(low_stock[0].id, "dewormer");
+
+        // Using stock below the threshold puts the item on the report
+        match db
+            .adjust_stock("food", -30.0, "Weekly feeding", "staff")
+            .unwrap()
+        {
+            AdjustStockResult::Adjusted(item) => assert_eq!(item.quantity_on_hand, 10.0),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        let low_stock = db.query_low_stock_items().unwrap();
+        assert_eq!(low_stock.len(), 2);
+        assert_eq!(low_stock[0].id, "dewormer");
+
+        // Stock cannot go below zero, and every change needs a reason
+        match db
+            .adjust_stock("food", -11.0, "Weekly feeding", "staff")
+            .unwrap()
+        {
+            AdjustStockResult::InsufficientStock(item) => assert_eq!(item.quantity_on_hand, 10.0),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert!(db.adjust_stock("food", 5.0, " ", "staff").is_err());
+        assert!(db.adjust_stock("food", 0.0, "Count", "staff").is_err());
+        assert_eq!(
+            db.adjust_stock("missing", 5.0, "Delivery", "staff")
+                .unwrap(),
+            AdjustStockResult::NotFound
+        );
+
+        db.adjust_stock("food", 25.0, " Delivery ", "staff")
+            .unwrap();
+        let adjustments = db.query_inventory_adjustments("food").unwrap();
+        assert_eq!(adjustments.len(), 2);
+        assert_eq!(adjustments[0].reason, "Delivery");
+        assert_eq!(adjustments[0].quantity_change, 25.0);
+
+        // Updating an item leaves its stock alone
+        food.reorder_threshold = 40.0;
+        food.quantity_on_hand = 0.0;
+        assert!(db.update_inventory_item(&food).unwrap());
+        let items = db.query_inventory_items().unwrap();
+        assert_eq!(items[1].quantity_on_hand, 35.0);
+        assert_eq!(db.query_low_stock_items().unwrap().len(), 2);
+
+        assert!(db.delete_inventory_item("food").unwrap());
+        assert!(!db.delete_inventory_item("food").unwrap());
+        assert!(db.query_inventory_adjustments("food").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_expenses() {
+        let db = create_test_db("test_expenses");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        let expense = |id: &str, category: ExpenseCategory, amount: i64, timestamp: i64| Expense {
+            id: id.to_string(),
+            category,
+            amount,
+            expense_timestamp: timestamp,
+            vendor: "Pet Supplies Co.".to_string(),
+            animal_id: None,
+        };
+        // 2024-01-15, 2024-02-10 and 2023-12-31 23:00 UTC
+        db.insert_expense(&expense("e1", ExpenseCategory::Food, 12050, 1705276800))
+            .unwrap();
+        db.insert_expense(&expense("e2", ExpenseCategory::Food, 7950, 1705276800))
+            .unwrap();
+        let mut surgery = expense("e3", ExpenseCategory::Veterinary, 35000, 1707523200);
+        surgery.animal_id = Some("a1".to_string());
+        db.insert_expense(&surgery).unwrap();
+        db.insert_expense(&expense("e4", ExpenseCategory::Other, 1000, 1704063600))
+            .unwrap();
+
+        // Amounts must be positive and the vendor is required
+        let mut invalid = expense("", ExpenseCategory::Supplies, 0, 1705276800);
+        invalid.vendor = " ".to_string();
+        let error = db.insert_expense(&invalid).unwrap_err();
+        let validation = error.downcast_ref::<ValidationError>().unwrap();
+        let fields: Vec<&str> = validation.fields.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["amount", "vendor"]);
+
+        let expenses = db.query_expenses(1704067200, 1735689600).unwrap();
+        assert_eq!(expenses.len(), 3);
+        assert_eq!(expenses[0].id, "e3");
+
+        // Each month of the year is reported, with its categories
+        let report = db.query_budget_report(2024).unwrap();
+        assert_eq!(report.months.len(), 12);
+        assert_eq!(report.total, 55000);
+        assert_eq!(report.months[0].total, 20000);
+        assert_eq!(report.months[0].by_category.len(), 1);
+        assert_eq!(
+            report.months[0].by_category[0].category,
+            ExpenseCategory::Food
+        );
+        assert_eq!(
+            report.months[1].by_category[0].category,
+            ExpenseCategory::Veterinary
+        );
+        assert!(report.months[2].by_category.is_empty());
+        assert_eq!(db.query_budget_report(2023).unwrap().total, 1000);
+
+        // Donations are set against the expenses, month by month and for the year
+        assert_eq!(report.donation_total, 0);
+        assert_eq!(report.net, -55000);
+        db.insert_contact(&Contact {
+            id: "donor".to_string(),
+            name: "Jane Doe".to_string(),
+            kind: ContactKind::Donor,
+            tel_number: "0812345678".to_string(),
+            email: String::new(),
+            address: String::new(),
+            notes: String::new(),
+        })
+        .unwrap();
+        for (amount, timestamp) in [(50000, 1705276800), (10000, 1707523200)] {
+            db.insert_donation(&Donation {
+                id: String::new(),
+                contact_id: Some("donor".to_string()),
+                donor_name: String::new(),
+                amount,
+                donation_timestamp: timestamp,
+                notes: String::new(),
+                receipt_number: None,
+            })
+            .unwrap();
+        }
+        let report = db.query_budget_report(2024).unwrap();
+        assert_eq!(report.months[0].donations, 50000);
+        assert_eq!(report.months[0].net, 30000);
+        assert_eq!(report.months[1].net, -25000);
+        assert_eq!(report.months[2].net, 0);
+        assert_eq!(report.donation_total, 60000);
+        assert_eq!(report.net, 5000);
+
+        surgery.amount = 40000;
+        assert!(db.update_expense(&surgery).unwrap());
+        assert_eq!(db.query_budget_report(2024).unwrap().total, 60000);
+        assert!(db.delete_expense("e4").unwrap());
+        assert!(!db.delete_expense("e4").unwrap());
+
+        // Deleting the animal keeps its expenses
+        db.delete_animal("a1", false).unwrap();
+        let expenses = db.query_expenses(1707523200, 1707523201).unwrap();
+        assert_eq!(expenses[0].animal_id, None);
+    }
+
+    #[test]
+    fn test_contacts() {
+        let db = create_test_db("test_contacts");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        let contact = |id: &str, name: &str, kind: ContactKind| Contact {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind,
+            tel_number: "0812345678".to_string(),
+            email: String::new(),
+            address: String::new(),
+            notes: String::new(),
+        };
+        db.insert_contact(&contact(
+            "vet",
+            "Riverside Clinic",
+            ContactKind::Veterinarian,
+        ))
+        .unwrap();
+        db.insert_contact(&contact("rescue", "Happy Paws", ContactKind::Rescue))
+            .unwrap();
+
+        let mut invalid = contact("", " ", ContactKind::Other);
+        invalid.email = "not an email".to_string();
+        let error = db.insert_contact(&invalid).unwrap_err();
+        let validation = error.downcast_ref::<ValidationError>().unwrap();
+        let fields: Vec<&str> = validation.fields.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["name", "email"]);
+
+        assert_eq!(db.query_contacts(None).unwrap().len(), 2);
+        let vets = db.query_contacts(Some(ContactKind::Veterinarian)).unwrap();
+        assert_eq!(vets.len(), 1);
+        assert_eq!(vets[0].name, "Riverside Clinic");
+
+        // Vaccinations and transfers can be linked when recorded or afterwards
+        db.insert_vaccination(&Vaccination {
+            id: "v1".to_string(),
+            animal_id: "a1".to_string(),
+            vaccine: "Rabies".to_string(),
+            administered_timestamp: 1_700_000_000,
+            due_timestamp: None,
+            recorded_by: "staff".to_string(),
+            contact_id: Some("vet".to_string()),
+        })
+        .unwrap();
+        db.record_transfer(&Transfer {
+            id: "t1".to_string(),
+            animal_id: "a1".to_string(),
+            direction: TransferDirection::Outbound,
+            from_location_id: None,
+            to_location_id: None,
+            partner_organization: Some("Happy Paws".to_string()),
+            reason: "Space".to_string(),
+            staff_username: "staff".to_string(),
+            transfer_timestamp: 1_700_000_000,
+            contact_id: None,
+        })
+        .unwrap();
+        assert!(db.link_transfer_to_contact("t1", Some("rescue")).unwrap());
+        assert!(!db
+            .link_transfer_to_contact("missing", Some("rescue"))
+            .unwrap());
+        assert!(db
+            .link_vaccination_to_contact("v1", Some("missing"))
+            .is_err());
+        assert_eq!(
+            db.query_transfers_by_animal_id("a1").unwrap()[0].contact_id,
+            Some("rescue".to_string())
+        );
+
+        // Deleting a contact keeps the records it took part in
+        assert!(db.delete_contact("vet").unwrap());
+        assert!(!db.delete_contact("vet").unwrap());
+        assert_eq!(
+            db.query_vaccinations_by_animal_id("a1").unwrap()[0].contact_id,
+            None
+        );
+    }
+
+    #[test]
+    fn test_appointments() {
+        let db = create_test_db("test_appointments");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        let mut adopted = sample_animal("a2");
+        adopted.status = AnimalStatus::Adopted;
+        db.insert_animal(&adopted).unwrap();
+        let slot = Utc::now().timestamp() + 7 * 24 * 60 * 60;
+
+        let error = db
+            .request_appointment("a1", "alice", Utc::now().timestamp() - 60)
+            .unwrap_err();
+        let validation = error.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(validation.fields[0].field, "slotTimestamp");
+        assert_eq!(
+            db.request_appointment("missing", "alice", slot).unwrap(),
+            RequestAppointmentResult::AnimalNotFound
+        );
+        assert_eq!(
+            db.request_appointment("a2", "alice", slot).unwrap(),
+            RequestAppointmentResult::AnimalNotAvailable
+        );
+
+        // The animal cannot be booked twice for overlapping slots
+        assert_eq!(
+            db.request_appointment("a1", "alice", slot).unwrap(),
+            RequestAppointmentResult::Requested
+        );
+        assert_eq!(
+            db.request_appointment("a1", "bob", slot + 10 * 60).unwrap(),
+            RequestAppointmentResult::SlotTaken
+        );
+        assert_eq!(
+            db.request_appointment("a1", "bob", slot + 30 * 60).unwrap(),
+            RequestAppointmentResult::Requested
+        );
+        let appointments = db.query_appointments(slot, slot + 60 * 60).unwrap();
+        assert_eq!(appointments.len(), 2);
+        assert_eq!(appointments[0].username, "alice");
+        assert_eq!(appointments[0].animal_name, "Buddy");
+        assert_eq!(appointments[0].status, AppointmentStatus::Requested);
+        let alice_id = appointments[0].id.clone();
+        let bob_id = appointments[1].id.clone();
+
+        // Confirming notifies the customer
+        let (result, notifications) = db.confirm_appointment(&alice_id).unwrap();
+        assert_eq!(result, ConfirmAppointmentResult::Confirmed);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].username, "alice");
+        assert_eq!(
+            db.confirm_appointment(&alice_id).unwrap().0,
+            ConfirmAppointmentResult::NotRequested
+        );
+        assert_eq!(
+            db.confirm_appointment("missing").unwrap().0,
+            ConfirmAppointmentResult::NotFound
+        );
+
+        // Customers can only cancel their own appointments, and are not notified of it
+        assert_eq!(
+            db.cancel_appointment(&alice_id, Some("bob")).unwrap().0,
+            CancelAppointmentResult::NotFound
+        );
+        let (result, notifications) = db.cancel_appointment(&bob_id, Some("bob")).unwrap();
+        assert_eq!(result, CancelAppointmentResult::Cancelled);
+        assert!(notifications.is_empty());
+        let (result, notifications) = db.cancel_appointment(&alice_id, None).unwrap();
+        assert_eq!(result, CancelAppointmentResult::Cancelled);
+        assert_eq!(notifications[0].username, "alice");
+        assert_eq!(
+            db.cancel_appointment(&alice_id, None).unwrap().0,
+            CancelAppointmentResult::AlreadyCancelled
+        );
+
+        // A cancelled appointment frees its slot
+        assert_eq!(
+            db.request_appointment("a1", "carol", slot).unwrap(),
+            RequestAppointmentResult::Requested
+        );
+        let mine = db.query_appointments_by_username("alice").unwrap();
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine[0].status, AppointmentStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_surgeries() {
+        let db = create_test_db("test_surgeries");
+        let mut animal = sample_animal("a1");
+        animal.neutered = false;
+        db.insert_animal(&animal).unwrap();
+        db.insert_animal(&sample_animal("a2")).unwrap();
+        let scheduled = Utc::now().timestamp() + 3 * 24 * 60 * 60;
+
+        assert_eq!(
+            db.schedule_surgery("missing", scheduled, None, "").unwrap(),
+            ScheduleSurgeryResult::AnimalNotFound
+        );
+        assert_eq!(
+            db.schedule_surgery("a2", scheduled, None, "").unwrap(),
+            ScheduleSurgeryResult::AlreadyNeutered
+        );
+        assert!(db.schedule_surgery("a1", 0, None, "").is_err());
+        assert_eq!(
+            db.schedule_surgery("a1", scheduled, None, "No food after midnight")
+                .unwrap(),
+            ScheduleSurgeryResult::Scheduled
+        );
+        assert_eq!(
+            db.schedule_surgery("a1", scheduled + 60, None, "").unwrap(),
+            ScheduleSurgeryResult::AlreadyScheduled
+        );
+
+        let upcoming = db.query_upcoming_surgeries().unwrap();
+        assert_eq!(upcoming.len(), 1);
+        assert_eq!(upcoming[0].animal_name, "Buddy");
+        assert_eq!(upcoming[0].completed_timestamp, None);
+        let surgery_id = upcoming[0].id.clone();
+
+        // Recording the surgery keeps the neutered flag accurate
+        let completed = Utc::now().timestamp();
+        assert!(db
+            .complete_surgery(&surgery_id, completed + 7 * 86_400)
+            .is_err());
+        match db.complete_surgery(&surgery_id, completed).unwrap() {
+            CompleteSurgeryResult::Completed(surgery) => {
+                assert_eq!(surgery.completed_timestamp, Some(completed))
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        let animal = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert!(animal.neutered);
+        assert_eq!(animal.version, 2);
+        assert_eq!(
+            db.complete_surgery(&surgery_id, completed).unwrap(),
+            CompleteSurgeryResult::AlreadyCompleted
+        );
+        assert_eq!(
+            db.complete_surgery("missing", completed).unwrap(),
+            CompleteSurgeryResult::NotFound
+        );
+        assert!(db.query_upcoming_surgeries().unwrap().is_empty());
+
+        // A performed surgery stays on record
+        assert!(!db.cancel_surgery(&surgery_id).unwrap());
+        assert_eq!(db.query_surgeries_by_animal_id("a1").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_record_transfer() {
+        let db = create_test_db("test_record_transfer");
+        let north = db
+            .insert_location(&Location {
+                id: String::new(),
+                name: "North Site".to_string(),
+                address: "1 North Road".to_string(),
+            })
+            .unwrap();
+        let south = db
+            .insert_location(&Location {
+                id: String::new(),
+                name: "South Site".to_string(),
+                address: "2 South Road".to_string(),
+            })
+            .unwrap();
+        let mut animal = sample_animal("a1");
+        animal.location_id = Some(north.clone());
+        db.insert_animal(&animal).unwrap();
+
+        let transfer = |direction, to_location_id: Option<&str>, partner: Option<&str>| Transfer {
+            id: String::new(),
+            animal_id: "a1".to_string(),
+            direction,
+            from_location_id: None,
+            to_location_id: to_location_id.map(str::to_string),
+            partner_organization: partner.map(str::to_string),
+            reason: "Space".to_string(),
+            staff_username: "staff".to_string(),
+            transfer_timestamp: 1_700_000_000,
+            contact_id: None,
+        };
+
+        // An outbound transfer must have exactly one destination
+        assert!(db
+            .record_transfer(&transfer(TransferDirection::Outbound, None, None))
+            .is_err());
+        assert!(db
+            .record_transfer(&transfer(
+                TransferDirection::Outbound,
+                Some(&south),
+                Some("Rescue")
+            ))
+            .is_err());
+        assert_eq!(
+            db.record_transfer(&transfer(
+                TransferDirection::Outbound,
+                Some("missing"),
+                None
+            ))
+            .unwrap(),
+            RecordTransferResult::LocationNotFound
+        );
+
+        // Moving between locations keeps the status
+        assert_eq!(
+            db.record_transfer(&transfer(TransferDirection::Outbound, Some(&south), None))
+                .unwrap(),
+            RecordTransferResult::Success
+        );
+        let moved = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(moved.status, AnimalStatus::Available);
+        assert_eq!(moved.location_id, Some(south.clone()));
+
+        // Handing the animal to a partner marks it as transferred
+        assert_eq!(
+            db.record_transfer(&transfer(TransferDirection::Outbound, None, Some("Rescue")))
+                .unwrap(),
+            RecordTransferResult::Success
+        );
+        let transferred = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(transferred.status, AnimalStatus::Transferred);
+        assert_eq!(transferred.location_id, None);
+        assert_eq!(
+            db.record_transfer(&transfer(TransferDirection::Outbound, Some(&north), None))
+                .unwrap(),
+            RecordTransferResult::AnimalNotAvailable
+        );
+
+        // The animal comes back from the partner
+        assert!(db
+            .record_transfer(&transfer(TransferDirection::Inbound, Some(&north), None))
+            .is_err());
+        assert_eq!(
+            db.record_transfer(&transfer(
+                TransferDirection::Inbound,
+                Some(&north),
+                Some("Rescue")
+            ))
+            .unwrap(),
+            RecordTransferResult::Success
+        );
+        let returned = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(returned.status, AnimalStatus::Available);
+        assert_eq!(returned.location_id, Some(north.clone()));
+
+        // Every transfer is kept with the location the animal left
+        let history = db.query_transfers_by_animal_id("a1").unwrap();
+        assert_eq!(history.len(), 3);
+        let from_locations: Vec<Option<String>> = history
+            .iter()
+            .map(|transfer| transfer.from_location_id.clone())
+            .collect();
+        assert!(from_locations.contains(&Some(north.clone())));
+        assert!(from_locations.contains(&Some(south.clone())));
+        assert!(from_locations.contains(&None));
+
+        assert_eq!(
+            db.record_transfer(&Transfer {
+                animal_id: "missing".to_string(),
+                ..transfer(TransferDirection::Outbound, Some(&south), None)
+            })
+            .unwrap(),
+            RecordTransferResult::AnimalNotFound
+        );
+    }
+
+    #[test]
+    fn test_sync_changes() {
+        let front_desk = create_test_db("test_sync_changes_front_desk");
+        let back_office = create_test_db("test_sync_changes_back_office");
+        let location = front_desk
+            .insert_location(&Location {
+                id: String::new(),
+                name: "North Site".to_string(),
+                address: "1 North Road".to_string(),
+            })
+            .unwrap();
+        let mut animal = sample_animal("a1");
+        animal.location_id = Some(location.clone());
+        front_desk.insert_animal(&animal).unwrap();
+        front_desk
+            .insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+
+        // New records are stamped with the time they changed
+        let updated_timestamp: i64 = front_desk
+            .connection
+            .query_row(
+                "SELECT updated_timestamp FROM animals WHERE id = 'a1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(updated_timestamp > 0);
+
+        // Records from the other installation are created, once
+        let changelog = front_desk.export_changes(0, 1_700_000_000).unwrap();
+        assert_eq!(changelog.animals.len(), 1);
+        assert_eq!(changelog.adoption_requests.len(), 1);
+        let result = back_office.import_changes(&changelog).unwrap();
+        assert_eq!(result.applied_count, 2);
+        assert_eq!(
+            back_office
+                .query_animal_by_id("a1")
+                .unwrap()
+                .unwrap()
+                .location_id,
+            Some(location.clone())
+        );
+        let result = back_office.import_changes(&changelog).unwrap();
+        assert_eq!(result.applied_count, 0);
+        assert_eq!(result.skipped_count, 2);
+
+        // The most recent change wins
+        for (db, name, timestamp) in [(&front_desk, "Max", 200), (&back_office, "Rex", 300)] {
+            let mut animal = db.query_animal_by_id("a1").unwrap().unwrap();
+            animal.name = name.to_string();
+            db.update_animal(&animal).unwrap();
+            db.connection
+                .execute(
+                    "UPDATE animals SET updated_timestamp = ?1 WHERE id = 'a1'",
+                    [timestamp],
+                )
+                .unwrap();
+        }
+        let result = back_office
+            .import_changes(&front_desk.export_changes(0, 1_700_000_000).unwrap())
+            .unwrap();
+        assert_eq!(result.applied_count, 0);
+        assert_eq!(
+            back_office.query_animal_by_id("a1").unwrap().unwrap().name,
+            "Rex"
+        );
+        let result = front_desk
+            .import_changes(&back_office.export_changes(250, 1_700_000_000).unwrap())
+            .unwrap();
+        assert_eq!(result.applied_count, 1);
+        assert_eq!(
+            front_desk.query_animal_by_id("a1").unwrap().unwrap().name,
+            "Rex"
+        );
+
+        // Deletions are carried over through the trash
+        assert!(front_desk.delete_adoption_request("r1").unwrap());
+        let changelog = front_desk.export_changes(0, 1_700_000_000).unwrap();
+        assert_eq!(changelog.deletions.len(), 1);
+        let result = back_office.import_changes(&changelog).unwrap();
+        assert_eq!(result.deleted_count, 1);
+        assert!(back_office
+            .query_adoption_request_by_id("r1")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_seed_demo_data() {
+        let db = create_test_db("test_seed_demo_data");
+
+        let summary = db.seed_demo_data().unwrap();
+        assert_eq!(db.count_animals(None).unwrap(), summary.animal_count as i64);
+        let requests = db
+            .query_adoption_requests(&AdoptionRequestFilters::default())
+            .unwrap();
+        assert_eq!(requests.len(), summary.request_count);
+
+        // Approved requests adopt their animal and reject the competing requests
+        let adopted = db.query_animal_by_id("demo-animal-3").unwrap().unwrap();
+        assert_eq!(adopted.status, AnimalStatus::Adopted);
+        let statuses: Vec<RequestStatus> = requests.iter().map(|r| r.status.clone()).collect();
+        assert!(statuses.contains(&RequestStatus::Pending));
+        assert!(statuses.contains(&RequestStatus::Approved));
+        assert!(statuses.contains(&RequestStatus::Rejected));
+
+        // Seeding never mixes demo records with existing ones
+        assert!(db.seed_demo_data().is_err());
+    }
+
+    #[test]
+    fn test_webhook_events_and_retries() {
+        let db = create_test_db("test_webhook_events_and_retries");
+        let webhook_id = db
+            .insert_webhook(&Webhook {
+                id: String::new(),
+                url: "https://hooks.example.com/shelter".to_string(),
+                secret: "shelter-secret".to_string(),
+                event_types: vec![
+                    WebhookEvent::AnimalAdopted,
+                    WebhookEvent::RequestStatusChanged,
+                ],
+                created_timestamp: Utc::now().timestamp(),
+            })
+            .unwrap();
+        assert!(db
+            .insert_webhook(&Webhook {
+                id: String::new(),
+                url: "ftp://hooks.example.com".to_string(),
+                secret: "shelter-secret".to_string(),
+                event_types: vec![WebhookEvent::AnimalCreated],
+                created_timestamp: 0,
+            })
+            .is_err());
+        assert_eq!(
+            db.query_webhooks().unwrap()[0].event_types,
+            vec![
+                WebhookEvent::AnimalAdopted,
+                WebhookEvent::RequestStatusChanged
+            ]
+        );
+
+        // Only the subscribed events are queued: two filed requests, one approval,
+        // one rejection and one adoption, but no animal creation
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+        let mut other = sample_request("r2", "a1");
+        other.username = "Someone".to_string();
+        db.insert_adoption_request(&other).unwrap();
+        db.approve_adoption_request("r1", false, None, &ApprovalRequirements::default())
+            .unwrap();
+
+        let deliveries = db.query_webhook_deliveries(&webhook_id).unwrap();
+        let count = |event: WebhookEvent| deliveries.iter().filter(|d| d.event == event).count();
+        assert_eq!(count(WebhookEvent::AnimalCreated), 0);
+        assert_eq!(count(WebhookEvent::RequestStatusChanged), 4);
+        assert_eq!(count(WebhookEvent::AnimalAdopted), 1);
+        let rejection = deliveries
+            .iter()
+            .find(|d| d.payload.contains("\"status\":\"rejected\""))
+            .unwrap();
+        assert!(rejection.payload.contains("\"id\":\"r2\""));
+
+        // Failed attempts are retried with a growing delay, then given up on
+        let now = Utc::now().timestamp();
+        let delivery_id = &rejection.id;
+        let due = |at: i64| {
+            db.query_due_webhook_deliveries(at)
+                .unwrap()
+                .iter()
+                .any(|due| &due.delivery.id == delivery_id)
+        };
+        assert!(due(now));
+        let mut attempted = now;
+        for attempt in 1..=5 {
+            let status = db
+                .record_webhook_attempt(delivery_id, Some(503), Some("Unavailable"), attempted)
+                .unwrap();
+            if attempt < 5 {
+                assert_eq!(status, WebhookDeliveryStatus::Pending);
+                assert!(!due(attempted));
+                attempted += 60 << (attempt - 1);
+                assert!(due(attempted));
+            } else {
+                assert_eq!(status, WebhookDeliveryStatus::Failed);
+                assert!(!due(i64::MAX));
+            }
+        }
+
+        // Deleting the webhook removes its delivery log
+        assert!(db.delete_webhook(&webhook_id).unwrap());
+        assert!(db.query_webhook_deliveries(&webhook_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_status_emails() {
+        let db = create_test_db("test_status_emails");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+        let mut other = sample_request("r2", "a1");
+        other.username = "Someone".to_string();
+        other.name = "Some One".to_string();
+        other.email = "some.one@example.org".to_string();
+        db.insert_adoption_request(&other).unwrap();
+
+        // Filing requests sends nothing; approving one emails its applicant and every
+        // applicant rejected along with it
+        assert!(db.query_emails().unwrap().is_empty());
+        db.approve_adoption_request("r1", false, None, &ApprovalRequirements::default())
+            .unwrap();
+        let emails = db.query_emails().unwrap();
+        assert_eq!(emails.len(), 2);
+        let approval = emails.iter().find(|e| e.request_id == "r1").unwrap();
+        assert_eq!(approval.request_status, RequestStatus::Approved);
+        assert_eq!(approval.recipient, "jira.pit@gmail.com");
+        assert_eq!(approval.recipient_name, "Jira Pit");
+        assert_eq!(approval.animal_name, "Buddy");
+        let rejection = emails.iter().find(|e| e.request_id == "r2").unwrap();
+        assert_eq!(rejection.request_status, RequestStatus::Rejected);
+        assert_eq!(rejection.recipient, "some.one@example.org");
+        assert_eq!(db.query_pending_emails().unwrap().len(), 2);
+
+        // Attempts are logged; failures stay failed until resent
+        let status = db
+            .record_email_attempt(
+                &approval.id,
+                Some("Subject"),
+                Some("Body"),
+                Some("Connection refused"),
+                100,
+            )
+            .unwrap();
+        assert_eq!(status, EmailStatus::Failed);
+        let status = db
+            .record_email_attempt(&approval.id, None, None, None, 200)
+            .unwrap();
+        assert_eq!(status, EmailStatus::Sent);
+        let approval = db.query_email_by_id(&approval.id).unwrap().unwrap();
+        assert_eq!(approval.attempts, 2);
+        assert_eq!(approval.subject.as_deref(), Some("Subject"));
+        assert_eq!(approval.error, None);
+        assert_eq!(approval.attempted_timestamp, Some(200));
+        assert_eq!(db.query_pending_emails().unwrap().len(), 1);
+        assert!(db.query_email_by_id("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_favorites() {
+        let db = create_test_db("test_favorites");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_animal(&sample_animal("a2")).unwrap();
+
+        // Favorites are per user and added once
+        assert!(db.insert_favorite("JiraPit", "a1").unwrap());
+        assert!(!db.insert_favorite("JiraPit", "a1").unwrap());
+        assert!(db.insert_favorite("JiraPit", "a2").unwrap());
+        assert!(db.insert_favorite("NonPrajogo", "a1").unwrap());
+        assert!(db.insert_favorite("JiraPit", "missing").is_err());
+        assert_eq!(db.query_favorites_by_username("JiraPit").unwrap().len(), 2);
+
+        assert!(db.delete_favorite("JiraPit", "a2").unwrap());
+        assert!(!db.delete_favorite("JiraPit", "a2").unwrap());
+        let favorites = db.query_favorites_by_username("JiraPit").unwrap();
+        assert_eq!(favorites.len(), 1);
+        assert_eq!(favorites[0].id, "a1");
+
+        // Favorites never block deleting an animal
+        assert!(db.delete_animal("a1", false).unwrap());
+        assert!(db
+            .query_favorites_by_username("JiraPit")
+            .unwrap()
+            .is_empty());
+        assert!(db
+            .query_favorites_by_username("NonPrajogo")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_user_profiles() {
+        let db = create_test_db("test_user_profiles");
+        assert!(db.query_profile("JiraPit").unwrap().is_none());
+
+        // Only the full name is required, but given contact details must be well formed
+        let mut profile = UserProfile {
+            username: "JiraPit".to_string(),
+            full_name: "Jira Pitakpong".to_string(),
+            email: "not-an-email".to_string(),
+            updated_timestamp: Utc::now().timestamp(),
+            ..Default::default()
+        };
+        let error = db.upsert_profile(&profile).unwrap_err();
+        let error = error.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(error.fields.len(), 1);
+        assert_eq!(error.fields[0].field, "email");
+
+        profile.email = "jira@example.com".to_string();
+        db.upsert_profile(&profile).unwrap();
+        profile.tel_number = "+66 81 234 5678".to_string();
+        db.upsert_profile(&profile).unwrap();
+        let stored = db.query_profile("JiraPit").unwrap().unwrap();
+        assert_eq!(stored.full_name, "Jira Pitakpong");
+        assert_eq!(stored.tel_number, "+66 81 234 5678");
+
+        // Requests take the profile's contact details, keeping fields the profile leaves blank
+        let mut request = sample_request("r1", "a1");
+        assert!(db.fill_request_from_profile(&mut request).unwrap());
+        assert_eq!(request.name, "Jira Pitakpong");
+        assert_eq!(request.email, "jira@example.com");
+        assert_eq!(request.tel_number, "+66 81 234 5678");
+        assert_eq!(request.address, "Bangkok, Thailand");
+
+        request.username = "NonPrajogo".to_string();
+        assert!(!db.fill_request_from_profile(&mut request).unwrap());
+    }
+
+    #[test]
+    fn test_user_data_export_and_erasure() {
+        let db = create_test_db("test_user_data_export_and_erasure");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        let mut requested = sample_animal("a2");
+        requested.status = AnimalStatus::Requested;
+        db.insert_animal(&requested).unwrap();
+
+        let mut adopted = sample_request("r1", "a1");
+        adopted.status = RequestStatus::Approved;
+        adopted.co_applicant = Some(CoApplicant {
+            name: "Mali Pit".to_string(),
+            relationship: "Spouse".to_string(),
+            contact: "0812345678".to_string(),
+        });
+        db.insert_adoption_request(&adopted).unwrap();
+        db.insert_adoption_request(&sample_request("r2", "a2"))
+            .unwrap();
+        let mut other = sample_request("r3", "a1");
+        other.username = "NonPrajogo".to_string();
+        db.insert_adoption_request(&other).unwrap();
+        db.upsert_profile(&UserProfile {
+            username: "JiraPit".to_string(),
+            full_name: "Jira Pitakpong".to_string(),
+            updated_timestamp: Utc::now().timestamp(),
+            ..Default::default()
+        })
+        .unwrap();
+        db.insert_favorite("JiraPit", "a1").unwrap();
+        db.insert_notification("JiraPit", "Welcome", None).unwrap();
+        db.insert_request_interview(&sample_interview("r1"))
+            .unwrap();
+        db.insert_reference_check(&sample_reference_check("r1", ReferenceOutcome::Positive))
+            .unwrap();
+
+        // The export holds only the customer's own data
+        let export = db.query_user_data_export("JiraPit").unwrap();
+        assert_eq!(export.profile.unwrap().full_name, "Jira Pitakpong");
+        assert_eq!(export.adoption_requests.len(), 2);
+        assert_eq!(export.favorites[0].id, "a1");
+
+        // A customer has at most one request waiting for review
+        let CreateErasureResult::Created(request) = db.insert_erasure_request("JiraPit").unwrap()
+        else {
+            panic!("The erasure request should be created");
+        };
+        assert!(matches!(
+            db.insert_erasure_request("JiraPit").unwrap(),
+            CreateErasureResult::AlreadyPending(pending) if pending.id == request.id
+        ));
+        assert_eq!(
+            db.query_erasure_requests(Some(ErasureRequestStatus::Pending))
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Approving anonymizes the requests and deletes everything else
+        assert!(matches!(
+            db.approve_erasure_request("missing", "manager").unwrap(),
+            ReviewErasureResult::NotFound
+        ));
+        let ReviewErasureResult::Reviewed(approved) =
+            db.approve_erasure_request(&request.id, "manager").unwrap()
+        else {
+            panic!("The erasure request should be approved");
+        };
+        assert_eq!(approved.status, ErasureRequestStatus::Approved);
+        assert_eq!(approved.reviewed_by.as_deref(), Some("manager"));
+        assert!(matches!(
+            db.reject_erasure_request(&request.id, "manager").unwrap(),
+            ReviewErasureResult::AlreadyReviewed(_)
+        ));
+
+        let export = db.query_user_data_export("JiraPit").unwrap();
+        assert!(export.profile.is_none());
+        assert!(export.adoption_requests.is_empty());
+        assert!(export.favorites.is_empty());
+        assert!(db
+            .query_notifications_by_username("JiraPit")
+            .unwrap()
+            .is_empty());
+
+        let kept = db.query_adoption_request_by_id("r1").unwrap().unwrap();
+        assert_eq!(kept.name, ERASED_NAME);
+        assert_eq!(kept.email, "");
+        assert_eq!(kept.address, "");
+        assert_eq!(kept.annual_income, "");
+        assert_eq!(kept.co_applicant, None);
+        let checks = db.query_request_checks("r1").unwrap();
+        assert!(checks.interviews.is_empty());
+        assert!(checks.reference_checks.is_empty());
+        assert_eq!(kept.status, RequestStatus::Approved);
+        assert_ne!(kept.username, "JiraPit");
+
+        // Pending requests are rejected, freeing animals nobody else asked for
+        let rejected = db.query_adoption_request_by_id("r2").unwrap().unwrap();
+        assert_eq!(rejected.status, RequestStatus::Rejected);
+        assert_eq!(
+            db.query_animal_by_id("a2").unwrap().unwrap().status,
+            AnimalStatus::Available
+        );
+
+        // Other applicants are left alone
+        let untouched = db.query_adoption_request_by_id("r3").unwrap().unwrap();
+        assert_eq!(untouched.name, "Jira Pit");
+
+        // Rejected requests erase nothing
+        let CreateErasureResult::Created(request) =
+            db.insert_erasure_request("NonPrajogo").unwrap()
+        else {
+            panic!("The erasure request should be created");
+        };
+        let ReviewErasureResult::Reviewed(rejected) =
+            db.reject_erasure_request(&request.id, "manager").unwrap()
+        else {
+            panic!("The erasure request should be rejected");
+        };
+        assert_eq!(rejected.status, ErasureRequestStatus::Rejected);
+        assert_eq!(
+            db.query_user_data_export("NonPrajogo")
+                .unwrap()
+                .adoption_requests
+                .len(),
+            1
+        );
+        assert_eq!(db.query_erasure_requests(None).unwrap().len(), 2);
+    }
+
+    // ==================== BANNED ADOPTER TESTS ====================
+
+    #[test]
+    fn test_screening_scores() {
+        assert_eq!(parse_income("50,000"), Some(50_000));
+        assert_eq!(parse_income(" $42000.75 "), Some(42_000));
+        assert_eq!(parse_income("65K"), Some(65_000));
+        assert_eq!(parse_income("prefer not to say"), None);
+
+        let config = ScreeningConfig {
+            points_per_person: 5,
+            points_per_child: -2,
+            income_bands: vec![
+                IncomeBand {
+                    min_income: 20_000,
+                    points: 10,
+                },
+                IncomeBand {
+                    min_income: 50_000,
+                    points: 20,
+                },
+            ],
+            points_per_return: -25,
+        };
+        let mut request = sample_request("r1", "a1");
+        request.num_people = 3;
+        request.num_children = 1;
+        // 3 people, 1 child and the highest band reached
+        assert_eq!(screening_score(&request, 0, &config), 15 - 2 + 20);
+        request.annual_income = "10000".to_string();
+        assert_eq!(screening_score(&request, 2, &config), 15 - 2 - 50);
+
+        let db = create_test_db("test_screening_scores");
+        let mut animal = sample_animal("a1");
+        animal.status = AnimalStatus::Adopted;
+        db.insert_animal(&animal).unwrap();
+        db.insert_animal(&sample_animal("a2")).unwrap();
+        let mut returned = sample_request("r1", "a1");
+        returned.status = RequestStatus::Approved;
+        returned.adoption_timestamp = Utc::now().timestamp();
+        db.insert_adoption_request(&returned).unwrap();
+        db.insert_adoption_request(&sample_request("r2", "a2"))
+            .unwrap();
+
+        let scores = || -> Vec<(String, Option<i64>)> {
+            let mut scores: Vec<(String, Option<i64>)> = db
+                .query_adoption_requests(&AdoptionRequestFilters::default())
+                .unwrap()
+                .into_iter()
+                .map(|summary| (summary.id, summary.screening_score))
+                .collect();
+            scores.sort();
+            scores
+        };
+
+        // Requests are unscored until they are scored; 2 people and an income of 50000
+        assert_eq!(db.score_adoption_request("missing", &config).unwrap(), None);
+        assert_eq!(db.score_adoption_request("r2", &config).unwrap(), Some(30));
+        assert_eq!(
+            scores(),
+            vec![("r1".to_string(), None), ("r2".to_string(), Some(30))]
+        );
+
+        // A returned adoption counts against the applicant's other requests
+        db.process_return("r1", "Allergies").unwrap();
+        assert_eq!(db.recompute_screening_scores(&config).unwrap(), 2);
+        assert_eq!(
+            scores(),
+            vec![("r1".to_string(), Some(30)), ("r2".to_string(), Some(5))]
+        );
+    }
+
+    #[test]
+    fn test_audit_log() {
+        let db = create_test_db("test_audit_log");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+
+        let created = db
+            .insert_audit_entry("Somchai", AuditAction::AnimalCreated, "a1")
+            .unwrap();
+        assert_eq!(created.animal_id.as_deref(), Some("a1"));
+        assert_eq!(created.animal_name.as_deref(), Some("Buddy"));
+
+        // Requests are traced back to their animal, even once they are in the trash
+        db.delete_adoption_request("r1").unwrap();
+        let deleted = db
+            .insert_audit_entry("Jane", AuditAction::RequestDeleted, "r1")
+            .unwrap();
+        assert_eq!(deleted.animal_id.as_deref(), Some("a1"));
+        assert_eq!(deleted.animal_name.as_deref(), Some("Buddy"));
+
+        // Unknown records are still recorded, without an animal name
+        let unknown = db
+            .insert_audit_entry("Jane", AuditAction::RequestApproved, "missing")
+            .unwrap();
+        assert_eq!(unknown.animal_id, None);
+        assert_eq!(unknown.animal_name, None);
+
+        // Pages are most recent first
+        let (page, total) = db.query_audit_log(0, 2).unwrap();
+        assert_eq!(total, 3);
+        let ids: Vec<&str> = page.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, vec![unknown.id.as_str(), deleted.id.as_str()]);
+        let (page, _) = db.query_audit_log(2, 2).unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].action, AuditAction::AnimalCreated);
+        assert_eq!(page[0].username, "Somchai");
+    }
+
+    #[test]
+    fn test_banned_adopters() {
+        let db = create_test_db("test_banned_adopters");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+
+        // An entry needs at least one pattern and a reason
+        let mut banned = BannedAdopter {
+            id: String::new(),
+            name_pattern: Some("  ".to_string()),
+            email_pattern: None,
+            tel_number_pattern: None,
+            reason: String::new(),
+            added_by: "NonPrajogo".to_string(),
+            created_timestamp: Utc::now().timestamp(),
+        };
+        let error = db.insert_banned_adopter(&banned).unwrap_err();
+        let error = error.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(error.fields.len(), 2);
+
+        // Names match case-insensitively, with wildcards
+        banned.name_pattern = Some("jira *".to_string());
+        banned.reason = "Neglected a previous adoptee".to_string();
+        let name_ban = db.insert_banned_adopter(&banned).unwrap();
+        let matches = db.query_banned_adopter_matches("r1").unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].id, name_ban);
+
+        // Telephone numbers are compared by their digits only
+        banned.name_pattern = None;
+        banned.tel_number_pattern = Some("012-345-6789".to_string());
+        db.insert_banned_adopter(&banned).unwrap();
+        banned.tel_number_pattern = None;
+        banned.email_pattern = Some("*@example.com".to_string());
+        db.insert_banned_adopter(&banned).unwrap();
+        assert_eq!(db.query_banned_adopters().unwrap().len(), 3);
+        assert_eq!(db.query_banned_adopter_matches("r1").unwrap().len(), 2);
+
+        assert!(db.delete_banned_adopter(&name_ban).unwrap());
+        assert!(!db.delete_banned_adopter(&name_ban).unwrap());
+        assert_eq!(db.query_banned_adopter_matches("r1").unwrap().len(), 1);
+        assert!(db.query_banned_adopter_matches("missing").is_err());
+    }
+
+    // ==================== WAITLIST TESTS ====================
+
+    #[test]
+    fn test_waitlist_notifications() {
+        let db = create_test_db("test_waitlist_notifications");
+        let now = Utc::now().timestamp();
+
+        let any_dog = WaitlistEntry {
+            id: "w1".to_string(),
+            username: "JiraPit".to_string(),
+            specie: "Dog".to_string(),
+            breed: None,
+            created_timestamp: now,
+        };
+        let beagle = WaitlistEntry {
+            id: "w2".to_string(),
+            username: "NonPrajogo".to_string(),
+            breed: Some("Beagle".to_string()),
+            ..any_dog.clone()
+        };
+        assert_eq!(
+            db.insert_waitlist_entry(&any_dog).unwrap(),
+            JoinWaitlistResult::Success
+        );
+        assert_eq!(
+            db.insert_waitlist_entry(&beagle).unwrap(),
+            JoinWaitlistResult::Success
+        );
+
+        // Joining twice for the same species and breed is reported
+        let mut again = any_dog.clone();
+        again.id = "w3".to_string();
+        assert_eq!(
+            db.insert_waitlist_entry(&again).unwrap(),
+            JoinWaitlistResult::AlreadyJoined
+        );
+
+        // A golden retriever only matches the user waiting for any dog
+        let notifications = db.insert_animal(&sample_animal("a1")).unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].username, "JiraPit");
+        assert_eq!(notifications[0].animal_id.as_deref(), Some("a1"));
+
+        // A beagle matches both users
+        let mut beagle_animal = sample_animal("a2");
+        beagle_animal.breed = "Beagle".to_string();
+        assert_eq!(db.insert_animal(&beagle_animal).unwrap().len(), 2);
+
+        // Notifications are listed per user and can be marked as read by their owner only
+        let inbox = db.query_notifications_by_username("JiraPit").unwrap();
+        assert_eq!(inbox.len(), 2);
+        assert!(inbox.iter().all(|n| !n.read));
+        assert!(!db
+            .mark_notification_read(&inbox[0].id, "NonPrajogo")
+            .unwrap());
+        assert!(db.mark_notification_read(&inbox[0].id, "JiraPit").unwrap());
+        let inbox = db.query_notifications_by_username("JiraPit").unwrap();
+        assert_eq!(inbox.iter().filter(|n| n.read).count(), 1);
+
+        // Leaving the waitlist stops further notifications
+        assert!(!db.delete_waitlist_entry("w1", "NonPrajogo").unwrap());
+        assert!(db.delete_waitlist_entry("w1", "JiraPit").unwrap());
+        assert!(db.query_waitlist_by_username("JiraPit").unwrap().is_empty());
+        assert!(db.insert_animal(&sample_animal("a3")).unwrap().is_empty());
+    }
+
+    // ==================== ANIMAL RELATIONSHIPS TESTS ====================
+
+    #[test]
+    fn test_link_and_unlink_bonded_animals() {
+        let db = create_test_db("test_link_and_unlink_bonded_animals");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_animal(&sample_animal("a2")).unwrap();
+
+        // Bonds are symmetric and stored once
+        assert!(db.link_bonded_animals("a2", "a1").unwrap());
+        assert!(!db.link_bonded_animals("a1", "a2").unwrap());
+        assert!(db.link_bonded_animals("a1", "a1").is_err());
+        assert_eq!(db.query_bonded_animals("a1").unwrap()[0].id, "a2");
+        assert_eq!(db.query_bonded_animals("a2").unwrap()[0].id, "a1");
+
+        assert!(db.unlink_bonded_animals("a1", "a2").unwrap());
+        assert!(!db.unlink_bonded_animals("a2", "a1").unwrap());
+        assert!(db.query_bonded_animals("a1").unwrap().is_empty());
+
+        // Deleting an animal removes its bonds
+        db.link_bonded_animals("a1", "a2").unwrap();
+        assert!(db.delete_animal("a1", false).unwrap());
+        assert!(db.query_bonded_animals("a2").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_approve_adoption_request_with_bonded_animals() {
+        let db = create_test_db("test_approve_adoption_request_with_bonded_animals");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_animal(&sample_animal("a2")).unwrap();
+        db.link_bonded_animals("a1", "a2").unwrap();
+
+        let mut other_user = sample_request("r2", "a1");
+        other_user.username = "NonPrajogo".to_string();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+        db.insert_adoption_request(&other_user).unwrap();
+
+        // Approving only one of the pair is flagged
+        assert_eq!(
+            db.approve_adoption_request("r1", false, None, &ApprovalRequirements::default())
+                .unwrap(),
+            ApproveRequestResult::BondedPairSplit(vec!["a2".to_string()])
+        );
+        let request = db.query_adoption_request_by_id("r1").unwrap().unwrap();
+        assert_eq!(request.status, RequestStatus::Pending);
+
+        // Once the same user requests the partner, approval goes through
+        db.insert_adoption_request(&sample_request("r3", "a2"))
+            .unwrap();
+        assert_eq!(
+            db.approve_adoption_request("r1", false, None, &ApprovalRequirements::default())
+                .unwrap(),
+            ApproveRequestResult::Approved
+        );
+        let animal = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(animal.status, AnimalStatus::Adopted);
+        assert_eq!(animal.version, 2);
+        let rejected = db.query_adoption_request_by_id("r2").unwrap().unwrap();
+        assert_eq!(rejected.status, RequestStatus::Rejected);
+
+        assert_eq!(
+            db.approve_adoption_request("r1", false, None, &ApprovalRequirements::default())
+                .unwrap(),
+            ApproveRequestResult::NotPending
+        );
+        assert_eq!(
+            db.approve_adoption_request("missing", false, None, &ApprovalRequirements::default())
+                .unwrap(),
+            ApproveRequestResult::NotFound
+        );
+    }
+
+    #[test]
+    fn test_request_checks() {
+        let db = create_test_db("test_request_checks");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+
+        // Checks can only be recorded for existing requests, with valid details
+        assert_eq!(
+            db.insert_request_interview(&sample_interview("missing"))
+                .unwrap(),
+            RecordCheckResult::RequestNotFound
+        );
+        let mut empty_notes = sample_interview("r1");
+        empty_notes.notes = "  ".to_string();
+        assert!(db.insert_request_interview(&empty_notes).is_err());
+        let mut unreachable = sample_reference_check("r1", ReferenceOutcome::Pending);
+        unreachable.referee_contact = "somewhere".to_string();
+        let error = db.insert_reference_check(&unreachable).unwrap_err();
+        let validation_error = error.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(validation_error.fields[0].field, "refereeContact");
+
+        // Approval waits for the required interview, then a positive reference
+        let requirements = ApprovalRequirements {
+            interview: true,
+            reference_check: true,
+        };
+        assert_eq!(
+            db.approve_adoption_request("r1", false, None, &requirements)
+                .unwrap(),
+            ApproveRequestResult::InterviewRequired
+        );
+        assert_eq!(
+            db.insert_request_interview(&sample_interview("r1"))
+                .unwrap(),
+            RecordCheckResult::Recorded
+        );
+        assert_eq!(
+            db.approve_adoption_request("r1", false, None, &requirements)
+                .unwrap(),
+            ApproveRequestResult::ReferenceCheckRequired
+        );
+        assert_eq!(
+            db.insert_reference_check(&sample_reference_check("r1", ReferenceOutcome::Pending))
+                .unwrap(),
+            RecordCheckResult::Recorded
+        );
+        assert_eq!(
+            db.approve_adoption_request("r1", false, None, &requirements)
+                .unwrap(),
+            ApproveRequestResult::ReferenceCheckRequired
+        );
+
+        let checks = db.query_request_checks("r1").unwrap();
+        assert_eq!(checks.interviews.len(), 1);
+        assert_eq!(checks.interviews[0].interviewer, "manager");
+        assert_eq!(checks.reference_checks.len(), 1);
+        let check = &checks.reference_checks[0];
+        assert_eq!(check.outcome, ReferenceOutcome::Pending);
+        assert!(db
+            .update_reference_check_outcome("missing", ReferenceOutcome::Positive, "", "staff")
+            .unwrap()
+            .is_none());
+        let check = db
+            .update_reference_check_outcome(
+                &check.id,
+                ReferenceOutcome::Positive,
+                " Walked their dogs for years ",
+                "staff",
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            check,
+            db.query_request_checks("r1").unwrap().reference_checks[0]
+        );
+        assert_eq!(check.outcome, ReferenceOutcome::Positive);
+        assert_eq!(check.notes, "Walked their dogs for years");
+        assert_eq!(check.checked_by, "staff");
+
+        assert_eq!(
+            db.approve_adoption_request("r1", false, None, &requirements)
+                .unwrap(),
+            ApproveRequestResult::Approved
+        );
+    }
+
+    #[test]
+    fn test_payments() {
+        let db = create_test_db("test_payments");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+
+        // Fees can only be charged for existing requests, with a positive amount
+        assert_eq!(
+            db.insert_payment(&sample_payment("missing")).unwrap(),
+            RecordPaymentResult::RequestNotFound
+        );
+        let mut free = sample_payment("r1");
+        free.amount = 0;
+        let error = db.insert_payment(&free).unwrap_err();
+        let validation_error = error.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(validation_error.fields[0].field, "amount");
+        for _ in 0..2 {
+            assert_eq!(
+                db.insert_payment(&sample_payment("r1")).unwrap(),
+                RecordPaymentResult::Recorded
+            );
+        }
+        let payments = db.query_payments("r1").unwrap();
+        assert_eq!(payments.len(), 2);
+        let (first, second) = (&payments[0].id, &payments[1].id);
+
+        // Invoices are numbered in sequence, and keep their number when generated again
+        let invoice = db
+            .issue_payment_document(first, PaymentDocument::Invoice)
+            .unwrap()
+            .unwrap();
+        assert_eq!(invoice.invoice_number, Some(1));
+        assert_eq!(
+            db.issue_payment_document(first, PaymentDocument::Invoice)
+                .unwrap()
+                .unwrap()
+                .invoice_number,
+            Some(1)
+        );
+        assert_eq!(
+            db.issue_payment_document(second, PaymentDocument::Invoice)
+                .unwrap()
+                .unwrap()
+                .invoice_number,
+            Some(2)
+        );
+        assert!(db
+            .issue_payment_document("missing", PaymentDocument::Invoice)
+            .unwrap()
+            .is_none());
+
+        // Receipts are only issued once paid, from a sequence of their own
+        assert!(db
+            .issue_payment_document(second, PaymentDocument::Receipt)
+            .is_err());
+        assert!(db
+            .mark_payment_paid("missing", 1_710_000_000)
+            .unwrap()
+            .is_none());
+        let paid = db
+            .mark_payment_paid(second, 1_710_000_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(paid.paid_timestamp, Some(1_710_000_000));
+        let paid = db
+            .mark_payment_paid(second, 1_720_000_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(paid.paid_timestamp, Some(1_710_000_000));
+        let receipt = db
+            .issue_payment_document(second, PaymentDocument::Receipt)
+            .unwrap()
+            .unwrap();
+        assert_eq!(receipt.receipt_number, Some(1));
+        assert_eq!(receipt.invoice_number, Some(2));
+
+        // Numbers are not reused even when the payments holding them are gone
+        db.connection.execute("DELETE FROM payments", []).unwrap();
+        db.insert_payment(&sample_payment("r1")).unwrap();
+        let payment = &db.query_payments("r1").unwrap()[0];
+        assert_eq!(
+            db.issue_payment_document(&payment.id, PaymentDocument::Invoice)
+                .unwrap()
+                .unwrap()
+                .invoice_number,
+            Some(3)
+        );
+        assert_eq!(PaymentDocument::Invoice.format_number(3), "INV-000003");
+        assert_eq!(PaymentDocument::Receipt.format_number(12), "RCT-000012");
+    }
+
+    #[test]
+    fn test_donations() {
+        let db = create_test_db("test_donations");
+        db.insert_contact(&Contact {
+            id: "donor".to_string(),
+            name: "Malee Boonmee".to_string(),
+            kind: ContactKind::Donor,
+            tel_number: String::new(),
+            email: "malee@example.com".to_string(),
+            address: "12 Sukhumvit Road".to_string(),
+            notes: String::new(),
+        })
+        .unwrap();
+        let donation = |contact_id: Option<&str>, amount: i64, donation_timestamp: i64| Donation {
+            id: String::new(),
+            contact_id: contact_id.map(str::to_string),
+            donor_name: String::new(),
+            amount,
+            donation_timestamp,
+            notes: " Spring fundraiser ".to_string(),
+            receipt_number: None,
+        };
+
+        // Donations need a known donor and a positive amount
+        assert!(db
+            .insert_donation(&donation(Some("missing"), 5000, 1_710_000_000))
+            .unwrap()
+            .is_none());
+        assert!(db
+            .insert_donation(&donation(None, 5000, 1_710_000_000))
+            .unwrap()
+            .is_none());
+        let error = db
+            .insert_donation(&donation(Some("donor"), -500, 1_710_000_000))
+            .unwrap_err();
+        assert!(error.downcast_ref::<ValidationError>().is_some());
+
+        // Two donations in 2024 and one on the first second of 2025
+        let first = db
+            .insert_donation(&donation(Some("donor"), 5000, 1_704_067_200))
+            .unwrap()
+            .unwrap();
+        db.insert_donation(&donation(Some("donor"), 2550, 1_720_000_000))
+            .unwrap();
+        db.insert_donation(&donation(Some("donor"), 10000, 1_735_689_600))
+            .unwrap();
+        let donations = db.query_donations("donor").unwrap();
+        assert_eq!(donations.len(), 3);
+        assert_eq!(donations[2].id, first);
+        assert_eq!(donations[2].donor_name, "Malee Boonmee");
+        assert_eq!(donations[2].notes, "Spring fundraiser");
+
+        let statement = db.query_donor_statement("donor", 2024).unwrap().unwrap();
+        assert_eq!(statement.donor.name, "Malee Boonmee");
+        assert_eq!(statement.donations.len(), 2);
+        assert_eq!(statement.donations[0].id, first);
+        assert_eq!(statement.total, 7550);
+        assert!(db.query_donor_statement("missing", 2024).unwrap().is_none());
+
+        // Receipts keep their number, taken from a sequence apart from adoption fees
+        let receipt = db.issue_donation_receipt(&first).unwrap().unwrap();
+        assert_eq!(receipt.receipt_number, Some(1));
+        assert_eq!(
+            db.issue_donation_receipt(&first)
+                .unwrap()
+                .unwrap()
+                .receipt_number,
+            Some(1)
+        );
+        assert_eq!(
+            db.issue_donation_receipt(&donations[0].id)
+                .unwrap()
+                .unwrap()
+                .receipt_number,
+            Some(2)
+        );
+        assert!(db.issue_donation_receipt("missing").unwrap().is_none());
+        assert_eq!(Donation::format_receipt_number(2), "DON-000002");
+
+        // Deleting the donor keeps their donations and the name on them
+        assert!(db.delete_contact("donor").unwrap());
+        assert!(db.query_donations("donor").unwrap().is_empty());
+        let receipt = db.issue_donation_receipt(&first).unwrap().unwrap();
+        assert_eq!(receipt.contact_id, None);
+        assert_eq!(receipt.donor_name, "Malee Boonmee");
+    }
+
+    #[test]
+    fn test_trial_adoption() {
+        let db = create_test_db("test_trial_adoption");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_animal(&sample_animal("a2")).unwrap();
+        let mut other_user = sample_request("r2", "a1");
+        other_user.username = "NonPrajogo".to_string();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+        db.insert_adoption_request(&other_user).unwrap();
+        db.insert_adoption_request(&sample_request("r3", "a2"))
+            .unwrap();
+
+        // A trial must end in the future
+        let now = Utc::now().timestamp();
+        assert!(db
+            .approve_adoption_request("r1", false, Some(now), &ApprovalRequirements::default())
+            .is_err());
+        let request = db.query_adoption_request_by_id("r1").unwrap().unwrap();
+        assert_eq!(request.status, RequestStatus::Pending);
+
+        // Starting a trial puts the request and the animal on trial
+        let end = now + 14 * 24 * 60 * 60;
+        assert_eq!(
+            db.approve_adoption_request("r1", false, Some(end), &ApprovalRequirements::default())
+                .unwrap(),
+            ApproveRequestResult::TrialStarted
+        );
+        let animal = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(animal.status, AnimalStatus::TrialAdoption);
+        let request = db.query_adoption_request_by_id("r1").unwrap().unwrap();
+        assert_eq!(request.status, RequestStatus::TrialAdoption);
+        assert_eq!(request.adoption_timestamp, 0);
+        let rejected = db.query_adoption_request_by_id("r2").unwrap().unwrap();
+        assert_eq!(rejected.status, RequestStatus::Rejected);
+        let trials = db.query_ongoing_trial_adoptions().unwrap();
+        assert_eq!(trials.len(), 1);
+        assert_eq!(trials[0].adoption_request_id, "r1");
+        assert_eq!(trials[0].end_timestamp, end);
+
+        // Finalizing makes the adoption final
+        assert_eq!(
+            db.finalize_trial_adoption("r1").unwrap(),
+            FinalizeTrialResult::Finalized
+        );
+        let animal = db.query_animal_by_id("a1").unwrap().unwrap();
+        assert_eq!(animal.status, AnimalStatus::Adopted);
+        let request = db.query_adoption_request_by_id("r1").unwrap().unwrap();
+        assert_eq!(request.status, RequestStatus::Approved);
+        assert!(request.adoption_timestamp > 0);
+        assert!(db.query_ongoing_trial_adoptions().unwrap().is_empty());
+        assert_eq!(
+            db.finalize_trial_adoption("r1").unwrap(),
+            FinalizeTrialResult::NotInTrial
+        );
+        assert_eq!(
+            db.finalize_trial_adoption("missing").unwrap(),
+            FinalizeTrialResult::NotFound
+        );
+
+        // Returning during a trial ends it without an adoption
+        assert_eq!(
+            db.approve_adoption_request("r3", false, Some(end), &ApprovalRequirements::default())
+                .unwrap(),
+            ApproveRequestResult::TrialStarted
+        );
+        assert_eq!(
+            db.process_return("r3", "Did not get along with the cat")
+                .unwrap(),
+            ProcessReturnResult::Success
+        );
+        let animal = db.query_animal_by_id("a2").unwrap().unwrap();
+        assert_eq!(animal.status, AnimalStatus::Available);
+        let request = db.query_adoption_request_by_id("r3").unwrap().unwrap();
+        assert_eq!(request.status, RequestStatus::Rejected);
+        assert_eq!(
+            db.query_adoption_returns_by_animal_id("a2").unwrap().len(),
+            1
+        );
+        assert_eq!(
+            db.finalize_trial_adoption("r3").unwrap(),
+            FinalizeTrialResult::NotInTrial
+        );
+    }
+
+    #[test]
+    fn test_approve_adoption_request_allowing_split_bond() {
+        let db = create_test_db("test_approve_adoption_request_allowing_split_bond");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_animal(&sample_animal("a2")).unwrap();
+        db.link_bonded_animals("a1", "a2").unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+
+        assert_eq!(
+            db.approve_adoption_request("r1", true, None, &ApprovalRequirements::default())
+                .unwrap(),
+            ApproveRequestResult::Approved
+        );
+        let animal = db.query_animal_by_id("a2").unwrap().unwrap();
+        assert_eq!(animal.status, AnimalStatus::Available);
+    }
+
+    // ==================== CALENDAR TESTS ====================
+
+    #[test]
+    fn test_calendar_events() {
+        let db = create_test_db("test_calendar_events");
+        let now = Utc::now().timestamp();
+        let day = 86_400;
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_animal(&sample_animal("a2")).unwrap();
+
+        let vaccination = |id: &str, animal_id: &str, administered: i64, due: i64| Vaccination {
+            id: id.to_string(),
+            animal_id: animal_id.to_string(),
+            vaccine: "Rabies".to_string(),
+            administered_timestamp: administered,
+            due_timestamp: Some(due),
+            recorded_by: "staffer".to_string(),
+            contact_id: None,
+        };
+        // Only the latest dose of a vaccine counts
+        db.insert_vaccination(&vaccination("v1", "a1", now - 300 * day, now + 5 * day))
+            .unwrap();
+        db.insert_vaccination(&vaccination("v2", "a1", now - day, now + 20 * day))
+            .unwrap();
+        db.insert_vaccination(&vaccination("v3", "a2", now - day, now + 40 * day))
+            .unwrap();
+
+        // Meet-and-greets and surgeries are booked before the animal goes to foster
+        db.request_appointment("a1", "alice", now + 15 * day)
+            .unwrap();
+        let mut unneutered = sample_animal("a3");
+        unneutered.neutered = false;
+        db.insert_animal(&unneutered).unwrap();
+        db.schedule_surgery("a3", now + 25 * day, None, "").unwrap();
+
+        let mut placement = sample_placement("f1", "a1");
+        placement.end_timestamp = now + 10 * day;
+        db.start_foster_placement(&placement).unwrap();
+
+        let events = db.query_calendar_events(now, now + 30 * day).unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0].kind, CalendarEventKind::FosterReturnDue);
+        assert_eq!(events[0].record_id, "f1");
+        assert_eq!(events[0].subject, "Somchai Jaidee");
+        assert_eq!(events[1].kind, CalendarEventKind::Appointment);
+        assert_eq!(events[1].subject, "alice");
+        assert_eq!(events[1].timestamp, now + 15 * day);
+        assert_eq!(events[2].kind, CalendarEventKind::VaccinationDue);
+        assert_eq!(events[2].record_id, "v2");
+        assert_eq!(events[2].subject, "Rabies");
+        assert_eq!(events[2].timestamp, now + 20 * day);
+        assert_eq!(events[3].kind, CalendarEventKind::Surgery);
+        assert_eq!(events[3].animal_id, "a3");
+
+        // Ended placements, cancelled appointments, performed surgeries and animals no
+        // longer in care have nothing due
+        db.end_foster_placement("f1").unwrap();
+        db.cancel_appointment(&events[1].record_id, None).unwrap();
+        db.complete_surgery(&events[3].record_id, now).unwrap();
+        let mut animal = db.query_animal_by_id("a2").unwrap().unwrap();
+        animal.status = AnimalStatus::Adopted;
+        db.update_animal(&animal).unwrap();
+        assert!(db
+            .query_calendar_events(now, now + 60 * day)
+            .unwrap()
+            .iter()
+            .all(|event| event.record_id == "v2"));
+
+        assert!(db.query_calendar_events(now, now).is_err());
+    }
+
+    // ==================== LISTINGS TESTS ====================
+
+    #[test]
+    fn test_listed_animals() {
+        let db = create_test_db("test_listed_animals");
+        let now = Utc::now().timestamp();
+        for id in ["a1", "a2", "a3", "a4"] {
+            db.insert_animal(&sample_animal(id)).unwrap();
+        }
+        let mut adopted = db.query_animal_by_id("a4").unwrap().unwrap();
+        adopted.status = AnimalStatus::Adopted;
+        db.update_animal(&adopted).unwrap();
+
+        let note = |id: &str, animal_id: &str, category: NoteCategory, internal: bool| AnimalNote {
+            id: id.to_string(),
+            animal_id: animal_id.to_string(),
+            author_username: "staff".to_string(),
+            category,
+            content: format!("Note {}", id),
+            internal,
+            created_timestamp: now,
+        };
+        // A staff-only behavior note flags an animal; public or general notes do not
+        db.insert_animal_note(&note("n1", "a1", NoteCategory::Behavior, true))
+            .unwrap();
+        db.insert_animal_note(&note("n2", "a2", NoteCategory::Medical, false))
+            .unwrap();
+        db.insert_animal_note(&note("n3", "a3", NoteCategory::General, true))
+            .unwrap();
+
+        let ids = |animals: Vec<Animal>| {
+            let mut ids: Vec<String> = animals.into_iter().map(|animal| animal.id).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids(db.query_listed_animals(true).unwrap()), ["a2", "a3"]);
+        assert_eq!(
+            ids(db.query_listed_animals(false).unwrap()),
+            ["a1", "a2", "a3"]
+        );
+    }
+
+    // ==================== STATISTICS TESTS ====================
+
+    #[test]
+    fn test_shelter_statistics() {
+        let db = create_test_db("test_shelter_statistics");
+        let now = Utc::now().timestamp();
+
+        // Empty database reports zero counts for every month
+        let statistics = db.query_shelter_statistics(None).unwrap();
+        assert!(statistics.animals_by_status.is_empty());
+        assert_eq!(statistics.adoptions_per_month.len(), 12);
+        assert!(statistics.adoptions_per_month.iter().all(|m| m.count == 0));
+        assert_eq!(statistics.average_length_of_stay_days, None);
+        assert_eq!(statistics.pending_request_count, 0);
+
+        // Adopted dog that stayed for ten days
+        let mut adopted = sample_animal("a1");
+        adopted.status = AnimalStatus::Adopted;
+        adopted.admission_timestamp = now - 10 * 86_400;
+        db.insert_animal(&adopted).unwrap();
+        let mut approved = sample_request("r1", "a1");
+        approved.status = RequestStatus::Approved;
+        approved.adoption_timestamp = now;
+        db.insert_adoption_request(&approved).unwrap();
+
+        // Available cat with a pending request
+        let mut cat = sample_animal("a2");
+        cat.specie = "Cat".to_string();
+        db.insert_animal(&cat).unwrap();
+        db.insert_adoption_request(&sample_request("r2", "a2"))
+            .unwrap();
+
+        let statistics = db.query_shelter_statistics(None).unwrap();
+        assert_eq!(statistics.animals_by_status.len(), 2);
+        assert!(statistics
+            .animals_by_status
+            .iter()
+            .any(|c| c.label == "adopted" && c.count == 1));
+        assert!(statistics
+            .animals_by_specie
+            .iter()
+            .any(|c| c.label == "Cat" && c.count == 1));
+        assert_eq!(statistics.adoptions_per_month.last().unwrap().count, 1);
+        assert_eq!(statistics.average_length_of_stay_days, Some(10.0));
+        assert_eq!(statistics.pending_request_count, 1);
+    }
+
+    #[test]
+    fn test_monthly_report() {
+        let db = create_test_db("test_monthly_report");
+        // Midday on 15 March 2024 and 15 April 2024
+        let march = 1_710_504_000;
+        let april = 1_713_182_400;
+
+        let mut returned = sample_animal("a1");
+        returned.admission_timestamp = march;
+        let mut adopted = sample_animal("a2");
+        adopted.admission_timestamp = april;
+        db.insert_animal(&returned).unwrap();
+        db.insert_animal(&adopted).unwrap();
+
+        let stray = IntakeRecord {
+            id: String::new(),
+            animal_id: "a1".to_string(),
+            intake_type: IntakeType::Stray,
+            source: "Found near Chatuchak Market".to_string(),
+            condition: "Healthy".to_string(),
+            staff_username: "staff1".to_string(),
+            intake_timestamp: march,
+            hold_until_timestamp: None,
+        };
+        db.insert_intake_record(&stray, 5).unwrap();
+
+        let mut first = sample_request("r1", "a1");
+        first.status = RequestStatus::Approved;
+        first.adoption_timestamp = march;
+        let mut second = sample_request("r2", "a2");
+        second.status = RequestStatus::Approved;
+        second.adoption_timestamp = april;
+        db.insert_adoption_request(&first).unwrap();
+        db.insert_adoption_request(&second).unwrap();
+        db.process_return("r1", "Allergies").unwrap();
+
+        // A returned animal that was put to sleep, its fee and a donation, all in March
+        db.record_death(&DeceasedRecord {
+            id: String::new(),
+            animal_id: "a1".to_string(),
+            death_timestamp: march,
+            cause: "Euthanasia".to_string(),
+            authorized_by: "Dr. Somchai".to_string(),
+            notes: String::new(),
+        })
+        .unwrap();
+        let mut fee = sample_payment("r1");
+        fee.id = "p1".to_string();
+        db.insert_payment(&fee).unwrap();
+        db.mark_payment_paid("p1", march).unwrap();
+        db.insert_contact(&Contact {
+            id: "donor".to_string(),
+            name: "Jane Doe".to_string(),
+            kind: ContactKind::Donor,
+            tel_number: "0812345678".to_string(),
+            email: String::new(),
+            address: String::new(),
+            notes: String::new(),
+        })
+        .unwrap();
+        db.insert_donation(&Donation {
+            id: String::new(),
+            contact_id: Some("donor".to_string()),
+            donor_name: String::new(),
+            amount: 100000,
+            donation_timestamp: march,
+            notes: String::new(),
+            receipt_number: None,
+        })
+        .unwrap();
+
+        let report = db.query_monthly_report(2024, 3, None).unwrap();
+        assert_eq!(report.admission_count, 1);
+        assert_eq!(report.intakes_by_type.len(), 1);
+        assert_eq!(report.intakes_by_type[0].label, "stray");
+        assert_eq!(report.adoption_count, 1);
+        assert_eq!(report.return_count, 0);
+        assert_eq!(report.death_count, 1);
+        assert_eq!(report.deaths_by_cause[0].label, "Euthanasia");
+        assert_eq!(report.fee_revenue, 15000);
+        assert_eq!(report.donation_revenue, 100000);
+
+        // Donations are not made to a single location
+        let report = db.query_monthly_report(2024, 3, Some("nowhere")).unwrap();
+        assert_eq!(report.death_count, 0);
+        assert_eq!(report.fee_revenue, 0);
+        assert_eq!(report.donation_revenue, 0);
+
+        // The return happened now, not in April 2024
+        let report = db.query_monthly_report(2024, 4, None).unwrap();
+        assert_eq!(report.admission_count, 1);
+        assert!(report.intakes_by_type.is_empty());
+        assert_eq!(report.adoption_count, 1);
+        assert_eq!(report.death_count, 0);
+        assert_eq!(report.fee_revenue, 0);
+
+        let today = Utc::now().date_naive();
+        let report = db
+            .query_monthly_report(today.year(), today.month(), None)
+            .unwrap();
+        assert_eq!(report.return_count, 1);
+
+        assert!(db.query_monthly_report(2024, 13, None).is_err());
+    }
+
+    #[test]
+    fn test_adoption_trends() {
+        let db = create_test_db("test_adoption_trends");
+        // Midday on Friday 15 March 2024 and Monday 15 April 2024
+        let march = 1_710_504_000;
+        let april = 1_713_182_400;
+
+        let mut adopted = sample_animal("a1");
+        adopted.admission_timestamp = march;
+        let mut waiting = sample_animal("a2");
+        waiting.admission_timestamp = april;
+        db.insert_animal(&adopted).unwrap();
+        db.insert_animal(&waiting).unwrap();
+        let mut approved = sample_request("r1", "a1");
+        approved.status = RequestStatus::Approved;
+        approved.adoption_timestamp = march + 86_400;
+        db.insert_adoption_request(&approved).unwrap();
+        // Pending requests are not adoptions
+        db.insert_adoption_request(&sample_request("r2", "a2"))
+            .unwrap();
+
+        // 1 March to 1 May 2024
+        let trends = db
+            .query_adoption_trends(TrendGranularity::Month, 1_709_251_200, 1_714_521_600, None)
+            .unwrap();
+        assert_eq!(
+            trends,
+            vec![
+                TrendPoint {
+                    period_start_timestamp: 1_709_251_200,
+                    intake_count: 1,
+                    adoption_count: 1,
+                },
+                TrendPoint {
+                    period_start_timestamp: 1_711_929_600,
+                    intake_count: 1,
+                    adoption_count: 0,
+                },
+            ]
+        );
+
+        // Weeks start on the Monday before the range, 11 March 2024, and empty weeks
+        // are still reported
+        let trends = db
+            .query_adoption_trends(TrendGranularity::Week, march, march + 14 * 86_400, None)
+            .unwrap();
+        let starts: Vec<i64> = trends
+            .iter()
+            .map(|point| point.period_start_timestamp)
+            .collect();
+        assert_eq!(starts, vec![1_710_115_200, 1_710_720_000, 1_711_324_800]);
+        assert_eq!((trends[0].intake_count, trends[0].adoption_count), (1, 1));
+        assert!(trends[1..]
+            .iter()
+            .all(|point| point.intake_count == 0 && point.adoption_count == 0));
+
+        // Events outside the range are left out
+        let trends = db
+            .query_adoption_trends(TrendGranularity::Day, march + 3_600, april, None)
+            .unwrap();
+        assert_eq!(trends.len(), 32);
+        assert_eq!(trends[0].intake_count, 0);
+        assert_eq!(trends[1].adoption_count, 1);
+
+        assert!(db
+            .query_adoption_trends(TrendGranularity::Day, april, march, None)
+            .is_err());
+        assert!(db
+            .query_adoption_trends(TrendGranularity::Day, 0, april, None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_long_stay_animals() {
+        let db = create_test_db("test_long_stay_animals");
+        let now = 1_750_000_000;
+        let day = 86_400;
+
+        for (id, name, days, status) in [
+            ("a1", "Buddy", 45, AnimalStatus::Available),
+            ("a2", "Misty", 120, AnimalStatus::Available),
+            ("a3", "Rex", 10, AnimalStatus::Available),
+            ("a4", "Coco", 200, AnimalStatus::Requested),
+            ("a5", "Luna", 300, AnimalStatus::Adopted),
+        ] {
+            let mut animal = sample_animal(id);
+            animal.name = name.to_string();
+            animal.status = status;
+            animal.admission_timestamp = now - days * day - 3_600;
+            db.insert_animal(&animal).unwrap();
+        }
+
+        // Only available animals count, longest staying first
+        let animals = db.query_long_stay_animals(30, now).unwrap();
+        let stays: Vec<(&str, i64)> = animals
+            .iter()
+            .map(|animal| (animal.animal.name.as_str(), animal.days_in_care))
+            .collect();
+        assert_eq!(stays, vec![("Misty", 120), ("Buddy", 45)]);
+
+        assert_eq!(db.query_long_stay_animals(0, now).unwrap().len(), 3);
+        assert!(db.query_long_stay_animals(365, now).unwrap().is_empty());
+    }
+
+    // ==================== SEARCH TESTS ====================
+
+    #[test]
+    fn test_search() {
+        let db = create_test_db("test_search");
+        for (id, name, breed, bio, status) in [
+            (
+                "a1",
+                "Max",
+                "Beagle",
+                "Loves long walks.",
+                AnimalStatus::Available,
+            ),
+            (
+                "a2",
+                "Maxine",
+                "Poodle",
+                "Shy at first.",
+                AnimalStatus::Available,
+            ),
+            (
+                "a3",
+                "Biscuit",
+                "Maxi Schnauzer",
+                "Quiet.",
+                AnimalStatus::Available,
+            ),
+            (
+                "a4",
+                "Rover",
+                "Terrier",
+                "Best friends with Max.",
+                AnimalStatus::Adopted,
+            ),
+            (
+                "a5",
+                "100% Pup",
+                "Mixed",
+                "Energetic.",
+                AnimalStatus::Available,
+            ),
+        ] {
+            let mut animal = sample_animal(id);
+            animal.name = name.to_string();
+            animal.breed = breed.to_string();
+            animal.bio = bio.to_string();
+            animal.status = status;
+            db.insert_animal(&animal).unwrap();
+        }
+
+        // Whole names rank above names starting with the text, then breeds, then bios
+        let hits = db.search_animals("max", None, 10).unwrap();
+        let found: Vec<(&str, i64)> = hits
+            .iter()
+            .map(|hit| (hit.record.name.as_str(), hit.score))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("Max", 9), ("Maxine", 6), ("Biscuit", 4), ("Rover", 2)]
+        );
+
+        // Filters narrow the search, and wildcards are matched literally
+        let filters = HashMap::from([(
+            FilterCriteria::Status,
+            Some(FilterValue::ChooseMany(vec!["available".to_string()])),
+        )]);
+        let hits = db.search_animals("max", Some(filters), 10).unwrap();
+        assert_eq!(hits.len(), 3);
+        assert_eq!(db.search_animals("%", None, 10).unwrap().len(), 1);
+        assert_eq!(db.search_animals("max", None, 2).unwrap().len(), 2);
+
+        let mut approved = sample_request("r1", "a4");
+        approved.name = "Maxwell Smart".to_string();
+        approved.status = RequestStatus::Approved;
+        db.insert_adoption_request(&approved).unwrap();
+        db.insert_adoption_request(&sample_request("r2", "a1"))
+            .unwrap();
+
+        let hits = db
+            .search_adoption_requests("smart", &AdoptionRequestFilters::default(), 10)
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].record.id, "r1");
+        assert_eq!(hits[0].record.animal_name, "Rover");
+        assert_eq!(hits[0].score, 6);
+        let pending = AdoptionRequestFilters {
+            status: Some(RequestStatus::Pending),
+            ..Default::default()
+        };
+        assert!(db
+            .search_adoption_requests("smart", &pending, 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    // ==================== INDEX TESTS ====================
+
+    /// Helper function to describe how SQLite plans to run a query
+    ///
+    /// # Arguments
+    /// * `db` - The database service to plan the query on
+    /// * `query` - The SQL query to plan
+    ///
+    /// # Returns
+    /// * `String` - The details of every step of the query plan, one per line
+    fn query_plan(db: &DatabaseService, query: &str) -> String {
+        let mut statement = db
+            .connection
+            .prepare(&format!("EXPLAIN QUERY PLAN {}", query))
+            .unwrap();
+        statement
+            .query_map([], |row| row.get::<_, String>(3))
+            .unwrap()
+            .map(|detail| detail.unwrap())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_check_integrity() {
+        let db = create_test_db("test_check_integrity");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_adoption_request(&sample_request("r1", "a1"))
+            .unwrap();
+
+        let report = db.check_integrity().unwrap();
+        assert!(report.healthy);
+        assert!(report.integrity_errors.is_empty());
+        assert!(report.orphaned_records.is_empty());
+
+        // A request left behind by a deletion that bypassed foreign keys is reported
+        db.connection
+            .pragma_update(None, "foreign_keys", false)
+            .unwrap();
+        db.connection
+            .execute("DELETE FROM animals WHERE id = 'a1'", [])
+            .unwrap();
+        let report = db.check_integrity().unwrap();
+        assert!(!report.healthy);
+        assert_eq!(report.orphaned_records.len(), 1);
+        assert_eq!(report.orphaned_records[0].table, "adoption_requests");
+        assert_eq!(report.orphaned_records[0].id, "r1");
+        assert_eq!(report.orphaned_records[0].animal_id, "a1");
+    }
+
+    #[test]
+    fn test_replace_image_paths() {
+        let db = create_test_db("test_replace_image_paths");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        db.insert_animal(&sample_animal("a2")).unwrap();
+        db.delete_animal("a2", true).unwrap();
+
+        // Both live and trashed animals follow their image to its new path
+        let changed = vec![(
+            "/test/images/buddy.jpg".to_string(),
+            "/test/animals/a1/buddy.jpg".to_string(),
+        )];
+        db.replace_image_paths(&changed).unwrap();
+        let paths = db.query_image_paths().unwrap();
+        assert_eq!(paths.len(), 2);
+        assert!(paths
+            .iter()
+            .all(|(_, image_path)| image_path == "/test/animals/a1/buddy.jpg"));
+    }
+
+    #[test]
+    fn test_set_animal_image() {
+        let db = create_test_db("test_set_animal_image");
+        db.insert_animal(&sample_animal("a1")).unwrap();
+        let before = db.query_animal_by_id("a1").unwrap().unwrap();
+
+        // The previous path is returned so its file can be deleted
+        let (updated, previous) = db
+            .set_animal_image("a1", Some("/test/animals/a1/new.jpg"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(previous, before.image_path);
+        assert_eq!(
+            updated.image_path.as_deref(),
+            Some("/test/animals/a1/new.jpg")
+        );
+        assert_eq!(updated.version, before.version + 1);
+
+        assert!(db.is_image_path_in_use("/test/animals/a1/new.jpg").unwrap());
+        assert!(!db
+            .is_image_path_in_use(before.image_path.as_deref().unwrap())
+            .unwrap());
+        assert!(db.set_animal_image("missing", None).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_run_maintenance() {
+        let db = create_test_db("test_run_maintenance");
+        for index in 0..200 {
+            db.insert_animal(&sample_animal(&format!("a{}", index)))
+                .unwrap();
+        }
+        for index in 0..200 {
+            db.delete_animal(&format!("a{}", index), true).unwrap();
+        }
+        db.purge_expired_trash(Utc::now().timestamp() + 31 * 24 * 60 * 60)
+            .unwrap();
+        assert_eq!(db.query_last_maintenance_timestamp().unwrap(), None);
+
+        // Deleted rows leave free pages that vacuuming gives back
+        let before = Utc::now().timestamp();
+        let result = db.run_maintenance().unwrap();
+        assert!(db.query_last_maintenance_timestamp().unwrap().unwrap() >= before);
+        assert_eq!(result.database, "animal_shelter");
+        assert!(result.reclaimed_bytes > 0);
+        assert_eq!(
+            result.size_before_bytes - result.size_after_bytes,
+            result.reclaimed_bytes
+        );
+    }
+
+    #[test]
+    fn test_indexes_used_by_common_filters() {
+        let db = create_test_db("test_indexes_used_by_common_filters");
+
+        let queries = [
+            (
+                "SELECT id FROM animals WHERE status IN ('available')",
+                "idx_animals_status",
+            ),
+            (
+                "SELECT id FROM animals WHERE sex IN ('Male')",
+                "idx_animals_sex",
+            ),
+            (
+                "SELECT id FROM animals WHERE specie = 'Dog' AND breed IN ('Poodle')",
+                "idx_animals_specie_breed",
+            ),
+            (
+                "SELECT id FROM animals WHERE admission_timestamp >= 0",
+                "idx_animals_admission_timestamp",
+            ),
+            (
+                "SELECT id FROM adoption_requests WHERE animal_id = 'a1'",
+                "idx_adoption_requests_animal_id",
+            ),
+            (
+                "SELECT id FROM adoption_requests WHERE username = 'JiraPit'",
+                "idx_adoption_requests_username",
+            ),
+        ];
+
+        for (query, index) in queries {
+            let plan = query_plan(&db, query);
+            assert!(
+                plan.contains(index),
+                "{} did not use {}: {}",
+                query,
+                index,
+                plan
+            );
+        }
+    }
+
+    /// Benchmarks a filtered animal query with and without indexes.
+    /// Run with `cargo test -- --ignored --nocapture` to see the timings.
+    #[test]
+    #[ignore]
+    fn bench_indexed_status_filter() {
+        let db = create_test_db("bench_indexed_status_filter");
+
+        // Fill the table with mostly available animals and a few adopted ones
+        db.connection.execute_batch("BEGIN").unwrap();
+        for i in 0..20_000 {
+            let mut animal = sample_animal(&format!("a{}", i));
+            if i % 1_000 == 0 {
+                animal.status = AnimalStatus::Adopted;
+            }
+            db.insert_animal(&animal).unwrap();
+        }
+        db.connection.execute_batch("COMMIT").unwrap();
+
+        let mut filters = HashMap::new();
+        filters.insert(
+            FilterCriteria::Status,
+            Some(FilterValue::ChooseMany(vec!["adopted".to_string()])),
+        );
+        let run = |label: &str| {
+            let start = std::time::Instant::now();
+            for _ in 0..100 {
+                assert_eq!(db.query_animals(Some(filters.clone())).unwrap().len(), 20);
+            }
+            let elapsed = start.elapsed();
+            println!("{}: {:?} for 100 queries", label, elapsed);
+            elapsed
+        };
+
+        let indexed = run("with index");
+        db.connection
+            .execute_batch("DROP INDEX idx_animals_status")
+            .unwrap();
+        let unindexed = run("without index");
+        assert!(indexed < unindexed);
+    }
 }
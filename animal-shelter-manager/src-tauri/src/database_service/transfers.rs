@@ -0,0 +1,197 @@
+//
+// database_service/transfers.rs
+//
+// This module provides operations for moving animals between locations and to
+// or from partner organizations, keeping a record of every transfer.
+//
+
+use super::types::{AnimalStatus, RecordTransferResult, Transfer, TransferDirection};
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, OptionalExtension};
+use uuid::Uuid;
+
+impl DatabaseService {
+    // ==================== TRANSFERS TABLE OPERATIONS ====================
+
+    /// Retrieves the transfer history of a specific animal, most recent first
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to retrieve transfers for
+    ///
+    /// # Returns
+    /// * `Result<Vec<Transfer>>` - List of transfers or error
+    pub fn query_transfers_by_animal_id(&self, animal_id: &str) -> Result<Vec<Transfer>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, animal_id, direction, from_location_id, to_location_id, partner_organization, reason, staff_username, transfer_timestamp, contact_id FROM transfers WHERE animal_id = ?1 ORDER BY transfer_timestamp DESC",
+            )
+            .context("Failed to prepare query for transfers by animal ID")?;
+
+        let transfer_iter = statement
+            .query_map(params![animal_id], |row| {
+                Ok(Transfer {
+                    id: row.get(0)?,
+                    animal_id: row.get(1)?,
+                    direction: row.get(2)?,
+                    from_location_id: row.get(3)?,
+                    to_location_id: row.get(4)?,
+                    partner_organization: row.get(5)?,
+                    reason: row.get(6)?,
+                    staff_username: row.get(7)?,
+                    transfer_timestamp: row.get(8)?,
+                    contact_id: row.get(9)?,
+                })
+            })
+            .context("Failed to execute query for transfers by animal ID")?;
+
+        let mut transfers = Vec::new();
+        for transfer in transfer_iter {
+            transfers.push(transfer.context("Failed to parse transfer row")?);
+        }
+
+        log::debug!(
+            "Retrieved {} transfers for animal ID: {}",
+            transfers.len(),
+            animal_id
+        );
+        Ok(transfers)
+    }
+
+    /// Records a transfer and moves the animal accordingly
+    ///
+    /// An outbound transfer either moves the animal to another location, or hands an
+    /// available animal over to a partner organization, marking it as transferred.
+    /// An inbound transfer takes an animal back from a partner organization, making it
+    /// available at the destination location. The location the animal left is taken
+    /// from its current record, and its open kennel assignment ends.
+    ///
+    /// # Arguments
+    /// * `transfer` - The transfer to record; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<RecordTransferResult>` - Whether the transfer was recorded, or why it could not be
+    pub fn record_transfer(&self, transfer: &Transfer) -> Result<RecordTransferResult> {
+        if transfer.reason.trim().is_empty() {
+            bail!("Transfer must give a reason");
+        }
+        if transfer.staff_username.trim().is_empty() {
+            bail!("Transfer must name the staff member who recorded it");
+        }
+        let partner_organization = transfer
+            .partner_organization
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty());
+        match (&transfer.direction, partner_organization) {
+            (TransferDirection::Outbound, Some(_)) if transfer.to_location_id.is_some() => {
+                bail!("Outbound transfer must go to either a location or a partner organization")
+            }
+            (TransferDirection::Outbound, None) if transfer.to_location_id.is_none() => {
+                bail!("Outbound transfer must name its destination")
+            }
+            (TransferDirection::Inbound, None) => {
+                bail!("Inbound transfer must name the partner organization it came from")
+            }
+            _ => {}
+        }
+
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for transfer")?;
+
+        let animal: Option<(AnimalStatus, Option<String>)> = transaction
+            .query_row(
+                "SELECT status, location_id FROM animals WHERE id = ?1",
+                params![transfer.animal_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .context("Failed to read animal status and location")?;
+        let Some((status, from_location_id)) = animal else {
+            return Ok(RecordTransferResult::AnimalNotFound);
+        };
+
+        if let Some(to_location_id) = &transfer.to_location_id {
+            let location_exists: bool = transaction
+                .query_row(
+                    "SELECT EXISTS (SELECT 1 FROM locations WHERE id = ?1)",
+                    params![to_location_id],
+                    |row| row.get(0),
+                )
+                .context("Failed to check destination location")?;
+            if !location_exists {
+                return Ok(RecordTransferResult::LocationNotFound);
+            }
+        }
+
+        // Work out the animal's new status from the kind of transfer
+        let new_status = match (&transfer.direction, partner_organization) {
+            (TransferDirection::Outbound, Some(_)) => match status {
+                AnimalStatus::Available => AnimalStatus::Transferred,
+                _ => return Ok(RecordTransferResult::AnimalNotAvailable),
+            },
+            (TransferDirection::Outbound, None) => match status {
+                AnimalStatus::Available | AnimalStatus::Requested | AnimalStatus::Fostered => {
+                    status
+                }
+                _ => return Ok(RecordTransferResult::AnimalNotAvailable),
+            },
+            (TransferDirection::Inbound, _) => match status {
+                AnimalStatus::Transferred | AnimalStatus::Available => AnimalStatus::Available,
+                _ => return Ok(RecordTransferResult::AnimalNotAvailable),
+            },
+        };
+
+        let id = if transfer.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            transfer.id.clone()
+        };
+
+        transaction
+            .execute(
+                "INSERT INTO transfers (id, animal_id, direction, from_location_id, to_location_id, partner_organization, reason, staff_username, transfer_timestamp, contact_id) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    id,
+                    transfer.animal_id,
+                    transfer.direction,
+                    from_location_id,
+                    transfer.to_location_id,
+                    partner_organization,
+                    transfer.reason.trim(),
+                    transfer.staff_username,
+                    transfer.transfer_timestamp,
+                    transfer.contact_id
+                ],
+            )
+            .context("Failed to insert transfer into database")?;
+
+        transaction
+            .execute(
+                "UPDATE animals SET status = ?2, location_id = ?3, version = version + 1 WHERE id = ?1",
+                params![transfer.animal_id, new_status, transfer.to_location_id],
+            )
+            .context("Failed to move transferred animal")?;
+
+        // The animal no longer occupies the kennel it was in
+        transaction
+            .execute(
+                "UPDATE kennel_assignments SET removed_timestamp = ?2 WHERE animal_id = ?1 AND removed_timestamp IS NULL",
+                params![transfer.animal_id, transfer.transfer_timestamp],
+            )
+            .context("Failed to end kennel assignment of transferred animal")?;
+
+        transaction.commit().context("Failed to commit transfer")?;
+
+        log::info!(
+            "Recorded {} transfer {} for animal with ID: {}",
+            transfer.direction,
+            id,
+            transfer.animal_id
+        );
+        Ok(RecordTransferResult::Success)
+    }
+}
@@ -0,0 +1,497 @@
+//
+// database_service/trash.rs
+//
+// This module provides operations for the trash, which keeps deleted animals
+// and adoption requests for a while so their deletion can be undone. The
+// other records of a deleted animal, such as its vaccinations and notes, are
+// kept alongside it as JSON so they come back when the animal is restored.
+//
+
+use super::types::{TrashItem, TrashItemType};
+use super::{DatabaseService, ANIMAL_COLUMNS, ANIMAL_DEPENDENT_TABLES};
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, params_from_iter, types::Value as SqlValue, Connection, OptionalExtension};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+/// Number of days deleted records stay in the trash before they are purged
+const TRASH_RETENTION_DAYS: i64 = 30;
+
+/// Columns copied between the adoption requests table and its trash table
+const ADOPTION_REQUEST_COLUMNS: &str = "id, animal_id, username, name, email, tel_number, address, occupation, annual_income, num_people, num_children, request_timestamp, adoption_timestamp, status, country, co_applicant_name, co_applicant_relationship, co_applicant_contact";
+
+impl DatabaseService {
+    // ==================== TRASH TABLE OPERATIONS ====================
+
+    /// Retrieves the records in the trash, most recently deleted first
+    ///
+    /// Adoption requests deleted along with their animal are left out, since they are
+    /// restored together with the animal.
+    ///
+    /// # Returns
+    /// * `Result<Vec<TrashItem>>` - List of deleted records or error
+    pub fn query_trash(&self) -> Result<Vec<TrashItem>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT 0, id, name, id, deleted_timestamp FROM trashed_animals UNION ALL SELECT 1, id, name, animal_id, deleted_timestamp FROM trashed_adoption_requests WHERE deleted_with_animal = 0 ORDER BY 5 DESC",
+            )
+            .context("Failed to prepare query for trash")?;
+
+        let item_iter = statement
+            .query_map([], |row| {
+                let kind: i64 = row.get(0)?;
+                let deleted_timestamp: i64 = row.get(4)?;
+                Ok(TrashItem {
+                    item_type: match kind {
+                        0 => TrashItemType::Animal,
+                        _ => TrashItemType::AdoptionRequest,
+                    },
+                    id: row.get(1)?,
+                    name: row.get(2)?,
+                    animal_id: row.get(3)?,
+                    deleted_timestamp,
+                    expires_timestamp: deleted_timestamp + retention_seconds(),
+                })
+            })
+            .context("Failed to execute query for trash")?;
+
+        let mut items = Vec::new();
+        for item in item_iter {
+            items.push(item.context("Failed to parse trash row")?);
+        }
+
+        log::debug!("Retrieved {} records from trash", items.len());
+        Ok(items)
+    }
+
+    /// Restores a deleted record from the trash
+    ///
+    /// Restoring an animal also restores the adoption requests and other records deleted
+    /// along with it.
+    /// An adoption request can only be restored while its animal is in the shelter.
+    ///
+    /// # Arguments
+    /// * `item_type` - Kind of the deleted record
+    /// * `id` - ID of the deleted record
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the record was restored, false if it is not in the trash
+    pub fn restore_from_trash(&self, item_type: TrashItemType, id: &str) -> Result<bool> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for restoring from trash")?;
+
+        match item_type {
+            TrashItemType::Animal => {
+                let restored = transaction
+                    .execute(
+                        &format!(
+                            "INSERT INTO animals ({0}) SELECT {0} FROM trashed_animals WHERE id = ?1",
+                            ANIMAL_COLUMNS
+                        ),
+                        params![id],
+                    )
+                    .context("Failed to restore animal from trash")?;
+                if restored == 0 {
+                    log::warn!("No animal found in trash with ID: {}", id);
+                    return Ok(false);
+                }
+
+                transaction
+                    .execute(
+                        &format!(
+                            "INSERT INTO adoption_requests ({0}) SELECT {0} FROM trashed_adoption_requests WHERE animal_id = ?1 AND deleted_with_animal = 1",
+                            ADOPTION_REQUEST_COLUMNS
+                        ),
+                        params![id],
+                    )
+                    .context("Failed to restore adoption requests of animal from trash")?;
+                transaction
+                    .execute(
+                        "DELETE FROM trashed_adoption_requests WHERE animal_id = ?1 AND deleted_with_animal = 1",
+                        params![id],
+                    )
+                    .context("Failed to remove restored adoption requests from trash")?;
+                restore_animal_records(&transaction, id)?;
+                transaction
+                    .execute("DELETE FROM trashed_animals WHERE id = ?1", params![id])
+                    .context("Failed to remove restored animal from trash")?;
+            }
+            TrashItemType::AdoptionRequest => {
+                let animal_id: Option<String> = transaction
+                    .query_row(
+                        "SELECT animal_id FROM trashed_adoption_requests WHERE id = ?1 AND deleted_with_animal = 0",
+                        params![id],
+                        |row| row.get(0),
+                    )
+                    .optional()
+                    .context("Failed to query adoption request in trash")?;
+                let Some(animal_id) = animal_id else {
+                    log::warn!("No adoption request found in trash with ID: {}", id);
+                    return Ok(false);
+                };
+
+                let animal_exists: bool = transaction
+                    .query_row(
+                        "SELECT EXISTS (SELECT 1 FROM animals WHERE id = ?1)",
+                        params![animal_id],
+                        |row| row.get(0),
+                    )
+                    .context("Failed to check animal of adoption request")?;
+                if !animal_exists {
+                    bail!(
+                        "Cannot restore adoption request with ID {} because animal with ID {} is not in the shelter",
+                        id,
+                        animal_id
+                    );
+                }
+
+                transaction
+                    .execute(
+                        &format!(
+                            "INSERT INTO adoption_requests ({0}) SELECT {0} FROM trashed_adoption_requests WHERE id = ?1",
+                            ADOPTION_REQUEST_COLUMNS
+                        ),
+                        params![id],
+                    )
+                    .context("Failed to restore adoption request from trash")?;
+                transaction
+                    .execute(
+                        "DELETE FROM trashed_adoption_requests WHERE id = ?1",
+                        params![id],
+                    )
+                    .context("Failed to remove restored adoption request from trash")?;
+            }
+        }
+
+        transaction
+            .commit()
+            .context("Failed to commit restoring from trash")?;
+        log::info!("Restored {:?} with ID: {} from trash", item_type, id);
+        Ok(true)
+    }
+
+    /// Permanently removes records that have been in the trash longer than the retention period
+    ///
+    /// # Arguments
+    /// * `now` - The current timestamp
+    ///
+    /// # Returns
+    /// * `Result<(usize, Vec<String>)>` - The number of purged records and the image paths
+    ///   of purged animals, whose files can now be deleted, or error
+    pub fn purge_expired_trash(&self, now: i64) -> Result<(usize, Vec<String>)> {
+        let cutoff = now - retention_seconds();
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for purging trash")?;
+
+        let image_paths = {
+            let mut statement = transaction
+                .prepare(
+                    "SELECT image_path FROM trashed_animals WHERE deleted_timestamp <= ?1 AND image_path IS NOT NULL",
+                )
+                .context("Failed to prepare query for expired image paths")?;
+            let path_iter = statement
+                .query_map(params![cutoff], |row| row.get::<_, String>(0))
+                .context("Failed to execute query for expired image paths")?;
+
+            let mut image_paths = Vec::new();
+            for path in path_iter {
+                image_paths.push(path.context("Failed to parse image path row")?);
+            }
+            image_paths
+        };
+
+        let purged_requests = transaction
+            .execute(
+                "DELETE FROM trashed_adoption_requests WHERE deleted_timestamp <= ?1",
+                params![cutoff],
+            )
+            .context("Failed to purge adoption requests from trash")?;
+        let purged_animals = transaction
+            .execute(
+                "DELETE FROM trashed_animals WHERE deleted_timestamp <= ?1",
+                params![cutoff],
+            )
+            .context("Failed to purge animals from trash")?;
+        transaction
+            .execute(
+                "DELETE FROM trashed_animal_records WHERE animal_id NOT IN (SELECT id FROM trashed_animals)",
+                [],
+            )
+            .context("Failed to purge records of purged animals from trash")?;
+        transaction
+            .execute(
+                "DELETE FROM screening_scores WHERE request_id NOT IN (SELECT id FROM adoption_requests) AND request_id NOT IN (SELECT id FROM trashed_adoption_requests)",
+                [],
+            )
+            .context("Failed to purge screening scores of purged adoption requests")?;
+        for table in ["request_interviews", "reference_checks"] {
+            transaction
+                .execute(
+                    &format!(
+                        "DELETE FROM {} WHERE request_id NOT IN (SELECT id FROM adoption_requests) AND request_id NOT IN (SELECT id FROM trashed_adoption_requests)",
+                        table
+                    ),
+                    [],
+                )
+                .context(format!("Failed to purge {} of purged adoption requests", table))?;
+        }
+
+        transaction
+            .commit()
+            .context("Failed to commit purging trash")?;
+
+        let purged = purged_requests + purged_animals;
+        if purged > 0 {
+            log::info!("Purged {} expired record(s) from trash", purged);
+        }
+        Ok((purged, image_paths))
+    }
+}
+
+/// Copies an animal, its adoption requests and its other records into the trash before
+/// they are deleted
+///
+/// # Arguments
+/// * `connection` - The connection or transaction performing the deletion
+/// * `animal_id` - The ID of the animal being deleted
+/// * `deleted_timestamp` - The timestamp of the deletion
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub(super) fn copy_animal_to_trash(
+    connection: &Connection,
+    animal_id: &str,
+    deleted_timestamp: i64,
+) -> Result<()> {
+    connection
+        .execute(
+            &format!(
+                "INSERT INTO trashed_animals ({0}, deleted_timestamp) SELECT {0}, ?2 FROM animals WHERE id = ?1",
+                ANIMAL_COLUMNS
+            ),
+            params![animal_id, deleted_timestamp],
+        )
+        .context("Failed to move animal to trash")?;
+    connection
+        .execute(
+            &format!(
+                "INSERT INTO trashed_adoption_requests ({0}, deleted_timestamp, deleted_with_animal) SELECT {0}, ?2, 1 FROM adoption_requests WHERE animal_id = ?1",
+                ADOPTION_REQUEST_COLUMNS
+            ),
+            params![animal_id, deleted_timestamp],
+        )
+        .context("Failed to move adoption requests of animal to trash")?;
+
+    // Keep the other records as JSON, so they survive later changes to their tables
+    for table in animal_record_tables() {
+        let record = table_columns(connection, table)?
+            .iter()
+            .map(|column| format!("'{0}', {0}", column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        connection
+            .execute(
+                &format!(
+                    "INSERT INTO trashed_animal_records (animal_id, table_name, record) SELECT ?1, ?2, json_object({}) FROM {} WHERE animal_id = ?1",
+                    record, table
+                ),
+                params![animal_id, table],
+            )
+            .context(format!("Failed to move {} of animal to trash", table))?;
+    }
+    Ok(())
+}
+
+/// Restores the records of an animal kept in the trash, in the reverse of their deletion
+/// order so the records they reference come first
+///
+/// A reference to a record deleted in the meantime is cleared, and a record that cannot
+/// do without it is left out.
+///
+/// # Arguments
+/// * `connection` - The transaction restoring the animal
+/// * `animal_id` - The ID of the animal being restored
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+fn restore_animal_records(connection: &Connection, animal_id: &str) -> Result<()> {
+    for table in animal_record_tables().rev() {
+        let columns = table_columns(connection, table)?;
+        let references = table_references(connection, table)?;
+        let records = {
+            let mut statement = connection
+                .prepare(
+                    "SELECT record FROM trashed_animal_records WHERE animal_id = ?1 AND table_name = ?2 ORDER BY rowid",
+                )
+                .context("Failed to prepare query for trashed records")?;
+            let record_iter = statement
+                .query_map(params![animal_id, table], |row| row.get::<_, String>(0))
+                .context("Failed to execute query for trashed records")?;
+
+            let mut records = Vec::new();
+            for record in record_iter {
+                records.push(record.context("Failed to parse trashed record row")?);
+            }
+            records
+        };
+
+        for record in records {
+            let record: HashMap<String, JsonValue> =
+                serde_json::from_str(&record).context("Failed to parse trashed record")?;
+
+            // Columns added since the deletion keep their defaults, and dropped ones are ignored
+            let mut names = Vec::new();
+            let mut placeholders = Vec::new();
+            let mut values = Vec::new();
+            for column in &columns {
+                let Some(value) = record.get(column) else {
+                    continue;
+                };
+                values.push(sql_value(value));
+                let placeholder = format!("?{}", values.len());
+                placeholders.push(match references.get(column) {
+                    Some((parent, key)) => format!(
+                        "(SELECT {1} FROM {0} WHERE {1} = {2})",
+                        parent, key, placeholder
+                    ),
+                    None => placeholder,
+                });
+                names.push(column.as_str());
+            }
+
+            connection
+                .execute(
+                    &format!(
+                        "INSERT OR IGNORE INTO {} ({}) VALUES ({})",
+                        table,
+                        names.join(", "),
+                        placeholders.join(", ")
+                    ),
+                    params_from_iter(values),
+                )
+                .context(format!("Failed to restore {} of animal from trash", table))?;
+        }
+    }
+
+    connection
+        .execute(
+            "DELETE FROM trashed_animal_records WHERE animal_id = ?1",
+            params![animal_id],
+        )
+        .context("Failed to remove restored records of animal from trash")?;
+    Ok(())
+}
+
+/// Copies an adoption request into the trash before it is deleted
+///
+/// # Arguments
+/// * `connection` - The connection or transaction performing the deletion
+/// * `request_id` - The ID of the adoption request being deleted
+/// * `deleted_timestamp` - The timestamp of the deletion
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub(super) fn copy_adoption_request_to_trash(
+    connection: &Connection,
+    request_id: &str,
+    deleted_timestamp: i64,
+) -> Result<()> {
+    connection
+        .execute(
+            &format!(
+                "INSERT INTO trashed_adoption_requests ({0}, deleted_timestamp) SELECT {0}, ?2 FROM adoption_requests WHERE id = ?1",
+                ADOPTION_REQUEST_COLUMNS
+            ),
+            params![request_id, deleted_timestamp],
+        )
+        .context("Failed to move adoption request to trash")?;
+    Ok(())
+}
+
+/// Returns the tables whose records are kept in `trashed_animal_records`, in deletion
+/// order; adoption requests have a trash table of their own
+fn animal_record_tables() -> impl DoubleEndedIterator<Item = &'static str> {
+    ANIMAL_DEPENDENT_TABLES
+        .iter()
+        .map(|(table, _)| *table)
+        .filter(|table| *table != "adoption_requests")
+}
+
+/// Reads the names of the columns of a table
+///
+/// # Arguments
+/// * `connection` - The database connection
+/// * `table` - The name of the table
+///
+/// # Returns
+/// * `Result<Vec<String>>` - The column names, in table order
+fn table_columns(connection: &Connection, table: &str) -> Result<Vec<String>> {
+    let mut statement = connection
+        .prepare(&format!("PRAGMA table_info({})", table))
+        .context(format!("Failed to read columns of table {}", table))?;
+    let column_iter = statement
+        .query_map([], |row| row.get::<_, String>(1))
+        .context(format!("Failed to read columns of table {}", table))?;
+
+    let mut columns = Vec::new();
+    for column in column_iter {
+        columns.push(column.context("Failed to parse column row")?);
+    }
+    Ok(columns)
+}
+
+/// Reads the foreign keys of a table
+///
+/// # Arguments
+/// * `connection` - The database connection
+/// * `table` - The name of the table
+///
+/// # Returns
+/// * `Result<HashMap<String, (String, String)>>` - The referenced table and column, by column
+fn table_references(
+    connection: &Connection,
+    table: &str,
+) -> Result<HashMap<String, (String, String)>> {
+    let mut statement = connection
+        .prepare(&format!("PRAGMA foreign_key_list({})", table))
+        .context(format!("Failed to read foreign keys of table {}", table))?;
+    let reference_iter = statement
+        .query_map([], |row| {
+            let parent: String = row.get(2)?;
+            let column: String = row.get(3)?;
+            let key: Option<String> = row.get(4)?;
+            Ok((column, (parent, key.unwrap_or_else(|| "id".to_string()))))
+        })
+        .context(format!("Failed to read foreign keys of table {}", table))?;
+
+    let mut references = HashMap::new();
+    for reference in reference_iter {
+        let (column, target) = reference.context("Failed to parse foreign key row")?;
+        references.insert(column, target);
+    }
+    Ok(references)
+}
+
+/// Converts a value read from a trashed record back into an SQL value
+fn sql_value(value: &JsonValue) -> SqlValue {
+    match value {
+        JsonValue::Null => SqlValue::Null,
+        JsonValue::Bool(flag) => SqlValue::Integer(i64::from(*flag)),
+        JsonValue::Number(number) => match number.as_i64() {
+            Some(integer) => SqlValue::Integer(integer),
+            None => SqlValue::Real(number.as_f64().unwrap_or_default()),
+        },
+        JsonValue::String(text) => SqlValue::Text(text.clone()),
+        other => SqlValue::Text(other.to_string()),
+    }
+}
+
+/// Returns how long deleted records stay in the trash, in seconds
+fn retention_seconds() -> i64 {
+    TRASH_RETENTION_DAYS * 24 * 60 * 60
+}
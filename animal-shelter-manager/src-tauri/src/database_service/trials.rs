@@ -0,0 +1,118 @@
+//
+// database_service/trials.rs
+//
+// This module provides operations for adoptions approved on trial, which the
+// adopter can still return before the trial is finalized into an adoption.
+//
+
+use super::types::{AnimalStatus, FinalizeTrialResult, RequestStatus, TrialAdoption, WebhookEvent};
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+
+impl DatabaseService {
+    // ==================== TRIAL_ADOPTIONS TABLE OPERATIONS ====================
+
+    /// Retrieves the trial adoptions that are still ongoing, ending soonest first
+    ///
+    /// # Returns
+    /// * `Result<Vec<TrialAdoption>>` - List of ongoing trials or error
+    pub fn query_ongoing_trial_adoptions(&self) -> Result<Vec<TrialAdoption>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT t.id, t.adoption_request_id, t.animal_id, a.name, r.username, t.started_timestamp, t.end_timestamp FROM trial_adoptions t JOIN adoption_requests r ON r.id = t.adoption_request_id JOIN animals a ON a.id = t.animal_id WHERE r.status = ?1 ORDER BY t.end_timestamp ASC",
+            )
+            .context("Failed to prepare query for ongoing trial adoptions")?;
+
+        let trials = statement
+            .query_map(params![RequestStatus::TrialAdoption], |row| {
+                Ok(TrialAdoption {
+                    id: row.get(0)?,
+                    adoption_request_id: row.get(1)?,
+                    animal_id: row.get(2)?,
+                    animal_name: row.get(3)?,
+                    username: row.get(4)?,
+                    started_timestamp: row.get(5)?,
+                    end_timestamp: row.get(6)?,
+                })
+            })
+            .context("Failed to execute query for ongoing trial adoptions")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse trial adoption row")?;
+
+        log::debug!("Retrieved {} ongoing trial adoptions", trials.len());
+        Ok(trials)
+    }
+
+    /// Turns a trial adoption into a final adoption and marks the animal as adopted
+    ///
+    /// The trial period is kept so the adoption history shows how it started.
+    ///
+    /// # Arguments
+    /// * `adoption_request_id` - The ID of the adoption request on trial
+    ///
+    /// # Returns
+    /// * `Result<FinalizeTrialResult>` - Finalized, or why the trial could not be finalized
+    pub fn finalize_trial_adoption(
+        &self,
+        adoption_request_id: &str,
+    ) -> Result<FinalizeTrialResult> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for trial adoption finalization")?;
+
+        let request: Option<(String, RequestStatus)> = transaction
+            .query_row(
+                "SELECT animal_id, status FROM adoption_requests WHERE id = ?1",
+                params![adoption_request_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .context("Failed to read adoption request")?;
+        let animal_id = match request {
+            None => return Ok(FinalizeTrialResult::NotFound),
+            Some((_, status)) if status != RequestStatus::TrialAdoption => {
+                return Ok(FinalizeTrialResult::NotInTrial)
+            }
+            Some((animal_id, _)) => animal_id,
+        };
+
+        let now = Utc::now().timestamp();
+        transaction
+            .execute(
+                "UPDATE adoption_requests SET status = ?2, adoption_timestamp = ?3 WHERE id = ?1",
+                params![adoption_request_id, RequestStatus::Approved, now],
+            )
+            .context("Failed to finalize trial adoption")?;
+        transaction
+            .execute(
+                "UPDATE animals SET status = ?2, version = version + 1 WHERE id = ?1",
+                params![animal_id, AnimalStatus::Adopted],
+            )
+            .context("Failed to mark animal as adopted")?;
+
+        self.queue_request_status_change(
+            adoption_request_id,
+            &animal_id,
+            &RequestStatus::Approved,
+            now,
+        )?;
+        if let Some(animal) = self.query_animal_by_id(&animal_id)? {
+            self.queue_webhook_event(WebhookEvent::AnimalAdopted, &animal, now)?;
+        }
+
+        transaction
+            .commit()
+            .context("Failed to commit trial adoption finalization")?;
+
+        log::info!(
+            "Finalized trial adoption of animal with ID: {} from adoption request: {}",
+            animal_id,
+            adoption_request_id
+        );
+        Ok(FinalizeTrialResult::Finalized)
+    }
+}
@@ -5,6 +5,7 @@
 // for animals, adoption requests, and their associated data types.
 //
 
+use crate::authentication_service::types::UserAccount;
 use rusqlite::{types::FromSql, ToSql};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -21,8 +22,14 @@ pub enum AnimalStatus {
     Requested,
     /// Animal has been successfully adopted
     Adopted,
+    /// Animal is temporarily living with a foster caregiver
+    Fostered,
     /// Animal has passed away
     PassedAway,
+    /// Animal has been transferred to a partner organization
+    Transferred,
+    /// Animal is living with an adopter for a trial period before the adoption is final
+    TrialAdoption,
 }
 
 /// Implement ToSql and FromSql for AnimalStatus to store it as a string in the database
@@ -42,6 +49,33 @@ impl FromSql for AnimalStatus {
     }
 }
 
+/// Size category of an animal, as visitors usually describe what they are looking for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum AnimalSize {
+    Small,
+    Medium,
+    Large,
+}
+
+/// Implement ToSql and FromSql for AnimalSize to store it as a string in the database
+impl ToSql for AnimalSize {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for AnimalSize {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
 /// Status of an adoption request in the system
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Display, EnumString)]
 #[strum(serialize_all = "kebab-case")]
@@ -53,6 +87,8 @@ pub enum RequestStatus {
     Rejected,
     /// Request has been approved
     Approved,
+    /// Request has been approved for a trial period that has not been finalized yet
+    TrialAdoption,
 }
 
 /// Implement ToSql and FromSql for RequestStatus to store it as a string in the database
@@ -72,6 +108,68 @@ impl FromSql for RequestStatus {
     }
 }
 
+/// How an animal came into the shelter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum IntakeType {
+    /// Animal was found as a stray
+    Stray,
+    /// Animal was surrendered by its owner
+    OwnerSurrender,
+    /// Animal was transferred from another shelter or rescue
+    Transfer,
+    /// Animal was born while in the shelter's care
+    BornInCare,
+}
+
+/// Implement ToSql and FromSql for IntakeType to store it as a string in the database
+impl ToSql for IntakeType {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for IntakeType {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
+/// Category of a staff note about an animal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum NoteCategory {
+    /// Observations about the animal's temperament and behavior
+    Behavior,
+    /// Observations about the animal's health and treatment
+    Medical,
+    /// Anything else worth recording
+    General,
+}
+
+/// Implement ToSql and FromSql for NoteCategory to store it as a string in the database
+impl ToSql for NoteCategory {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for NoteCategory {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
 /// Represents an animal in the shelter system
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -102,6 +200,60 @@ pub struct Animal {
     pub appearance: String,
     /// Bio & Characteristics of the animal
     pub bio: String,
+    /// Version of the record, incremented on every update to detect concurrent edits
+    pub version: i64,
+    /// Number of the animal's identification microchip, unique across animals
+    #[serde(default)]
+    pub microchip_number: Option<String>,
+    /// ID of the location housing the animal (None if the organization has a single site)
+    #[serde(default)]
+    pub location_id: Option<String>,
+    /// Whether the animal gets along with children (None if unknown)
+    #[serde(default)]
+    pub good_with_kids: Option<bool>,
+    /// Whether the animal gets along with dogs (None if unknown)
+    #[serde(default)]
+    pub good_with_dogs: Option<bool>,
+    /// Whether the animal gets along with cats (None if unknown)
+    #[serde(default)]
+    pub good_with_cats: Option<bool>,
+    /// Whether the animal has special needs such as medication or a disability (None if unknown)
+    #[serde(default)]
+    pub special_needs: Option<bool>,
+    /// Size category of the animal (None if not recorded)
+    #[serde(default)]
+    pub size: Option<AnimalSize>,
+    /// Main coat or skin color of the animal, such as "Brown" (None if not recorded)
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Result of updating an animal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", content = "animal", rename_all = "kebab-case")]
+pub enum UpdateAnimalResult {
+    /// The animal was updated; contains the stored record with its new version
+    Updated(Animal),
+    /// No animal exists with the given ID
+    NotFound,
+    /// The animal was changed by someone else since it was read; contains the current record
+    Conflict(Animal),
+}
+
+/// An animal together with the related records shown on its detail page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimalDetail {
+    /// The animal itself
+    pub animal: Animal,
+    /// All adoption requests for the animal, redacted for viewers who may not see them
+    pub adoption_requests: Vec<AdoptionRequestView>,
+    /// The most recent medical note about the animal, if any
+    pub latest_medical_note: Option<AnimalNote>,
+    /// Age of the animal in months (None if the birth date is unknown)
+    pub age_months: Option<i32>,
+    /// How the animal passed away, for staff viewers of an animal that has passed away
+    pub deceased_record: Option<DeceasedRecord>,
 }
 
 /// Simplified animal information for listing views
@@ -160,26 +312,1940 @@ pub struct AdoptionRequest {
     pub status: RequestStatus,
     /// Country of the requester
     pub country: String,
+    /// Second person applying together with the requester, such as a partner (None if applying alone)
+    #[serde(default)]
+    pub co_applicant: Option<CoApplicant>,
 }
 
-/// Represents the criteria available for filtering animals.
-/// This enum is designed to be sent from the TypeScript frontend.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString)]
+/// Second person applying to adopt together with the requester, named on the adoption contract
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoApplicant {
+    /// Full name of the co-applicant
+    pub name: String,
+    /// Relationship of the co-applicant to the requester (e.g., "Spouse")
+    pub relationship: String,
+    /// Email address or telephone number of the co-applicant
+    pub contact: String,
+}
+
+impl AdoptionRequest {
+    /// Gets the names of everyone applying, as they appear on the adoption contract
+    pub fn applicant_names(&self) -> String {
+        match &self.co_applicant {
+            Some(co_applicant) => format!("{} & {}", self.name, co_applicant.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Gets the request without the requester's personal details
+    pub fn redacted(&self) -> RedactedAdoptionRequest {
+        RedactedAdoptionRequest {
+            id: self.id.clone(),
+            animal_id: self.animal_id.clone(),
+            request_timestamp: self.request_timestamp,
+            adoption_timestamp: self.adoption_timestamp,
+            status: self.status.clone(),
+        }
+    }
+}
+
+/// An adoption request as shown to users other than staff and the requester, without
+/// the requester's name, contact details, household or income
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedactedAdoptionRequest {
+    /// Unique identifier for the adoption request
+    pub id: String,
+    /// ID of the animal being requested for adoption
+    pub animal_id: String,
+    /// Timestamp when the request was submitted
+    pub request_timestamp: i64,
+    /// Timestamp when the adoption was completed (0 if not completed)
+    pub adoption_timestamp: i64,
+    /// Current status of the request
+    pub status: RequestStatus,
+}
+
+/// An adoption request as the current user may see it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AdoptionRequestView {
+    /// Every field, for staff members and the requester
+    Full(Box<AdoptionRequest>),
+    /// Only the status and timestamps, for everyone else
+    Redacted(RedactedAdoptionRequest),
+}
+
+/// Contact details a user saves once and reuses for their adoption requests
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserProfile {
+    /// Username of the user the profile belongs to
+    pub username: String,
+    /// Full name of the user
+    pub full_name: String,
+    /// Email address of the user
+    pub email: String,
+    /// Telephone number of the user
+    pub tel_number: String,
+    /// Street address of the user
+    pub address: String,
+    /// Country of the user
+    pub country: String,
+    /// Timestamp when the profile was last updated
+    pub updated_timestamp: i64,
+}
+
+/// Simplified adoption request information for listing views, with the requested animal's details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdoptionRequestSummary {
+    /// Unique identifier for the adoption request
+    pub id: String,
+    /// ID of the animal being requested for adoption
+    pub animal_id: String,
+    /// Name of the animal being requested
+    pub animal_name: String,
+    /// Species of the animal being requested
+    pub animal_specie: String,
+    /// Username of the user who made the request
+    pub username: String,
+    /// Full name of the person requesting adoption
+    pub name: String,
+    /// Email address of the requester
+    pub email: String,
+    /// Timestamp when the request was submitted
+    pub request_timestamp: i64,
+    /// Current status of the request
+    pub status: RequestStatus,
+    /// Screening score of the request, None until it is scored
+    pub screening_score: Option<i64>,
+}
+
+/// An adoption request together with summary information about the requested animal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimalAdoptionRequest {
+    /// Summary of the requested animal
+    pub animal: AnimalSummary,
+    /// The adoption request
+    pub request: AdoptionRequest,
+}
+
+/// Criteria for listing adoption requests; unset fields do not filter
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AdoptionRequestFilters {
+    /// Only include requests with this status
+    pub status: Option<RequestStatus>,
+    /// Only include requests submitted at or after this timestamp
+    pub from_timestamp: Option<i64>,
+    /// Only include requests submitted at or before this timestamp
+    pub to_timestamp: Option<i64>,
+    /// Only include requests for animals of this species
+    pub specie: Option<String>,
+}
+
+/// Result of submitting a new adoption request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CreateRequestResult {
+    /// The request was stored successfully
+    Success,
+    /// The user already has a pending request for the same animal
+    DuplicatePendingRequest,
+}
+
+/// Represents a placement of an animal with a foster caregiver
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FosterPlacement {
+    /// Unique identifier for the placement
+    pub id: String,
+    /// ID of the animal being fostered
+    pub animal_id: String,
+    /// Full name of the foster caregiver
+    pub caregiver_name: String,
+    /// Email address of the foster caregiver
+    pub caregiver_email: String,
+    /// Telephone number of the foster caregiver
+    pub caregiver_tel_number: String,
+    /// Timestamp when the placement started
+    pub start_timestamp: i64,
+    /// Timestamp when the placement is planned to end
+    pub end_timestamp: i64,
+    /// Timestamp when the animal actually returned from foster care (None while ongoing)
+    pub ended_timestamp: Option<i64>,
+}
+
+/// Result of starting a foster placement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StartFosterResult {
+    /// The placement was stored and the animal marked as fostered
+    Success,
+    /// No animal exists with the given ID
+    AnimalNotFound,
+    /// The animal is not available, so it cannot be placed in foster care
+    AnimalNotAvailable,
+}
+
+/// Represents the record of an animal passing away or being euthanized while in the shelter's care
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeceasedRecord {
+    /// Unique identifier for the record
+    pub id: String,
+    /// ID of the animal that passed away
+    pub animal_id: String,
+    /// Timestamp when the animal passed away
+    pub death_timestamp: i64,
+    /// What the animal died of, or why it was euthanized
+    pub cause: String,
+    /// Name of the person who authorized the euthanasia or confirmed the death
+    pub authorized_by: String,
+    /// Any further details worth keeping
+    pub notes: String,
+}
+
+/// Result of recording that an animal passed away
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RecordDeathResult {
+    /// The record was stored and the animal marked as passed away
+    Success,
+    /// No animal exists with the given ID
+    AnimalNotFound,
+    /// The animal is no longer in the shelter's care, such as when it was adopted or transferred
+    AnimalNotInCare,
+    /// The animal has already been recorded as passed away
+    AlreadyDeceased,
+}
+
+/// Represents the record of an animal coming into the shelter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntakeRecord {
+    /// Unique identifier for the intake record
+    pub id: String,
+    /// ID of the animal that came in
+    pub animal_id: String,
+    /// How the animal came into the shelter
+    pub intake_type: IntakeType,
+    /// Where the animal came from, such as a location, owner, or transferring organisation
+    pub source: String,
+    /// Condition of the animal when it came in
+    pub condition: String,
+    /// Username of the staff member who took the animal in, filled in from the logged-in
+    /// user when the intake is recorded
+    #[serde(default)]
+    pub staff_username: String,
+    /// Timestamp when the animal came in
+    pub intake_timestamp: i64,
+    /// Timestamp until which a stray must be held before it can be adopted (None if no hold)
+    #[serde(default)]
+    pub hold_until_timestamp: Option<i64>,
+}
+
+/// A stray being held after intake before it can be adopted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StrayHold {
+    /// ID of the animal on hold
+    pub animal_id: String,
+    /// Name of the animal on hold
+    pub animal_name: String,
+    /// Species of the animal on hold
+    pub specie: String,
+    /// Timestamp when the animal came in
+    pub intake_timestamp: i64,
+    /// Timestamp when the hold ends and the animal can be adopted
+    pub hold_until_timestamp: i64,
+}
+
+/// Represents an adopted animal being returned to the shelter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdoptionReturn {
+    /// Unique identifier for the return
+    pub id: String,
+    /// ID of the approved adoption request the return undoes
+    pub adoption_request_id: String,
+    /// ID of the animal that was returned
+    pub animal_id: String,
+    /// Timestamp when the animal was returned
+    pub return_timestamp: i64,
+    /// Why the animal was returned
+    pub reason: String,
+}
+
+/// Result of processing the return of an adopted animal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProcessReturnResult {
+    /// The return was recorded and the animal is available again
+    Success,
+    /// No adoption request exists with the given ID
+    RequestNotFound,
+    /// The adoption request was never approved nor put on trial, so there is nothing to return
+    RequestNotApproved,
+    /// The adoption has already been returned
+    AlreadyReturned,
+}
+
+/// Represents a timestamped note written by a staff member about an animal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimalNote {
+    /// Unique identifier for the note
+    pub id: String,
+    /// ID of the animal the note is about
+    pub animal_id: String,
+    /// Username of the staff member who wrote the note
+    pub author_username: String,
+    /// Category of the note
+    pub category: NoteCategory,
+    /// Text of the note
+    pub content: String,
+    /// Whether the note is for staff only and hidden from customers
+    pub internal: bool,
+    /// Timestamp when the note was written
+    pub created_timestamp: i64,
+}
+
+/// Represents a vaccination given to an animal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Vaccination {
+    /// Unique identifier for the vaccination
+    pub id: String,
+    /// ID of the animal that was vaccinated
+    pub animal_id: String,
+    /// Name of the vaccine, such as Rabies
+    pub vaccine: String,
+    /// Timestamp when the vaccine was given
+    pub administered_timestamp: i64,
+    /// Timestamp when the next dose is due (None if no further dose is needed)
+    pub due_timestamp: Option<i64>,
+    /// Username of the staff member who recorded the vaccination
+    pub recorded_by: String,
+    /// ID of the veterinarian who gave the vaccine, if known
+    #[serde(default)]
+    pub contact_id: Option<String>,
+}
+
+/// A person staff should not let adopt, recognized by patterns matched against
+/// the contact details of adoption requests
+///
+/// Patterns are matched case-insensitively against the whole field, and `*` matches
+/// any run of characters. Telephone numbers are compared by their digits only.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BannedAdopter {
+    /// Unique identifier for the entry
+    pub id: String,
+    /// Pattern matched against the requester's full name
+    pub name_pattern: Option<String>,
+    /// Pattern matched against the requester's email address
+    pub email_pattern: Option<String>,
+    /// Pattern matched against the requester's telephone number
+    pub tel_number_pattern: Option<String>,
+    /// Why the person was banned
+    pub reason: String,
+    /// Username of the staff member who added the entry
+    pub added_by: String,
+    /// Timestamp when the entry was added
+    pub created_timestamp: i64,
+}
+
+/// Represents one of the sites run by the organization
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    /// Unique identifier for the location
+    pub id: String,
+    /// Name of the location, unique within the organization
+    pub name: String,
+    /// Street address of the location
+    pub address: String,
+}
+
+/// Direction of an animal transfer, seen from the organization
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Display, EnumString)]
 #[strum(serialize_all = "kebab-case")]
 #[serde(rename_all = "kebab-case")]
-pub enum FilterCriteria {
-    Status,
-    Sex,
-    SpeciesAndBreeds,
-    AdmissionDate,
-    AdoptionDate,
+pub enum TransferDirection {
+    /// Animal left a location, for another location or a partner organization
+    Outbound,
+    /// Animal came back from a partner organization
+    Inbound,
 }
 
-/// Represents the different types of values that can be associated with a filter criterion.
+/// Implement ToSql and FromSql for TransferDirection to store it as a string in the database
+impl ToSql for TransferDirection {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for TransferDirection {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
+/// Represents an animal moving between locations, or to or from a partner organization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transfer {
+    /// Unique identifier for the transfer
+    pub id: String,
+    /// ID of the animal that was transferred
+    pub animal_id: String,
+    /// Whether the animal left or came back
+    pub direction: TransferDirection,
+    /// ID of the location the animal left (None if it was not at a location)
+    #[serde(default)]
+    pub from_location_id: Option<String>,
+    /// ID of the location the animal moved to (None if it went to a partner organization)
+    #[serde(default)]
+    pub to_location_id: Option<String>,
+    /// Name of the partner organization the animal went to or came from (None between locations)
+    #[serde(default)]
+    pub partner_organization: Option<String>,
+    /// Why the animal was transferred
+    pub reason: String,
+    /// Username of the staff member who recorded the transfer
+    pub staff_username: String,
+    /// Timestamp when the animal was transferred
+    pub transfer_timestamp: i64,
+    /// ID of the contact at the partner organization or transport partner, if known
+    #[serde(default)]
+    pub contact_id: Option<String>,
+}
+
+/// Result of recording a transfer
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum FilterValue {
-    ChooseOne(String),
-    ChooseMany(Vec<String>),
-    NestedChooseMany(HashMap<String, Vec<String>>),
+#[serde(rename_all = "kebab-case")]
+pub enum RecordTransferResult {
+    /// The transfer was stored and the animal's status and location updated
+    Success,
+    /// No animal exists with the given ID
+    AnimalNotFound,
+    /// The animal's status does not allow this transfer
+    AnimalNotAvailable,
+    /// No location exists with the given destination ID
+    LocationNotFound,
+}
+
+/// Represents a kennel or enclosure that houses animals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Kennel {
+    /// Unique identifier for the kennel
+    pub id: String,
+    /// Name of the kennel, unique within the shelter
+    pub name: String,
+    /// Species the kennel is restricted to (None if any species may use it)
+    pub specie_restriction: Option<String>,
+    /// Maximum number of animals the kennel can house
+    pub capacity: i32,
+}
+
+/// Current occupancy of a kennel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KennelOccupancy {
+    /// The kennel
+    pub kennel: Kennel,
+    /// IDs of the animals currently assigned to the kennel
+    pub animal_ids: Vec<String>,
+}
+
+/// How full the shelter is, overall and for each species
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapacityReport {
+    /// Number of animals all kennels can house together
+    pub total_capacity: i64,
+    /// Number of animals kennels open to any species can house
+    pub shared_capacity: i64,
+    /// Number of animals at the shelter, available or requested
+    pub in_shelter_count: i64,
+    /// Number of animals living with foster caregivers
+    pub foster_count: i64,
+    /// Animals at the shelter as a percentage of the total capacity (None without kennels)
+    pub utilization_percent: Option<f64>,
+    /// Capacity and counts for each species with kennels or animals, ordered by species
+    pub species: Vec<SpecieCapacity>,
+}
+
+/// How full the shelter is for one species
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecieCapacity {
+    /// The species
+    pub specie: String,
+    /// Number of animals kennels restricted to the species can house
+    pub dedicated_capacity: i64,
+    /// Number of animals of the species at the shelter, available or requested
+    pub in_shelter_count: i64,
+    /// Number of animals of the species living with foster caregivers
+    pub foster_count: i64,
+    /// Animals of the species at the shelter as a percentage of the dedicated and shared
+    /// capacity (None if no kennel can house the species)
+    pub utilization_percent: Option<f64>,
+}
+
+/// Result of assigning an animal to a kennel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AssignKennelResult {
+    /// The animal was assigned, leaving any kennel it was in before
+    Success,
+    /// No animal exists with the given ID
+    AnimalNotFound,
+    /// No kennel exists with the given ID
+    KennelNotFound,
+    /// The kennel is restricted to a different species
+    SpecieNotAllowed,
+    /// The kennel is already at capacity
+    KennelFull,
+}
+
+/// Represents a customer waiting to hear about animals of a species or breed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WaitlistEntry {
+    /// Unique identifier for the waitlist entry
+    pub id: String,
+    /// Username of the customer waiting
+    pub username: String,
+    /// Species the customer is waiting for
+    pub specie: String,
+    /// Breed the customer is waiting for (None for any breed of the species)
+    pub breed: Option<String>,
+    /// Timestamp when the customer joined the waitlist
+    pub created_timestamp: i64,
+}
+
+/// Result of joining the waitlist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JoinWaitlistResult {
+    /// The customer was added to the waitlist
+    Success,
+    /// The customer is already waiting for the same species and breed
+    AlreadyJoined,
+}
+
+/// Represents an in-app notification for a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    /// Unique identifier for the notification
+    pub id: String,
+    /// Username of the user the notification is for
+    pub username: String,
+    /// Text of the notification
+    pub message: String,
+    /// ID of the animal the notification is about, if any
+    pub animal_id: Option<String>,
+    /// Timestamp when the notification was created
+    pub created_timestamp: i64,
+    /// Whether the user has read the notification
+    pub read: bool,
+}
+
+/// Number of animals that share a value, such as a status or species
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryCount {
+    /// The shared value
+    pub label: String,
+    /// Number of animals with the value
+    pub count: i64,
+}
+
+/// Number of events that happened in a calendar month
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyCount {
+    /// Calendar year of the month
+    pub year: i32,
+    /// Month of the year (1-12)
+    pub month: u32,
+    /// Number of events in the month
+    pub count: i64,
+}
+
+/// Length of the periods adoption trends are counted over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrendGranularity {
+    /// Calendar days, in UTC
+    Day,
+    /// Weeks starting on Monday
+    Week,
+    /// Calendar months
+    Month,
+}
+
+/// Number of animals taken in and adopted during one period of a trend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendPoint {
+    /// Timestamp of the start of the period
+    pub period_start_timestamp: i64,
+    /// Number of animals admitted during the period
+    pub intake_count: i64,
+    /// Number of adoptions approved during the period
+    pub adoption_count: i64,
+}
+
+/// Aggregate statistics about the shelter, used by the dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShelterStatistics {
+    /// Number of animals for each status
+    pub animals_by_status: Vec<CategoryCount>,
+    /// Number of animals for each species
+    pub animals_by_specie: Vec<CategoryCount>,
+    /// Approved adoptions for each of the last twelve months, oldest first
+    pub adoptions_per_month: Vec<MonthlyCount>,
+    /// Average number of days between admission and adoption (None if no adoptions yet)
+    pub average_length_of_stay_days: Option<f64>,
+    /// Number of adoption requests awaiting review
+    pub pending_request_count: i64,
+}
+
+/// Result of approving an adoption request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "bondedAnimalIds", rename_all = "kebab-case")]
+pub enum ApproveRequestResult {
+    /// The request was approved and the animal marked as adopted
+    Approved,
+    /// The request was approved for a trial period and the animal is on trial with the adopter
+    TrialStarted,
+    /// No adoption request exists with the given ID
+    NotFound,
+    /// The request has already been approved or rejected
+    NotPending,
+    /// The animal is a stray still within the hold period required before adoption
+    OnStrayHold,
+    /// The animal has bonded partners the adopter is not also adopting; contains their IDs
+    BondedPairSplit(Vec<String>),
+    /// A phone interview must be recorded for the request before it can be approved
+    InterviewRequired,
+    /// A referee must give a positive reference before the request can be approved
+    ReferenceCheckRequired,
+}
+
+/// Represents the trial period of an adoption, during which the adopter can still return the animal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrialAdoption {
+    /// Unique identifier for the trial
+    pub id: String,
+    /// ID of the adoption request the trial belongs to
+    pub adoption_request_id: String,
+    /// ID of the animal on trial
+    pub animal_id: String,
+    /// Name of the animal on trial
+    pub animal_name: String,
+    /// Username of the adopter
+    pub username: String,
+    /// Timestamp when the trial started
+    pub started_timestamp: i64,
+    /// Timestamp when the trial is planned to end
+    pub end_timestamp: i64,
+}
+
+/// Result of turning a trial adoption into a final adoption
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FinalizeTrialResult {
+    /// The adoption is final and the animal marked as adopted
+    Finalized,
+    /// No adoption request exists with the given ID
+    NotFound,
+    /// The adoption request is not on trial
+    NotInTrial,
+}
+
+/// Activity of the shelter during a calendar month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyReport {
+    /// Year of the reported month
+    pub year: i32,
+    /// Reported month, from 1 to 12
+    pub month: u32,
+    /// Number of animals admitted during the month
+    pub admission_count: i64,
+    /// Number of recorded intakes during the month, by intake type
+    pub intakes_by_type: Vec<CategoryCount>,
+    /// Number of adoptions approved during the month
+    pub adoption_count: i64,
+    /// Number of adopted animals returned during the month
+    pub return_count: i64,
+    /// Number of animals that passed away or were euthanized during the month
+    pub death_count: i64,
+    /// Number of deaths during the month, by recorded cause
+    pub deaths_by_cause: Vec<CategoryCount>,
+    /// Total of the adoption fees paid during the month, in minor currency units
+    pub fee_revenue: i64,
+    /// Total of the donations received during the month, in minor currency units; always
+    /// zero for a single location
+    pub donation_revenue: i64,
+}
+
+/// Outcome of compacting a database file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceResult {
+    /// Name of the database
+    pub database: String,
+    /// Size of the database before maintenance, in bytes
+    pub size_before_bytes: i64,
+    /// Size of the database after maintenance, in bytes
+    pub size_after_bytes: i64,
+    /// Space freed by maintenance, in bytes
+    pub reclaimed_bytes: i64,
+}
+
+/// Records created by seeding demo data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DemoDataSummary {
+    /// Number of demo animals admitted
+    pub animal_count: usize,
+    /// Number of demo adoption requests filed
+    pub request_count: usize,
+}
+
+/// A record that references an animal which no longer exists
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedRecord {
+    /// Table holding the record
+    pub table: String,
+    /// ID of the record
+    pub id: String,
+    /// ID of the missing animal
+    pub animal_id: String,
+}
+
+/// An animal whose image file cannot be found
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingImage {
+    /// ID of the animal
+    pub animal_id: String,
+    /// Path to the missing image file
+    pub image_path: String,
+}
+
+/// Problems found by a database integrity check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    /// Whether no problems were found
+    pub healthy: bool,
+    /// Corruption reported by SQLite's integrity check
+    pub integrity_errors: Vec<String>,
+    /// Records that reference missing animals
+    pub orphaned_records: Vec<OrphanedRecord>,
+    /// Animals, in the shelter or in the trash, whose image file is missing
+    pub missing_images: Vec<MissingImage>,
+    /// Uploaded files that no animal references
+    pub unreferenced_files: Vec<String>,
+}
+
+/// A problem with one field of a submitted animal or adoption request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldError {
+    /// Name of the invalid field, as used by the frontend
+    pub field: String,
+    /// Description of what is wrong with the field
+    pub message: String,
+}
+
+/// Kind of record held in the trash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrashItemType {
+    Animal,
+    AdoptionRequest,
+}
+
+/// A deleted record waiting in the trash until it is restored or purged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashItem {
+    /// Kind of the deleted record
+    pub item_type: TrashItemType,
+    /// ID of the deleted record
+    pub id: String,
+    /// Name of the animal, or of the applicant for adoption requests
+    pub name: String,
+    /// ID of the animal the record belongs to
+    pub animal_id: String,
+    /// Timestamp when the record was deleted
+    pub deleted_timestamp: i64,
+    /// Timestamp after which the record is purged for good
+    pub expires_timestamp: i64,
+}
+
+/// A record that changed, with the time of its last change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedRecord<T> {
+    /// The record as it is after the change
+    pub record: T,
+    /// Timestamp of the last change to the record
+    pub updated_timestamp: i64,
+}
+
+/// A record that was deleted, identified by its kind and ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedRecord {
+    /// Kind of the deleted record
+    pub item_type: TrashItemType,
+    /// ID of the deleted record
+    pub id: String,
+    /// Timestamp when the record was deleted
+    pub deleted_timestamp: i64,
+}
+
+/// The changes made on one installation, to be applied on another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Changelog {
+    /// Timestamp when the changelog was exported
+    pub exported_timestamp: i64,
+    /// Only changes made at or after this timestamp are included
+    pub since_timestamp: i64,
+    /// Every location, since animals refer to them
+    pub locations: Vec<Location>,
+    /// Animals that changed
+    pub animals: Vec<ChangedRecord<Animal>>,
+    /// Adoption requests that changed
+    pub adoption_requests: Vec<ChangedRecord<AdoptionRequest>>,
+    /// Animals and adoption requests that were deleted and are still in the trash
+    pub deletions: Vec<DeletedRecord>,
+}
+
+/// Outcome of importing a changelog
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportChangesResult {
+    /// Number of animals and adoption requests created or updated
+    pub applied_count: usize,
+    /// Number of changes ignored because the local record changed more recently
+    pub skipped_count: usize,
+    /// Number of animals and adoption requests deleted
+    pub deleted_count: usize,
+}
+
+/// Event that can be sent to webhooks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookEvent {
+    /// A new animal was admitted
+    AnimalCreated,
+    /// An animal was adopted
+    AnimalAdopted,
+    /// An adoption request was filed or changed status
+    RequestStatusChanged,
+}
+
+/// Implement ToSql and FromSql for WebhookEvent to store it as a string in the database
+impl ToSql for WebhookEvent {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for WebhookEvent {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
+/// Represents an external endpoint that receives signed event notifications
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    /// Unique identifier for the webhook
+    pub id: String,
+    /// URL the events are posted to
+    pub url: String,
+    /// Secret used to sign the body of each event
+    pub secret: String,
+    /// Events the webhook subscribes to
+    pub event_types: Vec<WebhookEvent>,
+    /// Timestamp when the webhook was created
+    pub created_timestamp: i64,
+}
+
+/// Delivery status of an event sent to a webhook
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookDeliveryStatus {
+    /// The event has not been delivered yet and will be attempted again
+    Pending,
+    /// The webhook accepted the event
+    Delivered,
+    /// Every attempt failed and the event was given up on
+    Failed,
+}
+
+/// Implement ToSql and FromSql for WebhookDeliveryStatus to store it as a string in the database
+impl ToSql for WebhookDeliveryStatus {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for WebhookDeliveryStatus {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
+/// Represents an event queued for a webhook, along with the outcome of its attempts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    /// Unique identifier for the delivery
+    pub id: String,
+    /// ID of the webhook the event is sent to
+    pub webhook_id: String,
+    /// The event being sent
+    pub event: WebhookEvent,
+    /// JSON body posted to the webhook
+    pub payload: String,
+    /// Whether the event was delivered, is pending, or failed
+    pub status: WebhookDeliveryStatus,
+    /// Number of attempts made so far
+    pub attempts: i64,
+    /// HTTP status code of the last response (None if no response was received)
+    pub response_code: Option<i64>,
+    /// Why the last attempt failed (None if it succeeded or none was made)
+    pub error: Option<String>,
+    /// Timestamp when the event was queued
+    pub created_timestamp: i64,
+    /// Timestamp of the last attempt (None if none was made)
+    pub attempted_timestamp: Option<i64>,
+    /// Timestamp after which the next attempt is due
+    pub next_attempt_timestamp: i64,
+}
+
+/// A pending delivery that is due, with what is needed to send it
+#[derive(Debug, Clone)]
+pub struct DueWebhookDelivery {
+    /// The delivery to attempt
+    pub delivery: WebhookDelivery,
+    /// URL the event is posted to
+    pub url: String,
+    /// Secret used to sign the payload
+    pub secret: String,
+}
+
+/// A rule of the reminder scheduler, each reminding staff once per record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum ReminderRule {
+    /// A vaccination's next dose is due soon or overdue
+    VaccinationDue,
+    /// An animal has been at the shelter for a long time
+    LengthOfStay,
+    /// An adoption request has been pending for a long time
+    PendingRequest,
+    /// A confirmed meet-and-greet appointment starts soon
+    AppointmentUpcoming,
+}
+
+/// Implement ToSql for ReminderRule to store it as a string in the database
+impl ToSql for ReminderRule {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+
+/// When the reminder rules apply, each in days, or None if the rule is turned off
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReminderThresholds {
+    /// Days ahead of a vaccination's due date
+    pub vaccination_days: Option<u32>,
+    /// Days an animal has been at the shelter
+    pub length_of_stay_days: Option<u32>,
+    /// Days a request has been pending
+    pub pending_request_days: Option<u32>,
+    /// Hours ahead of a confirmed meet-and-greet appointment
+    pub appointment_hours: Option<u32>,
+}
+
+/// An available animal that has been in the shelter's care longer than a threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LongStayAnimal {
+    /// The animal
+    #[serde(flatten)]
+    pub animal: AnimalSummary,
+    /// Whole days since the animal was admitted
+    pub days_in_care: i64,
+}
+
+/// Send status of an email to an applicant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum EmailStatus {
+    /// The email is waiting to be sent
+    Pending,
+    /// The SMTP server accepted the email
+    Sent,
+    /// The email could not be sent; it can be resent by staff
+    Failed,
+}
+
+/// Implement ToSql and FromSql for EmailStatus to store it as a string in the database
+impl ToSql for EmailStatus {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for EmailStatus {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
+/// Represents an email telling an applicant their adoption request changed status,
+/// along with the outcome of the attempts to send it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailMessage {
+    /// Unique identifier for the email
+    pub id: String,
+    /// ID of the adoption request the email is about
+    pub request_id: String,
+    /// The status the request changed to, which decides the template used
+    pub request_status: RequestStatus,
+    /// Email address of the applicant
+    pub recipient: String,
+    /// Name of the applicant, as when the email was queued
+    pub recipient_name: String,
+    /// Name of the requested animal, as when the email was queued
+    pub animal_name: String,
+    /// Subject of the last attempt (None if none was made)
+    pub subject: Option<String>,
+    /// Body of the last attempt (None if none was made)
+    pub body: Option<String>,
+    /// Whether the email was sent, is pending, or failed
+    pub status: EmailStatus,
+    /// Number of attempts made so far
+    pub attempts: i64,
+    /// Why the last attempt failed (None if it succeeded or none was made)
+    pub error: Option<String>,
+    /// Timestamp when the email was queued
+    pub created_timestamp: i64,
+    /// Timestamp of the last attempt (None if none was made)
+    pub attempted_timestamp: Option<i64>,
+}
+
+/// Kind of dated event put on the shelter calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum CalendarEventKind {
+    /// The next dose of a vaccine is due
+    VaccinationDue,
+    /// A fostered animal is due back at the shelter
+    FosterReturnDue,
+    /// A customer is booked to meet an animal
+    Appointment,
+    /// An animal is booked for its spay or neuter surgery
+    Surgery,
+}
+
+/// A dated event on the shelter calendar, taken from the record it is about
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEvent {
+    /// Kind of the event
+    pub kind: CalendarEventKind,
+    /// ID of the record the event comes from, such as the vaccination
+    pub record_id: String,
+    /// ID of the animal the event is about
+    pub animal_id: String,
+    /// Name of the animal the event is about
+    pub animal_name: String,
+    /// What the event concerns: the vaccine, the foster caregiver's name, the username
+    /// of the customer booked to meet the animal, or the veterinarian's name, if known
+    pub subject: String,
+    /// Timestamp when the event is due
+    pub timestamp: i64,
+}
+
+/// Represents the criteria available for filtering animals.
+/// This enum is designed to be sent from the TypeScript frontend.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum FilterCriteria {
+    Status,
+    Sex,
+    SpeciesAndBreeds,
+    AdmissionDate,
+    AdoptionDate,
+    Neutered,
+    HasImage,
+    Location,
+    GoodWithKids,
+    GoodWithDogs,
+    GoodWithCats,
+    SpecialNeeds,
+    Size,
+    Color,
+    Age,
+}
+
+/// Represents the different types of values that can be associated with a filter criterion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FilterValue {
+    ChooseOne(String),
+    ChooseMany(Vec<String>),
+    NestedChooseMany(HashMap<String, Vec<String>>),
+}
+
+/// Kind of record the global search looks through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SearchScope {
+    /// Animals, by name, breed and bio
+    Animals,
+    /// Adoption requests, by applicant name, username and email
+    Requests,
+    /// User accounts, by username
+    Users,
+}
+
+/// A record found by the global search, with how well it matched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit<T> {
+    /// The record found
+    #[serde(flatten)]
+    pub record: T,
+    /// How well the record matched; higher is better
+    pub score: i64,
+}
+
+/// Records found by the global search, grouped by kind, best matches first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResults {
+    /// Animals found
+    pub animals: Vec<SearchHit<AnimalSummary>>,
+    /// Adoption requests found
+    pub requests: Vec<SearchHit<AdoptionRequestSummary>>,
+    /// User accounts found, for admins only
+    pub users: Vec<SearchHit<UserAccount>>,
+}
+
+/// Everything the shelter stores about a customer, as exported to them on request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserDataExport {
+    /// Username of the customer
+    pub username: String,
+    /// Timestamp when the export was made
+    pub exported_timestamp: i64,
+    /// The contact details saved in the customer's profile, if any
+    pub profile: Option<UserProfile>,
+    /// Every adoption request the customer has made
+    pub adoption_requests: Vec<AdoptionRequest>,
+    /// The animals the customer has favorited
+    pub favorites: Vec<AnimalSummary>,
+    /// The meet-and-greet appointments the customer has booked
+    pub appointments: Vec<Appointment>,
+}
+
+/// Review status of a request to erase a customer's personal data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum ErasureRequestStatus {
+    /// Waiting for an admin to review it
+    Pending,
+    /// Approved by an admin; the customer's data has been erased
+    Approved,
+    /// Rejected by an admin; nothing was erased
+    Rejected,
+}
+
+/// Implement ToSql and FromSql for ErasureRequestStatus to store it as a string in the database
+impl ToSql for ErasureRequestStatus {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for ErasureRequestStatus {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
+/// A customer's request to have their personal data erased
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErasureRequest {
+    /// Unique identifier for the erasure request
+    pub id: String,
+    /// Username of the customer whose data is to be erased
+    pub username: String,
+    /// Review status of the request
+    pub status: ErasureRequestStatus,
+    /// Timestamp when the customer made the request
+    pub requested_timestamp: i64,
+    /// Username of the admin who reviewed the request, if it has been reviewed
+    pub reviewed_by: Option<String>,
+    /// Timestamp when the request was reviewed, if it has been
+    pub reviewed_timestamp: Option<i64>,
+}
+
+/// Result of asking for a customer's data to be erased
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", content = "request", rename_all = "kebab-case")]
+pub enum CreateErasureResult {
+    /// The request was stored and waits for an admin; contains the stored request
+    Created(ErasureRequest),
+    /// The customer already has a request waiting for review; contains that request
+    AlreadyPending(ErasureRequest),
+}
+
+/// Result of reviewing an erasure request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", content = "request", rename_all = "kebab-case")]
+pub enum ReviewErasureResult {
+    /// The request was approved or rejected; contains the reviewed request
+    Reviewed(ErasureRequest),
+    /// No erasure request exists with the given ID
+    NotFound,
+    /// The request was already reviewed; contains it as it stands
+    AlreadyReviewed(ErasureRequest),
+}
+
+/// Something a staff member did, as recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditAction {
+    /// Added an animal
+    AnimalCreated,
+    /// Edited an animal
+    AnimalUpdated,
+    /// Moved an animal to the trash
+    AnimalDeleted,
+    /// Approved an adoption request
+    RequestApproved,
+    /// Approved an adoption request for a trial period
+    TrialStarted,
+    /// Turned a trial adoption into a final adoption
+    TrialFinalized,
+    /// Rejected an adoption request
+    RequestRejected,
+    /// Edited an adoption request, other than rejecting it
+    RequestUpdated,
+    /// Moved an adoption request to the trash
+    RequestDeleted,
+    /// Took back an adopted animal
+    ReturnProcessed,
+    /// Added a note to an animal
+    NoteAdded,
+    /// Recorded that an animal passed away
+    DeathRecorded,
+    /// Recorded that an animal's spay or neuter surgery was performed
+    SurgeryCompleted,
+    /// Recorded a phone interview with the applicant of an adoption request
+    InterviewRecorded,
+    /// Recorded a reference check for an adoption request, or its outcome
+    ReferenceChecked,
+    /// Charged a fee for an adoption request
+    PaymentRecorded,
+    /// Recorded that a fee charged for an adoption request was paid
+    PaymentReceived,
+}
+
+impl AuditAction {
+    /// Whether the action was taken on an adoption request, rather than an animal
+    pub fn is_about_request(self) -> bool {
+        matches!(
+            self,
+            AuditAction::RequestApproved
+                | AuditAction::TrialStarted
+                | AuditAction::TrialFinalized
+                | AuditAction::RequestRejected
+                | AuditAction::RequestUpdated
+                | AuditAction::RequestDeleted
+                | AuditAction::ReturnProcessed
+                | AuditAction::InterviewRecorded
+                | AuditAction::ReferenceChecked
+                | AuditAction::PaymentRecorded
+                | AuditAction::PaymentReceived
+        )
+    }
+}
+
+/// Implement ToSql and FromSql for AuditAction to store it as a string in the database
+impl ToSql for AuditAction {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for AuditAction {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
+/// An entry of the audit log, recording who did what to which record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// Unique identifier for the entry
+    pub id: String,
+    /// Username of the staff member who acted
+    pub username: String,
+    /// What they did
+    pub action: AuditAction,
+    /// ID of the animal or adoption request they acted on
+    pub record_id: String,
+    /// ID of the animal concerned, if any
+    pub animal_id: Option<String>,
+    /// Name of that animal when they acted, if it could be found
+    pub animal_name: Option<String>,
+    /// Timestamp when they acted
+    pub timestamp: i64,
+}
+
+/// An audit log entry described in words, for the activity feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityFeedItem {
+    /// The entry
+    #[serde(flatten)]
+    pub entry: AuditEntry,
+    /// What happened, such as "jane approved request r14 for Buddy"
+    pub message: String,
+}
+
+/// A page of the activity feed, most recent first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityFeed {
+    /// The entries of the page
+    pub items: Vec<ActivityFeedItem>,
+    /// Number of entries in the whole feed
+    pub total: i64,
+}
+
+/// Points added to the screening score of an applicant earning at least an annual income
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeBand {
+    /// Lowest annual income in the band
+    pub min_income: u64,
+    /// Points added for an income in the band
+    pub points: i64,
+}
+
+/// How adoption requests are scored so staff can triage them; the score is the sum of
+/// the points for each part of the request, and points may be negative
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreeningConfig {
+    /// Points for each person in the household
+    pub points_per_person: i64,
+    /// Points for each child in the household
+    pub points_per_child: i64,
+    /// Income bands; the points of the highest band the income reaches are added
+    pub income_bands: Vec<IncomeBand>,
+    /// Points for each animal the applicant adopted before and returned
+    pub points_per_return: i64,
+}
+
+/// Checks an adoption request must pass before it can be approved, as set by an admin
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequirements {
+    /// Whether a phone interview with the applicant must have been recorded
+    pub interview: bool,
+    /// Whether a referee must have given a positive reference
+    pub reference_check: bool,
+}
+
+/// A supply kept in stock, such as food, litter or medicine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryItem {
+    /// Unique identifier for the item
+    pub id: String,
+    /// Name of the item, unique within the shelter
+    pub name: String,
+    /// Unit the quantity is counted in, such as "kg" or "boxes"
+    pub unit: String,
+    /// Quantity currently in stock
+    pub quantity_on_hand: f64,
+    /// Quantity at or below which the item should be reordered
+    pub reorder_threshold: f64,
+}
+
+/// A change to the stock of an inventory item, kept so shortages can be traced
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryAdjustment {
+    /// Unique identifier for the adjustment
+    pub id: String,
+    /// ID of the adjusted item
+    pub item_id: String,
+    /// Quantity added to the stock, negative when stock was used or discarded
+    pub quantity_change: f64,
+    /// Why the stock changed, such as "Delivery" or "Expired"
+    pub reason: String,
+    /// Username of the staff member who adjusted the stock
+    pub username: String,
+    /// Timestamp when the stock was adjusted
+    pub timestamp: i64,
+}
+
+/// Result of adjusting the stock of an inventory item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "item", rename_all = "kebab-case")]
+pub enum AdjustStockResult {
+    /// The stock was adjusted; contains the item with its new quantity
+    Adjusted(InventoryItem),
+    /// No item exists with the given ID
+    NotFound,
+    /// The change would take the stock below zero; contains the item with its current quantity
+    InsufficientStock(InventoryItem),
+}
+
+/// What money was spent on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum ExpenseCategory {
+    /// Food and treats
+    Food,
+    /// Veterinary care and medicine
+    Veterinary,
+    /// Litter, bedding, toys and other supplies
+    Supplies,
+    /// Rent, utilities and repairs of the shelter's buildings
+    Facilities,
+    /// Fuel and vehicles used to move animals
+    Transport,
+    /// Anything else
+    Other,
+}
+
+/// Implement ToSql and FromSql for ExpenseCategory to store it as a string in the database
+impl ToSql for ExpenseCategory {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for ExpenseCategory {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
+/// Money spent by the shelter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Expense {
+    /// Unique identifier for the expense
+    pub id: String,
+    /// What the money was spent on
+    pub category: ExpenseCategory,
+    /// Amount spent, in minor units of the shelter's default currency, such as satang
+    pub amount: i64,
+    /// Timestamp when the money was spent
+    pub expense_timestamp: i64,
+    /// Who was paid, such as a supplier or veterinary clinic
+    pub vendor: String,
+    /// ID of the animal the money was spent on, if it was spent on a single animal
+    pub animal_id: Option<String>,
+}
+
+/// Amount spent on one category of expenses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryAmount {
+    /// The category
+    pub category: ExpenseCategory,
+    /// Amount spent on the category, in minor currency units
+    pub amount: i64,
+}
+
+/// Money spent during a calendar month
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyExpenses {
+    /// Month of the year (1-12)
+    pub month: u32,
+    /// Amount spent during the month, in minor currency units
+    pub total: i64,
+    /// Amount spent during the month on each category, largest first
+    pub by_category: Vec<CategoryAmount>,
+    /// Amount donated during the month, in minor currency units
+    pub donations: i64,
+    /// Amount donated minus amount spent during the month; negative when more was spent
+    pub net: i64,
+}
+
+/// Money spent and donated during a calendar year, month by month
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetReport {
+    /// The reported year
+    pub year: i32,
+    /// Expenses of each month of the year, January first
+    pub months: Vec<MonthlyExpenses>,
+    /// Amount spent during the whole year, in minor currency units
+    pub total: i64,
+    /// Amount donated during the whole year, in minor currency units
+    pub donation_total: i64,
+    /// Amount donated minus amount spent during the whole year; negative when more was spent
+    pub net: i64,
+}
+
+/// Kind of outside contact the shelter works with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum ContactKind {
+    /// A veterinarian or veterinary clinic
+    Veterinarian,
+    /// A rescue or another shelter that takes in animals
+    Rescue,
+    /// Someone who moves animals between sites and organizations
+    TransportPartner,
+    /// A person or organization that donates to the shelter
+    Donor,
+    /// Anyone else
+    Other,
+}
+
+/// Implement ToSql and FromSql for ContactKind to store it as a string in the database
+impl ToSql for ContactKind {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for ContactKind {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
+/// An outside person or organization the shelter works with, such as a vet or rescue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Contact {
+    /// Unique identifier for the contact
+    pub id: String,
+    /// Name of the person or organization
+    pub name: String,
+    /// Kind of contact
+    pub kind: ContactKind,
+    /// Telephone number of the contact
+    pub tel_number: String,
+    /// Email address of the contact
+    pub email: String,
+    /// Postal address of the contact
+    pub address: String,
+    /// Anything else worth knowing, such as opening hours
+    pub notes: String,
+}
+
+/// Where a meet-and-greet appointment is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum AppointmentStatus {
+    /// Booked by the customer, waiting for staff to confirm it
+    Requested,
+    /// Confirmed by staff
+    Confirmed,
+    /// Cancelled by the customer or by staff
+    Cancelled,
+}
+
+/// Implement ToSql and FromSql for AppointmentStatus to store it as a string in the database
+impl ToSql for AppointmentStatus {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for AppointmentStatus {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
+/// A time booked by a customer to meet an animal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Appointment {
+    /// Unique identifier for the appointment
+    pub id: String,
+    /// ID of the animal to meet
+    pub animal_id: String,
+    /// Name of the animal to meet
+    pub animal_name: String,
+    /// Username of the customer who booked the appointment
+    pub username: String,
+    /// Timestamp when the appointment starts
+    pub slot_timestamp: i64,
+    /// Where the appointment is in its lifecycle
+    pub status: AppointmentStatus,
+    /// Timestamp when the appointment was booked
+    pub created_timestamp: i64,
+}
+
+/// Result of booking a meet-and-greet appointment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RequestAppointmentResult {
+    /// The appointment was booked and waits for staff to confirm it
+    Requested,
+    /// No animal exists with the given ID
+    AnimalNotFound,
+    /// The animal can no longer be adopted, so there is nobody to meet
+    AnimalNotAvailable,
+    /// The animal or the customer already has an appointment overlapping the slot
+    SlotTaken,
+}
+
+/// Result of confirming a meet-and-greet appointment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfirmAppointmentResult {
+    /// The appointment was confirmed and the customer notified
+    Confirmed,
+    /// No appointment exists with the given ID
+    NotFound,
+    /// The appointment was already confirmed or was cancelled
+    NotRequested,
+}
+
+/// Result of cancelling a meet-and-greet appointment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CancelAppointmentResult {
+    /// The appointment was cancelled
+    Cancelled,
+    /// No appointment exists with the given ID for the user
+    NotFound,
+    /// The appointment was already cancelled
+    AlreadyCancelled,
+}
+
+/// A spay or neuter surgery booked for an animal that is not neutered yet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Surgery {
+    /// Unique identifier for the surgery
+    pub id: String,
+    /// ID of the animal having the surgery
+    pub animal_id: String,
+    /// Name of the animal having the surgery
+    pub animal_name: String,
+    /// Timestamp when the surgery is booked for
+    pub scheduled_timestamp: i64,
+    /// ID of the veterinarian in the contacts directory performing the surgery, if known
+    pub contact_id: Option<String>,
+    /// Timestamp when the surgery was performed, or None while it is still to come
+    pub completed_timestamp: Option<i64>,
+    /// Anything worth knowing, such as fasting instructions
+    pub notes: String,
+}
+
+/// Result of booking a spay or neuter surgery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScheduleSurgeryResult {
+    /// The surgery was booked
+    Scheduled,
+    /// No animal exists with the given ID
+    AnimalNotFound,
+    /// The animal is already neutered
+    AlreadyNeutered,
+    /// The animal already has a surgery that has not been performed yet
+    AlreadyScheduled,
+}
+
+/// Result of recording that a spay or neuter surgery was performed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "surgery", rename_all = "kebab-case")]
+pub enum CompleteSurgeryResult {
+    /// The surgery was recorded as performed and the animal marked as neutered; contains
+    /// the completed surgery
+    Completed(Surgery),
+    /// No surgery exists with the given ID
+    NotFound,
+    /// The surgery was already recorded as performed
+    AlreadyCompleted,
+}
+
+/// A phone interview staff held with the applicant of an adoption request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestInterview {
+    /// Unique identifier for the interview
+    pub id: String,
+    /// ID of the adoption request the interview is about
+    pub request_id: String,
+    /// Username of the staff member who held the interview
+    pub interviewer: String,
+    /// Timestamp when the interview was held
+    pub interview_timestamp: i64,
+    /// What was discussed and the impression the applicant made
+    pub notes: String,
+}
+
+/// What a referee said about an applicant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum ReferenceOutcome {
+    /// The referee has not been reached yet
+    Pending,
+    /// The referee recommended the applicant
+    Positive,
+    /// The referee raised concerns about the applicant
+    Negative,
+    /// The referee could not be reached
+    Unreachable,
+}
+
+/// Implement ToSql and FromSql for ReferenceOutcome to store it as a string in the database
+impl ToSql for ReferenceOutcome {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.to_string()))
+    }
+}
+impl FromSql for ReferenceOutcome {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value)?.parse().map_err(|e| {
+            rusqlite::types::FromSqlError::Other(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            )))
+        })
+    }
+}
+
+/// A reference given by an applicant of an adoption request, and the outcome of checking it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceCheck {
+    /// Unique identifier for the reference check
+    pub id: String,
+    /// ID of the adoption request the reference was given for
+    pub request_id: String,
+    /// Name of the referee, such as a former landlord or veterinarian
+    pub referee_name: String,
+    /// Email address or telephone number the referee can be reached at
+    pub referee_contact: String,
+    /// What the referee said about the applicant
+    pub outcome: ReferenceOutcome,
+    /// Anything worth knowing about what the referee said
+    pub notes: String,
+    /// Username of the staff member who last recorded the check
+    pub checked_by: String,
+    /// Timestamp when the check was last recorded
+    pub checked_timestamp: i64,
+}
+
+/// The interviews and reference checks recorded for an adoption request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestChecks {
+    /// Phone interviews with the applicant, latest first
+    pub interviews: Vec<RequestInterview>,
+    /// References given by the applicant, in the order they were recorded
+    pub reference_checks: Vec<ReferenceCheck>,
+}
+
+/// Result of recording an interview or reference check for an adoption request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RecordCheckResult {
+    /// The interview or reference check was recorded
+    Recorded,
+    /// No adoption request exists with the given ID
+    RequestNotFound,
+}
+
+/// An adoption fee charged to the applicant of an adoption request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Payment {
+    /// Unique identifier for the payment
+    pub id: String,
+    /// ID of the adoption request the fee is charged for
+    pub request_id: String,
+    /// Amount charged, in minor units of the shelter's default currency, such as satang
+    pub amount: i64,
+    /// What the fee is for, such as the adoption fee and microchip registration
+    pub description: String,
+    /// Timestamp when the fee was charged
+    pub created_timestamp: i64,
+    /// Timestamp when the fee was paid, if it has been
+    pub paid_timestamp: Option<i64>,
+    /// Number of the invoice issued for the fee, once one has been generated
+    pub invoice_number: Option<i64>,
+    /// Number of the receipt issued for the fee, once one has been generated
+    pub receipt_number: Option<i64>,
+}
+
+/// A numbered document issued for a payment, each kind numbered in its own sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum PaymentDocument {
+    /// Asks the applicant to pay the fee
+    Invoice,
+    /// Acknowledges that the fee was paid
+    Receipt,
+}
+
+impl PaymentDocument {
+    /// Formats the number of a document the way it is printed, such as INV-000042
+    ///
+    /// # Arguments
+    /// * `number` - The number of the document in its sequence
+    ///
+    /// # Returns
+    /// * `String` - The number with the prefix of the kind of document
+    pub fn format_number(self, number: i64) -> String {
+        let prefix = match self {
+            PaymentDocument::Invoice => "INV",
+            PaymentDocument::Receipt => "RCT",
+        };
+        format!("{}-{:06}", prefix, number)
+    }
+}
+
+/// Result of charging a fee for an adoption request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RecordPaymentResult {
+    /// The fee was recorded
+    Recorded,
+    /// No adoption request exists with the given ID
+    RequestNotFound,
+}
+
+/// A gift of money a donor made to the shelter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Donation {
+    /// Unique identifier for the donation
+    pub id: String,
+    /// ID of the donor's contact, or None if the contact was deleted since
+    pub contact_id: Option<String>,
+    /// Name of the donor when the donation was made, filled in from their contact
+    pub donor_name: String,
+    /// Amount donated, in minor units of the shelter's default currency, such as satang
+    pub amount: i64,
+    /// Timestamp when the donation was received
+    pub donation_timestamp: i64,
+    /// Anything worth knowing, such as a fundraiser the donation was made for
+    pub notes: String,
+    /// Number of the receipt issued for the donation, once one has been generated
+    pub receipt_number: Option<i64>,
+}
+
+impl Donation {
+    /// Formats the number of a donation receipt the way it is printed, such as DON-000042
+    ///
+    /// # Arguments
+    /// * `number` - The number of the receipt in its sequence
+    ///
+    /// # Returns
+    /// * `String` - The number with the prefix of donation receipts
+    pub fn format_receipt_number(number: i64) -> String {
+        format!("DON-{:06}", number)
+    }
+}
+
+/// The donations a donor made during a year, summed for their tax return
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DonorStatement {
+    /// The donor
+    pub donor: Contact,
+    /// The year the statement covers
+    pub year: i32,
+    /// Donations received during the year, in the order they were received
+    pub donations: Vec<Donation>,
+    /// Sum of the donations received during the year, in minor currency units
+    pub total: i64,
 }
@@ -0,0 +1,133 @@
+//
+// database_service/vaccinations.rs
+//
+// This module provides operations for recording the vaccinations given to
+// animals and when their next doses are due.
+//
+
+use super::types::Vaccination;
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use rusqlite::params;
+use uuid::Uuid;
+
+impl DatabaseService {
+    // ==================== VACCINATIONS TABLE OPERATIONS ====================
+
+    /// Retrieves the vaccinations of a specific animal, most recent first
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the animal to retrieve vaccinations for
+    ///
+    /// # Returns
+    /// * `Result<Vec<Vaccination>>` - List of vaccinations or error
+    pub fn query_vaccinations_by_animal_id(&self, animal_id: &str) -> Result<Vec<Vaccination>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, animal_id, vaccine, administered_timestamp, due_timestamp, recorded_by, contact_id FROM vaccinations WHERE animal_id = ?1 ORDER BY administered_timestamp DESC",
+            )
+            .context("Failed to prepare query for vaccinations by animal ID")?;
+
+        let vaccinations = statement
+            .query_map(params![animal_id], |row| {
+                Ok(Vaccination {
+                    id: row.get(0)?,
+                    animal_id: row.get(1)?,
+                    vaccine: row.get(2)?,
+                    administered_timestamp: row.get(3)?,
+                    due_timestamp: row.get(4)?,
+                    recorded_by: row.get(5)?,
+                    contact_id: row.get(6)?,
+                })
+            })
+            .context("Failed to execute query for vaccinations by animal ID")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse vaccination row")?;
+
+        log::debug!(
+            "Retrieved {} vaccinations for animal ID: {}",
+            vaccinations.len(),
+            animal_id
+        );
+        Ok(vaccinations)
+    }
+
+    /// Inserts a new vaccination into the database
+    ///
+    /// # Arguments
+    /// * `vaccination` - The vaccination to insert; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<String>` - The ID of the inserted vaccination or error
+    pub fn insert_vaccination(&self, vaccination: &Vaccination) -> Result<String> {
+        let vaccine = vaccination.vaccine.trim();
+        if vaccine.is_empty() {
+            bail!("Vaccine name cannot be empty");
+        }
+        if vaccination
+            .due_timestamp
+            .is_some_and(|due| due <= vaccination.administered_timestamp)
+        {
+            bail!("The next dose must be due after the vaccination was given");
+        }
+
+        let id = if vaccination.id.trim().is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            vaccination.id.clone()
+        };
+
+        self.connection
+            .execute(
+                "INSERT INTO vaccinations (id, animal_id, vaccine, administered_timestamp, due_timestamp, recorded_by, contact_id) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    id,
+                    vaccination.animal_id,
+                    vaccine,
+                    vaccination.administered_timestamp,
+                    vaccination.due_timestamp,
+                    vaccination.recorded_by,
+                    vaccination.contact_id
+                ],
+            )
+            .context("Failed to insert vaccination into database")?;
+
+        log::info!(
+            "Recorded vaccination {} for animal with ID: {}",
+            id,
+            vaccination.animal_id
+        );
+        Ok(id)
+    }
+
+    /// Deletes a vaccination from the database by ID
+    ///
+    /// # Arguments
+    /// * `vaccination_id` - The ID of the vaccination to delete
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the vaccination was found and deleted, false if not found
+    pub fn delete_vaccination(&self, vaccination_id: &str) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "DELETE FROM vaccinations WHERE id = ?1",
+                params![vaccination_id],
+            )
+            .context("Failed to delete vaccination from database")?;
+
+        if rows_affected == 1 {
+            log::info!(
+                "Successfully deleted vaccination with ID: {}",
+                vaccination_id
+            );
+        } else {
+            log::warn!(
+                "No vaccination found with ID: {} for deletion",
+                vaccination_id
+            );
+        }
+        Ok(rows_affected == 1)
+    }
+}
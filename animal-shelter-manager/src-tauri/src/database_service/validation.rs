@@ -0,0 +1,605 @@
+//
+// database_service/validation.rs
+//
+// This module checks animals, adoption requests and user profiles before they are stored,
+// reporting every invalid field so the frontend can point at each of them.
+//
+
+use super::types::{
+    AdoptionRequest, Animal, AnimalStatus, BannedAdopter, Contact, DeceasedRecord, Donation,
+    Expense, FieldError, InventoryItem, Payment, ReferenceCheck, RequestInterview, RequestStatus,
+    UserProfile,
+};
+use chrono::{Datelike, Utc};
+use std::fmt;
+
+/// Earliest birth year accepted for an animal
+const MIN_BIRTH_YEAR: i32 = 1900;
+/// How far in the future a timestamp may be, in seconds, to allow for clock differences
+const MAX_CLOCK_SKEW_SECONDS: i64 = 24 * 60 * 60;
+/// Minimum number of digits in a telephone number
+const MIN_TEL_DIGITS: usize = 6;
+/// Maximum number of digits in a telephone number
+const MAX_TEL_DIGITS: usize = 15;
+
+/// Error returned when a record fails validation, listing every invalid field
+#[derive(Debug, Clone)]
+pub struct ValidationError {
+    /// The invalid fields and what is wrong with each of them
+    pub fields: Vec<FieldError>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|e| format!("{} {}", e.field, e.message))
+            .collect();
+        write!(f, "Invalid fields: {}", fields.join(", "))
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Checks the fields of an animal
+///
+/// # Arguments
+/// * `animal` - The animal to check
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the animal is valid
+pub fn validate_animal(animal: &Animal) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    require_text(&mut errors, "name", &animal.name);
+    require_text(&mut errors, "specie", &animal.specie);
+    require_text(&mut errors, "breed", &animal.breed);
+    require_text(&mut errors, "sex", &animal.sex);
+    require_text(&mut errors, "appearance", &animal.appearance);
+    require_text(&mut errors, "bio", &animal.bio);
+
+    let today = Utc::now();
+    if let Some(month) = animal.birth_month {
+        if !(1..=12).contains(&month) {
+            push(&mut errors, "birthMonth", "must be between 1 and 12");
+        }
+    }
+    if let Some(year) = animal.birth_year {
+        if !(MIN_BIRTH_YEAR..=today.year()).contains(&year) {
+            push(
+                &mut errors,
+                "birthYear",
+                &format!("must be between {} and {}", MIN_BIRTH_YEAR, today.year()),
+            );
+        } else if year == today.year()
+            && animal
+                .birth_month
+                .is_some_and(|month| month > today.month() as i32 && month <= 12)
+        {
+            push(&mut errors, "birthMonth", "must not be in the future");
+        }
+    }
+
+    check_timestamp(
+        &mut errors,
+        "admissionTimestamp",
+        animal.admission_timestamp,
+    );
+    errors
+}
+
+/// Checks the fields of an adoption request
+///
+/// # Arguments
+/// * `request` - The adoption request to check
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the request is valid
+pub fn validate_adoption_request(request: &AdoptionRequest) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    require_text(&mut errors, "animalId", &request.animal_id);
+    require_text(&mut errors, "username", &request.username);
+    require_text(&mut errors, "name", &request.name);
+    require_text(&mut errors, "address", &request.address);
+    require_text(&mut errors, "occupation", &request.occupation);
+    require_text(&mut errors, "annualIncome", &request.annual_income);
+    require_text(&mut errors, "country", &request.country);
+
+    if require_text(&mut errors, "email", &request.email) && !is_valid_email(&request.email) {
+        push(&mut errors, "email", "must be a valid email address");
+    }
+    if require_text(&mut errors, "telNumber", &request.tel_number)
+        && !is_valid_tel_number(&request.tel_number)
+    {
+        push(
+            &mut errors,
+            "telNumber",
+            &format!(
+                "must contain {} to {} digits",
+                MIN_TEL_DIGITS, MAX_TEL_DIGITS
+            ),
+        );
+    }
+
+    if request.num_people < 1 {
+        push(&mut errors, "numPeople", "must be at least 1");
+    }
+    if request.num_children < 0 {
+        push(&mut errors, "numChildren", "must not be negative");
+    } else if request.num_children >= request.num_people.max(1) {
+        push(
+            &mut errors,
+            "numChildren",
+            "must be less than the number of people",
+        );
+    }
+
+    if let Some(co_applicant) = &request.co_applicant {
+        require_text(&mut errors, "coApplicantName", &co_applicant.name);
+        require_text(
+            &mut errors,
+            "coApplicantRelationship",
+            &co_applicant.relationship,
+        );
+        if require_text(&mut errors, "coApplicantContact", &co_applicant.contact)
+            && !is_valid_email(&co_applicant.contact)
+            && !is_valid_tel_number(&co_applicant.contact)
+        {
+            push(
+                &mut errors,
+                "coApplicantContact",
+                "must be a valid email address or telephone number",
+            );
+        }
+    }
+
+    check_timestamp(&mut errors, "requestTimestamp", request.request_timestamp);
+    if request.adoption_timestamp != 0 {
+        check_timestamp(&mut errors, "adoptionTimestamp", request.adoption_timestamp);
+    }
+    errors
+}
+
+/// Checks the fields of a user profile; only the full name is required, but any
+/// contact details given must be well formed
+///
+/// # Arguments
+/// * `profile` - The user profile to check
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the profile is valid
+pub fn validate_profile(profile: &UserProfile) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    require_text(&mut errors, "username", &profile.username);
+    require_text(&mut errors, "fullName", &profile.full_name);
+
+    if !profile.email.trim().is_empty() && !is_valid_email(&profile.email) {
+        push(&mut errors, "email", "must be a valid email address");
+    }
+    if !profile.tel_number.trim().is_empty() && !is_valid_tel_number(&profile.tel_number) {
+        push(
+            &mut errors,
+            "telNumber",
+            &format!(
+                "must contain {} to {} digits",
+                MIN_TEL_DIGITS, MAX_TEL_DIGITS
+            ),
+        );
+    }
+    errors
+}
+
+/// Checks the fields of a banned adopter; at least one pattern and a reason are required
+///
+/// # Arguments
+/// * `banned` - The entry to validate
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the entry is valid
+pub fn validate_banned_adopter(banned: &BannedAdopter) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    let has_pattern = [
+        &banned.name_pattern,
+        &banned.email_pattern,
+        &banned.tel_number_pattern,
+    ]
+    .iter()
+    .any(|pattern| pattern.as_deref().is_some_and(|p| !p.trim().is_empty()));
+    if !has_pattern {
+        push(
+            &mut errors,
+            "namePattern",
+            "a name, email or telephone number pattern is required",
+        );
+    }
+    require_text(&mut errors, "reason", &banned.reason);
+    errors
+}
+
+/// Checks the fields of a deceased record; the date, cause and who authorized it are required
+///
+/// # Arguments
+/// * `record` - The record to check
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the record is valid
+pub fn validate_deceased_record(record: &DeceasedRecord) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    check_timestamp(&mut errors, "deathTimestamp", record.death_timestamp);
+    require_text(&mut errors, "cause", &record.cause);
+    require_text(&mut errors, "authorizedBy", &record.authorized_by);
+    errors
+}
+
+/// Checks that an edit may move an animal from one status to another
+///
+/// An animal can only be marked as passed away by recording how it passed away, and
+/// only enters or leaves foster care through its foster placement, so the records
+/// behind those statuses are never missing. Passing away cannot be undone by an edit.
+///
+/// # Arguments
+/// * `previous` - The status the animal has
+/// * `next` - The status the edit gives it
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the change is allowed
+pub fn validate_status_change(previous: &AnimalStatus, next: &AnimalStatus) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if previous == next {
+        return errors;
+    }
+    let message = match (previous, next) {
+        (AnimalStatus::PassedAway, _) => Some("cannot change once the animal passed away"),
+        (_, AnimalStatus::PassedAway) => {
+            Some("can only be set to passed away by recording how the animal passed away")
+        }
+        (AnimalStatus::Fostered, _) => {
+            Some("can only leave fostered by ending the animal's foster placement")
+        }
+        (_, AnimalStatus::Fostered) => {
+            Some("can only be set to fostered by starting a foster placement")
+        }
+        _ => None,
+    };
+    if let Some(message) = message {
+        push(&mut errors, "status", message);
+    }
+    errors
+}
+
+/// Checks the status a new animal is created with, which cannot be one that needs a
+/// record behind it, such as a deceased record or a foster placement
+///
+/// # Arguments
+/// * `status` - The status of the new animal
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the status is allowed
+pub fn validate_initial_status(status: &AnimalStatus) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if matches!(status, AnimalStatus::PassedAway | AnimalStatus::Fostered) {
+        push(
+            &mut errors,
+            "status",
+            "cannot be passed away or fostered for a new animal",
+        );
+    }
+    errors
+}
+
+/// Checks that an edit may move an adoption request from one status to another
+///
+/// A request can only be approved or put on trial through its approval, which checks
+/// the stray hold, the interview and reference requirements and bonded partners.
+///
+/// # Arguments
+/// * `previous` - The status the request has
+/// * `next` - The status the edit gives it
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the change is allowed
+pub fn validate_request_status_change(
+    previous: &RequestStatus,
+    next: &RequestStatus,
+) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if matches!(next, RequestStatus::Approved | RequestStatus::TrialAdoption) && previous != next {
+        push(
+            &mut errors,
+            "status",
+            "can only be set to approved or trial adoption by approving the request",
+        );
+    }
+    errors
+}
+
+/// Checks the planned end of a trial adoption, which must lie in the future
+///
+/// # Arguments
+/// * `end_timestamp` - Timestamp when the trial is planned to end
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the end is valid
+pub fn validate_trial_end(end_timestamp: i64) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if end_timestamp <= Utc::now().timestamp() {
+        push(&mut errors, "trialEndTimestamp", "must be in the future");
+    }
+    errors
+}
+
+/// Checks an inventory item, whose quantities must not be negative
+///
+/// # Arguments
+/// * `item` - The item to check
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the item is valid
+pub fn validate_inventory_item(item: &InventoryItem) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    require_text(&mut errors, "name", &item.name);
+    require_text(&mut errors, "unit", &item.unit);
+    check_quantity(&mut errors, "quantityOnHand", item.quantity_on_hand);
+    check_quantity(&mut errors, "reorderThreshold", item.reorder_threshold);
+    errors
+}
+
+/// Checks a change to the stock of an inventory item, which must say why the stock changed
+///
+/// # Arguments
+/// * `quantity_change` - Quantity added to the stock, negative when stock was used
+/// * `reason` - Why the stock changed
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the change is valid
+pub fn validate_stock_adjustment(quantity_change: f64, reason: &str) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if !quantity_change.is_finite() || quantity_change == 0.0 {
+        push(&mut errors, "quantityChange", "must be a non-zero number");
+    }
+    require_text(&mut errors, "reason", reason);
+    errors
+}
+
+/// Checks an expense before it is recorded
+///
+/// # Arguments
+/// * `expense` - The expense to check
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the expense is valid
+pub fn validate_expense(expense: &Expense) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if expense.amount <= 0 {
+        push(&mut errors, "amount", "must be greater than zero");
+    }
+    check_timestamp(&mut errors, "expenseTimestamp", expense.expense_timestamp);
+    require_text(&mut errors, "vendor", &expense.vendor);
+    errors
+}
+
+/// Checks a contact, whose telephone number and email address are optional
+///
+/// # Arguments
+/// * `contact` - The contact to check
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the contact is valid
+pub fn validate_contact(contact: &Contact) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    require_text(&mut errors, "name", &contact.name);
+    if !contact.email.trim().is_empty() && !is_valid_email(&contact.email) {
+        push(&mut errors, "email", "must be a valid email address");
+    }
+    if !contact.tel_number.trim().is_empty() && !is_valid_tel_number(&contact.tel_number) {
+        push(
+            &mut errors,
+            "telNumber",
+            &format!(
+                "must contain {} to {} digits",
+                MIN_TEL_DIGITS, MAX_TEL_DIGITS
+            ),
+        );
+    }
+    errors
+}
+
+/// Checks the slot of a meet-and-greet appointment, which must lie in the future
+///
+/// # Arguments
+/// * `slot_timestamp` - Timestamp when the appointment starts
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the slot is valid
+pub fn validate_appointment_slot(slot_timestamp: i64) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if slot_timestamp <= Utc::now().timestamp() {
+        push(&mut errors, "slotTimestamp", "must be in the future");
+    }
+    errors
+}
+
+/// Checks the date a spay or neuter surgery is booked for
+///
+/// # Arguments
+/// * `scheduled_timestamp` - Timestamp when the surgery is booked for
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the date is valid
+pub fn validate_surgery_schedule(scheduled_timestamp: i64) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if scheduled_timestamp <= 0 {
+        push(&mut errors, "scheduledTimestamp", "must be a valid date");
+    }
+    errors
+}
+
+/// Checks the date a spay or neuter surgery was performed, which cannot lie in the future
+///
+/// # Arguments
+/// * `completed_timestamp` - Timestamp when the surgery was performed
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the date is valid
+pub fn validate_surgery_completion(completed_timestamp: i64) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    check_timestamp(&mut errors, "completedTimestamp", completed_timestamp);
+    errors
+}
+
+/// Checks a phone interview held with the applicant of an adoption request
+///
+/// # Arguments
+/// * `interview` - The interview to check
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the interview is valid
+pub fn validate_request_interview(interview: &RequestInterview) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    require_text(&mut errors, "notes", &interview.notes);
+    check_timestamp(
+        &mut errors,
+        "interviewTimestamp",
+        interview.interview_timestamp,
+    );
+    errors
+}
+
+/// Checks a reference given by the applicant of an adoption request; the referee must be
+/// reachable by email or telephone
+///
+/// # Arguments
+/// * `check` - The reference check to check
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the reference check is valid
+pub fn validate_reference_check(check: &ReferenceCheck) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    require_text(&mut errors, "refereeName", &check.referee_name);
+    if require_text(&mut errors, "refereeContact", &check.referee_contact)
+        && !is_valid_email(&check.referee_contact)
+        && !is_valid_tel_number(&check.referee_contact)
+    {
+        push(
+            &mut errors,
+            "refereeContact",
+            "must be a valid email address or telephone number",
+        );
+    }
+    check_timestamp(&mut errors, "checkedTimestamp", check.checked_timestamp);
+    errors
+}
+
+/// Checks a fee charged for an adoption request
+///
+/// # Arguments
+/// * `payment` - The payment to check
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the payment is valid
+pub fn validate_payment(payment: &Payment) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if payment.amount <= 0 {
+        push(&mut errors, "amount", "must be greater than zero");
+    }
+    require_text(&mut errors, "description", &payment.description);
+    check_timestamp(&mut errors, "createdTimestamp", payment.created_timestamp);
+    errors
+}
+
+/// Checks a donation made to the shelter
+///
+/// # Arguments
+/// * `donation` - The donation to check
+///
+/// # Returns
+/// * `Vec<FieldError>` - The invalid fields, empty if the donation is valid
+pub fn validate_donation(donation: &Donation) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if donation.amount <= 0 {
+        push(&mut errors, "amount", "must be greater than zero");
+    }
+    check_timestamp(
+        &mut errors,
+        "donationTimestamp",
+        donation.donation_timestamp,
+    );
+    errors
+}
+
+/// Fails with a `ValidationError` if any field is invalid
+///
+/// # Arguments
+/// * `errors` - The invalid fields found by a validation function
+///
+/// # Returns
+/// * `Result<(), ValidationError>` - Success if there are no invalid fields
+pub(super) fn ensure_valid(errors: Vec<FieldError>) -> Result<(), ValidationError> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError { fields: errors })
+    }
+}
+
+/// Records an error for a field
+fn push(errors: &mut Vec<FieldError>, field: &str, message: &str) {
+    errors.push(FieldError {
+        field: field.to_string(),
+        message: message.to_string(),
+    });
+}
+
+/// Records an error if a required text field is blank
+///
+/// # Returns
+/// * `bool` - True if the field has a value
+fn require_text(errors: &mut Vec<FieldError>, field: &str, value: &str) -> bool {
+    if value.trim().is_empty() {
+        push(errors, field, "is required");
+        return false;
+    }
+    true
+}
+
+/// Records an error if a timestamp is not positive or lies in the future
+fn check_timestamp(errors: &mut Vec<FieldError>, field: &str, timestamp: i64) {
+    if timestamp <= 0 {
+        push(errors, field, "must be a valid date");
+    } else if timestamp > Utc::now().timestamp() + MAX_CLOCK_SKEW_SECONDS {
+        push(errors, field, "must not be in the future");
+    }
+}
+
+/// Records an error if a quantity is negative or not a number
+fn check_quantity(errors: &mut Vec<FieldError>, field: &str, quantity: f64) {
+    if !quantity.is_finite() || quantity < 0.0 {
+        push(errors, field, "must not be negative");
+    }
+}
+
+/// Checks that an email address has a local part and a dotted domain, without spaces
+fn is_valid_email(email: &str) -> bool {
+    let email = email.trim();
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && !email.chars().any(char::is_whitespace)
+                && domain.split('.').all(|label| !label.is_empty())
+                && domain.contains('.')
+        }
+        None => false,
+    }
+}
+
+/// Checks that a telephone number only holds digits, separators and a leading plus sign
+fn is_valid_tel_number(tel_number: &str) -> bool {
+    let tel_number = tel_number.trim();
+    let tel_number = tel_number.strip_prefix('+').unwrap_or(tel_number);
+    let digits = tel_number.chars().filter(char::is_ascii_digit).count();
+    tel_number
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, ' ' | '-' | '(' | ')'))
+        && (MIN_TEL_DIGITS..=MAX_TEL_DIGITS).contains(&digits)
+}
@@ -0,0 +1,163 @@
+//
+// database_service/waitlist.rs
+//
+// This module provides operations for the species and breed waitlist,
+// and notifies waiting customers when a matching animal is admitted.
+//
+
+use super::types::{Animal, JoinWaitlistResult, Notification, WaitlistEntry};
+use super::DatabaseService;
+use anyhow::{Context, Result};
+use rusqlite::params;
+use uuid::Uuid;
+
+impl DatabaseService {
+    // ==================== WAITLIST TABLE OPERATIONS ====================
+
+    /// Retrieves the waitlist entries of a specific user, most recent first
+    ///
+    /// # Arguments
+    /// * `username` - The username to retrieve waitlist entries for
+    ///
+    /// # Returns
+    /// * `Result<Vec<WaitlistEntry>>` - List of waitlist entries or error
+    pub fn query_waitlist_by_username(&self, username: &str) -> Result<Vec<WaitlistEntry>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, username, specie, breed, created_timestamp FROM waitlist WHERE username = ?1 ORDER BY created_timestamp DESC",
+            )
+            .context("Failed to prepare query for waitlist by username")?;
+
+        let entry_iter = statement
+            .query_map(params![username], |row| {
+                Ok(WaitlistEntry {
+                    id: row.get(0)?,
+                    username: row.get(1)?,
+                    specie: row.get(2)?,
+                    breed: row.get(3)?,
+                    created_timestamp: row.get(4)?,
+                })
+            })
+            .context("Failed to execute query for waitlist by username")?;
+
+        let mut entries = Vec::new();
+        for entry in entry_iter {
+            entries.push(entry.context("Failed to parse waitlist row")?);
+        }
+        Ok(entries)
+    }
+
+    /// Adds a user to the waitlist for a species, and optionally a breed
+    ///
+    /// # Arguments
+    /// * `entry` - The waitlist entry to insert; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<JoinWaitlistResult>` - Success, or AlreadyJoined if the user already waits for the same animals
+    pub fn insert_waitlist_entry(&self, entry: &WaitlistEntry) -> Result<JoinWaitlistResult> {
+        let already_joined: bool = self
+            .connection
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM waitlist WHERE username = ?1 AND specie = ?2 AND breed IS ?3)",
+                params![entry.username, entry.specie, entry.breed],
+                |row| row.get(0),
+            )
+            .context("Failed to check for an existing waitlist entry")?;
+        if already_joined {
+            return Ok(JoinWaitlistResult::AlreadyJoined);
+        }
+
+        let id = if entry.id.trim().is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            entry.id.clone()
+        };
+
+        self.connection
+            .execute(
+                "INSERT INTO waitlist (id, username, specie, breed, created_timestamp) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    id,
+                    entry.username,
+                    entry.specie,
+                    entry.breed,
+                    entry.created_timestamp
+                ],
+            )
+            .context("Failed to insert waitlist entry into database")?;
+
+        log::info!(
+            "User {} joined the waitlist for {}",
+            entry.username,
+            entry.specie
+        );
+        Ok(JoinWaitlistResult::Success)
+    }
+
+    /// Removes a waitlist entry belonging to a user
+    ///
+    /// # Arguments
+    /// * `entry_id` - The ID of the waitlist entry to remove
+    /// * `username` - The username of the user the entry must belong to
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the entry was found and removed, false if not found
+    pub fn delete_waitlist_entry(&self, entry_id: &str, username: &str) -> Result<bool> {
+        let rows_affected = self
+            .connection
+            .execute(
+                "DELETE FROM waitlist WHERE id = ?1 AND username = ?2",
+                params![entry_id, username],
+            )
+            .context("Failed to delete waitlist entry from database")?;
+
+        Ok(rows_affected == 1)
+    }
+
+    /// Creates a notification for every user waiting for the species and breed of a new animal
+    ///
+    /// # Arguments
+    /// * `animal_id` - The ID of the new animal
+    /// * `animal` - The new animal
+    ///
+    /// # Returns
+    /// * `Result<Vec<Notification>>` - The notifications created, one per waiting user
+    pub(super) fn notify_waitlist(
+        &self,
+        animal_id: &str,
+        animal: &Animal,
+    ) -> Result<Vec<Notification>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT DISTINCT username FROM waitlist WHERE specie = ?1 AND (breed IS NULL OR breed = ?2)",
+            )
+            .context("Failed to prepare query for matching waitlist entries")?;
+        let usernames = statement
+            .query_map(params![animal.specie, animal.breed], |row| {
+                row.get::<_, String>(0)
+            })
+            .context("Failed to execute query for matching waitlist entries")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse waitlist row")?;
+
+        let message = format!(
+            "{}, a {} {}, has just arrived at the shelter",
+            animal.name, animal.breed, animal.specie
+        );
+        let mut notifications = Vec::new();
+        for username in usernames {
+            notifications.push(self.insert_notification(&username, &message, Some(animal_id))?);
+        }
+
+        if !notifications.is_empty() {
+            log::info!(
+                "Notified {} waiting user(s) about animal with ID: {}",
+                notifications.len(),
+                animal_id
+            );
+        }
+        Ok(notifications)
+    }
+}
@@ -0,0 +1,422 @@
+//
+// database_service/webhooks.rs
+//
+// This module provides operations for managing webhooks, queueing the events they
+// subscribe to, and keeping a log of every delivery attempt.
+//
+
+use super::types::{
+    DueWebhookDelivery, RequestStatus, Webhook, WebhookDelivery, WebhookDeliveryStatus,
+    WebhookEvent,
+};
+use super::DatabaseService;
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, Row};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Number of attempts made to deliver an event before giving up on it
+const WEBHOOK_MAX_ATTEMPTS: i64 = 5;
+
+/// Delay before the first retry of a failed delivery, doubled after every further failure
+const WEBHOOK_RETRY_DELAY_SECONDS: i64 = 60;
+
+/// Columns selected when reading a delivery, in the order expected by `delivery_from_row`
+const DELIVERY_COLUMNS: &str = "id, webhook_id, event, payload, status, attempts, response_code, error, created_timestamp, attempted_timestamp, next_attempt_timestamp";
+
+impl DatabaseService {
+    // ==================== WEBHOOKS TABLE OPERATIONS ====================
+
+    /// Retrieves every webhook
+    ///
+    /// # Returns
+    /// * `Result<Vec<Webhook>>` - Webhooks ordered by creation time, or error
+    pub fn query_webhooks(&self) -> Result<Vec<Webhook>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT id, url, secret, event_types, created_timestamp FROM webhooks ORDER BY created_timestamp",
+            )
+            .context("Failed to prepare query for webhooks")?;
+
+        let webhook_iter = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, i64>(4)?,
+                ))
+            })
+            .context("Failed to execute query for webhooks")?;
+
+        let mut webhooks = Vec::new();
+        for webhook in webhook_iter {
+            let (id, url, secret, event_types, created_timestamp) =
+                webhook.context("Failed to parse webhook row")?;
+            webhooks.push(Webhook {
+                id,
+                url,
+                secret,
+                event_types: parse_event_types(&event_types)?,
+                created_timestamp,
+            });
+        }
+
+        log::debug!("Retrieved {} webhooks", webhooks.len());
+        Ok(webhooks)
+    }
+
+    /// Inserts a new webhook into the database
+    ///
+    /// # Arguments
+    /// * `webhook` - The webhook to insert; an ID is generated if it is empty
+    ///
+    /// # Returns
+    /// * `Result<String>` - The ID of the inserted webhook or error
+    pub fn insert_webhook(&self, webhook: &Webhook) -> Result<String> {
+        let url = webhook.url.trim();
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            bail!("Webhook URL must start with http:// or https://");
+        }
+        if webhook.secret.is_empty() {
+            bail!("Webhook secret must not be empty");
+        }
+        if webhook.event_types.is_empty() {
+            bail!("Webhook must subscribe to at least one event");
+        }
+
+        let id = if webhook.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            webhook.id.clone()
+        };
+        let event_types = webhook
+            .event_types
+            .iter()
+            .map(WebhookEvent::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        self.connection
+            .execute(
+                "INSERT INTO webhooks (id, url, secret, event_types, created_timestamp) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![id, url, webhook.secret, event_types, webhook.created_timestamp],
+            )
+            .context("Failed to insert webhook into database")?;
+
+        log::info!("Successfully inserted webhook with ID: {}", id);
+        Ok(id)
+    }
+
+    /// Deletes a webhook along with its delivery log
+    ///
+    /// # Arguments
+    /// * `webhook_id` - The ID of the webhook to delete
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the webhook was found and deleted, false if not found
+    pub fn delete_webhook(&self, webhook_id: &str) -> Result<bool> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for webhook deletion")?;
+
+        transaction
+            .execute(
+                "DELETE FROM webhook_deliveries WHERE webhook_id = ?1",
+                params![webhook_id],
+            )
+            .context("Failed to delete deliveries of webhook")?;
+        let rows_affected = transaction
+            .execute("DELETE FROM webhooks WHERE id = ?1", params![webhook_id])
+            .context("Failed to delete webhook from database")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit webhook deletion")?;
+
+        if rows_affected == 1 {
+            log::info!("Successfully deleted webhook with ID: {}", webhook_id);
+        } else {
+            log::warn!("No webhook found with ID: {} for deletion", webhook_id);
+        }
+        Ok(rows_affected == 1)
+    }
+
+    /// Retrieves the delivery log of a specific webhook, most recent first
+    ///
+    /// # Arguments
+    /// * `webhook_id` - The ID of the webhook to retrieve deliveries for
+    ///
+    /// # Returns
+    /// * `Result<Vec<WebhookDelivery>>` - List of deliveries or error
+    pub fn query_webhook_deliveries(&self, webhook_id: &str) -> Result<Vec<WebhookDelivery>> {
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {} FROM webhook_deliveries WHERE webhook_id = ?1 ORDER BY created_timestamp DESC",
+                DELIVERY_COLUMNS
+            ))
+            .context("Failed to prepare query for webhook deliveries")?;
+
+        let deliveries = statement
+            .query_map(params![webhook_id], delivery_from_row)
+            .context("Failed to execute query for webhook deliveries")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse webhook delivery row")?;
+
+        log::debug!(
+            "Retrieved {} deliveries for webhook ID: {}",
+            deliveries.len(),
+            webhook_id
+        );
+        Ok(deliveries)
+    }
+
+    /// Retrieves the pending deliveries whose next attempt is due, oldest first
+    ///
+    /// # Arguments
+    /// * `now` - The current timestamp
+    ///
+    /// # Returns
+    /// * `Result<Vec<DueWebhookDelivery>>` - The due deliveries with their webhook's URL and secret
+    pub fn query_due_webhook_deliveries(&self, now: i64) -> Result<Vec<DueWebhookDelivery>> {
+        let columns = DELIVERY_COLUMNS
+            .split(", ")
+            .map(|column| format!("d.{}", column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT {}, w.url, w.secret FROM webhook_deliveries d JOIN webhooks w ON w.id = d.webhook_id WHERE d.status = ?1 AND d.next_attempt_timestamp <= ?2 ORDER BY d.created_timestamp",
+                columns
+            ))
+            .context("Failed to prepare query for due webhook deliveries")?;
+
+        let deliveries = statement
+            .query_map(params![WebhookDeliveryStatus::Pending, now], |row| {
+                Ok(DueWebhookDelivery {
+                    delivery: delivery_from_row(row)?,
+                    url: row.get(11)?,
+                    secret: row.get(12)?,
+                })
+            })
+            .context("Failed to execute query for due webhook deliveries")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to parse due webhook delivery row")?;
+
+        Ok(deliveries)
+    }
+
+    /// Records the outcome of an attempt to deliver an event
+    ///
+    /// A failed attempt is retried later with an exponential backoff, until
+    /// `WEBHOOK_MAX_ATTEMPTS` attempts have been made.
+    ///
+    /// # Arguments
+    /// * `delivery_id` - The ID of the delivery that was attempted
+    /// * `response_code` - HTTP status code of the response, if one was received
+    /// * `error` - Why the attempt failed, or None if the event was delivered
+    /// * `attempted_timestamp` - The timestamp of the attempt
+    ///
+    /// # Returns
+    /// * `Result<WebhookDeliveryStatus>` - The status of the delivery after the attempt
+    pub fn record_webhook_attempt(
+        &self,
+        delivery_id: &str,
+        response_code: Option<i64>,
+        error: Option<&str>,
+        attempted_timestamp: i64,
+    ) -> Result<WebhookDeliveryStatus> {
+        let transaction = self
+            .connection
+            .unchecked_transaction()
+            .context("Failed to begin transaction for webhook attempt")?;
+
+        let previous_attempts: i64 = transaction
+            .query_row(
+                "SELECT attempts FROM webhook_deliveries WHERE id = ?1",
+                params![delivery_id],
+                |row| row.get(0),
+            )
+            .context("Failed to read attempts of webhook delivery")?;
+        let attempts = previous_attempts + 1;
+
+        let (status, next_attempt_timestamp) = match error {
+            None => (WebhookDeliveryStatus::Delivered, attempted_timestamp),
+            Some(_) if attempts >= WEBHOOK_MAX_ATTEMPTS => {
+                (WebhookDeliveryStatus::Failed, attempted_timestamp)
+            }
+            Some(_) => (
+                WebhookDeliveryStatus::Pending,
+                attempted_timestamp + (WEBHOOK_RETRY_DELAY_SECONDS << (attempts - 1)),
+            ),
+        };
+
+        transaction
+            .execute(
+                "UPDATE webhook_deliveries SET status = ?2, attempts = ?3, response_code = ?4, error = ?5, attempted_timestamp = ?6, next_attempt_timestamp = ?7 WHERE id = ?1",
+                params![
+                    delivery_id,
+                    status,
+                    attempts,
+                    response_code,
+                    error,
+                    attempted_timestamp,
+                    next_attempt_timestamp
+                ],
+            )
+            .context("Failed to record webhook attempt")?;
+
+        transaction
+            .commit()
+            .context("Failed to commit webhook attempt")?;
+
+        match &status {
+            WebhookDeliveryStatus::Failed => log::warn!(
+                "Gave up on webhook delivery {} after {} attempts",
+                delivery_id,
+                attempts
+            ),
+            _ => log::debug!(
+                "Webhook delivery {} is {} after {} attempt(s)",
+                delivery_id,
+                status,
+                attempts
+            ),
+        }
+        Ok(status)
+    }
+
+    /// Queues an event for every webhook subscribed to it, to be delivered by the
+    /// webhook dispatcher; runs within the caller's transaction, if any
+    ///
+    /// # Arguments
+    /// * `event` - The event that happened
+    /// * `data` - The record the event is about, sent as the `data` of the payload
+    /// * `timestamp` - The timestamp of the event
+    ///
+    /// # Returns
+    /// * `Result<usize>` - The number of deliveries queued
+    pub(super) fn queue_webhook_event<T: Serialize>(
+        &self,
+        event: WebhookEvent,
+        data: &T,
+        timestamp: i64,
+    ) -> Result<usize> {
+        let webhook_ids: Vec<String> = self
+            .query_webhooks()?
+            .into_iter()
+            .filter(|webhook| webhook.event_types.contains(&event))
+            .map(|webhook| webhook.id)
+            .collect();
+        if webhook_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let payload = serde_json::json!({
+            "event": event,
+            "timestamp": timestamp,
+            "data": data,
+        })
+        .to_string();
+
+        for webhook_id in &webhook_ids {
+            self.connection
+                .execute(
+                    "INSERT INTO webhook_deliveries (id, webhook_id, event, payload, status, attempts, created_timestamp, next_attempt_timestamp) VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6, ?6)",
+                    params![
+                        Uuid::new_v4().to_string(),
+                        webhook_id,
+                        event,
+                        payload,
+                        WebhookDeliveryStatus::Pending,
+                        timestamp
+                    ],
+                )
+                .context("Failed to queue webhook delivery")?;
+        }
+
+        log::debug!(
+            "Queued {} event for {} webhook(s)",
+            event,
+            webhook_ids.len()
+        );
+        Ok(webhook_ids.len())
+    }
+
+    /// Queues the event of an adoption request being filed or changing status, along
+    /// with the email telling the applicant if it was approved or rejected
+    ///
+    /// # Arguments
+    /// * `request_id` - The ID of the adoption request
+    /// * `animal_id` - The ID of the requested animal
+    /// * `status` - The new status of the request
+    /// * `timestamp` - The timestamp of the change
+    ///
+    /// # Returns
+    /// * `Result<usize>` - The number of deliveries queued
+    pub(super) fn queue_request_status_change(
+        &self,
+        request_id: &str,
+        animal_id: &str,
+        status: &RequestStatus,
+        timestamp: i64,
+    ) -> Result<usize> {
+        self.queue_status_email(request_id, status, timestamp)?;
+        self.queue_webhook_event(
+            WebhookEvent::RequestStatusChanged,
+            &serde_json::json!({
+                "id": request_id,
+                "animalId": animal_id,
+                "status": status,
+            }),
+            timestamp,
+        )
+    }
+}
+
+/// Builds a WebhookDelivery from a row selected with `DELIVERY_COLUMNS`
+///
+/// # Arguments
+/// * `row` - The row to read
+///
+/// # Returns
+/// * `rusqlite::Result<WebhookDelivery>` - The delivery or error
+fn delivery_from_row(row: &Row) -> rusqlite::Result<WebhookDelivery> {
+    Ok(WebhookDelivery {
+        id: row.get(0)?,
+        webhook_id: row.get(1)?,
+        event: row.get(2)?,
+        payload: row.get(3)?,
+        status: row.get(4)?,
+        attempts: row.get(5)?,
+        response_code: row.get(6)?,
+        error: row.get(7)?,
+        created_timestamp: row.get(8)?,
+        attempted_timestamp: row.get(9)?,
+        next_attempt_timestamp: row.get(10)?,
+    })
+}
+
+/// Parses the comma-separated event types stored for a webhook
+///
+/// # Arguments
+/// * `event_types` - The stored event types
+///
+/// # Returns
+/// * `Result<Vec<WebhookEvent>>` - The events or error
+fn parse_event_types(event_types: &str) -> Result<Vec<WebhookEvent>> {
+    event_types
+        .split(',')
+        .filter(|event| !event.is_empty())
+        .map(|event| {
+            event
+                .parse()
+                .context(format!("Unknown webhook event type: {}", event))
+        })
+        .collect()
+}
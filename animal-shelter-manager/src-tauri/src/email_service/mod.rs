@@ -0,0 +1,243 @@
+//
+// email_service/mod.rs
+//
+// This module sends the emails the DatabaseService queues when an adoption
+// request is approved or rejected, through the SMTP server set in the settings,
+// and records the outcome of every attempt in the send log.
+//
+
+use crate::database_service::{
+    types::{EmailMessage, EmailStatus, RequestStatus},
+    DatabaseService,
+};
+use crate::settings_service::{types::EmailConfig, SettingsService};
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Address, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod test;
+
+/// How often the queue is checked for emails waiting to be sent
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long the SMTP server has to respond before the attempt fails
+const SEND_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// SMTP port that uses TLS from the start of the connection instead of STARTTLS
+const IMPLICIT_TLS_PORT: u16 = 465;
+
+/// Sends queued emails until the application exits
+///
+/// # Arguments
+/// * `database_service` - Service for handling database operations
+/// * `settings_service` - Service holding the SMTP settings and templates
+pub async fn run(
+    database_service: Arc<Mutex<DatabaseService>>,
+    settings_service: Arc<Mutex<SettingsService>>,
+) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = send_pending(&database_service, &settings_service).await {
+            log::error!("Failed to send queued emails: {:#}", e);
+        }
+    }
+}
+
+/// Attempts every email waiting to be sent, recording the outcome in the send log
+///
+/// # Arguments
+/// * `database_service` - Service for handling database operations
+/// * `settings_service` - Service holding the SMTP settings and templates
+///
+/// # Returns
+/// * `Result<usize>` - The number of emails attempted
+pub async fn send_pending(
+    database_service: &Arc<Mutex<DatabaseService>>,
+    settings_service: &Arc<Mutex<SettingsService>>,
+) -> Result<usize> {
+    let pending = run_database_task(database_service, |db| db.query_pending_emails()).await?;
+    let attempted = pending.len();
+    for email in pending {
+        send(database_service, settings_service, &email).await?;
+    }
+    Ok(attempted)
+}
+
+/// Sends an email with the current settings and templates, recording the outcome
+/// in the send log; also used by staff to resend an email that failed
+///
+/// # Arguments
+/// * `database_service` - Service for handling database operations
+/// * `settings_service` - Service holding the SMTP settings and templates
+/// * `email` - The email to send
+///
+/// # Returns
+/// * `Result<EmailStatus>` - The status of the email after the attempt, or error if
+///   the outcome could not be recorded
+pub async fn send(
+    database_service: &Arc<Mutex<DatabaseService>>,
+    settings_service: &Arc<Mutex<SettingsService>>,
+    email: &EmailMessage,
+) -> Result<EmailStatus> {
+    let settings_service = settings_service.clone();
+    let config = tokio::task::spawn_blocking(move || {
+        settings_service
+            .lock()
+            .map_err(|_| anyhow!("Settings service lock was poisoned"))?
+            .email_config()
+    })
+    .await
+    .map_err(|e| anyhow!("Settings task did not complete: {}", e))??;
+
+    let (subject, body, error) = match &config {
+        Some(config) => {
+            let (subject, body) = compose(config, email);
+            let error = deliver(config, email, &subject, &body)
+                .await
+                .err()
+                .map(|e| format!("{:#}", e));
+            (Some(subject), Some(body), error)
+        }
+        None => (
+            None,
+            None,
+            Some("Email is not set up: set the SMTP host and sender first".to_string()),
+        ),
+    };
+    if let Some(error) = &error {
+        log::warn!(
+            "Email {} to {} failed: {}",
+            email.id,
+            email.recipient,
+            error
+        );
+    }
+
+    let email_id = email.id.clone();
+    run_database_task(database_service, move |db| {
+        db.record_email_attempt(
+            &email_id,
+            subject.as_deref(),
+            body.as_deref(),
+            error.as_deref(),
+            Utc::now().timestamp(),
+        )
+    })
+    .await
+}
+
+/// Fills in the subject and template body of an email
+///
+/// # Arguments
+/// * `config` - The email settings, holding the templates
+/// * `email` - The email being sent
+///
+/// # Returns
+/// * `(String, String)` - The subject and body
+fn compose(config: &EmailConfig, email: &EmailMessage) -> (String, String) {
+    let (subject, template) = match email.request_status {
+        RequestStatus::Approved => (
+            format!(
+                "Your request to adopt {} has been approved",
+                email.animal_name
+            ),
+            &config.approved_template,
+        ),
+        _ => (
+            format!("Your request to adopt {}", email.animal_name),
+            &config.rejected_template,
+        ),
+    };
+    let body = template
+        .replace("{name}", &email.recipient_name)
+        .replace("{animal}", &email.animal_name)
+        .replace("{shelter}", &config.shelter_name);
+    (subject, body)
+}
+
+/// Sends an email through the SMTP server
+///
+/// # Arguments
+/// * `config` - The email settings
+/// * `email` - The email being sent
+/// * `subject` - The subject of the email
+/// * `body` - The body of the email
+///
+/// # Returns
+/// * `Result<()>` - Success, or error if the server did not accept the email
+async fn deliver(
+    config: &EmailConfig,
+    email: &EmailMessage,
+    subject: &str,
+    body: &str,
+) -> Result<()> {
+    let sender: Mailbox = config
+        .sender
+        .parse()
+        .context(format!("Invalid sender address: {}", config.sender))?;
+    let address: Address = email
+        .recipient
+        .parse()
+        .context(format!("Invalid recipient address: {}", email.recipient))?;
+    let message = Message::builder()
+        .from(sender)
+        .to(Mailbox::new(Some(email.recipient_name.clone()), address))
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(body.to_string())
+        .context("Failed to build email")?;
+
+    let builder = if config.smtp_port == IMPLICIT_TLS_PORT {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host)
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)
+    }
+    .context(format!("Invalid SMTP host: {}", config.smtp_host))?;
+    let mut builder = builder.port(config.smtp_port).timeout(Some(SEND_TIMEOUT));
+    if !config.smtp_username.is_empty() {
+        builder = builder.credentials(Credentials::new(
+            config.smtp_username.clone(),
+            config.smtp_password.clone(),
+        ));
+    }
+
+    builder
+        .build()
+        .send(message)
+        .await
+        .context(format!("Failed to send email through {}", config.smtp_host))?;
+    log::info!("Sent email {} to {}", email.id, email.recipient);
+    Ok(())
+}
+
+/// Runs a task against the DatabaseService on the blocking thread pool
+///
+/// # Arguments
+/// * `database_service` - Service for handling database operations
+/// * `task` - The database work to run
+///
+/// # Returns
+/// * `Result<T>` - The result of the database work
+async fn run_database_task<T, F>(
+    database_service: &Arc<Mutex<DatabaseService>>,
+    task: F,
+) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&DatabaseService) -> Result<T> + Send + 'static,
+{
+    let database_service = database_service.clone();
+    tokio::task::spawn_blocking(move || {
+        let database_service = database_service
+            .lock()
+            .map_err(|_| anyhow!("Database service lock was poisoned"))?;
+        task(&database_service)
+    })
+    .await
+    .map_err(|e| anyhow!("Database task did not complete: {}", e))?
+}
@@ -0,0 +1,172 @@
+//
+// email_service/test.rs
+//
+// This file contains unit tests for the email sending module.
+//
+
+#[cfg(test)]
+mod email_service_tests {
+    use crate::database_service::{
+        types::{
+            AdoptionRequest, Animal, AnimalStatus, ApprovalRequirements, EmailStatus, RequestStatus,
+        },
+        DatabaseService,
+    };
+    use crate::email_service::{send, send_pending};
+    use crate::settings_service::{types::SettingKey, SettingsService};
+    use chrono::Utc;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    /// Helper function to create test services with an approved and a rejected request
+    ///
+    /// # Arguments
+    /// * `test_name` - Name of the test for unique database paths
+    ///
+    /// # Returns
+    /// * `(Arc<Mutex<DatabaseService>>, Arc<Mutex<SettingsService>>)` - The services
+    fn create_test_services(
+        test_name: &str,
+    ) -> (Arc<Mutex<DatabaseService>>, Arc<Mutex<SettingsService>>) {
+        let root_path = PathBuf::from("test_artifacts/email_service").join(test_name);
+        if root_path.exists() {
+            fs::remove_dir_all(&root_path).expect("Failed to remove existing test directory");
+        }
+        fs::create_dir_all(&root_path).expect("Failed to create test artifacts directory");
+        let db = DatabaseService::new(root_path.join("test.db"))
+            .expect("Failed to create test db service");
+        let settings = SettingsService::new(root_path.join("settings.db"))
+            .expect("Failed to create test settings service");
+
+        db.insert_animal(&Animal {
+            id: "a1".to_string(),
+            name: "Buddy".to_string(),
+            specie: "Dog".to_string(),
+            breed: "Golden Retriever".to_string(),
+            sex: "Male".to_string(),
+            birth_month: Some(6),
+            birth_year: Some(2020),
+            neutered: true,
+            admission_timestamp: Utc::now().timestamp(),
+            status: AnimalStatus::Available,
+            image_path: None,
+            appearance: "Golden coat with friendly eyes".to_string(),
+            bio: "Buddy loves playing fetch.".to_string(),
+            version: 1,
+            microchip_number: None,
+            location_id: None,
+            good_with_kids: None,
+            good_with_dogs: None,
+            good_with_cats: None,
+            special_needs: None,
+            size: None,
+            color: None,
+        })
+        .expect("Failed to insert test animal");
+        for (id, username, name) in [("r1", "JiraPit", "Jira Pit"), ("r2", "Someone", "Some One")] {
+            db.insert_adoption_request(&AdoptionRequest {
+                id: id.to_string(),
+                username: username.to_string(),
+                animal_id: "a1".to_string(),
+                name: name.to_string(),
+                email: format!("{}@example.org", username.to_lowercase()),
+                tel_number: "0123456789".to_string(),
+                address: "Bangkok, Thailand".to_string(),
+                occupation: "Software Engineer".to_string(),
+                annual_income: "50000".to_string(),
+                num_people: 2,
+                num_children: 0,
+                request_timestamp: Utc::now().timestamp(),
+                adoption_timestamp: 0,
+                status: RequestStatus::Pending,
+                country: "Thailand".to_string(),
+                co_applicant: None,
+            })
+            .expect("Failed to insert test request");
+        }
+        db.approve_adoption_request("r1", false, None, &ApprovalRequirements::default())
+            .expect("Failed to approve test request");
+
+        (Arc::new(Mutex::new(db)), Arc::new(Mutex::new(settings)))
+    }
+
+    #[tokio::test]
+    async fn test_send_without_settings() {
+        let (db, settings) = create_test_services("test_send_without_settings");
+
+        // Without an SMTP host nothing is sent, and the emails are kept for resending
+        assert_eq!(send_pending(&db, &settings).await.unwrap(), 2);
+        let emails = db.lock().unwrap().query_emails().unwrap();
+        for email in &emails {
+            assert_eq!(email.status, EmailStatus::Failed);
+            assert_eq!(email.attempts, 1);
+            assert!(email.error.as_deref().unwrap().contains("not set up"));
+            assert_eq!(email.subject, None);
+        }
+        assert_eq!(send_pending(&db, &settings).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_send_with_templates() {
+        let (db, settings) = create_test_services("test_send_with_templates");
+
+        // Point the settings at a port nothing listens on, so every attempt fails quickly
+        let port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        {
+            let settings = settings.lock().unwrap();
+            settings
+                .set_setting(SettingKey::SmtpHost, "localhost")
+                .unwrap();
+            settings
+                .set_setting(SettingKey::SmtpPort, &port.to_string())
+                .unwrap();
+            settings
+                .set_setting(SettingKey::EmailSender, "Happy Paws <adopt@example.org>")
+                .unwrap();
+            settings
+                .set_setting(SettingKey::ShelterName, "Happy Paws")
+                .unwrap();
+            settings
+                .set_setting(
+                    SettingKey::ApprovedEmailTemplate,
+                    "Hi {name}, {animal} is yours! - {shelter}",
+                )
+                .unwrap();
+        }
+
+        assert_eq!(send_pending(&db, &settings).await.unwrap(), 2);
+        let emails = db.lock().unwrap().query_emails().unwrap();
+        let approval = emails.iter().find(|e| e.request_id == "r1").unwrap();
+        assert_eq!(approval.status, EmailStatus::Failed);
+        assert!(approval.error.as_deref().unwrap().contains("localhost"));
+        assert_eq!(
+            approval.subject.as_deref(),
+            Some("Your request to adopt Buddy has been approved")
+        );
+        assert_eq!(
+            approval.body.as_deref(),
+            Some("Hi Jira Pit, Buddy is yours! - Happy Paws")
+        );
+        let rejection = emails.iter().find(|e| e.request_id == "r2").unwrap();
+        assert!(rejection
+            .body
+            .as_deref()
+            .unwrap()
+            .starts_with("Dear Some One,"));
+
+        // Resending makes another attempt with the current settings
+        let status = send(&db, &settings, approval).await.unwrap();
+        assert_eq!(status, EmailStatus::Failed);
+        let approval = db
+            .lock()
+            .unwrap()
+            .query_email_by_id(&approval.id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(approval.attempts, 2);
+    }
+}
@@ -0,0 +1,59 @@
+//
+// export_service/anonymize.rs
+//
+// This module strips adoption requests of the applicants' names, contact
+// details, occupations and incomes before they are shared with research
+// partners. Applicants are replaced by salted hashes, so requests by the same
+// person can still be counted together without revealing who they are.
+//
+
+use super::types::AnonymizedAdoptionRequest;
+use crate::database_service::types::AnimalAdoptionRequest;
+use sha2::{Digest, Sha256};
+
+/// Number of hex digits kept from the hash standing in for an applicant
+const PSEUDONYM_LENGTH: usize = 12;
+
+/// Replaces an identifying value with a hash of it
+///
+/// # Arguments
+/// * `value` - The value to hide, such as a username
+/// * `salt` - Secret mixed into the hash, so it cannot be reversed by hashing known values
+///
+/// # Returns
+/// * `String` - The first hex digits of the salted hash
+pub fn pseudonymize(value: &str, salt: &str) -> String {
+    let digest = Sha256::digest(format!("{}:{}", salt, value.trim().to_lowercase()));
+    hex::encode(digest)[..PSEUDONYM_LENGTH].to_string()
+}
+
+/// Strips adoption requests of everything that identifies the applicants
+///
+/// # Arguments
+/// * `requests` - The adoption requests to anonymize, with their animals
+/// * `salt` - Secret mixed into the applicants' hashes; use a new one for every export
+///
+/// # Returns
+/// * `Vec<AnonymizedAdoptionRequest>` - The requests, in the same order
+pub fn anonymize_adoption_requests(
+    requests: &[AnimalAdoptionRequest],
+    salt: &str,
+) -> Vec<AnonymizedAdoptionRequest> {
+    requests
+        .iter()
+        .map(
+            |AnimalAdoptionRequest { animal, request }| AnonymizedAdoptionRequest {
+                applicant: pseudonymize(&request.username, salt),
+                specie: animal.specie.clone(),
+                breed: animal.breed.clone(),
+                request_timestamp: request.request_timestamp,
+                adoption_timestamp: Some(request.adoption_timestamp)
+                    .filter(|&timestamp| timestamp > 0),
+                status: request.status.to_string(),
+                country: request.country.clone(),
+                num_people: request.num_people,
+                num_children: request.num_children,
+            },
+        )
+        .collect()
+}
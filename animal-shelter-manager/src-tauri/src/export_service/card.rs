@@ -0,0 +1,324 @@
+//
+// export_service/card.rs
+//
+// This module renders kennel cards: one-page printable profiles of an animal,
+// with its photo, key facts, temperament and a QR code of its ID, which staff
+// put up on its enclosure.
+//
+
+use super::pdf::{LINE_HEIGHT_MM, MARGIN_MM, PAGE_HEIGHT_MM, PAGE_WIDTH_MM};
+use crate::database_service::types::{AnimalDetail, AnimalNote};
+use crate::i18n::{
+    self,
+    types::{Locale, MessageKey},
+};
+use anyhow::{anyhow, Context, Result};
+use chrono::DateTime;
+use printpdf::path::PaintMode;
+use printpdf::{
+    BuiltinFont, ColorBits, ColorSpace, Image, ImageTransform, ImageXObject, Mm, PdfDocument,
+    PdfLayerReference, Px, Rect,
+};
+use qrcode::{Color, QrCode};
+
+/// Size of the square the photo is fitted into, in millimetres
+pub(super) const PHOTO_SIZE_MM: f32 = 90.0;
+/// Largest width or height the photo is embedded at, in pixels
+const MAX_PHOTO_PIXELS: u32 = 1000;
+/// Size of the QR code in millimetres
+const QR_SIZE_MM: f32 = 40.0;
+/// Horizontal position of the values of the facts, in millimetres
+const FACT_VALUE_X_MM: f32 = 150.0;
+/// Most characters written on one line of body text
+const MAX_LINE_CHARACTERS: usize = 85;
+/// Most behavior notes listed under the temperament heading
+const MAX_TEMPERAMENT_NOTES: usize = 3;
+
+/// Renders the kennel card of an animal into a single-page PDF document
+///
+/// # Arguments
+/// * `shelter_name` - Name of the shelter, printed above the animal's name
+/// * `detail` - The animal and its age
+/// * `behavior_notes` - Notes about the animal's temperament, most recent first
+/// * `photo` - The bytes of the animal's photo, if it has one; a photo that cannot be
+///   read is left out
+/// * `locale` - The locale to write the labels in
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bytes of the PDF document or error
+pub fn render_kennel_card_pdf(
+    shelter_name: &str,
+    detail: &AnimalDetail,
+    behavior_notes: &[AnimalNote],
+    photo: Option<&[u8]>,
+    locale: Locale,
+) -> Result<Vec<u8>> {
+    let animal = &detail.animal;
+    let (document, page, layer) = PdfDocument::new(
+        &animal.name,
+        Mm(PAGE_WIDTH_MM),
+        Mm(PAGE_HEIGHT_MM),
+        "Kennel card",
+    );
+    let regular = document
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let bold = document
+        .add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let layer = document.get_page(page).get_layer(layer);
+
+    let mut y = PAGE_HEIGHT_MM - MARGIN_MM;
+    layer.use_text(shelter_name, 12.0, Mm(MARGIN_MM), Mm(y), &regular);
+    y -= LINE_HEIGHT_MM * 2.0;
+    layer.use_text(&animal.name, 32.0, Mm(MARGIN_MM), Mm(y), &bold);
+    y -= LINE_HEIGHT_MM * 1.5;
+
+    // Photo on the left, facts on the right
+    let photo_top = y;
+    if let Some(bytes) = photo {
+        match photo_image(bytes) {
+            Ok(image) => add_photo(&layer, image, MARGIN_MM, photo_top - PHOTO_SIZE_MM),
+            Err(e) => log::warn!("Leaving photo off kennel card of {}: {:#}", animal.id, e),
+        }
+    }
+    let yes_no = |value: bool| {
+        i18n::text(
+            if value {
+                MessageKey::Yes
+            } else {
+                MessageKey::No
+            },
+            locale,
+        )
+    };
+    let admitted = DateTime::from_timestamp(animal.admission_timestamp, 0)
+        .map(|admitted| admitted.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let facts = [
+        (MessageKey::ColumnSpecies, animal.specie.clone()),
+        (MessageKey::ColumnBreed, animal.breed.clone()),
+        (MessageKey::ColumnSex, animal.sex.clone()),
+        (MessageKey::CardAge, age_label(detail.age_months, locale)),
+        (
+            MessageKey::CardNeutered,
+            yes_no(animal.neutered).to_string(),
+        ),
+        (MessageKey::ColumnAdmitted, admitted),
+        (MessageKey::ColumnAnimalId, animal.id.clone()),
+    ];
+    let facts_x = MARGIN_MM + PHOTO_SIZE_MM + 10.0;
+    let mut fact_y = photo_top - LINE_HEIGHT_MM;
+    for (label, value) in facts {
+        layer.use_text(
+            i18n::text(label, locale),
+            11.0,
+            Mm(facts_x),
+            Mm(fact_y),
+            &bold,
+        );
+        layer.use_text(value, 11.0, Mm(FACT_VALUE_X_MM), Mm(fact_y), &regular);
+        fact_y -= LINE_HEIGHT_MM;
+    }
+    y = photo_top - PHOTO_SIZE_MM - LINE_HEIGHT_MM * 1.5;
+
+    // Temperament and bio, stopping above the QR code
+    let text_bottom = MARGIN_MM + QR_SIZE_MM + LINE_HEIGHT_MM;
+    let temperament: Vec<&str> = behavior_notes
+        .iter()
+        .take(MAX_TEMPERAMENT_NOTES)
+        .map(|note| note.content.as_str())
+        .collect();
+    let sections = [
+        (MessageKey::CardTemperament, temperament),
+        (MessageKey::CardAbout, vec![animal.bio.as_str()]),
+    ];
+    for (heading, paragraphs) in sections {
+        let lines: Vec<String> = paragraphs
+            .iter()
+            .flat_map(|paragraph| wrap_text(paragraph, MAX_LINE_CHARACTERS))
+            .collect();
+        if lines.is_empty() || y < text_bottom {
+            continue;
+        }
+        layer.use_text(
+            i18n::text(heading, locale),
+            14.0,
+            Mm(MARGIN_MM),
+            Mm(y),
+            &bold,
+        );
+        y -= LINE_HEIGHT_MM;
+        for line in lines {
+            if y < text_bottom {
+                break;
+            }
+            layer.use_text(line, 11.0, Mm(MARGIN_MM), Mm(y), &regular);
+            y -= LINE_HEIGHT_MM * 0.75;
+        }
+        y -= LINE_HEIGHT_MM * 0.75;
+    }
+
+    // QR code of the ID in the bottom right corner, for looking the animal up
+    let qr_x = PAGE_WIDTH_MM - MARGIN_MM - QR_SIZE_MM;
+    add_qr_code(&layer, &animal.id, qr_x, MARGIN_MM + LINE_HEIGHT_MM * 0.5)?;
+    layer.use_text(
+        i18n::text(MessageKey::CardScanToLookUp, locale),
+        9.0,
+        Mm(qr_x),
+        Mm(MARGIN_MM - LINE_HEIGHT_MM * 0.25),
+        &regular,
+    );
+
+    document
+        .save_to_bytes()
+        .map_err(|e| anyhow!("Failed to render PDF document: {}", e))
+}
+
+/// Describes the age of an animal, such as "2 years, 3 months"
+///
+/// # Arguments
+/// * `age_months` - Age of the animal in months, or None if its birth date is unknown
+/// * `locale` - The locale to write the age in
+///
+/// # Returns
+/// * `String` - The age
+pub(super) fn age_label(age_months: Option<i32>, locale: Locale) -> String {
+    let Some(age_months) = age_months.filter(|&months| months >= 0) else {
+        return i18n::text(MessageKey::AgeUnknown, locale).to_string();
+    };
+    let years = (age_months / 12).to_string();
+    let months = (age_months % 12).to_string();
+    match (age_months / 12, age_months % 12) {
+        (0, _) => i18n::format(MessageKey::AgeMonths, locale, &[("months", &months)]),
+        (_, 0) => i18n::format(MessageKey::AgeYears, locale, &[("years", &years)]),
+        _ => i18n::format(
+            MessageKey::AgeYearsMonths,
+            locale,
+            &[("years", &years), ("months", &months)],
+        ),
+    }
+}
+
+/// Splits text into lines of at most a number of characters, breaking between words
+///
+/// Words longer than a line are put on a line of their own rather than split.
+///
+/// # Arguments
+/// * `text` - The text to split
+/// * `max_characters` - Most characters on one line
+///
+/// # Returns
+/// * `Vec<String>` - The lines, empty if the text is blank
+pub(super) fn wrap_text(text: &str, max_characters: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_characters {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Decodes a photo into raw RGB pixels, shrinking it if it is larger than needed for print
+///
+/// # Arguments
+/// * `bytes` - The bytes of the photo file
+///
+/// # Returns
+/// * `Result<ImageXObject>` - The photo, ready to be placed on a page, or error
+pub(super) fn photo_image(bytes: &[u8]) -> Result<ImageXObject> {
+    let photo = image::load_from_memory(bytes)
+        .context("Failed to decode photo")?
+        .thumbnail(MAX_PHOTO_PIXELS, MAX_PHOTO_PIXELS)
+        .to_rgb8();
+    Ok(ImageXObject {
+        width: Px(photo.width() as usize),
+        height: Px(photo.height() as usize),
+        color_space: ColorSpace::Rgb,
+        bits_per_component: ColorBits::Bit8,
+        interpolate: true,
+        image_data: photo.into_raw(),
+        image_filter: None,
+        smask: None,
+        clipping_bbox: None,
+    })
+}
+
+/// Places a photo in the photo square, as large as fits and centered
+///
+/// # Arguments
+/// * `layer` - The page layer to draw on
+/// * `photo` - The photo
+/// * `x` - Left edge of the square in millimetres
+/// * `y` - Bottom edge of the square in millimetres
+pub(super) fn add_photo(layer: &PdfLayerReference, photo: ImageXObject, x: f32, y: f32) {
+    let (width_px, height_px) = (photo.width.0 as f32, photo.height.0 as f32);
+
+    // Resolution at which the longer side of the photo fills the square
+    let dpi = width_px.max(height_px) * 25.4 / PHOTO_SIZE_MM;
+    let width_mm = width_px * 25.4 / dpi;
+    let height_mm = height_px * 25.4 / dpi;
+    Image::from(photo).add_to_layer(
+        layer.clone(),
+        ImageTransform {
+            translate_x: Some(Mm(x + (PHOTO_SIZE_MM - width_mm) / 2.0)),
+            translate_y: Some(Mm(y + (PHOTO_SIZE_MM - height_mm) / 2.0)),
+            dpi: Some(dpi),
+            ..ImageTransform::default()
+        },
+    );
+}
+
+/// Draws a QR code as filled squares, so it stays sharp at any print size
+///
+/// # Arguments
+/// * `layer` - The page layer to draw on
+/// * `data` - The text to encode
+/// * `x` - Left edge of the code in millimetres
+/// * `y` - Bottom edge of the code in millimetres
+///
+/// # Returns
+/// * `Result<()>` - Success, or error if the text is too long to encode
+fn add_qr_code(layer: &PdfLayerReference, data: &str, x: f32, y: f32) -> Result<()> {
+    let code =
+        QrCode::new(data.as_bytes()).map_err(|e| anyhow!("Failed to encode QR code: {}", e))?;
+    let width = code.width();
+    let module_mm = QR_SIZE_MM / width as f32;
+    let colors = code.to_colors();
+
+    for (row, modules) in colors.chunks(width).enumerate() {
+        let top = y + QR_SIZE_MM - row as f32 * module_mm;
+
+        // Draw each run of dark modules in a row as a single rectangle
+        let mut column = 0;
+        while column < width {
+            if modules[column] == Color::Light {
+                column += 1;
+                continue;
+            }
+            let start = column;
+            while column < width && modules[column] == Color::Dark {
+                column += 1;
+            }
+            layer.add_rect(
+                Rect::new(
+                    Mm(x + start as f32 * module_mm),
+                    Mm(top - module_mm),
+                    Mm(x + column as f32 * module_mm),
+                    Mm(top),
+                )
+                .with_mode(PaintMode::Fill),
+            );
+        }
+    }
+    Ok(())
+}
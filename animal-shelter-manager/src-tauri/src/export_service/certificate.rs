@@ -0,0 +1,347 @@
+//
+// export_service/certificate.rs
+//
+// This module renders the certificates given to adopters: vaccination
+// certificates listing the vaccines an animal has been given, which adopters
+// hand over to their own vet, and adoption certificates with the animal's photo,
+// handed over at pickup. Both carry a signature line.
+//
+
+use super::card::{add_photo, photo_image, wrap_text, PHOTO_SIZE_MM};
+use super::pdf::{LINE_HEIGHT_MM, MARGIN_MM, PAGE_HEIGHT_MM, PAGE_WIDTH_MM};
+use crate::database_service::types::{Animal, Vaccination};
+use crate::i18n::{
+    self,
+    types::{Locale, MessageKey},
+};
+use anyhow::{anyhow, Result};
+use chrono::DateTime;
+use printpdf::path::PaintMode;
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfLayerReference, Rect};
+
+/// Distance of the decorative border from the edge of the page, in millimetres
+const BORDER_INSET_MM: f32 = 10.0;
+/// Horizontal position of the values of the facts, in millimetres
+const FACT_VALUE_X_MM: f32 = 70.0;
+/// Horizontal positions of the vaccination table columns in millimetres
+const TABLE_COLUMNS_MM: [f32; 4] = [MARGIN_MM, 85.0, 120.0, 155.0];
+/// Headings of the vaccination table columns
+const TABLE_HEADINGS: [MessageKey; 4] = [
+    MessageKey::CertificateVaccine,
+    MessageKey::CertificateAdministered,
+    MessageKey::CertificateNextDue,
+    MessageKey::CertificateRecordedBy,
+];
+/// Height kept free at the bottom of the last page for the signature, in millimetres
+const SIGNATURE_HEIGHT_MM: f32 = 45.0;
+/// Width of the signature line in millimetres
+const SIGNATURE_WIDTH_MM: f32 = 70.0;
+/// Most characters written on one line of the adoption certificate's dedication
+const MAX_DEDICATION_CHARACTERS: usize = 60;
+
+/// Renders the vaccination certificate of an animal into a PDF document
+///
+/// The vaccinations are listed in the order given, continuing on further pages when
+/// they do not fit on one.
+///
+/// # Arguments
+/// * `shelter_name` - Name of the shelter issuing the certificate
+/// * `animal` - The animal the certificate is for
+/// * `vaccinations` - The vaccinations the animal was given, in the order to list them
+/// * `issued_timestamp` - Timestamp when the certificate is issued
+/// * `locale` - The locale to write the labels in
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bytes of the PDF document or error
+pub fn render_vaccination_certificate_pdf(
+    shelter_name: &str,
+    animal: &Animal,
+    vaccinations: &[Vaccination],
+    issued_timestamp: i64,
+    locale: Locale,
+) -> Result<Vec<u8>> {
+    let title = i18n::text(MessageKey::CertificateTitle, locale);
+    let (document, page, layer) = PdfDocument::new(
+        format!("{} - {}", title, animal.name),
+        Mm(PAGE_WIDTH_MM),
+        Mm(PAGE_HEIGHT_MM),
+        "Certificate",
+    );
+    let regular = document
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let bold = document
+        .add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let mut layer = document.get_page(page).get_layer(layer);
+    add_border(&layer);
+
+    let mut y = PAGE_HEIGHT_MM - MARGIN_MM;
+    layer.use_text(shelter_name, 12.0, Mm(MARGIN_MM), Mm(y), &regular);
+    y -= LINE_HEIGHT_MM * 2.0;
+    layer.use_text(title, 24.0, Mm(MARGIN_MM), Mm(y), &bold);
+    y -= LINE_HEIGHT_MM * 1.5;
+
+    // Who the certificate is for
+    let mut facts = vec![
+        (MessageKey::ColumnName, animal.name.clone()),
+        (MessageKey::ColumnSpecies, animal.specie.clone()),
+        (MessageKey::ColumnBreed, animal.breed.clone()),
+        (MessageKey::ColumnSex, animal.sex.clone()),
+    ];
+    if let Some(microchip_number) = &animal.microchip_number {
+        facts.push((MessageKey::CertificateMicrochip, microchip_number.clone()));
+    }
+    facts.push((MessageKey::ColumnAnimalId, animal.id.clone()));
+    for (label, value) in facts {
+        layer.use_text(i18n::text(label, locale), 11.0, Mm(MARGIN_MM), Mm(y), &bold);
+        layer.use_text(value, 11.0, Mm(FACT_VALUE_X_MM), Mm(y), &regular);
+        y -= LINE_HEIGHT_MM;
+    }
+    y -= LINE_HEIGHT_MM;
+
+    // The vaccinations, starting a new page whenever one is full
+    let headings = TABLE_HEADINGS.map(|heading| i18n::text(heading, locale).to_string());
+    write_row(&layer, &bold, y, headings.clone());
+    y -= LINE_HEIGHT_MM;
+    if vaccinations.is_empty() {
+        layer.use_text(
+            i18n::text(MessageKey::CertificateNoVaccinations, locale),
+            11.0,
+            Mm(MARGIN_MM),
+            Mm(y),
+            &regular,
+        );
+        y -= LINE_HEIGHT_MM;
+    }
+    for (index, vaccination) in vaccinations.iter().enumerate() {
+        write_row(
+            &layer,
+            &regular,
+            y,
+            [
+                vaccination.vaccine.clone(),
+                format_date(vaccination.administered_timestamp),
+                vaccination
+                    .due_timestamp
+                    .map(format_date)
+                    .unwrap_or_else(|| "-".to_string()),
+                vaccination.recorded_by.clone(),
+            ],
+        );
+        y -= LINE_HEIGHT_MM;
+
+        // Leave room for the signature below the last row
+        let bottom = if index + 1 == vaccinations.len() {
+            MARGIN_MM + SIGNATURE_HEIGHT_MM
+        } else {
+            MARGIN_MM
+        };
+        if y < bottom {
+            let (page, new_layer) =
+                document.add_page(Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Certificate");
+            layer = document.get_page(page).get_layer(new_layer);
+            add_border(&layer);
+            y = PAGE_HEIGHT_MM - MARGIN_MM;
+            if index + 1 < vaccinations.len() {
+                write_row(&layer, &bold, y, headings.clone());
+                y -= LINE_HEIGHT_MM;
+            }
+        }
+    }
+
+    // Issue date and signature line at the bottom of the last page
+    add_signature(&layer, &regular, shelter_name, issued_timestamp, locale);
+
+    document
+        .save_to_bytes()
+        .map_err(|e| anyhow!("Failed to render PDF document: {}", e))
+}
+
+/// Renders the adoption certificate of an animal into a single-page PDF document
+///
+/// # Arguments
+/// * `shelter_name` - Name of the shelter issuing the certificate
+/// * `animal` - The adopted animal
+/// * `adopter_name` - Full name of the adopter
+/// * `adoption_timestamp` - Timestamp when the adoption was completed
+/// * `photo` - The bytes of the animal's photo, if it has one; a photo that cannot be
+///   read is left out
+/// * `locale` - The locale to write the text in
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bytes of the PDF document or error
+pub fn render_adoption_certificate_pdf(
+    shelter_name: &str,
+    animal: &Animal,
+    adopter_name: &str,
+    adoption_timestamp: i64,
+    photo: Option<&[u8]>,
+    locale: Locale,
+) -> Result<Vec<u8>> {
+    let title = i18n::text(MessageKey::AdoptionCertificateTitle, locale);
+    let (document, page, layer) = PdfDocument::new(
+        format!("{} - {}", title, animal.name),
+        Mm(PAGE_WIDTH_MM),
+        Mm(PAGE_HEIGHT_MM),
+        "Certificate",
+    );
+    let regular = document
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let bold = document
+        .add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let layer = document.get_page(page).get_layer(layer);
+    add_border(&layer);
+
+    let mut y = PAGE_HEIGHT_MM - MARGIN_MM;
+    layer.use_text(shelter_name, 12.0, Mm(MARGIN_MM), Mm(y), &regular);
+    y -= LINE_HEIGHT_MM * 2.0;
+    layer.use_text(title, 28.0, Mm(MARGIN_MM), Mm(y), &bold);
+    y -= LINE_HEIGHT_MM * 1.5;
+
+    // Photo in the middle of the page
+    if let Some(bytes) = photo {
+        match photo_image(bytes) {
+            Ok(image) => {
+                let x = (PAGE_WIDTH_MM - PHOTO_SIZE_MM) / 2.0;
+                add_photo(&layer, image, x, y - PHOTO_SIZE_MM);
+                y -= PHOTO_SIZE_MM + LINE_HEIGHT_MM * 1.5;
+            }
+            Err(e) => log::warn!(
+                "Leaving photo off adoption certificate of {}: {:#}",
+                animal.id,
+                e
+            ),
+        }
+    }
+
+    // Who adopted whom, and when
+    layer.use_text(&animal.name, 24.0, Mm(MARGIN_MM), Mm(y), &bold);
+    y -= LINE_HEIGHT_MM * 1.5;
+    let dedication = i18n::format(
+        MessageKey::AdoptionCertificateText,
+        locale,
+        &[
+            ("adopter", adopter_name),
+            ("animal", &animal.name),
+            ("date", &format_date(adoption_timestamp)),
+        ],
+    );
+    for line in wrap_text(&dedication, MAX_DEDICATION_CHARACTERS) {
+        layer.use_text(line, 14.0, Mm(MARGIN_MM), Mm(y), &regular);
+        y -= LINE_HEIGHT_MM;
+    }
+    y -= LINE_HEIGHT_MM * 0.5;
+    layer.use_text(
+        i18n::format(
+            MessageKey::AdoptionCertificateWelcome,
+            locale,
+            &[("animal", &animal.name)],
+        ),
+        16.0,
+        Mm(MARGIN_MM),
+        Mm(y),
+        &bold,
+    );
+
+    add_signature(&layer, &regular, shelter_name, adoption_timestamp, locale);
+
+    document
+        .save_to_bytes()
+        .map_err(|e| anyhow!("Failed to render PDF document: {}", e))
+}
+
+/// Writes who issued a certificate and when, and draws the line it is signed on,
+/// at the bottom of a page
+///
+/// # Arguments
+/// * `layer` - The page layer to draw on
+/// * `font` - The font to write with
+/// * `shelter_name` - Name of the shelter issuing the certificate
+/// * `issued_timestamp` - Timestamp when the certificate is issued
+/// * `locale` - The locale to write the text in
+fn add_signature(
+    layer: &PdfLayerReference,
+    font: &IndirectFontRef,
+    shelter_name: &str,
+    issued_timestamp: i64,
+    locale: Locale,
+) {
+    let signature_y = MARGIN_MM + LINE_HEIGHT_MM;
+    layer.use_text(
+        i18n::format(
+            MessageKey::CertificateIssued,
+            locale,
+            &[
+                ("shelter", shelter_name),
+                ("date", &format_date(issued_timestamp)),
+            ],
+        ),
+        11.0,
+        Mm(MARGIN_MM),
+        Mm(signature_y + LINE_HEIGHT_MM * 3.0),
+        font,
+    );
+    let signature_x = PAGE_WIDTH_MM - MARGIN_MM - SIGNATURE_WIDTH_MM;
+    layer.add_rect(
+        Rect::new(
+            Mm(signature_x),
+            Mm(signature_y),
+            Mm(PAGE_WIDTH_MM - MARGIN_MM),
+            Mm(signature_y + 0.3),
+        )
+        .with_mode(PaintMode::Fill),
+    );
+    layer.use_text(
+        i18n::text(MessageKey::CertificateSignature, locale),
+        9.0,
+        Mm(signature_x),
+        Mm(signature_y - LINE_HEIGHT_MM * 0.75),
+        font,
+    );
+}
+
+/// Draws the border framing a page of the certificate
+///
+/// # Arguments
+/// * `layer` - The page layer to draw on
+fn add_border(layer: &PdfLayerReference) {
+    layer.set_outline_thickness(1.5);
+    layer.add_rect(
+        Rect::new(
+            Mm(BORDER_INSET_MM),
+            Mm(BORDER_INSET_MM),
+            Mm(PAGE_WIDTH_MM - BORDER_INSET_MM),
+            Mm(PAGE_HEIGHT_MM - BORDER_INSET_MM),
+        )
+        .with_mode(PaintMode::Stroke),
+    );
+}
+
+/// Writes one row of the vaccination table
+///
+/// # Arguments
+/// * `layer` - The page layer to write on
+/// * `font` - The font to write with
+/// * `y` - Vertical position of the row in millimetres
+/// * `cells` - The text of each column
+fn write_row(layer: &PdfLayerReference, font: &IndirectFontRef, y: f32, cells: [String; 4]) {
+    for (cell, x) in cells.into_iter().zip(TABLE_COLUMNS_MM) {
+        layer.use_text(cell, 11.0, Mm(x), Mm(y), font);
+    }
+}
+
+/// Formats a timestamp as a date, such as 2025-03-14
+///
+/// # Arguments
+/// * `timestamp` - The timestamp in seconds
+///
+/// # Returns
+/// * `String` - The date
+pub(super) fn format_date(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,284 @@
+//
+// export_service/donation.rs
+//
+// This module renders the receipts given to donors for their donations and the
+// yearly statements summing a donor's donations, which donors file with their
+// tax returns.
+//
+
+use super::card::wrap_text;
+use super::certificate::format_date;
+use super::format_amount;
+use super::pdf::{LINE_HEIGHT_MM, MARGIN_MM, PAGE_HEIGHT_MM, PAGE_WIDTH_MM};
+use crate::database_service::types::{Donation, DonorStatement};
+use crate::i18n::{
+    self,
+    types::{Locale, MessageKey},
+};
+use anyhow::{anyhow, Result};
+use printpdf::path::PaintMode;
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfLayerReference, Rect};
+
+/// Horizontal position of the values of the details, in millimetres
+const DETAIL_VALUE_X_MM: f32 = 70.0;
+/// Horizontal positions of the statement table columns in millimetres
+const TABLE_COLUMNS_MM: [f32; 3] = [MARGIN_MM, 70.0, 130.0];
+/// Most characters written on one line of a donation's notes
+const MAX_NOTES_CHARACTERS: usize = 70;
+
+/// Renders the receipt of a donation into a single-page PDF document
+///
+/// # Arguments
+/// * `shelter_name` - Name of the shelter issuing the receipt
+/// * `donation` - The donation, numbered for its receipt
+/// * `currency` - Three-letter code of the currency the amount is in
+/// * `locale` - The locale to write the labels in
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bytes of the PDF document, or an error if the donation has
+///   no receipt number
+pub fn render_donation_receipt_pdf(
+    shelter_name: &str,
+    donation: &Donation,
+    currency: &str,
+    locale: Locale,
+) -> Result<Vec<u8>> {
+    let number = Donation::format_receipt_number(
+        donation
+            .receipt_number
+            .ok_or_else(|| anyhow!("Donation {} has no receipt number", donation.id))?,
+    );
+    let title = i18n::text(MessageKey::DonationReceiptTitle, locale);
+    let (document, page, layer) = PdfDocument::new(
+        format!("{} {}", title, number),
+        Mm(PAGE_WIDTH_MM),
+        Mm(PAGE_HEIGHT_MM),
+        "Receipt",
+    );
+    let regular = document
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let bold = document
+        .add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let layer = document.get_page(page).get_layer(layer);
+
+    let mut y = PAGE_HEIGHT_MM - MARGIN_MM;
+    layer.use_text(shelter_name, 12.0, Mm(MARGIN_MM), Mm(y), &regular);
+    y -= LINE_HEIGHT_MM * 2.0;
+    layer.use_text(title, 24.0, Mm(MARGIN_MM), Mm(y), &bold);
+    y -= LINE_HEIGHT_MM * 1.5;
+
+    let details = [
+        (MessageKey::PaymentNumber, number),
+        (
+            MessageKey::PaymentDate,
+            format_date(donation.donation_timestamp),
+        ),
+        (MessageKey::ReceiptReceivedFrom, donation.donor_name.clone()),
+        (
+            MessageKey::DonationAmount,
+            format!("{} {}", format_amount(donation.amount), currency),
+        ),
+    ];
+    for (label, value) in details {
+        layer.use_text(i18n::text(label, locale), 11.0, Mm(MARGIN_MM), Mm(y), &bold);
+        layer.use_text(value, 11.0, Mm(DETAIL_VALUE_X_MM), Mm(y), &regular);
+        y -= LINE_HEIGHT_MM;
+    }
+    y -= LINE_HEIGHT_MM;
+
+    if !donation.notes.is_empty() {
+        for line in wrap_text(&donation.notes, MAX_NOTES_CHARACTERS) {
+            layer.use_text(line, 11.0, Mm(MARGIN_MM), Mm(y), &regular);
+            y -= LINE_HEIGHT_MM;
+        }
+        y -= LINE_HEIGHT_MM;
+    }
+
+    let thanks = i18n::format(
+        MessageKey::DonationThanks,
+        locale,
+        &[("shelter", shelter_name)],
+    );
+    for line in wrap_text(&thanks, MAX_NOTES_CHARACTERS) {
+        layer.use_text(line, 11.0, Mm(MARGIN_MM), Mm(y), &regular);
+        y -= LINE_HEIGHT_MM;
+    }
+
+    document
+        .save_to_bytes()
+        .map_err(|e| anyhow!("Failed to render PDF document: {}", e))
+}
+
+/// Renders the yearly statement of a donor's donations into a PDF document
+///
+/// The donations are listed in the order given, continuing on further pages when
+/// they do not fit on one, followed by their total.
+///
+/// # Arguments
+/// * `shelter_name` - Name of the shelter issuing the statement
+/// * `statement` - The donor's donations during the year and their total
+/// * `currency` - Three-letter code of the currency the amounts are in
+/// * `issued_timestamp` - Timestamp when the statement is issued
+/// * `locale` - The locale to write the labels in
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bytes of the PDF document or error
+pub fn render_donor_statement_pdf(
+    shelter_name: &str,
+    statement: &DonorStatement,
+    currency: &str,
+    issued_timestamp: i64,
+    locale: Locale,
+) -> Result<Vec<u8>> {
+    let year = statement.year.to_string();
+    let title = i18n::format(MessageKey::DonorStatementTitle, locale, &[("year", &year)]);
+    let (document, page, layer) = PdfDocument::new(
+        format!("{} - {}", title, statement.donor.name),
+        Mm(PAGE_WIDTH_MM),
+        Mm(PAGE_HEIGHT_MM),
+        "Statement",
+    );
+    let regular = document
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let bold = document
+        .add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let mut layer = document.get_page(page).get_layer(layer);
+
+    let mut y = PAGE_HEIGHT_MM - MARGIN_MM;
+    layer.use_text(shelter_name, 12.0, Mm(MARGIN_MM), Mm(y), &regular);
+    y -= LINE_HEIGHT_MM * 2.0;
+    layer.use_text(&title, 24.0, Mm(MARGIN_MM), Mm(y), &bold);
+    y -= LINE_HEIGHT_MM * 1.5;
+
+    // Who the statement is for, and when it was issued
+    layer.use_text(
+        i18n::text(MessageKey::DonorStatementDonor, locale),
+        11.0,
+        Mm(MARGIN_MM),
+        Mm(y),
+        &bold,
+    );
+    layer.use_text(
+        &statement.donor.name,
+        11.0,
+        Mm(DETAIL_VALUE_X_MM),
+        Mm(y),
+        &regular,
+    );
+    y -= LINE_HEIGHT_MM;
+    if !statement.donor.address.is_empty() {
+        layer.use_text(
+            &statement.donor.address,
+            11.0,
+            Mm(DETAIL_VALUE_X_MM),
+            Mm(y),
+            &regular,
+        );
+        y -= LINE_HEIGHT_MM;
+    }
+    layer.use_text(
+        i18n::format(
+            MessageKey::CertificateIssued,
+            locale,
+            &[
+                ("shelter", shelter_name),
+                ("date", &format_date(issued_timestamp)),
+            ],
+        ),
+        11.0,
+        Mm(MARGIN_MM),
+        Mm(y),
+        &regular,
+    );
+    y -= LINE_HEIGHT_MM * 2.0;
+
+    // The donations, starting a new page whenever one is full
+    let headings = [
+        i18n::text(MessageKey::PaymentDate, locale).to_string(),
+        i18n::text(MessageKey::DonorStatementReceipt, locale).to_string(),
+        i18n::text(MessageKey::DonationAmount, locale).to_string(),
+    ];
+    write_row(&layer, &bold, y, headings.clone());
+    y -= LINE_HEIGHT_MM;
+    if statement.donations.is_empty() {
+        layer.use_text(
+            i18n::text(MessageKey::DonorStatementNoDonations, locale),
+            11.0,
+            Mm(MARGIN_MM),
+            Mm(y),
+            &regular,
+        );
+        y -= LINE_HEIGHT_MM;
+    }
+    for donation in &statement.donations {
+        if y < MARGIN_MM + LINE_HEIGHT_MM * 2.0 {
+            let (page, new_layer) =
+                document.add_page(Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Statement");
+            layer = document.get_page(page).get_layer(new_layer);
+            y = PAGE_HEIGHT_MM - MARGIN_MM;
+            write_row(&layer, &bold, y, headings.clone());
+            y -= LINE_HEIGHT_MM;
+        }
+        write_row(
+            &layer,
+            &regular,
+            y,
+            [
+                format_date(donation.donation_timestamp),
+                donation
+                    .receipt_number
+                    .map(Donation::format_receipt_number)
+                    .unwrap_or_else(|| "-".to_string()),
+                format!("{} {}", format_amount(donation.amount), currency),
+            ],
+        );
+        y -= LINE_HEIGHT_MM;
+    }
+
+    // The total, ruled off from the donations above it
+    layer.add_rect(
+        Rect::new(
+            Mm(MARGIN_MM),
+            Mm(y + LINE_HEIGHT_MM * 0.5),
+            Mm(PAGE_WIDTH_MM - MARGIN_MM),
+            Mm(y + LINE_HEIGHT_MM * 0.5 + 0.3),
+        )
+        .with_mode(PaintMode::Fill),
+    );
+    y -= LINE_HEIGHT_MM * 0.5;
+    layer.use_text(
+        i18n::format(MessageKey::DonorStatementTotal, locale, &[("year", &year)]),
+        12.0,
+        Mm(MARGIN_MM),
+        Mm(y),
+        &bold,
+    );
+    layer.use_text(
+        format!("{} {}", format_amount(statement.total), currency),
+        12.0,
+        Mm(TABLE_COLUMNS_MM[2]),
+        Mm(y),
+        &bold,
+    );
+
+    document
+        .save_to_bytes()
+        .map_err(|e| anyhow!("Failed to render PDF document: {}", e))
+}
+
+/// Writes one row of the statement table
+///
+/// # Arguments
+/// * `layer` - The page layer to write on
+/// * `font` - The font to write with
+/// * `y` - Vertical position of the row in millimetres
+/// * `cells` - The text of each column
+fn write_row(layer: &PdfLayerReference, font: &IndirectFontRef, y: f32, cells: [String; 3]) {
+    for (cell, x) in cells.into_iter().zip(TABLE_COLUMNS_MM) {
+        layer.use_text(cell, 11.0, Mm(x), Mm(y), font);
+    }
+}
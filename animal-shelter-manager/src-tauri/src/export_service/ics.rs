@@ -0,0 +1,171 @@
+//
+// export_service/ics.rs
+//
+// This module writes the events of the shelter calendar as an iCalendar (.ics)
+// file, which Outlook, Google Calendar and other calendar apps can import or
+// subscribe to. Every event is an all-day event on the day it is due.
+//
+
+use crate::database_service::types::{CalendarEvent, CalendarEventKind};
+use crate::i18n::{
+    self,
+    types::{Locale, MessageKey},
+};
+use chrono::{DateTime, Duration, NaiveDate};
+
+/// Identifies the application that wrote the calendar
+const PRODUCT_ID: &str = "-//SEN201//Animal Shelter Manager//EN";
+/// Domain appended to event IDs so they stay unique across calendars
+const UID_DOMAIN: &str = "animal-shelter-manager";
+/// Longest a content line may be, in bytes, before it is folded
+const MAX_LINE_BYTES: usize = 75;
+
+/// Writes calendar events as an iCalendar file
+///
+/// # Arguments
+/// * `shelter_name` - Name of the shelter, used as the name of the calendar
+/// * `events` - The events to write
+/// * `now` - The current timestamp, stamped on every event
+/// * `locale` - The language the titles of the events are written in
+///
+/// # Returns
+/// * `Vec<u8>` - The contents of the file
+pub fn render_calendar_ics(
+    shelter_name: &str,
+    events: &[CalendarEvent],
+    now: i64,
+    locale: Locale,
+) -> Vec<u8> {
+    let stamp = DateTime::from_timestamp(now, 0)
+        .unwrap_or_default()
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string();
+
+    let mut ics = String::new();
+    write_line(&mut ics, "BEGIN:VCALENDAR");
+    write_line(&mut ics, "VERSION:2.0");
+    write_line(&mut ics, &format!("PRODID:{}", PRODUCT_ID));
+    write_line(&mut ics, "CALSCALE:GREGORIAN");
+    write_line(&mut ics, "METHOD:PUBLISH");
+    write_line(
+        &mut ics,
+        &format!("X-WR-CALNAME:{}", escape_text(shelter_name)),
+    );
+
+    for event in events {
+        let day = event_day(event.timestamp);
+        write_line(&mut ics, "BEGIN:VEVENT");
+        write_line(
+            &mut ics,
+            &format!("UID:{}-{}@{}", event.kind, event.record_id, UID_DOMAIN),
+        );
+        write_line(&mut ics, &format!("DTSTAMP:{}", stamp));
+        write_line(
+            &mut ics,
+            &format!("DTSTART;VALUE=DATE:{}", day.format("%Y%m%d")),
+        );
+        write_line(
+            &mut ics,
+            &format!(
+                "DTEND;VALUE=DATE:{}",
+                (day + Duration::days(1)).format("%Y%m%d")
+            ),
+        );
+        write_line(
+            &mut ics,
+            &format!("SUMMARY:{}", escape_text(&event_title(event, locale))),
+        );
+        let description = i18n::format(
+            MessageKey::CalendarAnimalId,
+            locale,
+            &[("id", &event.animal_id)],
+        );
+        write_line(
+            &mut ics,
+            &format!("DESCRIPTION:{}", escape_text(&description)),
+        );
+        write_line(&mut ics, "TRANSP:TRANSPARENT");
+        write_line(&mut ics, "END:VEVENT");
+    }
+
+    write_line(&mut ics, "END:VCALENDAR");
+    ics.into_bytes()
+}
+
+/// Gets the localized title of an event
+fn event_title(event: &CalendarEvent, locale: Locale) -> String {
+    match event.kind {
+        CalendarEventKind::VaccinationDue => i18n::format(
+            MessageKey::CalendarVaccinationDue,
+            locale,
+            &[("vaccine", &event.subject), ("animal", &event.animal_name)],
+        ),
+        CalendarEventKind::FosterReturnDue => i18n::format(
+            MessageKey::CalendarFosterReturnDue,
+            locale,
+            &[
+                ("animal", &event.animal_name),
+                ("caregiver", &event.subject),
+            ],
+        ),
+        CalendarEventKind::Appointment => i18n::format(
+            MessageKey::CalendarAppointment,
+            locale,
+            &[("visitor", &event.subject), ("animal", &event.animal_name)],
+        ),
+        CalendarEventKind::Surgery => i18n::format(
+            MessageKey::CalendarSurgery,
+            locale,
+            &[("animal", &event.animal_name)],
+        ),
+    }
+}
+
+/// Gets the day an event is due on, in UTC like the dates of reminders
+fn event_day(timestamp: i64) -> NaiveDate {
+    DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .date_naive()
+}
+
+/// Escapes the characters that have a meaning in iCalendar text values
+///
+/// # Arguments
+/// * `text` - The text to escape
+///
+/// # Returns
+/// * `String` - The text with backslashes, semicolons, commas and line breaks escaped
+pub(super) fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Appends a content line, folding it into lines of at most 75 bytes that each
+/// continue with a leading space, and ending every line with CRLF
+///
+/// # Arguments
+/// * `ics` - The file being written
+/// * `line` - The content line
+pub(super) fn write_line(ics: &mut String, line: &str) {
+    let mut line_bytes = 0;
+    for character in line.chars() {
+        let width = character.len_utf8();
+        if line_bytes + width > MAX_LINE_BYTES {
+            ics.push_str("\r\n ");
+            line_bytes = 1;
+        }
+        ics.push(character);
+        line_bytes += width;
+    }
+    ics.push_str("\r\n");
+}
@@ -0,0 +1,167 @@
+//
+// export_service/invoice.rs
+//
+// This module renders the invoices sent to adopters for their adoption fees
+// and the receipts given once the fees are paid, each printed with the number
+// it was issued under.
+//
+
+use super::card::wrap_text;
+use super::certificate::format_date;
+use super::format_amount;
+use super::pdf::{LINE_HEIGHT_MM, MARGIN_MM, PAGE_HEIGHT_MM, PAGE_WIDTH_MM};
+use crate::database_service::types::{Payment, PaymentDocument};
+use crate::i18n::{
+    self,
+    types::{Locale, MessageKey},
+};
+use anyhow::{anyhow, Result};
+use printpdf::path::PaintMode;
+use printpdf::{BuiltinFont, Mm, PdfDocument, Rect};
+
+/// Horizontal position of the values of the details, in millimetres
+const DETAIL_VALUE_X_MM: f32 = 70.0;
+/// Horizontal position where amounts end, in millimetres
+const AMOUNT_X_MM: f32 = 150.0;
+/// Most characters written on one line of the description
+const MAX_DESCRIPTION_CHARACTERS: usize = 60;
+
+/// Renders the invoice or receipt of a payment into a single-page PDF document
+///
+/// An invoice is dated when the fee was charged and a receipt when it was paid.
+///
+/// # Arguments
+/// * `document` - Whether to render the invoice or the receipt
+/// * `shelter_name` - Name of the shelter issuing the document
+/// * `payment` - The payment, numbered for the document
+/// * `payer_name` - Full name of whoever the fee is charged to
+/// * `animal_name` - Name of the animal the fee is for
+/// * `currency` - Three-letter code of the currency the amount is in
+/// * `locale` - The locale to write the labels in
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bytes of the PDF document, or an error if the payment has
+///   no number for the document or a receipt is asked for an unpaid payment
+pub fn render_payment_document_pdf(
+    document: PaymentDocument,
+    shelter_name: &str,
+    payment: &Payment,
+    payer_name: &str,
+    animal_name: &str,
+    currency: &str,
+    locale: Locale,
+) -> Result<Vec<u8>> {
+    let (number, issued_timestamp, title, payer_label, amount_label) = match document {
+        PaymentDocument::Invoice => (
+            payment.invoice_number,
+            Some(payment.created_timestamp),
+            MessageKey::InvoiceTitle,
+            MessageKey::InvoiceBilledTo,
+            MessageKey::InvoiceAmountDue,
+        ),
+        PaymentDocument::Receipt => (
+            payment.receipt_number,
+            payment.paid_timestamp,
+            MessageKey::ReceiptTitle,
+            MessageKey::ReceiptReceivedFrom,
+            MessageKey::ReceiptAmountPaid,
+        ),
+    };
+    let number = document.format_number(
+        number.ok_or_else(|| anyhow!("Payment {} has no {} number", payment.id, document))?,
+    );
+    let issued_timestamp =
+        issued_timestamp.ok_or_else(|| anyhow!("Payment {} has not been paid", payment.id))?;
+
+    let title = i18n::text(title, locale);
+    let (pdf, page, layer) = PdfDocument::new(
+        format!("{} {}", title, number),
+        Mm(PAGE_WIDTH_MM),
+        Mm(PAGE_HEIGHT_MM),
+        "Document",
+    );
+    let regular = pdf
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let bold = pdf
+        .add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let layer = pdf.get_page(page).get_layer(layer);
+
+    let mut y = PAGE_HEIGHT_MM - MARGIN_MM;
+    layer.use_text(shelter_name, 12.0, Mm(MARGIN_MM), Mm(y), &regular);
+    y -= LINE_HEIGHT_MM * 2.0;
+    layer.use_text(title, 24.0, Mm(MARGIN_MM), Mm(y), &bold);
+    y -= LINE_HEIGHT_MM * 1.5;
+
+    // Which document this is and who it is for
+    let details = [
+        (MessageKey::PaymentNumber, number),
+        (MessageKey::PaymentDate, format_date(issued_timestamp)),
+        (payer_label, payer_name.to_string()),
+        (MessageKey::PaymentAnimal, animal_name.to_string()),
+    ];
+    for (label, value) in details {
+        layer.use_text(i18n::text(label, locale), 11.0, Mm(MARGIN_MM), Mm(y), &bold);
+        layer.use_text(value, 11.0, Mm(DETAIL_VALUE_X_MM), Mm(y), &regular);
+        y -= LINE_HEIGHT_MM;
+    }
+    y -= LINE_HEIGHT_MM;
+
+    // What the fee is for, ruled off from the total below it
+    let amount = format!("{} {}", format_amount(payment.amount), currency);
+    layer.use_text(
+        i18n::text(MessageKey::PaymentDescription, locale),
+        11.0,
+        Mm(MARGIN_MM),
+        Mm(y),
+        &bold,
+    );
+    y -= LINE_HEIGHT_MM;
+    for (index, line) in wrap_text(&payment.description, MAX_DESCRIPTION_CHARACTERS)
+        .into_iter()
+        .enumerate()
+    {
+        layer.use_text(line, 11.0, Mm(MARGIN_MM), Mm(y), &regular);
+        if index == 0 {
+            layer.use_text(&amount, 11.0, Mm(AMOUNT_X_MM), Mm(y), &regular);
+        }
+        y -= LINE_HEIGHT_MM;
+    }
+    layer.add_rect(
+        Rect::new(
+            Mm(MARGIN_MM),
+            Mm(y + LINE_HEIGHT_MM * 0.5),
+            Mm(PAGE_WIDTH_MM - MARGIN_MM),
+            Mm(y + LINE_HEIGHT_MM * 0.5 + 0.3),
+        )
+        .with_mode(PaintMode::Fill),
+    );
+    y -= LINE_HEIGHT_MM * 0.5;
+    layer.use_text(
+        i18n::text(amount_label, locale),
+        12.0,
+        Mm(MARGIN_MM),
+        Mm(y),
+        &bold,
+    );
+    layer.use_text(&amount, 12.0, Mm(AMOUNT_X_MM), Mm(y), &bold);
+
+    if document == PaymentDocument::Receipt {
+        y -= LINE_HEIGHT_MM * 2.0;
+        layer.use_text(
+            i18n::format(
+                MessageKey::ReceiptThanks,
+                locale,
+                &[("date", &format_date(issued_timestamp))],
+            ),
+            11.0,
+            Mm(MARGIN_MM),
+            Mm(y),
+            &regular,
+        );
+    }
+
+    pdf.save_to_bytes()
+        .map_err(|e| anyhow!("Failed to render PDF document: {}", e))
+}
@@ -0,0 +1,158 @@
+//
+// export_service/listings.rs
+//
+// This module writes the public listings feed: the animals available for
+// adoption, with only the fields safe to publish, as a JSON or XML document
+// that the organization's website and adoption aggregators can read. Photos
+// are referred to by paths relative to the feed, where copies are put.
+//
+
+use super::types::{Listing, ListingPhoto, ListingsFeed, ListingsFormat};
+use crate::database_service::types::Animal;
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat};
+use std::path::Path;
+
+/// Lists animals in a feed, naming a copy of each photo in the photo directory
+///
+/// # Arguments
+/// * `shelter_name` - Name of the shelter listing the animals
+/// * `animals` - The animals to list
+/// * `photo_directory` - Name of the directory next to the feed the photos are copied to
+/// * `now` - The current timestamp
+///
+/// # Returns
+/// * `(ListingsFeed, Vec<ListingPhoto>)` - The feed, and the photos to copy next to it
+pub fn build_listings_feed(
+    shelter_name: &str,
+    animals: &[Animal],
+    photo_directory: &str,
+    now: i64,
+) -> (ListingsFeed, Vec<ListingPhoto>) {
+    let mut photos = Vec::new();
+    let listings = animals
+        .iter()
+        .map(|animal| {
+            let photo = animal.image_path.as_ref().map(|image_path| {
+                let extension = Path::new(image_path)
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .unwrap_or("jpg")
+                    .to_ascii_lowercase();
+                let relative_path = format!("{}/{}.{}", photo_directory, animal.id, extension);
+                photos.push(ListingPhoto {
+                    source_path: image_path.clone(),
+                    relative_path: relative_path.clone(),
+                });
+                relative_path
+            });
+            Listing {
+                id: animal.id.clone(),
+                name: animal.name.clone(),
+                species: animal.specie.clone(),
+                breed: animal.breed.clone(),
+                sex: animal.sex.clone(),
+                birth_month: animal.birth_month,
+                birth_year: animal.birth_year,
+                neutered: animal.neutered,
+                appearance: animal.appearance.clone(),
+                bio: animal.bio.clone(),
+                admission_date: DateTime::from_timestamp(animal.admission_timestamp, 0)
+                    .map(|date| date.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default(),
+                photo,
+            }
+        })
+        .collect();
+
+    let feed = ListingsFeed {
+        shelter_name: shelter_name.to_string(),
+        generated_at: DateTime::from_timestamp(now, 0)
+            .unwrap_or_default()
+            .to_rfc3339_opts(SecondsFormat::Secs, true),
+        animals: listings,
+    };
+    (feed, photos)
+}
+
+/// Writes a listings feed in a file format
+///
+/// # Arguments
+/// * `feed` - The feed to write
+/// * `format` - The file format to write it in
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The contents of the file or error
+pub fn render_listings_feed(feed: &ListingsFeed, format: ListingsFormat) -> Result<Vec<u8>> {
+    match format {
+        ListingsFormat::Json => {
+            serde_json::to_vec_pretty(feed).context("Failed to write listings as JSON")
+        }
+        ListingsFormat::Xml => Ok(render_listings_xml(feed).into_bytes()),
+    }
+}
+
+/// Writes a listings feed as an XML document, with one `animal` element per listing
+fn render_listings_xml(feed: &ListingsFeed) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<listings shelterName=\"{}\" generatedAt=\"{}\">\n",
+        escape_xml(&feed.shelter_name),
+        escape_xml(&feed.generated_at)
+    ));
+    for listing in &feed.animals {
+        xml.push_str(&format!("  <animal id=\"{}\">\n", escape_xml(&listing.id)));
+        let optional = |value: Option<i32>| value.map(|value| value.to_string());
+        let fields = [
+            ("name", Some(listing.name.clone())),
+            ("species", Some(listing.species.clone())),
+            ("breed", Some(listing.breed.clone())),
+            ("sex", Some(listing.sex.clone())),
+            ("birthMonth", optional(listing.birth_month)),
+            ("birthYear", optional(listing.birth_year)),
+            ("neutered", Some(listing.neutered.to_string())),
+            ("appearance", Some(listing.appearance.clone())),
+            ("bio", Some(listing.bio.clone())),
+            ("admissionDate", Some(listing.admission_date.clone())),
+            ("photo", listing.photo.clone()),
+        ];
+        // Unknown values are left out rather than written empty
+        for (element, value) in fields {
+            if let Some(value) = value {
+                xml.push_str(&format!(
+                    "    <{0}>{1}</{0}>\n",
+                    element,
+                    escape_xml(&value)
+                ));
+            }
+        }
+        xml.push_str("  </animal>\n");
+    }
+    xml.push_str("</listings>\n");
+    xml
+}
+
+/// Escapes the characters that have a meaning in XML text and attribute values
+///
+/// # Arguments
+/// * `text` - The text to escape
+///
+/// # Returns
+/// * `String` - The text with markup characters replaced by entities and control
+///   characters XML does not allow removed
+pub(super) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(character),
+            _ if character.is_control() => {}
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
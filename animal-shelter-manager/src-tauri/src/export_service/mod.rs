@@ -0,0 +1,91 @@
+//
+// export_service/mod.rs
+//
+// This module renders shelter data into printable and spreadsheet documents,
+// such as activity reports, the kennel cards put up on enclosures and the
+// vaccination and adoption certificates given to adopters, the invoices and
+// receipts for adoption fees, donation receipts and yearly donor statements,
+// and into the feed of animals published to the website. Adoption requests can also be
+// anonymized before they are shared with research partners.
+// It only produces the bytes of each document; saving them is left to the
+// file service. Headings are written in the locale they are given.
+//
+
+use crate::database_service::{types::MonthlyReport, MINOR_UNITS_PER_UNIT};
+use crate::i18n::{
+    self,
+    types::{Locale, MessageKey},
+};
+
+mod anonymize;
+mod card;
+mod certificate;
+mod donation;
+mod ics;
+mod invoice;
+mod listings;
+mod pdf;
+mod test;
+pub mod types;
+mod xlsx;
+
+pub use anonymize::anonymize_adoption_requests;
+pub use card::render_kennel_card_pdf;
+pub use certificate::{render_adoption_certificate_pdf, render_vaccination_certificate_pdf};
+pub use donation::{render_donation_receipt_pdf, render_donor_statement_pdf};
+pub use ics::render_calendar_ics;
+pub use invoice::render_payment_document_pdf;
+pub use listings::{build_listings_feed, render_listings_feed};
+pub use pdf::render_reports_pdf;
+pub use xlsx::{
+    render_adoption_requests_xlsx, render_animals_xlsx, render_anonymized_adoption_requests_xlsx,
+    render_reports_xlsx,
+};
+
+/// Formats the month of a report, such as "March 2024"
+///
+/// # Arguments
+/// * `report` - The report to label
+/// * `locale` - The locale to name the month in
+///
+/// # Returns
+/// * `String` - The month name and year
+pub fn month_label(report: &MonthlyReport, locale: Locale) -> String {
+    match MessageKey::month(report.month) {
+        Some(month) => i18n::format(
+            MessageKey::MonthLabel,
+            locale,
+            &[
+                ("month", i18n::text(month, locale)),
+                ("year", &report.year.to_string()),
+            ],
+        ),
+        None => format!("{}-{:02}", report.year, report.month),
+    }
+}
+
+/// Formats an amount of money stored in minor currency units, such as 1234.50
+///
+/// # Arguments
+/// * `amount` - The amount in minor units, such as satang
+///
+/// # Returns
+/// * `String` - The amount in units of the currency, with two decimals
+pub fn format_amount(amount: i64) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+    let amount = amount.unsigned_abs();
+    let units = MINOR_UNITS_PER_UNIT.unsigned_abs();
+    format!("{}{}.{:02}", sign, amount / units, amount % units)
+}
+
+/// Gets the text of several headings
+///
+/// # Arguments
+/// * `keys` - The keys of the headings
+/// * `locale` - The locale to write the headings in
+///
+/// # Returns
+/// * `[&'static str; N]` - The headings, in the order of their keys
+fn headings<const N: usize>(keys: [MessageKey; N], locale: Locale) -> [&'static str; N] {
+    keys.map(|key| i18n::text(key, locale))
+}
@@ -0,0 +1,242 @@
+//
+// export_service/pdf.rs
+//
+// This module renders activity reports into printable PDF documents.
+//
+
+use super::{format_amount, headings, month_label};
+use crate::database_service::types::MonthlyReport;
+use crate::i18n::{
+    self,
+    types::{Locale, MessageKey},
+};
+use anyhow::{anyhow, Result};
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfLayerReference};
+
+/// Width of an A4 page in millimetres
+pub(super) const PAGE_WIDTH_MM: f32 = 210.0;
+/// Height of an A4 page in millimetres
+pub(super) const PAGE_HEIGHT_MM: f32 = 297.0;
+/// Margin around the content of a page in millimetres
+pub(super) const MARGIN_MM: f32 = 20.0;
+/// Vertical distance between two lines of text in millimetres
+pub(super) const LINE_HEIGHT_MM: f32 = 8.0;
+/// Horizontal positions of the report table columns in millimetres
+const TABLE_COLUMNS_MM: [f32; 8] = [MARGIN_MM, 50.0, 73.0, 94.0, 116.0, 136.0, 154.0, 174.0];
+/// Font size of the report table, small enough to fit every column on the page
+const TABLE_FONT_SIZE: f32 = 9.0;
+/// Headings of the report table columns
+const REPORT_COLUMNS: [MessageKey; 8] = [
+    MessageKey::ReportMonth,
+    MessageKey::ReportAdmissions,
+    MessageKey::ReportIntakes,
+    MessageKey::ReportAdoptions,
+    MessageKey::ReportReturns,
+    MessageKey::ReportDeaths,
+    MessageKey::ReportFees,
+    MessageKey::ReportDonations,
+];
+
+/// Renders one or more monthly reports into a single-page PDF document
+///
+/// A single report is rendered with its intakes broken down by type, its deaths by cause
+/// and its revenue; several reports, such as the twelve months of a year, are rendered
+/// as a table with a total row.
+///
+/// # Arguments
+/// * `title` - Title printed at the top of the document
+/// * `reports` - The monthly reports to include, in the order to print them
+/// * `currency` - Three-letter code of the currency the amounts are in
+/// * `locale` - The locale to write the headings in
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bytes of the PDF document or error
+pub fn render_reports_pdf(
+    title: &str,
+    reports: &[MonthlyReport],
+    currency: &str,
+    locale: Locale,
+) -> Result<Vec<u8>> {
+    let (document, page, layer) =
+        PdfDocument::new(title, Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Report");
+    let regular = document
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let bold = document
+        .add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| anyhow!("Failed to load font: {}", e))?;
+    let layer = document.get_page(page).get_layer(layer);
+
+    let mut y = PAGE_HEIGHT_MM - MARGIN_MM;
+    layer.use_text(title, 18.0, Mm(MARGIN_MM), Mm(y), &bold);
+    y -= LINE_HEIGHT_MM * 2.0;
+
+    // Table of counts and amounts, one row per month
+    write_row(&layer, &bold, y, headings(REPORT_COLUMNS, locale));
+    y -= LINE_HEIGHT_MM;
+    for report in reports {
+        let intake_count: i64 = report.intakes_by_type.iter().map(|c| c.count).sum();
+        write_row(
+            &layer,
+            &regular,
+            y,
+            [
+                month_label(report, locale),
+                report.admission_count.to_string(),
+                intake_count.to_string(),
+                report.adoption_count.to_string(),
+                report.return_count.to_string(),
+                report.death_count.to_string(),
+                format_amount(report.fee_revenue),
+                format_amount(report.donation_revenue),
+            ],
+        );
+        y -= LINE_HEIGHT_MM;
+    }
+
+    if reports.len() > 1 {
+        let total = |count: fn(&MonthlyReport) -> i64| -> String {
+            reports.iter().map(count).sum::<i64>().to_string()
+        };
+        let total_amount = |amount: fn(&MonthlyReport) -> i64| -> String {
+            format_amount(reports.iter().map(amount).sum())
+        };
+        write_row(
+            &layer,
+            &bold,
+            y,
+            [
+                i18n::text(MessageKey::ReportTotal, locale).to_string(),
+                total(|r| r.admission_count),
+                total(|r| r.intakes_by_type.iter().map(|c| c.count).sum()),
+                total(|r| r.adoption_count),
+                total(|r| r.return_count),
+                total(|r| r.death_count),
+                total_amount(|r| r.fee_revenue),
+                total_amount(|r| r.donation_revenue),
+            ],
+        );
+        y -= LINE_HEIGHT_MM;
+        layer.use_text(
+            i18n::format(
+                MessageKey::ReportAmountsIn,
+                locale,
+                &[("currency", currency)],
+            ),
+            TABLE_FONT_SIZE,
+            Mm(MARGIN_MM),
+            Mm(y),
+            &regular,
+        );
+    } else if let Some(report) = reports.first() {
+        // Break a single month's intakes down by type, and its deaths by cause
+        for (heading, empty, counts) in [
+            (
+                MessageKey::ReportIntakesByType,
+                MessageKey::ReportNoIntakes,
+                &report.intakes_by_type,
+            ),
+            (
+                MessageKey::ReportDeathsByCause,
+                MessageKey::ReportNoDeaths,
+                &report.deaths_by_cause,
+            ),
+        ] {
+            y -= LINE_HEIGHT_MM;
+            write_heading(&layer, &bold, y, i18n::text(heading, locale));
+            y -= LINE_HEIGHT_MM;
+            if counts.is_empty() {
+                write_line(
+                    &layer,
+                    &regular,
+                    y,
+                    i18n::text(empty, locale),
+                    String::new(),
+                );
+                y -= LINE_HEIGHT_MM;
+            }
+            for count in counts {
+                write_line(&layer, &regular, y, &count.label, count.count.to_string());
+                y -= LINE_HEIGHT_MM;
+            }
+        }
+
+        // Then its revenue
+        y -= LINE_HEIGHT_MM;
+        write_heading(
+            &layer,
+            &bold,
+            y,
+            i18n::text(MessageKey::ReportRevenue, locale),
+        );
+        for (key, amount) in [
+            (MessageKey::ReportFees, report.fee_revenue),
+            (MessageKey::ReportDonations, report.donation_revenue),
+            (
+                MessageKey::ReportTotal,
+                report.fee_revenue + report.donation_revenue,
+            ),
+        ] {
+            y -= LINE_HEIGHT_MM;
+            write_line(
+                &layer,
+                &regular,
+                y,
+                i18n::text(key, locale),
+                format!("{} {}", format_amount(amount), currency),
+            );
+        }
+    }
+
+    document
+        .save_to_bytes()
+        .map_err(|e| anyhow!("Failed to render PDF document: {}", e))
+}
+
+/// Writes the heading of a section below the report table
+///
+/// # Arguments
+/// * `layer` - The page layer to write on
+/// * `font` - The font to write with
+/// * `y` - Vertical position of the heading in millimetres
+/// * `heading` - The text of the heading
+fn write_heading(layer: &PdfLayerReference, font: &IndirectFontRef, y: f32, heading: &str) {
+    layer.use_text(heading, 12.0, Mm(MARGIN_MM), Mm(y), font);
+}
+
+/// Writes a label and its value on one line of a section below the report table
+///
+/// # Arguments
+/// * `layer` - The page layer to write on
+/// * `font` - The font to write with
+/// * `y` - Vertical position of the line in millimetres
+/// * `label` - The text on the left
+/// * `value` - The text lined up with the third column of the table
+fn write_line(
+    layer: &PdfLayerReference,
+    font: &IndirectFontRef,
+    y: f32,
+    label: &str,
+    value: String,
+) {
+    layer.use_text(label, 11.0, Mm(MARGIN_MM), Mm(y), font);
+    layer.use_text(value, 11.0, Mm(TABLE_COLUMNS_MM[2]), Mm(y), font);
+}
+
+/// Writes one row of the report table
+///
+/// # Arguments
+/// * `layer` - The page layer to write on
+/// * `font` - The font to write with
+/// * `y` - Vertical position of the row in millimetres
+/// * `cells` - The text of each column
+fn write_row<S: Into<String>>(
+    layer: &PdfLayerReference,
+    font: &IndirectFontRef,
+    y: f32,
+    cells: [S; 8],
+) {
+    for (cell, x) in cells.into_iter().zip(TABLE_COLUMNS_MM) {
+        layer.use_text(cell, TABLE_FONT_SIZE, Mm(x), Mm(y), font);
+    }
+}
@@ -0,0 +1,552 @@
+//
+// export_service/test.rs
+//
+// This file contains unit tests for the export service module.
+//
+
+#[cfg(test)]
+mod export_service_tests {
+    use crate::database_service::types::{
+        AdoptionRequest, AdoptionRequestSummary, Animal, AnimalAdoptionRequest, AnimalDetail,
+        AnimalNote, AnimalStatus, AnimalSummary, CalendarEvent, CalendarEventKind, CategoryCount,
+        Contact, ContactKind, Donation, DonorStatement, MonthlyReport, NoteCategory, Payment,
+        PaymentDocument, RequestStatus, Vaccination,
+    };
+    use crate::export_service::{
+        anonymize::pseudonymize,
+        anonymize_adoption_requests, build_listings_feed,
+        card::{age_label, wrap_text},
+        format_amount,
+        ics::{escape_text, write_line},
+        listings::escape_xml,
+        month_label, render_adoption_certificate_pdf, render_adoption_requests_xlsx,
+        render_animals_xlsx, render_anonymized_adoption_requests_xlsx, render_calendar_ics,
+        render_donation_receipt_pdf, render_donor_statement_pdf, render_kennel_card_pdf,
+        render_listings_feed, render_payment_document_pdf, render_reports_pdf, render_reports_xlsx,
+        render_vaccination_certificate_pdf,
+        types::{ListingsFeed, ListingsFormat},
+    };
+    use crate::i18n::types::Locale;
+    use std::io::Cursor;
+
+    /// Helper function to create a sample monthly report for testing
+    ///
+    /// # Arguments
+    /// * `month` - The month of the report
+    ///
+    /// # Returns
+    /// * `MonthlyReport` - Sample report with a few counts
+    fn sample_report(month: u32) -> MonthlyReport {
+        MonthlyReport {
+            year: 2024,
+            month,
+            admission_count: 3,
+            intakes_by_type: vec![CategoryCount {
+                label: "stray".to_string(),
+                count: 2,
+            }],
+            adoption_count: 1,
+            return_count: 0,
+            death_count: 1,
+            deaths_by_cause: vec![CategoryCount {
+                label: "Euthanasia".to_string(),
+                count: 1,
+            }],
+            fee_revenue: 150000,
+            donation_revenue: 25050,
+        }
+    }
+
+    /// Helper function to create the details of a sample animal for testing
+    ///
+    /// # Returns
+    /// * `AnimalDetail` - Sample animal aged two years and three months
+    fn sample_detail() -> AnimalDetail {
+        AnimalDetail {
+            animal: Animal {
+                id: "animal-1".to_string(),
+                name: "Rex".to_string(),
+                specie: "Dog".to_string(),
+                breed: "Beagle".to_string(),
+                sex: "Male".to_string(),
+                birth_month: Some(1),
+                birth_year: Some(2022),
+                neutered: true,
+                admission_timestamp: 1_700_000_000,
+                status: AnimalStatus::Available,
+                image_path: None,
+                appearance: "Tricolor coat".to_string(),
+                bio: "Rex loves long walks and belly rubs. ".repeat(20),
+                version: 1,
+                microchip_number: None,
+                location_id: None,
+                good_with_kids: None,
+                good_with_dogs: None,
+                good_with_cats: None,
+                special_needs: None,
+                size: None,
+                color: None,
+            },
+            adoption_requests: Vec::new(),
+            latest_medical_note: None,
+            age_months: Some(27),
+            deceased_record: None,
+        }
+    }
+
+    #[test]
+    fn test_month_label() {
+        assert_eq!(month_label(&sample_report(3), Locale::En), "March 2024");
+        assert_eq!(month_label(&sample_report(3), Locale::Es), "marzo de 2024");
+        assert_eq!(month_label(&sample_report(13), Locale::En), "2024-13");
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(150050), "1500.50");
+        assert_eq!(format_amount(5), "0.05");
+        assert_eq!(format_amount(0), "0.00");
+        assert_eq!(format_amount(-25000), "-250.00");
+    }
+
+    #[test]
+    fn test_render_reports_pdf() {
+        let monthly =
+            render_reports_pdf("Monthly Report", &[sample_report(3)], "THB", Locale::En).unwrap();
+        assert!(monthly.starts_with(b"%PDF"));
+
+        let reports: Vec<MonthlyReport> = (1..=12).map(sample_report).collect();
+        let annual = render_reports_pdf("Annual Report", &reports, "THB", Locale::En).unwrap();
+        assert!(annual.starts_with(b"%PDF"));
+
+        // Spanish headings are written with the built-in fonts
+        let spanish = render_reports_pdf("Informe anual", &reports, "THB", Locale::Es).unwrap();
+        assert!(spanish.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_render_kennel_card_pdf() {
+        let detail = sample_detail();
+        let notes = vec![AnimalNote {
+            id: "note-1".to_string(),
+            animal_id: "animal-1".to_string(),
+            author_username: "staffer".to_string(),
+            category: NoteCategory::Behavior,
+            content: "Shy with strangers at first, then very affectionate".to_string(),
+            internal: false,
+            created_timestamp: 1_700_000_000,
+        }];
+        let mut photo = Vec::new();
+        image::RgbImage::new(40, 30)
+            .write_to(&mut Cursor::new(&mut photo), image::ImageFormat::Png)
+            .unwrap();
+
+        let card = render_kennel_card_pdf("Happy Paws", &detail, &notes, Some(&photo), Locale::En)
+            .unwrap();
+        assert!(card.starts_with(b"%PDF"));
+
+        // A photo that cannot be read is left out rather than failing the card
+        let card =
+            render_kennel_card_pdf("Happy Paws", &detail, &[], Some(b"not a photo"), Locale::Es)
+                .unwrap();
+        assert!(card.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_render_vaccination_certificate_pdf() {
+        let mut animal = sample_detail().animal;
+        animal.microchip_number = Some("985112345678901".to_string());
+        let vaccinations: Vec<Vaccination> = (0..40)
+            .map(|index| Vaccination {
+                id: format!("v{}", index),
+                animal_id: animal.id.clone(),
+                vaccine: "Rabies".to_string(),
+                administered_timestamp: 1_700_000_000 + index * 86_400,
+                due_timestamp: Some(1_731_536_000),
+                recorded_by: "staffer".to_string(),
+                contact_id: None,
+            })
+            .collect();
+
+        // A long history continues on further pages
+        let certificate = render_vaccination_certificate_pdf(
+            "Happy Paws",
+            &animal,
+            &vaccinations,
+            1_710_000_000,
+            Locale::En,
+        )
+        .unwrap();
+        assert!(certificate.starts_with(b"%PDF"));
+
+        let empty = render_vaccination_certificate_pdf(
+            "Happy Paws",
+            &animal,
+            &[],
+            1_710_000_000,
+            Locale::Es,
+        )
+        .unwrap();
+        assert!(empty.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_render_adoption_certificate_pdf() {
+        let animal = sample_detail().animal;
+        let mut photo = Vec::new();
+        image::RgbImage::new(40, 30)
+            .write_to(&mut Cursor::new(&mut photo), image::ImageFormat::Png)
+            .unwrap();
+
+        let certificate = render_adoption_certificate_pdf(
+            "Happy Paws",
+            &animal,
+            "Jira Pit",
+            1_710_000_000,
+            Some(&photo),
+            Locale::En,
+        )
+        .unwrap();
+        assert!(certificate.starts_with(b"%PDF"));
+
+        // A photo that cannot be read is left out rather than failing the certificate
+        let certificate = render_adoption_certificate_pdf(
+            "Happy Paws",
+            &animal,
+            "Jira Pit",
+            1_710_000_000,
+            Some(b"not a photo"),
+            Locale::Es,
+        )
+        .unwrap();
+        assert!(certificate.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_render_payment_document_pdf() {
+        let mut payment = Payment {
+            id: "payment-1".to_string(),
+            request_id: "request-1".to_string(),
+            amount: 15000,
+            description: "Adoption fee, including vaccinations and microchip".to_string(),
+            created_timestamp: 1_710_000_000,
+            paid_timestamp: None,
+            invoice_number: Some(42),
+            receipt_number: None,
+        };
+        let render = |document, payment: &Payment| {
+            render_payment_document_pdf(
+                document,
+                "Happy Paws",
+                payment,
+                "Jira Pit",
+                "Rex",
+                "USD",
+                Locale::En,
+            )
+        };
+        assert!(render(PaymentDocument::Invoice, &payment)
+            .unwrap()
+            .starts_with(b"%PDF"));
+
+        // A receipt needs the fee to be paid and numbered
+        assert!(render(PaymentDocument::Receipt, &payment).is_err());
+        payment.paid_timestamp = Some(1_711_000_000);
+        assert!(render(PaymentDocument::Receipt, &payment).is_err());
+        payment.receipt_number = Some(7);
+        assert!(render(PaymentDocument::Receipt, &payment)
+            .unwrap()
+            .starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_render_donation_documents_pdf() {
+        let mut donation = Donation {
+            id: "donation-1".to_string(),
+            contact_id: Some("donor".to_string()),
+            donor_name: "Malee Boonmee".to_string(),
+            amount: 5000,
+            donation_timestamp: 1_710_000_000,
+            notes: "Spring fundraiser".to_string(),
+            receipt_number: None,
+        };
+
+        // A receipt needs a number
+        assert!(render_donation_receipt_pdf("Happy Paws", &donation, "USD", Locale::En).is_err());
+        donation.receipt_number = Some(3);
+        let receipt =
+            render_donation_receipt_pdf("Happy Paws", &donation, "USD", Locale::Es).unwrap();
+        assert!(receipt.starts_with(b"%PDF"));
+
+        // A statement long enough to need a second page, and an empty one
+        let mut statement = DonorStatement {
+            donor: Contact {
+                id: "donor".to_string(),
+                name: "Malee Boonmee".to_string(),
+                kind: ContactKind::Donor,
+                tel_number: String::new(),
+                email: String::new(),
+                address: "12 Sukhumvit Road".to_string(),
+                notes: String::new(),
+            },
+            year: 2024,
+            donations: vec![donation; 40],
+            total: 200000,
+        };
+        let render = |statement: &DonorStatement| {
+            render_donor_statement_pdf("Happy Paws", statement, "USD", 1_735_000_000, Locale::En)
+        };
+        assert!(render(&statement).unwrap().starts_with(b"%PDF"));
+        statement.donations.clear();
+        statement.total = 0;
+        assert!(render(&statement).unwrap().starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_age_label_and_wrap_text() {
+        assert_eq!(age_label(Some(27), Locale::En), "2 years, 3 months");
+        assert_eq!(age_label(Some(24), Locale::En), "2 years");
+        assert_eq!(age_label(Some(5), Locale::Es), "5 meses");
+        assert_eq!(age_label(None, Locale::En), "Unknown");
+
+        assert_eq!(
+            wrap_text("a quick brown fox  jumps", 11),
+            vec!["a quick", "brown fox", "jumps"]
+        );
+        assert_eq!(
+            wrap_text("extraordinarily long", 5),
+            vec!["extraordinarily", "long"]
+        );
+        assert!(wrap_text("   ", 10).is_empty());
+    }
+
+    #[test]
+    fn test_render_xlsx_workbooks() {
+        let animals = vec![AnimalSummary {
+            id: "animal-1".to_string(),
+            name: "Rex".to_string(),
+            specie: "Dog".to_string(),
+            breed: "Beagle".to_string(),
+            sex: "Male".to_string(),
+            admission_timestamp: 1_700_000_000,
+            status: AnimalStatus::Available,
+            image_path: None,
+        }];
+        let workbook = render_animals_xlsx(&animals, Locale::En).unwrap();
+        assert!(workbook.starts_with(b"PK"));
+
+        let requests = vec![AdoptionRequestSummary {
+            id: "request-1".to_string(),
+            animal_id: "animal-1".to_string(),
+            animal_name: "Rex".to_string(),
+            animal_specie: "Dog".to_string(),
+            username: "jane".to_string(),
+            name: "Jane Doe".to_string(),
+            email: "jane@example.com".to_string(),
+            request_timestamp: 1_700_000_000,
+            status: RequestStatus::Pending,
+            screening_score: Some(20),
+        }];
+        let workbook = render_adoption_requests_xlsx(&requests, Locale::Es).unwrap();
+        assert!(workbook.starts_with(b"PK"));
+
+        let reports: Vec<MonthlyReport> = (1..=12).map(sample_report).collect();
+        let workbook = render_reports_xlsx(&reports, Locale::Es).unwrap();
+        assert!(workbook.starts_with(b"PK"));
+    }
+
+    #[test]
+    fn test_anonymize_adoption_requests() {
+        assert_eq!(pseudonymize("Jane", "salt"), pseudonymize(" jane ", "salt"));
+        assert_ne!(pseudonymize("jane", "salt"), pseudonymize("jane", "pepper"));
+        assert_ne!(pseudonymize("jane", "salt"), pseudonymize("john", "salt"));
+        assert_eq!(pseudonymize("jane", "salt").len(), 12);
+
+        let requests = vec![AnimalAdoptionRequest {
+            animal: AnimalSummary {
+                id: "animal-1".to_string(),
+                name: "Rex".to_string(),
+                specie: "Dog".to_string(),
+                breed: "Beagle".to_string(),
+                sex: "Male".to_string(),
+                admission_timestamp: 1_700_000_000,
+                status: AnimalStatus::Requested,
+                image_path: None,
+            },
+            request: AdoptionRequest {
+                id: "request-1".to_string(),
+                animal_id: "animal-1".to_string(),
+                username: "jane".to_string(),
+                name: "Jane Doe".to_string(),
+                email: "jane@example.com".to_string(),
+                tel_number: "555-0100".to_string(),
+                address: "1 Main Street".to_string(),
+                occupation: "Engineer".to_string(),
+                annual_income: "50000".to_string(),
+                num_people: 3,
+                num_children: 1,
+                request_timestamp: 1_700_000_000,
+                adoption_timestamp: 0,
+                status: RequestStatus::Pending,
+                country: "Thailand".to_string(),
+                co_applicant: None,
+            },
+        }];
+
+        let anonymized = anonymize_adoption_requests(&requests, "salt");
+        assert_eq!(anonymized.len(), 1);
+        let request = &anonymized[0];
+        assert_eq!(request.applicant, pseudonymize("jane", "salt"));
+        assert_eq!(request.specie, "Dog");
+        assert_eq!(request.country, "Thailand");
+        assert_eq!((request.num_people, request.num_children), (3, 1));
+        assert_eq!(request.adoption_timestamp, None);
+
+        // Nothing identifying the applicant is left in what is shared
+        let json = serde_json::to_string(&anonymized).unwrap();
+        for value in [
+            "jane",
+            "Jane Doe",
+            "555-0100",
+            "1 Main Street",
+            "Engineer",
+            "50000",
+        ] {
+            assert!(!json.contains(value), "{} was not stripped", value);
+        }
+
+        let workbook = render_anonymized_adoption_requests_xlsx(&anonymized, Locale::Es).unwrap();
+        assert!(workbook.starts_with(b"PK"));
+    }
+
+    #[test]
+    fn test_render_calendar_ics() {
+        let events = vec![
+            CalendarEvent {
+                kind: CalendarEventKind::VaccinationDue,
+                record_id: "v1".to_string(),
+                animal_id: "animal-1".to_string(),
+                animal_name: "Rex".to_string(),
+                subject: "Rabies".to_string(),
+                // 2023-11-14 22:13:20 UTC
+                timestamp: 1_700_000_000,
+            },
+            CalendarEvent {
+                kind: CalendarEventKind::FosterReturnDue,
+                record_id: "f1".to_string(),
+                animal_id: "animal-2".to_string(),
+                animal_name: "Luna".to_string(),
+                subject: "Doe, Jane".to_string(),
+                timestamp: 1_700_100_000,
+            },
+            CalendarEvent {
+                kind: CalendarEventKind::Appointment,
+                record_id: "p1".to_string(),
+                animal_id: "animal-2".to_string(),
+                animal_name: "Luna".to_string(),
+                subject: "alice".to_string(),
+                timestamp: 1_700_200_000,
+            },
+            CalendarEvent {
+                kind: CalendarEventKind::Surgery,
+                record_id: "s1".to_string(),
+                animal_id: "animal-1".to_string(),
+                animal_name: "Rex".to_string(),
+                subject: String::new(),
+                timestamp: 1_700_300_000,
+            },
+        ];
+        let ics = String::from_utf8(render_calendar_ics(
+            "Happy Paws",
+            &events,
+            1_700_000_000,
+            Locale::En,
+        ))
+        .unwrap();
+
+        // Every line ends with CRLF
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(!ics.replace("\r\n", "").contains('\n'));
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 4);
+        assert!(ics.contains("UID:vaccination-due-v1@animal-shelter-manager\r\n"));
+        assert!(ics.contains("DTSTAMP:20231114T221320Z\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20231114\r\nDTEND;VALUE=DATE:20231115\r\n"));
+        assert!(ics.contains("SUMMARY:Rabies vaccination due for Rex\r\n"));
+        assert!(ics.contains("SUMMARY:Luna due back from foster with Doe\\, Jane\r\n"));
+        assert!(ics.contains("DESCRIPTION:Animal ID: animal-2\r\n"));
+        assert!(ics.contains("UID:appointment-p1@animal-shelter-manager\r\n"));
+        assert!(ics.contains("SUMMARY:alice meets Luna\r\n"));
+        assert!(ics.contains("UID:surgery-s1@animal-shelter-manager\r\n"));
+        assert!(ics.contains("SUMMARY:Spay/neuter surgery for Rex\r\n"));
+
+        let spanish =
+            String::from_utf8(render_calendar_ics("Happy Paws", &events, 0, Locale::Es)).unwrap();
+        assert!(spanish.contains("SUMMARY:Vacuna Rabies pendiente para Rex\r\n"));
+
+        assert_eq!(escape_text("a;b,c\\d\r\ne"), "a\\;b\\,c\\\\d\\ne");
+
+        // Long lines are folded within 75 bytes without splitting a character
+        let mut folded = String::new();
+        write_line(&mut folded, &format!("SUMMARY:{}", "é".repeat(60)));
+        let lines: Vec<&str> = folded.trim_end_matches("\r\n").split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|line| line.len() <= 75));
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(
+            folded.replace("\r\n ", ""),
+            format!("SUMMARY:{}\r\n", "é".repeat(60))
+        );
+    }
+
+    #[test]
+    fn test_render_listings_feed() {
+        let mut with_photo = sample_detail().animal;
+        with_photo.image_path = Some("files/animals/animal-1/photo.PNG".to_string());
+        let mut without_photo = with_photo.clone();
+        without_photo.id = "animal-2".to_string();
+        without_photo.name = "Tom & Jerry".to_string();
+        without_photo.image_path = None;
+        without_photo.birth_year = None;
+
+        let (feed, photos) = build_listings_feed(
+            "Happy Paws",
+            &[with_photo, without_photo],
+            "listings-photos",
+            1_700_000_000,
+        );
+        assert_eq!(feed.generated_at, "2023-11-14T22:13:20Z");
+        assert_eq!(feed.animals.len(), 2);
+        assert_eq!(
+            feed.animals[0].photo.as_deref(),
+            Some("listings-photos/animal-1.png")
+        );
+        assert!(feed.animals[1].photo.is_none());
+        assert_eq!(photos.len(), 1);
+        assert_eq!(photos[0].source_path, "files/animals/animal-1/photo.PNG");
+        assert_eq!(photos[0].relative_path, "listings-photos/animal-1.png");
+
+        // The JSON feed reads back as it was written, without any staff-only fields
+        let json = render_listings_feed(&feed, ListingsFormat::Json).unwrap();
+        let parsed: ListingsFeed = serde_json::from_slice(&json).unwrap();
+        assert_eq!(parsed, feed);
+        let text = String::from_utf8(json).unwrap();
+        assert!(text.contains("\"shelterName\": \"Happy Paws\""));
+        assert!(!text.contains("microchip"));
+        assert!(!text.contains("version"));
+
+        let xml =
+            String::from_utf8(render_listings_feed(&feed, ListingsFormat::Xml).unwrap()).unwrap();
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert_eq!(xml.matches("<animal id=").count(), 2);
+        assert!(xml.contains("<name>Tom &amp; Jerry</name>"));
+        assert!(xml.contains("<photo>listings-photos/animal-1.png</photo>"));
+        // Unknown values are left out
+        assert_eq!(xml.matches("<birthYear>").count(), 1);
+
+        assert_eq!(
+            escape_xml("<a href=\"x\">'b'\u{1}</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&apos;b&apos;&lt;/a&gt;"
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//
+// export_service/types.rs
+//
+// This module contains the type definitions for the public listings feed: the
+// formats it is written in, and the animals and photos it lists; and for the
+// anonymized adoption requests shared with research partners.
+//
+
+use serde::{Deserialize, Serialize};
+
+/// File format the public listings feed is written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ListingsFormat {
+    /// A JSON document
+    Json,
+    /// An XML document
+    Xml,
+}
+
+impl ListingsFormat {
+    /// Gets the file extension of the format, without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            ListingsFormat::Json => "json",
+            ListingsFormat::Xml => "xml",
+        }
+    }
+
+    /// Gets the name of the file type shown in save dialogs
+    pub fn filter_name(self) -> &'static str {
+        match self {
+            ListingsFormat::Json => "JSON",
+            ListingsFormat::Xml => "XML",
+        }
+    }
+}
+
+/// The animals available for adoption, as published to the website and aggregators
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingsFeed {
+    /// Name of the shelter listing the animals
+    pub shelter_name: String,
+    /// When the feed was written, in RFC 3339 format
+    pub generated_at: String,
+    /// The listed animals, longest waiting first
+    pub animals: Vec<Listing>,
+}
+
+/// An animal in the public listings feed, with only the fields safe to publish
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Listing {
+    /// Unique identifier for the animal
+    pub id: String,
+    /// Name of the animal
+    pub name: String,
+    /// Species of the animal
+    pub species: String,
+    /// Breed of the animal
+    pub breed: String,
+    /// Sex of the animal
+    pub sex: String,
+    /// Birth month of the animal, from 1 to 12 (None if unknown)
+    pub birth_month: Option<i32>,
+    /// Birth year of the animal (None if unknown)
+    pub birth_year: Option<i32>,
+    /// Whether the animal is neutered
+    pub neutered: bool,
+    /// Appearance description of the animal
+    pub appearance: String,
+    /// Bio & Characteristics of the animal
+    pub bio: String,
+    /// Day the animal was admitted, such as 2024-03-01
+    pub admission_date: String,
+    /// Path of the animal's photo relative to the feed (None if it has no photo)
+    pub photo: Option<String>,
+}
+
+/// A photo to copy next to the feed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListingPhoto {
+    /// Path of the stored photo
+    pub source_path: String,
+    /// Path of the copy relative to the feed, as given in its listing
+    pub relative_path: String,
+}
+
+/// An adoption request stripped of anything that identifies the applicant, keeping the
+/// household details research partners aggregate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnonymizedAdoptionRequest {
+    /// Hash standing in for the applicant, the same for every request they made in one
+    /// export but unrelated to their username
+    pub applicant: String,
+    /// Species of the requested animal
+    pub specie: String,
+    /// Breed of the requested animal
+    pub breed: String,
+    /// Timestamp when the request was submitted
+    pub request_timestamp: i64,
+    /// Timestamp when the adoption was completed, if it was
+    pub adoption_timestamp: Option<i64>,
+    /// Current status of the request
+    pub status: String,
+    /// Country of the applicant
+    pub country: String,
+    /// Number of people in the applicant's household
+    pub num_people: i32,
+    /// Number of children in the applicant's household
+    pub num_children: i32,
+}
@@ -0,0 +1,277 @@
+//
+// export_service/xlsx.rs
+//
+// This module renders animals, adoption requests, anonymized or not, and
+// activity reports into Excel workbooks, keeping numbers and dates as typed cells.
+//
+
+use super::{headings, month_label, types::AnonymizedAdoptionRequest};
+use crate::database_service::{
+    types::{AdoptionRequestSummary, AnimalSummary, MonthlyReport},
+    MINOR_UNITS_PER_UNIT,
+};
+use crate::i18n::{
+    self,
+    types::{Locale, MessageKey},
+};
+use anyhow::{Context, Result};
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, Worksheet, XlsxError};
+
+/// Number format used for date and time cells
+const DATETIME_FORMAT: &str = "yyyy-mm-dd hh:mm";
+/// Number format used for amounts of money
+const AMOUNT_FORMAT: &str = "#,##0.00";
+
+/// Renders animal summaries into an Excel workbook with one row per animal
+///
+/// # Arguments
+/// * `animals` - The animals to include
+/// * `locale` - The locale to write the headings in
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bytes of the workbook or error
+pub fn render_animals_xlsx(animals: &[AnimalSummary], locale: Locale) -> Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let datetime_format = Format::new().set_num_format(DATETIME_FORMAT);
+    let sheet = add_sheet(
+        &mut workbook,
+        i18n::text(MessageKey::AnimalsSheet, locale),
+        &headings(
+            [
+                MessageKey::ColumnId,
+                MessageKey::ColumnName,
+                MessageKey::ColumnSpecies,
+                MessageKey::ColumnBreed,
+                MessageKey::ColumnSex,
+                MessageKey::ColumnAdmitted,
+                MessageKey::ColumnStatus,
+            ],
+            locale,
+        ),
+    )?;
+
+    for (index, animal) in animals.iter().enumerate() {
+        let row = index as u32 + 1;
+        sheet.write_string(row, 0, &animal.id)?;
+        sheet.write_string(row, 1, &animal.name)?;
+        sheet.write_string(row, 2, &animal.specie)?;
+        sheet.write_string(row, 3, &animal.breed)?;
+        sheet.write_string(row, 4, &animal.sex)?;
+        write_timestamp(sheet, row, 5, animal.admission_timestamp, &datetime_format)?;
+        sheet.write_string(row, 6, animal.status.to_string())?;
+    }
+
+    finish(workbook)
+}
+
+/// Renders adoption request summaries into an Excel workbook with one row per request
+///
+/// # Arguments
+/// * `requests` - The adoption requests to include
+/// * `locale` - The locale to write the headings in
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bytes of the workbook or error
+pub fn render_adoption_requests_xlsx(
+    requests: &[AdoptionRequestSummary],
+    locale: Locale,
+) -> Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let datetime_format = Format::new().set_num_format(DATETIME_FORMAT);
+    let sheet = add_sheet(
+        &mut workbook,
+        i18n::text(MessageKey::AdoptionRequestsSheet, locale),
+        &headings(
+            [
+                MessageKey::ColumnId,
+                MessageKey::ColumnAnimalId,
+                MessageKey::ColumnAnimal,
+                MessageKey::ColumnSpecies,
+                MessageKey::ColumnUsername,
+                MessageKey::ColumnName,
+                MessageKey::ColumnEmail,
+                MessageKey::ColumnRequested,
+                MessageKey::ColumnStatus,
+            ],
+            locale,
+        ),
+    )?;
+
+    for (index, request) in requests.iter().enumerate() {
+        let row = index as u32 + 1;
+        sheet.write_string(row, 0, &request.id)?;
+        sheet.write_string(row, 1, &request.animal_id)?;
+        sheet.write_string(row, 2, &request.animal_name)?;
+        sheet.write_string(row, 3, &request.animal_specie)?;
+        sheet.write_string(row, 4, &request.username)?;
+        sheet.write_string(row, 5, &request.name)?;
+        sheet.write_string(row, 6, &request.email)?;
+        write_timestamp(sheet, row, 7, request.request_timestamp, &datetime_format)?;
+        sheet.write_string(row, 8, request.status.to_string())?;
+    }
+
+    finish(workbook)
+}
+
+/// Renders anonymized adoption requests into an Excel workbook with one row per request,
+/// for research partners
+///
+/// # Arguments
+/// * `requests` - The anonymized adoption requests to include
+/// * `locale` - The locale to write the headings in
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bytes of the workbook or error
+pub fn render_anonymized_adoption_requests_xlsx(
+    requests: &[AnonymizedAdoptionRequest],
+    locale: Locale,
+) -> Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let datetime_format = Format::new().set_num_format(DATETIME_FORMAT);
+    let sheet = add_sheet(
+        &mut workbook,
+        i18n::text(MessageKey::AdoptionRequestsSheet, locale),
+        &headings(
+            [
+                MessageKey::ColumnApplicant,
+                MessageKey::ColumnSpecies,
+                MessageKey::ColumnBreed,
+                MessageKey::ColumnRequested,
+                MessageKey::ColumnAdopted,
+                MessageKey::ColumnStatus,
+                MessageKey::ColumnCountry,
+                MessageKey::ColumnHouseholdSize,
+                MessageKey::ColumnChildren,
+            ],
+            locale,
+        ),
+    )?;
+
+    for (index, request) in requests.iter().enumerate() {
+        let row = index as u32 + 1;
+        sheet.write_string(row, 0, &request.applicant)?;
+        sheet.write_string(row, 1, &request.specie)?;
+        sheet.write_string(row, 2, &request.breed)?;
+        write_timestamp(sheet, row, 3, request.request_timestamp, &datetime_format)?;
+        if let Some(adoption_timestamp) = request.adoption_timestamp {
+            write_timestamp(sheet, row, 4, adoption_timestamp, &datetime_format)?;
+        }
+        sheet.write_string(row, 5, &request.status)?;
+        sheet.write_string(row, 6, &request.country)?;
+        sheet.write_number(row, 7, request.num_people)?;
+        sheet.write_number(row, 8, request.num_children)?;
+    }
+
+    finish(workbook)
+}
+
+/// Renders monthly reports into an Excel workbook with one row per month, with the
+/// amounts in the shelter's currency
+///
+/// # Arguments
+/// * `reports` - The monthly reports to include, in the order to list them
+/// * `locale` - The locale to write the headings in
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bytes of the workbook or error
+pub fn render_reports_xlsx(reports: &[MonthlyReport], locale: Locale) -> Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let sheet = add_sheet(
+        &mut workbook,
+        i18n::text(MessageKey::ReportSheet, locale),
+        &headings(
+            [
+                MessageKey::ReportMonth,
+                MessageKey::ReportAdmissions,
+                MessageKey::ReportIntakes,
+                MessageKey::ReportAdoptions,
+                MessageKey::ReportReturns,
+                MessageKey::ReportDeaths,
+                MessageKey::ReportFees,
+                MessageKey::ReportDonations,
+            ],
+            locale,
+        ),
+    )?;
+
+    let amount_format = Format::new().set_num_format(AMOUNT_FORMAT);
+    for (index, report) in reports.iter().enumerate() {
+        let row = index as u32 + 1;
+        let intake_count: i64 = report.intakes_by_type.iter().map(|c| c.count).sum();
+        sheet.write_string(row, 0, month_label(report, locale))?;
+        sheet.write_number(row, 1, report.admission_count as f64)?;
+        sheet.write_number(row, 2, intake_count as f64)?;
+        sheet.write_number(row, 3, report.adoption_count as f64)?;
+        sheet.write_number(row, 4, report.return_count as f64)?;
+        sheet.write_number(row, 5, report.death_count as f64)?;
+        for (column, amount) in [(6, report.fee_revenue), (7, report.donation_revenue)] {
+            let units = amount as f64 / MINOR_UNITS_PER_UNIT as f64;
+            sheet.write_number_with_format(row, column, units, &amount_format)?;
+        }
+    }
+
+    finish(workbook)
+}
+
+/// Adds a named worksheet with a bold, frozen header row
+///
+/// # Arguments
+/// * `workbook` - The workbook to add the worksheet to
+/// * `name` - Name of the worksheet
+/// * `headers` - Text of each column header
+///
+/// # Returns
+/// * `Result<&mut Worksheet, XlsxError>` - The new worksheet or error
+fn add_sheet<'a>(
+    workbook: &'a mut Workbook,
+    name: &str,
+    headers: &[&str],
+) -> Result<&'a mut Worksheet, XlsxError> {
+    let header_format = Format::new().set_bold();
+    let sheet = workbook.add_worksheet();
+    sheet.set_name(name)?;
+    for (column, header) in headers.iter().enumerate() {
+        sheet.write_string_with_format(0, column as u16, *header, &header_format)?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    Ok(sheet)
+}
+
+/// Writes a Unix timestamp as a date and time cell
+///
+/// # Arguments
+/// * `sheet` - The worksheet to write to
+/// * `row` - Row of the cell
+/// * `column` - Column of the cell
+/// * `timestamp` - Unix timestamp in seconds
+/// * `format` - Date and time format of the cell
+///
+/// # Returns
+/// * `Result<(), XlsxError>` - Success or error
+fn write_timestamp(
+    sheet: &mut Worksheet,
+    row: u32,
+    column: u16,
+    timestamp: i64,
+    format: &Format,
+) -> Result<(), XlsxError> {
+    let datetime = ExcelDateTime::from_timestamp(timestamp)?;
+    sheet.write_datetime_with_format(row, column, datetime, format)?;
+    Ok(())
+}
+
+/// Sizes the columns of every worksheet and serializes the workbook
+///
+/// # Arguments
+/// * `workbook` - The workbook to serialize
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bytes of the workbook or error
+fn finish(mut workbook: Workbook) -> Result<Vec<u8>> {
+    for sheet in workbook.worksheets_mut() {
+        sheet.autofit();
+    }
+    workbook
+        .save_to_buffer()
+        .context("Failed to render Excel workbook")
+}
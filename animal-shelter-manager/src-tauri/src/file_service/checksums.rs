@@ -0,0 +1,285 @@
+//
+// file_service/checksums.rs
+//
+// This module writes uploads so that a crash can never leave a half-written
+// file in place: each file is written to a temporary location, flushed to disk
+// and checked before it is renamed to its final path. The size and SHA-256 hash
+// of every stored file are kept in a manifest so they can be checked again later.
+//
+
+use super::FileService;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+/// Name of the manifest file within the root directory
+const CHECKSUM_MANIFEST: &str = "checksums.json";
+
+/// Directory within the root directory that files are written to before being
+/// renamed into place. It is on the same disk, so the rename cannot be interrupted.
+const PARTIAL_DIRECTORY: &str = ".partial";
+
+/// Size and hash of a stored file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileChecksum {
+    /// Size of the file, in bytes
+    bytes: u64,
+    /// SHA-256 hash of the file, as hex
+    sha256: String,
+}
+
+impl FileChecksum {
+    /// Computes the checksum of a file's contents
+    fn of(contents: &[u8]) -> Self {
+        FileChecksum {
+            bytes: contents.len() as u64,
+            sha256: hex::encode(Sha256::digest(contents)),
+        }
+    }
+}
+
+/// Result of checking stored files against their recorded checksums
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileVerificationReport {
+    /// Number of files that were checked
+    pub checked_count: usize,
+    /// Files whose checksum was recorded but that no longer exist
+    pub missing: Vec<PathBuf>,
+    /// Files whose size or hash no longer matches the recorded checksum
+    pub corrupted: Vec<PathBuf>,
+    /// Uploads without a recorded checksum, such as those stored by older versions
+    pub unrecorded: Vec<PathBuf>,
+}
+
+impl FileVerificationReport {
+    /// Checks whether every checked file matched its checksum
+    pub fn is_intact(&self) -> bool {
+        self.missing.is_empty() && self.corrupted.is_empty()
+    }
+}
+
+impl FileService {
+    /// Writes a file by way of a temporary file, so that the destination either holds
+    /// the complete contents or does not exist, and records its checksum
+    ///
+    /// # Arguments
+    /// * `destination_path` - Final path of the file
+    /// * `contents` - The bytes of the file
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or error if the file could not be written intact
+    pub(super) async fn write_verified(
+        &self,
+        destination_path: &Path,
+        contents: &[u8],
+    ) -> Result<()> {
+        let expected = FileChecksum::of(contents);
+
+        let partial_directory = self.root_path.join(PARTIAL_DIRECTORY);
+        fs::create_dir_all(&partial_directory)
+            .await
+            .context(format!(
+                "Failed to create directory: {:?}",
+                partial_directory
+            ))?;
+        let partial_path = partial_directory.join(Uuid::new_v4().to_string());
+
+        let result = async {
+            let mut file = fs::File::create(&partial_path)
+                .await
+                .context(format!("Failed to create file: {:?}", partial_path))?;
+            file.write_all(contents)
+                .await
+                .context(format!("Failed to write file: {:?}", partial_path))?;
+            file.sync_all()
+                .await
+                .context(format!("Failed to flush file to disk: {:?}", partial_path))?;
+            drop(file);
+
+            // Read the file back, so what reached the disk is what gets renamed into place
+            let written = fs::read(&partial_path)
+                .await
+                .context(format!("Failed to read back file: {:?}", partial_path))?;
+            if FileChecksum::of(&written) != expected {
+                bail!("The file was not written intact: {:?}", destination_path);
+            }
+
+            fs::rename(&partial_path, destination_path)
+                .await
+                .context(format!(
+                    "Failed to move file from {:?} to {:?}",
+                    partial_path, destination_path
+                ))
+        }
+        .await;
+        if result.is_err() {
+            let _ = fs::remove_file(&partial_path).await;
+        }
+        result?;
+
+        let key = self.manifest_key(destination_path);
+        self.update_manifest(|manifest| {
+            manifest.insert(key, expected);
+        })
+        .await
+    }
+
+    /// Forgets the checksum of a file that was deleted
+    ///
+    /// # Arguments
+    /// * `file_path` - Path of the deleted file
+    pub(super) async fn forget_checksum(&self, file_path: &Path) -> Result<()> {
+        let key = self.manifest_key(file_path);
+        self.update_manifest(|manifest| {
+            manifest.remove(&key);
+        })
+        .await
+    }
+
+    /// Moves the checksums of files that were renamed to their new paths
+    ///
+    /// # Arguments
+    /// * `moved_paths` - Pairs of old and new file path
+    pub(super) async fn move_checksums(&self, moved_paths: &[(PathBuf, PathBuf)]) -> Result<()> {
+        let moved_keys: Vec<(String, String)> = moved_paths
+            .iter()
+            .map(|(old_path, new_path)| (self.manifest_key(old_path), self.manifest_key(new_path)))
+            .collect();
+        self.update_manifest(|manifest| {
+            for (old_key, new_key) in moved_keys {
+                if let Some(checksum) = manifest.remove(&old_key) {
+                    manifest.insert(new_key, checksum);
+                }
+            }
+        })
+        .await
+    }
+
+    /// Checks every stored file against its recorded size and hash
+    ///
+    /// # Returns
+    /// * `Result<FileVerificationReport>` - The files that are missing, damaged or unrecorded
+    pub async fn verify_files(&self) -> Result<FileVerificationReport> {
+        let manifest = {
+            let _guard = self.manifest_lock.lock().await;
+            self.read_manifest().await?
+        };
+
+        let mut report = FileVerificationReport::default();
+        for (key, expected) in &manifest {
+            let path = self.root_path.join(key);
+            report.checked_count += 1;
+            match fs::read(&path).await {
+                Ok(contents) => {
+                    if FileChecksum::of(&contents) != *expected {
+                        report.corrupted.push(path);
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => report.missing.push(path),
+                Err(e) => {
+                    return Err(e).context(format!("Failed to read file: {:?}", path));
+                }
+            }
+        }
+
+        for path in self.list_uploaded_files().await? {
+            if !manifest.contains_key(&self.manifest_key(&path)) {
+                report.unrecorded.push(path);
+            }
+        }
+
+        if report.is_intact() {
+            log::info!("Verified {} stored files", report.checked_count);
+        } else {
+            log::warn!(
+                "Stored file verification found {} missing and {} damaged files",
+                report.missing.len(),
+                report.corrupted.len()
+            );
+        }
+        Ok(report)
+    }
+
+    /// Removes temporary files left behind by writes that were interrupted
+    pub(super) fn remove_partial_files(root_path: &Path) {
+        let partial_directory = root_path.join(PARTIAL_DIRECTORY);
+        if partial_directory.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&partial_directory) {
+                log::warn!("Failed to remove interrupted writes: {}", e);
+            }
+        }
+    }
+
+    /// Gets the key a file is recorded under in the manifest: its path relative to the
+    /// root directory, so the manifest stays valid if the root directory moves
+    fn manifest_key(&self, file_path: &Path) -> String {
+        let relative = file_path.strip_prefix(&self.root_path).unwrap_or(file_path);
+        relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Reads the manifest, which is empty until the first file is written
+    async fn read_manifest(&self) -> Result<BTreeMap<String, FileChecksum>> {
+        let manifest_path = self.root_path.join(CHECKSUM_MANIFEST);
+        match fs::read(&manifest_path).await {
+            Ok(contents) => serde_json::from_slice(&contents).context(format!(
+                "Failed to parse checksum manifest: {:?}",
+                manifest_path
+            )),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => Err(e).context(format!(
+                "Failed to read checksum manifest: {:?}",
+                manifest_path
+            )),
+        }
+    }
+
+    /// Applies a change to the manifest and writes it back, replacing the old manifest
+    /// only once the new one is completely written
+    ///
+    /// # Arguments
+    /// * `change` - The change to make to the recorded checksums
+    async fn update_manifest<F>(&self, change: F) -> Result<()>
+    where
+        F: FnOnce(&mut BTreeMap<String, FileChecksum>),
+    {
+        let _guard = self.manifest_lock.lock().await;
+        let mut manifest = self.read_manifest().await?;
+        change(&mut manifest);
+
+        let contents = serde_json::to_vec_pretty(&manifest)
+            .context("Failed to serialize checksum manifest")?;
+        let partial_directory = self.root_path.join(PARTIAL_DIRECTORY);
+        fs::create_dir_all(&partial_directory)
+            .await
+            .context(format!(
+                "Failed to create directory: {:?}",
+                partial_directory
+            ))?;
+        let partial_path = partial_directory.join(CHECKSUM_MANIFEST);
+        let mut file = fs::File::create(&partial_path)
+            .await
+            .context(format!("Failed to create file: {:?}", partial_path))?;
+        file.write_all(&contents)
+            .await
+            .context("Failed to write checksum manifest")?;
+        file.sync_all()
+            .await
+            .context("Failed to flush checksum manifest to disk")?;
+        drop(file);
+
+        fs::rename(&partial_path, self.root_path.join(CHECKSUM_MANIFEST))
+            .await
+            .context("Failed to replace checksum manifest")
+    }
+}
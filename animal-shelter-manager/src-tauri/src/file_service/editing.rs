@@ -0,0 +1,130 @@
+//
+// file_service/editing.rs
+//
+// This module makes simple edits to uploaded images, such as turning a photo
+// taken sideways on a phone the right way up. Edits are written to a new file
+// next to the original, which is left untouched.
+//
+
+use super::{unique_path_in, upload_extension, FileService};
+use anyhow::{bail, Context, Result};
+use image::DynamicImage;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+impl FileService {
+    /// Rotates an uploaded image clockwise, writing the result to a new file
+    ///
+    /// # Arguments
+    /// * `file_path` - Path of the uploaded image
+    /// * `degrees` - Angle to rotate by, a multiple of 90; negative angles rotate counterclockwise
+    ///
+    /// # Returns
+    /// * `Result<PathBuf>` - Path of the rotated image, or error if the angle or image is unsuitable
+    pub async fn rotate_image<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        degrees: i32,
+    ) -> Result<PathBuf> {
+        if degrees % 90 != 0 {
+            bail!("Images can only be rotated by multiples of 90 degrees");
+        }
+        self.edit_image(file_path.as_ref(), move |image| {
+            Ok(match degrees.rem_euclid(360) {
+                90 => image.rotate90(),
+                180 => image.rotate180(),
+                270 => image.rotate270(),
+                _ => image,
+            })
+        })
+        .await
+    }
+
+    /// Crops an uploaded image to a rectangle, writing the result to a new file
+    ///
+    /// # Arguments
+    /// * `file_path` - Path of the uploaded image
+    /// * `x` - Left edge of the rectangle, in pixels from the left of the image
+    /// * `y` - Top edge of the rectangle, in pixels from the top of the image
+    /// * `width` - Width of the rectangle, in pixels
+    /// * `height` - Height of the rectangle, in pixels
+    ///
+    /// # Returns
+    /// * `Result<PathBuf>` - Path of the cropped image, or error if the rectangle does not fit
+    ///   within the image
+    pub async fn crop_image<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<PathBuf> {
+        if width == 0 || height == 0 {
+            bail!("The cropped image cannot be empty");
+        }
+        self.edit_image(file_path.as_ref(), move |image| {
+            let fits_horizontally = x
+                .checked_add(width)
+                .is_some_and(|right| right <= image.width());
+            let fits_vertically = y
+                .checked_add(height)
+                .is_some_and(|bottom| bottom <= image.height());
+            if !fits_horizontally || !fits_vertically {
+                bail!(
+                    "The area to crop does not fit within the {}x{} pixel image",
+                    image.width(),
+                    image.height()
+                );
+            }
+            Ok(image.crop_imm(x, y, width, height))
+        })
+        .await
+    }
+
+    /// Decodes an uploaded image, applies an edit and writes the result in the same
+    /// format to a new file in the same directory
+    ///
+    /// # Arguments
+    /// * `file_path` - Path of the uploaded image
+    /// * `edit` - The edit to apply to the decoded image
+    ///
+    /// # Returns
+    /// * `Result<PathBuf>` - Path of the edited image or error
+    async fn edit_image<F>(&self, file_path: &Path, edit: F) -> Result<PathBuf>
+    where
+        F: FnOnce(DynamicImage) -> Result<DynamicImage> + Send + 'static,
+    {
+        // Only uploads are edited, never the databases or other application files
+        self.ensure_within_uploads(file_path, "edit")?;
+        let contents = fs::read(file_path)
+            .await
+            .context(format!("Failed to read file: {:?}", file_path))?;
+
+        // Decoding and encoding are slow for large photos, so they run off the async runtime
+        let edited = tokio::task::spawn_blocking(move || -> Result<Vec<u8>> {
+            let format = image::guess_format(&contents).context("The file is not an image")?;
+            let image = image::load_from_memory_with_format(&contents, format)
+                .context("The image is damaged and cannot be read")?;
+            let image = edit(image)?;
+
+            let mut edited = Cursor::new(Vec::new());
+            image
+                .write_to(&mut edited, format)
+                .context(format!("Failed to encode edited image as {:?}", format))?;
+            Ok(edited.into_inner())
+        })
+        .await
+        .context("Image editing task failed")??;
+
+        let directory = file_path
+            .parent()
+            .context(format!("File has no directory: {:?}", file_path))?;
+        let destination_path = unique_path_in(directory, upload_extension(file_path)).await?;
+        self.write_verified(&destination_path, &edited).await?;
+
+        log::info!("Image edited: {:?} -> {:?}", file_path, destination_path);
+        Ok(destination_path)
+    }
+}
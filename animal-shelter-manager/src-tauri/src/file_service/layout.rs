@@ -0,0 +1,306 @@
+//
+// file_service/layout.rs
+//
+// This module decides where uploaded files are stored: each record's files live
+// in a directory of their own, such as animals/<id>/, so they can be found and
+// cleaned up together. It also moves files stored by older versions, which kept
+// every upload directly in the root directory.
+//
+
+use super::FileService;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Directory within the root directory holding a directory per animal
+const ANIMALS_DIRECTORY: &str = "animals";
+
+/// Directory within the root directory holding a directory per adoption request
+const REQUESTS_DIRECTORY: &str = "requests";
+
+/// Directory within the root directory for files uploaded before their record exists,
+/// such as the photo chosen while admitting an animal
+const UNASSIGNED_DIRECTORY: &str = "uploads";
+
+/// The record an uploaded file belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "kebab-case")]
+pub enum StorageOwner {
+    /// An animal, by ID
+    Animal(String),
+    /// An adoption request, by ID
+    Request(String),
+}
+
+impl StorageOwner {
+    /// Gets the path of the owner's directory relative to the root directory
+    ///
+    /// # Returns
+    /// * `Result<PathBuf>` - The relative path, or error if the ID could escape the directory
+    fn relative_directory(&self) -> Result<PathBuf> {
+        let (parent, id) = match self {
+            StorageOwner::Animal(id) => (ANIMALS_DIRECTORY, id),
+            StorageOwner::Request(id) => (REQUESTS_DIRECTORY, id),
+        };
+        let is_safe = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !is_safe {
+            bail!("Invalid ID for file storage: {:?}", id);
+        }
+        Ok(Path::new(parent).join(id))
+    }
+}
+
+impl FileService {
+    /// Gets the directory uploads for a record are stored in, creating it if needed
+    ///
+    /// # Arguments
+    /// * `owner` - The record the upload belongs to, or None if it does not exist yet
+    ///
+    /// # Returns
+    /// * `Result<PathBuf>` - The directory or error
+    pub(super) async fn upload_directory(&self, owner: Option<&StorageOwner>) -> Result<PathBuf> {
+        let directory = match owner {
+            Some(owner) => self.root_path.join(owner.relative_directory()?),
+            None => self.root_path.join(UNASSIGNED_DIRECTORY),
+        };
+        fs::create_dir_all(&directory)
+            .await
+            .context(format!("Failed to create directory: {:?}", directory))?;
+        Ok(directory)
+    }
+
+    /// Moves a file that was uploaded before its record existed into the record's directory.
+    /// Files stored anywhere else are left where they are.
+    ///
+    /// # Arguments
+    /// * `file_path` - Path of the uploaded file
+    /// * `owner` - The record the file belongs to
+    ///
+    /// # Returns
+    /// * `Result<String>` - The path of the file after any move, or error
+    pub async fn assign_to_owner(&self, file_path: &str, owner: &StorageOwner) -> Result<String> {
+        let path = Path::new(file_path);
+        let unassigned = self.root_path.join(UNASSIGNED_DIRECTORY);
+        let is_unassigned = match (path.parent(), path.file_name()) {
+            (Some(parent), Some(_)) => same_path(parent, &unassigned),
+            _ => false,
+        };
+        if !is_unassigned || !path.is_file() {
+            return Ok(file_path.to_string());
+        }
+
+        let destination = self
+            .upload_directory(Some(owner))
+            .await?
+            .join(path.file_name().unwrap_or_default());
+        fs::rename(path, &destination).await.context(format!(
+            "Failed to move file from {:?} to {:?}",
+            path, destination
+        ))?;
+        self.move_checksums(&[(path.to_path_buf(), destination.clone())])
+            .await?;
+
+        log::info!("Moved uploaded file {:?} -> {:?}", path, destination);
+        Ok(destination.to_string_lossy().into_owned())
+    }
+
+    /// Lists every file in the upload directories
+    ///
+    /// # Returns
+    /// * `Result<Vec<PathBuf>>` - Paths of the files or error
+    pub(super) async fn files_in_upload_directories(&self) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        let mut directories: Vec<PathBuf> =
+            [ANIMALS_DIRECTORY, REQUESTS_DIRECTORY, UNASSIGNED_DIRECTORY]
+                .iter()
+                .map(|name| self.root_path.join(name))
+                .filter(|directory| directory.is_dir())
+                .collect();
+        while let Some(directory) = directories.pop() {
+            let mut entries = fs::read_dir(&directory)
+                .await
+                .context(format!("Failed to read directory: {:?}", directory))?;
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .context("Failed to read directory entry")?
+            {
+                let file_type = entry.file_type().await?;
+                if file_type.is_dir() {
+                    directories.push(entry.path());
+                } else if file_type.is_file() {
+                    files.push(entry.path());
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    /// Fails unless a file lies within one of the upload directories, so that deleting
+    /// uploads can never touch the databases or other application files
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file being accessed
+    /// * `action` - What is being done to the file, for the error message
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success if the file is an upload
+    pub(super) fn ensure_within_uploads(&self, file_path: &Path, action: &str) -> Result<()> {
+        self.ensure_within_root(file_path, action)?;
+
+        let canonical_file_path = file_path
+            .canonicalize()
+            .context(format!("Failed to resolve file path: {:?}", file_path))?;
+        if !self.is_upload(&canonical_file_path) {
+            bail!(
+                "Security violation: Attempted to {} a file that is not an upload: {:?}",
+                action,
+                canonical_file_path
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks whether a file lies within one of the upload directories
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file, which must exist
+    ///
+    /// # Returns
+    /// * `bool` - True if the file is an upload, false if it is not or cannot be resolved
+    pub fn is_upload(&self, file_path: &Path) -> bool {
+        let Ok(canonical_file_path) = file_path.canonicalize() else {
+            return false;
+        };
+        [ANIMALS_DIRECTORY, REQUESTS_DIRECTORY, UNASSIGNED_DIRECTORY]
+            .iter()
+            .filter_map(|name| self.root_path.join(name).canonicalize().ok())
+            .any(|directory| canonical_file_path.starts_with(directory))
+    }
+
+    /// Removes a record's directory once its last file is gone
+    ///
+    /// # Arguments
+    /// * `directory` - The directory a file was just deleted from
+    pub(super) async fn remove_directory_if_empty(&self, directory: &Path) {
+        let is_owner_directory = [ANIMALS_DIRECTORY, REQUESTS_DIRECTORY].iter().any(|name| {
+            directory
+                .parent()
+                .is_some_and(|parent| same_path(parent, &self.root_path.join(name)))
+        });
+        if is_owner_directory {
+            // Fails harmlessly if the directory still holds files
+            let _ = fs::remove_dir(directory).await;
+        }
+    }
+
+    /// Moves the uploads that older versions stored directly in the root directory into
+    /// the directory of the animal whose image they are, or into the directory for
+    /// unassigned uploads if no animal uses them
+    ///
+    /// # Arguments
+    /// * `image_paths` - Pairs of animal ID and image path, for every animal with an image
+    ///
+    /// # Returns
+    /// * `Result<Vec<(String, String)>>` - Each image path that must change, paired with its
+    ///   new path
+    pub async fn migrate_flat_uploads(
+        &self,
+        image_paths: &[(String, String)],
+    ) -> Result<Vec<(String, String)>> {
+        let mut changed_paths: Vec<(String, String)> = Vec::new();
+        for file in self.flat_uploads().await? {
+            let file_name = file.file_name().unwrap_or_default();
+            let references: Vec<&(String, String)> = image_paths
+                .iter()
+                .filter(|(_, image_path)| Path::new(image_path).file_name() == Some(file_name))
+                .collect();
+
+            let owner = references
+                .first()
+                .map(|(animal_id, _)| StorageOwner::Animal(animal_id.clone()));
+            let destination = self.upload_directory(owner.as_ref()).await?.join(file_name);
+            fs::rename(&file, &destination).await.context(format!(
+                "Failed to move file from {:?} to {:?}",
+                file, destination
+            ))?;
+
+            let new_path = destination.to_string_lossy().into_owned();
+            for (_, image_path) in references {
+                if !changed_paths
+                    .iter()
+                    .any(|(old_path, _)| old_path == image_path)
+                {
+                    changed_paths.push((image_path.clone(), new_path.clone()));
+                }
+            }
+        }
+
+        if !changed_paths.is_empty() {
+            log::info!(
+                "Moved {} uploaded images into per-animal directories",
+                changed_paths.len()
+            );
+        }
+        Ok(changed_paths)
+    }
+
+    /// Moves files back to where they were before `migrate_flat_uploads`, for when the
+    /// database could not be updated to point at their new paths
+    ///
+    /// # Arguments
+    /// * `changed_paths` - The image paths returned by `migrate_flat_uploads`
+    pub async fn revert_migrated_uploads(&self, changed_paths: &[(String, String)]) {
+        for (old_path, new_path) in changed_paths {
+            if let Err(e) = fs::rename(new_path, old_path).await {
+                log::error!(
+                    "Failed to move {:?} back to {:?}: {}",
+                    new_path,
+                    old_path,
+                    e
+                );
+            }
+        }
+    }
+
+    /// Lists the uploads stored directly in the root directory, which are named after
+    /// their upload time
+    ///
+    /// # Returns
+    /// * `Result<Vec<PathBuf>>` - Paths of the uploaded files or error
+    pub(super) async fn flat_uploads(&self) -> Result<Vec<PathBuf>> {
+        let mut entries = fs::read_dir(&self.root_path).await.context(format!(
+            "Failed to read root directory: {:?}",
+            self.root_path
+        ))?;
+
+        let mut files = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .context("Failed to read directory entry")?
+        {
+            let path = entry.path();
+            let is_upload = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| !stem.is_empty() && stem.chars().all(|c| c.is_ascii_digit()));
+            if is_upload && entry.file_type().await?.is_file() {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    }
+}
+
+/// Checks whether two paths name the same location, resolving them if they exist
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
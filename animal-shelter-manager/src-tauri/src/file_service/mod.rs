@@ -2,23 +2,46 @@
 // file_service/mod.rs
 //
 // This module provides file-related functionality to other components,
-// including file upload with user selection dialogs and secure file deletion.
-// All file operations are performed within a designated root directory.
+// including file upload with user selection dialogs, content validation of
+// uploads, storage usage reporting, checksum verification of stored files and
+// secure file deletion.
+// All file operations are performed within a designated root directory, with
+// uploads kept in a directory per record.
 //
 
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
+use base64::prelude::{Engine, BASE64_STANDARD};
 use chrono::Utc;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use tauri::AppHandle;
 use tauri_plugin_dialog::DialogExt;
 use tokio::fs;
 
+mod checksums;
+mod editing;
+mod layout;
+mod stats;
 mod test;
+mod validation;
+
+pub use checksums::FileVerificationReport;
+pub use layout::StorageOwner;
+pub use stats::{StorageStats, BACKUP_DIRECTORY};
+pub use validation::UploadKind;
+
+/// Largest file that can be uploaded, in bytes, until the upload limit setting is applied
+const DEFAULT_MAX_UPLOAD_BYTES: u64 = 20 * 1024 * 1024;
 
 /// Service for handling file operations in the application
 pub struct FileService {
     /// Root directory where all application files are stored
     root_path: PathBuf,
+    /// Held while the checksum manifest is read and rewritten, so concurrent uploads
+    /// do not lose each other's checksums
+    manifest_lock: tokio::sync::Mutex<()>,
+    /// Largest file that can be uploaded, in bytes
+    max_upload_bytes: AtomicU64,
 }
 
 impl FileService {
@@ -39,20 +62,65 @@ impl FileService {
                 .context(format!("Failed to create root directory: {:?}", root_path))?;
         }
 
-        Ok(FileService { root_path })
+        // Files that were still being written when the application stopped are incomplete
+        FileService::remove_partial_files(&root_path);
+
+        Ok(FileService {
+            root_path,
+            manifest_lock: tokio::sync::Mutex::new(()),
+            max_upload_bytes: AtomicU64::new(DEFAULT_MAX_UPLOAD_BYTES),
+        })
+    }
+
+    /// Changes the largest file that can be uploaded
+    ///
+    /// # Arguments
+    /// * `bytes` - The new limit, in bytes
+    pub fn set_max_upload_bytes(&self, bytes: u64) {
+        self.max_upload_bytes.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Fails if a file is larger than the upload limit
+    ///
+    /// # Arguments
+    /// * `bytes` - Size of the file, in bytes
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success if the file is small enough
+    fn ensure_upload_size(&self, bytes: u64) -> Result<()> {
+        let max_upload_bytes = self.max_upload_bytes.load(Ordering::Relaxed);
+        if bytes > max_upload_bytes {
+            bail!(
+                "The file is {:.1} MB; files can be at most {:.1} MB",
+                bytes as f64 / (1024.0 * 1024.0),
+                max_upload_bytes as f64 / (1024.0 * 1024.0)
+            );
+        }
+        Ok(())
     }
 
     /// Allows user to select and upload a file from their computer
     ///
     /// # Arguments
     /// * `app_handle` - Tauri application handle for accessing dialog plugin
+    /// * `kind` - What the file is for, to check its contents suit it; None accepts any file
+    /// * `owner` - The record the file belongs to, or None if it does not exist yet
     ///
     /// # Returns
     /// * `Result<Option<PathBuf>>` - Path where the file was saved, or None if cancelled
-    pub async fn upload_file(&self, app_handle: &AppHandle) -> Result<Option<PathBuf>> {
+    pub async fn upload_file(
+        &self,
+        app_handle: &AppHandle,
+        kind: Option<UploadKind>,
+        owner: Option<&StorageOwner>,
+    ) -> Result<Option<PathBuf>> {
         // Open file selection dialog using tokio oneshot channel for async handling
         let (tx, rx) = tokio::sync::oneshot::channel();
-        app_handle.dialog().file().pick_file(move |file_path| {
+        let mut dialog = app_handle.dialog().file();
+        if let Some(kind) = kind {
+            dialog = dialog.add_filter(kind.filter_name(), &kind.allowed_extensions());
+        }
+        dialog.pick_file(move |file_path| {
             let _ = tx.send(file_path);
         });
 
@@ -67,38 +135,164 @@ impl FileService {
                 // Convert FilePath to PathBuf
                 let selected_path_buf = selected_path.into_path()?;
 
-                // Generate unique filename using timestamp in milliseconds
-                let timestamp = Utc::now().timestamp_millis();
+                self.check_upload(&selected_path_buf, kind).await?;
+                Ok(Some(self.store_upload(&selected_path_buf, owner).await?))
+            }
+            None => {
+                log::info!("File selection was cancelled by user");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Allows user to select several files from their computer in one dialog and uploads them
+    ///
+    /// # Arguments
+    /// * `app_handle` - Tauri application handle for accessing dialog plugin
+    /// * `kind` - What the files are for, to check their contents suit it; None accepts any file
+    /// * `owner` - The record the files belong to, or None if it does not exist yet
+    ///
+    /// # Returns
+    /// * `Result<Vec<PathBuf>>` - Paths where the files were saved, in the order they were
+    ///   selected, or an empty list if cancelled
+    pub async fn upload_files(
+        &self,
+        app_handle: &AppHandle,
+        kind: Option<UploadKind>,
+        owner: Option<&StorageOwner>,
+    ) -> Result<Vec<PathBuf>> {
+        // Open file selection dialog using tokio oneshot channel for async handling
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let mut dialog = app_handle.dialog().file();
+        if let Some(kind) = kind {
+            dialog = dialog.add_filter(kind.filter_name(), &kind.allowed_extensions());
+        }
+        dialog.pick_files(move |file_paths| {
+            let _ = tx.send(file_paths);
+        });
+
+        // Wait for user to select the files or cancel
+        let file_paths = rx
+            .await
+            .context("Failed to receive file selection result")?;
+        let Some(file_paths) = file_paths else {
+            log::info!("File selection was cancelled by user");
+            return Ok(Vec::new());
+        };
+        let selected_paths = file_paths
+            .into_iter()
+            .map(|file_path| file_path.into_path())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Check every file before storing any, so one unsuitable file does not leave
+        // the others half uploaded
+        for selected_path in &selected_paths {
+            self.check_upload(selected_path, kind).await?;
+        }
+
+        let mut stored_paths = Vec::with_capacity(selected_paths.len());
+        for selected_path in &selected_paths {
+            stored_paths.push(self.store_upload(selected_path, owner).await?);
+        }
+        Ok(stored_paths)
+    }
+
+    /// Asks the user where to save a document
+    ///
+    /// # Arguments
+    /// * `app_handle` - Tauri application handle for accessing dialog plugin
+    /// * `file_name` - File name suggested to the user
+    /// * `filter_name` - Name of the file type shown in the dialog, such as "PDF"
+    /// * `extension` - File extension of the document, without the dot
+    ///
+    /// # Returns
+    /// * `Result<Option<PathBuf>>` - Path chosen for the document, or None if cancelled
+    pub async fn choose_save_path(
+        &self,
+        app_handle: &AppHandle,
+        file_name: &str,
+        filter_name: &str,
+        extension: &str,
+    ) -> Result<Option<PathBuf>> {
+        // Open file save dialog using tokio oneshot channel for async handling
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        app_handle
+            .dialog()
+            .file()
+            .set_file_name(file_name)
+            .add_filter(filter_name, &[extension])
+            .save_file(move |file_path| {
+                let _ = tx.send(file_path);
+            });
+
+        // Wait for user to choose a destination or cancel
+        let file_path = rx.await.context("Failed to receive file save result")?;
+
+        match file_path {
+            Some(selected_path) => Ok(Some(selected_path.into_path()?)),
+            None => {
+                log::info!("File save was cancelled by user");
+                Ok(None)
+            }
+        }
+    }
 
-                // Get file extension from original file
-                let extension = selected_path_buf
-                    .extension()
-                    .and_then(|ext| ext.to_str())
-                    .unwrap_or("");
+    /// Writes a document to the path the user chose for it
+    ///
+    /// # Arguments
+    /// * `destination_path` - Path chosen with `choose_save_path`
+    /// * `contents` - The bytes of the document
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub async fn save_document(&self, destination_path: &Path, contents: Vec<u8>) -> Result<()> {
+        fs::write(destination_path, contents)
+            .await
+            .context(format!("Failed to write file: {:?}", destination_path))?;
+
+        log::info!("File saved successfully: {:?}", destination_path);
+        Ok(())
+    }
 
-                let filename = if extension.is_empty() {
-                    format!("{}", timestamp)
-                } else {
-                    format!("{}.{}", timestamp, extension)
-                };
+    /// Asks the user to pick a document and reads it
+    ///
+    /// # Arguments
+    /// * `app_handle` - Tauri application handle for accessing dialog plugin
+    /// * `filter_name` - Name of the file type shown in the dialog, such as "JSON"
+    /// * `extension` - File extension of the document, without the dot
+    ///
+    /// # Returns
+    /// * `Result<Option<Vec<u8>>>` - The bytes of the document, or None if cancelled
+    pub async fn open_file(
+        &self,
+        app_handle: &AppHandle,
+        filter_name: &str,
+        extension: &str,
+    ) -> Result<Option<Vec<u8>>> {
+        // Open file selection dialog using tokio oneshot channel for async handling
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        app_handle
+            .dialog()
+            .file()
+            .add_filter(filter_name, &[extension])
+            .pick_file(move |file_path| {
+                let _ = tx.send(file_path);
+            });
 
-                let destination_path = self.root_path.join(filename);
+        // Wait for user to select a file or cancel
+        let file_path = rx
+            .await
+            .context("Failed to receive file selection result")?;
 
-                // Copy the selected file to our storage location
-                fs::copy(&selected_path_buf, &destination_path)
+        match file_path {
+            Some(selected_path) => {
+                let source_path = selected_path.into_path()?;
+                let contents = fs::read(&source_path)
                     .await
-                    .context(format!(
-                        "Failed to copy file from {:?} to {:?}",
-                        selected_path_buf, destination_path
-                    ))?;
-
-                log::info!(
-                    "File uploaded successfully: {:?} -> {:?}",
-                    selected_path_buf,
-                    destination_path
-                );
-
-                Ok(Some(destination_path))
+                    .context(format!("Failed to read file: {:?}", source_path))?;
+
+                log::info!("File opened successfully: {:?}", source_path);
+                Ok(Some(contents))
             }
             None => {
                 log::info!("File selection was cancelled by user");
@@ -107,6 +301,17 @@ impl FileService {
         }
     }
 
+    /// Lists the uploaded files, both those in the upload directories and those that
+    /// older versions stored directly in the root directory
+    ///
+    /// # Returns
+    /// * `Result<Vec<PathBuf>>` - Paths of the uploaded files or error
+    pub async fn list_uploaded_files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = self.flat_uploads().await?;
+        files.extend(self.files_in_upload_directories().await?);
+        Ok(files)
+    }
+
     /// Deletes a file from the specified path
     ///
     /// # Arguments
@@ -123,7 +328,225 @@ impl FileService {
             bail!("File does not exist: {:?}", file_path);
         }
 
-        // Ensure the file is within our root directory for security
+        // Ensure the file is an upload, never a database or other application file
+        self.ensure_within_uploads(file_path, "delete")?;
+
+        // Delete the file
+        fs::remove_file(file_path)
+            .await
+            .context(format!("Failed to delete file: {:?}", file_path))?;
+        if let Some(directory) = file_path.parent() {
+            self.remove_directory_if_empty(directory).await;
+        }
+        self.forget_checksum(file_path).await?;
+
+        log::info!("File deleted successfully: {:?}", file_path);
+        Ok(())
+    }
+
+    /// Reads an uploaded file
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file to read
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>>` - The contents of the file or error
+    pub async fn read_file<P: AsRef<Path>>(&self, file_path: P) -> Result<Vec<u8>> {
+        let file_path = file_path.as_ref();
+
+        // Ensure the file is an upload, never a database or other application file
+        self.ensure_within_uploads(file_path, "read")?;
+
+        fs::read(file_path)
+            .await
+            .context(format!("Failed to read file: {:?}", file_path))
+    }
+
+    /// Copies a stored file to a path outside the root directory, such as next to
+    /// an exported document, creating the directory it goes in
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the stored file
+    /// * `destination_path` - Path to copy the file to
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub async fn copy_file_out<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        destination_path: &Path,
+    ) -> Result<()> {
+        let file_path = file_path.as_ref();
+
+        // Only files within our root directory can be copied out
+        self.ensure_within_root(file_path, "copy")?;
+
+        if let Some(parent) = destination_path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .context(format!("Failed to create directory: {:?}", parent))?;
+        }
+        fs::copy(file_path, destination_path)
+            .await
+            .context(format!(
+                "Failed to copy file {:?} to {:?}",
+                file_path, destination_path
+            ))?;
+        Ok(())
+    }
+
+    /// Stores the contents of a file that was dropped or pasted into the application
+    /// rather than selected in a dialog
+    ///
+    /// # Arguments
+    /// * `filename_hint` - Original name of the file, used only for its extension
+    /// * `contents` - The bytes of the file
+    /// * `kind` - What the file is for, to check its contents suit it; None accepts any file
+    /// * `owner` - The record the file belongs to, or None if it does not exist yet
+    ///
+    /// # Returns
+    /// * `Result<PathBuf>` - Path where the file was saved, or error if it is unsuitable
+    pub async fn save_bytes(
+        &self,
+        filename_hint: &str,
+        contents: &[u8],
+        kind: Option<UploadKind>,
+        owner: Option<&StorageOwner>,
+    ) -> Result<PathBuf> {
+        // Only the extension of the hint is kept, so it cannot name a path of its own
+        let extension = upload_extension(Path::new(filename_hint));
+        let extension = if extension.chars().all(|c| c.is_ascii_alphanumeric()) {
+            extension
+        } else {
+            ""
+        };
+
+        self.ensure_upload_size(contents.len() as u64)
+            .map_err(|e| anyhow!("Cannot upload {}: {}", filename_hint, e))?;
+        if let Some(kind) = kind {
+            validation::validate_upload(kind, extension, contents)
+                .map_err(|e| anyhow!("Cannot upload {}: {}", filename_hint, e))?;
+        }
+
+        let destination_path = self.unique_upload_path(extension, owner).await?;
+        self.write_verified(&destination_path, contents).await?;
+
+        log::info!(
+            "File uploaded successfully: {} -> {:?}",
+            filename_hint,
+            destination_path
+        );
+        Ok(destination_path)
+    }
+
+    /// Checks that a selected file is within the upload limit and suits what it is uploaded
+    /// for. The dialog filter only looks at file names, so the contents are checked as well.
+    ///
+    /// # Arguments
+    /// * `source_path` - Path of the selected file
+    /// * `kind` - What the file is for; None accepts any file
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or error describing why the file cannot be used
+    async fn check_upload(&self, source_path: &Path, kind: Option<UploadKind>) -> Result<()> {
+        let cannot_upload = |e: anyhow::Error| {
+            let file_name = source_path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy();
+            anyhow!("Cannot upload {}: {}", file_name, e)
+        };
+
+        let metadata = fs::metadata(source_path)
+            .await
+            .context(format!("Failed to read file: {:?}", source_path))?;
+        self.ensure_upload_size(metadata.len())
+            .map_err(cannot_upload)?;
+
+        let Some(kind) = kind else {
+            return Ok(());
+        };
+        let contents = fs::read(source_path)
+            .await
+            .context(format!("Failed to read file: {:?}", source_path))?;
+        validation::validate_upload(kind, upload_extension(source_path), &contents)
+            .map_err(cannot_upload)
+    }
+
+    /// Copies a selected file into its record's directory, named after its upload time
+    ///
+    /// # Arguments
+    /// * `source_path` - Path of the selected file
+    /// * `owner` - The record the file belongs to, or None if it does not exist yet
+    ///
+    /// # Returns
+    /// * `Result<PathBuf>` - Path where the file was saved or error
+    async fn store_upload(
+        &self,
+        source_path: &Path,
+        owner: Option<&StorageOwner>,
+    ) -> Result<PathBuf> {
+        let destination_path = self
+            .unique_upload_path(upload_extension(source_path), owner)
+            .await?;
+
+        // Copy the selected file to our storage location
+        let contents = fs::read(source_path)
+            .await
+            .context(format!("Failed to read file: {:?}", source_path))?;
+        self.write_verified(&destination_path, &contents).await?;
+
+        log::info!(
+            "File uploaded successfully: {:?} -> {:?}",
+            source_path,
+            destination_path
+        );
+        Ok(destination_path)
+    }
+
+    /// Chooses an unused path in a record's directory for an uploaded file
+    ///
+    /// # Arguments
+    /// * `extension` - Extension of the uploaded file, without the dot
+    /// * `owner` - The record the file belongs to, or None if it does not exist yet
+    ///
+    /// # Returns
+    /// * `Result<PathBuf>` - The path, named after the upload time
+    async fn unique_upload_path(
+        &self,
+        extension: &str,
+        owner: Option<&StorageOwner>,
+    ) -> Result<PathBuf> {
+        let directory = self.upload_directory(owner).await?;
+        unique_path_in(&directory, extension).await
+    }
+
+    /// Reads an uploaded file as a data URL, so it can be displayed without going
+    /// through the asset protocol
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file to read
+    ///
+    /// # Returns
+    /// * `Result<String>` - The contents of the file, base64 encoded with their MIME type
+    pub async fn read_file_data_url<P: AsRef<Path>>(&self, file_path: P) -> Result<String> {
+        let contents = self.read_file(file_path).await?;
+        Ok(format!(
+            "data:{};base64,{}",
+            validation::sniff_mime_type(&contents),
+            BASE64_STANDARD.encode(&contents)
+        ))
+    }
+
+    /// Fails unless a file lies within the root directory
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file being accessed
+    /// * `action` - What is being done to the file, for the error message
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success if the file is within the root directory
+    fn ensure_within_root(&self, file_path: &Path, action: &str) -> Result<()> {
         let canonical_file_path = file_path
             .canonicalize()
             .context(format!("Failed to resolve file path: {:?}", file_path))?;
@@ -133,17 +556,50 @@ impl FileService {
             .context(format!("Failed to resolve root path: {:?}", self.root_path))?;
         if !canonical_file_path.starts_with(&canonical_root_path) {
             bail!(
-                "Security violation: Attempted to delete file outside root directory. File: {:?}, Root: {:?}",
-                canonical_file_path, canonical_root_path
+                "Security violation: Attempted to {} file outside root directory. File: {:?}, Root: {:?}",
+                action, canonical_file_path, canonical_root_path
             );
         }
+        Ok(())
+    }
+}
 
-        // Delete the file
-        fs::remove_file(file_path)
-            .await
-            .context(format!("Failed to delete file: {:?}", file_path))?;
+/// Gets the extension of a selected file, or an empty string if it has none
+///
+/// # Arguments
+/// * `source_path` - Path of the selected file
+///
+/// # Returns
+/// * `&str` - The extension, without the dot
+fn upload_extension(source_path: &Path) -> &str {
+    source_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+}
 
-        log::info!("File deleted successfully: {:?}", file_path);
-        Ok(())
+/// Chooses an unused path in a directory for a stored file
+///
+/// # Arguments
+/// * `directory` - The directory the file is stored in
+/// * `extension` - Extension of the file, without the dot
+///
+/// # Returns
+/// * `Result<PathBuf>` - The path, named after the current time
+async fn unique_path_in(directory: &Path, extension: &str) -> Result<PathBuf> {
+    // Generate unique filename using timestamp in milliseconds, moving on to the next
+    // millisecond when several files are stored at once
+    let mut timestamp = Utc::now().timestamp_millis();
+    loop {
+        let filename = if extension.is_empty() {
+            format!("{}", timestamp)
+        } else {
+            format!("{}.{}", timestamp, extension)
+        };
+        let destination_path = directory.join(filename);
+        if !fs::try_exists(&destination_path).await? {
+            return Ok(destination_path);
+        }
+        timestamp += 1;
     }
 }
@@ -0,0 +1,244 @@
+//
+// file_service/stats.rs
+//
+// This module reports how much disk space the application's files take up,
+// so staff can notice before the computer runs out of space.
+//
+
+use super::{upload_extension, FileService, UploadKind};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Name of the directory within the root directory that holds backups
+pub const BACKUP_DIRECTORY: &str = "backups";
+
+/// Number of files and bytes in one category of stored files
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageCategory {
+    /// Number of files in the category
+    pub file_count: u64,
+    /// Total size of the files, in bytes
+    pub bytes: u64,
+}
+
+impl StorageCategory {
+    /// Counts one more file of the given size
+    fn add(&mut self, bytes: u64) {
+        self.file_count += 1;
+        self.bytes += bytes;
+    }
+}
+
+/// Size of a database file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseFileSize {
+    /// Name of the file, such as `animal_shelter.db`
+    pub name: String,
+    /// Size of the file, in bytes
+    pub bytes: u64,
+}
+
+/// Disk space used by the files in the root directory
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageStats {
+    /// Total size of every file, in bytes
+    pub total_bytes: u64,
+    /// Uploaded images
+    pub animal_photos: StorageCategory,
+    /// Uploaded files that are not images
+    pub documents: StorageCategory,
+    /// Files in the backup directory
+    pub backups: StorageCategory,
+    /// Database files, with their journals, largest first
+    pub databases: Vec<DatabaseFileSize>,
+    /// Any other files
+    pub other: StorageCategory,
+}
+
+impl FileService {
+    /// Adds up the size of every file in the root directory by category
+    ///
+    /// # Returns
+    /// * `Result<StorageStats>` - The disk space used or error
+    pub async fn storage_stats(&self) -> Result<StorageStats> {
+        let photo_extensions = UploadKind::AnimalPhoto.allowed_extensions();
+        let uploads = self.list_uploaded_files().await?;
+        let backup_root = self.root_path.join(BACKUP_DIRECTORY);
+
+        let mut stats = StorageStats::default();
+        for (path, bytes) in self.all_files().await? {
+            stats.total_bytes += bytes;
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            if path.starts_with(&backup_root) {
+                stats.backups.add(bytes);
+            } else if uploads.contains(&path) {
+                let extension = upload_extension(&path).to_lowercase();
+                if photo_extensions.contains(&extension.as_str()) {
+                    stats.animal_photos.add(bytes);
+                } else {
+                    stats.documents.add(bytes);
+                }
+            } else if is_database_file(&file_name) {
+                stats.databases.push(DatabaseFileSize {
+                    name: file_name,
+                    bytes,
+                });
+            } else {
+                stats.other.add(bytes);
+            }
+        }
+        stats
+            .databases
+            .sort_by_key(|database| Reverse(database.bytes));
+
+        log::debug!(
+            "Storage used under {:?}: {} bytes",
+            self.root_path,
+            stats.total_bytes
+        );
+        Ok(stats)
+    }
+
+    /// Lists every file under the root directory, including those in subdirectories
+    ///
+    /// # Returns
+    /// * `Result<Vec<(PathBuf, u64)>>` - The path and size of each file or error
+    async fn all_files(&self) -> Result<Vec<(PathBuf, u64)>> {
+        let mut files = Vec::new();
+        let mut directories = vec![self.root_path.clone()];
+        while let Some(directory) = directories.pop() {
+            let mut entries = fs::read_dir(&directory)
+                .await
+                .context(format!("Failed to read directory: {:?}", directory))?;
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .context("Failed to read directory entry")?
+            {
+                let metadata = entry
+                    .metadata()
+                    .await
+                    .context(format!("Failed to read metadata: {:?}", entry.path()))?;
+                if metadata.is_dir() {
+                    directories.push(entry.path());
+                } else if metadata.is_file() {
+                    files.push((entry.path(), metadata.len()));
+                }
+            }
+        }
+        Ok(files)
+    }
+}
+
+impl FileService {
+    /// Gets the free disk space on the drive holding the root directory
+    ///
+    /// # Returns
+    /// * `Result<u64>` - The bytes the application can still write or error
+    pub async fn available_space(&self) -> Result<u64> {
+        let root_path = self.root_path.clone();
+        tokio::task::spawn_blocking(move || available_bytes(&root_path))
+            .await
+            .context("Disk space check did not complete")?
+    }
+
+    /// Gets when the most recent backup was made
+    ///
+    /// # Returns
+    /// * `Result<Option<i64>>` - Timestamp the newest file in the backup directory was
+    ///   written, or None if there are no backups
+    pub async fn last_backup_timestamp(&self) -> Result<Option<i64>> {
+        let backup_root = self.root_path.join(BACKUP_DIRECTORY);
+        let mut entries = match fs::read_dir(&backup_root).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).context(format!("Failed to read directory: {:?}", backup_root))
+            }
+        };
+
+        let mut latest: Option<i64> = None;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .context("Failed to read directory entry")?
+        {
+            let modified = entry
+                .metadata()
+                .await
+                .and_then(|metadata| metadata.modified())
+                .context(format!("Failed to read metadata: {:?}", entry.path()))?;
+            let timestamp = chrono::DateTime::<chrono::Utc>::from(modified).timestamp();
+            latest = latest.max(Some(timestamp));
+        }
+        Ok(latest)
+    }
+}
+
+/// Gets the free disk space on the drive holding a path
+///
+/// # Arguments
+/// * `path` - A path on the drive
+///
+/// # Returns
+/// * `Result<u64>` - The bytes an unprivileged user can still write or error
+#[cfg(unix)]
+fn available_bytes(path: &Path) -> Result<u64> {
+    let stats =
+        rustix::fs::statvfs(path).context(format!("Failed to read disk space: {:?}", path))?;
+    Ok(stats.f_bavail.saturating_mul(stats.f_frsize))
+}
+
+/// Gets the free disk space on the drive holding a path
+///
+/// # Arguments
+/// * `path` - A path on the drive
+///
+/// # Returns
+/// * `Result<u64>` - The bytes the current user can still write or error
+#[cfg(windows)]
+fn available_bytes(path: &Path) -> Result<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide_path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut available: u64 = 0;
+    // SAFETY: the path is NUL-terminated and outlives the call, and the only output
+    // pointer given is to a local; the totals are not asked for
+    let succeeded = unsafe {
+        GetDiskFreeSpaceExW(
+            wide_path.as_ptr(),
+            &mut available,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    if succeeded == 0 {
+        return Err(std::io::Error::last_os_error())
+            .context(format!("Failed to read disk space: {:?}", path));
+    }
+    Ok(available)
+}
+
+/// Checks whether a file is a SQLite database or one of its journals
+///
+/// # Arguments
+/// * `file_name` - Name of the file
+///
+/// # Returns
+/// * `bool` - True for `.db` files and their `-wal`, `-shm` and `-journal` files
+fn is_database_file(file_name: &str) -> bool {
+    [".db", ".db-wal", ".db-shm", ".db-journal"]
+        .iter()
+        .any(|suffix| file_name.ends_with(suffix))
+}
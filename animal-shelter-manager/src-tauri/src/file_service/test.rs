@@ -6,7 +6,8 @@
 
 #[cfg(test)]
 mod file_service_tests {
-    use crate::file_service::FileService;
+    use crate::file_service::validation::{validate_upload, MAX_IMAGE_DIMENSION};
+    use crate::file_service::{FileService, StorageOwner, UploadKind};
     use std::fs;
     use std::io::Write;
     use std::path::PathBuf;
@@ -41,7 +42,8 @@ mod file_service_tests {
     #[tokio::test]
     async fn test_delete_file_success() {
         let (file_service, root_path) = create_test_fs("test_delete_file_success");
-        let file_path = root_path.join("test_file.txt");
+        fs::create_dir_all(root_path.join("animals/a1")).unwrap();
+        let file_path = root_path.join("animals/a1/test_file.txt");
 
         // Create a dummy file to delete.
         let mut file = fs::File::create(&file_path).expect("Failed to create test file");
@@ -51,9 +53,11 @@ mod file_service_tests {
         // Call the delete function.
         let result = file_service.delete_file(&file_path).await;
 
-        // Assert that the deletion was successful and the file is gone.
+        // Assert that the deletion was successful and the file is gone, along with the
+        // animal's directory now that it is empty.
         assert!(result.is_ok());
         assert!(!file_path.exists());
+        assert!(!root_path.join("animals/a1").exists());
     }
 
     #[tokio::test]
@@ -98,4 +102,417 @@ mod file_service_tests {
         fs::remove_file(&outside_file_path).expect("Failed to clean up outside file");
         fs::remove_dir(&outside_dir).expect("Failed to clean up outside directory");
     }
+
+    #[tokio::test]
+    async fn test_copy_file_out() {
+        let (file_service, root_path) = create_test_fs("test_copy_file_out");
+        let file_path = root_path.join("photo.png");
+        fs::write(&file_path, b"photo").unwrap();
+
+        // The directory the copy goes in is created
+        let destination = PathBuf::from("test_artifacts/file_service/test_copy_file_out_export");
+        if destination.exists() {
+            fs::remove_dir_all(&destination).unwrap();
+        }
+        let copy_path = destination.join("photos/a1.png");
+        file_service
+            .copy_file_out(&file_path, &copy_path)
+            .await
+            .unwrap();
+        assert_eq!(fs::read(&copy_path).unwrap(), b"photo");
+
+        // Files outside the root directory cannot be copied out
+        let result = file_service
+            .copy_file_out(&copy_path, &destination.join("again.png"))
+            .await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Security violation"));
+    }
+
+    #[tokio::test]
+    async fn test_list_uploaded_files() {
+        let (file_service, root_path) = create_test_fs("test_list_uploaded_files");
+
+        // Only files named after their upload time count as uploads.
+        fs::write(root_path.join("1700000000000.png"), b"image").unwrap();
+        fs::write(root_path.join("animal_shelter.db"), b"database").unwrap();
+        fs::create_dir_all(root_path.join("1700000000001")).unwrap();
+
+        let files = file_service.list_uploaded_files().await.unwrap();
+        assert_eq!(files, vec![root_path.join("1700000000000.png")]);
+    }
+
+    #[tokio::test]
+    async fn test_store_upload_unique_names() {
+        let (file_service, root_path) = create_test_fs("test_store_upload_unique_names");
+        let source_path = root_path.join("photo.png");
+        fs::write(&source_path, b"image").unwrap();
+
+        // Files stored within the same millisecond still get their own names.
+        let first = file_service.store_upload(&source_path, None).await.unwrap();
+        let second = file_service.store_upload(&source_path, None).await.unwrap();
+        assert_ne!(first, second);
+        assert_eq!(first.extension().unwrap(), "png");
+
+        let mut files = file_service.list_uploaded_files().await.unwrap();
+        files.sort();
+        assert_eq!(files, vec![first, second]);
+    }
+
+    #[tokio::test]
+    async fn test_save_bytes() {
+        let (file_service, root_path) = create_test_fs("test_save_bytes");
+
+        // Dropped files are stored under the root, keeping only the extension of their name.
+        let png = png_header(640, 480);
+        let saved = file_service
+            .save_bytes("../../photo.png", &png, Some(UploadKind::AnimalPhoto), None)
+            .await
+            .unwrap();
+        assert_eq!(saved.parent().unwrap(), root_path.join("uploads"));
+        assert_eq!(saved.extension().unwrap(), "png");
+        assert_eq!(fs::read(&saved).unwrap(), png);
+
+        // Their contents are checked like those of selected files.
+        let result = file_service
+            .save_bytes(
+                "photo.png",
+                b"MZ\x90\0",
+                Some(UploadKind::AnimalPhoto),
+                None,
+            )
+            .await;
+        assert!(result.is_err());
+        let saved = file_service
+            .save_bytes("notes", b"text", None, None)
+            .await
+            .unwrap();
+        assert!(saved.extension().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_upload_size_limit() {
+        let (file_service, _) = create_test_fs("test_upload_size_limit");
+        let png = png_header(1, 1);
+
+        file_service.set_max_upload_bytes(png.len() as u64 - 1);
+        let result = file_service.save_bytes("photo.png", &png, None, None).await;
+        assert!(result.unwrap_err().to_string().contains("at most"));
+
+        file_service.set_max_upload_bytes(png.len() as u64);
+        assert!(file_service
+            .save_bytes("photo.png", &png, None, None)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_read_file_data_url() {
+        let (file_service, root_path) = create_test_fs("test_read_file_data_url");
+        fs::create_dir_all(root_path.join("uploads")).unwrap();
+        let file_path = root_path.join("uploads/1700000000000.png");
+        fs::write(&file_path, png_header(1, 1)).unwrap();
+
+        let data_url = file_service.read_file_data_url(&file_path).await.unwrap();
+        assert!(data_url.starts_with("data:image/png;base64,iVBORw0KGgo"));
+
+        // Files outside the root directory cannot be read.
+        let outside_path = PathBuf::from("test_artifacts/file_service/outside_read.png");
+        fs::write(&outside_path, png_header(1, 1)).unwrap();
+        assert!(file_service
+            .read_file_data_url(&outside_path)
+            .await
+            .is_err());
+        fs::remove_file(&outside_path).unwrap();
+
+        // Neither can the databases or other application files within it.
+        fs::write(root_path.join("authentication.db"), b"database").unwrap();
+        fs::write(root_path.join("settings.db"), b"database").unwrap();
+        for path in ["uploads/../authentication.db", "settings.db"] {
+            let result = file_service.read_file_data_url(root_path.join(path)).await;
+            assert!(result.unwrap_err().to_string().contains("not an upload"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_storage_stats() {
+        let (file_service, root_path) = create_test_fs("test_storage_stats");
+        assert!(file_service
+            .last_backup_timestamp()
+            .await
+            .unwrap()
+            .is_none());
+        fs::write(root_path.join("1700000000000.png"), [0u8; 100]).unwrap();
+        fs::write(root_path.join("1700000000001.JPG"), [0u8; 50]).unwrap();
+        fs::write(root_path.join("1700000000002.pdf"), [0u8; 30]).unwrap();
+        fs::write(root_path.join("animal_shelter.db"), [0u8; 400]).unwrap();
+        fs::write(root_path.join("animal_shelter.db-wal"), [0u8; 200]).unwrap();
+        fs::write(root_path.join("authentication.db"), [0u8; 300]).unwrap();
+        fs::create_dir_all(root_path.join("backups/2024")).unwrap();
+        fs::write(root_path.join("backups/2024/backup.zip"), [0u8; 20]).unwrap();
+        fs::write(root_path.join("notes.txt"), [0u8; 7]).unwrap();
+
+        let last_backup = file_service.last_backup_timestamp().await.unwrap();
+        assert!(last_backup.is_some_and(|timestamp| timestamp > 1_700_000_000));
+        assert!(file_service.available_space().await.unwrap() > 0);
+
+        let stats = file_service.storage_stats().await.unwrap();
+        assert_eq!(stats.total_bytes, 1107);
+        assert_eq!(
+            (stats.animal_photos.file_count, stats.animal_photos.bytes),
+            (2, 150)
+        );
+        assert_eq!((stats.documents.file_count, stats.documents.bytes), (1, 30));
+        assert_eq!((stats.backups.file_count, stats.backups.bytes), (1, 20));
+        assert_eq!((stats.other.file_count, stats.other.bytes), (1, 7));
+        let databases: Vec<_> = stats.databases.iter().map(|db| db.name.as_str()).collect();
+        assert_eq!(
+            databases,
+            vec![
+                "animal_shelter.db",
+                "authentication.db",
+                "animal_shelter.db-wal"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_non_upload_fails() {
+        let (file_service, root_path) = create_test_fs("test_delete_non_upload_fails");
+        let database_path = root_path.join("animal_shelter.db");
+        fs::write(&database_path, b"database").unwrap();
+
+        // Files outside the upload directories, such as the databases, cannot be deleted.
+        let result = file_service.delete_file(&database_path).await;
+        assert!(result.unwrap_err().to_string().contains("not an upload"));
+        assert!(database_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_per_record_storage() {
+        let (file_service, root_path) = create_test_fs("test_per_record_storage");
+        let png = png_header(1, 1);
+        let animal = StorageOwner::Animal("a1".to_string());
+
+        // Uploads for an existing record go straight to its directory.
+        let saved = file_service
+            .save_bytes("photo.png", &png, None, Some(&animal))
+            .await
+            .unwrap();
+        assert_eq!(saved.parent().unwrap(), root_path.join("animals/a1"));
+
+        // Uploads made before the record existed are moved once it does.
+        let staged = file_service
+            .save_bytes("photo.png", &png, None, None)
+            .await
+            .unwrap();
+        let staged = staged.to_string_lossy().into_owned();
+        let assigned = file_service
+            .assign_to_owner(&staged, &animal)
+            .await
+            .unwrap();
+        assert_eq!(
+            PathBuf::from(&assigned).parent().unwrap(),
+            root_path.join("animals/a1")
+        );
+        assert_eq!(
+            file_service
+                .assign_to_owner(&assigned, &animal)
+                .await
+                .unwrap(),
+            assigned
+        );
+
+        // IDs that could escape the directory are refused.
+        let escaping = StorageOwner::Request("../..".to_string());
+        assert!(file_service
+            .save_bytes("photo.png", &png, None, Some(&escaping))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rotate_and_crop_image() {
+        let (file_service, root_path) = create_test_fs("test_rotate_and_crop_image");
+        let mut png = std::io::Cursor::new(Vec::new());
+        image::RgbImage::new(40, 20)
+            .write_to(&mut png, image::ImageFormat::Png)
+            .unwrap();
+        let original = file_service
+            .save_bytes(
+                "photo.png",
+                &png.into_inner(),
+                Some(UploadKind::AnimalPhoto),
+                Some(&StorageOwner::Animal("a1".to_string())),
+            )
+            .await
+            .unwrap();
+
+        // Edits are written to a new file next to the original, which is kept.
+        let rotated = file_service.rotate_image(&original, -90).await.unwrap();
+        assert_ne!(rotated, original);
+        assert!(original.exists());
+        assert_eq!(rotated.parent(), original.parent());
+        assert_eq!(image::image_dimensions(&rotated).unwrap(), (20, 40));
+
+        let cropped = file_service
+            .crop_image(&rotated, 5, 10, 15, 30)
+            .await
+            .unwrap();
+        assert_eq!(image::image_dimensions(&cropped).unwrap(), (15, 30));
+
+        // Angles that are not quarter turns and areas outside the image are refused.
+        assert!(file_service.rotate_image(&original, 45).await.is_err());
+        assert!(file_service
+            .crop_image(&original, 30, 0, 20, 20)
+            .await
+            .is_err());
+
+        // Only uploads can be edited.
+        let other = root_path.join("other.png");
+        fs::copy(&original, &other).unwrap();
+        assert!(file_service.rotate_image(&other, 90).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_files() {
+        let (file_service, root_path) = create_test_fs("test_verify_files");
+        let png = png_header(1, 1);
+        let animal = StorageOwner::Animal("a1".to_string());
+        let intact = file_service
+            .save_bytes("intact.png", &png, None, Some(&animal))
+            .await
+            .unwrap();
+        let damaged = file_service
+            .save_bytes("damaged.png", &png, None, Some(&animal))
+            .await
+            .unwrap();
+        let removed = file_service
+            .save_bytes("removed.png", &png, None, None)
+            .await
+            .unwrap();
+
+        // Nothing is left behind in the directory files are written to before being moved.
+        assert_eq!(fs::read_dir(root_path.join(".partial")).unwrap().count(), 0);
+        let report = file_service.verify_files().await.unwrap();
+        assert_eq!(report.checked_count, 3);
+        assert!(report.is_intact());
+
+        // Checksums follow files that are moved and are forgotten for deleted files.
+        let staged = file_service
+            .save_bytes("staged.png", &png, None, None)
+            .await
+            .unwrap();
+        file_service
+            .assign_to_owner(&staged.to_string_lossy(), &animal)
+            .await
+            .unwrap();
+        let deleted = file_service
+            .save_bytes("deleted.png", &png, None, Some(&animal))
+            .await
+            .unwrap();
+        file_service.delete_file(&deleted).await.unwrap();
+        assert!(file_service.verify_files().await.unwrap().is_intact());
+
+        // Changes made outside the application are reported.
+        fs::write(&damaged, b"not the original").unwrap();
+        fs::remove_file(&removed).unwrap();
+        let unrecorded = root_path.join("animals/a1/unrecorded.png");
+        fs::write(&unrecorded, &png).unwrap();
+        let report = file_service.verify_files().await.unwrap();
+        assert_eq!(report.checked_count, 4);
+        assert_eq!(report.corrupted, vec![damaged]);
+        assert_eq!(report.missing, vec![removed]);
+        assert_eq!(report.unrecorded, vec![unrecorded]);
+        assert!(intact.exists());
+
+        // Interrupted writes are cleaned up when the service starts.
+        fs::write(root_path.join(".partial/interrupted"), b"half").unwrap();
+        FileService::new(&root_path).unwrap();
+        assert!(!root_path.join(".partial").exists());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_flat_uploads() {
+        let (file_service, root_path) = create_test_fs("test_migrate_flat_uploads");
+        let used = root_path.join("1700000000000.png");
+        let unused = root_path.join("1700000000001.png");
+        fs::write(&used, b"image").unwrap();
+        fs::write(&unused, b"image").unwrap();
+        fs::write(root_path.join("animal_shelter.db"), b"database").unwrap();
+
+        let image_paths = vec![("a1".to_string(), used.to_string_lossy().into_owned())];
+        let changed = file_service
+            .migrate_flat_uploads(&image_paths)
+            .await
+            .unwrap();
+
+        // Images move to their animal's directory and other uploads are set aside.
+        let moved = root_path.join("animals/a1/1700000000000.png");
+        assert_eq!(
+            changed,
+            vec![(
+                image_paths[0].1.clone(),
+                moved.to_string_lossy().into_owned()
+            )]
+        );
+        assert!(moved.exists());
+        assert!(root_path.join("uploads/1700000000001.png").exists());
+        assert!(root_path.join("animal_shelter.db").exists());
+
+        // Nothing is left to move the second time.
+        assert!(file_service
+            .migrate_flat_uploads(&image_paths)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Helper function to build the start of a PNG file with the given dimensions.
+    ///
+    /// # Arguments
+    /// * `width` - Width of the image in pixels.
+    /// * `height` - Height of the image in pixels.
+    ///
+    /// # Returns
+    /// * `Vec<u8>` - The signature and IHDR chunk of the image.
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut contents = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        contents.extend_from_slice(&width.to_be_bytes());
+        contents.extend_from_slice(&height.to_be_bytes());
+        contents
+    }
+
+    #[test]
+    fn test_validate_upload() {
+        // Photos must be images, both by name and by contents.
+        let png = png_header(640, 480);
+        assert!(validate_upload(UploadKind::AnimalPhoto, "PNG", &png).is_ok());
+        assert!(validate_upload(UploadKind::AnimalPhoto, "exe", b"MZ\x90\0").is_err());
+        assert!(validate_upload(UploadKind::AnimalPhoto, "png", b"MZ\x90\0").is_err());
+        assert!(validate_upload(UploadKind::AnimalPhoto, "jpg", &png).is_err());
+
+        // Documents may also be PDFs, which photos may not.
+        let pdf = b"%PDF-1.7\n";
+        assert!(validate_upload(UploadKind::Document, "pdf", pdf).is_ok());
+        assert!(validate_upload(UploadKind::AnimalPhoto, "pdf", pdf).is_err());
+
+        // Images that are too large or have a damaged header are refused.
+        let huge = png_header(MAX_IMAGE_DIMENSION + 1, 100);
+        let error = validate_upload(UploadKind::AnimalPhoto, "png", &huge).unwrap_err();
+        assert!(error.to_string().contains("at most"));
+        assert!(validate_upload(UploadKind::AnimalPhoto, "png", &png[..20]).is_err());
+
+        // The dimensions of the other image formats are read from their headers.
+        let gif = b"GIF89a\x80\x02\xe0\x01";
+        assert!(validate_upload(UploadKind::AnimalPhoto, "gif", gif).is_ok());
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x40,
+            0x01, 0x01, 0xE0,
+        ];
+        let error = validate_upload(UploadKind::AnimalPhoto, "jpeg", &jpeg).unwrap_err();
+        assert!(error.to_string().contains("480x16385"));
+    }
 }
@@ -0,0 +1,281 @@
+//
+// file_service/validation.rs
+//
+// This module checks the contents of uploaded files, so that a file is only
+// accepted for a purpose its actual format is suited to, whatever its name says.
+//
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// Largest width or height, in pixels, accepted for an uploaded image
+pub const MAX_IMAGE_DIMENSION: u32 = 8192;
+
+/// What an uploaded file is going to be used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UploadKind {
+    /// A photo shown for an animal
+    AnimalPhoto,
+    /// A document kept on record, such as a scanned form
+    Document,
+}
+
+impl UploadKind {
+    /// Formats that can be uploaded for this purpose
+    fn allowed_formats(self) -> &'static [FileFormat] {
+        match self {
+            UploadKind::AnimalPhoto => &[
+                FileFormat::Png,
+                FileFormat::Jpeg,
+                FileFormat::Gif,
+                FileFormat::WebP,
+            ],
+            UploadKind::Document => &[
+                FileFormat::Pdf,
+                FileFormat::Png,
+                FileFormat::Jpeg,
+                FileFormat::Gif,
+                FileFormat::WebP,
+            ],
+        }
+    }
+
+    /// File extensions that can be uploaded for this purpose, without the dot
+    ///
+    /// # Returns
+    /// * `Vec<&'static str>` - The extensions, for filtering the file selection dialog
+    pub fn allowed_extensions(self) -> Vec<&'static str> {
+        self.allowed_formats()
+            .iter()
+            .flat_map(|format| format.extensions())
+            .copied()
+            .collect()
+    }
+
+    /// Name of the accepted files, shown in the file selection dialog
+    pub fn filter_name(self) -> &'static str {
+        match self {
+            UploadKind::AnimalPhoto => "Images",
+            UploadKind::Document => "Documents",
+        }
+    }
+}
+
+/// A file format recognized from the first bytes of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileFormat {
+    Png,
+    Jpeg,
+    Gif,
+    WebP,
+    Pdf,
+}
+
+impl FileFormat {
+    /// Recognizes the format of a file from its magic bytes
+    ///
+    /// # Arguments
+    /// * `contents` - The bytes of the file
+    ///
+    /// # Returns
+    /// * `Option<FileFormat>` - The format, or None if it is not one of the supported formats
+    fn sniff(contents: &[u8]) -> Option<FileFormat> {
+        if contents.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(FileFormat::Png)
+        } else if contents.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(FileFormat::Jpeg)
+        } else if contents.starts_with(b"GIF87a") || contents.starts_with(b"GIF89a") {
+            Some(FileFormat::Gif)
+        } else if contents.len() >= 12 && &contents[0..4] == b"RIFF" && &contents[8..12] == b"WEBP"
+        {
+            Some(FileFormat::WebP)
+        } else if contents.starts_with(b"%PDF-") {
+            Some(FileFormat::Pdf)
+        } else {
+            None
+        }
+    }
+
+    /// MIME type of the format
+    fn mime_type(self) -> &'static str {
+        match self {
+            FileFormat::Png => "image/png",
+            FileFormat::Jpeg => "image/jpeg",
+            FileFormat::Gif => "image/gif",
+            FileFormat::WebP => "image/webp",
+            FileFormat::Pdf => "application/pdf",
+        }
+    }
+
+    /// File extensions used for the format, without the dot
+    fn extensions(self) -> &'static [&'static str] {
+        match self {
+            FileFormat::Png => &["png"],
+            FileFormat::Jpeg => &["jpg", "jpeg"],
+            FileFormat::Gif => &["gif"],
+            FileFormat::WebP => &["webp"],
+            FileFormat::Pdf => &["pdf"],
+        }
+    }
+
+    /// Reads the width and height of an image from its header
+    ///
+    /// # Arguments
+    /// * `contents` - The bytes of the file
+    ///
+    /// # Returns
+    /// * `Option<Option<(u32, u32)>>` - None if the format is not an image, otherwise the
+    ///   dimensions, or None if the header is damaged
+    fn dimensions(self, contents: &[u8]) -> Option<Option<(u32, u32)>> {
+        match self {
+            FileFormat::Png => Some(png_dimensions(contents)),
+            FileFormat::Jpeg => Some(jpeg_dimensions(contents)),
+            FileFormat::Gif => Some(gif_dimensions(contents)),
+            FileFormat::WebP => Some(webp_dimensions(contents)),
+            FileFormat::Pdf => None,
+        }
+    }
+}
+
+/// Checks that a file's name and contents suit what it is uploaded for
+///
+/// # Arguments
+/// * `kind` - What the file is going to be used for
+/// * `extension` - Extension of the selected file, without the dot
+/// * `contents` - The bytes of the file
+///
+/// # Returns
+/// * `Result<()>` - Success, or error describing why the file cannot be used
+pub fn validate_upload(kind: UploadKind, extension: &str, contents: &[u8]) -> Result<()> {
+    let extension = extension.to_lowercase();
+    let allowed_extensions = kind.allowed_extensions();
+    if !allowed_extensions.contains(&extension.as_str()) {
+        bail!(
+            "Only {} files can be uploaded here",
+            allowed_extensions.join(", ")
+        );
+    }
+
+    // The contents must really be in an allowed format, whatever the file is called
+    let Some(format) =
+        FileFormat::sniff(contents).filter(|format| kind.allowed_formats().contains(format))
+    else {
+        bail!("The file's contents are not in a supported format");
+    };
+    if !format.extensions().contains(&extension.as_str()) {
+        bail!(
+            "The file is named .{} but its contents are .{}",
+            extension,
+            format.extensions()[0]
+        );
+    }
+
+    if let Some(dimensions) = format.dimensions(contents) {
+        let Some((width, height)) = dimensions else {
+            bail!("The image is damaged and its size cannot be read");
+        };
+        if width == 0 || height == 0 {
+            bail!("The image is empty");
+        }
+        if width > MAX_IMAGE_DIMENSION || height > MAX_IMAGE_DIMENSION {
+            bail!(
+                "The image is {}x{} pixels; images can be at most {} pixels wide and tall",
+                width,
+                height,
+                MAX_IMAGE_DIMENSION
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Recognizes the MIME type of a file from its contents
+///
+/// # Arguments
+/// * `contents` - The bytes of the file
+///
+/// # Returns
+/// * `&'static str` - The MIME type, or `application/octet-stream` if the format is not recognized
+pub fn sniff_mime_type(contents: &[u8]) -> &'static str {
+    FileFormat::sniff(contents).map_or("application/octet-stream", FileFormat::mime_type)
+}
+
+/// Reads a big-endian 16-bit number
+fn be_u16(contents: &[u8], offset: usize) -> Option<u32> {
+    let bytes = contents.get(offset..offset + 2)?;
+    Some(u32::from(u16::from_be_bytes([bytes[0], bytes[1]])))
+}
+
+/// Reads a little-endian 16-bit number
+fn le_u16(contents: &[u8], offset: usize) -> Option<u32> {
+    let bytes = contents.get(offset..offset + 2)?;
+    Some(u32::from(u16::from_le_bytes([bytes[0], bytes[1]])))
+}
+
+/// Reads a little-endian 24-bit number
+fn le_u24(contents: &[u8], offset: usize) -> Option<u32> {
+    let bytes = contents.get(offset..offset + 3)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]))
+}
+
+/// Reads the dimensions of a PNG image from its IHDR chunk
+fn png_dimensions(contents: &[u8]) -> Option<(u32, u32)> {
+    if contents.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let width = contents.get(16..20)?;
+    let height = contents.get(20..24)?;
+    Some((
+        u32::from_be_bytes(width.try_into().ok()?),
+        u32::from_be_bytes(height.try_into().ok()?),
+    ))
+}
+
+/// Reads the dimensions of a GIF image from its logical screen descriptor
+fn gif_dimensions(contents: &[u8]) -> Option<(u32, u32)> {
+    Some((le_u16(contents, 6)?, le_u16(contents, 8)?))
+}
+
+/// Reads the dimensions of a JPEG image from its start-of-frame segment
+fn jpeg_dimensions(contents: &[u8]) -> Option<(u32, u32)> {
+    let mut offset = 2;
+    loop {
+        if *contents.get(offset)? != 0xFF {
+            return None;
+        }
+        let marker = *contents.get(offset + 1)?;
+        match marker {
+            // Padding before a marker
+            0xFF => offset += 1,
+            // Markers without a segment
+            0x01 | 0xD0..=0xD7 => offset += 2,
+            // Start of frame, except for the markers that share its range
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                return Some((be_u16(contents, offset + 7)?, be_u16(contents, offset + 5)?));
+            }
+            // The image data starts before any frame was described
+            0xD9 | 0xDA => return None,
+            _ => offset += 2 + be_u16(contents, offset + 2)? as usize,
+        }
+    }
+}
+
+/// Reads the dimensions of a WebP image from its first chunk
+fn webp_dimensions(contents: &[u8]) -> Option<(u32, u32)> {
+    match contents.get(12..16)? {
+        // Lossy image: 14-bit dimensions after the frame tag and start code
+        b"VP8 " => Some((
+            le_u16(contents, 26)? & 0x3FFF,
+            le_u16(contents, 28)? & 0x3FFF,
+        )),
+        // Lossless image: 14-bit dimensions minus one, packed after the signature byte
+        b"VP8L" => {
+            let bits = u32::from_le_bytes(contents.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        // Extended image: 24-bit canvas dimensions minus one
+        b"VP8X" => Some((le_u24(contents, 24)? + 1, le_u24(contents, 27)? + 1)),
+        _ => None,
+    }
+}
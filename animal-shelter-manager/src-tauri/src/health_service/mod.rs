@@ -0,0 +1,91 @@
+//
+// health_service/mod.rs
+//
+// This module judges the state of the application gathered by a health check,
+// such as whether the database answers and how much disk space is left, and
+// warns about anything that needs attention before it causes failures.
+//
+
+use crate::i18n::{
+    self,
+    types::{Locale, Message, MessageKey},
+};
+use types::HealthReport;
+
+mod test;
+pub mod types;
+
+/// Free disk space below which a warning is given, in bytes
+pub const LOW_DISK_SPACE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// How many backup intervals can pass without a backup before a warning is given
+const MISSED_BACKUPS_ALLOWED: i64 = 2;
+
+/// Finds the problems in a health report
+///
+/// # Arguments
+/// * `report` - The state gathered by the health check
+/// * `locale` - The locale to write the warnings in
+///
+/// # Returns
+/// * `Vec<Message>` - A warning for each problem, empty if all is well
+pub fn find_warnings(report: &HealthReport, locale: Locale) -> Vec<Message> {
+    let mut warnings = Vec::new();
+    let mut warn = |key: MessageKey, args: &[(&str, &str)]| {
+        warnings.push(Message {
+            key,
+            text: i18n::format(key, locale, args),
+        });
+    };
+
+    // Database
+    if !report.services.database_service || !report.database_connected {
+        warn(MessageKey::HealthDatabaseUnreachable, &[]);
+    } else if report.schema_version != Some(report.expected_schema_version) {
+        let found = report
+            .schema_version
+            .map(|version| version.to_string())
+            .unwrap_or_default();
+        warn(
+            MessageKey::HealthSchemaMismatch,
+            &[
+                ("found", &found),
+                ("expected", &report.expected_schema_version.to_string()),
+            ],
+        );
+    }
+
+    // Storage
+    if !report.services.file_service {
+        warn(MessageKey::HealthStorageUnavailable, &[]);
+    } else if let Some(available) = report.available_disk_bytes {
+        if available < LOW_DISK_SPACE_BYTES {
+            let megabytes = available / (1024 * 1024);
+            warn(
+                MessageKey::HealthLowDiskSpace,
+                &[("megabytes", &megabytes.to_string())],
+            );
+        }
+    }
+
+    // Backups, unless they are turned off
+    match report.backup_interval_hours {
+        None => warn(MessageKey::HealthSettingsUnavailable, &[]),
+        Some(0) => {}
+        Some(_) if !report.services.file_service => {}
+        Some(interval_hours) => match report.last_backup_timestamp {
+            None => warn(MessageKey::HealthNoBackup, &[]),
+            Some(last_backup) => {
+                let hours_since = (report.checked_timestamp - last_backup) / 3600;
+                if hours_since >= i64::from(interval_hours) * MISSED_BACKUPS_ALLOWED {
+                    warn(
+                        MessageKey::HealthBackupOverdue,
+                        &[("hours", &hours_since.to_string())],
+                    );
+                }
+            }
+        },
+    }
+
+    warnings
+}
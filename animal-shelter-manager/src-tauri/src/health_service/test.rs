@@ -0,0 +1,108 @@
+//
+// health_service/test.rs
+//
+// This file contains unit tests for judging health reports.
+//
+
+#[cfg(test)]
+mod health_service_tests {
+    use crate::health_service::{
+        find_warnings,
+        types::{HealthReport, ServiceStatus},
+        LOW_DISK_SPACE_BYTES,
+    };
+    use crate::i18n::types::{Locale, MessageKey};
+
+    /// Helper function to create the report of a healthy application
+    ///
+    /// # Returns
+    /// * `HealthReport` - Report with every service running and a recent backup
+    fn healthy_report() -> HealthReport {
+        HealthReport {
+            services: ServiceStatus {
+                file_service: true,
+                database_service: true,
+                authentication_service: true,
+                settings_service: true,
+                job_queue: false,
+            },
+            database_connected: true,
+            schema_version: Some(1),
+            expected_schema_version: 1,
+            available_disk_bytes: Some(LOW_DISK_SPACE_BYTES * 10),
+            last_backup_timestamp: Some(1_700_000_000 - 3600),
+            backup_interval_hours: Some(24),
+            warnings: Vec::new(),
+            checked_timestamp: 1_700_000_000,
+        }
+    }
+
+    /// Helper function to list the keys of the warnings for a report
+    ///
+    /// # Arguments
+    /// * `report` - The report to judge
+    ///
+    /// # Returns
+    /// * `Vec<MessageKey>` - The keys of the warnings, in order
+    fn warning_keys(report: &HealthReport) -> Vec<MessageKey> {
+        find_warnings(report, Locale::En)
+            .into_iter()
+            .map(|warning| warning.key)
+            .collect()
+    }
+
+    #[test]
+    fn test_healthy_report_has_no_warnings() {
+        assert!(warning_keys(&healthy_report()).is_empty());
+
+        // Services that have not been needed yet are not a problem
+        let mut report = healthy_report();
+        report.services.authentication_service = false;
+        assert!(warning_keys(&report).is_empty());
+
+        // Neither is a missing backup when backups are turned off
+        report.backup_interval_hours = Some(0);
+        report.last_backup_timestamp = None;
+        assert!(warning_keys(&report).is_empty());
+    }
+
+    #[test]
+    fn test_problems_are_warned_about() {
+        let mut report = healthy_report();
+        report.database_connected = false;
+        report.available_disk_bytes = Some(200 * 1024 * 1024);
+        report.last_backup_timestamp = Some(report.checked_timestamp - 50 * 3600);
+        let warnings = find_warnings(&report, Locale::En);
+        assert_eq!(
+            warnings.iter().map(|w| w.key).collect::<Vec<_>>(),
+            vec![
+                MessageKey::HealthDatabaseUnreachable,
+                MessageKey::HealthLowDiskSpace,
+                MessageKey::HealthBackupOverdue,
+            ]
+        );
+        assert!(warnings[1].text.contains("200 MB"));
+        assert!(warnings[2].text.contains("50 hours"));
+
+        let mut report = healthy_report();
+        report.schema_version = Some(0);
+        report.last_backup_timestamp = None;
+        assert_eq!(
+            warning_keys(&report),
+            vec![MessageKey::HealthSchemaMismatch, MessageKey::HealthNoBackup]
+        );
+
+        // Without storage or settings, disk space and backups cannot be judged
+        let mut report = healthy_report();
+        report.services.file_service = false;
+        report.available_disk_bytes = None;
+        report.backup_interval_hours = None;
+        assert_eq!(
+            warning_keys(&report),
+            vec![
+                MessageKey::HealthStorageUnavailable,
+                MessageKey::HealthSettingsUnavailable,
+            ]
+        );
+    }
+}
@@ -0,0 +1,48 @@
+//
+// health_service/types.rs
+//
+// This module contains the type definitions for health checks.
+//
+
+use crate::i18n::types::Message;
+use serde::{Deserialize, Serialize};
+
+/// Which services have been started
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceStatus {
+    /// Whether the file service is running
+    pub file_service: bool,
+    /// Whether the database service is running
+    pub database_service: bool,
+    /// Whether the authentication service is running
+    pub authentication_service: bool,
+    /// Whether the settings service is running
+    pub settings_service: bool,
+    /// Whether the background job queue is running
+    pub job_queue: bool,
+}
+
+/// The state of the application, with warnings about anything that needs attention
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    /// Which services have been started
+    pub services: ServiceStatus,
+    /// Whether the database answered a query
+    pub database_connected: bool,
+    /// Version of the data migrations applied to the database, None if it could not be read
+    pub schema_version: Option<i64>,
+    /// Version of the data migrations this version of the application applies
+    pub expected_schema_version: i64,
+    /// Free disk space where files are stored, in bytes, None if it could not be read
+    pub available_disk_bytes: Option<u64>,
+    /// Timestamp of the most recent backup, None if there are none
+    pub last_backup_timestamp: Option<i64>,
+    /// Hours between backups set in the settings, None if the settings could not be read
+    pub backup_interval_hours: Option<u32>,
+    /// Problems found, in the locale chosen in the settings; empty if all is well
+    pub warnings: Vec<Message>,
+    /// Timestamp of the check
+    pub checked_timestamp: i64,
+}
@@ -0,0 +1,388 @@
+//
+// http_service/mod.rs
+//
+// This module provides an optional HTTP API for the organization's public website,
+// which lists the animals available for adoption and submits adoption requests
+// straight to the shelter database. It is turned on, and its address chosen, in
+// the settings.
+//
+
+use crate::database_service::{
+    types::{
+        AdoptionRequest, Animal, AnimalStatus, AnimalSummary, CoApplicant, CreateRequestResult,
+        FieldError, FilterCriteria, FilterValue, RequestStatus,
+    },
+    validation, DatabaseService, WEB_INTAKE_USERNAME,
+};
+use crate::file_service::FileService;
+use anyhow::{anyhow, Context, Result};
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+mod test;
+
+/// Callback told the ID of every adoption request received through the API
+pub type RequestCreatedListener = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Services shared by the request handlers
+#[derive(Clone)]
+struct ApiState {
+    /// Service for handling database operations
+    database_service: Arc<Mutex<DatabaseService>>,
+    /// Service for reading animal images
+    file_service: Arc<FileService>,
+    /// Callback told about received adoption requests
+    on_request_created: RequestCreatedListener,
+}
+
+/// An animal available for adoption, as listed on the website
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListedAnimal {
+    /// Unique identifier for the animal
+    pub id: String,
+    /// Name of the animal
+    pub name: String,
+    /// Species of the animal
+    pub specie: String,
+    /// Breed of the animal
+    pub breed: String,
+    /// Sex of the animal
+    pub sex: String,
+    /// Timestamp when the animal was admitted to the shelter
+    pub admission_timestamp: i64,
+    /// URL of the animal's image, relative to the API (None if it has no image)
+    pub image_url: Option<String>,
+}
+
+/// Full description of an animal available for adoption
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListedAnimalDetail {
+    /// The animal as listed
+    #[serde(flatten)]
+    pub animal: ListedAnimal,
+    /// Birth month of the animal, from 1 to 12 (None if unknown)
+    pub birth_month: Option<i32>,
+    /// Birth year of the animal (None if unknown)
+    pub birth_year: Option<i32>,
+    /// Whether the animal is neutered
+    pub neutered: bool,
+    /// Appearance description of the animal
+    pub appearance: String,
+    /// Bio & Characteristics of the animal
+    pub bio: String,
+}
+
+/// An adoption request submitted through the website
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebsiteAdoptionRequest {
+    /// ID of the animal being requested
+    pub animal_id: String,
+    /// Full name of the applicant
+    pub name: String,
+    /// Email address the shelter contacts the applicant at
+    pub email: String,
+    /// Telephone number of the applicant
+    pub tel_number: String,
+    /// Address of the applicant
+    pub address: String,
+    /// Occupation of the applicant
+    pub occupation: String,
+    /// Annual income of the applicant
+    pub annual_income: String,
+    /// Number of people in the applicant's household
+    pub num_people: i32,
+    /// Number of children in the applicant's household
+    pub num_children: i32,
+    /// Country of residence of the applicant
+    pub country: String,
+    /// Second person applying together with the applicant, if any
+    #[serde(default)]
+    pub co_applicant: Option<CoApplicant>,
+}
+
+/// Error returned by the API, sent as a JSON body with a matching status code
+#[derive(Debug)]
+enum ApiError {
+    /// No available animal matches the request
+    NotFound,
+    /// The applicant already has a pending request for the animal
+    Conflict(String),
+    /// The submitted adoption request has invalid fields
+    Invalid(Vec<FieldError>),
+    /// The request could not be handled
+    Internal(anyhow::Error),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        match self {
+            ApiError::NotFound => (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({ "error": "Animal not found" })),
+            )
+                .into_response(),
+            ApiError::Conflict(message) => (
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({ "error": message })),
+            )
+                .into_response(),
+            ApiError::Invalid(fields) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(serde_json::json!({ "error": "Invalid fields", "fields": fields })),
+            )
+                .into_response(),
+            ApiError::Internal(e) => {
+                log::error!("HTTP API request failed: {:#}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({ "error": "Internal server error" })),
+                )
+                    .into_response()
+            }
+        }
+    }
+}
+
+/// Builds the routes of the API
+///
+/// # Arguments
+/// * `database_service` - Service for handling database operations
+/// * `file_service` - Service for reading animal images
+/// * `on_request_created` - Callback told about received adoption requests
+///
+/// # Returns
+/// * `Router` - The API routes
+pub fn router(
+    database_service: Arc<Mutex<DatabaseService>>,
+    file_service: Arc<FileService>,
+    on_request_created: RequestCreatedListener,
+) -> Router {
+    Router::new()
+        .route("/api/animals", get(list_animals))
+        .route("/api/animals/:id", get(get_animal))
+        .route("/api/animals/:id/image", get(get_animal_image))
+        .route("/api/adoption-requests", post(create_adoption_request))
+        .with_state(ApiState {
+            database_service,
+            file_service,
+            on_request_created,
+        })
+}
+
+/// Serves the API until the application exits
+///
+/// # Arguments
+/// * `address` - The address to listen on
+/// * `database_service` - Service for handling database operations
+/// * `file_service` - Service for reading animal images
+/// * `on_request_created` - Callback told about received adoption requests
+///
+/// # Returns
+/// * `Result<()>` - Error if the address cannot be bound or the server fails
+pub async fn serve(
+    address: SocketAddr,
+    database_service: Arc<Mutex<DatabaseService>>,
+    file_service: Arc<FileService>,
+    on_request_created: RequestCreatedListener,
+) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(address)
+        .await
+        .context(format!("Failed to listen on {}", address))?;
+    log::info!("HTTP API listening on {}", address);
+    axum::serve(
+        listener,
+        router(database_service, file_service, on_request_created),
+    )
+    .await
+    .context("HTTP API server failed")
+}
+
+/// Runs a task against the DatabaseService on the blocking thread pool
+///
+/// # Arguments
+/// * `state` - The services shared by the handlers
+/// * `task` - The database work to run
+///
+/// # Returns
+/// * `Result<T, ApiError>` - The result of the database work
+async fn run_database_task<T, F>(state: &ApiState, task: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce(&DatabaseService) -> Result<T> + Send + 'static,
+{
+    let database_service = state.database_service.clone();
+    tokio::task::spawn_blocking(move || {
+        let database_service = database_service
+            .lock()
+            .map_err(|_| anyhow!("Database service lock was poisoned"))?;
+        task(&database_service)
+    })
+    .await
+    .map_err(|e| ApiError::Internal(anyhow!("Database task did not complete: {}", e)))?
+    .map_err(ApiError::Internal)
+}
+
+/// Retrieves an animal if it is available for adoption
+///
+/// # Arguments
+/// * `state` - The services shared by the handlers
+/// * `animal_id` - The ID of the animal
+///
+/// # Returns
+/// * `Result<Animal, ApiError>` - The animal, or `NotFound` if it is not available
+async fn available_animal(state: &ApiState, animal_id: String) -> Result<Animal, ApiError> {
+    match run_database_task(state, move |db| db.query_animal_by_id(&animal_id)).await? {
+        Some(animal) if animal.status == AnimalStatus::Available => Ok(animal),
+        _ => Err(ApiError::NotFound),
+    }
+}
+
+/// Lists the animals available for adoption
+async fn list_animals(State(state): State<ApiState>) -> Result<Json<Vec<ListedAnimal>>, ApiError> {
+    let mut filters = HashMap::new();
+    filters.insert(
+        FilterCriteria::Status,
+        Some(FilterValue::ChooseMany(vec![
+            AnimalStatus::Available.to_string()
+        ])),
+    );
+    let animals = run_database_task(&state, move |db| db.query_animals(Some(filters))).await?;
+    Ok(Json(animals.into_iter().map(listed_animal).collect()))
+}
+
+/// Describes an animal available for adoption
+async fn get_animal(
+    State(state): State<ApiState>,
+    Path(animal_id): Path<String>,
+) -> Result<Json<ListedAnimalDetail>, ApiError> {
+    let animal = available_animal(&state, animal_id).await?;
+    Ok(Json(ListedAnimalDetail {
+        animal: ListedAnimal {
+            image_url: image_url(&animal.id, &animal.image_path),
+            id: animal.id,
+            name: animal.name,
+            specie: animal.specie,
+            breed: animal.breed,
+            sex: animal.sex,
+            admission_timestamp: animal.admission_timestamp,
+        },
+        birth_month: animal.birth_month,
+        birth_year: animal.birth_year,
+        neutered: animal.neutered,
+        appearance: animal.appearance,
+        bio: animal.bio,
+    }))
+}
+
+/// Sends the image of an animal available for adoption
+async fn get_animal_image(
+    State(state): State<ApiState>,
+    Path(animal_id): Path<String>,
+) -> Result<Response, ApiError> {
+    let animal = available_animal(&state, animal_id).await?;
+    let image_path = animal.image_path.ok_or(ApiError::NotFound)?;
+    let contents = state
+        .file_service
+        .read_file(&image_path)
+        .await
+        .map_err(ApiError::Internal)?;
+    Ok((
+        [(header::CONTENT_TYPE, content_type(&image_path))],
+        contents,
+    )
+        .into_response())
+}
+
+/// Files an adoption request submitted through the website, under the web intake
+/// username since the applicant has no account
+async fn create_adoption_request(
+    State(state): State<ApiState>,
+    Json(submitted): Json<WebsiteAdoptionRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    available_animal(&state, submitted.animal_id.clone()).await?;
+
+    let request = AdoptionRequest {
+        id: Uuid::new_v4().to_string(),
+        animal_id: submitted.animal_id,
+        username: WEB_INTAKE_USERNAME.to_string(),
+        name: submitted.name,
+        email: submitted.email,
+        tel_number: submitted.tel_number,
+        address: submitted.address,
+        occupation: submitted.occupation,
+        annual_income: submitted.annual_income,
+        num_people: submitted.num_people,
+        num_children: submitted.num_children,
+        request_timestamp: Utc::now().timestamp(),
+        adoption_timestamp: 0,
+        status: RequestStatus::Pending,
+        country: submitted.country,
+        co_applicant: submitted.co_applicant,
+    };
+    let errors = validation::validate_adoption_request(&request);
+    if !errors.is_empty() {
+        return Err(ApiError::Invalid(errors));
+    }
+
+    let id = request.id.clone();
+    match run_database_task(&state, move |db| db.insert_adoption_request(&request)).await? {
+        CreateRequestResult::Success => {
+            log::info!("Received adoption request {} from the website", id);
+            (state.on_request_created)(&id);
+            Ok((StatusCode::CREATED, Json(serde_json::json!({ "id": id }))))
+        }
+        CreateRequestResult::DuplicatePendingRequest => Err(ApiError::Conflict(
+            "A pending adoption request for this animal already exists".to_string(),
+        )),
+    }
+}
+
+/// Converts an animal summary to its listing
+fn listed_animal(animal: AnimalSummary) -> ListedAnimal {
+    ListedAnimal {
+        image_url: image_url(&animal.id, &animal.image_path),
+        id: animal.id,
+        name: animal.name,
+        specie: animal.specie,
+        breed: animal.breed,
+        sex: animal.sex,
+        admission_timestamp: animal.admission_timestamp,
+    }
+}
+
+/// Builds the URL of an animal's image, if it has one
+fn image_url(animal_id: &str, image_path: &Option<String>) -> Option<String> {
+    image_path
+        .as_ref()
+        .map(|_| format!("/api/animals/{}/image", animal_id))
+}
+
+/// Guesses the content type of an image from its file extension
+fn content_type(image_path: &str) -> &'static str {
+    let extension = std::path::Path::new(image_path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
@@ -0,0 +1,191 @@
+//
+// http_service/test.rs
+//
+// This file contains unit tests for the HTTP API module.
+//
+
+#[cfg(test)]
+mod http_service_tests {
+    use crate::database_service::{
+        types::{Animal, AnimalStatus},
+        DatabaseService, WEB_INTAKE_USERNAME,
+    };
+    use crate::file_service::FileService;
+    use crate::http_service::{router, ListedAnimal};
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Request, StatusCode},
+        Router,
+    };
+    use chrono::Utc;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+
+    /// Helper function to create the API over a fresh database with two animals,
+    /// only the first of which is available
+    ///
+    /// # Arguments
+    /// * `test_name` - Name of the test for unique artifact paths
+    ///
+    /// # Returns
+    /// * `(Router, Arc<Mutex<DatabaseService>>)` - The API routes and the database behind them
+    fn create_test_api(test_name: &str) -> (Router, Arc<Mutex<DatabaseService>>) {
+        let mut root_path = PathBuf::from("test_artifacts/http_service");
+        root_path.push(test_name);
+        if root_path.exists() {
+            fs::remove_dir_all(&root_path).expect("Failed to remove existing test directory");
+        }
+        fs::create_dir_all(&root_path).expect("Failed to create test artifacts directory");
+
+        let database_service = DatabaseService::new(root_path.join("test.db"))
+            .expect("Failed to create test db service");
+        for (id, status) in [
+            ("a1", AnimalStatus::Available),
+            ("a2", AnimalStatus::Adopted),
+        ] {
+            database_service
+                .insert_animal(&Animal {
+                    id: id.to_string(),
+                    name: "Buddy".to_string(),
+                    specie: "Dog".to_string(),
+                    breed: "Golden Retriever".to_string(),
+                    sex: "Male".to_string(),
+                    birth_month: Some(6),
+                    birth_year: Some(2020),
+                    neutered: true,
+                    admission_timestamp: Utc::now().timestamp(),
+                    status,
+                    image_path: None,
+                    appearance: "Golden coat with friendly eyes".to_string(),
+                    bio: "Buddy loves playing fetch.".to_string(),
+                    version: 1,
+                    microchip_number: Some(format!("98511234567890{}", &id[1..])),
+                    location_id: None,
+                    good_with_kids: None,
+                    good_with_dogs: None,
+                    good_with_cats: None,
+                    special_needs: None,
+                    size: None,
+                    color: None,
+                })
+                .expect("Failed to insert test animal");
+        }
+
+        let file_service =
+            FileService::new(&root_path).expect("Failed to create test file service");
+        let database_service = Arc::new(Mutex::new(database_service));
+        let api = router(
+            database_service.clone(),
+            Arc::new(file_service),
+            Arc::new(|_| {}),
+        );
+        (api, database_service)
+    }
+
+    /// Sends a request to the API and returns the status and body
+    async fn send(api: &Router, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+        let response = api.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    /// Builds the JSON body of an adoption request submitted through the website
+    fn website_request(animal_id: &str, email: &str) -> Body {
+        Body::from(
+            serde_json::json!({
+                "animalId": animal_id,
+                "name": "Jira Pit",
+                "email": email,
+                "telNumber": "0123456789",
+                "address": "Bangkok, Thailand",
+                "occupation": "Software Engineer",
+                "annualIncome": "50000",
+                "numPeople": 2,
+                "numChildren": 0,
+                "country": "Thailand"
+            })
+            .to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_list_and_get_available_animals() {
+        let (api, _) = create_test_api("test_list_and_get_available_animals");
+
+        let (status, body) = send(
+            &api,
+            Request::get("/api/animals").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let animals: Vec<ListedAnimal> = serde_json::from_value(body).unwrap();
+        assert_eq!(animals.len(), 1);
+        assert_eq!(animals[0].id, "a1");
+
+        let (status, body) = send(
+            &api,
+            Request::get("/api/animals/a1").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["bio"], "Buddy loves playing fetch.");
+        // Internal details are not published
+        assert!(body.get("microchipNumber").is_none());
+
+        let (status, _) = send(
+            &api,
+            Request::get("/api/animals/a2").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_create_adoption_request() {
+        let (api, database_service) = create_test_api("test_create_adoption_request");
+        let post = |body: Body| {
+            Request::post("/api/adoption-requests")
+                .header("content-type", "application/json")
+                .body(body)
+                .unwrap()
+        };
+
+        let (status, body) = send(&api, post(website_request("a1", "jira.pit@gmail.com"))).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let id = body["id"].as_str().unwrap().to_string();
+
+        // The request is filed under the web intake username, never under an account
+        // named after the email address
+        let request = database_service
+            .lock()
+            .unwrap()
+            .query_adoption_request_by_id(&id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.username, WEB_INTAKE_USERNAME);
+        assert_eq!(request.email, "jira.pit@gmail.com");
+
+        // Website applicants are told apart by their email address
+        let (status, _) = send(&api, post(website_request("a1", " Jira.Pit@gmail.com"))).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, _) = send(&api, post(website_request("a1", "somchai@gmail.com"))).await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, _) = send(&api, post(website_request("a2", "jira.pit@gmail.com"))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let mut invalid: serde_json::Value = serde_json::from_slice(
+            &to_bytes(website_request("a1", "jira.pit@gmail.com"), usize::MAX)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        invalid["email"] = serde_json::Value::from("not an email");
+        let (status, body) = send(&api, post(Body::from(invalid.to_string()))).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["fields"][0]["field"], "email");
+    }
+}
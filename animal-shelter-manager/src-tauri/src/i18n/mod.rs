@@ -0,0 +1,378 @@
+//
+// i18n/mod.rs
+//
+// This module holds the catalog of user-facing text the backend produces,
+// such as error summaries and report headings, in every supported locale.
+// Text is looked up by key, and may contain `{placeholder}`s filled in when
+// it is formatted. The locale admins choose in the settings is kept here so
+// errors can be localized wherever they are returned.
+//
+
+use std::sync::RwLock;
+use strum::IntoEnumIterator;
+use types::{Locale, Message, MessageKey};
+
+mod test;
+pub mod types;
+
+/// The locale chosen in the settings
+static CURRENT_LOCALE: RwLock<Locale> = RwLock::new(Locale::En);
+
+/// Gets the locale chosen in the settings, or English until the settings are read
+pub fn current_locale() -> Locale {
+    match CURRENT_LOCALE.read() {
+        Ok(locale) => *locale,
+        Err(poisoned) => *poisoned.into_inner(),
+    }
+}
+
+/// Changes the locale text is written in
+///
+/// # Arguments
+/// * `locale` - The locale chosen in the settings
+pub fn set_current_locale(locale: Locale) {
+    match CURRENT_LOCALE.write() {
+        Ok(mut current) => *current = locale,
+        Err(poisoned) => *poisoned.into_inner() = locale,
+    }
+    log::info!("Locale set to {}", locale);
+}
+
+/// Gets the text of a key, with its placeholders left in
+///
+/// # Arguments
+/// * `key` - The key of the text
+/// * `locale` - The locale to write the text in
+///
+/// # Returns
+/// * `&'static str` - The text
+pub fn text(key: MessageKey, locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => english(key),
+        Locale::Es => spanish(key),
+    }
+}
+
+/// Gets the text of a key with its placeholders filled in
+///
+/// # Arguments
+/// * `key` - The key of the text
+/// * `locale` - The locale to write the text in
+/// * `args` - The name of each placeholder, without braces, and its value
+///
+/// # Returns
+/// * `String` - The text
+pub fn format(key: MessageKey, locale: Locale, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(text(key, locale).to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+/// Gets the whole catalog in a locale
+///
+/// # Arguments
+/// * `locale` - The locale to write the text in
+///
+/// # Returns
+/// * `Vec<Message>` - Every key with its text, placeholders left in
+pub fn messages(locale: Locale) -> Vec<Message> {
+    MessageKey::iter()
+        .map(|key| Message {
+            key,
+            text: text(key, locale).to_string(),
+        })
+        .collect()
+}
+
+/// Gets the English text of a key
+fn english(key: MessageKey) -> &'static str {
+    match key {
+        MessageKey::ErrorNotFound => "The record could not be found",
+        MessageKey::ErrorValidationFailed => "Some fields are not valid",
+        MessageKey::ErrorPermissionDenied => "You are not allowed to do this",
+        MessageKey::ErrorConflict => "This clashes with existing data",
+        MessageKey::ErrorIo => "A file could not be read or written",
+        MessageKey::ErrorDatabase => "Something went wrong while working with the data",
+        MessageKey::HealthDatabaseUnreachable => "The database cannot be reached",
+        MessageKey::HealthSchemaMismatch => {
+            "The database is at version {found}, but this application expects version {expected}"
+        }
+        MessageKey::HealthStorageUnavailable => "The file storage cannot be opened",
+        MessageKey::HealthLowDiskSpace => "Only {megabytes} MB of disk space is left",
+        MessageKey::HealthSettingsUnavailable => "The settings cannot be read",
+        MessageKey::HealthNoBackup => "No backup has been made yet",
+        MessageKey::HealthBackupOverdue => "The last backup was made {hours} hours ago",
+        MessageKey::CardAge => "Age",
+        MessageKey::CardNeutered => "Neutered",
+        MessageKey::CardTemperament => "Temperament",
+        MessageKey::CardAbout => "About me",
+        MessageKey::CardScanToLookUp => "Scan to look me up",
+        MessageKey::AgeMonths => "{months} months",
+        MessageKey::AgeYears => "{years} years",
+        MessageKey::AgeYearsMonths => "{years} years, {months} months",
+        MessageKey::AgeUnknown => "Unknown",
+        MessageKey::Yes => "Yes",
+        MessageKey::No => "No",
+        MessageKey::CertificateTitle => "Vaccination Certificate",
+        MessageKey::CertificateMicrochip => "Microchip",
+        MessageKey::CertificateVaccine => "Vaccine",
+        MessageKey::CertificateAdministered => "Given on",
+        MessageKey::CertificateNextDue => "Next dose due",
+        MessageKey::CertificateRecordedBy => "Recorded by",
+        MessageKey::CertificateNoVaccinations => "No vaccinations have been recorded",
+        MessageKey::CertificateIssued => "Issued by {shelter} on {date}",
+        MessageKey::CertificateSignature => "Authorized signature",
+        MessageKey::AdoptionCertificateTitle => "Certificate of Adoption",
+        MessageKey::AdoptionCertificateText => {
+            "This certifies that {adopter} adopted {animal} on {date}, giving them a loving forever home."
+        }
+        MessageKey::AdoptionCertificateWelcome => "Welcome home, {animal}!",
+        MessageKey::InvoiceTitle => "Invoice",
+        MessageKey::ReceiptTitle => "Receipt",
+        MessageKey::PaymentNumber => "Number",
+        MessageKey::PaymentDate => "Date",
+        MessageKey::InvoiceBilledTo => "Billed to",
+        MessageKey::ReceiptReceivedFrom => "Received from",
+        MessageKey::PaymentAnimal => "Animal",
+        MessageKey::PaymentDescription => "Description",
+        MessageKey::InvoiceAmountDue => "Amount due",
+        MessageKey::ReceiptAmountPaid => "Amount paid",
+        MessageKey::ReceiptThanks => "Paid in full on {date}. Thank you!",
+        MessageKey::DonationReceiptTitle => "Donation Receipt",
+        MessageKey::DonationAmount => "Amount donated",
+        MessageKey::DonationThanks => {
+            "Thank you for supporting {shelter}. No goods or services were provided in exchange for this donation."
+        }
+        MessageKey::DonorStatementTitle => "Donation Statement {year}",
+        MessageKey::DonorStatementDonor => "Donor",
+        MessageKey::DonorStatementReceipt => "Receipt",
+        MessageKey::DonorStatementTotal => "Total donated in {year}",
+        MessageKey::DonorStatementNoDonations => "No donations were received this year",
+        MessageKey::CalendarVaccinationDue => "{vaccine} vaccination due for {animal}",
+        MessageKey::CalendarFosterReturnDue => "{animal} due back from foster with {caregiver}",
+        MessageKey::CalendarAppointment => "{visitor} meets {animal}",
+        MessageKey::CalendarSurgery => "Spay/neuter surgery for {animal}",
+        MessageKey::CalendarAnimalId => "Animal ID: {id}",
+        MessageKey::ActivityAnimalCreated => "{user} added animal {id}",
+        MessageKey::ActivityAnimalUpdated => "{user} updated animal {id}",
+        MessageKey::ActivityAnimalDeleted => "{user} deleted animal {id}",
+        MessageKey::ActivityRequestApproved => "{user} approved request {id} for {animal}",
+        MessageKey::ActivityTrialStarted => {
+            "{user} started a trial adoption of {animal} for request {id}"
+        }
+        MessageKey::ActivityTrialFinalized => {
+            "{user} finalized the trial adoption of {animal} for request {id}"
+        }
+        MessageKey::ActivityRequestRejected => "{user} rejected request {id} for {animal}",
+        MessageKey::ActivityRequestUpdated => "{user} updated request {id} for {animal}",
+        MessageKey::ActivityRequestDeleted => "{user} deleted request {id} for {animal}",
+        MessageKey::ActivityReturnProcessed => "{user} took back {animal} from adoption",
+        MessageKey::ActivityNoteAdded => "{user} added a note to {animal}",
+        MessageKey::ActivityDeathRecorded => "{user} recorded the passing of {animal}",
+        MessageKey::ActivitySurgeryCompleted => {
+            "{user} recorded the spay/neuter surgery of {animal}"
+        }
+        MessageKey::ActivityInterviewRecorded => {
+            "{user} recorded an interview for request {id} for {animal}"
+        }
+        MessageKey::ActivityReferenceChecked => {
+            "{user} recorded a reference check for request {id} for {animal}"
+        }
+        MessageKey::ActivityPaymentRecorded => "{user} charged a fee for request {id} for {animal}",
+        MessageKey::ActivityPaymentReceived => {
+            "{user} recorded a payment for request {id} for {animal}"
+        }
+        MessageKey::MonthlyReportTitle => "{shelter} Monthly Report - {month}",
+        MessageKey::AnnualReportTitle => "{shelter} Annual Report - {year}",
+        MessageKey::MonthLabel => "{month} {year}",
+        MessageKey::ReportSheet => "Report",
+        MessageKey::ReportMonth => "Month",
+        MessageKey::ReportAdmissions => "Admissions",
+        MessageKey::ReportIntakes => "Intakes",
+        MessageKey::ReportAdoptions => "Adoptions",
+        MessageKey::ReportReturns => "Returns",
+        MessageKey::ReportDeaths => "Deaths",
+        MessageKey::ReportFees => "Fees",
+        MessageKey::ReportDonations => "Donations",
+        MessageKey::ReportTotal => "Total",
+        MessageKey::ReportIntakesByType => "Intakes by type",
+        MessageKey::ReportNoIntakes => "No intakes recorded",
+        MessageKey::ReportDeathsByCause => "Deaths by cause",
+        MessageKey::ReportNoDeaths => "No deaths recorded",
+        MessageKey::ReportRevenue => "Revenue",
+        MessageKey::ReportAmountsIn => "Amounts in {currency}",
+        MessageKey::AnimalsSheet => "Animals",
+        MessageKey::AdoptionRequestsSheet => "Adoption Requests",
+        MessageKey::ColumnId => "ID",
+        MessageKey::ColumnName => "Name",
+        MessageKey::ColumnSpecies => "Species",
+        MessageKey::ColumnBreed => "Breed",
+        MessageKey::ColumnSex => "Sex",
+        MessageKey::ColumnAdmitted => "Admitted",
+        MessageKey::ColumnStatus => "Status",
+        MessageKey::ColumnAnimalId => "Animal ID",
+        MessageKey::ColumnAnimal => "Animal",
+        MessageKey::ColumnUsername => "Username",
+        MessageKey::ColumnEmail => "Email",
+        MessageKey::ColumnRequested => "Requested",
+        MessageKey::ColumnApplicant => "Applicant",
+        MessageKey::ColumnAdopted => "Adopted",
+        MessageKey::ColumnCountry => "Country",
+        MessageKey::ColumnHouseholdSize => "Household size",
+        MessageKey::ColumnChildren => "Children",
+        MessageKey::January => "January",
+        MessageKey::February => "February",
+        MessageKey::March => "March",
+        MessageKey::April => "April",
+        MessageKey::May => "May",
+        MessageKey::June => "June",
+        MessageKey::July => "July",
+        MessageKey::August => "August",
+        MessageKey::September => "September",
+        MessageKey::October => "October",
+        MessageKey::November => "November",
+        MessageKey::December => "December",
+    }
+}
+
+/// Gets the Spanish text of a key
+fn spanish(key: MessageKey) -> &'static str {
+    match key {
+        MessageKey::ErrorNotFound => "No se encontró el registro",
+        MessageKey::ErrorValidationFailed => "Algunos campos no son válidos",
+        MessageKey::ErrorPermissionDenied => "No tiene permiso para hacer esto",
+        MessageKey::ErrorConflict => "Esto entra en conflicto con datos existentes",
+        MessageKey::ErrorIo => "No se pudo leer o escribir un archivo",
+        MessageKey::ErrorDatabase => "Se produjo un error al trabajar con los datos",
+        MessageKey::HealthDatabaseUnreachable => "No se puede acceder a la base de datos",
+        MessageKey::HealthSchemaMismatch => {
+            "La base de datos está en la versión {found}, pero esta aplicación espera la versión {expected}"
+        }
+        MessageKey::HealthStorageUnavailable => "No se puede abrir el almacenamiento de archivos",
+        MessageKey::HealthLowDiskSpace => "Solo quedan {megabytes} MB de espacio en disco",
+        MessageKey::HealthSettingsUnavailable => "No se puede leer la configuración",
+        MessageKey::HealthNoBackup => "Todavía no se ha hecho ninguna copia de seguridad",
+        MessageKey::HealthBackupOverdue => {
+            "La última copia de seguridad se hizo hace {hours} horas"
+        }
+        MessageKey::CardAge => "Edad",
+        MessageKey::CardNeutered => "Esterilizado",
+        MessageKey::CardTemperament => "Temperamento",
+        MessageKey::CardAbout => "Sobre mí",
+        MessageKey::CardScanToLookUp => "Escanéame para ver mi ficha",
+        MessageKey::AgeMonths => "{months} meses",
+        MessageKey::AgeYears => "{years} años",
+        MessageKey::AgeYearsMonths => "{years} años y {months} meses",
+        MessageKey::AgeUnknown => "Desconocida",
+        MessageKey::Yes => "Sí",
+        MessageKey::No => "No",
+        MessageKey::CertificateTitle => "Certificado de vacunación",
+        MessageKey::CertificateMicrochip => "Microchip",
+        MessageKey::CertificateVaccine => "Vacuna",
+        MessageKey::CertificateAdministered => "Administrada el",
+        MessageKey::CertificateNextDue => "Próxima dosis",
+        MessageKey::CertificateRecordedBy => "Registrada por",
+        MessageKey::CertificateNoVaccinations => "No se ha registrado ninguna vacuna",
+        MessageKey::CertificateIssued => "Expedido por {shelter} el {date}",
+        MessageKey::CertificateSignature => "Firma autorizada",
+        MessageKey::AdoptionCertificateTitle => "Certificado de adopción",
+        MessageKey::AdoptionCertificateText => {
+            "Se certifica que {adopter} adoptó a {animal} el {date}, dándole un hogar lleno de cariño para siempre."
+        }
+        MessageKey::AdoptionCertificateWelcome => "¡Bienvenido a casa, {animal}!",
+        MessageKey::InvoiceTitle => "Factura",
+        MessageKey::ReceiptTitle => "Recibo",
+        MessageKey::PaymentNumber => "Número",
+        MessageKey::PaymentDate => "Fecha",
+        MessageKey::InvoiceBilledTo => "Facturado a",
+        MessageKey::ReceiptReceivedFrom => "Recibido de",
+        MessageKey::PaymentAnimal => "Animal",
+        MessageKey::PaymentDescription => "Concepto",
+        MessageKey::InvoiceAmountDue => "Importe a pagar",
+        MessageKey::ReceiptAmountPaid => "Importe pagado",
+        MessageKey::ReceiptThanks => "Pagado en su totalidad el {date}. ¡Gracias!",
+        MessageKey::DonationReceiptTitle => "Recibo de donación",
+        MessageKey::DonationAmount => "Importe donado",
+        MessageKey::DonationThanks => {
+            "Gracias por apoyar a {shelter}. No se entregaron bienes ni servicios a cambio de esta donación."
+        }
+        MessageKey::DonorStatementTitle => "Certificado de donaciones {year}",
+        MessageKey::DonorStatementDonor => "Donante",
+        MessageKey::DonorStatementReceipt => "Recibo",
+        MessageKey::DonorStatementTotal => "Total donado en {year}",
+        MessageKey::DonorStatementNoDonations => "No se recibió ninguna donación este año",
+        MessageKey::CalendarVaccinationDue => "Vacuna {vaccine} pendiente para {animal}",
+        MessageKey::CalendarFosterReturnDue => "{animal} vuelve de acogida con {caregiver}",
+        MessageKey::CalendarAppointment => "{visitor} conoce a {animal}",
+        MessageKey::CalendarSurgery => "Esterilización de {animal}",
+        MessageKey::CalendarAnimalId => "ID del animal: {id}",
+        MessageKey::ActivityAnimalCreated => "{user} añadió el animal {id}",
+        MessageKey::ActivityAnimalUpdated => "{user} actualizó el animal {id}",
+        MessageKey::ActivityAnimalDeleted => "{user} eliminó el animal {id}",
+        MessageKey::ActivityRequestApproved => "{user} aprobó la solicitud {id} para {animal}",
+        MessageKey::ActivityTrialStarted => "{user} inició una adopción de prueba de {animal} para la solicitud {id}",
+        MessageKey::ActivityTrialFinalized => "{user} finalizó la adopción de prueba de {animal} para la solicitud {id}",
+        MessageKey::ActivityRequestRejected => "{user} rechazó la solicitud {id} para {animal}",
+        MessageKey::ActivityRequestUpdated => "{user} actualizó la solicitud {id} para {animal}",
+        MessageKey::ActivityRequestDeleted => "{user} eliminó la solicitud {id} para {animal}",
+        MessageKey::ActivityReturnProcessed => "{user} recibió de vuelta a {animal} tras su adopción",
+        MessageKey::ActivityNoteAdded => "{user} añadió una nota a {animal}",
+        MessageKey::ActivityDeathRecorded => "{user} registró el fallecimiento de {animal}",
+        MessageKey::ActivitySurgeryCompleted => "{user} registró la esterilización de {animal}",
+        MessageKey::ActivityInterviewRecorded => "{user} registró una entrevista para la solicitud {id} para {animal}",
+        MessageKey::ActivityReferenceChecked => "{user} registró una comprobación de referencias para la solicitud {id} para {animal}",
+        MessageKey::ActivityPaymentRecorded => "{user} cobró una tarifa por la solicitud {id} para {animal}",
+        MessageKey::ActivityPaymentReceived => "{user} registró un pago de la solicitud {id} para {animal}",
+        MessageKey::MonthlyReportTitle => "{shelter} - Informe mensual - {month}",
+        MessageKey::AnnualReportTitle => "{shelter} - Informe anual - {year}",
+        MessageKey::MonthLabel => "{month} de {year}",
+        MessageKey::ReportSheet => "Informe",
+        MessageKey::ReportMonth => "Mes",
+        MessageKey::ReportAdmissions => "Ingresos",
+        MessageKey::ReportIntakes => "Entradas",
+        MessageKey::ReportAdoptions => "Adopciones",
+        MessageKey::ReportReturns => "Devoluciones",
+        MessageKey::ReportDeaths => "Decesos",
+        MessageKey::ReportFees => "Tarifas",
+        MessageKey::ReportDonations => "Donaciones",
+        MessageKey::ReportTotal => "Total",
+        MessageKey::ReportIntakesByType => "Entradas por tipo",
+        MessageKey::ReportNoIntakes => "No se registraron entradas",
+        MessageKey::ReportDeathsByCause => "Decesos por causa",
+        MessageKey::ReportNoDeaths => "No se registraron decesos",
+        MessageKey::ReportRevenue => "Recaudación",
+        MessageKey::ReportAmountsIn => "Importes en {currency}",
+        MessageKey::AnimalsSheet => "Animales",
+        MessageKey::AdoptionRequestsSheet => "Solicitudes de adopción",
+        MessageKey::ColumnId => "ID",
+        MessageKey::ColumnName => "Nombre",
+        MessageKey::ColumnSpecies => "Especie",
+        MessageKey::ColumnBreed => "Raza",
+        MessageKey::ColumnSex => "Sexo",
+        MessageKey::ColumnAdmitted => "Ingresado",
+        MessageKey::ColumnStatus => "Estado",
+        MessageKey::ColumnAnimalId => "ID del animal",
+        MessageKey::ColumnAnimal => "Animal",
+        MessageKey::ColumnUsername => "Usuario",
+        MessageKey::ColumnEmail => "Correo electrónico",
+        MessageKey::ColumnRequested => "Solicitado",
+        MessageKey::ColumnApplicant => "Solicitante",
+        MessageKey::ColumnAdopted => "Adoptado",
+        MessageKey::ColumnCountry => "País",
+        MessageKey::ColumnHouseholdSize => "Tamaño del hogar",
+        MessageKey::ColumnChildren => "Niños",
+        MessageKey::January => "enero",
+        MessageKey::February => "febrero",
+        MessageKey::March => "marzo",
+        MessageKey::April => "abril",
+        MessageKey::May => "mayo",
+        MessageKey::June => "junio",
+        MessageKey::July => "julio",
+        MessageKey::August => "agosto",
+        MessageKey::September => "septiembre",
+        MessageKey::October => "octubre",
+        MessageKey::November => "noviembre",
+        MessageKey::December => "diciembre",
+    }
+}
@@ -0,0 +1,73 @@
+//
+// i18n/test.rs
+//
+// This file contains unit tests for the message catalog.
+//
+
+#[cfg(test)]
+mod i18n_tests {
+    use crate::i18n::{
+        format, messages, text,
+        types::{Locale, MessageKey},
+    };
+    use strum::IntoEnumIterator;
+
+    /// Helper function to list the placeholders of a text
+    ///
+    /// # Arguments
+    /// * `text` - The text to search
+    ///
+    /// # Returns
+    /// * `Vec<&str>` - The placeholders, braces included, sorted
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut found: Vec<&str> = text
+            .match_indices('{')
+            .filter_map(|(start, _)| {
+                let end = text[start..].find('}')?;
+                Some(&text[start..=start + end])
+            })
+            .collect();
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn test_every_locale_has_every_message() {
+        for locale in Locale::iter() {
+            assert_eq!(messages(locale).len(), MessageKey::iter().count());
+            for key in MessageKey::iter() {
+                let translated = text(key, locale);
+                assert!(!translated.is_empty(), "{} has no text in {}", key, locale);
+                assert_eq!(
+                    placeholders(translated),
+                    placeholders(text(key, Locale::En)),
+                    "{} has different placeholders in {}",
+                    key,
+                    locale
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        let args = [("shelter", "Happy Paws"), ("year", "2024")];
+        assert_eq!(
+            format(MessageKey::AnnualReportTitle, Locale::En, &args),
+            "Happy Paws Annual Report - 2024"
+        );
+        assert_eq!(
+            format(MessageKey::AnnualReportTitle, Locale::Es, &args),
+            "Happy Paws - Informe anual - 2024"
+        );
+
+        assert_eq!(MessageKey::month(1), Some(MessageKey::January));
+        assert_eq!(MessageKey::month(12), Some(MessageKey::December));
+        assert_eq!(MessageKey::month(0), None);
+        assert_eq!(MessageKey::month(13), None);
+
+        assert_eq!("es".parse::<Locale>().unwrap(), Locale::Es);
+        assert!("xx".parse::<Locale>().is_err());
+        assert_eq!(MessageKey::ErrorNotFound.to_string(), "error-not-found");
+    }
+}
@@ -0,0 +1,364 @@
+//
+// i18n/types.rs
+//
+// This module contains the type definitions for localization: the languages
+// the backend can write user-facing text in, and the keys of that text.
+//
+
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString};
+
+/// A language user-facing text can be written in
+///
+/// Every locale must be writable with the built-in PDF fonts, which only cover
+/// Western European characters.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    EnumIter,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum Locale {
+    /// English
+    #[default]
+    En,
+    /// Spanish
+    Es,
+}
+
+/// Key of a piece of user-facing text, returned alongside the text so the
+/// frontend can tell messages apart without reading them
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString, EnumIter,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum MessageKey {
+    // Error summaries, one for each kind of command error
+    /// Summary of an error about a record that does not exist
+    ErrorNotFound,
+    /// Summary of an error about a submitted record with invalid fields
+    ErrorValidationFailed,
+    /// Summary of an error about an action the user is not allowed to take
+    ErrorPermissionDenied,
+    /// Summary of an error about a command that clashes with the current state
+    ErrorConflict,
+    /// Summary of an error about a file that could not be read or written
+    ErrorIo,
+    /// Summary of an error from the database or another service
+    ErrorDatabase,
+
+    // Health warnings
+    /// Warning that the database could not be opened or queried
+    HealthDatabaseUnreachable,
+    /// Warning that the database was migrated by another version of the application,
+    /// with `{found}` and `{expected}` placeholders
+    HealthSchemaMismatch,
+    /// Warning that the file storage could not be opened
+    HealthStorageUnavailable,
+    /// Warning that disk space is running low, with a `{megabytes}` placeholder
+    HealthLowDiskSpace,
+    /// Warning that the settings could not be read
+    HealthSettingsUnavailable,
+    /// Warning that no backup has been made
+    HealthNoBackup,
+    /// Warning that the last backup is old, with an `{hours}` placeholder
+    HealthBackupOverdue,
+
+    // Kennel cards
+    /// Label of an animal's age on a kennel card
+    CardAge,
+    /// Label of whether an animal is neutered on a kennel card
+    CardNeutered,
+    /// Heading of the behavior notes on a kennel card
+    CardTemperament,
+    /// Heading of an animal's bio on a kennel card
+    CardAbout,
+    /// Caption of the QR code on a kennel card
+    CardScanToLookUp,
+    /// Age of an animal younger than a year, with a `{months}` placeholder
+    AgeMonths,
+    /// Age of an animal in whole years, with a `{years}` placeholder
+    AgeYears,
+    /// Age of an animal in years and months, with `{years}` and `{months}` placeholders
+    AgeYearsMonths,
+    /// Age of an animal whose birth date is unknown
+    AgeUnknown,
+    /// An affirmative answer
+    Yes,
+    /// A negative answer
+    No,
+
+    // Vaccination and adoption certificates
+    /// Title of a vaccination certificate
+    CertificateTitle,
+    /// Label of an animal's microchip number on a vaccination certificate
+    CertificateMicrochip,
+    /// Heading of the vaccine column on a vaccination certificate
+    CertificateVaccine,
+    /// Heading of the date a vaccine was given on a vaccination certificate
+    CertificateAdministered,
+    /// Heading of the date the next dose is due on a vaccination certificate
+    CertificateNextDue,
+    /// Heading of who recorded a vaccine on a vaccination certificate
+    CertificateRecordedBy,
+    /// Shown on a vaccination certificate when no vaccinations have been recorded
+    CertificateNoVaccinations,
+    /// Line stating who issued a certificate, with `{shelter}` and `{date}` placeholders
+    CertificateIssued,
+    /// Caption of the signature line on a certificate
+    CertificateSignature,
+    /// Title of an adoption certificate
+    AdoptionCertificateTitle,
+    /// Dedication of an adoption certificate, with `{adopter}`, `{animal}` and `{date}` placeholders
+    AdoptionCertificateText,
+    /// Closing line of an adoption certificate, with an `{animal}` placeholder
+    AdoptionCertificateWelcome,
+
+    // Invoices and receipts
+    /// Title of an invoice for an adoption fee
+    InvoiceTitle,
+    /// Title of a receipt for an adoption fee
+    ReceiptTitle,
+    /// Label of the number of an invoice or receipt
+    PaymentNumber,
+    /// Label of the date an invoice or receipt is issued on
+    PaymentDate,
+    /// Label of who an invoice is addressed to
+    InvoiceBilledTo,
+    /// Label of who paid the fee on a receipt
+    ReceiptReceivedFrom,
+    /// Label of the adopted animal on an invoice or receipt
+    PaymentAnimal,
+    /// Label of what the fee is for on an invoice or receipt
+    PaymentDescription,
+    /// Label of the amount left to pay on an invoice
+    InvoiceAmountDue,
+    /// Label of the amount paid on a receipt
+    ReceiptAmountPaid,
+    /// Closing line of a receipt, with a `{date}` placeholder for when the fee was paid
+    ReceiptThanks,
+
+    // Donation receipts and statements
+    /// Title of a receipt for a donation
+    DonationReceiptTitle,
+    /// Label of the amount donated on a donation receipt or statement
+    DonationAmount,
+    /// Closing line of a donation receipt, with a `{shelter}` placeholder
+    DonationThanks,
+    /// Title of a donor's yearly statement, with a `{year}` placeholder
+    DonorStatementTitle,
+    /// Label of the donor on a yearly statement
+    DonorStatementDonor,
+    /// Heading of the receipt number column on a yearly statement
+    DonorStatementReceipt,
+    /// Label of the total donated on a yearly statement, with a `{year}` placeholder
+    DonorStatementTotal,
+    /// Shown on a yearly statement when the donor made no donations that year
+    DonorStatementNoDonations,
+
+    // Calendar events
+    /// Title of a vaccination coming due, with `{vaccine}` and `{animal}` placeholders
+    CalendarVaccinationDue,
+    /// Title of a fostered animal due back, with `{animal}` and `{caregiver}` placeholders
+    CalendarFosterReturnDue,
+    /// Title of a meet-and-greet appointment, with `{animal}` and `{visitor}` placeholders
+    CalendarAppointment,
+    /// Title of a spay or neuter surgery, with an `{animal}` placeholder
+    CalendarSurgery,
+    /// Description of a calendar event, with an `{id}` placeholder for the animal's ID
+    CalendarAnimalId,
+
+    // Activity feed, with `{user}` placeholders for who acted
+    /// An animal was added, with an `{id}` placeholder for the animal's ID
+    ActivityAnimalCreated,
+    /// An animal was edited, with an `{id}` placeholder for the animal's ID
+    ActivityAnimalUpdated,
+    /// An animal was deleted, with an `{id}` placeholder for the animal's ID
+    ActivityAnimalDeleted,
+    /// An adoption request was approved, with `{id}` and `{animal}` placeholders
+    ActivityRequestApproved,
+    /// An adoption request was approved for a trial, with `{id}` and `{animal}` placeholders
+    ActivityTrialStarted,
+    /// A trial adoption was made final, with `{id}` and `{animal}` placeholders
+    ActivityTrialFinalized,
+    /// An adoption request was rejected, with `{id}` and `{animal}` placeholders
+    ActivityRequestRejected,
+    /// An adoption request was edited, with `{id}` and `{animal}` placeholders
+    ActivityRequestUpdated,
+    /// An adoption request was deleted, with `{id}` and `{animal}` placeholders
+    ActivityRequestDeleted,
+    /// An adopted animal was taken back, with an `{animal}` placeholder
+    ActivityReturnProcessed,
+    /// A note was added to an animal, with an `{animal}` placeholder
+    ActivityNoteAdded,
+    /// An animal was recorded as passed away, with an `{animal}` placeholder
+    ActivityDeathRecorded,
+    /// An animal's spay or neuter surgery was performed, with an `{animal}` placeholder
+    ActivitySurgeryCompleted,
+    /// A phone interview was recorded for an adoption request, with `{id}` and `{animal}`
+    /// placeholders
+    ActivityInterviewRecorded,
+    /// A reference check was recorded for an adoption request, with `{id}` and `{animal}`
+    /// placeholders
+    ActivityReferenceChecked,
+    /// A fee was charged for an adoption request, with `{id}` and `{animal}` placeholders
+    ActivityPaymentRecorded,
+    /// A fee charged for an adoption request was paid, with `{id}` and `{animal}`
+    /// placeholders
+    ActivityPaymentReceived,
+
+    // Report headings
+    /// Title of a monthly report, with `{shelter}` and `{month}` placeholders
+    MonthlyReportTitle,
+    /// Title of an annual report, with `{shelter}` and `{year}` placeholders
+    AnnualReportTitle,
+    /// Month and year of a report, with `{month}` and `{year}` placeholders
+    MonthLabel,
+    /// Name of the worksheet holding a report
+    ReportSheet,
+    /// Heading of the month column of a report
+    ReportMonth,
+    /// Heading of the admissions column of a report
+    ReportAdmissions,
+    /// Heading of the intakes column of a report
+    ReportIntakes,
+    /// Heading of the adoptions column of a report
+    ReportAdoptions,
+    /// Heading of the returns column of a report
+    ReportReturns,
+    /// Heading of the deaths column of a report
+    ReportDeaths,
+    /// Heading of the adoption fees column of a report
+    ReportFees,
+    /// Heading of the donations column of a report
+    ReportDonations,
+    /// Label of the total row of a report
+    ReportTotal,
+    /// Heading of a monthly report's intakes broken down by type
+    ReportIntakesByType,
+    /// Shown instead of the breakdown when a month has no intakes
+    ReportNoIntakes,
+    /// Heading of a monthly report's deaths broken down by cause
+    ReportDeathsByCause,
+    /// Shown instead of the breakdown when a month has no deaths
+    ReportNoDeaths,
+    /// Heading of a monthly report's revenue
+    ReportRevenue,
+    /// Note on the currency of a report's amounts, with a `{currency}` placeholder
+    ReportAmountsIn,
+
+    // Workbook headings
+    /// Name of the worksheet listing animals
+    AnimalsSheet,
+    /// Name of the worksheet listing adoption requests
+    AdoptionRequestsSheet,
+    /// Heading of an ID column
+    ColumnId,
+    /// Heading of a name column
+    ColumnName,
+    /// Heading of a species column
+    ColumnSpecies,
+    /// Heading of a breed column
+    ColumnBreed,
+    /// Heading of a sex column
+    ColumnSex,
+    /// Heading of an admission date column
+    ColumnAdmitted,
+    /// Heading of a status column
+    ColumnStatus,
+    /// Heading of an animal ID column
+    ColumnAnimalId,
+    /// Heading of an animal name column
+    ColumnAnimal,
+    /// Heading of a username column
+    ColumnUsername,
+    /// Heading of an email address column
+    ColumnEmail,
+    /// Heading of a request date column
+    ColumnRequested,
+    /// Heading of a column of hashes standing in for applicants
+    ColumnApplicant,
+    /// Heading of an adoption date column
+    ColumnAdopted,
+    /// Heading of a country column
+    ColumnCountry,
+    /// Heading of a household size column
+    ColumnHouseholdSize,
+    /// Heading of a number of children column
+    ColumnChildren,
+
+    // Month names
+    /// The first month of the year
+    January,
+    /// The second month of the year
+    February,
+    /// The third month of the year
+    March,
+    /// The fourth month of the year
+    April,
+    /// The fifth month of the year
+    May,
+    /// The sixth month of the year
+    June,
+    /// The seventh month of the year
+    July,
+    /// The eighth month of the year
+    August,
+    /// The ninth month of the year
+    September,
+    /// The tenth month of the year
+    October,
+    /// The eleventh month of the year
+    November,
+    /// The twelfth month of the year
+    December,
+}
+
+impl MessageKey {
+    /// Gets the key of a month's name
+    ///
+    /// # Arguments
+    /// * `month` - The month, from 1 to 12
+    ///
+    /// # Returns
+    /// * `Option<MessageKey>` - The key, or None if the month is out of range
+    pub fn month(month: u32) -> Option<Self> {
+        const MONTHS: [MessageKey; 12] = [
+            MessageKey::January,
+            MessageKey::February,
+            MessageKey::March,
+            MessageKey::April,
+            MessageKey::May,
+            MessageKey::June,
+            MessageKey::July,
+            MessageKey::August,
+            MessageKey::September,
+            MessageKey::October,
+            MessageKey::November,
+            MessageKey::December,
+        ];
+        let index = usize::try_from(month).ok()?.checked_sub(1)?;
+        MONTHS.get(index).copied()
+    }
+}
+
+/// A piece of user-facing text with its key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+    /// Key of the text
+    pub key: MessageKey,
+    /// The text, in the current locale
+    pub text: String,
+}
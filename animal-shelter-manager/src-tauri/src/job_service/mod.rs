@@ -0,0 +1,270 @@
+//
+// job_service/mod.rs
+//
+// This module runs long operations such as exports and bulk imports as
+// background jobs, so the command that starts one returns right away. Jobs are
+// worked on one at a time by a worker task, in the order they were queued, and
+// every change in a job's status or progress is reported to a listener.
+//
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use types::{Job, JobKind, JobStatus};
+use uuid::Uuid;
+
+mod test;
+pub mod types;
+
+/// How many finished jobs are kept for their results to be read
+const MAX_FINISHED_JOBS: usize = 50;
+
+/// Callback told about every change to a job
+pub type JobUpdatedListener = Arc<dyn Fn(&Job) + Send + Sync>;
+
+/// The work of a job, producing its result
+pub type JobFuture = Pin<Box<dyn Future<Output = Result<serde_json::Value>> + Send>>;
+
+/// Starts the work of a job once the worker reaches it
+type JobTask = Box<dyn FnOnce(JobProgress) -> JobFuture + Send>;
+
+/// Queue of background jobs, shared between the commands and the worker
+#[derive(Clone)]
+pub struct JobQueue {
+    /// Every job still queued or running, and the most recently finished ones
+    jobs: Arc<Mutex<Vec<Job>>>,
+    /// Hands queued jobs to the worker
+    sender: mpsc::UnboundedSender<(String, JobTask)>,
+    /// Called whenever a job changes
+    on_updated: JobUpdatedListener,
+}
+
+/// Works through the jobs of a queue, one at a time
+pub struct JobWorker {
+    /// The queue the jobs come from
+    queue: JobQueue,
+    /// Receives the queued jobs
+    receiver: mpsc::UnboundedReceiver<(String, JobTask)>,
+}
+
+/// Lets a running job report how far along it is
+#[derive(Clone)]
+pub struct JobProgress {
+    /// The queue the job belongs to, or None if the work runs outside the queue
+    queue: Option<JobQueue>,
+    /// ID of the job
+    job_id: String,
+}
+
+impl JobQueue {
+    /// Creates an empty queue and the worker for its jobs, which must be run for any
+    /// job to start
+    ///
+    /// # Arguments
+    /// * `on_updated` - Called whenever a job is queued, makes progress or finishes
+    ///
+    /// # Returns
+    /// * `(JobQueue, JobWorker)` - The queue and its worker
+    pub fn new(on_updated: JobUpdatedListener) -> (Self, JobWorker) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let queue = Self {
+            jobs: Arc::new(Mutex::new(Vec::new())),
+            sender,
+            on_updated,
+        };
+        let worker = JobWorker {
+            queue: queue.clone(),
+            receiver,
+        };
+        (queue, worker)
+    }
+
+    /// Queues a job behind any others
+    ///
+    /// # Arguments
+    /// * `kind` - The kind of work the job does
+    /// * `username` - The user queuing the job
+    /// * `task` - Starts the work, given a handle for reporting progress
+    ///
+    /// # Returns
+    /// * `Result<Job>` - The queued job, or error if the worker has stopped
+    pub fn enqueue<F>(&self, kind: JobKind, username: &str, task: F) -> Result<Job>
+    where
+        F: FnOnce(JobProgress) -> JobFuture + Send + 'static,
+    {
+        let job = Job {
+            id: Uuid::new_v4().to_string(),
+            kind,
+            username: username.to_string(),
+            status: JobStatus::Queued,
+            progress: 0,
+            message: "Waiting to start".to_string(),
+            result: None,
+            error: None,
+            created_timestamp: Utc::now().timestamp(),
+            finished_timestamp: None,
+        };
+
+        self.lock_jobs()?.push(job.clone());
+        if self.sender.send((job.id.clone(), Box::new(task))).is_err() {
+            self.lock_jobs()?.retain(|queued| queued.id != job.id);
+            return Err(anyhow!("The job worker has stopped"));
+        }
+
+        log::info!("Queued {:?} job {} for {}", kind, job.id, username);
+        (self.on_updated)(&job);
+        Ok(job)
+    }
+
+    /// Gets a job by ID
+    ///
+    /// # Arguments
+    /// * `job_id` - The ID of the job
+    ///
+    /// # Returns
+    /// * `Result<Option<Job>>` - The job, or None if it is unknown or was finished long ago
+    pub fn job(&self, job_id: &str) -> Result<Option<Job>> {
+        Ok(self
+            .lock_jobs()?
+            .iter()
+            .find(|job| job.id == job_id)
+            .cloned())
+    }
+
+    /// Lists the jobs queued by a user, most recent first
+    ///
+    /// # Arguments
+    /// * `username` - The user who queued the jobs
+    ///
+    /// # Returns
+    /// * `Result<Vec<Job>>` - The jobs still queued or running, and those finished recently
+    pub fn jobs_of(&self, username: &str) -> Result<Vec<Job>> {
+        Ok(self
+            .lock_jobs()?
+            .iter()
+            .rev()
+            .filter(|job| job.username == username)
+            .cloned()
+            .collect())
+    }
+
+    /// Changes a job and tells the listener about it
+    ///
+    /// # Arguments
+    /// * `job_id` - The ID of the job
+    /// * `change` - The change to make
+    fn update(&self, job_id: &str, change: impl FnOnce(&mut Job)) {
+        let updated = match self.lock_jobs() {
+            Ok(mut jobs) => {
+                let updated = jobs.iter_mut().find(|job| job.id == job_id).map(|job| {
+                    change(job);
+                    job.clone()
+                });
+                prune_finished(&mut jobs);
+                updated
+            }
+            Err(e) => {
+                log::error!("Failed to update job {}: {}", job_id, e);
+                None
+            }
+        };
+        if let Some(job) = updated {
+            (self.on_updated)(&job);
+        }
+    }
+
+    /// Locks the list of jobs
+    fn lock_jobs(&self) -> Result<std::sync::MutexGuard<'_, Vec<Job>>> {
+        self.jobs
+            .lock()
+            .map_err(|_| anyhow!("Job list lock was poisoned"))
+    }
+}
+
+impl JobWorker {
+    /// Works through queued jobs until the queue is dropped
+    pub async fn run(mut self) {
+        while let Some((job_id, task)) = self.receiver.recv().await {
+            self.queue.update(&job_id, |job| {
+                job.status = JobStatus::Running;
+                job.message = "Starting".to_string();
+            });
+
+            let progress = JobProgress {
+                queue: Some(self.queue.clone()),
+                job_id: job_id.clone(),
+            };
+            let outcome = task(progress).await;
+
+            let finished_timestamp = Some(Utc::now().timestamp());
+            match outcome {
+                Ok(result) => {
+                    log::info!("Job {} completed", job_id);
+                    self.queue.update(&job_id, |job| {
+                        job.status = JobStatus::Completed;
+                        job.progress = 100;
+                        job.message = "Done".to_string();
+                        job.result = Some(result);
+                        job.finished_timestamp = finished_timestamp;
+                    });
+                }
+                Err(e) => {
+                    log::error!("Job {} failed: {:#}", job_id, e);
+                    self.queue.update(&job_id, |job| {
+                        job.status = JobStatus::Failed;
+                        job.message = "Failed".to_string();
+                        job.error = Some(format!("{:#}", e));
+                        job.finished_timestamp = finished_timestamp;
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl JobProgress {
+    /// Creates a handle for work run directly rather than queued, whose progress
+    /// reports go nowhere
+    ///
+    /// # Returns
+    /// * `JobProgress` - A handle that ignores every report
+    pub fn detached() -> Self {
+        Self {
+            queue: None,
+            job_id: String::new(),
+        }
+    }
+
+    /// Reports how far along the job is
+    ///
+    /// # Arguments
+    /// * `progress` - How far along the job is, from 0 to 100
+    /// * `message` - What the job is doing now
+    pub fn report(&self, progress: u8, message: &str) {
+        if let Some(queue) = &self.queue {
+            queue.update(&self.job_id, |job| {
+                job.progress = progress.min(100);
+                job.message = message.to_string();
+            });
+        }
+    }
+}
+
+/// Drops the oldest finished jobs beyond the number that are kept
+///
+/// # Arguments
+/// * `jobs` - The jobs, oldest first
+fn prune_finished(jobs: &mut Vec<Job>) {
+    let finished_count = jobs.iter().filter(|job| job.status.is_finished()).count();
+    let mut excess = finished_count.saturating_sub(MAX_FINISHED_JOBS);
+    jobs.retain(|job| {
+        if excess > 0 && job.status.is_finished() {
+            excess -= 1;
+            return false;
+        }
+        true
+    });
+}
@@ -0,0 +1,138 @@
+//
+// job_service/test.rs
+//
+// This file contains unit tests for the background job queue.
+//
+
+#[cfg(test)]
+mod job_service_tests {
+    use crate::job_service::{
+        types::{Job, JobKind, JobStatus},
+        JobProgress, JobQueue, JobUpdatedListener,
+    };
+    use anyhow::anyhow;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Creates a queue that records every update, with its worker running
+    fn start_test_queue() -> (JobQueue, Arc<Mutex<Vec<Job>>>) {
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let recorded = updates.clone();
+        let on_updated: JobUpdatedListener =
+            Arc::new(move |job: &Job| recorded.lock().unwrap().push(job.clone()));
+        let (queue, worker) = JobQueue::new(on_updated);
+        tokio::spawn(worker.run());
+        (queue, updates)
+    }
+
+    /// Waits for a job to finish, failing the test if it takes too long
+    async fn wait_for(queue: &JobQueue, job_id: &str) -> Job {
+        for _ in 0..500 {
+            let job = queue.job(job_id).unwrap().expect("Job should be known");
+            if job.status.is_finished() {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("Job {} did not finish", job_id);
+    }
+
+    #[tokio::test]
+    async fn test_jobs_run_in_order() {
+        let (queue, updates) = start_test_queue();
+
+        let first = queue
+            .enqueue(JobKind::AnimalsImport, "staffer", |progress| {
+                Box::pin(async move {
+                    progress.report(50, "Halfway");
+                    Ok(serde_json::json!(["a1", "a2"]))
+                })
+            })
+            .unwrap();
+        assert_eq!(first.status, JobStatus::Queued);
+        let second = queue
+            .enqueue(JobKind::ChangesImport, "staffer", |_| {
+                Box::pin(async move { Err(anyhow!("The changelog is damaged")) })
+            })
+            .unwrap();
+        queue
+            .enqueue(JobKind::ReportExport, "other", |_| {
+                Box::pin(async move { Ok(serde_json::Value::Null) })
+            })
+            .unwrap();
+
+        let first = wait_for(&queue, &first.id).await;
+        assert_eq!(first.status, JobStatus::Completed);
+        assert_eq!(first.progress, 100);
+        assert_eq!(first.result, Some(serde_json::json!(["a1", "a2"])));
+        assert!(first.finished_timestamp.is_some());
+
+        let second = wait_for(&queue, &second.id).await;
+        assert_eq!(second.status, JobStatus::Failed);
+        assert_eq!(second.error.as_deref(), Some("The changelog is damaged"));
+        assert!(second.result.is_none());
+
+        // Each user sees their own jobs, most recent first
+        let jobs = queue.jobs_of("staffer").unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].id, second.id);
+        assert!(queue.job("unknown").unwrap().is_none());
+
+        // The first job was queued, started, reported progress and completed before
+        // the second one started
+        let updates = updates.lock().unwrap();
+        let first_updates: Vec<(JobStatus, u8)> = updates
+            .iter()
+            .filter(|job| job.id == first.id)
+            .map(|job| (job.status, job.progress))
+            .collect();
+        assert_eq!(
+            first_updates,
+            vec![
+                (JobStatus::Queued, 0),
+                (JobStatus::Running, 0),
+                (JobStatus::Running, 50),
+                (JobStatus::Completed, 100),
+            ]
+        );
+        let first_completed = updates
+            .iter()
+            .position(|job| job.id == first.id && job.status == JobStatus::Completed)
+            .unwrap();
+        let second_started = updates
+            .iter()
+            .position(|job| job.id == second.id && job.status == JobStatus::Running)
+            .unwrap();
+        assert!(first_completed < second_started);
+    }
+
+    #[tokio::test]
+    async fn test_finished_jobs_are_pruned() {
+        let (queue, _) = start_test_queue();
+
+        let mut last_id = String::new();
+        for _ in 0..60 {
+            last_id = queue
+                .enqueue(JobKind::AnimalsExport, "staffer", |_| {
+                    Box::pin(async move { Ok(serde_json::Value::Null) })
+                })
+                .unwrap()
+                .id;
+        }
+        wait_for(&queue, &last_id).await;
+
+        let jobs = queue.jobs_of("staffer").unwrap();
+        assert_eq!(jobs.len(), 50);
+        assert_eq!(jobs[0].id, last_id);
+    }
+
+    #[tokio::test]
+    async fn test_detached_progress_is_ignored() {
+        let (queue, updates) = start_test_queue();
+
+        // Work run outside the queue reports its progress to nobody
+        JobProgress::detached().report(50, "Halfway");
+        assert!(updates.lock().unwrap().is_empty());
+        assert!(queue.jobs_of("").unwrap().is_empty());
+    }
+}
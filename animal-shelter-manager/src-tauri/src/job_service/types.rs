@@ -0,0 +1,164 @@
+//
+// job_service/types.rs
+//
+// This module contains the type definitions for background jobs: the work that
+// can be queued, and the status, progress and result reported for each job.
+//
+
+use crate::database_service::types::{AdoptionRequestFilters, Animal, FilterCriteria, FilterValue};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Where a job is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JobStatus {
+    /// Waiting for the jobs queued before it to finish
+    Queued,
+    /// Being worked on
+    Running,
+    /// Finished, with its result
+    Completed,
+    /// Stopped with an error
+    Failed,
+}
+
+impl JobStatus {
+    /// Whether the job has stopped, successfully or not
+    pub fn is_finished(self) -> bool {
+        matches!(self, JobStatus::Completed | JobStatus::Failed)
+    }
+}
+
+/// The kind of work a job does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JobKind {
+    /// Exporting the monthly or annual activity report
+    ReportExport,
+    /// Exporting animals as an Excel workbook
+    AnimalsExport,
+    /// Exporting adoption requests as an Excel workbook
+    AdoptionRequestsExport,
+    /// Exporting the changes made on this installation as a changelog file
+    ChangesExport,
+    /// Importing a changelog file exported by another installation
+    ChangesImport,
+    /// Creating several animals at once
+    AnimalsImport,
+}
+
+/// File format a report is exported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReportFormat {
+    /// A PDF document
+    Pdf,
+    /// An Excel workbook
+    Xlsx,
+}
+
+impl ReportFormat {
+    /// Gets the file extension of the format, without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Pdf => "pdf",
+            ReportFormat::Xlsx => "xlsx",
+        }
+    }
+
+    /// Gets the name of the file type shown in save dialogs
+    pub fn filter_name(self) -> &'static str {
+        match self {
+            ReportFormat::Pdf => "PDF",
+            ReportFormat::Xlsx => "Excel",
+        }
+    }
+}
+
+/// Work requested from the frontend, with its parameters
+#[derive(Debug, Clone, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "kebab-case",
+    rename_all_fields = "camelCase"
+)]
+pub enum JobRequest {
+    /// Export the activity report of a month, or of a whole year
+    ReportExport {
+        /// The year to report on
+        year: i32,
+        /// The month to report on, from 1 to 12, or None for the whole year
+        month: Option<u32>,
+        /// Only include animals at this location, or None for the whole organization
+        location_id: Option<String>,
+        /// The file format to export in
+        format: ReportFormat,
+    },
+    /// Export the animals matching the filters
+    AnimalsExport {
+        /// Optional map of filter criteria and values
+        filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
+    },
+    /// Export the adoption requests matching the filters
+    AdoptionRequestsExport {
+        /// Status, date range and species filters; unset fields are ignored
+        #[serde(default)]
+        filters: AdoptionRequestFilters,
+        /// Whether to hide who the applicants are, for sharing with research partners
+        #[serde(default)]
+        anonymize: bool,
+    },
+    /// Export the changes made since a time
+    ChangesExport {
+        /// Only export changes made at or after this timestamp, or everything if None
+        since_timestamp: Option<i64>,
+    },
+    /// Import a changelog file chosen by the user
+    ChangesImport,
+    /// Create several animals in a single transaction
+    AnimalsImport {
+        /// The animals to create
+        animals: Vec<Animal>,
+    },
+}
+
+impl JobRequest {
+    /// Gets the kind of work requested
+    pub fn kind(&self) -> JobKind {
+        match self {
+            JobRequest::ReportExport { .. } => JobKind::ReportExport,
+            JobRequest::AnimalsExport { .. } => JobKind::AnimalsExport,
+            JobRequest::AdoptionRequestsExport { .. } => JobKind::AdoptionRequestsExport,
+            JobRequest::ChangesExport { .. } => JobKind::ChangesExport,
+            JobRequest::ChangesImport => JobKind::ChangesImport,
+            JobRequest::AnimalsImport { .. } => JobKind::AnimalsImport,
+        }
+    }
+}
+
+/// A queued, running or finished background job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Job {
+    /// Unique identifier for the job
+    pub id: String,
+    /// The kind of work the job does
+    pub kind: JobKind,
+    /// Username of the user who queued the job
+    pub username: String,
+    /// Where the job is in its lifecycle
+    pub status: JobStatus,
+    /// How far along the job is, from 0 to 100
+    pub progress: u8,
+    /// What the job is doing, such as "Rendering report"
+    pub message: String,
+    /// The result of a completed job; its shape depends on the kind
+    pub result: Option<serde_json::Value>,
+    /// Why the job failed, if it did
+    pub error: Option<String>,
+    /// Timestamp when the job was queued
+    pub created_timestamp: i64,
+    /// Timestamp when the job finished, if it has
+    pub finished_timestamp: Option<i64>,
+}
@@ -6,136 +6,992 @@
 //
 
 mod authentication_service;
+mod backup_service;
+mod cli;
+mod command_error;
 mod database_service;
+mod email_service;
+mod export_service;
 mod file_service;
+mod health_service;
+mod http_service;
+mod i18n;
+mod job_service;
+mod log_service;
+mod reminder_service;
+mod settings_service;
+mod webhook_service;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use authentication_service::{
-    types::{LoginResult, UserRole},
+    types::{
+        InviteCode, InviteCodeError, LoginResult, PasswordReset, SessionInfo, SessionPolicy,
+        TotpEnrollment, UserAccount, UserRole,
+    },
     AuthenticationService, CurrentUser,
 };
+use chrono::Utc;
+pub use cli::CLI_FLAG;
+use command_error::CommandError;
 use database_service::{
-    types::{AdoptionRequest, Animal, AnimalSummary, FilterCriteria, FilterValue},
-    DatabaseService,
+    audit, demo, search,
+    types::{
+        ActivityFeed, ActivityFeedItem, AdjustStockResult, AdoptionRequest, AdoptionRequestFilters,
+        AdoptionRequestSummary, AdoptionRequestView, AdoptionReturn, Animal, AnimalAdoptionRequest,
+        AnimalDetail, AnimalNote, AnimalSummary, Appointment, ApproveRequestResult,
+        AssignKennelResult, AuditAction, AuditEntry, BannedAdopter, BudgetReport,
+        CancelAppointmentResult, CapacityReport, Changelog, CompleteSurgeryResult,
+        ConfirmAppointmentResult, Contact, ContactKind, CreateErasureResult, CreateRequestResult,
+        DeceasedRecord, DemoDataSummary, Donation, EmailMessage, EmailStatus, ErasureRequest,
+        ErasureRequestStatus, Expense, FieldError, FilterCriteria, FilterValue,
+        FinalizeTrialResult, FosterPlacement, ImportChangesResult, IntakeRecord, IntegrityReport,
+        InventoryAdjustment, InventoryItem, JoinWaitlistResult, Kennel, KennelOccupancy, Location,
+        LongStayAnimal, MaintenanceResult, MissingImage, MonthlyReport, NoteCategory, Notification,
+        Payment, PaymentDocument, ProcessReturnResult, RecordCheckResult, RecordDeathResult,
+        RecordPaymentResult, RecordTransferResult, ReferenceCheck, ReferenceOutcome,
+        RequestAppointmentResult, RequestChecks, RequestInterview, RequestStatus,
+        ReviewErasureResult, ScheduleSurgeryResult, ScreeningConfig, SearchResults, SearchScope,
+        ShelterStatistics, StartFosterResult, StrayHold, Surgery, Transfer, TrashItem,
+        TrashItemType, TrendGranularity, TrendPoint, TrialAdoption, UpdateAnimalResult,
+        UserProfile, Vaccination, WaitlistEntry, Webhook, WebhookDelivery, WebhookEvent,
+    },
+    validation, DatabaseService, DATA_VERSION,
 };
-use file_service::FileService;
-use std::collections::HashMap;
-use std::path::PathBuf;
-use tauri::{AppHandle, Manager, State};
-use tokio::{fs, sync::Mutex};
+use export_service::types::ListingsFormat;
+use file_service::{FileService, FileVerificationReport, StorageOwner, StorageStats, UploadKind};
+use health_service::types::{HealthReport, ServiceStatus};
+use i18n::types::{Locale, Message, MessageKey};
+use job_service::{
+    types::{Job, JobRequest, ReportFormat},
+    JobFuture, JobProgress, JobQueue, JobUpdatedListener,
+};
+use log_service::{types::LogLevel, LogService};
+use reminder_service::{ReminderServices, RemindersCreatedListener};
+use settings_service::{
+    types::{Setting, SettingKey},
+    SettingsService,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+use tokio::{fs, sync::OnceCell};
+use uuid::Uuid;
+
+/// Names of the database files in the app data directory
+const SHELTER_DATABASE_FILE: &str = "animal_shelter.db";
+const AUTHENTICATION_DATABASE_FILE: &str = "authentication.db";
+const SETTINGS_DATABASE_FILE: &str = "settings.db";
+
+/// Every database file, in the order they are backed up
+const DATABASE_FILE_NAMES: [&str; 3] = [
+    SHELTER_DATABASE_FILE,
+    AUTHENTICATION_DATABASE_FILE,
+    SETTINGS_DATABASE_FILE,
+];
+
+/// Name of the event emitted to the frontend when notifications are created
+const NOTIFICATIONS_CREATED_EVENT: &str = "notifications-created";
+
+/// Names of the events emitted to the frontend when animals or adoption requests change,
+/// each carrying the ID of the changed record so open windows can refresh it
+const ANIMAL_CREATED_EVENT: &str = "animal-created";
+const ANIMAL_UPDATED_EVENT: &str = "animal-updated";
+const ANIMAL_DELETED_EVENT: &str = "animal-deleted";
+const REQUEST_CREATED_EVENT: &str = "request-created";
+const REQUEST_UPDATED_EVENT: &str = "request-updated";
+const REQUEST_DELETED_EVENT: &str = "request-deleted";
+
+/// Name of the event emitted to the frontend whenever a background job is queued, makes
+/// progress or finishes
+const JOB_UPDATED_EVENT: &str = "job-updated";
+
+/// How often expired records are purged from the trash while the app is running
+const TRASH_PURGE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+/// How often the app checks whether scheduled database maintenance is due while running
+const MAINTENANCE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
 
 /// Global state of the app
+///
+/// Each service is initialized lazily and locked independently, so a long running
+/// operation on one service does not block commands that use another.
 #[derive(Default)]
 struct AppState {
     /// Service for handling file operations
-    file_service: Option<FileService>,
+    file_service: OnceCell<Arc<FileService>>,
     /// Service for handling database operations
-    database_service: Option<DatabaseService>,
+    database_service: OnceCell<Arc<Mutex<DatabaseService>>>,
     /// Service for handling authentication operations
-    authentication_service: Option<AuthenticationService>,
+    authentication_service: OnceCell<Arc<Mutex<AuthenticationService>>>,
+    /// Service for handling application settings
+    settings_service: OnceCell<Arc<Mutex<SettingsService>>>,
+    /// Queue of exports and imports running in the background
+    job_queue: OnceCell<JobQueue>,
 }
 
 /// Lazily initializes the FileService if it hasn't been created yet
 ///
 /// # Arguments
-/// * `state` - Mutable reference to the application state
+/// * `state` - Reference to the application state
 /// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(Arc<FileService>)` - Handle to the file service
+/// * `Err(CommandError)` - An error message if the service could not be created
 async fn init_file_service_once(
-    state: &mut AppState,
+    state: &AppState,
     app_handle: &AppHandle,
-) -> Result<(), String> {
-    if state.file_service.is_none() {
-        log::info!("Initializing FileService");
-        let app_data_dir = app_handle
-            .path()
-            .app_data_dir()
-            .map_err(|e| e.to_string())?;
+) -> Result<Arc<FileService>, CommandError> {
+    state
+        .file_service
+        .get_or_try_init(|| async {
+            log::info!("Initializing FileService");
+            let app_data_dir = app_handle
+                .path()
+                .app_data_dir()
+                .map_err(|e| CommandError::from_service("Failed to find app data directory", e))?;
 
-        // Ensure the app data directory exists
-        if let Err(e) = fs::create_dir_all(&app_data_dir).await {
-            return Err(format!("Failed to create app data directory: {}", e));
-        }
+            // Ensure the app data directory exists
+            if let Err(e) = fs::create_dir_all(&app_data_dir).await {
+                return Err(CommandError::from_service(
+                    "Failed to create app data directory",
+                    e,
+                ));
+            }
 
-        // Initialize FileService with application app data directory
-        match FileService::new(app_data_dir) {
-            Ok(service) => state.file_service = Some(service),
-            Err(e) => return Err(format!("Failed to create FileService: {}", e)),
-        }
-    }
-    Ok(())
+            // Initialize FileService with application app data directory
+            let service = match FileService::new(app_data_dir) {
+                Ok(service) => service,
+                Err(e) => {
+                    return Err(CommandError::from_service(
+                        "Failed to create FileService",
+                        e,
+                    ))
+                }
+            };
+
+            // Apply the upload limit chosen by the admin, keeping the default if it cannot be read
+            let settings_service = init_settings_service_once(state, app_handle).await?;
+            let max_upload_bytes = tauri::async_runtime::spawn_blocking(move || {
+                settings_service
+                    .lock()
+                    .map_err(|_| anyhow!("Settings service lock was poisoned"))?
+                    .max_upload_bytes()
+            })
+            .await;
+            match max_upload_bytes {
+                Ok(Ok(bytes)) => service.set_max_upload_bytes(bytes),
+                Ok(Err(e)) => log::warn!("Failed to read upload limit: {}", e),
+                Err(e) => log::warn!("Failed to read upload limit: {}", e),
+            }
+            Ok(Arc::new(service))
+        })
+        .await
+        .cloned()
 }
 
 /// Lazily initializes the DatabaseService if it hasn't been created yet
 ///
 /// # Arguments
-/// * `state` - Mutable reference to the application state
+/// * `state` - Reference to the application state
 /// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(Arc<Mutex<DatabaseService>>)` - Handle to the database service
+/// * `Err(CommandError)` - An error message if the service could not be created
 async fn init_database_service_once(
-    state: &mut AppState,
+    state: &AppState,
+    app_handle: &AppHandle,
+) -> Result<Arc<Mutex<DatabaseService>>, CommandError> {
+    state
+        .database_service
+        .get_or_try_init(|| async {
+            log::info!("Initializing DatabaseService");
+            let app_data_dir = app_handle
+                .path()
+                .app_data_dir()
+                .map_err(|e| CommandError::from_service("Failed to find app data directory", e))?;
+
+            // Ensure the app data directory exists
+            if let Err(e) = fs::create_dir_all(&app_data_dir).await {
+                return Err(CommandError::from_service(
+                    "Failed to create app data directory",
+                    e,
+                ));
+            }
+
+            // Initialize DatabaseService with application app data directory
+            let db_path = app_data_dir.join(SHELTER_DATABASE_FILE);
+            match DatabaseService::new(db_path) {
+                Ok(service) => Ok(Arc::new(Mutex::new(service))),
+                Err(e) => Err(CommandError::from_service(
+                    "Failed to create DatabaseService",
+                    e,
+                )),
+            }
+        })
+        .await
+        .cloned()
+}
+
+/// Lazily initializes the AuthenticationService if it hasn't been created yet
+///
+/// # Arguments
+/// * `state` - Reference to the application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(Arc<Mutex<AuthenticationService>>)` - Handle to the authentication service
+/// * `Err(CommandError)` - An error message if the service could not be created
+async fn init_authentication_service_once(
+    state: &AppState,
+    app_handle: &AppHandle,
+) -> Result<Arc<Mutex<AuthenticationService>>, CommandError> {
+    state
+        .authentication_service
+        .get_or_try_init(|| async {
+            log::info!("Initializing AuthenticationService");
+            let app_data_dir = app_handle
+                .path()
+                .app_data_dir()
+                .map_err(|e| CommandError::from_service("Failed to find app data directory", e))?;
+
+            // Ensure the app data directory exists
+            if let Err(e) = fs::create_dir_all(&app_data_dir).await {
+                return Err(CommandError::from_service(
+                    "Failed to create app data directory",
+                    e,
+                ));
+            }
+
+            // Test creating a file in app_data_dir
+            let test_file_path = app_data_dir.join("test_file.txt");
+            if let Err(e) = fs::File::create(&test_file_path).await {
+                return Err(CommandError::from_service(
+                    "Failed to create test file in app data directory",
+                    e,
+                ));
+            }
+
+            // Initialize AuthenticationService with its own database in app data directory
+            let auth_db_path = app_data_dir.join(AUTHENTICATION_DATABASE_FILE);
+            match AuthenticationService::new(auth_db_path) {
+                Ok(service) => Ok(Arc::new(Mutex::new(service))),
+                Err(e) => Err(CommandError::from_service(
+                    "Failed to create AuthenticationService",
+                    e,
+                )),
+            }
+        })
+        .await
+        .cloned()
+}
+
+/// Lazily initializes the SettingsService if it hasn't been created yet
+///
+/// # Arguments
+/// * `state` - Reference to the application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(Arc<Mutex<SettingsService>>)` - Handle to the settings service
+/// * `Err(CommandError)` - An error message if the service could not be created
+async fn init_settings_service_once(
+    state: &AppState,
+    app_handle: &AppHandle,
+) -> Result<Arc<Mutex<SettingsService>>, CommandError> {
+    state
+        .settings_service
+        .get_or_try_init(|| async {
+            log::info!("Initializing SettingsService");
+            let app_data_dir = app_handle
+                .path()
+                .app_data_dir()
+                .map_err(|e| CommandError::from_service("Failed to find app data directory", e))?;
+
+            // Ensure the app data directory exists
+            if let Err(e) = fs::create_dir_all(&app_data_dir).await {
+                return Err(CommandError::from_service(
+                    "Failed to create app data directory",
+                    e,
+                ));
+            }
+
+            // Initialize SettingsService with its own database in app data directory
+            let settings_db_path = app_data_dir.join(SETTINGS_DATABASE_FILE);
+            match SettingsService::new(settings_db_path) {
+                Ok(service) => {
+                    // Write errors in the chosen locale, and log at the chosen level,
+                    // from now on
+                    match service.locale() {
+                        Ok(locale) => i18n::set_current_locale(locale),
+                        Err(e) => log::warn!("Failed to read locale: {}", e),
+                    }
+                    match service.log_level() {
+                        Ok(level) => apply_log_level(level),
+                        Err(e) => log::warn!("Failed to read log level: {}", e),
+                    }
+                    Ok(Arc::new(Mutex::new(service)))
+                }
+                Err(e) => Err(CommandError::from_service(
+                    "Failed to create SettingsService",
+                    e,
+                )),
+            }
+        })
+        .await
+        .cloned()
+}
+
+/// Lazily creates the job queue and starts its worker if they haven't been created yet
+///
+/// # Arguments
+/// * `state` - Reference to the application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `JobQueue` - Handle to the job queue
+async fn init_job_queue_once(state: &AppState, app_handle: &AppHandle) -> JobQueue {
+    state
+        .job_queue
+        .get_or_init(|| async {
+            log::info!("Starting job worker");
+            let event_handle = app_handle.clone();
+            let on_updated: JobUpdatedListener = Arc::new(move |job| {
+                if let Err(e) = event_handle.emit(JOB_UPDATED_EVENT, job) {
+                    log::warn!("Failed to emit update of job {}: {}", job.id, e);
+                }
+            });
+            let (queue, worker) = JobQueue::new(on_updated);
+            tauri::async_runtime::spawn(worker.run());
+            queue
+        })
+        .await
+        .clone()
+}
+
+/// Runs a task against the DatabaseService on the blocking thread pool
+///
+/// Only the database service is locked while the task runs, so a slow query does not
+/// hold up authentication or file commands.
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+/// * `task` - The database work to run
+///
+/// # Returns
+/// * `Ok(Result<T>)` - The result of the database work
+/// * `Err(CommandError)` - An error message if the service could not be initialized or the task did not complete
+async fn run_database_task<T, F>(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+    task: F,
+) -> Result<Result<T>, CommandError>
+where
+    T: Send + 'static,
+    F: FnOnce(&DatabaseService) -> Result<T> + Send + 'static,
+{
+    // Lazily initialize the database service
+    let database_service = init_database_service_once(state, app_handle).await?;
+
+    // Run the blocking database work off the async executor
+    tauri::async_runtime::spawn_blocking(move || {
+        let database_service = database_service
+            .lock()
+            .map_err(|_| anyhow!("Database service lock was poisoned"))?;
+        task(&database_service)
+    })
+    .await
+    .map_err(|e| CommandError::from_service("Database task did not complete", e))
+}
+
+/// Runs a task against the AuthenticationService on the blocking thread pool
+///
+/// Password hashing and verification are CPU heavy, so they must not run on the async executor.
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+/// * `task` - The authentication work to run
+///
+/// # Returns
+/// * `Ok(Result<T>)` - The result of the authentication work
+/// * `Err(CommandError)` - An error message if the service could not be initialized or the task did not complete
+async fn run_authentication_task<T, F>(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+    task: F,
+) -> Result<Result<T>, CommandError>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut AuthenticationService) -> Result<T> + Send + 'static,
+{
+    // Lazily initialize the authentication service
+    let authentication_service = init_authentication_service_once(state, app_handle).await?;
+
+    // Run the blocking authentication work off the async executor
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut authentication_service = authentication_service
+            .lock()
+            .map_err(|_| anyhow!("Authentication service lock was poisoned"))?;
+        task(&mut authentication_service)
+    })
+    .await
+    .map_err(|e| CommandError::from_service("Authentication task did not complete", e))
+}
+
+/// Runs a task against the SettingsService on the blocking thread pool
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+/// * `task` - The settings work to run
+///
+/// # Returns
+/// * `Ok(Result<T>)` - The result of the settings work
+/// * `Err(CommandError)` - An error message if the service could not be initialized or the task did not complete
+async fn run_settings_task<T, F>(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+    task: F,
+) -> Result<Result<T>, CommandError>
+where
+    T: Send + 'static,
+    F: FnOnce(&SettingsService) -> Result<T> + Send + 'static,
+{
+    // Lazily initialize the settings service
+    let settings_service = init_settings_service_once(state, app_handle).await?;
+
+    // Run the blocking settings work off the async executor
+    tauri::async_runtime::spawn_blocking(move || {
+        let settings_service = settings_service
+            .lock()
+            .map_err(|_| anyhow!("Settings service lock was poisoned"))?;
+        task(&settings_service)
+    })
+    .await
+    .map_err(|e| CommandError::from_service("Settings task did not complete", e))
+}
+
+/// Retrieves the logged-in user, if anyone is logged in
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(Some(CurrentUser))` - The logged-in user
+/// * `Ok(None)` - If nobody is logged in
+/// * `Err(CommandError)` - An error message if the lookup fails
+async fn current_user(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+) -> Result<Option<CurrentUser>, CommandError> {
+    match run_authentication_task(state, app_handle, |auth| auth.get_current_user()).await? {
+        Ok(user) => Ok(user),
+        Err(e) => Err(CommandError::from_service("Failed to get current user", e)),
+    }
+}
+
+/// Retrieves the logged-in user, failing if nobody is logged in
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(CurrentUser)` - The logged-in user
+/// * `Err(CommandError)` - An error message if nobody is logged in or the lookup fails
+async fn require_logged_in_user(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+) -> Result<CurrentUser, CommandError> {
+    current_user(state, app_handle).await?.ok_or_else(|| {
+        CommandError::permission_denied("You must be logged in to perform this action")
+    })
+}
+
+/// Retrieves the logged-in user, failing unless they are a staff member
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(CurrentUser)` - The logged-in staff member
+/// * `Err(CommandError)` - An error message if the user is not logged in as staff
+async fn require_staff(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+) -> Result<CurrentUser, CommandError> {
+    let user = require_logged_in_user(state, app_handle).await?;
+    if !user.role.is_staff() {
+        return Err(CommandError::permission_denied(
+            "Only staff members can perform this action",
+        ));
+    }
+    Ok(user)
+}
+
+/// Retrieves the logged-in user, failing unless they are an admin
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(CurrentUser)` - The logged-in admin
+/// * `Err(CommandError)` - An error message if the user is not logged in as an admin
+async fn require_admin(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+) -> Result<CurrentUser, CommandError> {
+    let user = require_logged_in_user(state, app_handle).await?;
+    if user.role != UserRole::Admin {
+        return Err(CommandError::permission_denied(
+            "Only admins can perform this action",
+        ));
+    }
+    Ok(user)
+}
+
+/// Gets an adoption request as a user may see it
+///
+/// Staff members and the requester see every field. Everyone else, including visitors
+/// who are not logged in, only sees the status and timestamps of the request.
+///
+/// # Arguments
+/// * `request` - The adoption request to show
+/// * `viewer` - The logged-in user, or None if nobody is logged in
+///
+/// # Returns
+/// * `AdoptionRequestView` - The request, redacted unless the viewer may see all of it
+fn adoption_request_view(
+    request: AdoptionRequest,
+    viewer: Option<&CurrentUser>,
+) -> AdoptionRequestView {
+    match viewer {
+        Some(user) if user.role.is_staff() || user.username == request.username => {
+            AdoptionRequestView::Full(Box::new(request))
+        }
+        _ => AdoptionRequestView::Redacted(request.redacted()),
+    }
+}
+
+/// Emits newly created notifications to the frontend
+///
+/// # Arguments
+/// * `app_handle` - Reference to the Tauri application handle
+/// * `notifications` - The notifications that were created
+fn emit_notifications(app_handle: &AppHandle, notifications: &[Notification]) {
+    if notifications.is_empty() {
+        return;
+    }
+    if let Err(e) = app_handle.emit(NOTIFICATIONS_CREATED_EVENT, notifications.to_vec()) {
+        log::warn!("Failed to emit created notifications: {}", e);
+    }
+}
+
+/// Emits a data change event to the frontend
+///
+/// # Arguments
+/// * `app_handle` - Reference to the Tauri application handle
+/// * `event` - Name of the event, such as `animal-updated`
+/// * `id` - ID of the record that changed
+fn emit_data_change(app_handle: &AppHandle, event: &str, id: &str) {
+    if let Err(e) = app_handle.emit(event, id) {
+        log::warn!("Failed to emit {} event for {}: {}", event, id, e);
+    }
+}
+
+/// Reads how adoption requests are scored for screening
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(ScreeningConfig)` - The scoring chosen in the settings
+/// * `Err(CommandError)` - An error message if the settings could not be read
+async fn screening_config(
+    state: &State<'_, AppState>,
     app_handle: &AppHandle,
-) -> Result<(), String> {
-    if state.database_service.is_none() {
-        log::info!("Initializing DatabaseService");
-        let app_data_dir = app_handle
-            .path()
-            .app_data_dir()
-            .map_err(|e| e.to_string())?;
+) -> Result<ScreeningConfig, CommandError> {
+    match run_settings_task(state, app_handle, |settings| settings.screening_config()).await? {
+        Ok(config) => Ok(config),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve screening settings",
+            e,
+        )),
+    }
+}
 
-        // Ensure the app data directory exists
-        if let Err(e) = fs::create_dir_all(&app_data_dir).await {
-            return Err(format!("Failed to create app data directory: {}", e));
+/// Scores an adoption request for screening and stores its score
+///
+/// The request has already been stored, so failing to score it is only logged; its
+/// score is filled in the next time scores are recomputed.
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+/// * `request_id` - The ID of the adoption request to score
+async fn score_adoption_request(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+    request_id: &str,
+) {
+    let config = match screening_config(state, app_handle).await {
+        Ok(config) => config,
+        Err(e) => {
+            log::warn!("Failed to score adoption request {}: {}", request_id, e);
+            return;
         }
+    };
+    let id = request_id.to_string();
+    match run_database_task(state, app_handle, move |db| {
+        db.score_adoption_request(&id, &config)
+    })
+    .await
+    {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => log::warn!("Failed to score adoption request {}: {}", request_id, e),
+        Err(e) => log::warn!("Failed to score adoption request {}: {}", request_id, e),
+    }
+}
 
-        // Initialize DatabaseService with application app data directory
-        let db_path = app_data_dir.join("animal_shelter.db");
-        match DatabaseService::new(db_path) {
-            Ok(service) => state.database_service = Some(service),
-            Err(e) => return Err(format!("Failed to create DatabaseService: {}", e)),
+/// Records an action of the logged-in staff member in the audit log, for the activity feed
+///
+/// Actions of customers, and of visitors who are not logged in, are not recorded. The
+/// action has already been taken, so failing to record it is only logged.
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+/// * `action` - What the staff member did
+/// * `record_id` - ID of the animal or adoption request they acted on
+async fn record_activity(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+    action: AuditAction,
+    record_id: &str,
+) {
+    let username = match current_user(state, app_handle).await {
+        Ok(Some(user)) if user.role.is_staff() => user.username,
+        Ok(_) => return,
+        Err(e) => {
+            log::warn!("Failed to record {} of {}: {}", action, record_id, e);
+            return;
         }
+    };
+    let id = record_id.to_string();
+    match run_database_task(state, app_handle, move |db| {
+        db.insert_audit_entry(&username, action, &id)
+    })
+    .await
+    {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => log::warn!("Failed to record {} of {}: {}", action, record_id, e),
+        Err(e) => log::warn!("Failed to record {} of {}: {}", action, record_id, e),
     }
-    Ok(())
 }
 
-/// Lazily initializes the AuthenticationService if it hasn't been created yet
+/// Starts the HTTP API for the public website in the background, if it is turned on
+/// in the settings
 ///
 /// # Arguments
-/// * `state` - Mutable reference to the application state
+/// * `app_handle` - The Tauri application handle
+fn start_http_api(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        let address =
+            match run_settings_task(&state, &app_handle, |settings| settings.http_api_address())
+                .await
+            {
+                Ok(Ok(Some(address))) => address,
+                Ok(Ok(None)) => {
+                    log::debug!("HTTP API is disabled");
+                    return;
+                }
+                Ok(Err(e)) => {
+                    log::error!("Failed to read HTTP API settings: {:#}", e);
+                    return;
+                }
+                Err(e) => {
+                    log::error!("Failed to read HTTP API settings: {}", e);
+                    return;
+                }
+            };
+        let services = (
+            init_database_service_once(&state, &app_handle).await,
+            init_file_service_once(&state, &app_handle).await,
+        );
+        let (database_service, file_service) = match services {
+            (Ok(database_service), Ok(file_service)) => (database_service, file_service),
+            (Err(e), _) | (_, Err(e)) => {
+                log::error!("Failed to start HTTP API: {}", e);
+                return;
+            }
+        };
+        let event_handle = app_handle.clone();
+        let on_request_created: http_service::RequestCreatedListener = Arc::new(move |id| {
+            emit_data_change(&event_handle, REQUEST_CREATED_EVENT, id);
+            let (handle, id) = (event_handle.clone(), id.to_string());
+            tauri::async_runtime::spawn(async move {
+                let state = handle.state::<AppState>();
+                score_adoption_request(&state, &handle, &id).await;
+            });
+        });
+        if let Err(e) =
+            http_service::serve(address, database_service, file_service, on_request_created).await
+        {
+            log::error!("HTTP API stopped: {:#}", e);
+        }
+    });
+}
+
+/// Starts delivering the events queued for webhooks in the background
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle
+fn start_webhook_dispatcher(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        match init_database_service_once(&state, &app_handle).await {
+            Ok(database_service) => webhook_service::run(database_service).await,
+            Err(e) => log::error!("Failed to start webhook dispatcher: {}", e),
+        }
+    });
+}
+
+/// Starts sending the emails queued for applicants in the background
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle
+fn start_email_dispatcher(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        let services = (
+            init_database_service_once(&state, &app_handle).await,
+            init_settings_service_once(&state, &app_handle).await,
+        );
+        match services {
+            (Ok(database_service), Ok(settings_service)) => {
+                email_service::run(database_service, settings_service).await
+            }
+            (Err(e), _) | (_, Err(e)) => log::error!("Failed to start email dispatcher: {}", e),
+        }
+    });
+}
+
+/// Gets the services the reminder scheduler reads from, initializing them if needed
+///
+/// # Arguments
+/// * `state` - The managed application state
 /// * `app_handle` - Reference to the Tauri application handle
-async fn init_authentication_service_once(
-    state: &mut AppState,
+///
+/// # Returns
+/// * `Result<ReminderServices, CommandError>` - The services or an error message
+async fn init_reminder_services(
+    state: &AppState,
     app_handle: &AppHandle,
-) -> Result<(), String> {
-    if state.authentication_service.is_none() {
-        log::info!("Initializing AuthenticationService");
-        let app_data_dir = app_handle
-            .path()
-            .app_data_dir()
-            .map_err(|e| e.to_string())?;
+) -> Result<ReminderServices, CommandError> {
+    Ok(ReminderServices {
+        database_service: init_database_service_once(state, app_handle).await?,
+        authentication_service: init_authentication_service_once(state, app_handle).await?,
+        settings_service: init_settings_service_once(state, app_handle).await?,
+    })
+}
+
+/// Starts checking the reminder rules in the background, notifying staff about
+/// vaccinations that are due, long stays and requests left pending
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle
+fn start_reminder_scheduler(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        let services = match init_reminder_services(&state, &app_handle).await {
+            Ok(services) => services,
+            Err(e) => {
+                log::error!("Failed to start reminder scheduler: {}", e);
+                return;
+            }
+        };
+        let event_handle = app_handle.clone();
+        let on_created: RemindersCreatedListener =
+            Arc::new(move |notifications| emit_notifications(&event_handle, notifications));
+        reminder_service::run(services, on_created).await;
+    });
+}
 
-        // Ensure the app data directory exists
-        if let Err(e) = fs::create_dir_all(&app_data_dir).await {
-            return Err(format!("Failed to create app data directory: {}", e));
+/// Purges expired records from the trash in the background, right away and then once a day
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle
+fn start_trash_purge(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        let mut interval = tokio::time::interval(TRASH_PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = purge_expired_trash(&state, &app_handle).await {
+                log::error!("Failed to purge trash: {}", e);
+            }
         }
+    });
+}
 
-        // Test creating a file in app_data_dir
-        let test_file_path = app_data_dir.join("test_file.txt");
-        if let Err(e) = fs::File::create(&test_file_path).await {
-            return Err(format!(
-                "Failed to create test file in app data directory: {}",
-                e
-            ));
+/// Vacuums and analyzes the databases in the background whenever the maintenance
+/// interval set in the settings has passed since the last run, checking once an hour
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle
+fn start_maintenance_schedule(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        let mut interval = tokio::time::interval(MAINTENANCE_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            match is_maintenance_due(&state, &app_handle).await {
+                Ok(true) => {
+                    if let Err(e) = maintain_databases(&state, &app_handle).await {
+                        log::error!("Failed to run scheduled maintenance: {}", e);
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => log::error!("Failed to check the maintenance schedule: {}", e),
+            }
         }
+    });
+}
+
+/// Checks whether scheduled maintenance is turned on and its interval has passed since
+/// the shelter database was last maintained
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(bool)` - Whether maintenance should run now
+/// * `Err(CommandError)` - An error message if the settings or database could not be read
+async fn is_maintenance_due(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+) -> Result<bool, CommandError> {
+    let interval_days = run_settings_task(state, app_handle, |settings| {
+        settings.maintenance_interval_days()
+    })
+    .await?
+    .map_err(|e| CommandError::from_service("Failed to read maintenance settings", e))?;
+    if interval_days == 0 {
+        return Ok(false);
+    }
+
+    let last_run = run_database_task(state, app_handle, |db| {
+        db.query_last_maintenance_timestamp()
+    })
+    .await?
+    .map_err(|e| CommandError::from_service("Failed to query last maintenance run", e))?;
+    Ok(last_run.is_none_or(|timestamp| {
+        Utc::now().timestamp() - timestamp >= i64::from(interval_days) * 86_400
+    }))
+}
 
-        // Initialize AuthenticationService with its own database in app data directory
-        let auth_db_path = app_data_dir.join("authentication.db");
-        match AuthenticationService::new(auth_db_path) {
-            Ok(service) => state.authentication_service = Some(service),
-            Err(e) => return Err(format!("Failed to create AuthenticationService: {}", e)),
+/// Moves the existing uploads that older versions stored directly in the app data
+/// directory into per-animal directories, in the background
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle
+fn start_storage_migration(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        if let Err(e) = migrate_storage_layout(&state, &app_handle).await {
+            log::error!("Failed to move uploads into per-record directories: {}", e);
         }
+    });
+}
+
+/// Moves the uploads stored directly in the app data directory into per-animal
+/// directories and points the animals at their new paths
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(())` - If every upload was moved, or there were none
+/// * `Err(CommandError)` - An error message if the files or database could not be updated
+async fn migrate_storage_layout(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+) -> Result<(), CommandError> {
+    let image_paths = run_database_task(state, app_handle, |db| db.query_image_paths())
+        .await?
+        .map_err(|e| CommandError::from_service("Failed to query image paths", e))?;
+
+    let file_service = init_file_service_once(state, app_handle).await?;
+    let changed_paths = file_service
+        .migrate_flat_uploads(&image_paths)
+        .await
+        .map_err(|e| CommandError::from_service("Failed to move uploads", e))?;
+    if changed_paths.is_empty() {
+        return Ok(());
+    }
+
+    // Put the files back if the animals cannot be pointed at them, so no image goes missing
+    let replaced = changed_paths.clone();
+    if let Err(e) = run_database_task(state, app_handle, move |db| {
+        db.replace_image_paths(&replaced)
+    })
+    .await?
+    {
+        file_service.revert_migrated_uploads(&changed_paths).await;
+        return Err(CommandError::from_service(
+            "Failed to update image paths",
+            e,
+        ));
     }
     Ok(())
 }
 
+/// Moves an animal's image into the animal's own directory if it was uploaded before
+/// the animal was saved
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+/// * `animal` - The animal being saved; its image path is updated if the file moves
+///
+/// # Returns
+/// * `Ok(())` - If the image is in place
+/// * `Err(CommandError)` - An error message if the image could not be moved
+async fn assign_image_to_animal(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+    animal: &mut Animal,
+) -> Result<(), CommandError> {
+    let Some(image_path) = animal.image_path.as_deref().filter(|path| !path.is_empty()) else {
+        return Ok(());
+    };
+
+    let file_service = init_file_service_once(state, app_handle).await?;
+    let owner = StorageOwner::Animal(animal.id.clone());
+    match file_service.assign_to_owner(image_path, &owner).await {
+        Ok(image_path) => {
+            animal.image_path = Some(image_path);
+            Ok(())
+        }
+        Err(e) => Err(CommandError::from_service(
+            "Failed to store animal image",
+            e,
+        )),
+    }
+}
+
 // ==================== ANIMAL TABLE COMMANDS ====================
 
 /// Command to retrieve animals from the database, with optional filtering
@@ -145,587 +1001,7825 @@ async fn init_authentication_service_once(
 ///
 /// # Returns
 /// * `Ok(Vec<AnimalSummary>)` - List of animal summaries if successful
-/// * `Err(String)` - An error message if the query fails
+/// * `Err(CommandError)` - An error message if the query fails
 #[tauri::command]
 async fn get_animals(
-    state: State<'_, Mutex<AppState>>,
+    state: State<'_, AppState>,
     app_handle: AppHandle,
     filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
-) -> Result<Vec<AnimalSummary>, String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the database service
-    init_database_service_once(&mut state_guard, &app_handle).await?;
-
+) -> Result<Vec<AnimalSummary>, CommandError> {
     // Query animals with filters
-    match state_guard
-        .database_service
-        .as_ref()
-        .unwrap()
-        .query_animals(filters)
-    {
+    match run_database_task(&state, &app_handle, move |db| db.query_animals(filters)).await? {
         Ok(animals) => Ok(animals),
-        Err(e) => Err(format!("Failed to retrieve animals: {}", e)),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve animals", e)),
     }
 }
 
-/// Command to retrieve a specific animal by ID
+/// Command to retrieve an animal together with its adoption requests, latest medical note
+/// and age, so the detail page needs a single round trip
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to retrieve
+///
+/// # Returns
+/// * `Ok(Some(AnimalDetail))` - The animal's details if found
+/// * `Ok(None)` - If no animal with the given ID exists
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_animal_detail(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Option<AnimalDetail>, CommandError> {
+    // Only staff members may see internal notes and other applicants' details
+    let viewer = current_user(&state, &app_handle).await?;
+    let include_internal = viewer.as_ref().is_some_and(|user| user.role.is_staff());
+
+    // Query animal detail by ID
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_animal_detail(&id, include_internal)
+    })
+    .await?
+    {
+        Ok(detail) => Ok(detail.map(|mut detail| {
+            detail.adoption_requests = detail
+                .adoption_requests
+                .into_iter()
+                .map(|view| match view {
+                    AdoptionRequestView::Full(request) => {
+                        adoption_request_view(*request, viewer.as_ref())
+                    }
+                    redacted => redacted,
+                })
+                .collect();
+            detail
+        })),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to retrieve details for animal ID {}", animal_id),
+            e,
+        )),
+    }
+}
+
+/// Command to count animals in the database, with optional filtering
+///
+/// # Arguments
+/// * `filters` - Optional map of filter criteria and values
+///
+/// # Returns
+/// * `Ok(i64)` - The number of matching animals if successful
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_animal_count(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
+) -> Result<i64, CommandError> {
+    // Count animals with filters
+    match run_database_task(&state, &app_handle, move |db| db.count_animals(filters)).await? {
+        Ok(count) => Ok(count),
+        Err(e) => Err(CommandError::from_service("Failed to count animals", e)),
+    }
+}
+
+/// Command to retrieve the distinct species of the animals in the database
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - Sorted list of species if successful
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_distinct_species(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<String>, CommandError> {
+    // Query distinct species
+    match run_database_task(&state, &app_handle, |db| db.query_distinct_species()).await? {
+        Ok(species) => Ok(species),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve species", e)),
+    }
+}
+
+/// Command to retrieve the distinct breeds of the animals of a species in the database
+///
+/// # Arguments
+/// * `specie` - The species to retrieve breeds for
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - Sorted list of breeds if successful
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_distinct_breeds(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    specie: String,
+) -> Result<Vec<String>, CommandError> {
+    // Query distinct breeds of the species
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_distinct_breeds(&specie)
+    })
+    .await?
+    {
+        Ok(breeds) => Ok(breeds),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve breeds", e)),
+    }
+}
+
+/// Command to retrieve the distinct colors recorded for the animals in the database
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - Sorted list of colors if successful
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_distinct_colors(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<String>, CommandError> {
+    match run_database_task(&state, &app_handle, |db| db.query_distinct_colors()).await? {
+        Ok(colors) => Ok(colors),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve colors", e)),
+    }
+}
+
+/// Command to retrieve a specific animal by ID
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to retrieve
+///
+/// # Returns
+/// * `Ok(Some(Animal))` - The animal data if found
+/// * `Ok(None)` - If no animal with the given ID exists
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_animal_by_id(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Option<Animal>, CommandError> {
+    // Query animal by ID
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.query_animal_by_id(&id)).await? {
+        Ok(animal) => Ok(animal),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to retrieve animal with ID {}", animal_id),
+            e,
+        )),
+    }
+}
+
+/// Command to find the animal with a specific microchip number
+///
+/// # Arguments
+/// * `microchip_number` - The scanned microchip number
+///
+/// # Returns
+/// * `Ok(Some(Animal))` - The animal data if an animal has the microchip
+/// * `Ok(None)` - If no animal has the microchip
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn find_animal_by_microchip(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    microchip_number: String,
+) -> Result<Option<Animal>, CommandError> {
+    // Query animal by microchip number
+    let number = microchip_number.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_animal_by_microchip(&number)
+    })
+    .await?
+    {
+        Ok(animal) => Ok(animal),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to find animal with microchip {}", microchip_number),
+            e,
+        )),
+    }
+}
+
+/// Command to check an animal before it is created or updated
+///
+/// The same checks run when the animal is stored, so this only lets the frontend show
+/// every problem next to its field before submitting.
+///
+/// # Arguments
+/// * `animal` - The animal data to check
+///
+/// # Returns
+/// * `Ok(Vec<FieldError>)` - The invalid fields, empty if the animal is valid
+#[tauri::command]
+fn validate_animal(animal: Animal) -> Result<Vec<FieldError>, CommandError> {
+    Ok(validation::validate_animal(&animal))
+}
+
+/// Command to insert a new animal into the database
+///
+/// Customers on the waitlist for the animal's species or breed are notified, and the
+/// new notifications are emitted to the frontend as a `notifications-created` event.
+///
+/// # Arguments
+/// * `animal` - The animal data to insert; an ID is generated if it is empty
+///
+/// # Returns
+/// * `Ok(())` - If the animal was successfully inserted
+/// * `Err(CommandError)` - An error message if the insertion fails
+#[tauri::command]
+async fn create_animal(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    mut animal: Animal,
+) -> Result<(), CommandError> {
+    if animal.id.trim().is_empty() {
+        animal.id = Uuid::new_v4().to_string();
+    }
+    assign_image_to_animal(&state, &app_handle, &mut animal).await?;
+
+    // Insert animal
+    let id = animal.id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.insert_animal(&animal)).await? {
+        Ok(notifications) => {
+            emit_notifications(&app_handle, &notifications);
+            emit_data_change(&app_handle, ANIMAL_CREATED_EVENT, &id);
+            record_activity(&state, &app_handle, AuditAction::AnimalCreated, &id).await;
+            Ok(())
+        }
+        Err(e) => Err(CommandError::from_service("Failed to create animal", e)),
+    }
+}
+
+/// Command to insert several animals into the database at once
+///
+/// All animals are inserted in a single transaction: if any of them fails, none are kept.
+/// Waitlist notifications are emitted as for `create_animal`. Unlike an animals import
+/// queued with `enqueue_job`, the command returns once the animals are inserted.
+///
+/// # Arguments
+/// * `animals` - The animal data to insert
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - The IDs of the inserted animals, in the order given
+/// * `Err(CommandError)` - An error message if the insertion fails
+#[tauri::command]
+async fn create_animals_batch(
+    app_handle: AppHandle,
+    animals: Vec<Animal>,
+) -> Result<Vec<String>, CommandError> {
+    run_animals_import(app_handle, JobProgress::detached(), animals).await
+}
+
+/// Command to update an existing animal in the database
+///
+/// # Arguments
+/// * `animal` - The updated animal data, carrying the version it was read at
+///
+/// # Returns
+/// * `Ok(UpdateAnimalResult)` - The updated animal, a conflict with the current animal, or not found
+/// * `Err(CommandError)` - An error message if the update fails
+#[tauri::command]
+async fn update_animal(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    mut animal: Animal,
+) -> Result<UpdateAnimalResult, CommandError> {
+    assign_image_to_animal(&state, &app_handle, &mut animal).await?;
+
+    // Update animal
+    match run_database_task(&state, &app_handle, move |db| db.update_animal(&animal)).await? {
+        Ok(result) => {
+            if let UpdateAnimalResult::Updated(animal) = &result {
+                emit_data_change(&app_handle, ANIMAL_UPDATED_EVENT, &animal.id);
+                record_activity(&state, &app_handle, AuditAction::AnimalUpdated, &animal.id).await;
+            }
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service("Failed to update animal", e)),
+    }
+}
+
+/// Command to replace an animal's image with one selected by the user
+///
+/// The new image is stored and the animal pointed at it before the previous image is
+/// deleted, so stale photos do not accumulate. If any step fails, the animal keeps its
+/// previous image and the new file is removed again. The previous image is kept if
+/// another animal still uses it.
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal
+///
+/// # Returns
+/// * `Ok(Some(Animal))` - The animal with its new image and version
+/// * `Ok(None)` - If the user cancels the file selection
+/// * `Err(CommandError)` - An error message if the user is not staff, the animal does not exist or the image could not be replaced
+#[tauri::command]
+async fn replace_animal_image(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Option<Animal>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Check the animal exists before asking the user for a file
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.query_animal_by_id(&id)).await? {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(CommandError::not_found(format!(
+                "Animal with ID {} not found",
+                animal_id
+            )))
+        }
+        Err(e) => return Err(CommandError::from_service("Failed to get animal", e)),
+    }
+
+    // Lazily initialize the file service
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+
+    let owner = StorageOwner::Animal(animal_id.clone());
+    let new_path = match file_service
+        .upload_file(&app_handle, Some(UploadKind::AnimalPhoto), Some(&owner))
+        .await
+    {
+        Ok(Some(path)) => path.to_string_lossy().into_owned(),
+        Ok(None) => return Ok(None),
+        Err(e) => return Err(CommandError::from_service("Failed to upload image", e)),
+    };
+
+    // Point the animal at the new image, removing the new file if that fails
+    let id = animal_id.clone();
+    let image_path = new_path.clone();
+    let replaced = run_database_task(&state, &app_handle, move |db| {
+        db.set_animal_image(&id, Some(&image_path))
+    })
+    .await
+    .and_then(|result| {
+        result.map_err(|e| CommandError::from_service("Failed to update animal image", e))
+    });
+    let (animal, previous_path) = match replaced {
+        Ok(Some(replaced)) => replaced,
+        Ok(None) => {
+            let _ = file_service.delete_file(&new_path).await;
+            return Err(CommandError::not_found(format!(
+                "Animal with ID {} not found",
+                animal_id
+            )));
+        }
+        Err(e) => {
+            let _ = file_service.delete_file(&new_path).await;
+            return Err(e);
+        }
+    };
+
+    // Delete the previous image unless it was not uploaded or another animal still uses it
+    if let Some(previous_path) =
+        previous_path.filter(|path| file_service.is_upload(Path::new(path)))
+    {
+        let path = previous_path.clone();
+        let in_use = run_database_task(&state, &app_handle, move |db| {
+            db.is_image_path_in_use(&path)
+        })
+        .await?
+        .map_err(|e| CommandError::from_service("Failed to check previous image", e))?;
+        if !in_use {
+            if let Err(e) = file_service.delete_file(&previous_path).await {
+                // Put the previous image back so the animal is left as it was
+                let id = animal_id.clone();
+                let restored = previous_path.clone();
+                if let Err(restore_error) = run_database_task(&state, &app_handle, move |db| {
+                    db.set_animal_image(&id, Some(&restored))
+                })
+                .await
+                .and_then(|result| {
+                    result.map_err(|e| {
+                        CommandError::from_service("Failed to restore previous image", e)
+                    })
+                }) {
+                    log::error!(
+                        "Failed to restore image of animal {}: {}",
+                        animal_id,
+                        restore_error
+                    );
+                    return Err(CommandError::from_service(
+                        "Failed to delete previous image",
+                        e,
+                    ));
+                }
+                let _ = file_service.delete_file(&new_path).await;
+                return Err(CommandError::from_service(
+                    "Failed to delete previous image",
+                    e,
+                ));
+            }
+        }
+    }
+
+    emit_data_change(&app_handle, ANIMAL_UPDATED_EVENT, &animal_id);
+    Ok(Some(animal))
+}
+
+/// Command to delete an animal from the database
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to delete
+/// * `cascade` - Whether to also delete the adoption requests for the animal
+///
+/// # Returns
+/// * `Ok(bool)` - True if animal was found and deleted, false if not found
+/// * `Err(CommandError)` - An error message if the deletion fails
+#[tauri::command]
+async fn delete_animal(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+    cascade: bool,
+) -> Result<bool, CommandError> {
+    // Delete animal
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.delete_animal(&id, cascade)
+    })
+    .await?
+    {
+        Ok(deleted) => {
+            if deleted {
+                emit_data_change(&app_handle, ANIMAL_DELETED_EVENT, &animal_id);
+                record_activity(&state, &app_handle, AuditAction::AnimalDeleted, &animal_id).await;
+            }
+            Ok(deleted)
+        }
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to delete animal with ID {}", animal_id),
+            e,
+        )),
+    }
+}
+
+// ==================== ADOPTION REQUEST TABLE COMMANDS ====================
+
+/// Command to retrieve a specific adoption request by ID
+///
+/// Only staff members and the requester see the requester's personal details.
+///
+/// # Arguments
+/// * `request_id` - The ID of the adoption request to retrieve
+///
+/// # Returns
+/// * `Ok(Some(AdoptionRequestView))` - The adoption request data if found
+/// * `Ok(None)` - If no request with the given ID exists
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_adoption_request_by_id(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request_id: String,
+) -> Result<Option<AdoptionRequestView>, CommandError> {
+    let viewer = current_user(&state, &app_handle).await?;
+
+    // Query adoption request by ID
+    let id = request_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_adoption_request_by_id(&id)
+    })
+    .await?
+    {
+        Ok(request) => Ok(request.map(|request| adoption_request_view(request, viewer.as_ref()))),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to retrieve adoption request with ID {}", request_id),
+            e,
+        )),
+    }
+}
+
+/// Command to check an adoption request before it is created or updated
+///
+/// The same checks run when the request is stored, so this only lets the frontend show
+/// every problem next to its field before submitting.
+///
+/// # Arguments
+/// * `request` - The adoption request data to check
+///
+/// # Returns
+/// * `Ok(Vec<FieldError>)` - The invalid fields, empty if the request is valid
+#[tauri::command]
+fn validate_adoption_request(request: AdoptionRequest) -> Result<Vec<FieldError>, CommandError> {
+    Ok(validation::validate_adoption_request(&request))
+}
+
+/// Command to insert a new adoption request into the database
+///
+/// # Arguments
+/// * `request` - The adoption request data to insert; an ID is generated if it is empty
+/// * `link_profile` - Whether to file the request under the logged-in user and fill its
+///   contact details from their profile
+///
+/// # Returns
+/// * `Ok(CreateRequestResult)` - Whether the request was stored or rejected as a duplicate pending request
+/// * `Err(CommandError)` - An error message if the insertion fails, or if the request should be
+///   linked to a profile and nobody is logged in or the user has no profile
+#[tauri::command]
+async fn create_adoption_request(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    mut request: AdoptionRequest,
+    link_profile: Option<bool>,
+) -> Result<CreateRequestResult, CommandError> {
+    if request.id.trim().is_empty() {
+        request.id = Uuid::new_v4().to_string();
+    }
+
+    let link_profile = link_profile.unwrap_or(false);
+    if link_profile {
+        request.username = require_logged_in_user(&state, &app_handle).await?.username;
+    }
+
+    // Insert adoption request
+    let id = request.id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        if link_profile && !db.fill_request_from_profile(&mut request)? {
+            return Err(anyhow!("You have not saved a profile yet"));
+        }
+        let result = db.insert_adoption_request(&request)?;
+        if result == CreateRequestResult::Success {
+            // Logs a warning for staff if the requester matches a banned adopter
+            db.query_banned_adopter_matches(&request.id)?;
+        }
+        Ok(result)
+    })
+    .await?
+    {
+        Ok(result) => {
+            if result == CreateRequestResult::Success {
+                score_adoption_request(&state, &app_handle, &id).await;
+                emit_data_change(&app_handle, REQUEST_CREATED_EVENT, &id);
+            }
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service(
+            "Failed to create adoption request",
+            e,
+        )),
+    }
+}
+
+/// Command to update an existing adoption request in the database
+///
+/// # Arguments
+/// * `request` - The updated adoption request data
+///
+/// # Returns
+/// * `Ok(bool)` - True if request was found and updated, false if not found
+/// * `Err(CommandError)` - An error message if the request would become approved or on
+///   trial, which only `approve_adoption_request` may do, or the update fails
+#[tauri::command]
+async fn update_adoption_request(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request: AdoptionRequest,
+) -> Result<bool, CommandError> {
+    // Update adoption request
+    let id = request.id.clone();
+    let action = if request.status == RequestStatus::Rejected {
+        AuditAction::RequestRejected
+    } else {
+        AuditAction::RequestUpdated
+    };
+    match run_database_task(&state, &app_handle, move |db| {
+        db.update_adoption_request(&request)
+    })
+    .await?
+    {
+        Ok(updated) => {
+            if updated {
+                score_adoption_request(&state, &app_handle, &id).await;
+                emit_data_change(&app_handle, REQUEST_UPDATED_EVENT, &id);
+                record_activity(&state, &app_handle, action, &id).await;
+            }
+            Ok(updated)
+        }
+        Err(e) => Err(CommandError::from_service(
+            "Failed to update adoption request",
+            e,
+        )),
+    }
+}
+
+/// Command to delete an adoption request from the database
+///
+/// # Arguments
+/// * `request_id` - The ID of the adoption request to delete
+///
+/// # Returns
+/// * `Ok(bool)` - True if request was found and deleted, false if not found
+/// * `Err(CommandError)` - An error message if the deletion fails
+#[tauri::command]
+async fn delete_adoption_request(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request_id: String,
+) -> Result<bool, CommandError> {
+    // Delete adoption request
+    let id = request_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.delete_adoption_request(&id)
+    })
+    .await?
+    {
+        Ok(deleted) => {
+            if deleted {
+                emit_data_change(&app_handle, REQUEST_DELETED_EVENT, &request_id);
+                record_activity(
+                    &state,
+                    &app_handle,
+                    AuditAction::RequestDeleted,
+                    &request_id,
+                )
+                .await;
+            }
+            Ok(deleted)
+        }
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to delete adoption request with ID {}", request_id),
+            e,
+        )),
+    }
+}
+
+/// Command to approve an adoption request, marking its animal as adopted, or putting it
+/// on trial with the adopter until the trial ends; an interview or positive reference is
+/// required first when the settings ask for it
+///
+/// # Arguments
+/// * `request_id` - The ID of the adoption request to approve
+/// * `allow_split_bond` - Whether to approve even if a bonded partner would be left behind
+/// * `trial_end_timestamp` - When the trial period ends, or None to adopt right away
+///
+/// # Returns
+/// * `Ok(ApproveRequestResult)` - The outcome of the approval
+/// * `Err(CommandError)` - An error message if the user is not staff, the trial end is not
+///   in the future or the approval fails
+#[tauri::command]
+async fn approve_adoption_request(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request_id: String,
+    allow_split_bond: bool,
+    trial_end_timestamp: Option<i64>,
+) -> Result<ApproveRequestResult, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let requirements = match run_settings_task(&state, &app_handle, |settings| {
+        settings.approval_requirements()
+    })
+    .await?
+    {
+        Ok(requirements) => requirements,
+        Err(e) => {
+            return Err(CommandError::from_service(
+                "Failed to retrieve approval requirements",
+                e,
+            ))
+        }
+    };
+
+    // Approve adoption request, looking up its animal and the other pending requests for
+    // it, which the approval rejects, to announce them
+    let id = request_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        let animal_id = db
+            .query_adoption_request_by_id(&id)?
+            .map(|request| request.animal_id);
+        let pending_ids: Vec<String> = match &animal_id {
+            Some(animal_id) => db
+                .query_adoption_requests_by_animal_id(animal_id)?
+                .into_iter()
+                .filter(|request| request.id != id && request.status == RequestStatus::Pending)
+                .map(|request| request.id)
+                .collect(),
+            None => Vec::new(),
+        };
+        let result =
+            db.approve_adoption_request(&id, allow_split_bond, trial_end_timestamp, &requirements)?;
+        Ok((result, animal_id, pending_ids))
+    })
+    .await?
+    {
+        Ok((result, animal_id, rejected_ids)) => {
+            let action = match result {
+                ApproveRequestResult::Approved => Some(AuditAction::RequestApproved),
+                ApproveRequestResult::TrialStarted => Some(AuditAction::TrialStarted),
+                _ => None,
+            };
+            if let Some(action) = action {
+                emit_data_change(&app_handle, REQUEST_UPDATED_EVENT, &request_id);
+                for rejected_id in &rejected_ids {
+                    emit_data_change(&app_handle, REQUEST_UPDATED_EVENT, rejected_id);
+                }
+                if let Some(animal_id) = animal_id {
+                    emit_data_change(&app_handle, ANIMAL_UPDATED_EVENT, &animal_id);
+                }
+                record_activity(&state, &app_handle, action, &request_id).await;
+            }
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to approve adoption request with ID {}", request_id),
+            e,
+        )),
+    }
+}
+
+/// Command to score every adoption request again for screening, such as after the
+/// screening settings changed (staff only)
+///
+/// # Returns
+/// * `Ok(usize)` - Number of requests scored
+/// * `Err(CommandError)` - An error message if the user is not staff or scoring fails
+#[tauri::command]
+async fn recompute_screening_scores(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<usize, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let config = screening_config(&state, &app_handle).await?;
+    match run_database_task(&state, &app_handle, move |db| {
+        db.recompute_screening_scores(&config)
+    })
+    .await?
+    {
+        Ok(count) => Ok(count),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to recompute screening scores",
+            e,
+        )),
+    }
+}
+
+/// Command to list adoption requests matching the given filters, newest first
+///
+/// # Arguments
+/// * `filters` - The status, submission date range and animal species to filter by
+///
+/// # Returns
+/// * `Ok(Vec<AdoptionRequestSummary>)` - List of adoption request summaries if successful
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_adoption_requests(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    filters: AdoptionRequestFilters,
+) -> Result<Vec<AdoptionRequestSummary>, CommandError> {
+    // Only staff members may list every applicant's requests; customers list their own
+    // with get_adoption_requests_by_username
+    require_staff(&state, &app_handle).await?;
+
+    // Query adoption requests with filters
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_adoption_requests(&filters)
+    })
+    .await?
+    {
+        Ok(requests) => Ok(requests),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve adoption requests",
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve adoption requests together with the animals they are for
+///
+/// # Arguments
+/// * `filters` - Optional map of filter criteria and values applied to the animals
+/// * `status` - Only include requests with this status, if given
+///
+/// # Returns
+/// * `Ok(Vec<AnimalAdoptionRequest>)` - Requests with their animal summaries if successful
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_adoption_requests_with_animals(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
+    status: Option<RequestStatus>,
+) -> Result<Vec<AnimalAdoptionRequest>, CommandError> {
+    // Only staff members may see every applicant's details
+    require_staff(&state, &app_handle).await?;
+
+    // Query adoption requests joined with animals
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_adoption_requests_with_animals(filters, status)
+    })
+    .await?
+    {
+        Ok(requests) => Ok(requests),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve adoption requests with animals",
+            e,
+        )),
+    }
+}
+
+/// Command to count the adoption requests that are still pending review
+///
+/// # Returns
+/// * `Ok(i64)` - The number of pending adoption requests if successful
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_pending_request_count(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<i64, CommandError> {
+    // Count pending adoption requests
+    match run_database_task(&state, &app_handle, |db| {
+        db.count_pending_adoption_requests(None)
+    })
+    .await?
+    {
+        Ok(count) => Ok(count),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to count pending adoption requests",
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the distinct countries of the requesters in the database
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - Sorted list of countries if successful
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_distinct_countries(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<String>, CommandError> {
+    // Query distinct countries
+    match run_database_task(&state, &app_handle, |db| db.query_distinct_countries()).await? {
+        Ok(countries) => Ok(countries),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve countries",
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve all adoption requests from the database for a specific animal ID
+///
+/// Only staff members see every applicant's personal details; other users only see
+/// their own, and the status and timestamps of the rest.
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to retrieve requests for
+///
+/// # Returns
+/// * `Ok(Vec<AdoptionRequestView>)` - List of adoption requests if successful
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_adoption_requests_by_animal_id(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Vec<AdoptionRequestView>, CommandError> {
+    let viewer = current_user(&state, &app_handle).await?;
+
+    // Query adoption requests by animal ID
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_adoption_requests_by_animal_id(&id)
+    })
+    .await?
+    {
+        Ok(requests) => Ok(requests
+            .into_iter()
+            .map(|request| adoption_request_view(request, viewer.as_ref()))
+            .collect()),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to retrieve adoption requests for animal ID {}",
+                animal_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve all adoption requests from the database for a specific user name
+///
+/// Customers may only retrieve their own requests.
+///
+/// # Arguments
+/// * `username` - The user name to retrieve requests for
+///
+/// # Returns
+/// * `Ok(Vec<AdoptionRequest>)` - List of adoption requests if successful
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_adoption_requests_by_username(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    username: String,
+) -> Result<Vec<AdoptionRequest>, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+    if !user.role.is_staff() && user.username != username {
+        return Err(CommandError::permission_denied(
+            "Customers can only view their own adoption requests",
+        ));
+    }
+
+    // Query adoption requests by user name
+    let name = username.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_adoption_requests_by_username(&name)
+    })
+    .await?
+    {
+        Ok(requests) => Ok(requests),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to retrieve adoption requests for user name {}",
+                username
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to record the return of an adopted animal, making it available again
+///
+/// # Arguments
+/// * `adoption_request_id` - The ID of the approved or trial adoption request being returned
+/// * `reason` - Why the animal was returned
+///
+/// # Returns
+/// * `Ok(ProcessReturnResult)` - Whether the return was recorded, or why it could not be
+/// * `Err(CommandError)` - An error message if the user is not staff or the operation fails
+#[tauri::command]
+async fn process_return(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    adoption_request_id: String,
+    reason: String,
+) -> Result<ProcessReturnResult, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Process adoption return
+    let id = adoption_request_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.process_return(&id, &reason)
+    })
+    .await?
+    {
+        Ok(result) => {
+            if result == ProcessReturnResult::Success {
+                emit_data_change(&app_handle, REQUEST_UPDATED_EVENT, &adoption_request_id);
+                record_activity(
+                    &state,
+                    &app_handle,
+                    AuditAction::ReturnProcessed,
+                    &adoption_request_id,
+                )
+                .await;
+            }
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to process return of adoption request with ID {}",
+                adoption_request_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to turn a trial adoption into a final adoption, marking its animal as adopted
+/// (staff only)
+///
+/// # Arguments
+/// * `adoption_request_id` - The ID of the adoption request on trial
+///
+/// # Returns
+/// * `Ok(FinalizeTrialResult)` - Whether the adoption was finalized, or why it could not be
+/// * `Err(CommandError)` - An error message if the user is not staff or the operation fails
+#[tauri::command]
+async fn finalize_trial_adoption(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    adoption_request_id: String,
+) -> Result<FinalizeTrialResult, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Finalize trial adoption, looking up its animal to announce the adoption
+    let id = adoption_request_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        let result = db.finalize_trial_adoption(&id)?;
+        let animal_id = db
+            .query_adoption_request_by_id(&id)?
+            .map(|request| request.animal_id);
+        Ok((result, animal_id))
+    })
+    .await?
+    {
+        Ok((result, animal_id)) => {
+            if result == FinalizeTrialResult::Finalized {
+                emit_data_change(&app_handle, REQUEST_UPDATED_EVENT, &adoption_request_id);
+                if let Some(animal_id) = animal_id {
+                    emit_data_change(&app_handle, ANIMAL_UPDATED_EVENT, &animal_id);
+                }
+                record_activity(
+                    &state,
+                    &app_handle,
+                    AuditAction::TrialFinalized,
+                    &adoption_request_id,
+                )
+                .await;
+            }
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to finalize trial adoption of adoption request with ID {}",
+                adoption_request_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the trial adoptions that are still ongoing, ending soonest first
+/// (staff only)
+///
+/// # Returns
+/// * `Ok(Vec<TrialAdoption>)` - List of ongoing trials
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_ongoing_trial_adoptions(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<TrialAdoption>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    match run_database_task(&state, &app_handle, |db| db.query_ongoing_trial_adoptions()).await? {
+        Ok(trials) => Ok(trials),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve ongoing trial adoptions",
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the returns of a specific animal ID
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to retrieve returns for
+///
+/// # Returns
+/// * `Ok(Vec<AdoptionReturn>)` - List of returns, most recent first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_adoption_returns_by_animal_id(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Vec<AdoptionReturn>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Query adoption returns by animal ID
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_adoption_returns_by_animal_id(&id)
+    })
+    .await?
+    {
+        Ok(returns) => Ok(returns),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to retrieve adoption returns for animal ID {}",
+                animal_id
+            ),
+            e,
+        )),
+    }
+}
+
+// ==================== REQUEST CHECK COMMANDS ====================
+
+/// Command to retrieve the phone interviews and reference checks recorded for an
+/// adoption request
+///
+/// # Arguments
+/// * `request_id` - The ID of the adoption request
+///
+/// # Returns
+/// * `Ok(RequestChecks)` - Interviews, latest first, and reference checks
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_request_checks(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request_id: String,
+) -> Result<RequestChecks, CommandError> {
+    // Only staff members may see interview notes and referees
+    require_staff(&state, &app_handle).await?;
+
+    let id = request_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.query_request_checks(&id)).await? {
+        Ok(checks) => Ok(checks),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to retrieve checks for adoption request with ID {}",
+                request_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to record a phone interview the logged-in staff member held with the applicant
+/// of an adoption request
+///
+/// # Arguments
+/// * `request_id` - The ID of the adoption request
+/// * `interview_timestamp` - Timestamp when the interview was held
+/// * `notes` - What was discussed and the impression the applicant made
+///
+/// # Returns
+/// * `Ok(RecordCheckResult)` - Recorded, or RequestNotFound
+/// * `Err(CommandError)` - An error message if the user is not staff, the interview is
+///   not valid or the insertion fails
+#[tauri::command]
+async fn record_request_interview(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request_id: String,
+    interview_timestamp: i64,
+    notes: String,
+) -> Result<RecordCheckResult, CommandError> {
+    let interviewer = require_staff(&state, &app_handle).await?;
+
+    let interview = RequestInterview {
+        id: String::new(),
+        request_id: request_id.clone(),
+        interviewer: interviewer.username,
+        interview_timestamp,
+        notes,
+    };
+    match run_database_task(&state, &app_handle, move |db| {
+        db.insert_request_interview(&interview)
+    })
+    .await?
+    {
+        Ok(result) => {
+            if result == RecordCheckResult::Recorded {
+                record_activity(
+                    &state,
+                    &app_handle,
+                    AuditAction::InterviewRecorded,
+                    &request_id,
+                )
+                .await;
+            }
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service("Failed to record interview", e)),
+    }
+}
+
+/// Command to record a reference given by the applicant of an adoption request, checked
+/// by the logged-in staff member
+///
+/// # Arguments
+/// * `request_id` - The ID of the adoption request
+/// * `referee_name` - Name of the referee
+/// * `referee_contact` - Email address or telephone number of the referee
+/// * `outcome` - What the referee said, or Pending if they were not reached yet
+/// * `notes` - Anything worth knowing about what the referee said
+///
+/// # Returns
+/// * `Ok(RecordCheckResult)` - Recorded, or RequestNotFound
+/// * `Err(CommandError)` - An error message if the user is not staff, the reference is
+///   not valid or the insertion fails
+#[tauri::command]
+async fn record_reference_check(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request_id: String,
+    referee_name: String,
+    referee_contact: String,
+    outcome: ReferenceOutcome,
+    notes: String,
+) -> Result<RecordCheckResult, CommandError> {
+    let checker = require_staff(&state, &app_handle).await?;
+
+    let check = ReferenceCheck {
+        id: String::new(),
+        request_id: request_id.clone(),
+        referee_name,
+        referee_contact,
+        outcome,
+        notes,
+        checked_by: checker.username,
+        checked_timestamp: Utc::now().timestamp(),
+    };
+    match run_database_task(&state, &app_handle, move |db| {
+        db.insert_reference_check(&check)
+    })
+    .await?
+    {
+        Ok(result) => {
+            if result == RecordCheckResult::Recorded {
+                record_activity(
+                    &state,
+                    &app_handle,
+                    AuditAction::ReferenceChecked,
+                    &request_id,
+                )
+                .await;
+            }
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service(
+            "Failed to record reference check",
+            e,
+        )),
+    }
+}
+
+/// Command to record the outcome of a reference check, such as once the referee was reached
+///
+/// # Arguments
+/// * `check_id` - The ID of the reference check
+/// * `outcome` - What the referee said about the applicant
+/// * `notes` - Anything worth knowing about what the referee said
+///
+/// # Returns
+/// * `Ok(ReferenceCheck)` - The updated reference check
+/// * `Err(CommandError)` - An error message if the user is not staff, the reference check
+///   does not exist or the update fails
+#[tauri::command]
+async fn update_reference_check_outcome(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    check_id: String,
+    outcome: ReferenceOutcome,
+    notes: String,
+) -> Result<ReferenceCheck, CommandError> {
+    let checker = require_staff(&state, &app_handle).await?;
+
+    let id = check_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.update_reference_check_outcome(&id, outcome, &notes, &checker.username)
+    })
+    .await?
+    {
+        Ok(Some(check)) => {
+            record_activity(
+                &state,
+                &app_handle,
+                AuditAction::ReferenceChecked,
+                &check.request_id,
+            )
+            .await;
+            Ok(check)
+        }
+        Ok(None) => Err(CommandError::not_found(format!(
+            "Reference check with ID {} not found",
+            check_id
+        ))),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to update reference check",
+            e,
+        )),
+    }
+}
+
+// ==================== ANIMAL NOTE COMMANDS ====================
+
+/// Command to retrieve the notes about a specific animal ID
+///
+/// Notes marked as internal are only returned to staff members.
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to retrieve notes for
+///
+/// # Returns
+/// * `Ok(Vec<AnimalNote>)` - List of notes visible to the current user, most recent first
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_animal_notes(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Vec<AnimalNote>, CommandError> {
+    // Only staff members may see internal notes
+    let include_internal = require_staff(&state, &app_handle).await.is_ok();
+
+    // Query notes by animal ID
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_animal_notes_by_animal_id(&id, include_internal)
+    })
+    .await?
+    {
+        Ok(notes) => Ok(notes),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to retrieve notes for animal ID {}", animal_id),
+            e,
+        )),
+    }
+}
+
+/// Command to add a note about an animal, written by the logged-in staff member
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal the note is about
+/// * `category` - Category of the note
+/// * `content` - Text of the note
+/// * `internal` - Whether the note is for staff only
+///
+/// # Returns
+/// * `Ok(())` - If the note was successfully added
+/// * `Err(CommandError)` - An error message if the user is not staff or the insertion fails
+#[tauri::command]
+async fn add_animal_note(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+    category: NoteCategory,
+    content: String,
+    internal: bool,
+) -> Result<(), CommandError> {
+    // Only staff members may write notes
+    let author = require_staff(&state, &app_handle).await?;
+
+    // Insert note
+    let note = AnimalNote {
+        id: String::new(),
+        animal_id: animal_id.clone(),
+        author_username: author.username,
+        category,
+        content,
+        internal,
+        created_timestamp: chrono::Utc::now().timestamp(),
+    };
+    match run_database_task(&state, &app_handle, move |db| db.insert_animal_note(&note)).await? {
+        Ok(()) => {
+            record_activity(&state, &app_handle, AuditAction::NoteAdded, &animal_id).await;
+            Ok(())
+        }
+        Err(e) => Err(CommandError::from_service("Failed to add note", e)),
+    }
+}
+
+/// Command to delete a note about an animal
+///
+/// # Arguments
+/// * `note_id` - The ID of the note to delete
+///
+/// # Returns
+/// * `Ok(bool)` - True if the note was found and deleted, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the deletion fails
+#[tauri::command]
+async fn delete_animal_note(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    note_id: String,
+) -> Result<bool, CommandError> {
+    // Only staff members may delete notes
+    require_staff(&state, &app_handle).await?;
+
+    // Delete note
+    let id = note_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.delete_animal_note(&id)).await? {
+        Ok(deleted) => Ok(deleted),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to delete note with ID {}", note_id),
+            e,
+        )),
+    }
+}
+
+// ==================== VACCINATION COMMANDS ====================
+
+/// Command to retrieve the vaccinations of a specific animal ID
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to retrieve vaccinations for
+///
+/// # Returns
+/// * `Ok(Vec<Vaccination>)` - List of vaccinations, most recent first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_vaccinations(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Vec<Vaccination>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Query vaccinations by animal ID
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_vaccinations_by_animal_id(&id)
+    })
+    .await?
+    {
+        Ok(vaccinations) => Ok(vaccinations),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to retrieve vaccinations for animal ID {}",
+                animal_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to record a vaccination given to an animal, recorded by the logged-in
+/// staff member. Staff are reminded when the next dose comes due.
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal that was vaccinated
+/// * `vaccine` - Name of the vaccine
+/// * `administered_timestamp` - Timestamp when the vaccine was given
+/// * `due_timestamp` - Timestamp when the next dose is due, if one is needed
+/// * `contact_id` - ID of the veterinarian who gave the vaccine, if known
+///
+/// # Returns
+/// * `Ok(String)` - The ID of the recorded vaccination
+/// * `Err(CommandError)` - An error message if the user is not staff or the insertion fails
+#[tauri::command]
+async fn add_vaccination(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+    vaccine: String,
+    administered_timestamp: i64,
+    due_timestamp: Option<i64>,
+    contact_id: Option<String>,
+) -> Result<String, CommandError> {
+    let recorder = require_staff(&state, &app_handle).await?;
+
+    // Insert vaccination
+    let vaccination = Vaccination {
+        id: String::new(),
+        animal_id,
+        vaccine,
+        administered_timestamp,
+        due_timestamp,
+        recorded_by: recorder.username,
+        contact_id,
+    };
+    match run_database_task(&state, &app_handle, move |db| {
+        db.insert_vaccination(&vaccination)
+    })
+    .await?
+    {
+        Ok(id) => Ok(id),
+        Err(e) => Err(CommandError::from_service("Failed to add vaccination", e)),
+    }
+}
+
+/// Command to delete a vaccination recorded by mistake
+///
+/// # Arguments
+/// * `vaccination_id` - The ID of the vaccination to delete
+///
+/// # Returns
+/// * `Ok(bool)` - True if the vaccination was found and deleted, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the deletion fails
+#[tauri::command]
+async fn delete_vaccination(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    vaccination_id: String,
+) -> Result<bool, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Delete vaccination
+    let id = vaccination_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.delete_vaccination(&id)).await? {
+        Ok(deleted) => Ok(deleted),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to delete vaccination with ID {}", vaccination_id),
+            e,
+        )),
+    }
+}
+
+// ==================== FOSTER PLACEMENT COMMANDS ====================
+
+/// Command to retrieve all foster placements for a specific animal ID
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to retrieve placements for
+///
+/// # Returns
+/// * `Ok(Vec<FosterPlacement>)` - List of foster placements, most recent first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_foster_placements_by_animal_id(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Vec<FosterPlacement>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Query foster placements by animal ID
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_foster_placements_by_animal_id(&id)
+    })
+    .await?
+    {
+        Ok(placements) => Ok(placements),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to retrieve foster placements for animal ID {}",
+                animal_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to start a foster placement, marking the animal as fostered
+///
+/// # Arguments
+/// * `placement` - The foster placement to start
+///
+/// # Returns
+/// * `Ok(StartFosterResult)` - Whether the placement was started, or why it could not be
+/// * `Err(CommandError)` - An error message if the user is not staff or the operation fails
+#[tauri::command]
+async fn start_foster_placement(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    placement: FosterPlacement,
+) -> Result<StartFosterResult, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Start foster placement
+    let animal_id = placement.animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.start_foster_placement(&placement)
+    })
+    .await?
+    {
+        Ok(result) => {
+            if result == StartFosterResult::Success {
+                emit_data_change(&app_handle, ANIMAL_UPDATED_EVENT, &animal_id);
+            }
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service(
+            "Failed to start foster placement",
+            e,
+        )),
+    }
+}
+
+/// Command to move the planned end of an ongoing foster placement
+///
+/// # Arguments
+/// * `placement_id` - The ID of the placement to extend
+/// * `end_timestamp` - The new planned end of the placement
+///
+/// # Returns
+/// * `Ok(bool)` - True if an ongoing placement was found and extended, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the update fails
+#[tauri::command]
+async fn extend_foster_placement(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    placement_id: String,
+    end_timestamp: i64,
+) -> Result<bool, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Extend foster placement
+    let id = placement_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.extend_foster_placement(&id, end_timestamp)
+    })
+    .await?
+    {
+        Ok(extended) => Ok(extended),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to extend foster placement with ID {}", placement_id),
+            e,
+        )),
+    }
+}
+
+/// Command to end an ongoing foster placement, making the animal available again
+///
+/// # Arguments
+/// * `placement_id` - The ID of the placement to end
+///
+/// # Returns
+/// * `Ok(bool)` - True if an ongoing placement was found and ended, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the update fails
+#[tauri::command]
+async fn end_foster_placement(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    placement_id: String,
+) -> Result<bool, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // End foster placement
+    let id = placement_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.end_foster_placement(&id)).await? {
+        Ok(ended) => Ok(ended),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to end foster placement with ID {}", placement_id),
+            e,
+        )),
+    }
+}
+
+// ==================== INTAKE COMMANDS ====================
+
+/// Command to record how an animal came into the shelter, putting strays on hold for the
+/// days set in the settings
+///
+/// The logged-in staff member is recorded as the one who took the animal in.
+///
+/// # Arguments
+/// * `record` - The intake record to insert
+///
+/// # Returns
+/// * `Ok(())` - If the intake record was successfully inserted
+/// * `Err(CommandError)` - An error message if the user is not staff or the insertion fails
+#[tauri::command]
+async fn record_intake(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    mut record: IntakeRecord,
+) -> Result<(), CommandError> {
+    // Only staff members may take animals in
+    let staff = require_staff(&state, &app_handle).await?;
+    record.staff_username = staff.username;
+
+    let stray_hold_days = match run_settings_task(&state, &app_handle, |settings| {
+        settings.stray_hold_days()
+    })
+    .await?
+    {
+        Ok(days) => days,
+        Err(e) => {
+            return Err(CommandError::from_service(
+                "Failed to retrieve stray hold setting",
+                e,
+            ))
+        }
+    };
+
+    // Insert intake record
+    match run_database_task(&state, &app_handle, move |db| {
+        db.insert_intake_record(&record, stray_hold_days)
+    })
+    .await?
+    {
+        Ok(()) => Ok(()),
+        Err(e) => Err(CommandError::from_service("Failed to record intake", e)),
+    }
+}
+
+/// Command to retrieve the intake history of a specific animal ID
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to retrieve intake records for
+///
+/// # Returns
+/// * `Ok(Vec<IntakeRecord>)` - List of intake records, most recent first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_intake_history(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Vec<IntakeRecord>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Query intake records by animal ID
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_intake_records_by_animal_id(&id)
+    })
+    .await?
+    {
+        Ok(records) => Ok(records),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to retrieve intake history for animal ID {}",
+                animal_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the strays whose hold ends within the next seven days, ending
+/// soonest first (staff only)
+///
+/// # Returns
+/// * `Ok(Vec<StrayHold>)` - The strays coming off hold this week
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_stray_holds_ending_this_week(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<StrayHold>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let now = Utc::now().timestamp();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_stray_holds_ending(now, now + 7 * 24 * 60 * 60)
+    })
+    .await?
+    {
+        Ok(holds) => Ok(holds),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve strays coming off hold",
+            e,
+        )),
+    }
+}
+
+// ==================== KENNEL COMMANDS ====================
+
+/// Command to create a new kennel
+///
+/// # Arguments
+/// * `kennel` - The kennel data to insert
+///
+/// # Returns
+/// * `Ok(())` - If the kennel was successfully inserted
+/// * `Err(CommandError)` - An error message if the user is not staff or the insertion fails
+#[tauri::command]
+async fn create_kennel(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    kennel: Kennel,
+) -> Result<(), CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Insert kennel
+    match run_database_task(&state, &app_handle, move |db| db.insert_kennel(&kennel)).await? {
+        Ok(()) => Ok(()),
+        Err(e) => Err(CommandError::from_service("Failed to create kennel", e)),
+    }
+}
+
+/// Command to retrieve every kennel with the animals currently assigned to it
+///
+/// # Returns
+/// * `Ok(Vec<KennelOccupancy>)` - Occupancy of each kennel
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_kennel_occupancy(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<KennelOccupancy>, CommandError> {
+    // Query kennel occupancy
+    match run_database_task(&state, &app_handle, |db| db.query_kennel_occupancy()).await? {
+        Ok(occupancy) => Ok(occupancy),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve kennel occupancy",
+            e,
+        )),
+    }
+}
+
+/// Command to compare the capacity of the kennels with the number of animals at the
+/// shelter and in foster care, so managers know when to pause intake
+///
+/// # Returns
+/// * `Ok(CapacityReport)` - Capacities, counts and utilization, overall and by species
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_capacity_report(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<CapacityReport, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    match run_database_task(&state, &app_handle, |db| db.query_capacity_report()).await? {
+        Ok(report) => Ok(report),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve capacity report",
+            e,
+        )),
+    }
+}
+
+/// Command to assign an animal to a kennel, moving it out of its current kennel
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to assign
+/// * `kennel_id` - The ID of the kennel to assign the animal to
+///
+/// # Returns
+/// * `Ok(AssignKennelResult)` - Whether the animal was assigned, or why the assignment was refused
+/// * `Err(CommandError)` - An error message if the user is not staff or the assignment fails
+#[tauri::command]
+async fn assign_animal_to_kennel(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+    kennel_id: String,
+) -> Result<AssignKennelResult, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Assign animal to kennel
+    match run_database_task(&state, &app_handle, move |db| {
+        db.assign_animal_to_kennel(&animal_id, &kennel_id)
+    })
+    .await?
+    {
+        Ok(result) => Ok(result),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to assign animal to kennel",
+            e,
+        )),
+    }
+}
+
+/// Command to remove an animal from the kennel it is currently assigned to
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to remove
+///
+/// # Returns
+/// * `Ok(bool)` - True if the animal was in a kennel and was removed, false otherwise
+/// * `Err(CommandError)` - An error message if the user is not staff or the removal fails
+#[tauri::command]
+async fn remove_animal_from_kennel(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<bool, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Remove animal from kennel
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.remove_animal_from_kennel(&id)
+    })
+    .await?
+    {
+        Ok(removed) => Ok(removed),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to remove animal with ID {} from kennel", animal_id),
+            e,
+        )),
+    }
+}
+
+// ==================== LOCATION COMMANDS ====================
+
+/// Command to create a new location run by the organization
+///
+/// # Arguments
+/// * `location` - The location to create; an ID is generated if it is empty
+///
+/// # Returns
+/// * `Ok(String)` - The ID of the created location
+/// * `Err(CommandError)` - An error message if the user is not staff or the insertion fails
+#[tauri::command]
+async fn create_location(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    location: Location,
+) -> Result<String, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Insert location
+    match run_database_task(&state, &app_handle, move |db| db.insert_location(&location)).await? {
+        Ok(id) => Ok(id),
+        Err(e) => Err(CommandError::from_service("Failed to create location", e)),
+    }
+}
+
+/// Command to retrieve every location run by the organization
+///
+/// # Returns
+/// * `Ok(Vec<Location>)` - Locations ordered by name
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_locations(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<Location>, CommandError> {
+    // Query locations
+    match run_database_task(&state, &app_handle, |db| db.query_locations()).await? {
+        Ok(locations) => Ok(locations),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve locations",
+            e,
+        )),
+    }
+}
+
+/// Command to update the name and address of a location
+///
+/// # Arguments
+/// * `location` - The updated location
+///
+/// # Returns
+/// * `Ok(bool)` - True if the location was found and updated, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the update fails
+#[tauri::command]
+async fn update_location(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    location: Location,
+) -> Result<bool, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Update location
+    match run_database_task(&state, &app_handle, move |db| db.update_location(&location)).await? {
+        Ok(updated) => Ok(updated),
+        Err(e) => Err(CommandError::from_service("Failed to update location", e)),
+    }
+}
+
+/// Command to delete a location that no longer houses any animals
+///
+/// # Arguments
+/// * `location_id` - The ID of the location to delete
+///
+/// # Returns
+/// * `Ok(bool)` - True if the location was found and deleted, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff, the location still houses animals or the deletion fails
+#[tauri::command]
+async fn delete_location(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    location_id: String,
+) -> Result<bool, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Delete location
+    let id = location_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.delete_location(&id)).await? {
+        Ok(deleted) => Ok(deleted),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to delete location with ID {}", location_id),
+            e,
+        )),
+    }
+}
+
+// ==================== TRANSFER COMMANDS ====================
+
+/// Command to record an animal moving between locations, or to or from a partner organization
+///
+/// The logged-in staff member is recorded as the one who made the transfer.
+///
+/// # Arguments
+/// * `transfer` - The transfer to record
+///
+/// # Returns
+/// * `Ok(RecordTransferResult)` - Whether the transfer was recorded, or why it could not be
+/// * `Err(CommandError)` - An error message if the user is not staff or the operation fails
+#[tauri::command]
+async fn record_transfer(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    mut transfer: Transfer,
+) -> Result<RecordTransferResult, CommandError> {
+    // Only staff members may transfer animals
+    let staff = require_staff(&state, &app_handle).await?;
+    transfer.staff_username = staff.username;
+
+    // Record transfer
+    let animal_id = transfer.animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.record_transfer(&transfer)).await? {
+        Ok(result) => {
+            if result == RecordTransferResult::Success {
+                emit_data_change(&app_handle, ANIMAL_UPDATED_EVENT, &animal_id);
+            }
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service("Failed to record transfer", e)),
+    }
+}
+
+/// Command to retrieve the transfer history of a specific animal ID
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to retrieve transfers for
+///
+/// # Returns
+/// * `Ok(Vec<Transfer>)` - List of transfers, most recent first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_transfer_history(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Vec<Transfer>, CommandError> {
+    // Only staff members may see the transfer history
+    require_staff(&state, &app_handle).await?;
+
+    // Query transfers by animal ID
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_transfers_by_animal_id(&id)
+    })
+    .await?
+    {
+        Ok(transfers) => Ok(transfers),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to retrieve transfer history for animal ID {}",
+                animal_id
+            ),
+            e,
+        )),
+    }
+}
+
+// ==================== DECEASED RECORD COMMANDS ====================
+
+/// Command to record that an animal passed away or was euthanized, marking it as passed away
+/// and rejecting its pending adoption requests, whose applicants are emailed as for any
+/// other rejection
+///
+/// # Arguments
+/// * `record` - The date, cause, who authorized it and any notes
+///
+/// # Returns
+/// * `Ok(RecordDeathResult)` - Whether the death was recorded, or why it could not be
+/// * `Err(CommandError)` - An error message if the user is not staff, a required field is
+///   missing or the operation fails
+#[tauri::command]
+async fn record_death(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    record: DeceasedRecord,
+) -> Result<RecordDeathResult, CommandError> {
+    // Only staff members may record the passing of an animal
+    require_staff(&state, &app_handle).await?;
+
+    // Record death, remembering the pending requests it rejects to announce them
+    let animal_id = record.animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        let pending_ids: Vec<String> = db
+            .query_adoption_requests_by_animal_id(&record.animal_id)?
+            .into_iter()
+            .filter(|request| request.status == RequestStatus::Pending)
+            .map(|request| request.id)
+            .collect();
+        Ok((db.record_death(&record)?, pending_ids))
+    })
+    .await?
+    {
+        Ok((result, rejected_ids)) => {
+            if result == RecordDeathResult::Success {
+                emit_data_change(&app_handle, ANIMAL_UPDATED_EVENT, &animal_id);
+                for request_id in &rejected_ids {
+                    emit_data_change(&app_handle, REQUEST_UPDATED_EVENT, request_id);
+                }
+                record_activity(&state, &app_handle, AuditAction::DeathRecorded, &animal_id).await;
+            }
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to record death of animal with ID {}", animal_id),
+            e,
+        )),
+    }
+}
+
+// ==================== INVENTORY COMMANDS ====================
+
+/// Command to add a supply to the inventory
+///
+/// # Arguments
+/// * `item` - The item to add, with its starting stock; an ID is generated if it is empty
+///
+/// # Returns
+/// * `Ok(String)` - The ID of the created item
+/// * `Err(CommandError)` - An error message if the user is not staff, a field is invalid or
+///   the insertion fails
+#[tauri::command]
+async fn create_inventory_item(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    item: InventoryItem,
+) -> Result<String, CommandError> {
+    // Only staff members may manage the inventory
+    require_staff(&state, &app_handle).await?;
+
+    // Insert inventory item
+    match run_database_task(&state, &app_handle, move |db| {
+        db.insert_inventory_item(&item)
+    })
+    .await?
+    {
+        Ok(id) => Ok(id),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to create inventory item",
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve every supply in the inventory
+///
+/// # Returns
+/// * `Ok(Vec<InventoryItem>)` - Items ordered by name
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_inventory_items(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<InventoryItem>, CommandError> {
+    // Only staff members may view the inventory
+    require_staff(&state, &app_handle).await?;
+
+    // Query inventory items
+    match run_database_task(&state, &app_handle, |db| db.query_inventory_items()).await? {
+        Ok(items) => Ok(items),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve inventory items",
+            e,
+        )),
+    }
+}
+
+/// Command to update the name, unit and reorder threshold of a supply
+///
+/// # Arguments
+/// * `item` - The updated item; its quantity on hand is ignored
+///
+/// # Returns
+/// * `Ok(bool)` - True if the item was found and updated, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff, a field is invalid or
+///   the update fails
+#[tauri::command]
+async fn update_inventory_item(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    item: InventoryItem,
+) -> Result<bool, CommandError> {
+    // Only staff members may manage the inventory
+    require_staff(&state, &app_handle).await?;
+
+    // Update inventory item
+    match run_database_task(&state, &app_handle, move |db| {
+        db.update_inventory_item(&item)
+    })
+    .await?
+    {
+        Ok(updated) => Ok(updated),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to update inventory item",
+            e,
+        )),
+    }
+}
+
+/// Command to delete a supply that is no longer stocked
+///
+/// # Arguments
+/// * `item_id` - The ID of the item to delete
+///
+/// # Returns
+/// * `Ok(bool)` - True if the item was found and deleted, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the deletion fails
+#[tauri::command]
+async fn delete_inventory_item(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    item_id: String,
+) -> Result<bool, CommandError> {
+    // Only staff members may manage the inventory
+    require_staff(&state, &app_handle).await?;
+
+    // Delete inventory item
+    let id = item_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.delete_inventory_item(&id)).await? {
+        Ok(deleted) => Ok(deleted),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to delete inventory item with ID {}", item_id),
+            e,
+        )),
+    }
+}
+
+/// Command to add to or take from the stock of a supply, recording why
+///
+/// # Arguments
+/// * `item_id` - The ID of the item to adjust
+/// * `quantity_change` - Quantity to add, negative to take stock away
+/// * `reason` - Why the stock changed, such as a delivery or expired medicine
+///
+/// # Returns
+/// * `Ok(AdjustStockResult)` - The adjusted item, or why the stock could not be adjusted
+/// * `Err(CommandError)` - An error message if the user is not staff, the change is invalid
+///   or the operation fails
+#[tauri::command]
+async fn adjust_stock(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    item_id: String,
+    quantity_change: f64,
+    reason: String,
+) -> Result<AdjustStockResult, CommandError> {
+    // Only staff members may adjust the stock, and the adjustment records who did
+    let staff = require_staff(&state, &app_handle).await?;
+
+    // Adjust stock
+    let id = item_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.adjust_stock(&id, quantity_change, &reason, &staff.username)
+    })
+    .await?
+    {
+        Ok(result) => Ok(result),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to adjust stock of inventory item with ID {}",
+                item_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the changes made to the stock of a supply
+///
+/// # Arguments
+/// * `item_id` - The ID of the item to retrieve the adjustments for
+///
+/// # Returns
+/// * `Ok(Vec<InventoryAdjustment>)` - Adjustments, most recent first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_inventory_adjustments(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    item_id: String,
+) -> Result<Vec<InventoryAdjustment>, CommandError> {
+    // Only staff members may view the inventory
+    require_staff(&state, &app_handle).await?;
+
+    // Query inventory adjustments
+    let id = item_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_inventory_adjustments(&id)
+    })
+    .await?
+    {
+        Ok(adjustments) => Ok(adjustments),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to retrieve adjustments of inventory item with ID {}",
+                item_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the low-stock report: the supplies at or below their reorder threshold
+///
+/// # Returns
+/// * `Ok(Vec<InventoryItem>)` - Items to reorder, the most depleted first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_low_stock_items(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<InventoryItem>, CommandError> {
+    // Only staff members may view the inventory
+    require_staff(&state, &app_handle).await?;
+
+    // Query low stock items
+    match run_database_task(&state, &app_handle, |db| db.query_low_stock_items()).await? {
+        Ok(items) => Ok(items),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve low stock items",
+            e,
+        )),
+    }
+}
+
+// ==================== EXPENSE COMMANDS ====================
+
+/// Command to record money spent by the shelter
+///
+/// # Arguments
+/// * `expense` - The expense to record; an ID is generated if it is empty
+///
+/// # Returns
+/// * `Ok(String)` - The ID of the created expense
+/// * `Err(CommandError)` - An error message if the user is not staff, a field is invalid or
+///   the insertion fails
+#[tauri::command]
+async fn create_expense(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    expense: Expense,
+) -> Result<String, CommandError> {
+    // Only staff members may manage the expenses
+    require_staff(&state, &app_handle).await?;
+
+    // Insert expense
+    match run_database_task(&state, &app_handle, move |db| db.insert_expense(&expense)).await? {
+        Ok(id) => Ok(id),
+        Err(e) => Err(CommandError::from_service("Failed to create expense", e)),
+    }
+}
+
+/// Command to retrieve the expenses made during a period
+///
+/// # Arguments
+/// * `from_timestamp` - Start of the period, inclusive
+/// * `to_timestamp` - End of the period, exclusive
+///
+/// # Returns
+/// * `Ok(Vec<Expense>)` - Expenses, most recent first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_expenses(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    from_timestamp: i64,
+    to_timestamp: i64,
+) -> Result<Vec<Expense>, CommandError> {
+    // Only staff members may view the expenses
+    require_staff(&state, &app_handle).await?;
+
+    // Query expenses
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_expenses(from_timestamp, to_timestamp)
+    })
+    .await?
+    {
+        Ok(expenses) => Ok(expenses),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve expenses", e)),
+    }
+}
+
+/// Command to update an expense
+///
+/// # Arguments
+/// * `expense` - The updated expense
+///
+/// # Returns
+/// * `Ok(bool)` - True if the expense was found and updated, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff, a field is invalid or
+///   the update fails
+#[tauri::command]
+async fn update_expense(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    expense: Expense,
+) -> Result<bool, CommandError> {
+    // Only staff members may manage the expenses
+    require_staff(&state, &app_handle).await?;
+
+    // Update expense
+    match run_database_task(&state, &app_handle, move |db| db.update_expense(&expense)).await? {
+        Ok(updated) => Ok(updated),
+        Err(e) => Err(CommandError::from_service("Failed to update expense", e)),
+    }
+}
+
+/// Command to delete an expense recorded by mistake
+///
+/// # Arguments
+/// * `expense_id` - The ID of the expense to delete
+///
+/// # Returns
+/// * `Ok(bool)` - True if the expense was found and deleted, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the deletion fails
+#[tauri::command]
+async fn delete_expense(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    expense_id: String,
+) -> Result<bool, CommandError> {
+    // Only staff members may manage the expenses
+    require_staff(&state, &app_handle).await?;
+
+    // Delete expense
+    let id = expense_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.delete_expense(&id)).await? {
+        Ok(deleted) => Ok(deleted),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to delete expense with ID {}", expense_id),
+            e,
+        )),
+    }
+}
+
+/// Command to generate the budget report: how much was spent in each month of a year, by
+/// category, against how much was donated
+///
+/// # Arguments
+/// * `year` - The year to report on
+///
+/// # Returns
+/// * `Ok(BudgetReport)` - Expenses, donations and net of each of the twelve months
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_budget_report(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    year: i32,
+) -> Result<BudgetReport, CommandError> {
+    // Only staff members may view the expenses
+    require_staff(&state, &app_handle).await?;
+
+    // Compute budget report
+    match run_database_task(&state, &app_handle, move |db| db.query_budget_report(year)).await? {
+        Ok(report) => Ok(report),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to generate budget report for {}", year),
+            e,
+        )),
+    }
+}
+
+// ==================== PAYMENT COMMANDS ====================
+
+/// Command to retrieve the fees charged for an adoption request
+///
+/// # Arguments
+/// * `request_id` - The ID of the adoption request
+///
+/// # Returns
+/// * `Ok(Vec<Payment>)` - Payments, in the order they were charged
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_payments(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request_id: String,
+) -> Result<Vec<Payment>, CommandError> {
+    // Only staff members may see what adopters were charged
+    require_staff(&state, &app_handle).await?;
+
+    let id = request_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.query_payments(&id)).await? {
+        Ok(payments) => Ok(payments),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to retrieve payments for adoption request with ID {}",
+                request_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to charge a fee for an adoption request, such as the adoption fee
+///
+/// # Arguments
+/// * `request_id` - The ID of the adoption request
+/// * `amount` - Amount charged, in minor units of the shelter's default currency, such as satang
+/// * `description` - What the fee is for
+///
+/// # Returns
+/// * `Ok(RecordPaymentResult)` - Recorded, or RequestNotFound
+/// * `Err(CommandError)` - An error message if the user is not staff, the fee is not
+///   valid or the insertion fails
+#[tauri::command]
+async fn record_adoption_fee(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request_id: String,
+    amount: i64,
+    description: String,
+) -> Result<RecordPaymentResult, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let payment = Payment {
+        id: String::new(),
+        request_id: request_id.clone(),
+        amount,
+        description,
+        created_timestamp: Utc::now().timestamp(),
+        paid_timestamp: None,
+        invoice_number: None,
+        receipt_number: None,
+    };
+    match run_database_task(&state, &app_handle, move |db| db.insert_payment(&payment)).await? {
+        Ok(result) => {
+            if result == RecordPaymentResult::Recorded {
+                record_activity(
+                    &state,
+                    &app_handle,
+                    AuditAction::PaymentRecorded,
+                    &request_id,
+                )
+                .await;
+            }
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service("Failed to record fee", e)),
+    }
+}
+
+/// Command to record that a fee was paid, so a receipt can be generated for it
+///
+/// # Arguments
+/// * `payment_id` - The ID of the payment
+/// * `paid_timestamp` - Timestamp when the fee was paid
+///
+/// # Returns
+/// * `Ok(Payment)` - The updated payment
+/// * `Err(CommandError)` - An error message if the user is not staff, the payment does
+///   not exist or the update fails
+#[tauri::command]
+async fn mark_payment_paid(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    payment_id: String,
+    paid_timestamp: i64,
+) -> Result<Payment, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let id = payment_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.mark_payment_paid(&id, paid_timestamp)
+    })
+    .await?
+    {
+        Ok(Some(payment)) => {
+            record_activity(
+                &state,
+                &app_handle,
+                AuditAction::PaymentReceived,
+                &payment.request_id,
+            )
+            .await;
+            Ok(payment)
+        }
+        Ok(None) => Err(CommandError::not_found(format!(
+            "Payment with ID {} not found",
+            payment_id
+        ))),
+        Err(e) => Err(CommandError::from_service("Failed to record payment", e)),
+    }
+}
+
+// ==================== DONATION COMMANDS ====================
+
+/// Command to record a donation made by a donor in the contact directory
+///
+/// # Arguments
+/// * `donation` - The donation to record; an ID is generated if it is empty
+///
+/// # Returns
+/// * `Ok(String)` - The ID of the recorded donation
+/// * `Err(CommandError)` - An error message if the user is not staff, the donor does not
+///   exist, a field is invalid or the insertion fails
+#[tauri::command]
+async fn create_donation(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    donation: Donation,
+) -> Result<String, CommandError> {
+    // Only staff members may manage the donations
+    require_staff(&state, &app_handle).await?;
+
+    let contact_id = donation.contact_id.clone().unwrap_or_default();
+    match run_database_task(&state, &app_handle, move |db| db.insert_donation(&donation)).await? {
+        Ok(Some(id)) => Ok(id),
+        Ok(None) => Err(CommandError::not_found(format!(
+            "Donor with contact ID {} not found",
+            contact_id
+        ))),
+        Err(e) => Err(CommandError::from_service("Failed to record donation", e)),
+    }
+}
+
+/// Command to retrieve the donations made by a donor
+///
+/// # Arguments
+/// * `contact_id` - The ID of the donor's contact
+///
+/// # Returns
+/// * `Ok(Vec<Donation>)` - Donations, most recent first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_donations(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    contact_id: String,
+) -> Result<Vec<Donation>, CommandError> {
+    // Only staff members may view the donations
+    require_staff(&state, &app_handle).await?;
+
+    let id = contact_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.query_donations(&id)).await? {
+        Ok(donations) => Ok(donations),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to retrieve donations from contact {}", contact_id),
+            e,
+        )),
+    }
+}
+
+// ==================== CONTACT COMMANDS ====================
+
+/// Command to add a vet, rescue or transport partner to the contacts directory
+///
+/// # Arguments
+/// * `contact` - The contact to add; an ID is generated if it is empty
+///
+/// # Returns
+/// * `Ok(String)` - The ID of the created contact
+/// * `Err(CommandError)` - An error message if the user is not staff, a field is invalid or
+///   the insertion fails
+#[tauri::command]
+async fn create_contact(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    contact: Contact,
+) -> Result<String, CommandError> {
+    // Only staff members may manage the contacts directory
+    require_staff(&state, &app_handle).await?;
+
+    // Insert contact
+    match run_database_task(&state, &app_handle, move |db| db.insert_contact(&contact)).await? {
+        Ok(id) => Ok(id),
+        Err(e) => Err(CommandError::from_service("Failed to create contact", e)),
+    }
+}
+
+/// Command to retrieve the contacts directory
+///
+/// # Arguments
+/// * `kind` - Only retrieve contacts of this kind, or every contact if None
+///
+/// # Returns
+/// * `Ok(Vec<Contact>)` - Contacts ordered by name
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_contacts(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    kind: Option<ContactKind>,
+) -> Result<Vec<Contact>, CommandError> {
+    // Only staff members may view the contacts directory
+    require_staff(&state, &app_handle).await?;
+
+    // Query contacts
+    match run_database_task(&state, &app_handle, move |db| db.query_contacts(kind)).await? {
+        Ok(contacts) => Ok(contacts),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve contacts", e)),
+    }
+}
+
+/// Command to update the details of a contact
+///
+/// # Arguments
+/// * `contact` - The updated contact
+///
+/// # Returns
+/// * `Ok(bool)` - True if the contact was found and updated, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff, a field is invalid or
+///   the update fails
+#[tauri::command]
+async fn update_contact(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    contact: Contact,
+) -> Result<bool, CommandError> {
+    // Only staff members may manage the contacts directory
+    require_staff(&state, &app_handle).await?;
+
+    // Update contact
+    match run_database_task(&state, &app_handle, move |db| db.update_contact(&contact)).await? {
+        Ok(updated) => Ok(updated),
+        Err(e) => Err(CommandError::from_service("Failed to update contact", e)),
+    }
+}
+
+/// Command to delete a contact, unlinking the vaccinations and transfers it took part in
+///
+/// # Arguments
+/// * `contact_id` - The ID of the contact to delete
+///
+/// # Returns
+/// * `Ok(bool)` - True if the contact was found and deleted, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the deletion fails
+#[tauri::command]
+async fn delete_contact(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    contact_id: String,
+) -> Result<bool, CommandError> {
+    // Only staff members may manage the contacts directory
+    require_staff(&state, &app_handle).await?;
+
+    // Delete contact
+    let id = contact_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.delete_contact(&id)).await? {
+        Ok(deleted) => Ok(deleted),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to delete contact with ID {}", contact_id),
+            e,
+        )),
+    }
+}
+
+/// Command to link a recorded vaccination to the veterinarian who gave it
+///
+/// # Arguments
+/// * `vaccination_id` - The ID of the vaccination
+/// * `contact_id` - The ID of the contact, or None to unlink the vaccination
+///
+/// # Returns
+/// * `Ok(bool)` - True if the vaccination was found and linked, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the update fails
+#[tauri::command]
+async fn link_vaccination_to_contact(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    vaccination_id: String,
+    contact_id: Option<String>,
+) -> Result<bool, CommandError> {
+    // Only staff members may edit medical records
+    require_staff(&state, &app_handle).await?;
+
+    // Link vaccination
+    let id = vaccination_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.link_vaccination_to_contact(&id, contact_id.as_deref())
+    })
+    .await?
+    {
+        Ok(linked) => Ok(linked),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to link vaccination with ID {} to contact",
+                vaccination_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to link a recorded transfer to the contact at the partner organization or
+/// transport partner
+///
+/// # Arguments
+/// * `transfer_id` - The ID of the transfer
+/// * `contact_id` - The ID of the contact, or None to unlink the transfer
+///
+/// # Returns
+/// * `Ok(bool)` - True if the transfer was found and linked, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the update fails
+#[tauri::command]
+async fn link_transfer_to_contact(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    transfer_id: String,
+    contact_id: Option<String>,
+) -> Result<bool, CommandError> {
+    // Only staff members may edit transfers
+    require_staff(&state, &app_handle).await?;
+
+    // Link transfer
+    let id = transfer_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.link_transfer_to_contact(&id, contact_id.as_deref())
+    })
+    .await?
+    {
+        Ok(linked) => Ok(linked),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to link transfer with ID {} to contact", transfer_id),
+            e,
+        )),
+    }
+}
+
+// ==================== APPOINTMENT COMMANDS ====================
+
+/// Command for the logged-in user to book a slot to meet an animal, waiting for staff
+/// to confirm it
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to meet
+/// * `slot_timestamp` - Timestamp when the appointment starts
+///
+/// # Returns
+/// * `Ok(RequestAppointmentResult)` - Requested, or why the slot could not be booked
+/// * `Err(CommandError)` - An error message if nobody is logged in, the slot is not in the
+///   future or the booking fails
+#[tauri::command]
+async fn request_appointment(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+    slot_timestamp: i64,
+) -> Result<RequestAppointmentResult, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+
+    // Book appointment
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.request_appointment(&id, &user.username, slot_timestamp)
+    })
+    .await?
+    {
+        Ok(result) => Ok(result),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to book appointment with animal ID {}", animal_id),
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the appointments the logged-in user has booked
+///
+/// # Returns
+/// * `Ok(Vec<Appointment>)` - Appointments, latest slot first
+/// * `Err(CommandError)` - An error message if nobody is logged in or the query fails
+#[tauri::command]
+async fn get_my_appointments(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<Appointment>, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+
+    // Query appointments by username
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_appointments_by_username(&user.username)
+    })
+    .await?
+    {
+        Ok(appointments) => Ok(appointments),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve appointments",
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the appointments starting during a period, for staff to plan the visits
+///
+/// # Arguments
+/// * `from_timestamp` - Start of the period, inclusive
+/// * `to_timestamp` - End of the period, exclusive
+///
+/// # Returns
+/// * `Ok(Vec<Appointment>)` - Appointments, earliest slot first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_appointments(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    from_timestamp: i64,
+    to_timestamp: i64,
+) -> Result<Vec<Appointment>, CommandError> {
+    // Only staff members may see every appointment
+    require_staff(&state, &app_handle).await?;
+
+    // Query appointments
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_appointments(from_timestamp, to_timestamp)
+    })
+    .await?
+    {
+        Ok(appointments) => Ok(appointments),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve appointments",
+            e,
+        )),
+    }
+}
+
+/// Command to confirm a requested appointment. The customer is notified, and the
+/// notification is emitted as for `create_animal`.
+///
+/// # Arguments
+/// * `appointment_id` - The ID of the appointment
+///
+/// # Returns
+/// * `Ok(ConfirmAppointmentResult)` - Confirmed, or why the appointment could not be confirmed
+/// * `Err(CommandError)` - An error message if the user is not staff or the update fails
+#[tauri::command]
+async fn confirm_appointment(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    appointment_id: String,
+) -> Result<ConfirmAppointmentResult, CommandError> {
+    // Only staff members may confirm appointments
+    require_staff(&state, &app_handle).await?;
+
+    // Confirm appointment
+    let id = appointment_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.confirm_appointment(&id)).await? {
+        Ok((result, notifications)) => {
+            emit_notifications(&app_handle, &notifications);
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to confirm appointment with ID {}", appointment_id),
+            e,
+        )),
+    }
+}
+
+/// Command to cancel an appointment. Customers can only cancel their own appointments;
+/// when staff cancel one, the customer is notified as for `confirm_appointment`.
+///
+/// # Arguments
+/// * `appointment_id` - The ID of the appointment
+///
+/// # Returns
+/// * `Ok(CancelAppointmentResult)` - Cancelled, or why the appointment could not be cancelled
+/// * `Err(CommandError)` - An error message if nobody is logged in or the update fails
+#[tauri::command]
+async fn cancel_appointment(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    appointment_id: String,
+) -> Result<CancelAppointmentResult, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+    let customer = (!user.role.is_staff()).then_some(user.username);
+
+    // Cancel appointment
+    let id = appointment_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.cancel_appointment(&id, customer.as_deref())
+    })
+    .await?
+    {
+        Ok((result, notifications)) => {
+            emit_notifications(&app_handle, &notifications);
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to cancel appointment with ID {}", appointment_id),
+            e,
+        )),
+    }
+}
+
+// ==================== SURGERY COMMANDS ====================
+
+/// Command to book a spay or neuter surgery for an animal that is not neutered yet
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal having the surgery
+/// * `scheduled_timestamp` - Timestamp when the surgery is booked for
+/// * `contact_id` - The ID of the veterinarian performing the surgery, if known
+/// * `notes` - Anything worth knowing about the surgery
+///
+/// # Returns
+/// * `Ok(ScheduleSurgeryResult)` - Scheduled, or why the surgery could not be booked
+/// * `Err(CommandError)` - An error message if the user is not staff, the date is not valid
+///   or the booking fails
+#[tauri::command]
+async fn schedule_surgery(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+    scheduled_timestamp: i64,
+    contact_id: Option<String>,
+    notes: String,
+) -> Result<ScheduleSurgeryResult, CommandError> {
+    // Only staff members may book surgeries
+    require_staff(&state, &app_handle).await?;
+
+    // Book surgery
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.schedule_surgery(&id, scheduled_timestamp, contact_id.as_deref(), &notes)
+    })
+    .await?
+    {
+        Ok(result) => Ok(result),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to schedule surgery for animal with ID {}",
+                animal_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the surgeries booked for a specific animal
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal
+///
+/// # Returns
+/// * `Ok(Vec<Surgery>)` - Surgeries, latest booking first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_surgeries(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Vec<Surgery>, CommandError> {
+    // Only staff members may see surgeries
+    require_staff(&state, &app_handle).await?;
+
+    // Query surgeries by animal ID
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_surgeries_by_animal_id(&id)
+    })
+    .await?
+    {
+        Ok(surgeries) => Ok(surgeries),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to retrieve surgeries for animal with ID {}",
+                animal_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the report of surgeries that have not been performed yet
+///
+/// # Returns
+/// * `Ok(Vec<Surgery>)` - Surgeries, earliest booking first so overdue ones lead
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_upcoming_surgeries(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<Surgery>, CommandError> {
+    // Only staff members may see surgeries
+    require_staff(&state, &app_handle).await?;
+
+    // Query surgeries still to come
+    match run_database_task(&state, &app_handle, |db| db.query_upcoming_surgeries()).await? {
+        Ok(surgeries) => Ok(surgeries),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve upcoming surgeries",
+            e,
+        )),
+    }
+}
+
+/// Command to record that a surgery was performed, marking the animal as neutered
+///
+/// # Arguments
+/// * `surgery_id` - The ID of the surgery
+/// * `completed_timestamp` - Timestamp when the surgery was performed
+///
+/// # Returns
+/// * `Ok(CompleteSurgeryResult)` - The completed surgery, or why it could not be recorded
+/// * `Err(CommandError)` - An error message if the user is not staff, the date is not valid
+///   or the update fails
+#[tauri::command]
+async fn complete_surgery(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    surgery_id: String,
+    completed_timestamp: i64,
+) -> Result<CompleteSurgeryResult, CommandError> {
+    // Only staff members may record surgeries
+    require_staff(&state, &app_handle).await?;
+
+    // Complete surgery
+    let id = surgery_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.complete_surgery(&id, completed_timestamp)
+    })
+    .await?
+    {
+        Ok(result) => {
+            if let CompleteSurgeryResult::Completed(surgery) = &result {
+                emit_data_change(&app_handle, ANIMAL_UPDATED_EVENT, &surgery.animal_id);
+                record_activity(
+                    &state,
+                    &app_handle,
+                    AuditAction::SurgeryCompleted,
+                    &surgery.animal_id,
+                )
+                .await;
+            }
+            Ok(result)
+        }
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to complete surgery with ID {}", surgery_id),
+            e,
+        )),
+    }
+}
+
+/// Command to cancel a surgery that has not been performed yet
+///
+/// # Arguments
+/// * `surgery_id` - The ID of the surgery
+///
+/// # Returns
+/// * `Ok(bool)` - True if a surgery still to come was found and cancelled
+/// * `Err(CommandError)` - An error message if the user is not staff or the deletion fails
+#[tauri::command]
+async fn cancel_surgery(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    surgery_id: String,
+) -> Result<bool, CommandError> {
+    // Only staff members may cancel surgeries
+    require_staff(&state, &app_handle).await?;
+
+    // Cancel surgery
+    let id = surgery_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.cancel_surgery(&id)).await? {
+        Ok(found) => Ok(found),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to cancel surgery with ID {}", surgery_id),
+            e,
+        )),
+    }
+}
+
+// ==================== TRASH COMMANDS ====================
+
+/// Command to list the deleted animals and adoption requests waiting in the trash
+///
+/// # Returns
+/// * `Ok(Vec<TrashItem>)` - Deleted records, most recently deleted first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_trash(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<TrashItem>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Query records in the trash
+    match run_database_task(&state, &app_handle, |db| db.query_trash()).await? {
+        Ok(items) => Ok(items),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve trash", e)),
+    }
+}
+
+/// Command to undo the deletion of an animal or adoption request
+///
+/// # Arguments
+/// * `item_type` - Kind of the deleted record
+/// * `id` - ID of the deleted record
+///
+/// # Returns
+/// * `Ok(bool)` - True if the record was restored, false if it is not in the trash
+/// * `Err(CommandError)` - An error message if the user is not staff or the record cannot be restored
+#[tauri::command]
+async fn restore_from_trash(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    item_type: TrashItemType,
+    id: String,
+) -> Result<bool, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Restore record from trash
+    let item_id = id.clone();
+    let event = match item_type {
+        TrashItemType::Animal => ANIMAL_CREATED_EVENT,
+        TrashItemType::AdoptionRequest => REQUEST_CREATED_EVENT,
+    };
+    match run_database_task(&state, &app_handle, move |db| {
+        db.restore_from_trash(item_type, &item_id)
+    })
+    .await?
+    {
+        Ok(restored) => {
+            if restored {
+                emit_data_change(&app_handle, event, &id);
+            }
+            Ok(restored)
+        }
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to restore record with ID {}", id),
+            e,
+        )),
+    }
+}
+
+/// Command to permanently remove expired records from the trash, along with the image
+/// files of purged animals
+///
+/// Expired records are also purged in the background while the app is running.
+///
+/// # Returns
+/// * `Ok(usize)` - The number of records purged
+/// * `Err(CommandError)` - An error message if the user is not staff or the purge fails
+#[tauri::command]
+async fn purge_trash(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<usize, CommandError> {
+    require_staff(&state, &app_handle).await?;
+    purge_expired_trash(&state, &app_handle).await
+}
+
+/// Permanently removes expired records from the trash, along with the image files of
+/// purged animals
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(usize)` - The number of records purged
+/// * `Err(CommandError)` - An error message if the purge fails
+async fn purge_expired_trash(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+) -> Result<usize, CommandError> {
+    // Purge expired records
+    let (purged, image_paths) = match run_database_task(state, app_handle, |db| {
+        db.purge_expired_trash(Utc::now().timestamp())
+    })
+    .await?
+    {
+        Ok(result) => result,
+        Err(e) => return Err(CommandError::from_service("Failed to purge trash", e)),
+    };
+
+    // Remove the image files of purged animals; a missing file does not fail the purge
+    if !image_paths.is_empty() {
+        let file_service = init_file_service_once(state, app_handle).await?;
+        for image_path in image_paths {
+            if let Err(e) = file_service.delete_file(&image_path).await {
+                log::warn!("Failed to delete image of purged animal: {}", e);
+            }
+        }
+    }
+
+    Ok(purged)
+}
+
+// ==================== INTEGRITY COMMANDS ====================
+
+/// Command to check the database and image files for corruption and dangling references
+///
+/// # Returns
+/// * `Ok(IntegrityReport)` - The problems found, if any
+/// * `Err(CommandError)` - An error message if the user is not staff or the check fails
+#[tauri::command]
+async fn check_integrity(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<IntegrityReport, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Check the database itself
+    let (mut report, image_paths) = match run_database_task(&state, &app_handle, |db| {
+        Ok((db.check_integrity()?, db.query_image_paths()?))
+    })
+    .await?
+    {
+        Ok(result) => result,
+        Err(e) => {
+            return Err(CommandError::from_service(
+                "Failed to check database integrity",
+                e,
+            ))
+        }
+    };
+
+    // Check the image files
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    add_file_problems(&mut report, &image_paths, &file_service)
+        .await
+        .map_err(|e| CommandError::from_service("Failed to list uploaded files", e))?;
+    Ok(report)
+}
+
+/// Adds the image files that are gone, and the uploaded files that no animal references,
+/// to an integrity report of the database
+///
+/// # Arguments
+/// * `report` - The report of the database check, marked unhealthy if problems are found
+/// * `image_paths` - The ID and image path of every animal with an image
+/// * `file_service` - The service holding the uploaded files
+///
+/// # Returns
+/// * `Result<()>` - Success, or error if the uploaded files cannot be listed
+async fn add_file_problems(
+    report: &mut IntegrityReport,
+    image_paths: &[(String, String)],
+    file_service: &FileService,
+) -> Result<()> {
+    // Find image paths pointing at files that are gone
+    for (animal_id, image_path) in image_paths {
+        if !fs::try_exists(image_path).await.unwrap_or(false) {
+            report.missing_images.push(MissingImage {
+                animal_id: animal_id.clone(),
+                image_path: image_path.clone(),
+            });
+        }
+    }
+
+    // Find uploaded files that no animal references
+    let uploaded_files = file_service.list_uploaded_files().await?;
+    let referenced_names: HashSet<_> = image_paths
+        .iter()
+        .filter_map(|(_, image_path)| Path::new(image_path).file_name())
+        .collect();
+    for file in uploaded_files {
+        if !file
+            .file_name()
+            .is_some_and(|name| referenced_names.contains(name))
+        {
+            report
+                .unreferenced_files
+                .push(file.to_string_lossy().into_owned());
+        }
+    }
+
+    report.healthy =
+        report.healthy && report.missing_images.is_empty() && report.unreferenced_files.is_empty();
+    Ok(())
+}
+
+// ==================== MAINTENANCE COMMANDS ====================
+
+/// Command to compact the shelter and authentication databases and refresh their statistics
+///
+/// # Returns
+/// * `Ok(Vec<MaintenanceResult>)` - The space reclaimed from each database
+/// * `Err(CommandError)` - An error message if the user is not staff or maintenance fails
+#[tauri::command]
+async fn run_database_maintenance(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<MaintenanceResult>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+    maintain_databases(&state, &app_handle).await
+}
+
+/// Compacts the shelter and authentication databases and refreshes their statistics
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(Vec<MaintenanceResult>)` - The space reclaimed from each database
+/// * `Err(CommandError)` - An error message if maintenance fails
+async fn maintain_databases(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+) -> Result<Vec<MaintenanceResult>, CommandError> {
+    // Maintain the shelter database
+    let shelter = match run_database_task(state, app_handle, |db| db.run_maintenance()).await? {
+        Ok(result) => result,
+        Err(e) => {
+            return Err(CommandError::from_service(
+                "Failed to maintain shelter database",
+                e,
+            ))
+        }
+    };
+
+    // Maintain the authentication database
+    let authentication =
+        match run_authentication_task(state, app_handle, |auth| auth.run_maintenance()).await? {
+            Ok(result) => result,
+            Err(e) => {
+                return Err(CommandError::from_service(
+                    "Failed to maintain authentication database",
+                    e,
+                ))
+            }
+        };
+
+    Ok(vec![shelter, authentication])
+}
+
+// ==================== DEMO DATA COMMANDS ====================
+
+/// Command to fill an empty database with demo animals and adoption requests, and create
+/// the demo staff and customer accounts that go with them
+///
+/// Only available in development builds and builds with the `demo` feature, so demo
+/// records never end up in a real shelter's database. Every demo account uses the
+/// password `demo1234`, and accounts that already exist are kept as they are.
+///
+/// # Returns
+/// * `Ok(DemoDataSummary)` - How many animals and adoption requests were created
+/// * `Err(CommandError)` - An error message if this is not a demo build, the database already
+///   holds animals, or seeding fails
+#[tauri::command]
+async fn seed_demo_data(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<DemoDataSummary, CommandError> {
+    if !cfg!(any(debug_assertions, feature = "demo")) {
+        return Err(CommandError::permission_denied(
+            "Demo data is only available in development and demo builds",
+        ));
+    }
+
+    // Seed the shelter database
+    let summary = match run_database_task(&state, &app_handle, |db| db.seed_demo_data()).await? {
+        Ok(summary) => summary,
+        Err(e) => return Err(CommandError::from_service("Failed to seed demo data", e)),
+    };
+
+    // Create the demo accounts
+    match run_authentication_task(&state, &app_handle, |auth| {
+        let accounts = std::iter::once((demo::DEMO_STAFF_USERNAME, UserRole::Staff)).chain(
+            demo::DEMO_CUSTOMERS
+                .iter()
+                .map(|customer| (customer.username, UserRole::Customer)),
+        );
+        for (username, role) in accounts {
+            if !auth.user_exists(username)? {
+                auth.register_user(username, demo::DEMO_PASSWORD, role)?;
+            }
+        }
+        Ok(())
+    })
+    .await?
+    {
+        Ok(()) => Ok(summary),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to create demo accounts",
+            e,
+        )),
+    }
+}
+
+// ==================== SYNC COMMANDS ====================
+
+/// Command to export the changes made on this installation as a changelog file, saved
+/// where the user chooses, for importing on another installation
+///
+/// The export runs before the command returns; `enqueue_job` runs it in the background.
+///
+/// # Arguments
+/// * `since_timestamp` - Only export changes made at or after this timestamp, or everything if None
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the changelog was saved to
+/// * `Ok(None)` - If the user cancels the save dialog
+/// * `Err(CommandError)` - An error message if the user is not staff or the export fails
+#[tauri::command]
+async fn export_changes(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    since_timestamp: Option<i64>,
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let exported_timestamp = Utc::now().timestamp();
+    let Some(path) = choose_changes_path(&state, &app_handle, exported_timestamp).await? else {
+        return Ok(None);
+    };
+    run_changes_export(
+        app_handle,
+        JobProgress::detached(),
+        since_timestamp.unwrap_or(0),
+        exported_timestamp,
+        path,
+    )
+    .await
+    .map(Some)
+}
+
+/// Command to import a changelog file exported by another installation, chosen by the user
+///
+/// The import runs before the command returns; `enqueue_job` runs it in the background.
+///
+/// # Returns
+/// * `Ok(Some(ImportChangesResult))` - How many changes were applied, skipped and deleted
+/// * `Ok(None)` - If the user cancels the file dialog
+/// * `Err(CommandError)` - An error message if the user is not staff or the import fails
+#[tauri::command]
+async fn import_changes(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Option<ImportChangesResult>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let Some(contents) = open_changes(&state, &app_handle).await? else {
+        return Ok(None);
+    };
+    run_changes_import(app_handle, JobProgress::detached(), contents)
+        .await
+        .map(Some)
+}
+
+// ==================== WEBHOOK COMMANDS ====================
+
+/// Command to register a webhook that receives signed notifications of the events it subscribes to
+///
+/// # Arguments
+/// * `url` - URL the events are posted to
+/// * `secret` - Secret used to sign the body of each event
+/// * `event_types` - Events the webhook subscribes to
+///
+/// # Returns
+/// * `Ok(String)` - The ID of the created webhook
+/// * `Err(CommandError)` - An error message if the user is not staff or the webhook is invalid
+#[tauri::command]
+async fn create_webhook(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    url: String,
+    secret: String,
+    event_types: Vec<WebhookEvent>,
+) -> Result<String, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let webhook = Webhook {
+        id: String::new(),
+        url,
+        secret,
+        event_types,
+        created_timestamp: Utc::now().timestamp(),
+    };
+
+    // Insert webhook
+    match run_database_task(&state, &app_handle, move |db| db.insert_webhook(&webhook)).await? {
+        Ok(id) => Ok(id),
+        Err(e) => Err(CommandError::from_service("Failed to create webhook", e)),
+    }
+}
+
+/// Command to retrieve every registered webhook
+///
+/// # Returns
+/// * `Ok(Vec<Webhook>)` - Webhooks ordered by creation time
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_webhooks(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<Webhook>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Query webhooks
+    match run_database_task(&state, &app_handle, |db| db.query_webhooks()).await? {
+        Ok(webhooks) => Ok(webhooks),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve webhooks", e)),
+    }
+}
+
+/// Command to delete a webhook along with its delivery log
+///
+/// # Arguments
+/// * `webhook_id` - The ID of the webhook to delete
+///
+/// # Returns
+/// * `Ok(bool)` - True if the webhook was found and deleted, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the deletion fails
+#[tauri::command]
+async fn delete_webhook(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    webhook_id: String,
+) -> Result<bool, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Delete webhook
+    match run_database_task(&state, &app_handle, move |db| {
+        db.delete_webhook(&webhook_id)
+    })
+    .await?
+    {
+        Ok(deleted) => Ok(deleted),
+        Err(e) => Err(CommandError::from_service("Failed to delete webhook", e)),
+    }
+}
+
+/// Command to retrieve the delivery log of a webhook
+///
+/// # Arguments
+/// * `webhook_id` - The ID of the webhook
+///
+/// # Returns
+/// * `Ok(Vec<WebhookDelivery>)` - Deliveries, most recent first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_webhook_deliveries(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    webhook_id: String,
+) -> Result<Vec<WebhookDelivery>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Query deliveries
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_webhook_deliveries(&webhook_id)
+    })
+    .await?
+    {
+        Ok(deliveries) => Ok(deliveries),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve webhook deliveries",
+            e,
+        )),
+    }
+}
+
+// ==================== EMAIL COMMANDS ====================
+
+/// Command to retrieve the send log of the emails to applicants
+///
+/// # Returns
+/// * `Ok(Vec<EmailMessage>)` - Emails, most recent first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_emails(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<EmailMessage>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Query emails
+    match run_database_task(&state, &app_handle, |db| db.query_emails()).await? {
+        Ok(emails) => Ok(emails),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve emails", e)),
+    }
+}
+
+/// Command to send an email to an applicant again, such as after it failed because
+/// the SMTP settings were wrong. The current settings and templates are used.
+///
+/// # Arguments
+/// * `email_id` - The ID of the email to resend
+///
+/// # Returns
+/// * `Ok(Some(EmailMessage))` - The email after the attempt; check its status to see
+///   whether it was sent
+/// * `Ok(None)` - If no email with the ID exists
+/// * `Err(CommandError)` - An error message if the user is not staff, the email is still
+///   queued, or the attempt could not be recorded
+#[tauri::command]
+async fn resend_email(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    email_id: String,
+) -> Result<Option<EmailMessage>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let id = email_id.clone();
+    let email =
+        match run_database_task(&state, &app_handle, move |db| db.query_email_by_id(&id)).await? {
+            Ok(Some(email)) => email,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(CommandError::from_service("Failed to resend email", e)),
+        };
+    // The dispatcher sends queued emails itself; sending here too would send them twice
+    if email.status == EmailStatus::Pending {
+        return Err(CommandError::conflict(
+            "Failed to resend email: it is still queued to be sent",
+        ));
+    }
+
+    let database_service = init_database_service_once(&state, &app_handle).await?;
+    let settings_service = init_settings_service_once(&state, &app_handle).await?;
+    if let Err(e) = email_service::send(&database_service, &settings_service, &email).await {
+        return Err(CommandError::from_service("Failed to resend email", e));
+    }
+
+    // Return the email with the outcome of the attempt
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_email_by_id(&email_id)
+    })
+    .await?
+    {
+        Ok(email) => Ok(email),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve email", e)),
+    }
+}
+
+// ==================== FAVORITES COMMANDS ====================
+
+/// Command to add an animal to the logged-in user's favorites
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to favorite
+///
+/// # Returns
+/// * `Ok(bool)` - True if the favorite was added, false if it already existed
+/// * `Err(CommandError)` - An error message if nobody is logged in or the insertion fails
+#[tauri::command]
+async fn add_favorite(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<bool, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+
+    // Insert favorite
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.insert_favorite(&user.username, &id)
+    })
+    .await?
+    {
+        Ok(added) => Ok(added),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to add animal with ID {} to favorites", animal_id),
+            e,
+        )),
+    }
+}
+
+/// Command to remove an animal from the logged-in user's favorites
+///
+/// # Arguments
+/// * `animal_id` - The ID of the favorited animal
+///
+/// # Returns
+/// * `Ok(bool)` - True if the favorite was found and removed, false if not found
+/// * `Err(CommandError)` - An error message if nobody is logged in or the deletion fails
+#[tauri::command]
+async fn remove_favorite(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<bool, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+
+    // Delete favorite
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.delete_favorite(&user.username, &id)
+    })
+    .await?
+    {
+        Ok(removed) => Ok(removed),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to remove animal with ID {} from favorites",
+                animal_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the animals the logged-in user has favorited
+///
+/// # Returns
+/// * `Ok(Vec<AnimalSummary>)` - List of favorited animals, most recent first
+/// * `Err(CommandError)` - An error message if nobody is logged in or the query fails
+#[tauri::command]
+async fn get_favorites(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<AnimalSummary>, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+
+    // Query favorites by username
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_favorites_by_username(&user.username)
+    })
+    .await?
+    {
+        Ok(animals) => Ok(animals),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve favorites",
+            e,
+        )),
+    }
+}
+
+// ==================== PROFILE COMMANDS ====================
+
+/// Command to retrieve the logged-in user's profile
+///
+/// # Returns
+/// * `Ok(Option<UserProfile>)` - The profile, None if the user has not saved one
+/// * `Err(CommandError)` - An error message if nobody is logged in or the query fails
+#[tauri::command]
+async fn get_profile(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Option<UserProfile>, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+
+    // Query profile by username
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_profile(&user.username)
+    })
+    .await?
+    {
+        Ok(profile) => Ok(profile),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve profile", e)),
+    }
+}
+
+/// Command to create or replace the logged-in user's profile
+///
+/// # Arguments
+/// * `profile` - The profile data; its username and update time are set by the backend
+///
+/// # Returns
+/// * `Ok(UserProfile)` - The stored profile
+/// * `Err(CommandError)` - An error message if nobody is logged in, the profile is invalid or storing it fails
+#[tauri::command]
+async fn update_profile(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    mut profile: UserProfile,
+) -> Result<UserProfile, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+    profile.username = user.username;
+    profile.updated_timestamp = Utc::now().timestamp();
+
+    // Upsert profile
+    let stored = profile.clone();
+    match run_database_task(&state, &app_handle, move |db| db.upsert_profile(&stored)).await? {
+        Ok(()) => Ok(profile),
+        Err(e) => Err(CommandError::from_service("Failed to update profile", e)),
+    }
+}
+
+// ==================== PRIVACY COMMANDS ====================
+
+/// Command to save everything stored about the logged-in user, their profile, adoption
+/// requests and favorites, as a JSON file where the user chooses
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the data was saved to
+/// * `Ok(None)` - If the user cancels
+/// * `Err(CommandError)` - An error message if nobody is logged in, or the data could
+///   not be read or saved
+#[tauri::command]
+async fn export_my_data(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Option<PathBuf>, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+
+    let username = user.username.clone();
+    let export = run_database_task(&state, &app_handle, move |db| {
+        db.query_user_data_export(&username)
+    })
+    .await?
+    .map_err(|e| CommandError::from_service("Failed to gather your data", e))?;
+
+    // Ask where to save the data
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    let file_name = format!(
+        "my-data-{}-{}.json",
+        user.username,
+        Utc::now().format("%Y-%m-%d")
+    );
+    let path = match file_service
+        .choose_save_path(&app_handle, &file_name, "JSON", "json")
+        .await
+    {
+        Ok(Some(path)) => path,
+        Ok(None) => return Ok(None),
+        Err(e) => {
+            return Err(CommandError::from_service(
+                "Failed to choose where to save",
+                e,
+            ))
+        }
+    };
+
+    let contents = serde_json::to_vec_pretty(&export)
+        .map_err(|e| CommandError::from_service("Failed to write your data", e))?;
+    save_export(&state, &app_handle, path, contents)
+        .await
+        .map(Some)
+}
+
+/// Command to ask for the logged-in customer's personal data to be erased; an admin
+/// must approve the request before anything is erased
+///
+/// # Returns
+/// * `Ok(CreateErasureResult)` - The stored request, or the one already waiting for review
+/// * `Err(CommandError)` - An error message if the user is not logged in as a customer
+///   or storing the request fails
+#[tauri::command]
+async fn request_erasure(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<CreateErasureResult, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+    if user.role != UserRole::Customer {
+        return Err(CommandError::permission_denied(
+            "Only customers can ask for their data to be erased",
+        ));
+    }
+
+    match run_database_task(&state, &app_handle, move |db| {
+        db.insert_erasure_request(&user.username)
+    })
+    .await?
+    {
+        Ok(result) => Ok(result),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to request erasure of your data",
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the customers' requests to erase their data, oldest first (admin only)
+///
+/// # Arguments
+/// * `status` - Only retrieve requests with this status, or all requests if None
+///
+/// # Returns
+/// * `Ok(Vec<ErasureRequest>)` - List of erasure requests if successful
+/// * `Err(CommandError)` - An error message if the user is not an admin or the query fails
+#[tauri::command]
+async fn get_erasure_requests(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    status: Option<ErasureRequestStatus>,
+) -> Result<Vec<ErasureRequest>, CommandError> {
+    require_admin(&state, &app_handle).await?;
+
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_erasure_requests(status)
+    })
+    .await?
+    {
+        Ok(requests) => Ok(requests),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve erasure requests",
+            e,
+        )),
+    }
+}
+
+/// Command to approve a customer's request to erase their data (admin only)
+///
+/// The customer's account is deleted first, then their profile, favorites, waitlist
+/// entries and notifications. Their adoption requests are kept but anonymized. Should
+/// erasing the data fail after the account is gone, approving again finishes the job.
+///
+/// # Arguments
+/// * `request_id` - The ID of the erasure request to approve
+///
+/// # Returns
+/// * `Ok(ReviewErasureResult)` - The approved request, or why it could not be approved
+/// * `Err(CommandError)` - An error message if the user is not an admin or erasure fails
+#[tauri::command]
+async fn approve_erasure_request(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request_id: String,
+) -> Result<ReviewErasureResult, CommandError> {
+    let admin = require_admin(&state, &app_handle).await?;
+
+    let id = request_id.clone();
+    let request = match run_database_task(&state, &app_handle, move |db| {
+        db.query_erasure_request_by_id(&id)
+    })
+    .await?
+    {
+        Ok(None) => return Ok(ReviewErasureResult::NotFound),
+        Ok(Some(request)) if request.status != ErasureRequestStatus::Pending => {
+            return Ok(ReviewErasureResult::AlreadyReviewed(request))
+        }
+        Ok(Some(request)) => request,
+        Err(e) => {
+            return Err(CommandError::from_service(
+                format!("Failed to retrieve erasure request with ID {}", request_id),
+                e,
+            ))
+        }
+    };
+
+    // Delete the account first, so the customer cannot log back in to data being erased
+    let username = request.username.clone();
+    if let Err(e) =
+        run_authentication_task(&state, &app_handle, move |auth| auth.erase_user(&username)).await?
+    {
+        return Err(CommandError::from_service(
+            format!("Failed to delete the account of {}", request.username),
+            e,
+        ));
+    }
+
+    match run_database_task(&state, &app_handle, move |db| {
+        db.approve_erasure_request(&request.id, &admin.username)
+    })
+    .await?
+    {
+        Ok(result) => Ok(result),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to approve erasure request with ID {}", request_id),
+            e,
+        )),
+    }
+}
+
+/// Command to reject a customer's request to erase their data (admin only)
+///
+/// # Arguments
+/// * `request_id` - The ID of the erasure request to reject
+///
+/// # Returns
+/// * `Ok(ReviewErasureResult)` - The rejected request, or why it could not be rejected
+/// * `Err(CommandError)` - An error message if the user is not an admin or the update fails
+#[tauri::command]
+async fn reject_erasure_request(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request_id: String,
+) -> Result<ReviewErasureResult, CommandError> {
+    let admin = require_admin(&state, &app_handle).await?;
+
+    let id = request_id.clone();
+    match run_database_task(&state, &app_handle, move |db| {
+        db.reject_erasure_request(&id, &admin.username)
+    })
+    .await?
+    {
+        Ok(result) => Ok(result),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to reject erasure request with ID {}", request_id),
+            e,
+        )),
+    }
+}
+
+// ==================== BANNED ADOPTER COMMANDS ====================
+
+/// Command to retrieve every banned adopter
+///
+/// # Returns
+/// * `Ok(Vec<BannedAdopter>)` - List of banned adopters, most recently added first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_banned_adopters(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<BannedAdopter>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    match run_database_task(&state, &app_handle, |db| db.query_banned_adopters()).await? {
+        Ok(banned) => Ok(banned),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to get banned adopters",
+            e,
+        )),
+    }
+}
+
+/// Command to ban a person from adopting, recorded as added by the logged-in staff member
+///
+/// # Arguments
+/// * `banned` - The patterns identifying the person and the reason for the ban
+///
+/// # Returns
+/// * `Ok(String)` - The ID of the new entry
+/// * `Err(CommandError)` - An error message if the user is not staff, the entry is invalid or the insertion fails
+#[tauri::command]
+async fn add_banned_adopter(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    mut banned: BannedAdopter,
+) -> Result<String, CommandError> {
+    let user = require_staff(&state, &app_handle).await?;
+    banned.added_by = user.username;
+    banned.created_timestamp = Utc::now().timestamp();
+
+    match run_database_task(&state, &app_handle, move |db| {
+        db.insert_banned_adopter(&banned)
+    })
+    .await?
+    {
+        Ok(id) => Ok(id),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to add banned adopter",
+            e,
+        )),
+    }
+}
+
+/// Command to lift a ban
+///
+/// # Arguments
+/// * `banned_id` - The ID of the entry to remove
+///
+/// # Returns
+/// * `Ok(bool)` - True if the entry was found and removed, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the deletion fails
+#[tauri::command]
+async fn remove_banned_adopter(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    banned_id: String,
+) -> Result<bool, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    match run_database_task(&state, &app_handle, move |db| {
+        db.delete_banned_adopter(&banned_id)
+    })
+    .await?
+    {
+        Ok(removed) => Ok(removed),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to remove banned adopter",
+            e,
+        )),
+    }
+}
+
+/// Command to check an adoption request against the banned adopters, so staff are
+/// warned before approving it
+///
+/// # Arguments
+/// * `request_id` - The ID of the adoption request to check
+///
+/// # Returns
+/// * `Ok(Vec<BannedAdopter>)` - The banned adopters matching the requester, empty if none
+/// * `Err(CommandError)` - An error message if the user is not staff or the request does not exist
+#[tauri::command]
+async fn get_banned_adopter_matches(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request_id: String,
+) -> Result<Vec<BannedAdopter>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_banned_adopter_matches(&request_id)
+    })
+    .await?
+    {
+        Ok(matches) => Ok(matches),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to check banned adopters",
+            e,
+        )),
+    }
+}
+
+// ==================== WAITLIST COMMANDS ====================
+
+/// Command to add the logged-in user to the waitlist for a species, and optionally a breed
+///
+/// # Arguments
+/// * `specie` - Species to wait for
+/// * `breed` - Breed to wait for, or None for any breed of the species
+///
+/// # Returns
+/// * `Ok(JoinWaitlistResult)` - Whether the user joined or was already waiting
+/// * `Err(CommandError)` - An error message if nobody is logged in or the insertion fails
+#[tauri::command]
+async fn join_waitlist(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    specie: String,
+    breed: Option<String>,
+) -> Result<JoinWaitlistResult, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+
+    // Insert waitlist entry
+    let entry = WaitlistEntry {
+        id: String::new(),
+        username: user.username,
+        specie,
+        breed,
+        created_timestamp: chrono::Utc::now().timestamp(),
+    };
+    match run_database_task(&state, &app_handle, move |db| {
+        db.insert_waitlist_entry(&entry)
+    })
+    .await?
+    {
+        Ok(result) => Ok(result),
+        Err(e) => Err(CommandError::from_service("Failed to join waitlist", e)),
+    }
+}
+
+/// Command to remove one of the logged-in user's waitlist entries
+///
+/// # Arguments
+/// * `entry_id` - The ID of the waitlist entry to remove
+///
+/// # Returns
+/// * `Ok(bool)` - True if the entry was found and removed, false if not found
+/// * `Err(CommandError)` - An error message if nobody is logged in or the deletion fails
+#[tauri::command]
+async fn leave_waitlist(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    entry_id: String,
+) -> Result<bool, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+
+    // Delete waitlist entry
+    match run_database_task(&state, &app_handle, move |db| {
+        db.delete_waitlist_entry(&entry_id, &user.username)
+    })
+    .await?
+    {
+        Ok(deleted) => Ok(deleted),
+        Err(e) => Err(CommandError::from_service("Failed to leave waitlist", e)),
+    }
+}
+
+/// Command to retrieve the logged-in user's waitlist entries
+///
+/// # Returns
+/// * `Ok(Vec<WaitlistEntry>)` - List of waitlist entries, most recent first
+/// * `Err(CommandError)` - An error message if nobody is logged in or the query fails
+#[tauri::command]
+async fn get_waitlist(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<WaitlistEntry>, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+
+    // Query waitlist entries by username
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_waitlist_by_username(&user.username)
+    })
+    .await?
+    {
+        Ok(entries) => Ok(entries),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve waitlist", e)),
+    }
+}
+
+// ==================== NOTIFICATION COMMANDS ====================
+
+/// Command to retrieve the logged-in user's notifications
+///
+/// # Returns
+/// * `Ok(Vec<Notification>)` - List of notifications, most recent first
+/// * `Err(CommandError)` - An error message if nobody is logged in or the query fails
+#[tauri::command]
+async fn get_notifications(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<Notification>, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+
+    // Query notifications by username
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_notifications_by_username(&user.username)
+    })
+    .await?
+    {
+        Ok(notifications) => Ok(notifications),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve notifications",
+            e,
+        )),
+    }
+}
+
+/// Command to mark one of the logged-in user's notifications as read
+///
+/// # Arguments
+/// * `notification_id` - The ID of the notification to mark
+///
+/// # Returns
+/// * `Ok(bool)` - True if the notification was found and marked, false if not found
+/// * `Err(CommandError)` - An error message if nobody is logged in or the update fails
+#[tauri::command]
+async fn mark_notification_read(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    notification_id: String,
+) -> Result<bool, CommandError> {
+    let user = require_logged_in_user(&state, &app_handle).await?;
+
+    // Mark notification as read
+    match run_database_task(&state, &app_handle, move |db| {
+        db.mark_notification_read(&notification_id, &user.username)
+    })
+    .await?
+    {
+        Ok(marked) => Ok(marked),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to mark notification as read",
+            e,
+        )),
+    }
+}
+
+/// Command to check the reminder rules right away instead of waiting for the scheduler,
+/// such as after changing the reminder settings. Created notifications are emitted as
+/// for `create_animal`.
+///
+/// # Returns
+/// * `Ok(usize)` - The number of notifications created
+/// * `Err(CommandError)` - An error message if the user is not an admin or the check fails
+#[tauri::command]
+async fn check_reminders(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<usize, CommandError> {
+    require_admin(&state, &app_handle).await?;
+
+    let services = init_reminder_services(&state, &app_handle).await?;
+    match reminder_service::create_due_reminders(&services).await {
+        Ok(notifications) => {
+            emit_notifications(&app_handle, &notifications);
+            Ok(notifications.len())
+        }
+        Err(e) => Err(CommandError::from_service("Failed to check reminders", e)),
+    }
+}
+
+/// Command to list the available animals that have been in the shelter's care for a long
+/// time, so staff can give them more attention
+///
+/// # Arguments
+/// * `min_days` - Days in care an animal must have reached, or None for the length of stay
+///   set for reminders
+///
+/// # Returns
+/// * `Ok(Vec<LongStayAnimal>)` - The animals with their days in care, longest staying first
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_long_stay_animals(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    min_days: Option<u32>,
+) -> Result<Vec<LongStayAnimal>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let min_days = match min_days {
+        Some(days) => days,
+        None => {
+            match run_settings_task(&state, &app_handle, |settings| settings.reminder_settings())
+                .await?
+            {
+                Ok(reminders) => reminders.length_of_stay_days,
+                Err(e) => {
+                    return Err(CommandError::from_service(
+                        "Failed to retrieve reminder settings",
+                        e,
+                    ))
+                }
+            }
+        }
+    };
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_long_stay_animals(min_days, Utc::now().timestamp())
+    })
+    .await?
+    {
+        Ok(animals) => Ok(animals),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve long-staying animals",
+            e,
+        )),
+    }
+}
+
+// ==================== STATISTICS COMMANDS ====================
+
+/// Command to retrieve aggregate statistics about the shelter
+///
+/// # Arguments
+/// * `location_id` - Only include animals at this location, or None for the whole organization
+///
+/// # Returns
+/// * `Ok(ShelterStatistics)` - Counts by status and species, adoptions per month, average length of stay, and pending request count
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_shelter_statistics(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    location_id: Option<String>,
+) -> Result<ShelterStatistics, CommandError> {
+    // Query shelter statistics
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_shelter_statistics(location_id.as_deref())
+    })
+    .await?
+    {
+        Ok(statistics) => Ok(statistics),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve shelter statistics",
+            e,
+        )),
+    }
+}
+
+/// Command to generate the report of the shelter's activity during a calendar month
+///
+/// # Arguments
+/// * `year` - The year of the month to report on
+/// * `month` - The month to report on, from 1 to 12
+/// * `location_id` - Only include animals at this location, or None for the whole organization
+///
+/// # Returns
+/// * `Ok(MonthlyReport)` - The report if successful
+/// * `Err(CommandError)` - An error message if the user is not staff, the month is
+///   invalid or the query fails
+#[tauri::command]
+async fn generate_monthly_report(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    year: i32,
+    month: u32,
+    location_id: Option<String>,
+) -> Result<MonthlyReport, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Query monthly report
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_monthly_report(year, month, location_id.as_deref())
+    })
+    .await?
+    {
+        Ok(report) => Ok(report),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to generate report for {}-{:02}", year, month),
+            e,
+        )),
+    }
+}
+
+/// Command to count the animals taken in and adopted during each day, week or month of
+/// a time range, for the dashboard's chart of adoptions over time
+///
+/// # Arguments
+/// * `granularity` - Whether to count by day, week or month
+/// * `from_timestamp` - Start of the range
+/// * `to_timestamp` - End of the range, excluded
+/// * `location_id` - Only include animals at this location, or None for the whole organization
+///
+/// # Returns
+/// * `Ok(Vec<TrendPoint>)` - One point per period, oldest first, including empty periods
+/// * `Err(CommandError)` - An error message if the range is empty or covers too many
+///   periods, or the query fails
+#[tauri::command]
+async fn get_adoption_trends(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    granularity: TrendGranularity,
+    from_timestamp: i64,
+    to_timestamp: i64,
+    location_id: Option<String>,
+) -> Result<Vec<TrendPoint>, CommandError> {
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_adoption_trends(
+            granularity,
+            from_timestamp,
+            to_timestamp,
+            location_id.as_deref(),
+        )
+    })
+    .await?
+    {
+        Ok(trends) => Ok(trends),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve adoption trends",
+            e,
+        )),
+    }
+}
+
+// ==================== SEARCH COMMANDS ====================
+
+/// Command to search animals, adoption requests and user accounts for some text at once,
+/// for the global search bar
+///
+/// Animals are matched by name, breed and bio, requests by applicant name, username and
+/// email, and accounts by username. Accounts are only searched for admins.
+///
+/// # Arguments
+/// * `query` - The text to look for, ignoring case; blank text finds nothing
+/// * `scopes` - The kinds of record to search, or None for all of them
+/// * `animal_filters` - Optional map of filter criteria and values the animals must match
+/// * `request_filters` - Status, date range and species the requests must match
+/// * `limit` - Most records to return of each kind, or None for the default
+///
+/// # Returns
+/// * `Ok(SearchResults)` - The records found, grouped by kind, best matches first
+/// * `Err(CommandError)` - An error message if the user is not staff or a search fails
+#[tauri::command]
+async fn search(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    query: String,
+    scopes: Option<Vec<SearchScope>>,
+    animal_filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
+    request_filters: Option<AdoptionRequestFilters>,
+    limit: Option<usize>,
+) -> Result<SearchResults, CommandError> {
+    let user = require_staff(&state, &app_handle).await?;
+
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Ok(SearchResults::default());
+    }
+    let in_scope =
+        |scope: SearchScope| scopes.as_ref().is_none_or(|scopes| scopes.contains(&scope));
+    let limit = limit.unwrap_or(search::DEFAULT_SEARCH_RESULTS);
+
+    let (search_animals, search_requests) = (
+        in_scope(SearchScope::Animals),
+        in_scope(SearchScope::Requests),
+    );
+    let text = query.clone();
+    let mut results = match run_database_task(&state, &app_handle, move |db| {
+        let mut results = SearchResults::default();
+        if search_animals {
+            results.animals = db.search_animals(&text, animal_filters, limit)?;
+        }
+        if search_requests {
+            results.requests =
+                db.search_adoption_requests(&text, &request_filters.unwrap_or_default(), limit)?;
+        }
+        Ok(results)
+    })
+    .await?
+    {
+        Ok(results) => results,
+        Err(e) => return Err(CommandError::from_service("Failed to search", e)),
+    };
+
+    if in_scope(SearchScope::Users) && user.role == UserRole::Admin {
+        results.users = match run_authentication_task(&state, &app_handle, move |auth| {
+            auth.search_users(&query, limit)
+        })
+        .await?
+        {
+            Ok(users) => users,
+            Err(e) => return Err(CommandError::from_service("Failed to search users", e)),
+        };
+    }
+    Ok(results)
+}
+
+// ==================== ACTIVITY COMMANDS ====================
+
+/// Describes an audit log entry in words, such as "jane approved request r14 for Buddy"
+///
+/// # Arguments
+/// * `entry` - The entry to describe
+/// * `locale` - The locale to write the description in
+///
+/// # Returns
+/// * `String` - The description, naming the animal by its ID if its name is unknown
+fn activity_message(entry: &AuditEntry, locale: Locale) -> String {
+    let key = match entry.action {
+        AuditAction::AnimalCreated => MessageKey::ActivityAnimalCreated,
+        AuditAction::AnimalUpdated => MessageKey::ActivityAnimalUpdated,
+        AuditAction::AnimalDeleted => MessageKey::ActivityAnimalDeleted,
+        AuditAction::RequestApproved => MessageKey::ActivityRequestApproved,
+        AuditAction::TrialStarted => MessageKey::ActivityTrialStarted,
+        AuditAction::TrialFinalized => MessageKey::ActivityTrialFinalized,
+        AuditAction::RequestRejected => MessageKey::ActivityRequestRejected,
+        AuditAction::RequestUpdated => MessageKey::ActivityRequestUpdated,
+        AuditAction::RequestDeleted => MessageKey::ActivityRequestDeleted,
+        AuditAction::ReturnProcessed => MessageKey::ActivityReturnProcessed,
+        AuditAction::NoteAdded => MessageKey::ActivityNoteAdded,
+        AuditAction::DeathRecorded => MessageKey::ActivityDeathRecorded,
+        AuditAction::SurgeryCompleted => MessageKey::ActivitySurgeryCompleted,
+        AuditAction::InterviewRecorded => MessageKey::ActivityInterviewRecorded,
+        AuditAction::ReferenceChecked => MessageKey::ActivityReferenceChecked,
+        AuditAction::PaymentRecorded => MessageKey::ActivityPaymentRecorded,
+        AuditAction::PaymentReceived => MessageKey::ActivityPaymentReceived,
+    };
+    let animal = entry
+        .animal_name
+        .as_deref()
+        .or(entry.animal_id.as_deref())
+        .unwrap_or(&entry.record_id);
+    i18n::format(
+        key,
+        locale,
+        &[
+            ("user", &entry.username),
+            ("id", &entry.record_id),
+            ("animal", animal),
+        ],
+    )
+}
+
+/// Command to retrieve what staff members have recently done, most recent first, for the
+/// team's activity panel
+///
+/// # Arguments
+/// * `offset` - Number of the most recent entries to skip, or None for none
+/// * `limit` - Most entries to return, or None for the default; at most 200 are returned
+///
+/// # Returns
+/// * `Ok(ActivityFeed)` - A page of the feed, each entry described in the current locale
+/// * `Err(CommandError)` - An error message if the user is not staff or the query fails
+#[tauri::command]
+async fn get_activity_feed(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<ActivityFeed, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let offset = offset.unwrap_or(0);
+    let limit = limit
+        .unwrap_or(audit::DEFAULT_ACTIVITY_FEED_LIMIT)
+        .min(audit::MAX_ACTIVITY_FEED_LIMIT);
+    match run_database_task(&state, &app_handle, move |db| {
+        db.query_audit_log(offset, limit)
+    })
+    .await?
+    {
+        Ok((entries, total)) => {
+            let locale = i18n::current_locale();
+            let items = entries
+                .into_iter()
+                .map(|entry| ActivityFeedItem {
+                    message: activity_message(&entry, locale),
+                    entry,
+                })
+                .collect();
+            Ok(ActivityFeed { items, total })
+        }
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve activity feed",
+            e,
+        )),
+    }
+}
+
+// ==================== DOCUMENT COMMANDS ====================
+
+/// Command to render the kennel card of an animal, a one-page profile with its photo,
+/// key facts, temperament and a QR code of its ID, and save it where the user chooses
+///
+/// The card goes up on the animal's enclosure, so staff-only notes are left off it.
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the card was saved to
+/// * `Ok(None)` - If the user cancels
+/// * `Err(CommandError)` - An error message if the user is not staff, the animal does not
+///   exist, or the card could not be rendered or saved
+#[tauri::command]
+async fn generate_kennel_card(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Gather the animal and the behavior notes customers can see
+    let id = animal_id.clone();
+    let (detail, behavior_notes) = match run_database_task(&state, &app_handle, move |db| {
+        let Some(detail) = db.query_animal_detail(&id, false)? else {
+            return Ok(None);
+        };
+        let behavior_notes: Vec<AnimalNote> = db
+            .query_animal_notes_by_animal_id(&id, false)?
+            .into_iter()
+            .filter(|note| note.category == NoteCategory::Behavior)
+            .collect();
+        Ok(Some((detail, behavior_notes)))
+    })
+    .await?
+    {
+        Ok(Some(card)) => card,
+        Ok(None) => {
+            return Err(CommandError::not_found(format!(
+                "Animal with ID {} not found",
+                animal_id
+            )))
+        }
+        Err(e) => return Err(CommandError::from_service("Failed to get animal", e)),
+    };
+
+    // Ask where to save the card
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    let file_name = format!("kennel-card-{}.pdf", animal_id);
+    let Some(path) =
+        choose_save_path(&file_service, &app_handle, &file_name, ReportFormat::Pdf).await?
+    else {
+        return Ok(None);
+    };
+
+    // A missing photo leaves the card without one rather than failing it
+    let photo = match &detail.animal.image_path {
+        Some(image_path) => match fs::read(image_path).await {
+            Ok(bytes) => Some(bytes),
+            Err(e) => {
+                log::warn!("Failed to read photo {:?}: {}", image_path, e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    // Render and save the card
+    let shelter_name =
+        match run_settings_task(&state, &app_handle, |settings| settings.shelter_name()).await? {
+            Ok(shelter_name) => shelter_name,
+            Err(e) => {
+                return Err(CommandError::from_service(
+                    "Failed to retrieve shelter name",
+                    e,
+                ))
+            }
+        };
+    let locale = settings_locale(&state, &app_handle).await?;
+    let contents = export_service::render_kennel_card_pdf(
+        &shelter_name,
+        &detail,
+        &behavior_notes,
+        photo.as_deref(),
+        locale,
+    )
+    .map_err(|e| CommandError::from_service("Failed to render kennel card", e))?;
+    save_export(&state, &app_handle, path, contents)
+        .await
+        .map(Some)
+}
+
+/// Command to render the vaccination certificate of an animal, listing the vaccines it
+/// has been given with a signature line, and save it where the user chooses
+///
+/// Adopters hand the certificate to their own vet.
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the certificate was saved to
+/// * `Ok(None)` - If the user cancels
+/// * `Err(CommandError)` - An error message if the user is not staff, the animal does not
+///   exist, or the certificate could not be rendered or saved
+#[tauri::command]
+async fn generate_vaccination_certificate(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Gather the animal and its vaccination history
+    let id = animal_id.clone();
+    let (animal, mut vaccinations) = match run_database_task(&state, &app_handle, move |db| {
+        let Some(animal) = db.query_animal_by_id(&id)? else {
+            return Ok(None);
+        };
+        let vaccinations = db.query_vaccinations_by_animal_id(&id)?;
+        Ok(Some((animal, vaccinations)))
+    })
+    .await?
+    {
+        Ok(Some(history)) => history,
+        Ok(None) => {
+            return Err(CommandError::not_found(format!(
+                "Animal with ID {} not found",
+                animal_id
+            )))
+        }
+        Err(e) => return Err(CommandError::from_service("Failed to get animal", e)),
+    };
+    // A vet reads the history from the first dose on
+    vaccinations.sort_by_key(|vaccination| vaccination.administered_timestamp);
+
+    // Ask where to save the certificate
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    let file_name = format!("vaccination-certificate-{}.pdf", animal_id);
+    let Some(path) =
+        choose_save_path(&file_service, &app_handle, &file_name, ReportFormat::Pdf).await?
+    else {
+        return Ok(None);
+    };
+
+    // Render and save the certificate
+    let shelter_name =
+        match run_settings_task(&state, &app_handle, |settings| settings.shelter_name()).await? {
+            Ok(shelter_name) => shelter_name,
+            Err(e) => {
+                return Err(CommandError::from_service(
+                    "Failed to retrieve shelter name",
+                    e,
+                ))
+            }
+        };
+    let locale = settings_locale(&state, &app_handle).await?;
+    let contents = export_service::render_vaccination_certificate_pdf(
+        &shelter_name,
+        &animal,
+        &vaccinations,
+        Utc::now().timestamp(),
+        locale,
+    )
+    .map_err(|e| CommandError::from_service("Failed to render vaccination certificate", e))?;
+    save_export(&state, &app_handle, path, contents)
+        .await
+        .map(Some)
+}
+
+/// Command to render the adoption certificate of an approved adoption request, with the
+/// animal's photo, the adopter's name and the adoption date, and save it where the user
+/// chooses
+///
+/// Staff print the certificate and hand it over when the adopter picks the animal up.
+///
+/// # Arguments
+/// * `request_id` - The ID of the approved adoption request
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the certificate was saved to
+/// * `Ok(None)` - If the user cancels
+/// * `Err(CommandError)` - An error message if the user is not staff, the request or its
+///   animal does not exist, the request is not approved, or the certificate could not be
+///   rendered or saved
+#[tauri::command]
+async fn generate_adoption_certificate(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request_id: String,
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Gather the request and the adopted animal
+    let id = request_id.clone();
+    let (request, animal) = match run_database_task(&state, &app_handle, move |db| {
+        let Some(request) = db.query_adoption_request_by_id(&id)? else {
+            return Ok(None);
+        };
+        let Some(animal) = db.query_animal_by_id(&request.animal_id)? else {
+            return Ok(None);
+        };
+        Ok(Some((request, animal)))
+    })
+    .await?
+    {
+        Ok(Some(adoption)) => adoption,
+        Ok(None) => {
+            return Err(CommandError::not_found(format!(
+                "Adoption request with ID {} or its animal not found",
+                request_id
+            )))
+        }
+        Err(e) => {
+            return Err(CommandError::from_service(
+                "Failed to get adoption request",
+                e,
+            ))
+        }
+    };
+    if request.status != RequestStatus::Approved {
+        return Err(CommandError::conflict(format!(
+            "Adoption request with ID {} has not been approved",
+            request_id
+        )));
+    }
+
+    // Ask where to save the certificate
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    let file_name = format!("adoption-certificate-{}.pdf", animal.id);
+    let Some(path) =
+        choose_save_path(&file_service, &app_handle, &file_name, ReportFormat::Pdf).await?
+    else {
+        return Ok(None);
+    };
+
+    // A missing photo leaves the certificate without one rather than failing it
+    let photo = match &animal.image_path {
+        Some(image_path) => match fs::read(image_path).await {
+            Ok(bytes) => Some(bytes),
+            Err(e) => {
+                log::warn!("Failed to read photo {:?}: {}", image_path, e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    // Render and save the certificate
+    let shelter_name =
+        match run_settings_task(&state, &app_handle, |settings| settings.shelter_name()).await? {
+            Ok(shelter_name) => shelter_name,
+            Err(e) => {
+                return Err(CommandError::from_service(
+                    "Failed to retrieve shelter name",
+                    e,
+                ))
+            }
+        };
+    let locale = settings_locale(&state, &app_handle).await?;
+    let contents = export_service::render_adoption_certificate_pdf(
+        &shelter_name,
+        &animal,
+        &request.applicant_names(),
+        request.adoption_timestamp,
+        photo.as_deref(),
+        locale,
+    )
+    .map_err(|e| CommandError::from_service("Failed to render adoption certificate", e))?;
+    save_export(&state, &app_handle, path, contents)
+        .await
+        .map(Some)
+}
+
+/// Command to generate the numbered invoice for a fee charged for an adoption request and
+/// save it where the user chooses
+///
+/// The invoice is given the next invoice number the first time it is generated, and keeps
+/// it when generated again.
+///
+/// # Arguments
+/// * `payment_id` - The ID of the payment
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the invoice was saved to
+/// * `Ok(None)` - If the user cancels
+/// * `Err(CommandError)` - An error message if the user is not staff, the payment or its
+///   request does not exist, or the invoice could not be rendered or saved
+#[tauri::command]
+async fn generate_invoice(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    payment_id: String,
+) -> Result<Option<PathBuf>, CommandError> {
+    generate_payment_document(&state, &app_handle, payment_id, PaymentDocument::Invoice).await
+}
+
+/// Command to generate the numbered receipt for a paid fee and save it where the user
+/// chooses
+///
+/// The receipt is given the next receipt number the first time it is generated, and keeps
+/// it when generated again.
+///
+/// # Arguments
+/// * `payment_id` - The ID of the payment
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the receipt was saved to
+/// * `Ok(None)` - If the user cancels
+/// * `Err(CommandError)` - An error message if the user is not staff, the payment or its
+///   request does not exist, the fee has not been paid, or the receipt could not be
+///   rendered or saved
+#[tauri::command]
+async fn generate_receipt(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    payment_id: String,
+) -> Result<Option<PathBuf>, CommandError> {
+    generate_payment_document(&state, &app_handle, payment_id, PaymentDocument::Receipt).await
+}
+
+/// Numbers the invoice or receipt of a payment, renders it and saves it where the user
+/// chooses
+///
+/// # Arguments
+/// * `state` - Application state
+/// * `app_handle` - Tauri application handle
+/// * `payment_id` - The ID of the payment
+/// * `document` - Whether to generate the invoice or the receipt
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the document was saved to
+/// * `Ok(None)` - If the user cancels
+/// * `Err(CommandError)` - An error message if the user is not staff, the payment or its
+///   request does not exist, a receipt is asked for an unpaid fee, or the document could
+///   not be rendered or saved
+async fn generate_payment_document(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+    payment_id: String,
+    document: PaymentDocument,
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(state, app_handle).await?;
+
+    // Number the document, unless a receipt is asked for a fee that was not paid
+    let id = payment_id.clone();
+    let (payment, request, animal) = match run_database_task(state, app_handle, move |db| {
+        let Some(payment) = db.query_payment_by_id(&id)? else {
+            return Ok(None);
+        };
+        let Some(request) = db.query_adoption_request_by_id(&payment.request_id)? else {
+            return Ok(None);
+        };
+        let Some(animal) = db.query_animal_by_id(&request.animal_id)? else {
+            return Ok(None);
+        };
+        if document == PaymentDocument::Receipt && payment.paid_timestamp.is_none() {
+            return Ok(Some((payment, request, animal)));
+        }
+        let payment = db.issue_payment_document(&id, document)?.unwrap_or(payment);
+        Ok(Some((payment, request, animal)))
+    })
+    .await?
+    {
+        Ok(Some(numbered)) => numbered,
+        Ok(None) => {
+            return Err(CommandError::not_found(format!(
+                "Payment with ID {} or its adoption request not found",
+                payment_id
+            )))
+        }
+        Err(e) => {
+            return Err(CommandError::from_service(
+                format!("Failed to number {}", document),
+                e,
+            ))
+        }
+    };
+    if payment.paid_timestamp.is_none() && document == PaymentDocument::Receipt {
+        return Err(CommandError::conflict(format!(
+            "Payment with ID {} has not been paid",
+            payment_id
+        )));
+    }
+    let number = match document {
+        PaymentDocument::Invoice => payment.invoice_number,
+        PaymentDocument::Receipt => payment.receipt_number,
+    }
+    .map(|number| document.format_number(number))
+    .unwrap_or_else(|| payment.id.clone());
+
+    // Ask where to save the document
+    let file_service = init_file_service_once(state, app_handle).await?;
+    let file_name = format!("{}.pdf", number);
+    let Some(path) =
+        choose_save_path(&file_service, app_handle, &file_name, ReportFormat::Pdf).await?
+    else {
+        return Ok(None);
+    };
+
+    // Render and save the document
+    let (shelter_name, currency) = settings_shelter_and_currency(state, app_handle).await?;
+    let locale = settings_locale(state, app_handle).await?;
+    let contents = export_service::render_payment_document_pdf(
+        document,
+        &shelter_name,
+        &payment,
+        &request.applicant_names(),
+        &animal.name,
+        &currency,
+        locale,
+    )
+    .map_err(|e| CommandError::from_service(format!("Failed to render {}", document), e))?;
+    save_export(state, app_handle, path, contents)
+        .await
+        .map(Some)
+}
+
+/// Command to generate the numbered receipt of a donation and save it where the user
+/// chooses
+///
+/// The receipt is given the next donation receipt number the first time it is
+/// generated, and keeps it when generated again.
+///
+/// # Arguments
+/// * `donation_id` - The ID of the donation
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the receipt was saved to
+/// * `Ok(None)` - If the user cancels
+/// * `Err(CommandError)` - An error message if the user is not staff, the donation does
+///   not exist, or the receipt could not be rendered or saved
+#[tauri::command]
+async fn generate_donation_receipt(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    donation_id: String,
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Number the receipt
+    let id = donation_id.clone();
+    let donation = match run_database_task(&state, &app_handle, move |db| {
+        db.issue_donation_receipt(&id)
+    })
+    .await?
+    {
+        Ok(Some(donation)) => donation,
+        Ok(None) => {
+            return Err(CommandError::not_found(format!(
+                "Donation with ID {} not found",
+                donation_id
+            )))
+        }
+        Err(e) => {
+            return Err(CommandError::from_service(
+                "Failed to number donation receipt",
+                e,
+            ))
+        }
+    };
+    let number = donation
+        .receipt_number
+        .map(Donation::format_receipt_number)
+        .unwrap_or_else(|| donation.id.clone());
+
+    // Ask where to save the receipt
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    let file_name = format!("{}.pdf", number);
+    let Some(path) =
+        choose_save_path(&file_service, &app_handle, &file_name, ReportFormat::Pdf).await?
+    else {
+        return Ok(None);
+    };
+
+    // Render and save the receipt
+    let (shelter_name, currency) = settings_shelter_and_currency(&state, &app_handle).await?;
+    let locale = settings_locale(&state, &app_handle).await?;
+    let contents =
+        export_service::render_donation_receipt_pdf(&shelter_name, &donation, &currency, locale)
+            .map_err(|e| CommandError::from_service("Failed to render donation receipt", e))?;
+    save_export(&state, &app_handle, path, contents)
+        .await
+        .map(Some)
+}
+
+/// Command to generate the statement summing the donations a donor made during a year,
+/// for their tax return, and save it where the user chooses
+///
+/// # Arguments
+/// * `contact_id` - The ID of the donor's contact
+/// * `year` - The year to sum the donations of
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the statement was saved to
+/// * `Ok(None)` - If the user cancels
+/// * `Err(CommandError)` - An error message if the user is not staff, the donor does not
+///   exist, or the statement could not be rendered or saved
+#[tauri::command]
+async fn generate_annual_donor_statement(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    contact_id: String,
+    year: i32,
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    // Sum the donations of the year
+    let id = contact_id.clone();
+    let statement = match run_database_task(&state, &app_handle, move |db| {
+        db.query_donor_statement(&id, year)
+    })
+    .await?
+    {
+        Ok(Some(statement)) => statement,
+        Ok(None) => {
+            return Err(CommandError::not_found(format!(
+                "Donor with contact ID {} not found",
+                contact_id
+            )))
+        }
+        Err(e) => {
+            return Err(CommandError::from_service(
+                format!("Failed to sum donations for {}", year),
+                e,
+            ))
+        }
+    };
+
+    // Ask where to save the statement
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    let file_name = format!("donation-statement-{}-{}.pdf", year, contact_id);
+    let Some(path) =
+        choose_save_path(&file_service, &app_handle, &file_name, ReportFormat::Pdf).await?
+    else {
+        return Ok(None);
+    };
+
+    // Render and save the statement
+    let (shelter_name, currency) = settings_shelter_and_currency(&state, &app_handle).await?;
+    let locale = settings_locale(&state, &app_handle).await?;
+    let contents = export_service::render_donor_statement_pdf(
+        &shelter_name,
+        &statement,
+        &currency,
+        Utc::now().timestamp(),
+        locale,
+    )
+    .map_err(|e| CommandError::from_service("Failed to render donor statement", e))?;
+    save_export(&state, &app_handle, path, contents)
+        .await
+        .map(Some)
+}
+
+/// Command to save the vaccinations coming due, the animals due back from foster care,
+/// the meet-and-greet appointments and the spay/neuter surgeries within a period as an
+/// iCalendar (.ics) file, which calendar apps such as Outlook and Google Calendar can import
+///
+/// # Arguments
+/// * `from_timestamp` - Start of the period, inclusive
+/// * `to_timestamp` - End of the period, exclusive
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the calendar was saved to
+/// * `Ok(None)` - If the user cancels
+/// * `Err(CommandError)` - An error message if the user is not staff, the period ends
+///   before it starts, or the calendar could not be saved
+#[tauri::command]
+async fn export_calendar(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    from_timestamp: i64,
+    to_timestamp: i64,
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let events = run_database_task(&state, &app_handle, move |db| {
+        db.query_calendar_events(from_timestamp, to_timestamp)
+    })
+    .await?
+    .map_err(|e| CommandError::from_service("Failed to get calendar events", e))?;
+
+    // Ask where to save the calendar
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    let file_name = format!("shelter-calendar-{}.ics", Utc::now().format("%Y-%m-%d"));
+    let path = match file_service
+        .choose_save_path(&app_handle, &file_name, "iCalendar", "ics")
+        .await
+    {
+        Ok(Some(path)) => path,
+        Ok(None) => return Ok(None),
+        Err(e) => {
+            return Err(CommandError::from_service(
+                "Failed to choose where to save",
+                e,
+            ))
+        }
+    };
+
+    let shelter_name =
+        match run_settings_task(&state, &app_handle, |settings| settings.shelter_name()).await? {
+            Ok(shelter_name) => shelter_name,
+            Err(e) => {
+                return Err(CommandError::from_service(
+                    "Failed to retrieve shelter name",
+                    e,
+                ))
+            }
+        };
+    let locale = settings_locale(&state, &app_handle).await?;
+    let contents =
+        export_service::render_calendar_ics(&shelter_name, &events, Utc::now().timestamp(), locale);
+    save_export(&state, &app_handle, path, contents)
+        .await
+        .map(Some)
+}
+
+/// Command to save the public listings feed, the animals available for adoption with
+/// only the fields safe to publish, where the user chooses, for the website and
+/// adoption aggregators to read
+///
+/// Photos are copied to a directory next to the feed, named after it, which the feed
+/// refers to. Animals flagged by a staff-only behavior or medical note are left out
+/// unless the listings setting says otherwise.
+///
+/// # Arguments
+/// * `format` - The file format to write the feed in
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the feed was saved to
+/// * `Ok(None)` - If the user cancels
+/// * `Err(CommandError)` - An error message if the user is not staff, or the feed could
+///   not be written or saved
+#[tauri::command]
+async fn export_public_listings(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    format: ListingsFormat,
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let (shelter_name, exclude_flagged) = match run_settings_task(&state, &app_handle, |settings| {
+        Ok((
+            settings.shelter_name()?,
+            settings.listings_exclude_flagged()?,
+        ))
+    })
+    .await?
+    {
+        Ok(listing_settings) => listing_settings,
+        Err(e) => {
+            return Err(CommandError::from_service(
+                "Failed to retrieve listings settings",
+                e,
+            ))
+        }
+    };
+    let animals = run_database_task(&state, &app_handle, move |db| {
+        db.query_listed_animals(exclude_flagged)
+    })
+    .await?
+    .map_err(|e| CommandError::from_service("Failed to get listed animals", e))?;
+
+    // Ask where to save the feed
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    let file_name = format!("listings.{}", format.extension());
+    let path = match file_service
+        .choose_save_path(
+            &app_handle,
+            &file_name,
+            format.filter_name(),
+            format.extension(),
+        )
+        .await
+    {
+        Ok(Some(path)) => path,
+        Ok(None) => return Ok(None),
+        Err(e) => {
+            return Err(CommandError::from_service(
+                "Failed to choose where to save",
+                e,
+            ))
+        }
+    };
+
+    let feed_directory = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    let photo_directory = format!(
+        "{}-photos",
+        path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("listings")
+    );
+    let (mut feed, photos) = export_service::build_listings_feed(
+        &shelter_name,
+        &animals,
+        &photo_directory,
+        Utc::now().timestamp(),
+    );
+
+    // A photo that cannot be copied is left out of its listing rather than failing the feed
+    let mut missing_photos = HashSet::new();
+    for photo in photos {
+        let destination = feed_directory.join(&photo.relative_path);
+        if let Err(e) = file_service
+            .copy_file_out(&photo.source_path, &destination)
+            .await
+        {
+            log::warn!("Failed to copy listing photo: {:#}", e);
+            missing_photos.insert(photo.relative_path);
+        }
+    }
+    for listing in &mut feed.animals {
+        if listing
+            .photo
+            .as_ref()
+            .is_some_and(|photo| missing_photos.contains(photo))
+        {
+            listing.photo = None;
+        }
+    }
+
+    let contents = export_service::render_listings_feed(&feed, format)
+        .map_err(|e| CommandError::from_service("Failed to write listings", e))?;
+    save_export(&state, &app_handle, path, contents)
+        .await
+        .map(Some)
+}
+
+/// Command to export the monthly or annual activity report of the shelter as a PDF,
+/// saved where the user chooses
+///
+/// The export runs before the command returns; `enqueue_job` runs it in the background.
+///
+/// # Arguments
+/// * `year` - The year to report on
+/// * `month` - The month to report on, from 1 to 12, or None for the whole year
+/// * `location_id` - Only include animals at this location, or None for the whole organization
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the PDF was saved to
+/// * `Ok(None)` - If the user cancels the save dialog
+/// * `Err(CommandError)` - An error message if the user is not staff, or the report could
+///   not be generated or saved
+#[tauri::command]
+async fn export_report_pdf(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    year: i32,
+    month: Option<u32>,
+    location_id: Option<String>,
+) -> Result<Option<PathBuf>, CommandError> {
+    export_report(
+        state,
+        app_handle,
+        year,
+        month,
+        location_id,
+        ReportFormat::Pdf,
+    )
+    .await
+}
+
+/// Command to export a monthly or annual activity report as an Excel workbook, saved
+/// where the user chooses
+///
+/// The export runs before the command returns; `enqueue_job` runs it in the background.
+///
+/// # Arguments
+/// * `year` - The year of the report
+/// * `month` - The month of the report (1-12), or None for the whole year
+/// * `location_id` - Only include animals at this location, or None for the whole organization
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the workbook was saved to
+/// * `Ok(None)` - If the user cancels the save dialog
+/// * `Err(CommandError)` - An error message if the user is not staff, or the report could
+///   not be generated or saved
+#[tauri::command]
+async fn export_report_xlsx(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    year: i32,
+    month: Option<u32>,
+    location_id: Option<String>,
+) -> Result<Option<PathBuf>, CommandError> {
+    export_report(
+        state,
+        app_handle,
+        year,
+        month,
+        location_id,
+        ReportFormat::Xlsx,
+    )
+    .await
+}
+
+/// Command to export animals as an Excel workbook, saved where the user chooses
+///
+/// The export runs before the command returns; `enqueue_job` runs it in the background.
+///
+/// # Arguments
+/// * `filters` - Optional map of filter criteria and values
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the workbook was saved to
+/// * `Ok(None)` - If the user cancels the save dialog
+/// * `Err(CommandError)` - An error message if the user is not staff or the animals could
+///   not be exported
+#[tauri::command]
+async fn export_animals_xlsx(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    let Some(path) = choose_save_path(
+        &file_service,
+        &app_handle,
+        "animals.xlsx",
+        ReportFormat::Xlsx,
+    )
+    .await?
+    else {
+        return Ok(None);
+    };
+    run_animals_export(app_handle, JobProgress::detached(), filters, path)
+        .await
+        .map(Some)
+}
+
+/// Command to export adoption requests as an Excel workbook, saved where the user chooses
+///
+/// The export runs before the command returns; `enqueue_job` runs it in the background.
+///
+/// # Arguments
+/// * `filters` - Status, date range and species filters; unset fields are ignored
+///
+/// # Returns
+/// * `Ok(Some(PathBuf))` - The path the workbook was saved to
+/// * `Ok(None)` - If the user cancels the save dialog
+/// * `Err(CommandError)` - An error message if the user is not staff or the requests could
+///   not be exported
+#[tauri::command]
+async fn export_adoption_requests_xlsx(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    filters: AdoptionRequestFilters,
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    let Some(path) = choose_save_path(
+        &file_service,
+        &app_handle,
+        "adoption-requests.xlsx",
+        ReportFormat::Xlsx,
+    )
+    .await?
+    else {
+        return Ok(None);
+    };
+    run_adoption_requests_export(app_handle, JobProgress::detached(), filters, false, path)
+        .await
+        .map(Some)
+}
+
+/// Exports the activity report of a month or year right away, saved where the user chooses
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+/// * `year` - The year to report on
+/// * `month` - The month to report on, from 1 to 12, or None for the whole year
+/// * `location_id` - Only include animals at this location, or None for the whole organization
+/// * `format` - The format to export in
+///
+/// # Returns
+/// * `Ok(Option<PathBuf>)` - The path the report was saved to, or None if the user cancels
+/// * `Err(CommandError)` - An error message if the user is not staff, or the report could
+///   not be generated or saved
+async fn export_report(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    year: i32,
+    month: Option<u32>,
+    location_id: Option<String>,
+    format: ReportFormat,
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    let file_name = report_file_name(year, month, format);
+    let Some(path) = choose_save_path(&file_service, &app_handle, &file_name, format).await? else {
+        return Ok(None);
+    };
+    run_report_export(
+        app_handle,
+        JobProgress::detached(),
+        year,
+        month,
+        location_id,
+        format,
+        path,
+    )
+    .await
+    .map(Some)
+}
+
+// ==================== JOB COMMANDS ====================
+
+/// Command to queue an export or bulk import to run in the background
+///
+/// Exports ask the user where to save, and change imports which file to read, before the
+/// job is queued, so the job runs without further input. Every change in the job's
+/// progress is emitted to the frontend as a `job-updated` event.
+///
+/// # Arguments
+/// * `request` - The work to do, with its parameters
+///
+/// # Returns
+/// * `Ok(Some(Job))` - The queued job
+/// * `Ok(None)` - If the user cancels the file dialog
+/// * `Err(CommandError)` - An error message if the user is not staff or the job could not be queued
+#[tauri::command]
+async fn enqueue_job(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    request: JobRequest,
+) -> Result<Option<Job>, CommandError> {
+    let user = require_staff(&state, &app_handle).await?;
+    let kind = request.kind();
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+
+    // Ask for the file the job writes or reads
+    let handle = app_handle.clone();
+    let task: Box<dyn FnOnce(JobProgress) -> JobFuture + Send> = match request {
+        JobRequest::ReportExport {
+            year,
+            month,
+            location_id,
+            format,
+        } => {
+            let file_name = report_file_name(year, month, format);
+            let Some(path) =
+                choose_save_path(&file_service, &app_handle, &file_name, format).await?
+            else {
+                return Ok(None);
+            };
+            Box::new(move |progress| {
+                job_future(run_report_export(
+                    handle,
+                    progress,
+                    year,
+                    month,
+                    location_id,
+                    format,
+                    path,
+                ))
+            })
+        }
+        JobRequest::AnimalsExport { filters } => {
+            let Some(path) = choose_save_path(
+                &file_service,
+                &app_handle,
+                "animals.xlsx",
+                ReportFormat::Xlsx,
+            )
+            .await?
+            else {
+                return Ok(None);
+            };
+            Box::new(move |progress| {
+                job_future(run_animals_export(handle, progress, filters, path))
+            })
+        }
+        JobRequest::AdoptionRequestsExport { filters, anonymize } => {
+            let file_name = if anonymize {
+                "adoption-requests-anonymized.xlsx"
+            } else {
+                "adoption-requests.xlsx"
+            };
+            let Some(path) =
+                choose_save_path(&file_service, &app_handle, file_name, ReportFormat::Xlsx).await?
+            else {
+                return Ok(None);
+            };
+            Box::new(move |progress| {
+                job_future(run_adoption_requests_export(
+                    handle, progress, filters, anonymize, path,
+                ))
+            })
+        }
+        JobRequest::ChangesExport { since_timestamp } => {
+            let exported_timestamp = Utc::now().timestamp();
+            let Some(path) = choose_changes_path(&state, &app_handle, exported_timestamp).await?
+            else {
+                return Ok(None);
+            };
+            Box::new(move |progress| {
+                job_future(run_changes_export(
+                    handle,
+                    progress,
+                    since_timestamp.unwrap_or(0),
+                    exported_timestamp,
+                    path,
+                ))
+            })
+        }
+        JobRequest::ChangesImport => {
+            let Some(contents) = open_changes(&state, &app_handle).await? else {
+                return Ok(None);
+            };
+            Box::new(move |progress| job_future(run_changes_import(handle, progress, contents)))
+        }
+        JobRequest::AnimalsImport { animals } => {
+            Box::new(move |progress| job_future(run_animals_import(handle, progress, animals)))
+        }
+    };
+
+    // Queue the job behind any others
+    let queue = init_job_queue_once(&state, &app_handle).await;
+    match queue.enqueue(kind, &user.username, task) {
+        Ok(job) => Ok(Some(job)),
+        Err(e) => Err(CommandError::from_service("Failed to queue job", e)),
+    }
+}
+
+/// Command to retrieve one of the logged-in staff member's background jobs
+///
+/// # Arguments
+/// * `job_id` - The ID of the job
+///
+/// # Returns
+/// * `Ok(Some(Job))` - The job, with its progress and, once finished, its result or error
+/// * `Ok(None)` - If the job is unknown, belongs to someone else, or finished long ago
+/// * `Err(CommandError)` - An error message if the user is not staff
+#[tauri::command]
+async fn get_job(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    job_id: String,
+) -> Result<Option<Job>, CommandError> {
+    let user = require_staff(&state, &app_handle).await?;
+
+    let queue = init_job_queue_once(&state, &app_handle).await;
+    match queue.job(&job_id) {
+        Ok(job) => Ok(job.filter(|job| job.username == user.username)),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to retrieve job with ID {}", job_id),
+            e,
+        )),
+    }
+}
+
+/// Command to list the logged-in staff member's background jobs, most recent first
+///
+/// # Returns
+/// * `Ok(Vec<Job>)` - The jobs still queued or running, and those finished recently
+/// * `Err(CommandError)` - An error message if the user is not staff
+#[tauri::command]
+async fn get_jobs(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<Job>, CommandError> {
+    let user = require_staff(&state, &app_handle).await?;
+
+    let queue = init_job_queue_once(&state, &app_handle).await;
+    match queue.jobs_of(&user.username) {
+        Ok(jobs) => Ok(jobs),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve jobs", e)),
+    }
+}
+
+/// Asks the user where to save an exported document
+///
+/// # Arguments
+/// * `file_service` - The file service showing the dialog
+/// * `app_handle` - Reference to the Tauri application handle
+/// * `file_name` - File name suggested to the user
+/// * `format` - The format of the document
+///
+/// # Returns
+/// * `Ok(Option<PathBuf>)` - The chosen path, or None if the user cancels
+/// * `Err(CommandError)` - An error message if the dialog fails
+async fn choose_save_path(
+    file_service: &FileService,
+    app_handle: &AppHandle,
+    file_name: &str,
+    format: ReportFormat,
+) -> Result<Option<PathBuf>, CommandError> {
+    file_service
+        .choose_save_path(
+            app_handle,
+            file_name,
+            format.filter_name(),
+            format.extension(),
+        )
+        .await
+        .map_err(|e| CommandError::from_service("Failed to choose where to save", e))
+}
+
+/// Suggests a file name for an exported activity report
+///
+/// # Arguments
+/// * `year` - The year of the report
+/// * `month` - The month of the report, or None for the whole year
+/// * `format` - The format of the report
+///
+/// # Returns
+/// * `String` - The file name, such as `report-2024-03.pdf`
+fn report_file_name(year: i32, month: Option<u32>, format: ReportFormat) -> String {
+    match month {
+        Some(month) => format!("report-{}-{:02}.{}", year, month, format.extension()),
+        None => format!("report-{}.{}", year, format.extension()),
+    }
+}
+
+/// Asks the user where to save a changelog of the changes made on this installation
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+/// * `exported_timestamp` - When the export was requested, which names the file
+///
+/// # Returns
+/// * `Ok(Option<PathBuf>)` - The chosen path, or None if the user cancels
+/// * `Err(CommandError)` - An error message if the dialog fails
+async fn choose_changes_path(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+    exported_timestamp: i64,
+) -> Result<Option<PathBuf>, CommandError> {
+    let file_service = init_file_service_once(state, app_handle).await?;
+    let file_name = format!("changes-{}.json", exported_timestamp);
+    file_service
+        .choose_save_path(app_handle, &file_name, "JSON", "json")
+        .await
+        .map_err(|e| CommandError::from_service("Failed to choose where to save", e))
+}
+
+/// Asks the user for a changelog file exported by another installation and reads it
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(Option<Vec<u8>>)` - The bytes of the file, or None if the user cancels
+/// * `Err(CommandError)` - An error message if the file could not be read
+async fn open_changes(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+) -> Result<Option<Vec<u8>>, CommandError> {
+    let file_service = init_file_service_once(state, app_handle).await?;
+    file_service
+        .open_file(app_handle, "JSON", "json")
+        .await
+        .map_err(|e| CommandError::from_service("Failed to open changes", e))
+}
+
+/// Boxes the work of a job, turning its result into JSON for the frontend
+///
+/// # Arguments
+/// * `work` - The work of the job
+///
+/// # Returns
+/// * `JobFuture` - The work, as run by the job worker
+fn job_future<T, F>(work: F) -> JobFuture
+where
+    T: serde::Serialize,
+    F: std::future::Future<Output = Result<T, CommandError>> + Send + 'static,
+{
+    Box::pin(async move {
+        let result = work.await.map_err(|e| anyhow!(e))?;
+        Ok(serde_json::to_value(result)?)
+    })
+}
+
+/// Writes an exported document to the path the user chose
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+/// * `path` - The chosen path
+/// * `contents` - The bytes of the document
+///
+/// # Returns
+/// * `Ok(PathBuf)` - The path the document was saved to
+/// * `Err(CommandError)` - An error message if the document could not be written
+async fn save_export(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+    path: PathBuf,
+    contents: Vec<u8>,
+) -> Result<PathBuf, CommandError> {
+    let file_service = init_file_service_once(state, app_handle).await?;
+    match file_service.save_document(&path, contents).await {
+        Ok(()) => Ok(path),
+        Err(e) => Err(CommandError::from_service("Failed to save export", e)),
+    }
+}
+
+/// Reads the locale exported documents are written in
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(Locale)` - The locale chosen in the settings
+/// * `Err(CommandError)` - An error message if the settings could not be read
+async fn settings_locale(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+) -> Result<Locale, CommandError> {
+    match run_settings_task(state, app_handle, |settings| settings.locale()).await? {
+        Ok(locale) => Ok(locale),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve locale", e)),
+    }
+}
+
+/// Reads the shelter name and currency printed on invoices, receipts and reports
+///
+/// # Arguments
+/// * `state` - The managed application state
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok((String, String))` - The shelter name and the code of its default currency
+/// * `Err(CommandError)` - An error message if the settings could not be read
+async fn settings_shelter_and_currency(
+    state: &State<'_, AppState>,
+    app_handle: &AppHandle,
+) -> Result<(String, String), CommandError> {
+    match run_settings_task(state, app_handle, |settings| {
+        Ok((settings.shelter_name()?, settings.default_currency()?))
+    })
+    .await?
+    {
+        Ok(settings) => Ok(settings),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve shelter name and currency",
+            e,
+        )),
+    }
+}
+
+/// Exports the monthly or annual activity report of the shelter, as a background job
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle
+/// * `progress` - Reports the progress of the job
+/// * `year` - The year to report on
+/// * `month` - The month to report on, from 1 to 12, or None for the whole year
+/// * `location_id` - Only include animals at this location, or None for the whole organization
+/// * `format` - The format to export in
+/// * `path` - Where to save the report
+///
+/// # Returns
+/// * `Ok(PathBuf)` - The path the report was saved to
+/// * `Err(CommandError)` - An error message if the report could not be generated or saved
+async fn run_report_export(
+    app_handle: AppHandle,
+    progress: JobProgress,
+    year: i32,
+    month: Option<u32>,
+    location_id: Option<String>,
+    format: ReportFormat,
+    path: PathBuf,
+) -> Result<PathBuf, CommandError> {
+    let state = app_handle.state::<AppState>();
+
+    // Query the reports to include
+    progress.report(10, "Gathering report data");
+    let reports = match run_database_task(&state, &app_handle, move |db| {
+        let location_id = location_id.as_deref();
+        match month {
+            Some(month) => db
+                .query_monthly_report(year, month, location_id)
+                .map(|r| vec![r]),
+            None => db.query_annual_report(year, location_id),
+        }
+    })
+    .await?
+    {
+        Ok(reports) => reports,
+        Err(e) => return Err(CommandError::from_service("Failed to generate report", e)),
+    };
+
+    // Render the document, headed with the shelter's name and currency if it is a PDF
+    progress.report(50, "Rendering report");
+    let locale = settings_locale(&state, &app_handle).await?;
+    let rendered = match format {
+        ReportFormat::Pdf => {
+            let (shelter_name, currency) =
+                settings_shelter_and_currency(&state, &app_handle).await?;
+            let title = match month {
+                Some(_) => i18n::format(
+                    MessageKey::MonthlyReportTitle,
+                    locale,
+                    &[
+                        ("shelter", &shelter_name),
+                        ("month", &export_service::month_label(&reports[0], locale)),
+                    ],
+                ),
+                None => i18n::format(
+                    MessageKey::AnnualReportTitle,
+                    locale,
+                    &[("shelter", &shelter_name), ("year", &year.to_string())],
+                ),
+            };
+            export_service::render_reports_pdf(&title, &reports, &currency, locale)
+        }
+        ReportFormat::Xlsx => export_service::render_reports_xlsx(&reports, locale),
+    };
+    let contents =
+        rendered.map_err(|e| CommandError::from_service("Failed to render report", e))?;
+
+    progress.report(90, "Saving report");
+    save_export(&state, &app_handle, path, contents).await
+}
+
+/// Exports animals as an Excel workbook, as a background job
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle
+/// * `progress` - Reports the progress of the job
+/// * `filters` - Optional map of filter criteria and values
+/// * `path` - Where to save the workbook
+///
+/// # Returns
+/// * `Ok(PathBuf)` - The path the workbook was saved to
+/// * `Err(CommandError)` - An error message if the animals could not be exported
+async fn run_animals_export(
+    app_handle: AppHandle,
+    progress: JobProgress,
+    filters: Option<HashMap<FilterCriteria, Option<FilterValue>>>,
+    path: PathBuf,
+) -> Result<PathBuf, CommandError> {
+    let state = app_handle.state::<AppState>();
+
+    // Query animals with filters
+    progress.report(10, "Gathering animals");
+    let animals =
+        match run_database_task(&state, &app_handle, move |db| db.query_animals(filters)).await? {
+            Ok(animals) => animals,
+            Err(e) => return Err(CommandError::from_service("Failed to retrieve animals", e)),
+        };
+
+    // Render the workbook
+    progress.report(50, "Rendering workbook");
+    let locale = settings_locale(&state, &app_handle).await?;
+    let contents = export_service::render_animals_xlsx(&animals, locale)
+        .map_err(|e| CommandError::from_service("Failed to render animals", e))?;
+
+    progress.report(90, "Saving workbook");
+    save_export(&state, &app_handle, path, contents).await
+}
+
+/// Exports adoption requests as an Excel workbook, as a background job
+///
+/// Anonymized workbooks leave out the applicants' names, contact details, occupations
+/// and incomes, and stand in for each applicant with a hash salted anew for every
+/// export, keeping the household details research partners aggregate.
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle
+/// * `progress` - Reports the progress of the job
+/// * `filters` - Status, date range and species filters; unset fields are ignored
+/// * `anonymize` - Whether to hide who the applicants are
+/// * `path` - Where to save the workbook
+///
+/// # Returns
+/// * `Ok(PathBuf)` - The path the workbook was saved to
+/// * `Err(CommandError)` - An error message if the requests could not be exported
+async fn run_adoption_requests_export(
+    app_handle: AppHandle,
+    progress: JobProgress,
+    filters: AdoptionRequestFilters,
+    anonymize: bool,
+    path: PathBuf,
+) -> Result<PathBuf, CommandError> {
+    let state = app_handle.state::<AppState>();
+
+    if anonymize {
+        progress.report(10, "Gathering adoption requests");
+        let requests = match run_database_task(&state, &app_handle, move |db| {
+            db.query_adoption_request_details(&filters)
+        })
+        .await?
+        {
+            Ok(requests) => requests,
+            Err(e) => {
+                return Err(CommandError::from_service(
+                    "Failed to retrieve adoption requests",
+                    e,
+                ))
+            }
+        };
+
+        progress.report(50, "Rendering workbook");
+        let anonymized =
+            export_service::anonymize_adoption_requests(&requests, &Uuid::new_v4().to_string());
+        let locale = settings_locale(&state, &app_handle).await?;
+        let contents =
+            export_service::render_anonymized_adoption_requests_xlsx(&anonymized, locale)
+                .map_err(|e| CommandError::from_service("Failed to render adoption requests", e))?;
+
+        progress.report(90, "Saving workbook");
+        return save_export(&state, &app_handle, path, contents).await;
+    }
+
+    // Query adoption requests with filters
+    progress.report(10, "Gathering adoption requests");
+    let requests = match run_database_task(&state, &app_handle, move |db| {
+        db.query_adoption_requests(&filters)
+    })
+    .await?
+    {
+        Ok(requests) => requests,
+        Err(e) => {
+            return Err(CommandError::from_service(
+                "Failed to retrieve adoption requests",
+                e,
+            ))
+        }
+    };
+
+    // Render the workbook
+    progress.report(50, "Rendering workbook");
+    let locale = settings_locale(&state, &app_handle).await?;
+    let contents = export_service::render_adoption_requests_xlsx(&requests, locale)
+        .map_err(|e| CommandError::from_service("Failed to render adoption requests", e))?;
+
+    progress.report(90, "Saving workbook");
+    save_export(&state, &app_handle, path, contents).await
+}
+
+/// Exports the changes made on this installation as a changelog file, as a background job
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle
+/// * `progress` - Reports the progress of the job
+/// * `since_timestamp` - Only export changes made at or after this timestamp
+/// * `exported_timestamp` - When the export was requested
+/// * `path` - Where to save the changelog
+///
+/// # Returns
+/// * `Ok(PathBuf)` - The path the changelog was saved to
+/// * `Err(CommandError)` - An error message if the export fails
+async fn run_changes_export(
+    app_handle: AppHandle,
+    progress: JobProgress,
+    since_timestamp: i64,
+    exported_timestamp: i64,
+    path: PathBuf,
+) -> Result<PathBuf, CommandError> {
+    let state = app_handle.state::<AppState>();
+
+    // Collect the changes
+    progress.report(10, "Collecting changes");
+    let changelog = match run_database_task(&state, &app_handle, move |db| {
+        db.export_changes(since_timestamp, exported_timestamp)
+    })
+    .await?
+    {
+        Ok(changelog) => changelog,
+        Err(e) => return Err(CommandError::from_service("Failed to export changes", e)),
+    };
+    let contents = serde_json::to_vec_pretty(&changelog)
+        .map_err(|e| CommandError::from_service("Failed to serialize changes", e))?;
+
+    progress.report(90, "Saving changes");
+    save_export(&state, &app_handle, path, contents).await
+}
+
+/// Imports a changelog file exported by another installation, as a background job
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle
+/// * `progress` - Reports the progress of the job
+/// * `contents` - The bytes of the changelog file
+///
+/// # Returns
+/// * `Ok(ImportChangesResult)` - How many changes were applied, skipped and deleted
+/// * `Err(CommandError)` - An error message if the file is not a changelog or the import fails
+async fn run_changes_import(
+    app_handle: AppHandle,
+    progress: JobProgress,
+    contents: Vec<u8>,
+) -> Result<ImportChangesResult, CommandError> {
+    let state = app_handle.state::<AppState>();
+
+    progress.report(10, "Reading changes");
+    let changelog: Changelog = serde_json::from_slice(&contents)
+        .map_err(|e| CommandError::from_service("Failed to read changes", e))?;
+
+    // Apply the changes
+    progress.report(30, "Applying changes");
+    match run_database_task(&state, &app_handle, move |db| db.import_changes(&changelog)).await? {
+        Ok(result) => Ok(result),
+        Err(e) => Err(CommandError::from_service("Failed to import changes", e)),
+    }
+}
+
+/// Inserts several animals into the database at once, as a background job
+///
+/// All animals are inserted in a single transaction: if any of them fails, none are kept.
+/// Waitlist notifications are emitted as for `create_animal`.
+///
+/// # Arguments
+/// * `app_handle` - The Tauri application handle
+/// * `progress` - Reports the progress of the job
+/// * `animals` - The animal data to insert
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - The IDs of the inserted animals, in the order given
+/// * `Err(CommandError)` - An error message if the insertion fails
+async fn run_animals_import(
+    app_handle: AppHandle,
+    progress: JobProgress,
+    animals: Vec<Animal>,
+) -> Result<Vec<String>, CommandError> {
+    let state = app_handle.state::<AppState>();
+
+    // Insert animals
+    progress.report(10, &format!("Creating {} animals", animals.len()));
+    match run_database_task(&state, &app_handle, move |db| {
+        db.insert_animals_batch(&animals)
+    })
+    .await?
+    {
+        Ok((ids, notifications)) => {
+            emit_notifications(&app_handle, &notifications);
+            for id in &ids {
+                emit_data_change(&app_handle, ANIMAL_CREATED_EVENT, id);
+            }
+            Ok(ids)
+        }
+        Err(e) => Err(CommandError::from_service("Failed to create animals", e)),
+    }
+}
+
+// ==================== AUTHENTICATION COMMANDS ====================
+
+/// Command to register a new user account
+///
+/// # Arguments
+/// * `username` - Username for the new account
+/// * `password` - Password for the new account
+/// * `role` - Role to assign to the user (Admin, Staff, or Customer) when no invite code is given
+/// * `invite_code` - Invite code issued by an admin, which decides the role; required for
+///   staff roles, except for the first account of a new shelter
+///
+/// # Returns
+/// * `Ok(())` - If the user was successfully registered and logged in
+/// * `Err(CommandError)` - An error message if registration fails or the invite code is invalid
+#[tauri::command]
+async fn sign_up(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    username: String,
+    password: String,
+    role: UserRole,
+    invite_code: Option<String>,
+) -> Result<(), CommandError> {
+    // Register user with new account
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.sign_up(&username, &password, role, invite_code.as_deref())
+    })
+    .await?;
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) if e.is::<InviteCodeError>() => Err(CommandError::permission_denied(format!(
+            "Failed to register user: {}",
+            e
+        ))),
+        Err(e) => Err(CommandError::from_service("Failed to register user", e)),
+    }
+}
+
+/// Command to log in an existing user
+///
+/// # Arguments
+/// * `username` - Username to log in
+/// * `password` - Password for authentication
+/// * `remember_me` - Whether the session should last for days instead of hours
+///
+/// # Returns
+/// * `Ok(LoginResult)` - Login result indicating success, invalid password, or user not found
+/// * `Err(CommandError)` - An error message if login process fails
+#[tauri::command]
+async fn log_in(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    username: String,
+    password: String,
+    remember_me: bool,
+) -> Result<LoginResult, CommandError> {
+    // Authenticate user credentials
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.log_in(&username, &password, remember_me)
+    })
+    .await?;
+
+    match result {
+        Ok(login_result) => Ok(login_result),
+        Err(e) => Err(CommandError::from_service("Failed to log in", e)),
+    }
+}
+
+/// Command to finish a login that is waiting for a two-factor code
+///
+/// # Arguments
+/// * `code` - Code shown by the user's authenticator app
+///
+/// # Returns
+/// * `Ok(LoginResult)` - Success, an invalid two-factor code, or too many attempts
+/// * `Err(CommandError)` - An error message if no login is waiting for a code
+#[tauri::command]
+async fn verify_totp(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    code: String,
+) -> Result<LoginResult, CommandError> {
+    let result =
+        run_authentication_task(&state, &app_handle, move |auth| auth.verify_totp(&code)).await?;
+
+    match result {
+        Ok(login_result) => Ok(login_result),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to verify two-factor code",
+            e,
+        )),
+    }
+}
+
+/// Command to check whether the logged-in staff member has two-factor authentication enabled
+///
+/// # Returns
+/// * `Ok(bool)` - True if logging in requires a two-factor code
+/// * `Err(CommandError)` - An error message if the user is not staff or retrieval fails
+#[tauri::command]
+async fn get_two_factor_status(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<bool, CommandError> {
+    let user = require_staff(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.is_totp_enabled(&user.username)
+    })
+    .await?;
+
+    match result {
+        Ok(enabled) => Ok(enabled),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to get two-factor status",
+            e,
+        )),
+    }
+}
+
+/// Command to start enrolling the logged-in staff member in two-factor authentication
+///
+/// # Returns
+/// * `Ok(TotpEnrollment)` - The secret and provisioning URI to add to an authenticator app
+/// * `Err(CommandError)` - An error message if the user is not staff or is already enrolled
+#[tauri::command]
+async fn enroll_totp(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<TotpEnrollment, CommandError> {
+    let user = require_staff(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.enroll_totp(&user.username)
+    })
+    .await?;
+
+    match result {
+        Ok(enrollment) => Ok(enrollment),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to enroll in two-factor authentication",
+            e,
+        )),
+    }
+}
+
+/// Command to finish enrolling the logged-in staff member in two-factor authentication
+///
+/// # Arguments
+/// * `code` - Code shown by the authenticator app the secret was added to
+///
+/// # Returns
+/// * `Ok(bool)` - True if two-factor authentication is now enabled, false if the code was wrong
+/// * `Err(CommandError)` - An error message if the user is not staff or saving fails
+#[tauri::command]
+async fn confirm_totp_enrollment(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    code: String,
+) -> Result<bool, CommandError> {
+    let user = require_staff(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.confirm_totp_enrollment(&user.username, &code)
+    })
+    .await?;
+
+    match result {
+        Ok(enabled) => Ok(enabled),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to confirm two-factor enrollment",
+            e,
+        )),
+    }
+}
+
+/// Command to turn off two-factor authentication for the logged-in staff member, or for
+/// another account when the user is an admin (such as after a lost phone)
+///
+/// # Arguments
+/// * `username` - Username of the account, or None for the logged-in user
+///
+/// # Returns
+/// * `Ok(())` - If two-factor authentication was turned off
+/// * `Err(CommandError)` - An error message if the user may not change the account or saving fails
+#[tauri::command]
+async fn disable_totp(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    username: Option<String>,
+) -> Result<(), CommandError> {
+    let user = require_staff(&state, &app_handle).await?;
+    let username = match username {
+        Some(username) if username != user.username => {
+            require_admin(&state, &app_handle).await?;
+            username
+        }
+        _ => user.username,
+    };
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.disable_totp(&username)
+    })
+    .await?;
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to disable two-factor authentication",
+            e,
+        )),
+    }
+}
+
+/// Command to issue a one-time temporary password for a user who forgot theirs (staff only)
+///
+/// Staff members can reset customers' passwords, while admins can reset anyone's.
+///
+/// # Arguments
+/// * `username` - Username of the account to reset
+///
+/// # Returns
+/// * `Ok(PasswordReset)` - The temporary password to hand to the user and when it expires
+/// * `Err(CommandError)` - An error message if the user may not reset the account or it does not exist
+#[tauri::command]
+async fn create_password_reset(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    username: String,
+) -> Result<PasswordReset, CommandError> {
+    let user = require_staff(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.create_password_reset(&username, &user.role)
+    })
+    .await?;
+
+    match result {
+        Ok(reset) => Ok(reset),
+        Err(e) => Err(CommandError::from_service("Failed to reset password", e)),
+    }
+}
+
+/// Command to set a new password using a temporary password, then log in
+///
+/// # Arguments
+/// * `username` - Username of the account being reset
+/// * `temporary_password` - The temporary password issued by staff
+/// * `new_password` - The new password for the account
+///
+/// # Returns
+/// * `Ok(LoginResult)` - Success, invalid password if the temporary password is wrong or
+///   expired, or too many attempts
+/// * `Err(CommandError)` - An error message if the new password is invalid or saving it fails
+#[tauri::command]
+async fn complete_password_reset(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    username: String,
+    temporary_password: String,
+    new_password: String,
+) -> Result<LoginResult, CommandError> {
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.complete_password_reset(&username, &temporary_password, &new_password)
+    })
+    .await?;
+
+    match result {
+        Ok(login_result) => Ok(login_result),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to complete password reset",
+            e,
+        )),
+    }
+}
+
+/// Command to replace the password of an account that must change it, then log in
+///
+/// # Arguments
+/// * `username` - Username of the account
+/// * `current_password` - The account's current or temporary password
+/// * `new_password` - The new password for the account
+///
+/// # Returns
+/// * `Ok(LoginResult)` - Success, invalid password if the current password is wrong,
+///   suspended, or too many attempts
+/// * `Err(CommandError)` - An error message if the new password is invalid or saving it fails
+#[tauri::command]
+async fn change_password(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    username: String,
+    current_password: String,
+    new_password: String,
+) -> Result<LoginResult, CommandError> {
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.change_password(&username, &current_password, &new_password)
+    })
+    .await?;
+
+    match result {
+        Ok(login_result) => Ok(login_result),
+        Err(e) => Err(CommandError::from_service("Failed to change password", e)),
+    }
+}
+
+/// Command to get current logged-in user information
+///
+/// # Returns
+/// * `Ok(Some(CurrentUser))` - Current user info if logged in
+/// * `Ok(None)` - If no user is currently logged in
+/// * `Err(CommandError)` - An error message if retrieval fails
+#[tauri::command]
+async fn get_current_user(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Option<CurrentUser>, CommandError> {
+    // Get current user
+    let result =
+        run_authentication_task(&state, &app_handle, |auth| auth.get_current_user()).await?;
+
+    match result {
+        Ok(user) => Ok(user),
+        Err(e) => Err(CommandError::from_service("Failed to get current user", e)),
+    }
+}
+
+/// Command to record activity by the current user, keeping their session from idling out
+///
+/// # Returns
+/// * `Ok(bool)` - True if the user is still logged in, false if their session has ended
+/// * `Err(CommandError)` - An error message if the session could not be refreshed
+#[tauri::command]
+async fn refresh_session(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<bool, CommandError> {
+    let result =
+        run_authentication_task(&state, &app_handle, |auth| auth.refresh_session()).await?;
+
+    match result {
+        Ok(active) => Ok(active),
+        Err(e) => Err(CommandError::from_service("Failed to refresh session", e)),
+    }
+}
+
+/// Command to log out the current user and invalidate their session
+///
+/// # Returns
+/// * `Ok(())` - If the user was logged out
+/// * `Err(CommandError)` - An error message if the session could not be invalidated
+#[tauri::command]
+async fn log_out(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), CommandError> {
+    // Log out user
+    let result = run_authentication_task(&state, &app_handle, |auth| auth.log_out()).await?;
+
+    result.map_err(|e| CommandError::from_service("Failed to log out", e))
+}
+
+/// Command to list every user account (admin only)
+///
+/// # Returns
+/// * `Ok(Vec<UserAccount>)` - The accounts, ordered by username
+/// * `Err(CommandError)` - An error message if the user is not an admin or retrieval fails
+#[tauri::command]
+async fn get_users(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<UserAccount>, CommandError> {
+    require_admin(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, |auth| auth.list_users()).await?;
+
+    match result {
+        Ok(users) => Ok(users),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve users", e)),
+    }
+}
+
+/// Command to change the role of a user account (admin only)
+///
+/// # Arguments
+/// * `username` - Username of the account to change
+/// * `role` - New role for the account
+///
+/// # Returns
+/// * `Ok(())` - If the role was changed
+/// * `Err(CommandError)` - An error message if the user is not an admin, the account does not exist,
+///   or it is the last admin
+#[tauri::command]
+async fn change_user_role(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    username: String,
+    role: UserRole,
+) -> Result<(), CommandError> {
+    require_admin(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.change_user_role(&username, role)
+    })
+    .await?;
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) => Err(CommandError::from_service("Failed to change user role", e)),
+    }
+}
+
+/// Command to issue a single-use invite code for registering a staff account (admin only)
+///
+/// # Arguments
+/// * `role` - Role the code grants; must be staff or admin
+///
+/// # Returns
+/// * `Ok(InviteCode)` - The code to hand to the new staff member
+/// * `Err(CommandError)` - An error message if the user is not an admin or the role needs no invite
+#[tauri::command]
+async fn create_invite_code(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    role: UserRole,
+) -> Result<InviteCode, CommandError> {
+    let admin = require_admin(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.create_invite_code(&admin.username, role)
+    })
+    .await?;
+
+    match result {
+        Ok(invite) => Ok(invite),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to create invite code",
+            e,
+        )),
+    }
+}
+
+/// Command to create an account on behalf of a user (admin only); the user must
+/// choose their own password the first time they log in
+///
+/// # Arguments
+/// * `username` - Username for the new account
+/// * `password` - Initial password to hand to the user
+/// * `role` - Role to assign to the new user
+///
+/// # Returns
+/// * `Ok(())` - If the account was created
+/// * `Err(CommandError)` - An error message if the user is not an admin or creation fails
+#[tauri::command]
+async fn create_user(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    username: String,
+    password: String,
+    role: UserRole,
+) -> Result<(), CommandError> {
+    require_admin(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.create_user(&username, &password, role)
+    })
+    .await?;
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) => Err(CommandError::from_service("Failed to create user", e)),
+    }
+}
+
+/// Command to suspend a user account so it cannot log in (admin only)
+///
+/// # Arguments
+/// * `username` - Username of the account to suspend
+///
+/// # Returns
+/// * `Ok(())` - If the account was suspended
+/// * `Err(CommandError)` - An error message if the user is not an admin, tries to suspend themselves,
+///   or the account does not exist or is the last active admin
+#[tauri::command]
+async fn suspend_user(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    username: String,
+) -> Result<(), CommandError> {
+    let admin = require_admin(&state, &app_handle).await?;
+    if admin.username == username {
+        return Err(CommandError::conflict(
+            "You cannot suspend your own account",
+        ));
+    }
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.suspend_user(&username)
+    })
+    .await?;
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) => Err(CommandError::from_service("Failed to suspend user", e)),
+    }
+}
+
+/// Command to reactivate a suspended user account (admin only)
+///
+/// # Arguments
+/// * `username` - Username of the account to reactivate
+///
+/// # Returns
+/// * `Ok(())` - If the account was reactivated
+/// * `Err(CommandError)` - An error message if the user is not an admin or the account does not exist
+#[tauri::command]
+async fn reactivate_user(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    username: String,
+) -> Result<(), CommandError> {
+    require_admin(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.reactivate_user(&username)
+    })
+    .await?;
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) => Err(CommandError::from_service("Failed to reactivate user", e)),
+    }
+}
+
+/// Command to reset the password of a user account (admin only)
+///
+/// # Arguments
+/// * `username` - Username of the account to change
+/// * `new_password` - New password for the account
+///
+/// # Returns
+/// * `Ok(())` - If the password was reset
+/// * `Err(CommandError)` - An error message if the user is not an admin, the account does not exist,
+///   or the password is invalid
+#[tauri::command]
+async fn reset_user_password(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    username: String,
+    new_password: String,
+) -> Result<(), CommandError> {
+    require_admin(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.reset_password(&username, &new_password)
+    })
+    .await?;
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) => Err(CommandError::from_service("Failed to reset password", e)),
+    }
+}
+
+/// Command to get how long sessions last (admin only)
+///
+/// # Returns
+/// * `Ok(SessionPolicy)` - The session lifetimes and idle timeout
+/// * `Err(CommandError)` - An error message if the user is not an admin or retrieval fails
+#[tauri::command]
+async fn get_session_policy(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<SessionPolicy, CommandError> {
+    require_admin(&state, &app_handle).await?;
+
+    let result =
+        run_authentication_task(&state, &app_handle, |auth| auth.get_session_policy()).await?;
+
+    match result {
+        Ok(policy) => Ok(policy),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to retrieve session policy",
+            e,
+        )),
+    }
+}
+
+/// Command to change how long sessions last (admin only)
+///
+/// # Arguments
+/// * `policy` - The new session lifetimes and idle timeout
+///
+/// # Returns
+/// * `Ok(())` - If the policy was saved
+/// * `Err(CommandError)` - An error message if the user is not an admin or a duration is invalid
+#[tauri::command]
+async fn set_session_policy(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    policy: SessionPolicy,
+) -> Result<(), CommandError> {
+    require_admin(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.set_session_policy(&policy)
+    })
+    .await?;
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to update session policy",
+            e,
+        )),
+    }
+}
+
+/// Command to list the sessions that are still active (admin only)
+///
+/// # Returns
+/// * `Ok(Vec<SessionInfo>)` - The active sessions, most recently used first
+/// * `Err(CommandError)` - An error message if the user is not an admin or retrieval fails
+#[tauri::command]
+async fn get_sessions(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<SessionInfo>, CommandError> {
+    require_admin(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, |auth| auth.list_sessions()).await?;
+
+    match result {
+        Ok(sessions) => Ok(sessions),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve sessions", e)),
+    }
+}
+
+/// Command to end a session, such as one left open on another device (admin only)
+///
+/// # Arguments
+/// * `session_id` - ID of the session, as listed by `get_sessions`
+///
+/// # Returns
+/// * `Ok(bool)` - True if the session was ended, false if it was not found
+/// * `Err(CommandError)` - An error message if the user is not an admin or deletion fails
+#[tauri::command]
+async fn terminate_session(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    session_id: String,
+) -> Result<bool, CommandError> {
+    require_admin(&state, &app_handle).await?;
+
+    let result = run_authentication_task(&state, &app_handle, move |auth| {
+        auth.terminate_session(&session_id)
+    })
+    .await?;
+
+    match result {
+        Ok(terminated) => Ok(terminated),
+        Err(e) => Err(CommandError::from_service("Failed to terminate session", e)),
+    }
+}
+
+// ==================== SETTINGS COMMANDS ====================
+
+/// Command to get the current value of a setting
+///
+/// # Arguments
+/// * `key` - The setting to get
+///
+/// # Returns
+/// * `Ok(Setting)` - The value of the setting, or its default if it was never changed
+/// * `Err(CommandError)` - An error message if retrieval fails
+#[tauri::command]
+async fn get_setting(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    key: SettingKey,
+) -> Result<Setting, CommandError> {
+    match run_settings_task(&state, &app_handle, move |settings| {
+        settings.get_setting(key)
+    })
+    .await?
+    {
+        Ok(setting) => Ok(setting),
+        Err(e) => Err(CommandError::from_service(
+            format!("Failed to retrieve setting {}", key),
+            e,
+        )),
+    }
+}
+
+/// Command to get the current value of every setting
+///
+/// # Returns
+/// * `Ok(Vec<Setting>)` - The settings, with defaults for those that were never changed
+/// * `Err(CommandError)` - An error message if retrieval fails
+#[tauri::command]
+async fn get_settings(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<Setting>, CommandError> {
+    match run_settings_task(&state, &app_handle, |settings| settings.get_settings()).await? {
+        Ok(settings) => Ok(settings),
+        Err(e) => Err(CommandError::from_service("Failed to retrieve settings", e)),
+    }
+}
+
+/// Command to change a setting (admin only)
+///
+/// # Arguments
+/// * `key` - The setting to change
+/// * `value` - The new value
+///
+/// # Returns
+/// * `Ok(Setting)` - The setting as stored
+/// * `Err(CommandError)` - An error message if the user is not an admin or the value is not allowed
+#[tauri::command]
+async fn set_setting(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    key: SettingKey,
+    value: String,
+) -> Result<Setting, CommandError> {
+    require_admin(&state, &app_handle).await?;
+
+    let setting = match run_settings_task(&state, &app_handle, move |settings| {
+        settings.set_setting(key, &value)
+    })
+    .await?
+    {
+        Ok(setting) => setting,
+        Err(e) => {
+            return Err(CommandError::from_service(
+                format!("Failed to change setting {}", key),
+                e,
+            ))
+        }
+    };
+
+    // Settings that other services hold on to take effect straight away
+    if key == SettingKey::MaxUploadMegabytes {
+        if let Some(file_service) = state.file_service.get() {
+            match run_settings_task(&state, &app_handle, |settings| settings.max_upload_bytes())
+                .await?
+            {
+                Ok(bytes) => file_service.set_max_upload_bytes(bytes),
+                Err(e) => log::warn!("Failed to apply upload limit: {}", e),
+            }
+        }
+    }
+    if key == SettingKey::Locale {
+        match run_settings_task(&state, &app_handle, |settings| settings.locale()).await? {
+            Ok(locale) => i18n::set_current_locale(locale),
+            Err(e) => log::warn!("Failed to apply locale: {}", e),
+        }
+    }
+    if key == SettingKey::LogLevel {
+        match run_settings_task(&state, &app_handle, |settings| settings.log_level()).await? {
+            Ok(level) => apply_log_level(level),
+            Err(e) => log::warn!("Failed to apply log level: {}", e),
+        }
+    }
+    Ok(setting)
+}
+
+/// Command to get the text the backend writes, in the locale chosen in the settings
+///
+/// # Returns
+/// * `Ok(Vec<Message>)` - Every message key with its text, placeholders such as `{year}` left in
+/// * `Err(CommandError)` - An error message if the settings could not be read
+#[tauri::command]
+async fn get_messages(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Vec<Message>, CommandError> {
+    // Reading the settings applies the chosen locale if it has not been yet
+    init_settings_service_once(&state, &app_handle).await?;
+    Ok(i18n::messages(i18n::current_locale()))
+}
+
+// ==================== LOG COMMANDS ====================
+
+/// Changes which messages are written to the log from now on
+///
+/// # Arguments
+/// * `level` - The least severe kind of message to write
+fn apply_log_level(level: LogLevel) {
+    log::set_max_level(level.filter());
+    log::info!("Log level set to {}", level);
+}
+
+/// Creates a log service for the app log directory
+///
+/// # Arguments
+/// * `app_handle` - Reference to the Tauri application handle
+///
+/// # Returns
+/// * `Ok(LogService)` - The log service
+/// * `Err(CommandError)` - An error message if the log directory could not be found
+fn log_service(app_handle: &AppHandle) -> Result<LogService, CommandError> {
+    match app_handle.path().app_log_dir() {
+        Ok(log_dir) => Ok(LogService::new(log_dir)),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to find app log directory",
+            e,
+        )),
+    }
+}
+
+/// Command to get which messages are currently written to the log
+///
+/// # Returns
+/// * `Ok(Option<LogLevel>)` - The least severe kind of message written, or None if logging is off
+/// * `Err(CommandError)` - An error message if the user is not staff
+#[tauri::command]
+async fn get_log_level(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<Option<LogLevel>, CommandError> {
+    require_staff(&state, &app_handle).await?;
+    Ok(LogLevel::from_filter(log::max_level()))
+}
+
+/// Command to change which messages are written to the log, straight away and after
+/// restarts (admin only)
 ///
 /// # Arguments
-/// * `animal_id` - The ID of the animal to retrieve
+/// * `level` - The least severe kind of message to write
 ///
 /// # Returns
-/// * `Ok(Some(Animal))` - The animal data if found
-/// * `Ok(None)` - If no animal with the given ID exists
-/// * `Err(String)` - An error message if the query fails
+/// * `Ok(())` - If the level was changed
+/// * `Err(CommandError)` - An error message if the user is not an admin or the level could not be stored
 #[tauri::command]
-async fn get_animal_by_id(
-    state: State<'_, Mutex<AppState>>,
+async fn set_log_level(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    animal_id: String,
-) -> Result<Option<Animal>, String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the database service
-    init_database_service_once(&mut state_guard, &app_handle).await?;
-
-    // Query animal by ID
-    match state_guard
-        .database_service
-        .as_ref()
-        .unwrap()
-        .query_animal_by_id(&animal_id)
-    {
-        Ok(animal) => Ok(animal),
-        Err(e) => Err(format!(
-            "Failed to retrieve animal with ID {}: {}",
-            animal_id, e
-        )),
-    }
+    level: LogLevel,
+) -> Result<(), CommandError> {
+    set_setting(state, app_handle, SettingKey::LogLevel, level.to_string()).await?;
+    Ok(())
 }
 
-/// Command to insert a new animal into the database
+/// Command to read the last lines of the log, for a support view
 ///
 /// # Arguments
-/// * `animal` - The animal data to insert
+/// * `line_count` - How many lines to read, at most 1000
 ///
 /// # Returns
-/// * `Ok(())` - If the animal was successfully inserted
-/// * `Err(String)` - An error message if the insertion fails
+/// * `Ok(Vec<String>)` - The lines, oldest first
+/// * `Err(CommandError)` - An error message if the user is not staff or the log could not be read
 #[tauri::command]
-async fn create_animal(
-    state: State<'_, Mutex<AppState>>,
+async fn get_log_tail(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    animal: Animal,
-) -> Result<(), String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the database service
-    init_database_service_once(&mut state_guard, &app_handle).await?;
+    line_count: usize,
+) -> Result<Vec<String>, CommandError> {
+    require_staff(&state, &app_handle).await?;
 
-    // Insert animal
-    match state_guard
-        .database_service
-        .as_ref()
-        .unwrap()
-        .insert_animal(&animal)
-    {
-        Ok(()) => Ok(()),
-        Err(e) => Err(format!("Failed to create animal: {}", e)),
+    match log_service(&app_handle)?.tail(line_count).await {
+        Ok(lines) => Ok(lines),
+        Err(e) => Err(CommandError::from_service("Failed to read log", e)),
     }
 }
 
-/// Command to update an existing animal in the database
-///
-/// # Arguments
-/// * `animal` - The updated animal data
+/// Command to archive the current log file and start it afresh (admin only)
 ///
 /// # Returns
-/// * `Ok(bool)` - True if animal was found and updated, false if not found
-/// * `Err(String)` - An error message if the update fails
+/// * `Ok(Some(PathBuf))` - The path of the archived log
+/// * `Ok(None)` - If nothing has been logged since the last archive
+/// * `Err(CommandError)` - An error message if the user is not an admin or archiving fails
 #[tauri::command]
-async fn update_animal(
-    state: State<'_, Mutex<AppState>>,
+async fn rotate_logs(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    animal: Animal,
-) -> Result<bool, String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the database service
-    init_database_service_once(&mut state_guard, &app_handle).await?;
+) -> Result<Option<PathBuf>, CommandError> {
+    require_admin(&state, &app_handle).await?;
 
-    // Update animal
-    match state_guard
-        .database_service
-        .as_ref()
-        .unwrap()
-        .update_animal(&animal)
-    {
-        Ok(updated) => Ok(updated),
-        Err(e) => Err(format!("Failed to update animal: {}", e)),
+    match log_service(&app_handle)?.rotate(Utc::now()).await {
+        Ok(archive) => Ok(archive),
+        Err(e) => Err(CommandError::from_service("Failed to rotate logs", e)),
     }
 }
 
-/// Command to delete an animal from the database
-///
-/// # Arguments
-/// * `animal_id` - The ID of the animal to delete
+/// Command to save every log file as a single text file, to attach to a bug report
 ///
 /// # Returns
-/// * `Ok(bool)` - True if animal was found and deleted, false if not found
-/// * `Err(String)` - An error message if the deletion fails
+/// * `Ok(Some(PathBuf))` - The path the logs were saved to
+/// * `Ok(None)` - If the user cancels
+/// * `Err(CommandError)` - An error message if the user is not staff or the logs could not be saved
 #[tauri::command]
-async fn delete_animal(
-    state: State<'_, Mutex<AppState>>,
+async fn export_logs(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    animal_id: String,
-) -> Result<bool, String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
+) -> Result<Option<PathBuf>, CommandError> {
+    require_staff(&state, &app_handle).await?;
 
-    // Lazily initialize the database service
-    init_database_service_once(&mut state_guard, &app_handle).await?;
-
-    // Delete animal
-    match state_guard
-        .database_service
-        .as_ref()
-        .unwrap()
-        .delete_animal(&animal_id)
+    let file_service = init_file_service_once(&state, &app_handle).await?;
+    let file_name = format!("logs-{}.txt", Utc::now().format("%Y-%m-%d"));
+    let path = match file_service
+        .choose_save_path(&app_handle, &file_name, "Text", "txt")
+        .await
     {
-        Ok(deleted) => Ok(deleted),
-        Err(e) => Err(format!(
-            "Failed to delete animal with ID {}: {}",
-            animal_id, e
-        )),
-    }
+        Ok(Some(path)) => path,
+        Ok(None) => return Ok(None),
+        Err(e) => {
+            return Err(CommandError::from_service(
+                "Failed to choose where to save",
+                e,
+            ))
+        }
+    };
+
+    let contents = log_service(&app_handle)?
+        .export()
+        .await
+        .map_err(|e| CommandError::from_service("Failed to export logs", e))?;
+    save_export(&state, &app_handle, path, contents)
+        .await
+        .map(Some)
 }
 
-// ==================== ADOPTION REQUEST TABLE COMMANDS ====================
+// ==================== HEALTH COMMANDS ====================
 
-/// Command to retrieve a specific adoption request by ID
+/// Command to check the state of the application, with warnings about anything that
+/// needs attention, such as an unreachable database, low disk space or an old backup
 ///
-/// # Arguments
-/// * `request_id` - The ID of the adoption request to retrieve
+/// Starts the database, file and settings services if they are not running yet. A
+/// service that cannot be started is reported as a warning rather than an error.
 ///
 /// # Returns
-/// * `Ok(Some(AdoptionRequest))` - The adoption request data if found
-/// * `Ok(None)` - If no request with the given ID exists
-/// * `Err(String)` - An error message if the query fails
+/// * `Ok(HealthReport)` - The state of the application and its warnings
+/// * `Err(CommandError)` - An error message if the user is not staff
 #[tauri::command]
-async fn get_adoption_request_by_id(
-    state: State<'_, Mutex<AppState>>,
+async fn get_health(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    request_id: String,
-) -> Result<Option<AdoptionRequest>, String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
+) -> Result<HealthReport, CommandError> {
+    require_staff(&state, &app_handle).await?;
 
-    // Lazily initialize the database service
-    init_database_service_once(&mut state_guard, &app_handle).await?;
+    let mut report = HealthReport {
+        expected_schema_version: DATA_VERSION,
+        checked_timestamp: Utc::now().timestamp(),
+        ..HealthReport::default()
+    };
 
-    // Query adoption request by ID
-    match state_guard
-        .database_service
-        .as_ref()
-        .unwrap()
-        .query_adoption_request_by_id(&request_id)
+    // Reading the schema version also checks the database can be queried
+    match run_database_task(&state, &app_handle, |db| db.schema_version()).await {
+        Ok(Ok(version)) => {
+            report.database_connected = true;
+            report.schema_version = Some(version);
+        }
+        Ok(Err(e)) => log::warn!("Health check failed to query the database: {:#}", e),
+        Err(e) => log::warn!("Health check failed to open the database: {}", e),
+    }
+
+    match init_file_service_once(&state, &app_handle).await {
+        Ok(file_service) => {
+            match file_service.available_space().await {
+                Ok(bytes) => report.available_disk_bytes = Some(bytes),
+                Err(e) => log::warn!("Health check failed to read free disk space: {:#}", e),
+            }
+            match file_service.last_backup_timestamp().await {
+                Ok(timestamp) => report.last_backup_timestamp = timestamp,
+                Err(e) => log::warn!("Health check failed to find the last backup: {:#}", e),
+            }
+        }
+        Err(e) => log::warn!("Health check failed to open the file storage: {}", e),
+    }
+
+    match run_settings_task(&state, &app_handle, |settings| {
+        settings.backup_interval_hours()
+    })
+    .await
     {
-        Ok(request) => Ok(request),
-        Err(e) => Err(format!(
-            "Failed to retrieve adoption request with ID {}: {}",
-            request_id, e
-        )),
+        Ok(Ok(hours)) => report.backup_interval_hours = Some(hours),
+        Ok(Err(e)) => log::warn!("Health check failed to read the backup interval: {:#}", e),
+        Err(e) => log::warn!("Health check failed to open the settings: {}", e),
+    }
+
+    report.services = ServiceStatus {
+        file_service: state.file_service.get().is_some(),
+        database_service: state.database_service.get().is_some(),
+        authentication_service: state.authentication_service.get().is_some(),
+        settings_service: state.settings_service.get().is_some(),
+        job_queue: state.job_queue.get().is_some(),
+    };
+    report.warnings = health_service::find_warnings(&report, i18n::current_locale());
+    for warning in &report.warnings {
+        log::warn!("Health check: {}", warning.text);
     }
+    Ok(report)
 }
 
-/// Command to insert a new adoption request into the database
+// ==================== FILE SERVICE COMMANDS ====================
+
+/// Command to upload a file selected by the user
 ///
 /// # Arguments
-/// * `request` - The adoption request data to insert
+/// * `kind` - What the file is for, to check its contents suit it; None accepts any file
+/// * `owner` - The record the file belongs to, or None if it does not exist yet
 ///
 /// # Returns
-/// * `Ok(())` - If the adoption request was successfully inserted
-/// * `Err(String)` - An error message if the insertion fails
+/// * `Ok(Some(PathBuf))` - The path of the uploaded file if successful
+/// * `Ok(None)` - If the user cancels the file selection
+/// * `Err(CommandError)` - An error message if the file is not suited to its kind or the upload fails
 #[tauri::command]
-async fn create_adoption_request(
-    state: State<'_, Mutex<AppState>>,
+async fn upload_file(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    request: AdoptionRequest,
-) -> Result<(), String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the database service
-    init_database_service_once(&mut state_guard, &app_handle).await?;
+    kind: Option<UploadKind>,
+    owner: Option<StorageOwner>,
+) -> Result<Option<PathBuf>, CommandError> {
+    // Lazily initialize the file service
+    let file_service = init_file_service_once(&state, &app_handle).await?;
 
-    // Insert adoption request
-    match state_guard
-        .database_service
-        .as_ref()
-        .unwrap()
-        .insert_adoption_request(&request)
+    // Perform file upload
+    match file_service
+        .upload_file(&app_handle, kind, owner.as_ref())
+        .await
     {
-        Ok(()) => Ok(()),
-        Err(e) => Err(format!("Failed to create adoption request: {}", e)),
+        Ok(result) => Ok(result),
+        Err(e) => Err(CommandError::from_service("Failed to upload file", e)),
     }
 }
 
-/// Command to update an existing adoption request in the database
+/// Command to upload several files selected by the user in one dialog
 ///
 /// # Arguments
-/// * `request` - The updated adoption request data
+/// * `kind` - What the files are for, to check their contents suit it; None accepts any file
+/// * `owner` - The record the files belong to, or None if it does not exist yet
 ///
 /// # Returns
-/// * `Ok(bool)` - True if request was found and updated, false if not found
-/// * `Err(String)` - An error message if the update fails
+/// * `Ok(Vec<PathBuf>)` - The paths of the uploaded files, empty if the user cancels the selection
+/// * `Err(CommandError)` - An error message if any file is not suited to its kind or the upload fails
 #[tauri::command]
-async fn update_adoption_request(
-    state: State<'_, Mutex<AppState>>,
+async fn upload_files(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    request: AdoptionRequest,
-) -> Result<bool, String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the database service
-    init_database_service_once(&mut state_guard, &app_handle).await?;
+    kind: Option<UploadKind>,
+    owner: Option<StorageOwner>,
+) -> Result<Vec<PathBuf>, CommandError> {
+    // Lazily initialize the file service
+    let file_service = init_file_service_once(&state, &app_handle).await?;
 
-    // Update adoption request
-    match state_guard
-        .database_service
-        .as_ref()
-        .unwrap()
-        .update_adoption_request(&request)
+    // Perform file uploads
+    match file_service
+        .upload_files(&app_handle, kind, owner.as_ref())
+        .await
     {
-        Ok(updated) => Ok(updated),
-        Err(e) => Err(format!("Failed to update adoption request: {}", e)),
+        Ok(result) => Ok(result),
+        Err(e) => Err(CommandError::from_service("Failed to upload files", e)),
     }
 }
 
-/// Command to delete an adoption request from the database
+/// Command to upload a file whose contents were dropped or pasted into the application
 ///
 /// # Arguments
-/// * `request_id` - The ID of the adoption request to delete
+/// * `filename_hint` - Original name of the file, used only for its extension
+/// * `contents` - The bytes of the file
+/// * `kind` - What the file is for, to check its contents suit it; None accepts any file
+/// * `owner` - The record the file belongs to, or None if it does not exist yet
 ///
 /// # Returns
-/// * `Ok(bool)` - True if request was found and deleted, false if not found
-/// * `Err(String)` - An error message if the deletion fails
+/// * `Ok(PathBuf)` - The path of the uploaded file
+/// * `Err(CommandError)` - An error message if the file is not suited to its kind or the upload fails
 #[tauri::command]
-async fn delete_adoption_request(
-    state: State<'_, Mutex<AppState>>,
+async fn upload_bytes(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    request_id: String,
-) -> Result<bool, String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the database service
-    init_database_service_once(&mut state_guard, &app_handle).await?;
+    filename_hint: String,
+    contents: Vec<u8>,
+    kind: Option<UploadKind>,
+    owner: Option<StorageOwner>,
+) -> Result<PathBuf, CommandError> {
+    // Lazily initialize the file service
+    let file_service = init_file_service_once(&state, &app_handle).await?;
 
-    // Delete adoption request
-    match state_guard
-        .database_service
-        .as_ref()
-        .unwrap()
-        .delete_adoption_request(&request_id)
+    match file_service
+        .save_bytes(&filename_hint, &contents, kind, owner.as_ref())
+        .await
     {
-        Ok(deleted) => Ok(deleted),
-        Err(e) => Err(format!(
-            "Failed to delete adoption request with ID {}: {}",
-            request_id, e
-        )),
+        Ok(path) => Ok(path),
+        Err(e) => Err(CommandError::from_service("Failed to upload file", e)),
     }
 }
 
-/// Command to retrieve all adoption requests from the database for a specific animal ID
+/// Command to delete a file from the specified path
 ///
 /// # Arguments
-/// * `animal_id` - The ID of the animal to retrieve requests for
-///
-/// # Returns
-/// * `Ok(Vec<AdoptionRequest>)` - List of adoption requests if successful
-/// * `Err(String)` - An error message if the query fails
+/// * `file_path` - The path of the file to be deleted
 #[tauri::command]
-async fn get_adoption_requests_by_animal_id(
-    state: State<'_, Mutex<AppState>>,
+async fn delete_file(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    animal_id: String,
-) -> Result<Vec<AdoptionRequest>, String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the database service
-    init_database_service_once(&mut state_guard, &app_handle).await?;
+    file_path: String,
+) -> Result<(), CommandError> {
+    // Lazily initialize the file service
+    let file_service = init_file_service_once(&state, &app_handle).await?;
 
-    // Query adoption requests by animal ID
-    match state_guard
-        .database_service
-        .as_ref()
-        .unwrap()
-        .query_adoption_requests_by_animal_id(&animal_id)
-    {
-        Ok(requests) => Ok(requests),
-        Err(e) => Err(format!(
-            "Failed to retrieve adoption requests for animal ID {}: {}",
-            animal_id, e
-        )),
+    // Perform file deletion
+    match file_service.delete_file(file_path).await {
+        Ok(()) => Ok(()),
+        Err(e) => Err(CommandError::from_service("Failed to delete file", e)),
     }
 }
 
-/// Command to retrieve all adoption requests from the database for a specific user name
+/// Command to read an uploaded file, such as an animal photo, for display
 ///
 /// # Arguments
-/// * `username` - The user name to retrieve requests for
+/// * `file_path` - The path of the file, which must be an upload
 ///
 /// # Returns
-/// * `Ok(Vec<AdoptionRequest>)` - List of adoption requests if successful
-/// * `Err(String)` - An error message if the query fails
+/// * `Ok(String)` - The contents of the file as a base64 data URL
+/// * `Err(CommandError)` - An error message if the user is not logged in, or the file is not
+///   an upload or cannot be read
 #[tauri::command]
-async fn get_adoption_requests_by_username(
-    state: State<'_, Mutex<AppState>>,
+async fn read_file(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    username: String,
-) -> Result<Vec<AdoptionRequest>, String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
+    file_path: String,
+) -> Result<String, CommandError> {
+    // Only logged-in users may read uploads
+    require_logged_in_user(&state, &app_handle).await?;
 
-    // Lazily initialize the database service
-    init_database_service_once(&mut state_guard, &app_handle).await?;
+    // Lazily initialize the file service
+    let file_service = init_file_service_once(&state, &app_handle).await?;
 
-    // Query adoption requests by user name
-    match state_guard
-        .database_service
-        .as_ref()
-        .unwrap()
-        .query_adoption_requests_by_username(&username)
-    {
-        Ok(requests) => Ok(requests),
-        Err(e) => Err(format!(
-            "Failed to retrieve adoption requests for user name {}: {}",
-            username, e
-        )),
+    match file_service.read_file_data_url(file_path).await {
+        Ok(data_url) => Ok(data_url),
+        Err(e) => Err(CommandError::from_service("Failed to read file", e)),
     }
 }
 
-// ==================== AUTHENTICATION COMMANDS ====================
-
-/// Command to register a new user account
+/// Command to rotate an uploaded image, such as a photo taken sideways
 ///
 /// # Arguments
-/// * `username` - Username for the new account
-/// * `password` - Password for the new account
-/// * `role` - Role to assign to the user (Staff or Customer)
+/// * `file_path` - The path of the uploaded image
+/// * `degrees` - Angle to rotate clockwise by, a multiple of 90; negative angles rotate counterclockwise
 ///
 /// # Returns
-/// * `Ok(())` - If the user was successfully registered and logged in
-/// * `Err(String)` - An error message if registration fails
+/// * `Ok(PathBuf)` - The path of the rotated image, written next to the original
+/// * `Err(CommandError)` - An error message if the user is not staff, the angle or file is unsuitable or the rotation fails
 #[tauri::command]
-async fn sign_up(
-    state: State<'_, Mutex<AppState>>,
+async fn rotate_image(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    username: String,
-    password: String,
-    role: UserRole,
-) -> Result<(), String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the authentication service
-    init_authentication_service_once(&mut state_guard, &app_handle).await?;
-
-    // Get reference to authentication service
-    let auth_service = state_guard.authentication_service.as_mut().unwrap();
+    file_path: String,
+    degrees: i32,
+) -> Result<PathBuf, CommandError> {
+    require_staff(&state, &app_handle).await?;
 
-    // Register user with new account
-    let result = auth_service.sign_up(&username, &password, role);
+    // Lazily initialize the file service
+    let file_service = init_file_service_once(&state, &app_handle).await?;
 
-    match result {
-        Ok(()) => Ok(()),
-        Err(e) => Err(format!("Failed to register user: {}", e)),
+    match file_service.rotate_image(file_path, degrees).await {
+        Ok(path) => Ok(path),
+        Err(e) => Err(CommandError::from_service("Failed to rotate image", e)),
     }
 }
 
-/// Command to log in an existing user
+/// Command to crop an uploaded image to a rectangle
 ///
 /// # Arguments
-/// * `username` - Username to log in
-/// * `password` - Password for authentication
+/// * `file_path` - The path of the uploaded image
+/// * `x` - Left edge of the rectangle, in pixels
+/// * `y` - Top edge of the rectangle, in pixels
+/// * `width` - Width of the rectangle, in pixels
+/// * `height` - Height of the rectangle, in pixels
 ///
 /// # Returns
-/// * `Ok(LoginResult)` - Login result indicating success, invalid password, or user not found
-/// * `Err(String)` - An error message if login process fails
+/// * `Ok(PathBuf)` - The path of the cropped image, written next to the original
+/// * `Err(CommandError)` - An error message if the user is not staff, the rectangle or file is unsuitable or the crop fails
 #[tauri::command]
-async fn log_in(
-    state: State<'_, Mutex<AppState>>,
+async fn crop_image(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    username: String,
-    password: String,
-) -> Result<LoginResult, String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the authentication service
-    init_authentication_service_once(&mut state_guard, &app_handle).await?;
-
-    // Get reference to authentication service
-    let auth_service = state_guard.authentication_service.as_mut().unwrap();
+    file_path: String,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<PathBuf, CommandError> {
+    require_staff(&state, &app_handle).await?;
 
-    // Authenticate user credentials
-    let result = auth_service.log_in(&username, &password);
+    // Lazily initialize the file service
+    let file_service = init_file_service_once(&state, &app_handle).await?;
 
-    match result {
-        Ok(login_result) => Ok(login_result),
-        Err(e) => Err(format!("Failed to log in: {}", e)),
+    match file_service
+        .crop_image(file_path, x, y, width, height)
+        .await
+    {
+        Ok(path) => Ok(path),
+        Err(e) => Err(CommandError::from_service("Failed to crop image", e)),
     }
 }
 
-/// Command to get current logged-in user information
+/// Command to report how much disk space the application's files take up
 ///
 /// # Returns
-/// * `Ok(Some(CurrentUser))` - Current user info if logged in
-/// * `Ok(None)` - If no user is currently logged in
-/// * `Err(String)` - An error message if retrieval fails
+/// * `Ok(StorageStats)` - The disk space used, by category
+/// * `Err(CommandError)` - An error message if the user is not staff or the files cannot be read
 #[tauri::command]
-async fn get_current_user(
-    state: State<'_, Mutex<AppState>>,
+async fn get_storage_stats(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-) -> Result<Option<CurrentUser>, String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
+) -> Result<StorageStats, CommandError> {
+    require_staff(&state, &app_handle).await?;
 
-    // Lazily initialize the authentication service
-    init_authentication_service_once(&mut state_guard, &app_handle).await?;
-
-    // Get reference to authentication service
-    let auth_service = state_guard.authentication_service.as_ref().unwrap();
-
-    // Get current user
-    let result = auth_service.get_current_user();
+    // Lazily initialize the file service
+    let file_service = init_file_service_once(&state, &app_handle).await?;
 
-    match result {
-        Ok(user) => Ok(user),
-        Err(e) => Err(format!("Failed to get current user: {}", e)),
+    match file_service.storage_stats().await {
+        Ok(stats) => Ok(stats),
+        Err(e) => Err(CommandError::from_service(
+            "Failed to get storage statistics",
+            e,
+        )),
     }
 }
 
-/// Command to log out the current user
+/// Command to check every stored file against the size and hash recorded when it was
+/// written, to find files damaged or removed outside the application
 ///
 /// # Returns
-/// * `Ok(())` - Always succeeds
+/// * `Ok(FileVerificationReport)` - The files that are missing, damaged or have no recorded checksum
+/// * `Err(CommandError)` - An error message if the user is not staff or the files cannot be read
 #[tauri::command]
-async fn log_out(state: State<'_, Mutex<AppState>>, app_handle: AppHandle) -> Result<(), String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the authentication service
-    init_authentication_service_once(&mut state_guard, &app_handle).await?;
+async fn verify_files(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<FileVerificationReport, CommandError> {
+    require_staff(&state, &app_handle).await?;
 
-    // Log out user
-    state_guard
-        .authentication_service
-        .as_mut()
-        .unwrap()
-        .log_out();
+    // Lazily initialize the file service
+    let file_service = init_file_service_once(&state, &app_handle).await?;
 
-    Ok(())
+    match file_service.verify_files().await {
+        Ok(report) => Ok(report),
+        Err(e) => Err(CommandError::from_service("Failed to verify files", e)),
+    }
 }
 
-// ==================== FILE SERVICE COMMANDS ====================
-
-/// Command to upload a file selected by the user
+/// Command to bond two animals so they are adopted together
+///
+/// # Arguments
+/// * `animal_id` - The ID of one animal
+/// * `other_animal_id` - The ID of the other animal
 ///
 /// # Returns
-/// * `Ok(Some(PathBuf))` - The path of the uploaded file if successful
-/// * `Ok(None)` - If the user cancels the file selection
-/// * `Err(String)` - An error message if the upload fails
+/// * `Ok(bool)` - True if the bond was created, false if it already existed
+/// * `Err(CommandError)` - An error message if the user is not staff or the bond could not be created
 #[tauri::command]
-async fn upload_file(
-    state: State<'_, Mutex<AppState>>,
+async fn link_bonded_animals(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-) -> Result<Option<PathBuf>, String> {
-    // Lock the state for safe concurrent access
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the file service
-    init_file_service_once(&mut state_guard, &app_handle).await?;
+    animal_id: String,
+    other_animal_id: String,
+) -> Result<bool, CommandError> {
+    require_staff(&state, &app_handle).await?;
 
-    // Perform file upload
-    match state_guard
-        .file_service
-        .as_ref()
-        .unwrap()
-        .upload_file(&app_handle)
-        .await
+    // Link animals
+    let (id, other_id) = (animal_id.clone(), other_animal_id.clone());
+    match run_database_task(&state, &app_handle, move |db| {
+        db.link_bonded_animals(&id, &other_id)
+    })
+    .await?
     {
-        Ok(result) => Ok(result),
-        Err(e) => Err(format!("Failed to upload file: {}", e)),
+        Ok(linked) => Ok(linked),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to bond animals with IDs {} and {}",
+                animal_id, other_animal_id
+            ),
+            e,
+        )),
     }
 }
 
-/// Command to delete a file from the specified path
+/// Command to remove the bond between two animals
 ///
 /// # Arguments
-/// * `file_path` - The path of the file to be deleted
+/// * `animal_id` - The ID of one animal
+/// * `other_animal_id` - The ID of the other animal
+///
+/// # Returns
+/// * `Ok(bool)` - True if the bond was found and removed, false if not found
+/// * `Err(CommandError)` - An error message if the user is not staff or the bond could not be removed
 #[tauri::command]
-async fn delete_file(
-    state: State<'_, Mutex<AppState>>,
+async fn unlink_bonded_animals(
+    state: State<'_, AppState>,
     app_handle: AppHandle,
-    file_path: String,
-) -> Result<(), String> {
-    // Lock the state for safe concurrent accesss
-    let mut state_guard = state.lock().await;
-
-    // Lazily initialize the file service
-    init_file_service_once(&mut state_guard, &app_handle).await?;
+    animal_id: String,
+    other_animal_id: String,
+) -> Result<bool, CommandError> {
+    require_staff(&state, &app_handle).await?;
 
-    // Perform file deletion
-    match state_guard
-        .file_service
-        .as_ref()
-        .unwrap()
-        .delete_file(file_path)
-        .await
+    // Unlink animals
+    let (id, other_id) = (animal_id.clone(), other_animal_id.clone());
+    match run_database_task(&state, &app_handle, move |db| {
+        db.unlink_bonded_animals(&id, &other_id)
+    })
+    .await?
     {
-        Ok(()) => Ok(()),
-        Err(e) => Err(format!("Failed to delete file: {}", e)),
+        Ok(unlinked) => Ok(unlinked),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to remove bond between animals with IDs {} and {}",
+                animal_id, other_animal_id
+            ),
+            e,
+        )),
+    }
+}
+
+/// Command to retrieve the animals bonded to a specific animal
+///
+/// # Arguments
+/// * `animal_id` - The ID of the animal to retrieve bonded animals for
+///
+/// # Returns
+/// * `Ok(Vec<AnimalSummary>)` - List of bonded animals if successful
+/// * `Err(CommandError)` - An error message if the query fails
+#[tauri::command]
+async fn get_bonded_animals(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    animal_id: String,
+) -> Result<Vec<AnimalSummary>, CommandError> {
+    // Query bonded animals
+    let id = animal_id.clone();
+    match run_database_task(&state, &app_handle, move |db| db.query_bonded_animals(&id)).await? {
+        Ok(animals) => Ok(animals),
+        Err(e) => Err(CommandError::from_service(
+            format!(
+                "Failed to retrieve bonded animals for animal ID {}",
+                animal_id
+            ),
+            e,
+        )),
     }
 }
 
+/// Runs a maintenance task from the command line, without starting the GUI
+///
+/// # Arguments
+/// * `args` - The arguments given after `CLI_FLAG`
+///
+/// # Returns
+/// * `i32` - The exit code of the task
+pub fn run_cli(args: &[String]) -> i32 {
+    cli::run(args)
+}
+
 /// Runs the Tauri application
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_fs::init())
-        .plugin(tauri_plugin_log::Builder::new().build())
+        .plugin(
+            tauri_plugin_log::Builder::new()
+                // Let everything through; the level chosen in the settings is applied
+                // once they are read
+                .level(log::LevelFilter::Trace)
+                .targets([
+                    Target::new(TargetKind::Stdout),
+                    Target::new(TargetKind::LogDir {
+                        file_name: Some(log_service::LOG_FILE_NAME.to_string()),
+                    }),
+                ])
+                .max_file_size(log_service::MAX_LOG_FILE_BYTES)
+                .rotation_strategy(RotationStrategy::KeepSome(log_service::MAX_ARCHIVED_LOGS))
+                .build(),
+        )
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
-        .manage(Mutex::new(AppState::default()))
+        .manage(AppState::default())
+        .setup(|app| {
+            start_http_api(app.handle().clone());
+            start_webhook_dispatcher(app.handle().clone());
+            start_email_dispatcher(app.handle().clone());
+            start_reminder_scheduler(app.handle().clone());
+            start_storage_migration(app.handle().clone());
+            start_trash_purge(app.handle().clone());
+            start_maintenance_schedule(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Authentication commands
             sign_up,
             log_in,
+            verify_totp,
+            get_two_factor_status,
+            enroll_totp,
+            confirm_totp_enrollment,
+            disable_totp,
+            create_password_reset,
+            complete_password_reset,
+            change_password,
             get_current_user,
+            refresh_session,
             log_out,
+            get_users,
+            create_invite_code,
+            create_user,
+            change_user_role,
+            suspend_user,
+            reactivate_user,
+            reset_user_password,
+            get_session_policy,
+            set_session_policy,
+            get_sessions,
+            terminate_session,
             // Animal commands
             get_animals,
+            get_animal_count,
+            get_animal_detail,
+            get_distinct_species,
+            get_distinct_breeds,
+            get_distinct_colors,
             get_animal_by_id,
+            find_animal_by_microchip,
+            validate_animal,
             create_animal,
+            create_animals_batch,
             update_animal,
+            replace_animal_image,
             delete_animal,
             // Adoption request commands
             get_adoption_request_by_id,
             get_adoption_requests_by_animal_id,
             get_adoption_requests_by_username,
+            validate_adoption_request,
             create_adoption_request,
             update_adoption_request,
             delete_adoption_request,
+            approve_adoption_request,
+            recompute_screening_scores,
+            get_adoption_requests,
+            get_adoption_requests_with_animals,
+            get_pending_request_count,
+            get_distinct_countries,
+            process_return,
+            finalize_trial_adoption,
+            get_ongoing_trial_adoptions,
+            get_adoption_returns_by_animal_id,
+            // Request check commands
+            get_request_checks,
+            record_request_interview,
+            record_reference_check,
+            update_reference_check_outcome,
+            // Animal note commands
+            get_animal_notes,
+            add_animal_note,
+            delete_animal_note,
+            // Vaccination commands
+            get_vaccinations,
+            add_vaccination,
+            delete_vaccination,
+            // Foster placement commands
+            get_foster_placements_by_animal_id,
+            start_foster_placement,
+            extend_foster_placement,
+            end_foster_placement,
+            // Intake commands
+            record_intake,
+            get_intake_history,
+            get_stray_holds_ending_this_week,
+            // Kennel commands
+            create_kennel,
+            get_kennel_occupancy,
+            get_capacity_report,
+            assign_animal_to_kennel,
+            remove_animal_from_kennel,
+            // Location commands
+            create_location,
+            get_locations,
+            update_location,
+            delete_location,
+            // Transfer commands
+            record_transfer,
+            get_transfer_history,
+            // Deceased record commands
+            record_death,
+            // Inventory commands
+            create_inventory_item,
+            get_inventory_items,
+            update_inventory_item,
+            delete_inventory_item,
+            adjust_stock,
+            get_inventory_adjustments,
+            get_low_stock_items,
+            // Expense commands
+            create_expense,
+            get_expenses,
+            update_expense,
+            delete_expense,
+            get_budget_report,
+            // Payment commands
+            get_payments,
+            record_adoption_fee,
+            mark_payment_paid,
+            // Donation commands
+            create_donation,
+            get_donations,
+            // Contact commands
+            create_contact,
+            get_contacts,
+            update_contact,
+            delete_contact,
+            link_vaccination_to_contact,
+            link_transfer_to_contact,
+            // Appointment commands
+            request_appointment,
+            get_my_appointments,
+            get_appointments,
+            confirm_appointment,
+            cancel_appointment,
+            // Surgery commands
+            schedule_surgery,
+            get_surgeries,
+            get_upcoming_surgeries,
+            complete_surgery,
+            cancel_surgery,
+            // Integrity commands
+            check_integrity,
+            // Maintenance commands
+            run_database_maintenance,
+            // Demo data commands
+            seed_demo_data,
+            // Sync commands
+            export_changes,
+            import_changes,
+            // Webhook commands
+            create_webhook,
+            get_webhooks,
+            delete_webhook,
+            get_webhook_deliveries,
+            // Email commands
+            get_emails,
+            resend_email,
+            // Trash commands
+            get_trash,
+            restore_from_trash,
+            purge_trash,
+            // Waitlist commands
+            add_favorite,
+            remove_favorite,
+            get_favorites,
+            join_waitlist,
+            leave_waitlist,
+            get_waitlist,
+            // Profile commands
+            get_profile,
+            update_profile,
+            // Privacy commands
+            export_my_data,
+            request_erasure,
+            get_erasure_requests,
+            approve_erasure_request,
+            reject_erasure_request,
+            // Banned adopter commands
+            get_banned_adopters,
+            add_banned_adopter,
+            remove_banned_adopter,
+            get_banned_adopter_matches,
+            // Notification commands
+            get_notifications,
+            mark_notification_read,
+            check_reminders,
+            get_long_stay_animals,
+            link_bonded_animals,
+            unlink_bonded_animals,
+            get_bonded_animals,
+            // Statistics commands
+            get_shelter_statistics,
+            generate_monthly_report,
+            get_adoption_trends,
+            export_report_pdf,
+            export_report_xlsx,
+            export_animals_xlsx,
+            export_adoption_requests_xlsx,
+            // Search commands
+            search,
+            // Activity commands
+            get_activity_feed,
+            // Document commands
+            generate_kennel_card,
+            generate_vaccination_certificate,
+            generate_adoption_certificate,
+            generate_invoice,
+            generate_receipt,
+            generate_donation_receipt,
+            generate_annual_donor_statement,
+            export_calendar,
+            export_public_listings,
+            // Job commands
+            enqueue_job,
+            get_job,
+            get_jobs,
             // File commands
             upload_file,
-            delete_file
+            upload_files,
+            upload_bytes,
+            delete_file,
+            read_file,
+            rotate_image,
+            crop_image,
+            get_storage_stats,
+            verify_files,
+            // Settings commands
+            get_setting,
+            get_settings,
+            set_setting,
+            get_messages,
+            // Health commands
+            get_health,
+            // Log commands
+            get_log_level,
+            set_log_level,
+            get_log_tail,
+            rotate_logs,
+            export_logs
         ])
         .run(tauri::generate_context!())
         .expect("Error while running tauri application");
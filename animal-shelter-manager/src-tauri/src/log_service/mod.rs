@@ -0,0 +1,184 @@
+//
+// log_service/mod.rs
+//
+// This module manages the application's log files so users can attach them to
+// bug reports. The log plugin writes to a single file in the app log
+// directory and archives it once it grows too large; this service reads the
+// end of that file, archives it on demand, and bundles every log file into a
+// single document.
+//
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+mod test;
+pub mod types;
+
+/// Name of the log file, without its extension, as given to the log plugin
+pub const LOG_FILE_NAME: &str = "animal-shelter-manager";
+
+/// Size at which the log plugin archives the log file
+pub const MAX_LOG_FILE_BYTES: u128 = 1024 * 1024;
+
+/// How many archived log files are kept
+pub const MAX_ARCHIVED_LOGS: usize = 5;
+
+/// Most lines that can be read from the end of the log
+pub const MAX_TAIL_LINES: usize = 1000;
+
+/// Service for reading and archiving log files
+pub struct LogService {
+    /// Directory the log files are written to
+    log_dir: PathBuf,
+}
+
+impl LogService {
+    /// Creates a new LogService instance for a log directory
+    ///
+    /// # Arguments
+    /// * `log_dir` - Directory the log plugin writes to
+    ///
+    /// # Returns
+    /// * `LogService` - New log service instance
+    pub fn new<P: AsRef<Path>>(log_dir: P) -> Self {
+        LogService {
+            log_dir: log_dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Gets the path of the log file currently written to
+    pub fn current_log_path(&self) -> PathBuf {
+        self.log_dir.join(format!("{}.log", LOG_FILE_NAME))
+    }
+
+    /// Reads the last lines of the current log file
+    ///
+    /// # Arguments
+    /// * `line_count` - How many lines to read, at most `MAX_TAIL_LINES`
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - The lines, oldest first; empty if nothing was logged yet
+    pub async fn tail(&self, line_count: usize) -> Result<Vec<String>> {
+        let path = self.current_log_path();
+        if !fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(Vec::new());
+        }
+        let contents = fs::read(&path)
+            .await
+            .context(format!("Failed to read log file: {:?}", path))?;
+        let contents = String::from_utf8_lossy(&contents);
+
+        let lines: Vec<&str> = contents.lines().collect();
+        let start = lines.len().saturating_sub(line_count.min(MAX_TAIL_LINES));
+        Ok(lines[start..].iter().map(|line| line.to_string()).collect())
+    }
+
+    /// Archives the current log file and starts it afresh, dropping the oldest archives
+    /// beyond the number kept
+    ///
+    /// The file is copied then emptied rather than renamed, since the log plugin keeps
+    /// it open; anything logged between the two steps is lost.
+    ///
+    /// # Arguments
+    /// * `now` - When the log is archived, used to name the archive
+    ///
+    /// # Returns
+    /// * `Result<Option<PathBuf>>` - The archive, or None if the log file was empty
+    pub async fn rotate(&self, now: DateTime<Utc>) -> Result<Option<PathBuf>> {
+        let path = self.current_log_path();
+        let size = match fs::metadata(&path).await {
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        };
+        if size == 0 {
+            return Ok(None);
+        }
+
+        // Name archives the way the log plugin does, so it prunes them alike
+        let archive_path = self.log_dir.join(format!(
+            "{}_{}.log",
+            LOG_FILE_NAME,
+            now.format("%Y-%m-%d_%H-%M-%S")
+        ));
+        fs::copy(&path, &archive_path)
+            .await
+            .context(format!("Failed to archive log file: {:?}", path))?;
+        fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .await
+            .context(format!("Failed to open log file: {:?}", path))?
+            .set_len(0)
+            .await
+            .context(format!("Failed to empty log file: {:?}", path))?;
+
+        let archives = self.archived_log_paths().await?;
+        let excess = archives.len().saturating_sub(MAX_ARCHIVED_LOGS);
+        for old_archive in &archives[..excess] {
+            fs::remove_file(old_archive)
+                .await
+                .context(format!("Failed to delete archived log: {:?}", old_archive))?;
+        }
+
+        log::info!("Log file archived to {:?}", archive_path);
+        Ok(Some(archive_path))
+    }
+
+    /// Bundles every log file into a single text document, oldest first
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>>` - The archived logs followed by the current one, each headed
+    ///   with its file name
+    pub async fn export(&self) -> Result<Vec<u8>> {
+        let mut paths = self.archived_log_paths().await?;
+        let current = self.current_log_path();
+        if fs::try_exists(&current).await.unwrap_or(false) {
+            paths.push(current);
+        }
+
+        let mut bundle = Vec::new();
+        for path in paths {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            bundle.extend_from_slice(format!("===== {} =====\n", file_name).as_bytes());
+            let contents = fs::read(&path)
+                .await
+                .context(format!("Failed to read log file: {:?}", path))?;
+            bundle.extend_from_slice(&contents);
+            if !contents.ends_with(b"\n") {
+                bundle.push(b'\n');
+            }
+        }
+        Ok(bundle)
+    }
+
+    /// Lists the archived log files, oldest first
+    ///
+    /// # Returns
+    /// * `Result<Vec<PathBuf>>` - The archives; their timestamped names sort by age
+    async fn archived_log_paths(&self) -> Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        let mut entries = match fs::read_dir(&self.log_dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(paths),
+            Err(e) => {
+                return Err(e).context(format!("Failed to list log directory: {:?}", self.log_dir))
+            }
+        };
+
+        let prefix = format!("{}_", LOG_FILE_NAME);
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .context("Failed to list log directory")?
+        {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.starts_with(&prefix) && file_name.ends_with(".log") {
+                paths.push(entry.path());
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+}
@@ -0,0 +1,97 @@
+//
+// log_service/test.rs
+//
+// This file contains unit tests for the log service module.
+//
+
+#[cfg(test)]
+mod log_service_tests {
+    use crate::log_service::{types::LogLevel, LogService, LOG_FILE_NAME, MAX_ARCHIVED_LOGS};
+    use chrono::{TimeZone, Utc};
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// Helper function to create a test log service with a dedicated log directory
+    ///
+    /// # Arguments
+    /// * `test_name` - Name of the test for unique directory path
+    ///
+    /// # Returns
+    /// * `(LogService, PathBuf)` - The log service and its log directory
+    fn create_test_log_service(test_name: &str) -> (LogService, PathBuf) {
+        let mut log_dir = PathBuf::from("test_artifacts/log_service");
+        log_dir.push(test_name);
+
+        if log_dir.exists() {
+            fs::remove_dir_all(&log_dir).expect("Failed to remove existing test directory");
+        }
+        fs::create_dir_all(&log_dir).expect("Failed to create test artifacts directory");
+
+        (LogService::new(&log_dir), log_dir)
+    }
+
+    #[tokio::test]
+    async fn test_tail() {
+        let (service, _) = create_test_log_service("test_tail");
+        assert!(service.tail(10).await.unwrap().is_empty());
+
+        let lines: Vec<String> = (1..=20).map(|i| format!("line {}", i)).collect();
+        fs::write(service.current_log_path(), lines.join("\n")).unwrap();
+
+        let tail = service.tail(3).await.unwrap();
+        assert_eq!(tail, vec!["line 18", "line 19", "line 20"]);
+        assert_eq!(service.tail(100).await.unwrap().len(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_and_export() {
+        let (service, log_dir) = create_test_log_service("test_rotate_and_export");
+
+        // Nothing to archive until something is logged
+        let now = Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap();
+        assert!(service.rotate(now).await.unwrap().is_none());
+
+        for day in 1..=(MAX_ARCHIVED_LOGS as u32 + 2) {
+            fs::write(service.current_log_path(), format!("day {}\n", day)).unwrap();
+            let now = Utc.with_ymd_and_hms(2024, 3, day, 9, 0, 0).unwrap();
+            let archive = service.rotate(now).await.unwrap().unwrap();
+            assert_eq!(
+                archive.file_name().unwrap().to_string_lossy(),
+                format!("{}_2024-03-{:02}_09-00-00.log", LOG_FILE_NAME, day)
+            );
+            assert_eq!(fs::read(service.current_log_path()).unwrap().len(), 0);
+        }
+
+        // Only the most recent archives are kept
+        let archive_count = fs::read_dir(&log_dir)
+            .unwrap()
+            .filter(|entry| {
+                let name = entry.as_ref().unwrap().file_name();
+                name.to_string_lossy()
+                    .starts_with(&format!("{}_", LOG_FILE_NAME))
+            })
+            .count();
+        assert_eq!(archive_count, MAX_ARCHIVED_LOGS);
+
+        // The export holds the kept archives, oldest first, then the current log
+        fs::write(service.current_log_path(), "today").unwrap();
+        let export = String::from_utf8(service.export().await.unwrap()).unwrap();
+        assert!(!export.contains("day 2\n"));
+        let day3 = export.find("day 3\n").unwrap();
+        let day7 = export.find("day 7\n").unwrap();
+        let today = export.find("today\n").unwrap();
+        assert!(day3 < day7 && day7 < today);
+        assert!(export.contains(&format!("===== {}.log =====", LOG_FILE_NAME)));
+    }
+
+    #[test]
+    fn test_log_level_filters() {
+        assert_eq!(LogLevel::Info.filter(), log::LevelFilter::Info);
+        assert_eq!(
+            LogLevel::from_filter(log::LevelFilter::Debug),
+            Some(LogLevel::Debug)
+        );
+        assert_eq!(LogLevel::from_filter(log::LevelFilter::Off), None);
+        assert_eq!("warn".parse::<LogLevel>().unwrap(), LogLevel::Warn);
+    }
+}
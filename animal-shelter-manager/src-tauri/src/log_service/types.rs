@@ -0,0 +1,59 @@
+//
+// log_service/types.rs
+//
+// This module contains the type definitions for log management.
+//
+
+use log::LevelFilter;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString};
+
+/// Least severe kind of message written to the log
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString, EnumIter,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum LogLevel {
+    /// Only errors
+    Error,
+    /// Errors and warnings
+    Warn,
+    /// Errors, warnings and what the application is doing
+    Info,
+    /// Everything above, and details useful when tracking down a problem
+    Debug,
+    /// Everything, including the messages of the libraries the application uses
+    Trace,
+}
+
+impl LogLevel {
+    /// Gets the filter the `log` crate applies for this level
+    pub fn filter(self) -> LevelFilter {
+        match self {
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
+
+    /// Gets the level matching a filter of the `log` crate
+    ///
+    /// # Arguments
+    /// * `filter` - The filter, such as the one currently applied
+    ///
+    /// # Returns
+    /// * `Option<LogLevel>` - The level, or None if logging is off
+    pub fn from_filter(filter: LevelFilter) -> Option<Self> {
+        match filter {
+            LevelFilter::Off => None,
+            LevelFilter::Error => Some(LogLevel::Error),
+            LevelFilter::Warn => Some(LogLevel::Warn),
+            LevelFilter::Info => Some(LogLevel::Info),
+            LevelFilter::Debug => Some(LogLevel::Debug),
+            LevelFilter::Trace => Some(LogLevel::Trace),
+        }
+    }
+}
@@ -8,6 +8,12 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 fn main() {
+    // Run a maintenance task instead of the GUI when asked to on the command line
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some(animal_shelter_manager_lib::CLI_FLAG) {
+        std::process::exit(animal_shelter_manager_lib::run_cli(&args[1..]));
+    }
+
     // Start the Tauri application
     animal_shelter_manager_lib::run()
 }
@@ -0,0 +1,116 @@
+//
+// reminder_service/mod.rs
+//
+// This module runs the reminder scheduler: every so often it checks the
+// reminder rules set in the settings against the database, and notifies every
+// active staff member about vaccinations that are due, animals that have
+// stayed a long time, and requests left pending, and customers about their
+// upcoming meet-and-greet appointments.
+//
+
+use crate::authentication_service::{types::UserStatus, AuthenticationService};
+use crate::database_service::{
+    types::{Notification, ReminderThresholds},
+    DatabaseService,
+};
+use crate::settings_service::SettingsService;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod test;
+
+/// How often the reminder rules are checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Callback told about the notifications created by each check
+pub type RemindersCreatedListener = Arc<dyn Fn(&[Notification]) + Send + Sync>;
+
+/// Services the reminder scheduler reads from
+#[derive(Clone)]
+pub struct ReminderServices {
+    /// Service for handling database operations
+    pub database_service: Arc<Mutex<DatabaseService>>,
+    /// Service listing the staff members to remind
+    pub authentication_service: Arc<Mutex<AuthenticationService>>,
+    /// Service holding the reminder settings
+    pub settings_service: Arc<Mutex<SettingsService>>,
+}
+
+/// Checks the reminder rules until the application exits, starting right away
+///
+/// # Arguments
+/// * `services` - The services the scheduler reads from
+/// * `on_created` - Called with the notifications created by each check
+pub async fn run(services: ReminderServices, on_created: RemindersCreatedListener) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+        match create_due_reminders(&services).await {
+            Ok(notifications) => on_created(&notifications),
+            Err(e) => log::error!("Failed to create reminders: {:#}", e),
+        }
+    }
+}
+
+/// Checks the reminder rules once, notifying staff about each reminder that has come due
+///
+/// # Arguments
+/// * `services` - The services the scheduler reads from
+///
+/// # Returns
+/// * `Result<Vec<Notification>>` - The notifications created
+pub async fn create_due_reminders(services: &ReminderServices) -> Result<Vec<Notification>> {
+    let settings = run_blocking(&services.settings_service, |settings| {
+        settings.reminder_settings()
+    })
+    .await?;
+    let thresholds = ReminderThresholds {
+        vaccination_days: Some(settings.vaccination_days).filter(|&days| days > 0),
+        length_of_stay_days: Some(settings.length_of_stay_days).filter(|&days| days > 0),
+        pending_request_days: Some(settings.pending_request_days).filter(|&days| days > 0),
+        appointment_hours: Some(settings.appointment_hours).filter(|&hours| hours > 0),
+    };
+    if thresholds == ReminderThresholds::default() {
+        return Ok(Vec::new());
+    }
+
+    let staff_usernames: Vec<String> =
+        run_blocking(&services.authentication_service, |auth| auth.list_users())
+            .await?
+            .into_iter()
+            .filter(|user| user.role.is_staff() && user.status == UserStatus::Active)
+            .map(|user| user.username)
+            .collect();
+
+    run_blocking(&services.database_service, move |db| {
+        db.create_due_reminders(&thresholds, &staff_usernames, Utc::now().timestamp())
+    })
+    .await
+}
+
+/// Runs a task against a service on the blocking thread pool
+///
+/// # Arguments
+/// * `service` - The service to lock
+/// * `task` - The work to run
+///
+/// # Returns
+/// * `Result<T>` - The result of the work
+async fn run_blocking<S, T, F>(service: &Arc<Mutex<S>>, task: F) -> Result<T>
+where
+    S: Send + 'static,
+    T: Send + 'static,
+    F: FnOnce(&S) -> Result<T> + Send + 'static,
+{
+    let service = service.clone();
+    tokio::task::spawn_blocking(move || {
+        let service = service
+            .lock()
+            .map_err(|_| anyhow!("Service lock was poisoned"))?;
+        task(&service)
+    })
+    .await
+    .map_err(|e| anyhow!("Reminder task did not complete: {}", e))?
+}
@@ -0,0 +1,198 @@
+//
+// reminder_service/test.rs
+//
+// This file contains unit tests for the reminder scheduler module.
+//
+
+#[cfg(test)]
+mod reminder_service_tests {
+    use crate::authentication_service::{types::UserRole, AuthenticationService};
+    use crate::database_service::{
+        types::{AdoptionRequest, Animal, AnimalStatus, RequestStatus, Vaccination},
+        DatabaseService,
+    };
+    use crate::reminder_service::{create_due_reminders, ReminderServices};
+    use crate::settings_service::{types::SettingKey, SettingsService};
+    use chrono::Utc;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    /// Number of seconds in a day
+    const DAY: i64 = 24 * 60 * 60;
+
+    #[tokio::test]
+    async fn test_create_due_reminders() {
+        let root_path = PathBuf::from("test_artifacts/reminder_service/test_create_due_reminders");
+        if root_path.exists() {
+            fs::remove_dir_all(&root_path).expect("Failed to remove existing test directory");
+        }
+        fs::create_dir_all(&root_path).expect("Failed to create test artifacts directory");
+        let db = DatabaseService::new(root_path.join("test.db"))
+            .expect("Failed to create test db service");
+        let auth = AuthenticationService::new(root_path.join("test_auth.db"))
+            .expect("Failed to create test auth service");
+        let settings = SettingsService::new(root_path.join("settings.db"))
+            .expect("Failed to create test settings service");
+
+        auth.register_user("staffer", "password123", UserRole::Staff)
+            .unwrap();
+        auth.register_user("customer", "password123", UserRole::Customer)
+            .unwrap();
+        let staff_count = auth
+            .list_users()
+            .unwrap()
+            .iter()
+            .filter(|user| user.role.is_staff())
+            .count();
+
+        // An animal admitted 100 days ago, with a request from 10 days ago and a
+        // vaccination due in 3 days, and another that arrived today
+        let now = Utc::now().timestamp();
+        for (id, name, admitted) in [("a1", "Buddy", now - 100 * DAY), ("a2", "Max", now)] {
+            db.insert_animal(&Animal {
+                id: id.to_string(),
+                name: name.to_string(),
+                specie: "Dog".to_string(),
+                breed: "Golden Retriever".to_string(),
+                sex: "Male".to_string(),
+                birth_month: Some(6),
+                birth_year: Some(2020),
+                neutered: true,
+                admission_timestamp: admitted,
+                status: AnimalStatus::Available,
+                image_path: None,
+                appearance: "Golden coat with friendly eyes".to_string(),
+                bio: "Loves playing fetch.".to_string(),
+                version: 1,
+                microchip_number: None,
+                location_id: None,
+                good_with_kids: None,
+                good_with_dogs: None,
+                good_with_cats: None,
+                special_needs: None,
+                size: None,
+                color: None,
+            })
+            .expect("Failed to insert test animal");
+        }
+        db.insert_adoption_request(&AdoptionRequest {
+            id: "r1".to_string(),
+            username: "customer".to_string(),
+            animal_id: "a1".to_string(),
+            name: "Jira Pit".to_string(),
+            email: "jira.pit@example.org".to_string(),
+            tel_number: "0123456789".to_string(),
+            address: "Bangkok, Thailand".to_string(),
+            occupation: "Software Engineer".to_string(),
+            annual_income: "50000".to_string(),
+            num_people: 2,
+            num_children: 0,
+            request_timestamp: now - 10 * DAY,
+            adoption_timestamp: 0,
+            status: RequestStatus::Pending,
+            country: "Thailand".to_string(),
+            co_applicant: None,
+        })
+        .expect("Failed to insert test request");
+        db.insert_vaccination(&Vaccination {
+            id: "v1".to_string(),
+            animal_id: "a1".to_string(),
+            vaccine: "Rabies".to_string(),
+            administered_timestamp: now - 362 * DAY,
+            due_timestamp: Some(now + 3 * DAY),
+            recorded_by: "staffer".to_string(),
+            contact_id: None,
+        })
+        .expect("Failed to insert test vaccination");
+        // A dose due much later is not reminded of yet
+        db.insert_vaccination(&Vaccination {
+            id: "v2".to_string(),
+            animal_id: "a2".to_string(),
+            vaccine: "Distemper".to_string(),
+            administered_timestamp: now,
+            due_timestamp: Some(now + 30 * DAY),
+            recorded_by: "staffer".to_string(),
+            contact_id: None,
+        })
+        .expect("Failed to insert test vaccination");
+
+        let services = ReminderServices {
+            database_service: Arc::new(Mutex::new(db)),
+            authentication_service: Arc::new(Mutex::new(auth)),
+            settings_service: Arc::new(Mutex::new(settings)),
+        };
+
+        // Every staff member is told about each of the three reminders, once
+        let notifications = create_due_reminders(&services).await.unwrap();
+        assert_eq!(notifications.len(), 3 * staff_count);
+        assert!(notifications
+            .iter()
+            .all(|notification| notification.username != "customer"));
+        let messages: Vec<&str> = notifications
+            .iter()
+            .filter(|notification| notification.username == "staffer")
+            .map(|notification| notification.message.as_str())
+            .collect();
+        assert!(messages
+            .iter()
+            .any(|message| message.starts_with("Buddy's Rabies vaccination is due on")));
+        assert!(messages.contains(&"Buddy has been at the shelter for 100 days"));
+        assert!(
+            messages.contains(&"Jira Pit's request to adopt Buddy has been pending for 10 days")
+        );
+        assert!(create_due_reminders(&services).await.unwrap().is_empty());
+
+        // A new dose recorded for a vaccine replaces its earlier due date, and turned
+        // off rules are not checked
+        {
+            let db = services.database_service.lock().unwrap();
+            db.insert_vaccination(&Vaccination {
+                id: "v3".to_string(),
+                animal_id: "a1".to_string(),
+                vaccine: "rabies".to_string(),
+                administered_timestamp: now,
+                due_timestamp: Some(now + DAY),
+                recorded_by: "staffer".to_string(),
+                contact_id: None,
+            })
+            .unwrap();
+        }
+        {
+            let settings = services.settings_service.lock().unwrap();
+            settings
+                .set_setting(SettingKey::VaccinationReminderDays, "0")
+                .unwrap();
+        }
+        assert!(create_due_reminders(&services).await.unwrap().is_empty());
+        {
+            let settings = services.settings_service.lock().unwrap();
+            settings
+                .set_setting(SettingKey::VaccinationReminderDays, "2")
+                .unwrap();
+        }
+        let notifications = create_due_reminders(&services).await.unwrap();
+        assert_eq!(notifications.len(), staff_count);
+        assert!(notifications[0]
+            .message
+            .starts_with("Buddy's rabies vaccination"));
+
+        // A customer is reminded of their own confirmed appointment, and staff are not
+        {
+            let db = services.database_service.lock().unwrap();
+            db.request_appointment("a2", "customer", now + 3 * 60 * 60)
+                .unwrap();
+            let appointment_id = db.query_appointments_by_username("customer").unwrap()[0]
+                .id
+                .clone();
+            db.confirm_appointment(&appointment_id).unwrap();
+        }
+        let notifications = create_due_reminders(&services).await.unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].username, "customer");
+        assert!(notifications[0]
+            .message
+            .starts_with("Reminder: your meet-and-greet with Max is on"));
+        assert!(create_due_reminders(&services).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,417 @@
+//
+// settings_service/mod.rs
+//
+// This module provides application settings that admins can change, such as
+// the shelter's name and the upload size limit, so they are not hardcoded.
+// Settings are kept as key-value pairs in their own SQLite database; settings
+// that were never changed take their default value. Secret settings, such as
+// the SMTP password, can be changed but are never shown again.
+//
+
+mod test;
+pub mod types;
+
+use crate::database_service::types::{ApprovalRequirements, ScreeningConfig};
+use crate::i18n::types::Locale;
+use crate::log_service::types::LogLevel;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::net::SocketAddr;
+use std::path::Path;
+use strum::IntoEnumIterator;
+use types::{
+    parse_income_bands, CloudBackupConfig, EmailConfig, ReminderSettings, Setting, SettingKey,
+};
+
+/// Value shown instead of a secret setting that has been set
+const SECRET_MASK: &str = "********";
+
+/// Service for reading and changing application settings
+pub struct SettingsService {
+    /// SQLite database connection for settings
+    connection: Connection,
+}
+
+impl SettingsService {
+    /// Creates a new SettingsService instance and initializes the settings database
+    ///
+    /// # Arguments
+    /// * `db_path` - Path where the settings SQLite database file should be created/opened
+    ///
+    /// # Returns
+    /// * `Result<SettingsService>` - New settings service instance or error
+    pub fn new<P: AsRef<Path>>(db_path: P) -> Result<Self> {
+        let connection = Connection::open(db_path.as_ref()).context(format!(
+            "Failed to open settings database at path: {:?}",
+            db_path.as_ref()
+        ))?;
+
+        connection
+            .execute(
+                "
+                CREATE TABLE IF NOT EXISTS settings (
+                    key TEXT PRIMARY KEY,
+                    value TEXT NOT NULL,
+                    updated_timestamp INTEGER NOT NULL
+                )",
+                [],
+            )
+            .context("Failed to create settings table")?;
+
+        log::info!(
+            "Settings service initialized successfully at path: {:?}",
+            db_path.as_ref()
+        );
+        Ok(SettingsService { connection })
+    }
+
+    /// Gets the current value of a setting, masking the value of secret settings
+    ///
+    /// # Arguments
+    /// * `key` - The setting to get
+    ///
+    /// # Returns
+    /// * `Result<Setting>` - The stored value, or the default if the setting was never changed
+    pub fn get_setting(&self, key: SettingKey) -> Result<Setting> {
+        let mut setting = self.read_setting(key)?;
+        if key.is_secret() && !setting.value.is_empty() {
+            setting.value = SECRET_MASK.to_string();
+        }
+        Ok(setting)
+    }
+
+    /// Gets the current value of a setting, including the value of secret settings
+    ///
+    /// # Arguments
+    /// * `key` - The setting to get
+    ///
+    /// # Returns
+    /// * `Result<Setting>` - The stored value, or the default if the setting was never changed
+    fn read_setting(&self, key: SettingKey) -> Result<Setting> {
+        let stored: Option<(String, i64)> = self
+            .connection
+            .query_row(
+                "SELECT value, updated_timestamp FROM settings WHERE key = ?1",
+                params![key.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .context(format!("Failed to read setting {}", key))?;
+
+        Ok(match stored {
+            Some((value, updated_timestamp)) => Setting {
+                key,
+                value,
+                is_default: false,
+                updated_timestamp: Some(updated_timestamp),
+            },
+            None => Setting {
+                key,
+                value: key.default_value().to_string(),
+                is_default: true,
+                updated_timestamp: None,
+            },
+        })
+    }
+
+    /// Gets the current value of every setting
+    ///
+    /// # Returns
+    /// * `Result<Vec<Setting>>` - The settings, in the order they are declared
+    pub fn get_settings(&self) -> Result<Vec<Setting>> {
+        SettingKey::iter()
+            .map(|key| self.get_setting(key))
+            .collect()
+    }
+
+    /// Changes a setting, after checking the value is allowed for it
+    ///
+    /// # Arguments
+    /// * `key` - The setting to change
+    /// * `value` - The new value
+    ///
+    /// # Returns
+    /// * `Result<Setting>` - The setting as stored, or error if the value is not allowed
+    pub fn set_setting(&self, key: SettingKey, value: &str) -> Result<Setting> {
+        let value = key.normalize(value)?;
+        self.connection
+            .execute(
+                "INSERT INTO settings (key, value, updated_timestamp) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_timestamp = excluded.updated_timestamp",
+                params![key.to_string(), value, Utc::now().timestamp()],
+            )
+            .context(format!("Failed to store setting {}", key))?;
+
+        if key.is_secret() {
+            log::info!("Setting {} changed", key);
+        } else {
+            log::info!("Setting {} changed to {:?}", key, value);
+        }
+        self.get_setting(key)
+    }
+
+    // ==================== TYPED ACCESSORS ====================
+
+    /// Gets the name of the shelter
+    pub fn shelter_name(&self) -> Result<String> {
+        Ok(self.get_setting(SettingKey::ShelterName)?.value)
+    }
+
+    /// Gets the three-letter code of the currency amounts are given in, such as USD
+    pub fn default_currency(&self) -> Result<String> {
+        Ok(self.get_setting(SettingKey::DefaultCurrency)?.value)
+    }
+
+    /// Gets the locale text written by the backend is in, falling back to English if
+    /// the stored value cannot be read
+    pub fn locale(&self) -> Result<Locale> {
+        let setting = self.get_setting(SettingKey::Locale)?;
+        match setting.value.parse() {
+            Ok(locale) => Ok(locale),
+            Err(_) => {
+                log::warn!(
+                    "Setting {} has an unreadable value {:?}, using the default",
+                    SettingKey::Locale,
+                    setting.value
+                );
+                Ok(Locale::default())
+            }
+        }
+    }
+
+    /// Gets the least severe kind of message written to the log, falling back to
+    /// info if the stored value cannot be read
+    pub fn log_level(&self) -> Result<LogLevel> {
+        let setting = self.get_setting(SettingKey::LogLevel)?;
+        match setting.value.parse() {
+            Ok(level) => Ok(level),
+            Err(_) => {
+                log::warn!(
+                    "Setting {} has an unreadable value {:?}, using the default",
+                    SettingKey::LogLevel,
+                    setting.value
+                );
+                Ok(LogLevel::Info)
+            }
+        }
+    }
+
+    /// Gets the hours between automatic backups, 0 if they are turned off
+    pub fn backup_interval_hours(&self) -> Result<u32> {
+        self.get_number(SettingKey::BackupIntervalHours)
+    }
+
+    /// Gets the days between automatic database maintenance, 0 if it is turned off
+    pub fn maintenance_interval_days(&self) -> Result<u32> {
+        self.get_number(SettingKey::MaintenanceIntervalDays)
+    }
+
+    /// Gets the largest file that can be uploaded, in bytes
+    pub fn max_upload_bytes(&self) -> Result<u64> {
+        let megabytes: u32 = self.get_number(SettingKey::MaxUploadMegabytes)?;
+        Ok(u64::from(megabytes) * 1024 * 1024)
+    }
+
+    /// Gets the settings for sending emails
+    ///
+    /// # Returns
+    /// * `Result<Option<EmailConfig>>` - The settings, or None if no SMTP host or sender is
+    ///   set, which turns email off
+    pub fn email_config(&self) -> Result<Option<EmailConfig>> {
+        let smtp_host = self.read_setting(SettingKey::SmtpHost)?.value;
+        let sender = self.read_setting(SettingKey::EmailSender)?.value;
+        if smtp_host.is_empty() || sender.is_empty() {
+            return Ok(None);
+        }
+
+        let smtp_port = self.get_number(SettingKey::SmtpPort)?;
+        Ok(Some(EmailConfig {
+            smtp_host,
+            smtp_port: u16::try_from(smtp_port).context("SMTP port is out of range")?,
+            smtp_username: self.read_setting(SettingKey::SmtpUsername)?.value,
+            smtp_password: self.read_setting(SettingKey::SmtpPassword)?.value,
+            sender,
+            approved_template: self.read_setting(SettingKey::ApprovedEmailTemplate)?.value,
+            rejected_template: self.read_setting(SettingKey::RejectedEmailTemplate)?.value,
+            shelter_name: self.shelter_name()?,
+        }))
+    }
+
+    /// Gets the settings for uploading backups to cloud storage
+    ///
+    /// # Returns
+    /// * `Result<Option<CloudBackupConfig>>` - The settings, or None if no endpoint or
+    ///   bucket is set, which keeps backups on site only
+    pub fn cloud_backup_config(&self) -> Result<Option<CloudBackupConfig>> {
+        let endpoint = self.read_setting(SettingKey::CloudBackupEndpoint)?.value;
+        let bucket = self.read_setting(SettingKey::CloudBackupBucket)?.value;
+        if endpoint.is_empty() || bucket.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(CloudBackupConfig {
+            endpoint,
+            region: self.read_setting(SettingKey::CloudBackupRegion)?.value,
+            bucket,
+            access_key_id: self.read_setting(SettingKey::CloudBackupAccessKeyId)?.value,
+            secret_access_key: self
+                .read_setting(SettingKey::CloudBackupSecretAccessKey)?
+                .value,
+        }))
+    }
+
+    /// Gets when the reminder scheduler reminds staff
+    pub fn reminder_settings(&self) -> Result<ReminderSettings> {
+        Ok(ReminderSettings {
+            vaccination_days: self.get_number(SettingKey::VaccinationReminderDays)?,
+            length_of_stay_days: self.get_number(SettingKey::LengthOfStayReminderDays)?,
+            pending_request_days: self.get_number(SettingKey::PendingRequestReminderDays)?,
+            appointment_hours: self.get_number(SettingKey::AppointmentReminderHours)?,
+        })
+    }
+
+    /// Gets whether flagged animals are left out of the public listings feed, falling
+    /// back to leaving them out if the stored value cannot be read
+    pub fn listings_exclude_flagged(&self) -> Result<bool> {
+        self.get_flag(SettingKey::ListingsExcludeFlagged)
+    }
+
+    /// Gets the days a stray must be held after intake before it can be adopted, 0 if there
+    /// is no hold
+    pub fn stray_hold_days(&self) -> Result<u32> {
+        self.get_number(SettingKey::StrayHoldDays)
+    }
+
+    /// Gets the checks an adoption request must pass before it can be approved
+    pub fn approval_requirements(&self) -> Result<ApprovalRequirements> {
+        Ok(ApprovalRequirements {
+            interview: self.get_flag(SettingKey::RequireInterview)?,
+            reference_check: self.get_flag(SettingKey::RequireReferenceCheck)?,
+        })
+    }
+
+    /// Gets how adoption requests are scored for screening, falling back to the default of
+    /// any setting whose stored value cannot be read
+    pub fn screening_config(&self) -> Result<ScreeningConfig> {
+        let bands = self.get_setting(SettingKey::ScreeningIncomeBands)?;
+        let income_bands = match parse_income_bands(&bands.value) {
+            Ok(income_bands) => income_bands,
+            Err(_) => {
+                log::warn!(
+                    "Setting {} has an unreadable value {:?}, using the default",
+                    SettingKey::ScreeningIncomeBands,
+                    bands.value
+                );
+                parse_income_bands(SettingKey::ScreeningIncomeBands.default_value())?
+            }
+        };
+        Ok(ScreeningConfig {
+            points_per_person: self.get_points(SettingKey::ScreeningPointsPerPerson)?,
+            points_per_child: self.get_points(SettingKey::ScreeningPointsPerChild)?,
+            income_bands,
+            points_per_return: self.get_points(SettingKey::ScreeningPointsPerReturn)?,
+        })
+    }
+
+    /// Gets the address the HTTP API for the public website listens on
+    ///
+    /// # Returns
+    /// * `Result<Option<SocketAddr>>` - The address, or None if the API is turned off
+    pub fn http_api_address(&self) -> Result<Option<SocketAddr>> {
+        if !self.get_flag(SettingKey::HttpApiEnabled)? {
+            return Ok(None);
+        }
+        let setting = self.get_setting(SettingKey::HttpApiAddress)?;
+        match setting.value.parse() {
+            Ok(address) => Ok(Some(address)),
+            Err(_) => {
+                log::warn!(
+                    "Setting {} has an unreadable value {:?}, using the default",
+                    SettingKey::HttpApiAddress,
+                    setting.value
+                );
+                SettingKey::HttpApiAddress
+                    .default_value()
+                    .parse()
+                    .map(Some)
+                    .context("Default of the HTTP API address is not an address")
+            }
+        }
+    }
+
+    /// Gets a numeric setting, falling back to its default if the stored value cannot
+    /// be read, such as after the database was edited by hand
+    ///
+    /// # Arguments
+    /// * `key` - The setting to get
+    ///
+    /// # Returns
+    /// * `Result<u32>` - The value of the setting
+    fn get_number(&self, key: SettingKey) -> Result<u32> {
+        let setting = self.get_setting(key)?;
+        match setting.value.parse() {
+            Ok(number) => Ok(number),
+            Err(_) => {
+                log::warn!(
+                    "Setting {} has an unreadable value {:?}, using the default",
+                    key,
+                    setting.value
+                );
+                key.default_value()
+                    .parse()
+                    .context(format!("Default of setting {} is not a number", key))
+            }
+        }
+    }
+
+    /// Gets a setting that is turned on or off, falling back to its default if the stored
+    /// value cannot be read
+    ///
+    /// # Arguments
+    /// * `key` - The setting to get
+    ///
+    /// # Returns
+    /// * `Result<bool>` - Whether the setting is turned on
+    fn get_flag(&self, key: SettingKey) -> Result<bool> {
+        let setting = self.get_setting(key)?;
+        match setting.value.parse() {
+            Ok(flag) => Ok(flag),
+            Err(_) => {
+                log::warn!(
+                    "Setting {} has an unreadable value {:?}, using the default",
+                    key,
+                    setting.value
+                );
+                key.default_value()
+                    .parse()
+                    .context(format!("Default of setting {} is not true or false", key))
+            }
+        }
+    }
+
+    /// Gets a setting holding screening points, which may be negative, falling back to its
+    /// default if the stored value cannot be read
+    ///
+    /// # Arguments
+    /// * `key` - The setting to get
+    ///
+    /// # Returns
+    /// * `Result<i64>` - The value of the setting
+    fn get_points(&self, key: SettingKey) -> Result<i64> {
+        let setting = self.get_setting(key)?;
+        match setting.value.parse() {
+            Ok(points) => Ok(points),
+            Err(_) => {
+                log::warn!(
+                    "Setting {} has an unreadable value {:?}, using the default",
+                    key,
+                    setting.value
+                );
+                key.default_value()
+                    .parse()
+                    .context(format!("Default of setting {} is not a number", key))
+            }
+        }
+    }
+}
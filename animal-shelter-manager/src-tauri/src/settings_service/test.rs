@@ -0,0 +1,320 @@
+//
+// settings_service/test.rs
+//
+// This file contains unit tests for the settings service module.
+//
+
+#[cfg(test)]
+mod settings_service_tests {
+    use super::super::{types::SettingKey, SettingsService};
+    use crate::database_service::types::{ApprovalRequirements, IncomeBand};
+    use crate::i18n::types::Locale;
+    use crate::log_service::types::LogLevel;
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// Helper function to create a test settings service with a dedicated database
+    ///
+    /// # Arguments
+    /// * `test_name` - Name of the test for unique database path
+    ///
+    /// # Returns
+    /// * `SettingsService` - A new settings service instance with test database
+    fn create_test_settings_service(test_name: &str) -> SettingsService {
+        let mut db_path = PathBuf::from("test_artifacts/settings_service");
+        db_path.push(test_name);
+        fs::create_dir_all(&db_path).expect("Failed to create test artifacts directory");
+        db_path.push("test_settings.db");
+        let _ = fs::remove_file(&db_path);
+
+        SettingsService::new(db_path).expect("Failed to create test settings service")
+    }
+
+    #[test]
+    fn test_defaults() {
+        let service = create_test_settings_service("test_defaults");
+
+        let settings = service.get_settings().unwrap();
+        assert_eq!(settings.len(), 33);
+        assert!(settings.iter().all(|setting| setting.is_default));
+        assert_eq!(service.shelter_name().unwrap(), "Animal Shelter");
+        assert_eq!(
+            service
+                .get_setting(SettingKey::DefaultCurrency)
+                .unwrap()
+                .value,
+            "USD"
+        );
+        assert_eq!(service.max_upload_bytes().unwrap(), 20 * 1024 * 1024);
+        assert_eq!(service.locale().unwrap(), Locale::En);
+        assert_eq!(service.log_level().unwrap(), LogLevel::Info);
+        assert!(service.listings_exclude_flagged().unwrap());
+        assert_eq!(
+            service.approval_requirements().unwrap(),
+            ApprovalRequirements::default()
+        );
+    }
+
+    #[test]
+    fn test_set_setting() {
+        let service = create_test_settings_service("test_set_setting");
+
+        // Values are stored in their normalized form
+        let setting = service
+            .set_setting(SettingKey::DefaultCurrency, " eur ")
+            .unwrap();
+        assert_eq!(setting.value, "EUR");
+        assert!(!setting.is_default);
+        assert!(setting.updated_timestamp.is_some());
+        assert_eq!(
+            service
+                .get_setting(SettingKey::DefaultCurrency)
+                .unwrap()
+                .value,
+            "EUR"
+        );
+        assert_eq!(service.default_currency().unwrap(), "EUR");
+        service.set_setting(SettingKey::Locale, "ES").unwrap();
+        assert_eq!(service.locale().unwrap(), Locale::Es);
+        service.set_setting(SettingKey::LogLevel, "Debug").unwrap();
+        assert_eq!(service.log_level().unwrap(), LogLevel::Debug);
+
+        service
+            .set_setting(SettingKey::ShelterName, "Happy Paws")
+            .unwrap();
+        service
+            .set_setting(SettingKey::ShelterName, "Happy Tails")
+            .unwrap();
+        assert_eq!(service.shelter_name().unwrap(), "Happy Tails");
+
+        service
+            .set_setting(SettingKey::MaxUploadMegabytes, "5")
+            .unwrap();
+        assert_eq!(service.max_upload_bytes().unwrap(), 5 * 1024 * 1024);
+        let setting = service
+            .set_setting(SettingKey::BackupIntervalHours, "0")
+            .unwrap();
+        assert_eq!(setting.value, "0");
+        assert_eq!(service.backup_interval_hours().unwrap(), 0);
+        assert_eq!(service.maintenance_interval_days().unwrap(), 0);
+        service
+            .set_setting(SettingKey::MaintenanceIntervalDays, "30")
+            .unwrap();
+        assert_eq!(service.maintenance_interval_days().unwrap(), 30);
+        service
+            .set_setting(SettingKey::ListingsExcludeFlagged, "FALSE")
+            .unwrap();
+        assert!(!service.listings_exclude_flagged().unwrap());
+        service
+            .set_setting(SettingKey::RequireReferenceCheck, "True")
+            .unwrap();
+        assert_eq!(
+            service.approval_requirements().unwrap(),
+            ApprovalRequirements {
+                interview: false,
+                reference_check: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_email_settings() {
+        let service = create_test_settings_service("test_email_settings");
+
+        // Email is off until both a host and a sender are set
+        assert!(service.email_config().unwrap().is_none());
+        service
+            .set_setting(SettingKey::SmtpHost, "SMTP.Example.org")
+            .unwrap();
+        assert!(service.email_config().unwrap().is_none());
+        service
+            .set_setting(SettingKey::EmailSender, "Happy Paws <adopt@example.org>")
+            .unwrap();
+
+        // The password is stored but never shown again
+        let setting = service
+            .set_setting(SettingKey::SmtpPassword, "hunter2")
+            .unwrap();
+        assert_eq!(setting.value, "********");
+        assert!(service
+            .get_settings()
+            .unwrap()
+            .iter()
+            .all(|setting| setting.value != "hunter2"));
+
+        let config = service.email_config().unwrap().unwrap();
+        assert_eq!(config.smtp_host, "smtp.example.org");
+        assert_eq!(config.smtp_port, 587);
+        assert_eq!(config.smtp_password, "hunter2");
+        assert!(config.approved_template.contains("{animal}"));
+
+        assert!(service
+            .set_setting(SettingKey::SmtpHost, "smtp://example.org")
+            .is_err());
+        assert!(service.set_setting(SettingKey::SmtpPort, "70000").is_err());
+        assert!(service
+            .set_setting(SettingKey::EmailSender, "not an address")
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::RejectedEmailTemplate, " ")
+            .is_err());
+    }
+
+    #[test]
+    fn test_http_api_settings() {
+        let service = create_test_settings_service("test_http_api_settings");
+
+        // The API stays off until it is turned on
+        assert_eq!(service.http_api_address().unwrap(), None);
+        service
+            .set_setting(SettingKey::HttpApiEnabled, "true")
+            .unwrap();
+        assert_eq!(
+            service.http_api_address().unwrap(),
+            Some("127.0.0.1:8080".parse().unwrap())
+        );
+        service
+            .set_setting(SettingKey::HttpApiAddress, " 0.0.0.0:9000 ")
+            .unwrap();
+        assert_eq!(
+            service.http_api_address().unwrap(),
+            Some("0.0.0.0:9000".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_cloud_backup_settings() {
+        let service = create_test_settings_service("test_cloud_backup_settings");
+
+        // Backups stay on site until both an endpoint and a bucket are set
+        assert!(service.cloud_backup_config().unwrap().is_none());
+        service
+            .set_setting(SettingKey::CloudBackupEndpoint, "HTTPS://S3.Example.org/")
+            .unwrap();
+        assert!(service.cloud_backup_config().unwrap().is_none());
+        service
+            .set_setting(SettingKey::CloudBackupBucket, "happy-paws-backups")
+            .unwrap();
+        let setting = service
+            .set_setting(SettingKey::CloudBackupSecretAccessKey, "s3cr3t")
+            .unwrap();
+        assert_eq!(setting.value, "********");
+
+        let config = service.cloud_backup_config().unwrap().unwrap();
+        assert_eq!(config.endpoint, "https://s3.example.org");
+        assert_eq!(config.region, "us-east-1");
+        assert_eq!(config.bucket, "happy-paws-backups");
+        assert_eq!(config.secret_access_key, "s3cr3t");
+
+        assert!(service
+            .set_setting(SettingKey::CloudBackupEndpoint, "s3.example.org")
+            .is_err());
+        assert!(service
+            .set_setting(
+                SettingKey::CloudBackupEndpoint,
+                "https://s3.example.org/bucket"
+            )
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::CloudBackupBucket, "Happy_Paws")
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::CloudBackupRegion, " ")
+            .is_err());
+        // Clearing the endpoint turns cloud backups off again
+        service
+            .set_setting(SettingKey::CloudBackupEndpoint, "")
+            .unwrap();
+        assert!(service.cloud_backup_config().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_screening_settings() {
+        let service = create_test_settings_service("test_screening_settings");
+
+        let config = service.screening_config().unwrap();
+        assert_eq!(config.points_per_person, 5);
+        assert_eq!(config.points_per_return, -25);
+        assert_eq!(config.income_bands.len(), 3);
+
+        // Bands are sorted and written in a uniform form
+        let setting = service
+            .set_setting(SettingKey::ScreeningIncomeBands, "60000 : 15,30000:5")
+            .unwrap();
+        assert_eq!(setting.value, "30000:5, 60000:15");
+        service
+            .set_setting(SettingKey::ScreeningPointsPerChild, "-3")
+            .unwrap();
+        let config = service.screening_config().unwrap();
+        assert_eq!(config.points_per_child, -3);
+        assert_eq!(
+            config.income_bands,
+            vec![
+                IncomeBand {
+                    min_income: 30_000,
+                    points: 5
+                },
+                IncomeBand {
+                    min_income: 60_000,
+                    points: 15
+                },
+            ]
+        );
+
+        // No bands means income does not count
+        service
+            .set_setting(SettingKey::ScreeningIncomeBands, "")
+            .unwrap();
+        assert!(service.screening_config().unwrap().income_bands.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_settings_rejected() {
+        let service = create_test_settings_service("test_invalid_settings_rejected");
+
+        assert!(service.set_setting(SettingKey::ShelterName, "  ").is_err());
+        assert!(service
+            .set_setting(SettingKey::DefaultCurrency, "DOLLARS")
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::BackupIntervalHours, "-1")
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::MaxUploadMegabytes, "0")
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::VaccinationReminderDays, "366")
+            .is_err());
+        assert!(service.set_setting(SettingKey::Locale, "xx").is_err());
+        assert!(service.set_setting(SettingKey::LogLevel, "loud").is_err());
+        assert!(service
+            .set_setting(SettingKey::ListingsExcludeFlagged, "maybe")
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::RequireInterview, "yes")
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::ScreeningPointsPerPerson, "1001")
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::ScreeningIncomeBands, "lots:10")
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::StrayHoldDays, "400")
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::MaintenanceIntervalDays, "366")
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::AppointmentReminderHours, "169")
+            .is_err());
+        assert!(service
+            .set_setting(SettingKey::HttpApiAddress, "localhost")
+            .is_err());
+        assert!(service
+            .get_settings()
+            .unwrap()
+            .iter()
+            .all(|setting| setting.is_default));
+    }
+}
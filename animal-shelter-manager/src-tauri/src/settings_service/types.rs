@@ -0,0 +1,406 @@
+//
+// settings_service/types.rs
+//
+// This module contains the type definitions for application settings,
+// including the known setting keys with their defaults and allowed values.
+//
+
+use crate::database_service::types::IncomeBand;
+use crate::i18n::types::Locale;
+use crate::log_service::types::LogLevel;
+use anyhow::{bail, Result};
+use lettre::message::Mailbox;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
+
+/// A setting that can be changed by an admin
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString, EnumIter,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum SettingKey {
+    /// Name of the shelter, shown in the application and on reports
+    ShelterName,
+    /// ISO 4217 code of the currency amounts such as adoption fees are given in
+    DefaultCurrency,
+    /// Language of the error summaries, reports and other text written by the backend
+    Locale,
+    /// Least severe kind of message written to the log
+    LogLevel,
+    /// Hours between automatic backups, or 0 to turn them off
+    BackupIntervalHours,
+    /// Days between automatic vacuuming and analyzing of the databases, or 0 to turn it off
+    MaintenanceIntervalDays,
+    /// Largest file that can be uploaded, in megabytes
+    MaxUploadMegabytes,
+    /// Host name of the SMTP server emails are sent through, or empty to turn email off
+    SmtpHost,
+    /// Port of the SMTP server; 465 uses TLS from the start, any other port STARTTLS
+    SmtpPort,
+    /// User name for the SMTP server, or empty if it does not require one
+    SmtpUsername,
+    /// Password for the SMTP server
+    SmtpPassword,
+    /// Address emails are sent from, such as `Happy Paws <adopt@example.org>`
+    EmailSender,
+    /// Body of the email sent when an adoption request is approved, in which `{name}`,
+    /// `{animal}` and `{shelter}` are replaced by the applicant, animal and shelter names
+    ApprovedEmailTemplate,
+    /// Body of the email sent when an adoption request is rejected, with the same
+    /// placeholders as `ApprovedEmailTemplate`
+    RejectedEmailTemplate,
+    /// Days ahead of a vaccination's due date to remind staff, or 0 to turn the reminder off
+    VaccinationReminderDays,
+    /// Days an animal can stay at the shelter before staff are reminded, or 0 to turn the
+    /// reminder off
+    LengthOfStayReminderDays,
+    /// Days a request can stay pending before staff are reminded, or 0 to turn the
+    /// reminder off
+    PendingRequestReminderDays,
+    /// Hours ahead of a confirmed meet-and-greet appointment to remind the customer, or 0
+    /// to turn the reminder off
+    AppointmentReminderHours,
+    /// Whether animals with a staff-only behavior or medical note are left out of the
+    /// public listings feed
+    ListingsExcludeFlagged,
+    /// Screening points for each person in an applicant's household
+    ScreeningPointsPerPerson,
+    /// Screening points for each child in an applicant's household
+    ScreeningPointsPerChild,
+    /// Screening points for an applicant's annual income, as bands such as
+    /// `20000:10, 50000:20` giving the points for an income at or above each amount
+    ScreeningIncomeBands,
+    /// Screening points for each animal an applicant adopted before and returned
+    ScreeningPointsPerReturn,
+    /// Days a stray must be held after intake before it can be adopted, or 0 for no hold
+    StrayHoldDays,
+    /// Whether an adoption request can only be approved once a phone interview with the
+    /// applicant was recorded
+    RequireInterview,
+    /// Whether an adoption request can only be approved once a referee gave a positive
+    /// reference for the applicant
+    RequireReferenceCheck,
+    /// Address of the S3-compatible storage backups are uploaded to, such as
+    /// `https://s3.eu-west-1.amazonaws.com`, or empty to keep backups on site only
+    CloudBackupEndpoint,
+    /// Region of the storage, used to sign requests
+    CloudBackupRegion,
+    /// Bucket backups are uploaded to
+    CloudBackupBucket,
+    /// Access key ID of the storage credentials
+    CloudBackupAccessKeyId,
+    /// Secret access key of the storage credentials
+    CloudBackupSecretAccessKey,
+    /// Whether the HTTP API for the public website is served; takes effect at the next start
+    HttpApiEnabled,
+    /// Address the HTTP API listens on, such as `0.0.0.0:8080`; takes effect at the next start
+    HttpApiAddress,
+}
+
+impl SettingKey {
+    /// Value used until an admin changes the setting
+    pub fn default_value(self) -> &'static str {
+        match self {
+            SettingKey::ShelterName => "Animal Shelter",
+            SettingKey::DefaultCurrency => "USD",
+            SettingKey::Locale => "en",
+            SettingKey::LogLevel => "info",
+            SettingKey::BackupIntervalHours => "24",
+            SettingKey::MaintenanceIntervalDays => "0",
+            SettingKey::MaxUploadMegabytes => "20",
+            SettingKey::SmtpHost => "",
+            SettingKey::SmtpPort => "587",
+            SettingKey::SmtpUsername => "",
+            SettingKey::SmtpPassword => "",
+            SettingKey::EmailSender => "",
+            SettingKey::ApprovedEmailTemplate => {
+                "Dear {name},\n\nGood news: your request to adopt {animal} has been approved. \
+                 We will be in touch soon to arrange the adoption.\n\n{shelter}"
+            }
+            SettingKey::RejectedEmailTemplate => {
+                "Dear {name},\n\nThank you for your interest in {animal}. Unfortunately your \
+                 adoption request was not successful this time.\n\n{shelter}"
+            }
+            SettingKey::VaccinationReminderDays => "7",
+            SettingKey::LengthOfStayReminderDays => "90",
+            SettingKey::PendingRequestReminderDays => "7",
+            SettingKey::AppointmentReminderHours => "24",
+            SettingKey::ListingsExcludeFlagged => "true",
+            SettingKey::ScreeningPointsPerPerson => "5",
+            SettingKey::ScreeningPointsPerChild => "0",
+            SettingKey::ScreeningIncomeBands => "20000:10, 50000:20, 100000:30",
+            SettingKey::ScreeningPointsPerReturn => "-25",
+            SettingKey::StrayHoldDays => "5",
+            SettingKey::RequireInterview => "false",
+            SettingKey::RequireReferenceCheck => "false",
+            SettingKey::CloudBackupEndpoint => "",
+            SettingKey::CloudBackupRegion => "us-east-1",
+            SettingKey::CloudBackupBucket => "",
+            SettingKey::CloudBackupAccessKeyId => "",
+            SettingKey::CloudBackupSecretAccessKey => "",
+            SettingKey::HttpApiEnabled => "false",
+            SettingKey::HttpApiAddress => "127.0.0.1:8080",
+        }
+    }
+
+    /// Whether the value must not be shown once stored, such as a password
+    pub fn is_secret(self) -> bool {
+        matches!(
+            self,
+            SettingKey::SmtpPassword | SettingKey::CloudBackupSecretAccessKey
+        )
+    }
+
+    /// Checks a value for the setting and puts it in the form it is stored in
+    ///
+    /// # Arguments
+    /// * `value` - The value entered for the setting
+    ///
+    /// # Returns
+    /// * `Result<String>` - The value to store, or error describing why it is not allowed
+    pub fn normalize(self, value: &str) -> Result<String> {
+        let value = value.trim();
+        match self {
+            SettingKey::ShelterName => {
+                if value.is_empty() {
+                    bail!("The shelter name cannot be empty");
+                }
+                if value.chars().count() > 100 {
+                    bail!("The shelter name can be at most 100 characters long");
+                }
+                Ok(value.to_string())
+            }
+            SettingKey::DefaultCurrency => {
+                if value.len() != 3 || !value.chars().all(|c| c.is_ascii_alphabetic()) {
+                    bail!("The currency must be a three-letter code such as USD");
+                }
+                Ok(value.to_ascii_uppercase())
+            }
+            SettingKey::Locale => match value.to_ascii_lowercase().parse::<Locale>() {
+                Ok(locale) => Ok(locale.to_string()),
+                Err(_) => {
+                    let locales: Vec<String> = Locale::iter().map(|l| l.to_string()).collect();
+                    bail!("The locale must be one of: {}", locales.join(", "))
+                }
+            },
+            SettingKey::LogLevel => match value.to_ascii_lowercase().parse::<LogLevel>() {
+                Ok(level) => Ok(level.to_string()),
+                Err(_) => {
+                    let levels: Vec<String> = LogLevel::iter().map(|l| l.to_string()).collect();
+                    bail!("The log level must be one of: {}", levels.join(", "))
+                }
+            },
+            SettingKey::BackupIntervalHours => match value.parse::<u32>() {
+                Ok(hours) if hours <= 24 * 30 => Ok(hours.to_string()),
+                _ => bail!("The backup interval must be a whole number of hours from 0 to 720"),
+            },
+            SettingKey::MaintenanceIntervalDays => match value.parse::<u32>() {
+                Ok(days) if days <= 365 => Ok(days.to_string()),
+                _ => bail!("The maintenance interval must be a whole number of days from 0 to 365"),
+            },
+            SettingKey::MaxUploadMegabytes => match value.parse::<u32>() {
+                Ok(megabytes) if (1..=100).contains(&megabytes) => Ok(megabytes.to_string()),
+                _ => bail!("The upload limit must be a whole number of megabytes from 1 to 100"),
+            },
+            SettingKey::SmtpHost => {
+                if value.contains(|c: char| c.is_whitespace() || c == '/' || c == ':') {
+                    bail!("The SMTP host must be a host name such as smtp.example.org");
+                }
+                Ok(value.to_ascii_lowercase())
+            }
+            SettingKey::SmtpPort => match value.parse::<u16>() {
+                Ok(port) if port > 0 => Ok(port.to_string()),
+                _ => bail!("The SMTP port must be a number from 1 to 65535"),
+            },
+            SettingKey::SmtpUsername | SettingKey::SmtpPassword => Ok(value.to_string()),
+            SettingKey::EmailSender => {
+                if !value.is_empty() && value.parse::<Mailbox>().is_err() {
+                    bail!("The sender must be an email address such as adopt@example.org");
+                }
+                Ok(value.to_string())
+            }
+            SettingKey::ApprovedEmailTemplate | SettingKey::RejectedEmailTemplate => {
+                if value.is_empty() {
+                    bail!("The email template cannot be empty");
+                }
+                if value.chars().count() > 5000 {
+                    bail!("The email template can be at most 5000 characters long");
+                }
+                Ok(value.to_string())
+            }
+            SettingKey::VaccinationReminderDays
+            | SettingKey::LengthOfStayReminderDays
+            | SettingKey::PendingRequestReminderDays => match value.parse::<u32>() {
+                Ok(days) if days <= 365 => Ok(days.to_string()),
+                _ => bail!("The reminder must be a whole number of days from 0 to 365"),
+            },
+            SettingKey::AppointmentReminderHours => match value.parse::<u32>() {
+                Ok(hours) if hours <= 168 => Ok(hours.to_string()),
+                _ => bail!("The reminder must be a whole number of hours from 0 to 168"),
+            },
+            SettingKey::ListingsExcludeFlagged
+            | SettingKey::RequireInterview
+            | SettingKey::RequireReferenceCheck
+            | SettingKey::HttpApiEnabled => match value.to_ascii_lowercase().parse::<bool>() {
+                Ok(flag) => Ok(flag.to_string()),
+                Err(_) => bail!("The setting must be true or false"),
+            },
+            SettingKey::ScreeningPointsPerPerson
+            | SettingKey::ScreeningPointsPerChild
+            | SettingKey::ScreeningPointsPerReturn => match value.parse::<i64>() {
+                Ok(points) if (-1000..=1000).contains(&points) => Ok(points.to_string()),
+                _ => bail!("The points must be a whole number from -1000 to 1000"),
+            },
+            SettingKey::StrayHoldDays => match value.parse::<u32>() {
+                Ok(days) if days <= 365 => Ok(days.to_string()),
+                _ => bail!("The hold must be a whole number of days from 0 to 365"),
+            },
+            SettingKey::ScreeningIncomeBands => {
+                let bands: Vec<String> = parse_income_bands(value)?
+                    .iter()
+                    .map(|band| format!("{}:{}", band.min_income, band.points))
+                    .collect();
+                Ok(bands.join(", "))
+            }
+            SettingKey::CloudBackupEndpoint => {
+                let endpoint = value.trim_end_matches('/').to_ascii_lowercase();
+                let address = endpoint
+                    .strip_prefix("https://")
+                    .or_else(|| endpoint.strip_prefix("http://"));
+                match address {
+                    None if endpoint.is_empty() => Ok(String::new()),
+                    Some(address)
+                        if !address.is_empty()
+                            && !address.contains(|c: char| c.is_whitespace() || c == '/') =>
+                    {
+                        Ok(endpoint)
+                    }
+                    _ => bail!(
+                        "The endpoint must be an address such as https://s3.eu-west-1.amazonaws.com"
+                    ),
+                }
+            }
+            SettingKey::CloudBackupRegion => {
+                if value.is_empty() || value.contains(char::is_whitespace) {
+                    bail!("The region must be a name such as us-east-1");
+                }
+                Ok(value.to_string())
+            }
+            SettingKey::CloudBackupBucket => {
+                let valid = (3..=63).contains(&value.len())
+                    && value.chars().all(|c| {
+                        c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '.'
+                    });
+                if !value.is_empty() && !valid {
+                    bail!("The bucket must be 3 to 63 lowercase letters, digits, dots or hyphens");
+                }
+                Ok(value.to_string())
+            }
+            SettingKey::CloudBackupAccessKeyId | SettingKey::CloudBackupSecretAccessKey => {
+                Ok(value.to_string())
+            }
+            SettingKey::HttpApiAddress => match value.parse::<SocketAddr>() {
+                Ok(address) => Ok(address.to_string()),
+                Err(_) => bail!("The address must be an IP address and port such as 0.0.0.0:8080"),
+            },
+        }
+    }
+}
+
+/// Reads income bands written as `amount:points` pairs separated by commas
+///
+/// # Arguments
+/// * `value` - The bands, such as `20000:10, 50000:20`; empty for none
+///
+/// # Returns
+/// * `Result<Vec<IncomeBand>>` - The bands from the lowest income up, or error describing
+///   why they cannot be read
+pub fn parse_income_bands(value: &str) -> Result<Vec<IncomeBand>> {
+    let mut bands = Vec::new();
+    for band in value
+        .split(',')
+        .map(str::trim)
+        .filter(|band| !band.is_empty())
+    {
+        let parsed = band.split_once(':').and_then(|(income, points)| {
+            Some(IncomeBand {
+                min_income: income.trim().parse().ok()?,
+                points: points.trim().parse().ok()?,
+            })
+        });
+        match parsed {
+            Some(band) if (-1000..=1000).contains(&band.points) => bands.push(band),
+            _ => bail!(
+                "Each income band must be an amount and points from -1000 to 1000, such as 20000:10"
+            ),
+        }
+    }
+    bands.sort_by_key(|band| band.min_income);
+    bands.dedup_by_key(|band| band.min_income);
+    Ok(bands)
+}
+
+/// The current value of a setting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Setting {
+    /// Which setting this is
+    pub key: SettingKey,
+    /// The value of the setting
+    pub value: String,
+    /// Whether the value is the default because the setting was never changed
+    pub is_default: bool,
+    /// Timestamp of the last change, None if the setting was never changed
+    pub updated_timestamp: Option<i64>,
+}
+
+/// Everything needed to send emails, read from the settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailConfig {
+    /// Host name of the SMTP server
+    pub smtp_host: String,
+    /// Port of the SMTP server
+    pub smtp_port: u16,
+    /// User name for the SMTP server, empty if it does not require one
+    pub smtp_username: String,
+    /// Password for the SMTP server
+    pub smtp_password: String,
+    /// Address emails are sent from
+    pub sender: String,
+    /// Body of the email sent when an adoption request is approved
+    pub approved_template: String,
+    /// Body of the email sent when an adoption request is rejected
+    pub rejected_template: String,
+    /// Name of the shelter, filled in for `{shelter}`
+    pub shelter_name: String,
+}
+
+/// Everything needed to upload backups to S3-compatible storage, read from the settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloudBackupConfig {
+    /// Address of the storage, such as `https://s3.eu-west-1.amazonaws.com`
+    pub endpoint: String,
+    /// Region of the storage
+    pub region: String,
+    /// Bucket backups are uploaded to
+    pub bucket: String,
+    /// Access key ID of the credentials
+    pub access_key_id: String,
+    /// Secret access key of the credentials
+    pub secret_access_key: String,
+}
+
+/// When the reminder scheduler reminds staff and customers; 0 turns a reminder off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReminderSettings {
+    /// Days ahead of a vaccination's due date
+    pub vaccination_days: u32,
+    /// Days an animal has been at the shelter
+    pub length_of_stay_days: u32,
+    /// Days a request has been pending
+    pub pending_request_days: u32,
+    /// Hours ahead of a confirmed meet-and-greet appointment
+    pub appointment_hours: u32,
+}
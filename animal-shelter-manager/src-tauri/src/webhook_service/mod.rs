@@ -0,0 +1,189 @@
+//
+// webhook_service/mod.rs
+//
+// This module delivers the events the DatabaseService queues for webhooks,
+// signing every body so external tools can check it came from the shelter.
+//
+
+use crate::database_service::{types::DueWebhookDelivery, DatabaseService};
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::header::CONTENT_TYPE;
+use sha2::Sha256;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod test;
+
+/// Header carrying the HMAC-SHA256 signature of the body, as `sha256=<hex digest>`
+pub const SIGNATURE_HEADER: &str = "X-Shelter-Signature";
+
+/// Header carrying the name of the event, such as `animal-created`
+pub const EVENT_HEADER: &str = "X-Shelter-Event";
+
+/// Header carrying the ID of the delivery, so receivers can ignore retried duplicates
+pub const DELIVERY_HEADER: &str = "X-Shelter-Delivery";
+
+/// How often the queue is checked for deliveries that are due
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How long a webhook has to respond before the attempt fails
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Signs the body of an event with the secret of a webhook
+///
+/// # Arguments
+/// * `secret` - The secret of the webhook
+/// * `body` - The body being sent
+///
+/// # Returns
+/// * `String` - The signature, as `sha256=<hex digest>`
+pub fn sign(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Builds the HTTP client used to deliver events
+///
+/// # Returns
+/// * `Result<reqwest::Client>` - The client or error
+pub fn client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .context("Failed to build webhook HTTP client")
+}
+
+/// Delivers queued events until the application exits
+///
+/// # Arguments
+/// * `database_service` - Service for handling database operations
+pub async fn run(database_service: Arc<Mutex<DatabaseService>>) {
+    let client = match client() {
+        Ok(client) => client,
+        Err(e) => {
+            log::error!("Webhook dispatcher stopped: {:#}", e);
+            return;
+        }
+    };
+
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = deliver_due(&database_service, &client).await {
+            log::error!("Failed to deliver webhook events: {:#}", e);
+        }
+    }
+}
+
+/// Attempts every delivery that is due, recording the outcome in the delivery log
+///
+/// # Arguments
+/// * `database_service` - Service for handling database operations
+/// * `client` - The HTTP client sending the events
+///
+/// # Returns
+/// * `Result<usize>` - The number of deliveries attempted
+pub async fn deliver_due(
+    database_service: &Arc<Mutex<DatabaseService>>,
+    client: &reqwest::Client,
+) -> Result<usize> {
+    let now = Utc::now().timestamp();
+    let due = run_database_task(database_service, move |db| {
+        db.query_due_webhook_deliveries(now)
+    })
+    .await?;
+
+    let attempted = due.len();
+    for due_delivery in due {
+        let (response_code, error) = attempt(client, &due_delivery).await;
+        if let Some(error) = &error {
+            log::warn!(
+                "Webhook delivery {} to {} failed: {}",
+                due_delivery.delivery.id,
+                due_delivery.url,
+                error
+            );
+        }
+
+        let delivery_id = due_delivery.delivery.id;
+        run_database_task(database_service, move |db| {
+            db.record_webhook_attempt(
+                &delivery_id,
+                response_code,
+                error.as_deref(),
+                Utc::now().timestamp(),
+            )
+        })
+        .await?;
+    }
+    Ok(attempted)
+}
+
+/// Posts a queued event to its webhook
+///
+/// # Arguments
+/// * `client` - The HTTP client sending the event
+/// * `due_delivery` - The delivery to attempt
+///
+/// # Returns
+/// * `(Option<i64>, Option<String>)` - The status code of the response, if any,
+///   and why the attempt failed, or None if the webhook accepted the event
+async fn attempt(
+    client: &reqwest::Client,
+    due_delivery: &DueWebhookDelivery,
+) -> (Option<i64>, Option<String>) {
+    let delivery = &due_delivery.delivery;
+    let result = client
+        .post(&due_delivery.url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, delivery.event.to_string())
+        .header(DELIVERY_HEADER, &delivery.id)
+        .header(
+            SIGNATURE_HEADER,
+            sign(&due_delivery.secret, &delivery.payload),
+        )
+        .body(delivery.payload.clone())
+        .send()
+        .await;
+
+    match result {
+        Ok(response) => {
+            let status = response.status();
+            let error =
+                (!status.is_success()).then(|| format!("Webhook responded with status {}", status));
+            (Some(i64::from(status.as_u16())), error)
+        }
+        Err(e) => (None, Some(format!("Failed to reach webhook: {}", e))),
+    }
+}
+
+/// Runs a task against the DatabaseService on the blocking thread pool
+///
+/// # Arguments
+/// * `database_service` - Service for handling database operations
+/// * `task` - The database work to run
+///
+/// # Returns
+/// * `Result<T>` - The result of the database work
+async fn run_database_task<T, F>(
+    database_service: &Arc<Mutex<DatabaseService>>,
+    task: F,
+) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&DatabaseService) -> Result<T> + Send + 'static,
+{
+    let database_service = database_service.clone();
+    tokio::task::spawn_blocking(move || {
+        let database_service = database_service
+            .lock()
+            .map_err(|_| anyhow!("Database service lock was poisoned"))?;
+        task(&database_service)
+    })
+    .await
+    .map_err(|e| anyhow!("Database task did not complete: {}", e))?
+}
@@ -0,0 +1,136 @@
+//
+// webhook_service/test.rs
+//
+// This file contains unit tests for the webhook delivery module.
+//
+
+#[cfg(test)]
+mod webhook_service_tests {
+    use crate::database_service::{
+        types::{Animal, AnimalStatus, Webhook, WebhookDeliveryStatus, WebhookEvent},
+        DatabaseService,
+    };
+    use crate::webhook_service::{client, deliver_due, sign, EVENT_HEADER, SIGNATURE_HEADER};
+    use axum::{
+        extract::State,
+        http::{HeaderMap, StatusCode},
+        routing::post,
+        Router,
+    };
+    use chrono::Utc;
+    use std::fs;
+    use std::net::SocketAddr;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    /// Requests received by the test receiver, as (event header, signature header, body)
+    type Received = Arc<Mutex<Vec<(String, String, String)>>>;
+
+    /// Starts a receiver that accepts events on `/ok` and fails them on `/broken`
+    ///
+    /// # Returns
+    /// * `(SocketAddr, Received)` - The address of the receiver and the requests it accepted
+    async fn start_receiver() -> (SocketAddr, Received) {
+        let received = Received::default();
+        let api = Router::new()
+            .route(
+                "/ok",
+                post(
+                    |State(received): State<Received>, headers: HeaderMap, body: String| async move {
+                        let header = |name: &str| {
+                            headers[name].to_str().unwrap_or_default().to_string()
+                        };
+                        received.lock().unwrap().push((
+                            header(EVENT_HEADER),
+                            header(SIGNATURE_HEADER),
+                            body,
+                        ));
+                        StatusCode::OK
+                    },
+                ),
+            )
+            .route("/broken", post(|| async { StatusCode::INTERNAL_SERVER_ERROR }))
+            .with_state(received.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, api).await.unwrap() });
+        (address, received)
+    }
+
+    #[tokio::test]
+    async fn test_deliver_signed_events() {
+        let root_path = PathBuf::from("test_artifacts/webhook_service/test_deliver_signed_events");
+        if root_path.exists() {
+            fs::remove_dir_all(&root_path).expect("Failed to remove existing test directory");
+        }
+        fs::create_dir_all(&root_path).expect("Failed to create test artifacts directory");
+        let db = DatabaseService::new(root_path.join("test.db"))
+            .expect("Failed to create test db service");
+
+        let (address, received) = start_receiver().await;
+        for (id, path) in [("ok", "ok"), ("broken", "broken")] {
+            db.insert_webhook(&Webhook {
+                id: id.to_string(),
+                url: format!("http://{}/{}", address, path),
+                secret: "shelter-secret".to_string(),
+                event_types: vec![WebhookEvent::AnimalCreated],
+                created_timestamp: Utc::now().timestamp(),
+            })
+            .expect("Failed to insert webhook");
+        }
+        db.insert_animal(&Animal {
+            id: "a1".to_string(),
+            name: "Buddy".to_string(),
+            specie: "Dog".to_string(),
+            breed: "Golden Retriever".to_string(),
+            sex: "Male".to_string(),
+            birth_month: Some(6),
+            birth_year: Some(2020),
+            neutered: true,
+            admission_timestamp: Utc::now().timestamp(),
+            status: AnimalStatus::Available,
+            image_path: None,
+            appearance: "Golden coat with friendly eyes".to_string(),
+            bio: "Buddy loves playing fetch.".to_string(),
+            version: 1,
+            microchip_number: None,
+            location_id: None,
+            good_with_kids: None,
+            good_with_dogs: None,
+            good_with_cats: None,
+            special_needs: None,
+            size: None,
+            color: None,
+        })
+        .expect("Failed to insert test animal");
+
+        let db = Arc::new(Mutex::new(db));
+        let client = client().expect("Failed to build client");
+        let attempted = deliver_due(&db, &client).await.unwrap();
+        assert_eq!(attempted, 2);
+
+        // The receiver got the event with a signature matching its body
+        let received = received.lock().unwrap().clone();
+        assert_eq!(received.len(), 1);
+        let (event, signature, body) = &received[0];
+        assert_eq!(event, "animal-created");
+        assert_eq!(signature, &sign("shelter-secret", body));
+        let payload: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(payload["data"]["id"], "a1");
+
+        // The failed delivery waits for a retry instead of being sent again right away
+        {
+            let db = db.lock().unwrap();
+            let delivered = db.query_webhook_deliveries("ok").unwrap();
+            assert_eq!(delivered[0].status, WebhookDeliveryStatus::Delivered);
+            assert_eq!(delivered[0].response_code, Some(200));
+            let failed = db.query_webhook_deliveries("broken").unwrap();
+            assert_eq!(failed[0].status, WebhookDeliveryStatus::Pending);
+            assert_eq!(failed[0].attempts, 1);
+            assert_eq!(failed[0].response_code, Some(500));
+            assert!(failed[0].next_attempt_timestamp > Utc::now().timestamp());
+        }
+        assert_eq!(deliver_due(&db, &client).await.unwrap(), 0);
+    }
+}
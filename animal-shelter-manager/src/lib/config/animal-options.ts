@@ -10,7 +10,10 @@ export type AnimalStatus =
   | "available"
   | "requested"
   | "adopted"
-  | "passed-away";
+  | "fostered"
+  | "passed-away"
+  | "transferred"
+  | "trial-adoption";
 
 /** Animal sex options for biological classification */
 export type AnimalSex = "Male" | "Female";
@@ -33,6 +36,12 @@ export type AnimalSpecies =
 /** Neutered status options for spay/neuter tracking */
 export type NeuteredStatus = "yes" | "no";
 
+/** Size categories visitors use to describe the animal they are looking for */
+export type AnimalSize = "small" | "medium" | "large";
+
+/** Household compatibility answers, where unknown means the shelter has not assessed it */
+export type CompatibilityStatus = "yes" | "no" | "unknown";
+
 /** Available animal status options with display labels */
 export const ANIMAL_STATUS_OPTIONS: Array<{
   value: AnimalStatus;
@@ -41,7 +50,10 @@ export const ANIMAL_STATUS_OPTIONS: Array<{
   { value: "available", label: "Available" },
   { value: "requested", label: "Requested" },
   { value: "adopted", label: "Adopted" },
+  { value: "fostered", label: "Fostered" },
   { value: "passed-away", label: "Passed Away" },
+  { value: "transferred", label: "Transferred" },
+  { value: "trial-adoption", label: "Trial Adoption" },
 ];
 
 /** Available animal sex options with display labels */
@@ -72,6 +84,16 @@ export const ANIMAL_SPECIES_OPTIONS: Array<{
   { value: "Pig", label: "Pig" },
 ];
 
+/** Available animal size options with display labels */
+export const ANIMAL_SIZE_OPTIONS: Array<{
+  value: AnimalSize;
+  label: string;
+}> = [
+  { value: "small", label: "Small" },
+  { value: "medium", label: "Medium" },
+  { value: "large", label: "Large" },
+];
+
 /** Available neutered status options with display labels */
 export const NEUTERED_STATUS_OPTIONS: Array<{
   value: NeuteredStatus;
@@ -81,6 +103,49 @@ export const NEUTERED_STATUS_OPTIONS: Array<{
   { value: "no", label: "No" },
 ];
 
+/** Yes/no filter options for neutered status and image presence filtering */
+export const YES_NO_FILTER_OPTIONS: Array<{
+  value: string;
+  label: string;
+}> = [
+  { value: "all", label: "All" },
+  { value: "yes", label: "Yes" },
+  { value: "no", label: "No" },
+];
+
+/** Available household compatibility options with display labels */
+export const COMPATIBILITY_STATUS_OPTIONS: Array<{
+  value: CompatibilityStatus;
+  label: string;
+}> = [
+  { value: "unknown", label: "Unknown" },
+  { value: "yes", label: "Yes" },
+  { value: "no", label: "No" },
+];
+
+/** Filter options for household compatibility, including animals not assessed yet */
+export const COMPATIBILITY_FILTER_OPTIONS: Array<{
+  value: string;
+  label: string;
+}> = [
+  { value: "all", label: "All" },
+  { value: "yes", label: "Yes" },
+  { value: "no", label: "No" },
+  { value: "unknown", label: "Unknown" },
+];
+
+/** Age filter options, including animals whose birth year is unknown */
+export const AGE_FILTER_OPTIONS: Array<{
+  value: string;
+  label: string;
+}> = [
+  { value: "all", label: "All" },
+  { value: "young", label: "Under 1 year" },
+  { value: "adult", label: "1 to 7 years" },
+  { value: "senior", label: "8 years and over" },
+  { value: "unknown", label: "Unknown" },
+];
+
 /** Date range filter options for admission and adoption date filtering */
 export const DATE_RANGE_FILTER_OPTIONS: Array<{
   value: string;
@@ -233,3 +298,33 @@ export function getBreedsForSpecies(species: AnimalSpecies): string[] {
 export function getAllSpecies(): AnimalSpecies[] {
   return Object.keys(ANIMAL_BREED_OPTIONS) as AnimalSpecies[];
 }
+
+/**
+ * Gets the display label for a household compatibility flag.
+ *
+ * @param flag - The flag stored on the animal, null or undefined when unknown
+ * @returns string - The matching label from COMPATIBILITY_STATUS_OPTIONS
+ */
+export function getCompatibilityLabel(
+  flag: boolean | null | undefined,
+): string {
+  const value: CompatibilityStatus =
+    flag === true ? "yes" : flag === false ? "no" : "unknown";
+  const match = COMPATIBILITY_STATUS_OPTIONS.find(
+    (option) => option.value === value,
+  );
+  return match!.label;
+}
+
+/**
+ * Gets the household compatibility flag for a label picked in a form.
+ *
+ * @param label - The label picked from COMPATIBILITY_STATUS_OPTIONS
+ * @returns boolean | null - The flag to store, null when unknown
+ */
+export function getCompatibilityFlag(label: string): boolean | null {
+  const value = COMPATIBILITY_STATUS_OPTIONS.find(
+    (option) => option.label === label,
+  )?.value;
+  return value === "yes" ? true : value === "no" ? false : null;
+}
@@ -7,11 +7,17 @@
 
 import { invoke } from "@tauri-apps/api/core";
 import { info, error } from "@tauri-apps/plugin-log";
+import {
+  CommandErrorKind,
+  describeError,
+  isCommandError,
+} from "$lib/utils/data-utils";
 
 // ==================== TYPES ====================
 
 /** User role enum type */
 export enum UserRole {
+  ADMIN = "admin",
   STAFF = "staff",
   CUSTOMER = "customer",
 }
@@ -21,6 +27,17 @@ export enum LoginResult {
   SUCCESS = "success",
   INVALID_PASSWORD = "invalid-password",
   USER_NOT_FOUND = "user-not-found",
+  TOO_MANY_ATTEMPTS = "too-many-attempts",
+  PASSWORD_CHANGE_REQUIRED = "password-change-required",
+  TWO_FACTOR_REQUIRED = "two-factor-required",
+  INVALID_TWO_FACTOR_CODE = "invalid-two-factor-code",
+  SUSPENDED = "suspended",
+}
+
+/** User account status enum type */
+export enum UserStatus {
+  ACTIVE = "active",
+  SUSPENDED = "suspended",
 }
 
 /** Current user type containing username and role */
@@ -31,6 +48,76 @@ export interface CurrentUser {
   role: UserRole;
 }
 
+/** A user account as listed to admins */
+export interface UserAccount {
+  /** Username of the account */
+  username: string;
+  /** Role of the account */
+  role: UserRole;
+  /** Timestamp when the account was created, null for accounts created before it was recorded */
+  createdTimestamp: number | null;
+  /** Whether the account may log in */
+  status: UserStatus;
+}
+
+/** A session that is still active, as listed to admins */
+export interface SessionInfo {
+  /** Identifier of the session */
+  id: string;
+  /** Username of the account the session belongs to */
+  username: string;
+  /** Whether the session lasts for days instead of hours */
+  remembered: boolean;
+  /** Timestamp when the session started */
+  createdTimestamp: number;
+  /** Timestamp of the last activity in the session */
+  lastActivityTimestamp: number;
+  /** Timestamp after which the session ends */
+  expiresTimestamp: number;
+  /** Name of the device the session was started on, if known */
+  deviceName: string | null;
+  /** Whether this is the session of this app */
+  current: boolean;
+}
+
+/** How long sessions last before the user must log in again */
+export interface SessionPolicy {
+  /** Lifetime of a session, in seconds */
+  lifetimeSeconds: number;
+  /** Lifetime of a session when the user asks to be remembered, in seconds */
+  rememberedLifetimeSeconds: number;
+  /** Time without activity after which a session that is not remembered ends, in seconds */
+  idleTimeoutSeconds: number;
+}
+
+/** A temporary password issued by staff for a user who forgot theirs */
+export interface PasswordReset {
+  /** Username of the account being reset */
+  username: string;
+  /** One-time password to hand to the user */
+  temporaryPassword: string;
+  /** Timestamp after which the temporary password no longer works */
+  expiresTimestamp: number;
+}
+
+/** A single-use code an admin hands to a new staff member so they can register */
+export interface InviteCode {
+  /** The code to hand out */
+  code: string;
+  /** Role an account registered with the code receives */
+  role: UserRole;
+  /** Timestamp after which the code no longer works */
+  expiresTimestamp: number;
+}
+
+/** A new two-factor secret to add to an authenticator app */
+export interface TotpEnrollment {
+  /** Base32 secret, for typing into the app by hand */
+  secret: string;
+  /** otpauth:// URI holding the secret, for showing as a QR code */
+  provisioningUri: string;
+}
+
 /** Validation result type */
 export interface ValidationResult {
   /** Indicates if the input is valid */
@@ -47,6 +134,8 @@ export interface AuthResult {
   message: string;
   requiresAccountCreation?: boolean;
   invalidPassword?: boolean;
+  requiresPasswordChange?: boolean;
+  requiresTwoFactor?: boolean;
 }
 
 /** User credentials interface */
@@ -54,6 +143,10 @@ export interface UserCredentials {
   username: string;
   password: string;
   role: UserRole;
+  /** Whether the session should last for days instead of hours */
+  rememberMe?: boolean;
+  /** Invite code issued by an admin, required to register a staff account; it decides the role */
+  inviteCode?: string;
 }
 
 // ==================== VALIDATION FUNCTIONS ====================
@@ -149,7 +242,7 @@ export function validateCredentials(
   }
 
   // Check role validity
-  if (!["staff", "customer"].includes(credentials.role)) {
+  if (!["admin", "staff", "customer"].includes(credentials.role)) {
     return {
       isValid: false,
       errorMessage: "Please select a valid role.",
@@ -194,6 +287,7 @@ export async function authenticateUser(
     const loginResult: LoginResult = (await invoke("log_in", {
       username: credentials.username,
       password: credentials.password,
+      rememberMe: credentials.rememberMe ?? false,
     })) as LoginResult;
 
     info(`Tauri log_in result: ${loginResult}`);
@@ -219,6 +313,32 @@ export async function authenticateUser(
           requiresAccountCreation: true,
         };
 
+      case "password-change-required":
+        return {
+          success: false,
+          message: "Temporary password accepted. Please choose a new password.",
+          requiresPasswordChange: true,
+        };
+
+      case "two-factor-required":
+        return {
+          success: false,
+          message: "Please enter the code from your authenticator app.",
+          requiresTwoFactor: true,
+        };
+
+      case "suspended":
+        return {
+          success: false,
+          message: "This account has been suspended. Please contact the shelter.",
+        };
+
+      case "too-many-attempts":
+        return {
+          success: false,
+          message: "Too many login attempts. Please wait a minute and try again.",
+        };
+
       default:
         return {
           success: false,
@@ -226,7 +346,7 @@ export async function authenticateUser(
         };
     }
   } catch (e) {
-    error(`Authentication error: ${e}`);
+    error(`Authentication error: ${describeError(e)}`);
     return {
       success: false,
       message: "Authentication failed. Please try again.",
@@ -275,6 +395,7 @@ export async function createUserAccount(
       username: credentials.username,
       password: credentials.password,
       role: credentials.role,
+      inviteCode: credentials.inviteCode || null,
     });
 
     return {
@@ -282,11 +403,16 @@ export async function createUserAccount(
       message: "Account created successfully. You are now logged in.",
     };
   } catch (e) {
-    error(`Account creation error: ${e}`);
+    error(`Account creation error: ${describeError(e)}`);
 
     /** Handle specific error cases */
-    const errorMessage: string = e as string;
-    if (errorMessage.includes("already exists")) {
+    if (isCommandError(e, CommandErrorKind.PERMISSION_DENIED)) {
+      return {
+        success: false,
+        message: "A valid invite code from an admin is required to register a staff account.",
+      };
+    }
+    if (isCommandError(e, CommandErrorKind.CONFLICT)) {
       return {
         success: false,
         message: "Username already exists. Please choose a different username.",
@@ -300,6 +426,190 @@ export async function createUserAccount(
   }
 }
 
+/**
+ * Sets a new password using a temporary password issued by staff, then logs the user in.
+ *
+ * @param username - Username of the account being reset
+ * @param temporaryPassword - The temporary password issued by staff
+ * @param newPassword - The new password for the account
+ * @returns Promise<AuthResult> - Password reset result with success status and message
+ */
+export async function completePasswordReset(
+  username: string,
+  temporaryPassword: string,
+  newPassword: string,
+): Promise<AuthResult> {
+  const validation = validatePassword(newPassword);
+  if (!validation.isValid) {
+    return {
+      success: false,
+      message: validation.errorMessage,
+    };
+  }
+
+  try {
+    const loginResult: LoginResult = await invoke<LoginResult>(
+      "complete_password_reset",
+      { username, temporaryPassword, newPassword },
+    );
+
+    switch (loginResult) {
+      case "success":
+        return {
+          success: true,
+          message: "Password changed. You are now logged in.",
+        };
+
+      case "two-factor-required":
+        return {
+          success: false,
+          message: "Password changed. Please enter the code from your authenticator app.",
+          requiresTwoFactor: true,
+        };
+
+      case "suspended":
+        return {
+          success: false,
+          message: "This account has been suspended. Please contact the shelter.",
+        };
+
+      case "too-many-attempts":
+        return {
+          success: false,
+          message: "Too many login attempts. Please wait a minute and try again.",
+        };
+
+      default:
+        return {
+          success: false,
+          message:
+            "The temporary password is incorrect or has expired. Please ask staff for a new one.",
+        };
+    }
+  } catch (e) {
+    error(`Password reset error: ${describeError(e)}`);
+    return {
+      success: false,
+      message: "Password change failed. Please try again.",
+    };
+  }
+}
+
+/**
+ * Replaces the password of an account that must change it, then logs the user in.
+ * Either the account's current password or a temporary password issued by staff is accepted.
+ *
+ * @param username - Username of the account
+ * @param currentPassword - The account's current or temporary password
+ * @param newPassword - The new password for the account
+ * @returns Promise<AuthResult> - Password change result with success status and message
+ */
+export async function changePassword(
+  username: string,
+  currentPassword: string,
+  newPassword: string,
+): Promise<AuthResult> {
+  const validation = validatePassword(newPassword);
+  if (!validation.isValid) {
+    return {
+      success: false,
+      message: validation.errorMessage,
+    };
+  }
+  if (newPassword === currentPassword) {
+    return {
+      success: false,
+      message: "The new password must be different from the current one.",
+    };
+  }
+
+  try {
+    const loginResult: LoginResult = await invoke<LoginResult>(
+      "change_password",
+      { username, currentPassword, newPassword },
+    );
+
+    switch (loginResult) {
+      case "success":
+        return {
+          success: true,
+          message: "Password changed. You are now logged in.",
+        };
+
+      case "two-factor-required":
+        return {
+          success: false,
+          message: "Password changed. Please enter the code from your authenticator app.",
+          requiresTwoFactor: true,
+        };
+
+      case "suspended":
+        return {
+          success: false,
+          message: "This account has been suspended. Please contact the shelter.",
+        };
+
+      case "too-many-attempts":
+        return {
+          success: false,
+          message: "Too many login attempts. Please wait a minute and try again.",
+        };
+
+      default:
+        return {
+          success: false,
+          message: "The current password is incorrect. Please log in again.",
+        };
+    }
+  } catch (e) {
+    error(`Password change error: ${describeError(e)}`);
+    return {
+      success: false,
+      message: "Password change failed. Please try again.",
+    };
+  }
+}
+
+/**
+ * Finishes a login that is waiting for a two-factor code.
+ *
+ * @param code - Code shown by the user's authenticator app
+ * @returns Promise<AuthResult> - Verification result with success status and message
+ */
+export async function verifyTotp(code: string): Promise<AuthResult> {
+  try {
+    const loginResult: LoginResult = await invoke<LoginResult>("verify_totp", {
+      code,
+    });
+
+    switch (loginResult) {
+      case "success":
+        return {
+          success: true,
+          message: "Login successful.",
+        };
+
+      case "too-many-attempts":
+        return {
+          success: false,
+          message: "Too many login attempts. Please wait a minute and try again.",
+        };
+
+      default:
+        return {
+          success: false,
+          message: "Incorrect code. Please try again.",
+        };
+    }
+  } catch (e) {
+    error(`Two-factor verification error: ${describeError(e)}`);
+    return {
+      success: false,
+      message: "The login timed out. Please log in again.",
+    };
+  }
+}
+
 // ==================== USER SESSION FUNCTIONS ====================
 
 /**
@@ -312,11 +622,25 @@ export async function getCurrentUser(): Promise<CurrentUser | null> {
     const currentUser: CurrentUser | null = await invoke("get_current_user");
     return currentUser;
   } catch (e) {
-    error(`Get current user error: ${e}`);
+    error(`Get current user error: ${describeError(e)}`);
     return null;
   }
 }
 
+/**
+ * Records activity by the current user, keeping their session from idling out.
+ *
+ * @returns Promise<boolean> - True if the user is still logged in, false if their session has ended or the operation fails
+ */
+export async function refreshSession(): Promise<boolean> {
+  try {
+    return await invoke<boolean>("refresh_session");
+  } catch (e) {
+    error(`Refresh session error: ${describeError(e)}`);
+    return false;
+  }
+}
+
 /**
  * Logs out the current user.
  *
@@ -327,7 +651,277 @@ export async function logoutUser(): Promise<boolean> {
     await invoke("log_out");
     return true;
   } catch (e) {
-    error(`Logout error: ${e}`);
+    error(`Logout error: ${describeError(e)}`);
+    return false;
+  }
+}
+
+// ==================== TWO-FACTOR AUTHENTICATION FUNCTIONS ====================
+
+/**
+ * Checks whether the logged-in staff member has two-factor authentication enabled.
+ *
+ * @returns Promise<boolean> - True if logging in requires a two-factor code. Returns false if the operation fails.
+ */
+export async function getTwoFactorStatus(): Promise<boolean> {
+  try {
+    return await invoke<boolean>("get_two_factor_status");
+  } catch (e) {
+    error(`Failed to get two-factor status: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Starts enrolling the logged-in staff member in two-factor authentication.
+ *
+ * @returns Promise<TotpEnrollment | null> - The secret and provisioning URI to add to an authenticator app. Returns null if the operation fails.
+ */
+export async function enrollTotp(): Promise<TotpEnrollment | null> {
+  try {
+    return await invoke<TotpEnrollment>("enroll_totp");
+  } catch (e) {
+    error(`Failed to enroll in two-factor authentication: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Finishes enrolling the logged-in staff member in two-factor authentication.
+ *
+ * @param code - Code shown by the authenticator app the secret was added to
+ * @returns Promise<boolean> - True if two-factor authentication is now enabled, false if the code was wrong or the operation fails
+ */
+export async function confirmTotpEnrollment(code: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("confirm_totp_enrollment", { code });
+  } catch (e) {
+    error(`Failed to confirm two-factor enrollment: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Turns off two-factor authentication for the logged-in staff member, or for
+ * another account when the user is an admin.
+ *
+ * @param username - Username of the account, or undefined for the logged-in user
+ * @returns Promise<boolean> - True if two-factor authentication was turned off, false if the operation fails
+ */
+export async function disableTotp(username?: string): Promise<boolean> {
+  try {
+    await invoke("disable_totp", { username: username ?? null });
+    return true;
+  } catch (e) {
+    error(`Failed to disable two-factor authentication: ${describeError(e)}`);
+    return false;
+  }
+}
+
+// ==================== USER MANAGEMENT FUNCTIONS ====================
+
+/**
+ * Lists every user account. Only available to admins.
+ *
+ * @returns Promise<UserAccount[]> - The accounts, ordered by username. Returns an empty array if the operation fails.
+ */
+export async function getUsers(): Promise<UserAccount[]> {
+  try {
+    return await invoke<UserAccount[]>("get_users");
+  } catch (e) {
+    error(`Failed to retrieve users: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Suspends a user account so it cannot log in. Only available to admins.
+ *
+ * @param username - Username of the account to suspend
+ * @returns Promise<boolean> - True if the account was suspended, false if the operation fails
+ */
+export async function suspendUser(username: string): Promise<boolean> {
+  try {
+    await invoke("suspend_user", { username });
+    return true;
+  } catch (e) {
+    error(`Failed to suspend ${username}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Reactivates a suspended user account. Only available to admins.
+ *
+ * @param username - Username of the account to reactivate
+ * @returns Promise<boolean> - True if the account was reactivated, false if the operation fails
+ */
+export async function reactivateUser(username: string): Promise<boolean> {
+  try {
+    await invoke("reactivate_user", { username });
+    return true;
+  } catch (e) {
+    error(`Failed to reactivate ${username}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Issues a one-time temporary password for a user who forgot theirs.
+ * Staff can reset customers' passwords, while admins can reset anyone's.
+ *
+ * @param username - Username of the account to reset
+ * @returns Promise<PasswordReset | null> - The temporary password to hand to the user. Returns null if the operation fails.
+ */
+export async function createPasswordReset(
+  username: string,
+): Promise<PasswordReset | null> {
+  try {
+    return await invoke<PasswordReset>("create_password_reset", { username });
+  } catch (e) {
+    error(`Failed to reset password of ${username}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Issues a single-use invite code for registering a staff account. Only available to admins.
+ *
+ * @param role - Role the code grants; staff or admin
+ * @returns Promise<InviteCode | null> - The code to hand to the new staff member. Returns null if the operation fails.
+ */
+export async function createInviteCode(
+  role: UserRole,
+): Promise<InviteCode | null> {
+  try {
+    return await invoke<InviteCode>("create_invite_code", { role });
+  } catch (e) {
+    error(`Failed to create invite code: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Creates an account on behalf of a user. Only available to admins.
+ * The user must choose their own password the first time they log in.
+ *
+ * @param username - Username for the new account
+ * @param password - Initial password to hand to the user
+ * @param role - Role to assign to the new user
+ * @returns Promise<boolean> - True if the account was created, false if the operation fails
+ */
+export async function createUser(
+  username: string,
+  password: string,
+  role: UserRole,
+): Promise<boolean> {
+  try {
+    await invoke("create_user", { username, password, role });
+    return true;
+  } catch (e) {
+    error(`Failed to create user ${username}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Changes the role of a user account. Only available to admins.
+ *
+ * @param username - Username of the account to change
+ * @param role - New role for the account
+ * @returns Promise<boolean> - True if the role was changed, false if the operation fails
+ */
+export async function changeUserRole(
+  username: string,
+  role: UserRole,
+): Promise<boolean> {
+  try {
+    await invoke("change_user_role", { username, role });
+    return true;
+  } catch (e) {
+    error(`Failed to change role of ${username}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Resets the password of a user account. Only available to admins.
+ * The user must choose their own password the next time they log in.
+ *
+ * @param username - Username of the account to change
+ * @param newPassword - New password for the account
+ * @returns Promise<boolean> - True if the password was reset, false if the operation fails
+ */
+export async function resetUserPassword(
+  username: string,
+  newPassword: string,
+): Promise<boolean> {
+  try {
+    await invoke("reset_user_password", { username, newPassword });
+    return true;
+  } catch (e) {
+    error(`Failed to reset password of ${username}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Retrieves how long sessions last. Only available to admins.
+ *
+ * @returns Promise<SessionPolicy | null> - The session lifetimes and idle timeout. Returns null if the operation fails.
+ */
+export async function getSessionPolicy(): Promise<SessionPolicy | null> {
+  try {
+    return await invoke<SessionPolicy>("get_session_policy");
+  } catch (e) {
+    error(`Failed to retrieve session policy: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Changes how long sessions last. Only available to admins.
+ *
+ * @param policy - The new session lifetimes and idle timeout
+ * @returns Promise<boolean> - True if the policy was saved, false if the operation fails
+ */
+export async function setSessionPolicy(
+  policy: SessionPolicy,
+): Promise<boolean> {
+  try {
+    await invoke("set_session_policy", { policy });
+    return true;
+  } catch (e) {
+    error(`Failed to update session policy: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Lists the sessions that are still active. Only available to admins.
+ *
+ * @returns Promise<SessionInfo[]> - The active sessions, most recently used first. Returns an empty array if the operation fails.
+ */
+export async function getSessions(): Promise<SessionInfo[]> {
+  try {
+    return await invoke<SessionInfo[]>("get_sessions");
+  } catch (e) {
+    error(`Failed to retrieve sessions: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Ends a session, such as one left open on another device. Only available to admins.
+ *
+ * @param sessionId - ID of the session to end
+ * @returns Promise<boolean> - True if the session was ended, false if not found or the operation fails
+ */
+export async function terminateSession(sessionId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("terminate_session", { sessionId });
+  } catch (e) {
+    error(`Failed to terminate session: ${describeError(e)}`);
     return false;
   }
 }
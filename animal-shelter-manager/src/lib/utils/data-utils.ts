@@ -6,8 +6,11 @@
  */
 
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { error } from "@tauri-apps/plugin-log";
 import { type FilterSelections } from "$lib/utils/filter-utils";
+import type { UserAccount } from "$lib/utils/authentication-utils";
+import type { AnimalSize } from "$lib/config/animal-options";
 
 // ==================== ENUMS ====================
 
@@ -19,8 +22,244 @@ export enum AnimalStatus {
   REQUESTED = "requested",
   /** Animal has been successfully adopted */
   ADOPTED = "adopted",
+  /** Animal is temporarily living with a foster caregiver */
+  FOSTERED = "fostered",
   /** Animal has passed away */
   PASSED_AWAY = "passed-away",
+  /** Animal has been transferred to a partner organization */
+  TRANSFERRED = "transferred",
+  /** Animal is living with an adopter for a trial period before the adoption is final */
+  TRIAL_ADOPTION = "trial-adoption",
+}
+
+/** Result of starting a foster placement */
+export enum StartFosterResult {
+  /** The placement was stored and the animal marked as fostered */
+  SUCCESS = "success",
+  /** No animal exists with the given ID */
+  ANIMAL_NOT_FOUND = "animal-not-found",
+  /** The animal is not available, so it cannot be placed in foster care */
+  ANIMAL_NOT_AVAILABLE = "animal-not-available",
+}
+
+/** Result of assigning an animal to a kennel */
+export enum AssignKennelResult {
+  /** The animal was assigned, leaving any kennel it was in before */
+  SUCCESS = "success",
+  /** No animal exists with the given ID */
+  ANIMAL_NOT_FOUND = "animal-not-found",
+  /** No kennel exists with the given ID */
+  KENNEL_NOT_FOUND = "kennel-not-found",
+  /** The kennel is restricted to a different species */
+  SPECIE_NOT_ALLOWED = "specie-not-allowed",
+  /** The kennel is already at capacity */
+  KENNEL_FULL = "kennel-full",
+}
+
+/** How an animal came into the shelter */
+export enum IntakeType {
+  /** Animal was found as a stray */
+  STRAY = "stray",
+  /** Animal was surrendered by its owner */
+  OWNER_SURRENDER = "owner-surrender",
+  /** Animal was transferred from another shelter or rescue */
+  TRANSFER = "transfer",
+  /** Animal was born while in the shelter's care */
+  BORN_IN_CARE = "born-in-care",
+}
+
+/** Direction of an animal transfer, seen from the organization */
+export enum TransferDirection {
+  /** Animal left a location, for another location or a partner organization */
+  OUTBOUND = "outbound",
+  /** Animal came back from a partner organization */
+  INBOUND = "inbound",
+}
+
+/** Result of recording that an animal passed away */
+export enum RecordDeathResult {
+  /** The record was stored and the animal marked as passed away */
+  SUCCESS = "success",
+  /** No animal exists with the given ID */
+  ANIMAL_NOT_FOUND = "animal-not-found",
+  /** The animal is no longer in the shelter's care, such as when it was adopted or transferred */
+  ANIMAL_NOT_IN_CARE = "animal-not-in-care",
+  /** The animal has already been recorded as passed away */
+  ALREADY_DECEASED = "already-deceased",
+}
+
+/** Kind of outside contact the shelter works with */
+export enum ContactKind {
+  /** A veterinarian or veterinary clinic */
+  VETERINARIAN = "veterinarian",
+  /** A rescue or another shelter that takes in animals */
+  RESCUE = "rescue",
+  /** Someone who moves animals between sites and organizations */
+  TRANSPORT_PARTNER = "transport-partner",
+  /** Someone who gives money to the shelter */
+  DONOR = "donor",
+  /** Anyone else */
+  OTHER = "other",
+}
+
+/** What money was spent on */
+export enum ExpenseCategory {
+  /** Food and treats */
+  FOOD = "food",
+  /** Veterinary care and medicine */
+  VETERINARY = "veterinary",
+  /** Litter, bedding, toys and other supplies */
+  SUPPLIES = "supplies",
+  /** Rent, utilities and repairs of the shelter's buildings */
+  FACILITIES = "facilities",
+  /** Fuel and vehicles used to move animals */
+  TRANSPORT = "transport",
+  /** Anything else */
+  OTHER = "other",
+}
+
+/** Outcome of adjusting the stock of an inventory item */
+export enum AdjustStockStatus {
+  /** The stock was adjusted */
+  ADJUSTED = "adjusted",
+  /** No item exists with the given ID */
+  NOT_FOUND = "not-found",
+  /** The change would take the stock below zero */
+  INSUFFICIENT_STOCK = "insufficient-stock",
+}
+
+/** Where a meet-and-greet appointment stands */
+export enum AppointmentStatus {
+  /** Booked by the customer, waiting for staff to confirm it */
+  REQUESTED = "requested",
+  /** Confirmed by staff */
+  CONFIRMED = "confirmed",
+  /** Cancelled by the customer or staff */
+  CANCELLED = "cancelled",
+}
+
+/** Result of booking a meet-and-greet appointment */
+export enum RequestAppointmentResult {
+  /** The appointment was booked and waits for staff to confirm it */
+  REQUESTED = "requested",
+  /** No animal exists with the given ID */
+  ANIMAL_NOT_FOUND = "animal-not-found",
+  /** The animal is not available for adoption */
+  ANIMAL_NOT_AVAILABLE = "animal-not-available",
+  /** The animal or the customer already has an appointment overlapping the slot */
+  SLOT_TAKEN = "slot-taken",
+}
+
+/** Result of confirming a meet-and-greet appointment */
+export enum ConfirmAppointmentResult {
+  /** The appointment was confirmed and the customer notified */
+  CONFIRMED = "confirmed",
+  /** No appointment exists with the given ID */
+  NOT_FOUND = "not-found",
+  /** The appointment was already confirmed or has been cancelled */
+  NOT_REQUESTED = "not-requested",
+}
+
+/** Result of cancelling a meet-and-greet appointment */
+export enum CancelAppointmentResult {
+  /** The appointment was cancelled */
+  CANCELLED = "cancelled",
+  /** No appointment exists with the given ID, or it belongs to another customer */
+  NOT_FOUND = "not-found",
+  /** The appointment has already been cancelled */
+  ALREADY_CANCELLED = "already-cancelled",
+}
+
+/** Result of booking a spay or neuter surgery */
+export enum ScheduleSurgeryResult {
+  /** The surgery was booked */
+  SCHEDULED = "scheduled",
+  /** No animal exists with the given ID */
+  ANIMAL_NOT_FOUND = "animal-not-found",
+  /** The animal is already neutered */
+  ALREADY_NEUTERED = "already-neutered",
+  /** The animal already has a surgery that has not been performed yet */
+  ALREADY_SCHEDULED = "already-scheduled",
+}
+
+/** Outcome of recording that a spay or neuter surgery was performed */
+export enum CompleteSurgeryStatus {
+  /** The surgery was recorded and the animal marked as neutered */
+  COMPLETED = "completed",
+  /** No surgery exists with the given ID */
+  NOT_FOUND = "not-found",
+  /** The surgery was already recorded as performed */
+  ALREADY_COMPLETED = "already-completed",
+}
+
+/** What a referee said about an applicant */
+export enum ReferenceOutcome {
+  /** The referee has not been reached yet */
+  PENDING = "pending",
+  /** The referee recommended the applicant */
+  POSITIVE = "positive",
+  /** The referee raised concerns about the applicant */
+  NEGATIVE = "negative",
+  /** The referee could not be reached */
+  UNREACHABLE = "unreachable",
+}
+
+/** Result of recording an interview or reference check for an adoption request */
+export enum RecordCheckResult {
+  /** The interview or reference check was recorded */
+  RECORDED = "recorded",
+  /** No adoption request exists with the given ID */
+  REQUEST_NOT_FOUND = "request-not-found",
+}
+
+/** Result of charging a fee for an adoption request */
+export enum RecordPaymentResult {
+  /** The fee was recorded */
+  RECORDED = "recorded",
+  /** No adoption request exists with the given ID */
+  REQUEST_NOT_FOUND = "request-not-found",
+}
+
+/** Result of recording a transfer */
+export enum RecordTransferResult {
+  /** The transfer was stored and the animal's status and location updated */
+  SUCCESS = "success",
+  /** No animal exists with the given ID */
+  ANIMAL_NOT_FOUND = "animal-not-found",
+  /** The animal's status does not allow this transfer */
+  ANIMAL_NOT_AVAILABLE = "animal-not-available",
+  /** No location exists with the given destination ID */
+  LOCATION_NOT_FOUND = "location-not-found",
+}
+
+/** Result of processing the return of an adopted animal */
+export enum ProcessReturnResult {
+  /** The return was recorded and the animal is available again */
+  SUCCESS = "success",
+  /** No adoption request exists with the given ID */
+  REQUEST_NOT_FOUND = "request-not-found",
+  /** The adoption request was never approved nor put on trial, so there is nothing to return */
+  REQUEST_NOT_APPROVED = "request-not-approved",
+  /** The adoption has already been returned */
+  ALREADY_RETURNED = "already-returned",
+}
+
+/** Category of a staff note about an animal */
+export enum NoteCategory {
+  /** Observations about the animal's temperament and behavior */
+  BEHAVIOR = "behavior",
+  /** Observations about the animal's health and treatment */
+  MEDICAL = "medical",
+  /** Anything else worth recording */
+  GENERAL = "general",
+}
+
+/** Result of joining the waitlist */
+export enum JoinWaitlistResult {
+  /** The customer was added to the waitlist */
+  SUCCESS = "success",
+  /** The customer is already waiting for the same species and breed */
+  ALREADY_JOINED = "already-joined",
 }
 
 /** Status of an adoption request in the system */
@@ -31,10 +270,291 @@ export enum RequestStatus {
   REJECTED = "rejected",
   /** Request has been approved */
   APPROVED = "approved",
+  /** Request has been approved for a trial period that has not been finalized yet */
+  TRIAL_ADOPTION = "trial-adoption",
+}
+
+/** Result of submitting a new adoption request */
+export enum CreateRequestResult {
+  /** The request was stored successfully */
+  SUCCESS = "success",
+  /** The user already has a pending request for the same animal */
+  DUPLICATE_PENDING_REQUEST = "duplicate-pending-request",
+}
+
+/** Outcome of approving an adoption request */
+export enum ApproveRequestStatus {
+  /** The request was approved and the animal marked as adopted */
+  APPROVED = "approved",
+  /** The request was approved for a trial period and the animal is on trial with the adopter */
+  TRIAL_STARTED = "trial-started",
+  /** No adoption request exists with the given ID */
+  NOT_FOUND = "not-found",
+  /** The request has already been approved or rejected */
+  NOT_PENDING = "not-pending",
+  /** The animal is a stray still within the hold period required before adoption */
+  ON_STRAY_HOLD = "on-stray-hold",
+  /** The animal has bonded partners the adopter is not also adopting */
+  BONDED_PAIR_SPLIT = "bonded-pair-split",
+  /** A phone interview must be recorded before the request can be approved */
+  INTERVIEW_REQUIRED = "interview-required",
+  /** A referee must give a positive reference before the request can be approved */
+  REFERENCE_CHECK_REQUIRED = "reference-check-required",
+}
+
+/** Result of turning a trial adoption into a final adoption */
+export enum FinalizeTrialResult {
+  /** The adoption is final and the animal marked as adopted */
+  FINALIZED = "finalized",
+  /** No adoption request exists with the given ID */
+  NOT_FOUND = "not-found",
+  /** The adoption request is not on trial */
+  NOT_IN_TRIAL = "not-in-trial",
+}
+
+/** Outcome of updating an animal */
+export enum UpdateAnimalStatus {
+  /** The animal was updated */
+  UPDATED = "updated",
+  /** No animal exists with the given ID */
+  NOT_FOUND = "not-found",
+  /** The animal was changed by someone else since it was read */
+  CONFLICT = "conflict",
+}
+
+/** Review status of a request to erase a customer's personal data */
+export enum ErasureRequestStatus {
+  /** Waiting for an admin to review it */
+  PENDING = "pending",
+  /** Approved by an admin; the customer's data has been erased */
+  APPROVED = "approved",
+  /** Rejected by an admin; nothing was erased */
+  REJECTED = "rejected",
+}
+
+/** Outcome of asking for a customer's data to be erased */
+export enum CreateErasureStatus {
+  /** The request was stored and waits for an admin */
+  CREATED = "created",
+  /** The customer already has a request waiting for review */
+  ALREADY_PENDING = "already-pending",
+}
+
+/** Outcome of reviewing an erasure request */
+export enum ReviewErasureStatus {
+  /** The request was approved or rejected */
+  REVIEWED = "reviewed",
+  /** No erasure request exists with the given ID */
+  NOT_FOUND = "not-found",
+  /** The request was already reviewed */
+  ALREADY_REVIEWED = "already-reviewed",
+}
+
+/** Kind of record held in the trash */
+export enum TrashItemType {
+  /** A deleted animal, along with the adoption requests deleted with it */
+  ANIMAL = "animal",
+  /** A deleted adoption request */
+  ADOPTION_REQUEST = "adoption-request",
+}
+
+/** Event that can be sent to webhooks */
+export enum WebhookEvent {
+  /** A new animal was admitted */
+  ANIMAL_CREATED = "animal-created",
+  /** An animal was adopted */
+  ANIMAL_ADOPTED = "animal-adopted",
+  /** An adoption request was filed or changed status */
+  REQUEST_STATUS_CHANGED = "request-status-changed",
+}
+
+/** Delivery status of an event sent to a webhook */
+export enum WebhookDeliveryStatus {
+  /** The event has not been delivered yet and will be attempted again */
+  PENDING = "pending",
+  /** The webhook accepted the event */
+  DELIVERED = "delivered",
+  /** Every attempt failed and the event was given up on */
+  FAILED = "failed",
+}
+
+/** Send status of an email to an applicant */
+export enum EmailStatus {
+  /** The email is waiting to be sent */
+  PENDING = "pending",
+  /** The SMTP server accepted the email */
+  SENT = "sent",
+  /** The email could not be sent; it can be resent by staff */
+  FAILED = "failed",
+}
+
+/** Events emitted by the backend when records change, carrying the ID of the record */
+export enum DataChangeEvent {
+  /** An animal was admitted or restored from the trash */
+  ANIMAL_CREATED = "animal-created",
+  /** An animal was edited, adopted, fostered or transferred */
+  ANIMAL_UPDATED = "animal-updated",
+  /** An animal was deleted */
+  ANIMAL_DELETED = "animal-deleted",
+  /** An adoption request was submitted or restored from the trash */
+  REQUEST_CREATED = "request-created",
+  /** An adoption request was edited, approved or returned */
+  REQUEST_UPDATED = "request-updated",
+  /** An adoption request was deleted */
+  REQUEST_DELETED = "request-deleted",
+}
+
+/** What an uploaded file is going to be used for, which decides the formats it may be in */
+export enum UploadKind {
+  /** A photo shown for an animal; PNG, JPEG, GIF or WebP */
+  ANIMAL_PHOTO = "animal-photo",
+  /** A document kept on record; PDF or any of the photo formats */
+  DOCUMENT = "document",
+}
+
+/** A setting that can be changed by an admin */
+export enum SettingKey {
+  /** Name of the shelter, shown in the application and on reports */
+  SHELTER_NAME = "shelter-name",
+  /** Three-letter code of the currency amounts are given in, such as USD */
+  DEFAULT_CURRENCY = "default-currency",
+  /** Language of the error summaries and reports written by the backend */
+  LOCALE = "locale",
+  /** Least severe kind of message written to the log */
+  LOG_LEVEL = "log-level",
+  /** Hours between automatic backups, or 0 to turn them off */
+  BACKUP_INTERVAL_HOURS = "backup-interval-hours",
+  /** Days between automatic database maintenance, or 0 to turn it off */
+  MAINTENANCE_INTERVAL_DAYS = "maintenance-interval-days",
+  /** Largest file that can be uploaded, in megabytes */
+  MAX_UPLOAD_MEGABYTES = "max-upload-megabytes",
+  /** Host name of the SMTP server, or empty to turn email off */
+  SMTP_HOST = "smtp-host",
+  /** Port of the SMTP server; 465 uses TLS from the start, any other STARTTLS */
+  SMTP_PORT = "smtp-port",
+  /** User name for the SMTP server, or empty if it does not require one */
+  SMTP_USERNAME = "smtp-username",
+  /** Password for the SMTP server; shown masked once set */
+  SMTP_PASSWORD = "smtp-password",
+  /** Address emails are sent from, such as `Happy Paws <adopt@example.org>` */
+  EMAIL_SENDER = "email-sender",
+  /** Body of the approval email; {name}, {animal} and {shelter} are filled in */
+  APPROVED_EMAIL_TEMPLATE = "approved-email-template",
+  /** Body of the rejection email, with the same placeholders */
+  REJECTED_EMAIL_TEMPLATE = "rejected-email-template",
+  /** Days ahead of a vaccination's due date staff are reminded, or 0 to turn off */
+  VACCINATION_REMINDER_DAYS = "vaccination-reminder-days",
+  /** Days an animal waits at the shelter before staff are reminded, or 0 to turn off */
+  LENGTH_OF_STAY_REMINDER_DAYS = "length-of-stay-reminder-days",
+  /** Days a request stays pending before staff are reminded, or 0 to turn off */
+  PENDING_REQUEST_REMINDER_DAYS = "pending-request-reminder-days",
+  /** Hours ahead of a confirmed appointment the customer is reminded, or 0 to turn off */
+  APPOINTMENT_REMINDER_HOURS = "appointment-reminder-hours",
+  /** Whether animals with a staff-only behavior or medical note are left out of the listings feed */
+  LISTINGS_EXCLUDE_FLAGGED = "listings-exclude-flagged",
+  /** Screening points for each person in an applicant's household */
+  SCREENING_POINTS_PER_PERSON = "screening-points-per-person",
+  /** Screening points for each child in an applicant's household */
+  SCREENING_POINTS_PER_CHILD = "screening-points-per-child",
+  /** Screening points by annual income, as bands such as `20000:10, 50000:20` */
+  SCREENING_INCOME_BANDS = "screening-income-bands",
+  /** Screening points for each animal an applicant adopted before and returned */
+  SCREENING_POINTS_PER_RETURN = "screening-points-per-return",
+  /** Days a stray is held after intake before it can be adopted, or 0 for no hold */
+  STRAY_HOLD_DAYS = "stray-hold-days",
+  /** Whether a request can only be approved once a phone interview was recorded */
+  REQUIRE_INTERVIEW = "require-interview",
+  /** Whether a request can only be approved once a referee gave a positive reference */
+  REQUIRE_REFERENCE_CHECK = "require-reference-check",
+  /** Address of the S3-compatible storage backups are uploaded to, or empty to keep them on site */
+  CLOUD_BACKUP_ENDPOINT = "cloud-backup-endpoint",
+  /** Region of the storage, used to sign requests */
+  CLOUD_BACKUP_REGION = "cloud-backup-region",
+  /** Bucket backups are uploaded to, or empty to keep them on site */
+  CLOUD_BACKUP_BUCKET = "cloud-backup-bucket",
+  /** Access key ID for the storage */
+  CLOUD_BACKUP_ACCESS_KEY_ID = "cloud-backup-access-key-id",
+  /** Secret access key for the storage; shown masked once set */
+  CLOUD_BACKUP_SECRET_ACCESS_KEY = "cloud-backup-secret-access-key",
+  /** Whether the HTTP API for the public website is served; takes effect at the next start */
+  HTTP_API_ENABLED = "http-api-enabled",
+  /** Address the HTTP API listens on, such as `0.0.0.0:8080`; takes effect at the next start */
+  HTTP_API_ADDRESS = "http-api-address",
+}
+
+/** Where a background job is in its lifecycle */
+export enum JobStatus {
+  /** Waiting for the jobs queued before it to finish */
+  QUEUED = "queued",
+  /** Being worked on */
+  RUNNING = "running",
+  /** Finished, with its result */
+  COMPLETED = "completed",
+  /** Stopped with an error */
+  FAILED = "failed",
+}
+
+/** The kind of work a background job does */
+export enum JobKind {
+  REPORT_EXPORT = "report-export",
+  ANIMALS_EXPORT = "animals-export",
+  ADOPTION_REQUESTS_EXPORT = "adoption-requests-export",
+  CHANGES_EXPORT = "changes-export",
+  CHANGES_IMPORT = "changes-import",
+  ANIMALS_IMPORT = "animals-import",
+}
+
+/** File format a report is exported in */
+export enum ReportFormat {
+  PDF = "pdf",
+  XLSX = "xlsx",
+}
+
+/** File format the public listings feed is written in */
+export enum ListingsFormat {
+  JSON = "json",
+  XML = "xml",
+}
+
+/** Language the backend writes error summaries and reports in */
+export enum Locale {
+  EN = "en",
+  ES = "es",
+}
+
+/** Least severe kind of message written to the log */
+export enum LogLevel {
+  ERROR = "error",
+  WARN = "warn",
+  INFO = "info",
+  DEBUG = "debug",
+  TRACE = "trace",
+}
+
+/** Kind of error returned by a failed command */
+export enum CommandErrorKind {
+  NOT_FOUND = "not-found",
+  VALIDATION_FAILED = "validation-failed",
+  PERMISSION_DENIED = "permission-denied",
+  CONFLICT = "conflict",
+  IO = "io",
+  DATABASE = "database",
 }
 
 // ==================== INTERFACES ====================
 
+/** The current value of a setting */
+export interface Setting {
+  /** Which setting this is */
+  key: SettingKey;
+  /** The value of the setting */
+  value: string;
+  /** Whether the value is the default because the setting was never changed */
+  isDefault: boolean;
+  /** Timestamp of the last change, null if the setting was never changed */
+  updatedTimestamp: number | null;
+}
+
 /** Represents an animal in the shelter system */
 export interface Animal {
   /** Unique identifier for the animal */
@@ -63,6 +583,62 @@ export interface Animal {
   appearance: string;
   /** Bio & Characteristics of the animal */
   bio: string;
+  /** Version of the record, incremented on every update to detect concurrent edits */
+  version: number;
+  /** Number of the animal's identification microchip, unique across animals */
+  microchipNumber: string | null;
+  /** ID of the location housing the animal (null if the organization has a single site) */
+  locationId?: string | null;
+  /** Whether the animal gets along with children (null if unknown) */
+  goodWithKids?: boolean | null;
+  /** Whether the animal gets along with dogs (null if unknown) */
+  goodWithDogs?: boolean | null;
+  /** Whether the animal gets along with cats (null if unknown) */
+  goodWithCats?: boolean | null;
+  /** Whether the animal has special needs such as medication or a disability (null if unknown) */
+  specialNeeds?: boolean | null;
+  /** Size category of the animal (null if not recorded) */
+  size?: AnimalSize | null;
+  /** Main coat or skin color of the animal, such as "Brown" (null if not recorded) */
+  color?: string | null;
+}
+
+/** A problem with one field of a submitted animal or adoption request */
+export interface FieldError {
+  /** Name of the invalid field, matching the property name */
+  field: string;
+  /** Description of what is wrong with the field */
+  message: string;
+}
+
+/** Error returned by a failed command */
+export interface CommandError {
+  /** Kind of error, for reacting to it without parsing the message */
+  kind: CommandErrorKind;
+  /** Key of the summary, the same in every locale */
+  key: string;
+  /** Summary of the kind of error, in the locale chosen in the settings */
+  summary: string;
+  /** Description of what went wrong */
+  message: string;
+  /** The invalid fields, when the kind is validation-failed */
+  fieldErrors?: FieldError[];
+}
+
+/** A piece of text written by the backend, with its key */
+export interface Message {
+  /** Key of the text, such as "error-not-found" */
+  key: string;
+  /** The text in the locale chosen in the settings, placeholders such as {year} left in */
+  text: string;
+}
+
+/** Result of updating an animal */
+export interface UpdateAnimalResult {
+  /** Outcome of the update */
+  status: UpdateAnimalStatus;
+  /** The updated animal, or the current animal when there is a conflict */
+  animal?: Animal;
 }
 
 /** Simplified animal information for listing views */
@@ -79,298 +655,4458 @@ export interface AnimalSummary {
   sex: string;
   /** Timestamp when the animal was admitted to the shelter */
   admissionTimestamp: number;
-  /** Path to the animal's image file */
-  imagePath?: string;
-  /** Current status of the animal */
+  /** Path to the animal's image file, shown as the thumbnail in list views (null if none) */
+  imagePath?: string | null;
+  /** Current status of the animal, shown as the availability badge in list views */
   status: AnimalStatus;
 }
 
-/** Represents an adoption request in the system */
-export interface AdoptionRequest {
-  /** Unique identifier for the adoption request */
-  id: string;
-  /** ID of the animal being requested for adoption */
-  animalId: string;
-  /** Username of the user who made the request */
-  username: string;
-  /** Full name of the person requesting adoption */
-  name: string;
-  /** Email address of the requester */
-  email: string;
-  /** Telephone number of the requester */
-  telNumber: string;
-  /** Address of the requester */
-  address: string;
-  /** Occupation of the requester */
-  occupation: string;
-  /** Annual income of the requester */
-  annualIncome: string;
-  /** Number of people in the household */
-  numPeople: number;
-  /** Number of children in the household */
-  numChildren: number;
-  /** Timestamp when the request was submitted */
-  requestTimestamp: number;
-  /** Timestamp when the adoption was completed (0 if not completed) */
-  adoptionTimestamp: number;
-  /** Current status of the request */
-  status: RequestStatus;
-  /** Country of the requester */
-  country: string;
+/** An available animal that has been in the shelter's care longer than a threshold */
+export interface LongStayAnimal extends AnimalSummary {
+  /** Whole days since the animal was admitted */
+  daysInCare: number;
+}
+
+/** An animal together with the related records shown on its detail page */
+export interface AnimalDetail {
+  /** The animal itself */
+  animal: Animal;
+  /** All adoption requests for the animal, redacted for viewers who may not see them */
+  adoptionRequests: AdoptionRequestView[];
+  /** The most recent medical note about the animal, if any */
+  latestMedicalNote: AnimalNote | null;
+  /** Age of the animal in months (null if the birth date is unknown) */
+  ageMonths: number | null;
+  /** How the animal passed away, for staff viewers of an animal that has passed away */
+  deceasedRecord: DeceasedRecord | null;
+}
+
+/** Contact details a user saves once and reuses for their adoption requests */
+export interface UserProfile {
+  /** Username of the user the profile belongs to */
+  username: string;
+  /** Full name of the user */
+  fullName: string;
+  /** Email address of the user */
+  email: string;
+  /** Telephone number of the user */
+  telNumber: string;
+  /** Street address of the user */
+  address: string;
+  /** Country of the user */
+  country: string;
+  /** Timestamp when the profile was last updated */
+  updatedTimestamp: number;
+}
+
+/** A customer's request to have their personal data erased */
+export interface ErasureRequest {
+  /** Unique identifier for the erasure request */
+  id: string;
+  /** Username of the customer whose data is to be erased */
+  username: string;
+  /** Review status of the request */
+  status: ErasureRequestStatus;
+  /** Timestamp when the customer made the request */
+  requestedTimestamp: number;
+  /** Username of the admin who reviewed the request, if it has been reviewed */
+  reviewedBy: string | null;
+  /** Timestamp when the request was reviewed, if it has been */
+  reviewedTimestamp: number | null;
+}
+
+/** Result of asking for a customer's data to be erased */
+export interface CreateErasureResult {
+  /** Outcome of the request */
+  status: CreateErasureStatus;
+  /** The stored request, or the one already waiting for review */
+  request: ErasureRequest;
+}
+
+/** Result of reviewing an erasure request */
+export interface ReviewErasureResult {
+  /** Outcome of the review */
+  status: ReviewErasureStatus;
+  /** The reviewed request, or the request as it stands if it was already reviewed */
+  request?: ErasureRequest;
+}
+
+/**
+ * A person staff should not let adopt. Patterns match whole fields case-insensitively,
+ * with `*` matching any run of characters.
+ */
+export interface BannedAdopter {
+  /** Unique identifier for the entry */
+  id: string;
+  /** Pattern matched against the requester's full name */
+  namePattern: string | null;
+  /** Pattern matched against the requester's email address */
+  emailPattern: string | null;
+  /** Pattern matched against the requester's telephone number, by digits only */
+  telNumberPattern: string | null;
+  /** Why the person was banned */
+  reason: string;
+  /** Username of the staff member who added the entry */
+  addedBy: string;
+  /** Timestamp when the entry was added */
+  createdTimestamp: number;
+}
+
+/** The record an uploaded file belongs to, which decides the directory it is stored in */
+export interface StorageOwner {
+  /** Kind of record */
+  type: "animal" | "request";
+  /** ID of the record */
+  id: string;
+}
+
+/** Number of files and bytes in one category of stored files */
+export interface StorageCategory {
+  /** Number of files in the category */
+  fileCount: number;
+  /** Total size of the files, in bytes */
+  bytes: number;
+}
+
+/** Size of a database file */
+export interface DatabaseFileSize {
+  /** Name of the file, such as `animal_shelter.db` */
+  name: string;
+  /** Size of the file, in bytes */
+  bytes: number;
+}
+
+/** Disk space used by the application's files */
+export interface StorageStats {
+  /** Total size of every file, in bytes */
+  totalBytes: number;
+  /** Uploaded images */
+  animalPhotos: StorageCategory;
+  /** Uploaded files that are not images */
+  documents: StorageCategory;
+  /** Files in the backup directory */
+  backups: StorageCategory;
+  /** Database files, with their journals, largest first */
+  databases: DatabaseFileSize[];
+  /** Any other files */
+  other: StorageCategory;
+}
+
+/** Result of checking stored files against their recorded checksums */
+export interface FileVerificationReport {
+  /** Number of files that were checked */
+  checkedCount: number;
+  /** Files whose checksum was recorded but that no longer exist */
+  missing: string[];
+  /** Files whose size or hash no longer matches the recorded checksum */
+  corrupted: string[];
+  /** Uploads without a recorded checksum, such as those stored by older versions */
+  unrecorded: string[];
+}
+
+/** Which services have been started */
+export interface ServiceStatus {
+  /** Whether the file service is running */
+  fileService: boolean;
+  /** Whether the database service is running */
+  databaseService: boolean;
+  /** Whether the authentication service is running */
+  authenticationService: boolean;
+  /** Whether the settings service is running */
+  settingsService: boolean;
+  /** Whether the background job queue is running */
+  jobQueue: boolean;
+}
+
+/** The state of the application, with warnings about anything that needs attention */
+export interface HealthReport {
+  /** Which services have been started */
+  services: ServiceStatus;
+  /** Whether the database answered a query */
+  databaseConnected: boolean;
+  /** Version of the data migrations applied to the database, null if it could not be read */
+  schemaVersion: number | null;
+  /** Version of the data migrations this version of the application applies */
+  expectedSchemaVersion: number;
+  /** Free disk space where files are stored, in bytes, null if it could not be read */
+  availableDiskBytes: number | null;
+  /** Timestamp of the most recent backup, null if there are none */
+  lastBackupTimestamp: number | null;
+  /** Hours between backups set in the settings, null if the settings could not be read */
+  backupIntervalHours: number | null;
+  /** Problems found, in the locale chosen in the settings; empty if all is well */
+  warnings: Message[];
+  /** Timestamp of the check */
+  checkedTimestamp: number;
+}
+
+/** Represents an adoption request in the system */
+export interface AdoptionRequest {
+  /** Unique identifier for the adoption request */
+  id: string;
+  /** ID of the animal being requested for adoption */
+  animalId: string;
+  /** Username of the user who made the request */
+  username: string;
+  /** Full name of the person requesting adoption */
+  name: string;
+  /** Email address of the requester */
+  email: string;
+  /** Telephone number of the requester */
+  telNumber: string;
+  /** Address of the requester */
+  address: string;
+  /** Occupation of the requester */
+  occupation: string;
+  /** Annual income of the requester */
+  annualIncome: string;
+  /** Number of people in the household */
+  numPeople: number;
+  /** Number of children in the household */
+  numChildren: number;
+  /** Timestamp when the request was submitted */
+  requestTimestamp: number;
+  /** Timestamp when the adoption was completed (0 if not completed) */
+  adoptionTimestamp: number;
+  /** Current status of the request */
+  status: RequestStatus;
+  /** Country of the requester */
+  country: string;
+  /** Second person applying together with the requester, such as a partner (null if applying alone) */
+  coApplicant?: CoApplicant | null;
+}
+
+/** Second person applying to adopt together with the requester, named on the adoption contract */
+export interface CoApplicant {
+  /** Full name of the co-applicant */
+  name: string;
+  /** Relationship of the co-applicant to the requester (e.g., "Spouse") */
+  relationship: string;
+  /** Email address or telephone number of the co-applicant */
+  contact: string;
+}
+
+/**
+ * An adoption request as shown to users other than staff and the requester, without
+ * the requester's name, contact details, household or income
+ */
+export interface RedactedAdoptionRequest {
+  /** Unique identifier for the adoption request */
+  id: string;
+  /** ID of the animal being requested for adoption */
+  animalId: string;
+  /** Timestamp when the request was submitted */
+  requestTimestamp: number;
+  /** Timestamp when the adoption was completed (0 if not completed) */
+  adoptionTimestamp: number;
+  /** Current status of the request */
+  status: RequestStatus;
+}
+
+/** An adoption request as the current user may see it */
+export type AdoptionRequestView = AdoptionRequest | RedactedAdoptionRequest;
+
+/**
+ * Checks whether an adoption request includes the requester's personal details.
+ *
+ * @param request - The adoption request as the current user may see it
+ * @returns boolean - True if every field of the request is present
+ */
+export function isFullAdoptionRequest(
+  request: AdoptionRequestView,
+): request is AdoptionRequest {
+  return "username" in request;
+}
+
+/** Simplified adoption request information for listing views */
+export interface AdoptionRequestSummary {
+  /** Unique identifier for the adoption request */
+  id: string;
+  /** ID of the animal being requested for adoption */
+  animalId: string;
+  /** Name of the animal being requested */
+  animalName: string;
+  /** Species of the animal being requested */
+  animalSpecie: string;
+  /** Username of the user who made the request */
+  username: string;
+  /** Full name of the person requesting adoption */
+  name: string;
+  /** Email address of the requester */
+  email: string;
+  /** Timestamp when the request was submitted */
+  requestTimestamp: number;
+  /** Current status of the request */
+  status: RequestStatus;
+  /** Screening score of the request, null until it is scored */
+  screeningScore: number | null;
+}
+
+/** An adoption request together with summary information about the requested animal */
+export interface AnimalAdoptionRequest {
+  /** Summary of the requested animal */
+  animal: AnimalSummary;
+  /** The adoption request */
+  request: AdoptionRequest;
+}
+
+/** Criteria for listing adoption requests; omitted fields do not filter */
+export interface AdoptionRequestFilters {
+  /** Only include requests with this status */
+  status?: RequestStatus;
+  /** Only include requests submitted at or after this timestamp */
+  fromTimestamp?: number;
+  /** Only include requests submitted at or before this timestamp */
+  toTimestamp?: number;
+  /** Only include requests for animals of this species */
+  specie?: string;
+}
+
+/** Kind of record the global search looks through */
+export enum SearchScope {
+  /** Animals, by name, breed and bio */
+  ANIMALS = "animals",
+  /** Adoption requests, by applicant name, username and email */
+  REQUESTS = "requests",
+  /** User accounts, by username */
+  USERS = "users",
+}
+
+/** A record found by the global search, with how well it matched; higher scores are better */
+export type SearchHit<T> = T & { score: number };
+
+/** Records found by the global search, grouped by kind, best matches first */
+export interface SearchResults {
+  /** Animals found */
+  animals: SearchHit<AnimalSummary>[];
+  /** Adoption requests found */
+  requests: SearchHit<AdoptionRequestSummary>[];
+  /** User accounts found, for admins only */
+  users: SearchHit<UserAccount>[];
+}
+
+/** Something a staff member did, as recorded in the audit log */
+export enum AuditAction {
+  /** Added an animal */
+  ANIMAL_CREATED = "animal-created",
+  /** Edited an animal */
+  ANIMAL_UPDATED = "animal-updated",
+  /** Moved an animal to the trash */
+  ANIMAL_DELETED = "animal-deleted",
+  /** Approved an adoption request */
+  REQUEST_APPROVED = "request-approved",
+  /** Approved an adoption request for a trial period */
+  TRIAL_STARTED = "trial-started",
+  /** Turned a trial adoption into a final adoption */
+  TRIAL_FINALIZED = "trial-finalized",
+  /** Rejected an adoption request */
+  REQUEST_REJECTED = "request-rejected",
+  /** Edited an adoption request, other than rejecting it */
+  REQUEST_UPDATED = "request-updated",
+  /** Moved an adoption request to the trash */
+  REQUEST_DELETED = "request-deleted",
+  /** Took back an adopted animal */
+  RETURN_PROCESSED = "return-processed",
+  /** Added a note to an animal */
+  NOTE_ADDED = "note-added",
+  /** Recorded that an animal passed away */
+  DEATH_RECORDED = "death-recorded",
+  /** Recorded that an animal's spay or neuter surgery was performed */
+  SURGERY_COMPLETED = "surgery-completed",
+  /** Recorded a phone interview with the applicant of an adoption request */
+  INTERVIEW_RECORDED = "interview-recorded",
+  /** Recorded a reference check for an adoption request, or its outcome */
+  REFERENCE_CHECKED = "reference-checked",
+  /** Charged a fee for an adoption request */
+  PAYMENT_RECORDED = "payment-recorded",
+  /** Recorded that a fee charged for an adoption request was paid */
+  PAYMENT_RECEIVED = "payment-received",
+}
+
+/** An entry of the activity feed, recording who did what to which record */
+export interface ActivityFeedItem {
+  /** Unique identifier for the entry */
+  id: string;
+  /** Username of the staff member who acted */
+  username: string;
+  /** What they did */
+  action: AuditAction;
+  /** ID of the animal or adoption request they acted on */
+  recordId: string;
+  /** ID of the animal concerned, if any */
+  animalId: string | null;
+  /** Name of that animal when they acted, if it could be found */
+  animalName: string | null;
+  /** Timestamp when they acted */
+  timestamp: number;
+  /** What happened, such as "jane approved request r14 for Buddy" */
+  message: string;
+}
+
+/** A page of the activity feed, most recent first */
+export interface ActivityFeed {
+  /** The entries of the page */
+  items: ActivityFeedItem[];
+  /** Number of entries in the whole feed */
+  total: number;
+}
+
+/** Represents a placement of an animal with a foster caregiver */
+export interface FosterPlacement {
+  /** Unique identifier for the placement (empty to generate one) */
+  id: string;
+  /** ID of the animal being fostered */
+  animalId: string;
+  /** Full name of the foster caregiver */
+  caregiverName: string;
+  /** Email address of the foster caregiver */
+  caregiverEmail: string;
+  /** Telephone number of the foster caregiver */
+  caregiverTelNumber: string;
+  /** Timestamp when the placement started */
+  startTimestamp: number;
+  /** Timestamp when the placement is planned to end */
+  endTimestamp: number;
+  /** Timestamp when the animal actually returned from foster care (null while ongoing) */
+  endedTimestamp: number | null;
+}
+
+/** Result of approving an adoption request */
+export interface ApproveRequestResult {
+  /** The outcome of the approval */
+  status: ApproveRequestStatus;
+  /** IDs of the bonded animals that would be left behind (only for BONDED_PAIR_SPLIT) */
+  bondedAnimalIds?: string[];
+}
+
+/** Represents the trial period of an adoption, during which the adopter can still return the animal */
+export interface TrialAdoption {
+  /** Unique identifier for the trial */
+  id: string;
+  /** ID of the adoption request the trial belongs to */
+  adoptionRequestId: string;
+  /** ID of the animal on trial */
+  animalId: string;
+  /** Name of the animal on trial */
+  animalName: string;
+  /** Username of the adopter */
+  username: string;
+  /** Timestamp when the trial started */
+  startedTimestamp: number;
+  /** Timestamp when the trial is planned to end */
+  endTimestamp: number;
+}
+
+/** Represents an adopted animal being returned to the shelter */
+export interface AdoptionReturn {
+  /** Unique identifier for the return */
+  id: string;
+  /** ID of the approved adoption request the return undoes */
+  adoptionRequestId: string;
+  /** ID of the animal that was returned */
+  animalId: string;
+  /** Timestamp when the animal was returned */
+  returnTimestamp: number;
+  /** Why the animal was returned */
+  reason: string;
+}
+
+/** Represents a timestamped note written by a staff member about an animal */
+export interface AnimalNote {
+  /** Unique identifier for the note */
+  id: string;
+  /** ID of the animal the note is about */
+  animalId: string;
+  /** Username of the staff member who wrote the note */
+  authorUsername: string;
+  /** Category of the note */
+  category: NoteCategory;
+  /** Text of the note */
+  content: string;
+  /** Whether the note is for staff only and hidden from customers */
+  internal: boolean;
+  /** Timestamp when the note was written */
+  createdTimestamp: number;
+}
+
+/** Represents a vaccination given to an animal */
+export interface Vaccination {
+  /** Unique identifier for the vaccination */
+  id: string;
+  /** ID of the animal that was vaccinated */
+  animalId: string;
+  /** Name of the vaccine, such as Rabies */
+  vaccine: string;
+  /** Timestamp when the vaccination was given */
+  administeredTimestamp: number;
+  /** Timestamp when the next dose is due, if one is needed */
+  dueTimestamp: number | null;
+  /** Username of the staff member who recorded the vaccination */
+  recordedBy: string;
+  /** ID of the veterinarian who gave the vaccine, if known */
+  contactId?: string | null;
+}
+
+/** Represents the record of an animal passing away or being euthanized while in the shelter's care */
+export interface DeceasedRecord {
+  /** Unique identifier for the record (empty to generate one) */
+  id: string;
+  /** ID of the animal that passed away */
+  animalId: string;
+  /** Timestamp when the animal passed away */
+  deathTimestamp: number;
+  /** What the animal died of, or why it was euthanized */
+  cause: string;
+  /** Name of the person who authorized the euthanasia or confirmed the death */
+  authorizedBy: string;
+  /** Any further details worth keeping */
+  notes: string;
+}
+
+/** A supply kept in stock, such as food, litter or medicine */
+export interface InventoryItem {
+  /** Unique identifier for the item (empty to generate one) */
+  id: string;
+  /** Name of the item, unique within the shelter */
+  name: string;
+  /** Unit the quantity is counted in, such as "kg" or "boxes" */
+  unit: string;
+  /** Quantity currently in stock */
+  quantityOnHand: number;
+  /** Quantity at or below which the item should be reordered */
+  reorderThreshold: number;
+}
+
+/** A change to the stock of an inventory item */
+export interface InventoryAdjustment {
+  /** Unique identifier for the adjustment */
+  id: string;
+  /** ID of the adjusted item */
+  itemId: string;
+  /** Quantity added to the stock, negative when stock was used or discarded */
+  quantityChange: number;
+  /** Why the stock changed, such as "Delivery" or "Expired" */
+  reason: string;
+  /** Username of the staff member who adjusted the stock */
+  username: string;
+  /** Timestamp when the stock was adjusted */
+  timestamp: number;
+}
+
+/** Result of adjusting the stock of an inventory item */
+export interface AdjustStockResult {
+  /** Outcome of the adjustment */
+  status: AdjustStockStatus;
+  /** The item with its new quantity, or with its current quantity when stock is insufficient */
+  item?: InventoryItem;
+}
+
+/** An outside person or organization the shelter works with, such as a vet or rescue */
+export interface Contact {
+  /** Unique identifier for the contact (empty to generate one) */
+  id: string;
+  /** Name of the person or organization */
+  name: string;
+  /** Kind of contact */
+  kind: ContactKind;
+  /** Telephone number of the contact */
+  telNumber: string;
+  /** Email address of the contact */
+  email: string;
+  /** Postal address of the contact */
+  address: string;
+  /** Anything else worth knowing, such as opening hours */
+  notes: string;
+}
+
+/** Money given to the shelter by a donor in the contacts directory */
+export interface Donation {
+  /** Unique identifier for the donation (empty to generate one) */
+  id: string;
+  /** ID of the donor's contact, or null once the contact was deleted */
+  contactId: string | null;
+  /** Name of the donor when the donation was recorded */
+  donorName: string;
+  /** Amount given, in minor units of the shelter's default currency, such as satang */
+  amount: number;
+  /** Timestamp when the donation was made */
+  donationTimestamp: number;
+  /** Anything worth noting, such as the campaign it was given to */
+  notes: string;
+  /** Number printed on the donation's receipt, or null until one is generated */
+  receiptNumber: number | null;
+}
+
+/** Money spent by the shelter */
+export interface Expense {
+  /** Unique identifier for the expense (empty to generate one) */
+  id: string;
+  /** What the money was spent on */
+  category: ExpenseCategory;
+  /** Amount spent, in minor units of the shelter's default currency, such as satang */
+  amount: number;
+  /** Timestamp when the money was spent */
+  expenseTimestamp: number;
+  /** Who was paid, such as a supplier or veterinary clinic */
+  vendor: string;
+  /** ID of the animal the money was spent on, if it was spent on a single animal */
+  animalId: string | null;
+}
+
+/** Amount spent on one category of expenses */
+export interface CategoryAmount {
+  /** The category */
+  category: ExpenseCategory;
+  /** Amount spent on the category, in minor currency units */
+  amount: number;
+}
+
+/** Money spent during a calendar month */
+export interface MonthlyExpenses {
+  /** Month of the year (1-12) */
+  month: number;
+  /** Amount spent during the month, in minor currency units */
+  total: number;
+  /** Amount spent during the month on each category, largest first */
+  byCategory: CategoryAmount[];
+  /** Amount donated during the month, in minor currency units */
+  donations: number;
+  /** Amount donated minus amount spent during the month; negative when more was spent */
+  net: number;
+}
+
+/** Money spent and donated during a calendar year, month by month */
+export interface BudgetReport {
+  /** The reported year */
+  year: number;
+  /** Expenses of each month of the year, January first */
+  months: MonthlyExpenses[];
+  /** Amount spent during the whole year, in minor currency units */
+  total: number;
+  /** Amount donated during the whole year, in minor currency units */
+  donationTotal: number;
+  /** Amount donated minus amount spent during the whole year; negative when more was spent */
+  net: number;
+}
+
+/** A slot booked by a customer to meet an animal before adopting it */
+export interface Appointment {
+  /** Unique identifier for the appointment */
+  id: string;
+  /** ID of the animal to meet */
+  animalId: string;
+  /** Name of the animal to meet */
+  animalName: string;
+  /** Username of the customer who booked the appointment */
+  username: string;
+  /** Timestamp when the appointment starts */
+  slotTimestamp: number;
+  /** Where the appointment stands */
+  status: AppointmentStatus;
+  /** Timestamp when the appointment was booked */
+  createdTimestamp: number;
+}
+
+/** A spay or neuter surgery booked for an animal that is not neutered yet */
+export interface Surgery {
+  /** Unique identifier for the surgery */
+  id: string;
+  /** ID of the animal having the surgery */
+  animalId: string;
+  /** Name of the animal having the surgery */
+  animalName: string;
+  /** Timestamp when the surgery is booked for */
+  scheduledTimestamp: number;
+  /** ID of the veterinarian in the contacts directory performing the surgery, if known */
+  contactId: string | null;
+  /** Timestamp when the surgery was performed, or null while it is still to come */
+  completedTimestamp: number | null;
+  /** Anything worth knowing, such as fasting instructions */
+  notes: string;
+}
+
+/** Result of recording that a spay or neuter surgery was performed */
+export interface CompleteSurgeryResult {
+  /** Outcome of the recording */
+  status: CompleteSurgeryStatus;
+  /** The completed surgery, when it was recorded */
+  surgery?: Surgery;
+}
+
+/** A phone interview staff held with the applicant of an adoption request */
+export interface RequestInterview {
+  /** Unique identifier for the interview */
+  id: string;
+  /** ID of the adoption request the interview is about */
+  requestId: string;
+  /** Username of the staff member who held the interview */
+  interviewer: string;
+  /** Timestamp when the interview was held */
+  interviewTimestamp: number;
+  /** What was discussed and the impression the applicant made */
+  notes: string;
+}
+
+/** A reference given by an applicant, and the outcome of checking it */
+export interface ReferenceCheck {
+  /** Unique identifier for the reference check */
+  id: string;
+  /** ID of the adoption request the reference was given for */
+  requestId: string;
+  /** Name of the referee, such as a former landlord or veterinarian */
+  refereeName: string;
+  /** Email address or telephone number the referee can be reached at */
+  refereeContact: string;
+  /** What the referee said about the applicant */
+  outcome: ReferenceOutcome;
+  /** Anything worth knowing about what the referee said */
+  notes: string;
+  /** Username of the staff member who last recorded the check */
+  checkedBy: string;
+  /** Timestamp when the check was last recorded */
+  checkedTimestamp: number;
+}
+
+/** The interviews and reference checks recorded for an adoption request */
+export interface RequestChecks {
+  /** Phone interviews with the applicant, latest first */
+  interviews: RequestInterview[];
+  /** References given by the applicant, in the order they were recorded */
+  referenceChecks: ReferenceCheck[];
+}
+
+/** An adoption fee charged to the applicant of an adoption request */
+export interface Payment {
+  /** Unique identifier for the payment */
+  id: string;
+  /** ID of the adoption request the fee is charged for */
+  requestId: string;
+  /** Amount charged, in minor units of the shelter's default currency, such as satang */
+  amount: number;
+  /** What the fee is for, such as the adoption fee and microchip registration */
+  description: string;
+  /** Timestamp when the fee was charged */
+  createdTimestamp: number;
+  /** Timestamp when the fee was paid, if it has been */
+  paidTimestamp: number | null;
+  /** Number of the invoice issued for the fee, once one has been generated */
+  invoiceNumber: number | null;
+  /** Number of the receipt issued for the fee, once one has been generated */
+  receiptNumber: number | null;
+}
+
+/** Represents the record of an animal coming into the shelter */
+export interface IntakeRecord {
+  /** Unique identifier for the intake record (empty to generate one) */
+  id: string;
+  /** ID of the animal that came in */
+  animalId: string;
+  /** How the animal came into the shelter */
+  intakeType: IntakeType;
+  /** Where the animal came from, such as a location, owner, or transferring organisation */
+  source: string;
+  /** Condition of the animal when it came in */
+  condition: string;
+  /** Username of the staff member who took the animal in (filled in when recorded) */
+  staffUsername: string;
+  /** Timestamp when the animal came in */
+  intakeTimestamp: number;
+  /** Timestamp until which a stray is held before it can be adopted (null if no hold; set by the backend) */
+  holdUntilTimestamp?: number | null;
+}
+
+/** A stray being held after intake before it can be adopted */
+export interface StrayHold {
+  /** ID of the animal on hold */
+  animalId: string;
+  /** Name of the animal on hold */
+  animalName: string;
+  /** Species of the animal on hold */
+  specie: string;
+  /** Timestamp when the animal came in */
+  intakeTimestamp: number;
+  /** Timestamp when the hold ends and the animal can be adopted */
+  holdUntilTimestamp: number;
+}
+
+/** Represents a site run by the organization, where animals are housed */
+export interface Location {
+  /** Unique identifier for the location (empty to generate one) */
+  id: string;
+  /** Name of the location, unique within the organization */
+  name: string;
+  /** Street address of the location */
+  address: string;
+}
+
+/** Represents an animal moving between locations, or to or from a partner organization */
+export interface Transfer {
+  /** Unique identifier for the transfer (empty to generate one) */
+  id: string;
+  /** ID of the animal that was transferred */
+  animalId: string;
+  /** Whether the animal left or came back */
+  direction: TransferDirection;
+  /** ID of the location the animal left (filled in when recorded) */
+  fromLocationId: string | null;
+  /** ID of the location the animal moved to (null if it went to a partner organization) */
+  toLocationId: string | null;
+  /** Name of the partner organization the animal went to or came from (null between locations) */
+  partnerOrganization: string | null;
+  /** Why the animal was transferred */
+  reason: string;
+  /** Username of the staff member who recorded the transfer (filled in when recorded) */
+  staffUsername: string;
+  /** Timestamp when the animal was transferred */
+  transferTimestamp: number;
+  /** ID of the contact at the partner organization or transport partner, if known */
+  contactId?: string | null;
+}
+
+/** Represents a kennel or enclosure that houses animals */
+export interface Kennel {
+  /** Unique identifier for the kennel (empty to generate one) */
+  id: string;
+  /** Name of the kennel, unique within the shelter */
+  name: string;
+  /** Species the kennel is restricted to (null if any species may use it) */
+  specieRestriction: string | null;
+  /** Maximum number of animals the kennel can house */
+  capacity: number;
+}
+
+/** Current occupancy of a kennel */
+export interface KennelOccupancy {
+  /** The kennel */
+  kennel: Kennel;
+  /** IDs of the animals currently assigned to the kennel */
+  animalIds: string[];
+}
+
+/** How full the shelter is for one species */
+export interface SpecieCapacity {
+  /** The species */
+  specie: string;
+  /** Number of animals kennels restricted to the species can house */
+  dedicatedCapacity: number;
+  /** Number of animals of the species at the shelter, available or requested */
+  inShelterCount: number;
+  /** Number of animals of the species living with foster caregivers */
+  fosterCount: number;
+  /** Animals of the species at the shelter as a percentage of the dedicated and shared capacity (null if no kennel can house the species) */
+  utilizationPercent: number | null;
+}
+
+/** How full the shelter is, overall and for each species */
+export interface CapacityReport {
+  /** Number of animals all kennels can house together */
+  totalCapacity: number;
+  /** Number of animals kennels open to any species can house */
+  sharedCapacity: number;
+  /** Number of animals at the shelter, available or requested */
+  inShelterCount: number;
+  /** Number of animals living with foster caregivers */
+  fosterCount: number;
+  /** Animals at the shelter as a percentage of the total capacity (null without kennels) */
+  utilizationPercent: number | null;
+  /** Capacity and counts for each species with kennels or animals, ordered by species */
+  species: SpecieCapacity[];
+}
+
+/** Represents a customer waiting to hear about animals of a species or breed */
+export interface WaitlistEntry {
+  /** Unique identifier for the waitlist entry */
+  id: string;
+  /** Username of the customer waiting */
+  username: string;
+  /** Species the customer is waiting for */
+  specie: string;
+  /** Breed the customer is waiting for (null for any breed of the species) */
+  breed: string | null;
+  /** Timestamp when the customer joined the waitlist */
+  createdTimestamp: number;
+}
+
+/** Represents an in-app notification for a user */
+export interface Notification {
+  /** Unique identifier for the notification */
+  id: string;
+  /** Username of the user the notification is for */
+  username: string;
+  /** Text of the notification */
+  message: string;
+  /** ID of the animal the notification is about, if any */
+  animalId: string | null;
+  /** Timestamp when the notification was created */
+  createdTimestamp: number;
+  /** Whether the user has read the notification */
+  read: boolean;
+}
+
+/** Number of animals that share a value, such as a status or species */
+export interface CategoryCount {
+  /** The shared value */
+  label: string;
+  /** Number of animals with the value */
+  count: number;
+}
+
+/** Number of events that happened in a calendar month */
+export interface MonthlyCount {
+  /** Calendar year of the month */
+  year: number;
+  /** Month of the year (1-12) */
+  month: number;
+  /** Number of events in the month */
+  count: number;
+}
+
+/** Length of the periods adoption trends are counted over */
+export enum TrendGranularity {
+  /** Calendar days, in UTC */
+  DAY = "day",
+  /** Weeks starting on Monday */
+  WEEK = "week",
+  /** Calendar months */
+  MONTH = "month",
+}
+
+/** Number of animals taken in and adopted during one period of a trend */
+export interface TrendPoint {
+  /** Timestamp of the start of the period */
+  periodStartTimestamp: number;
+  /** Number of animals admitted during the period */
+  intakeCount: number;
+  /** Number of adoptions approved during the period */
+  adoptionCount: number;
+}
+
+/** Aggregate statistics about the shelter, used by the dashboard */
+export interface ShelterStatistics {
+  /** Number of animals for each status */
+  animalsByStatus: CategoryCount[];
+  /** Number of animals for each species */
+  animalsBySpecie: CategoryCount[];
+  /** Approved adoptions for each of the last twelve months, oldest first */
+  adoptionsPerMonth: MonthlyCount[];
+  /** Average number of days between admission and adoption (null if no adoptions yet) */
+  averageLengthOfStayDays: number | null;
+  /** Number of adoption requests awaiting review */
+  pendingRequestCount: number;
+}
+
+/** Activity of the shelter during a calendar month */
+export interface MonthlyReport {
+  /** Year of the reported month */
+  year: number;
+  /** Reported month, from 1 to 12 */
+  month: number;
+  /** Number of animals admitted during the month */
+  admissionCount: number;
+  /** Number of recorded intakes during the month, by intake type */
+  intakesByType: CategoryCount[];
+  /** Number of adoptions approved during the month */
+  adoptionCount: number;
+  /** Number of adopted animals returned during the month */
+  returnCount: number;
+  /** Number of animals that passed away or were euthanized during the month */
+  deathCount: number;
+  /** Number of deaths during the month, by recorded cause */
+  deathsByCause: CategoryCount[];
+  /** Total of the adoption fees paid during the month, in minor currency units */
+  feeRevenue: number;
+  /** Total of the donations received during the month, in minor currency units; always zero for a single location */
+  donationRevenue: number;
+}
+
+/** Outcome of importing changes exported by another installation */
+export interface ImportChangesResult {
+  /** Number of animals and adoption requests created or updated */
+  appliedCount: number;
+  /** Number of changes ignored because the local record changed more recently */
+  skippedCount: number;
+  /** Number of animals and adoption requests deleted */
+  deletedCount: number;
+}
+
+/** Outcome of compacting a database file */
+export interface MaintenanceResult {
+  /** Name of the database */
+  database: string;
+  /** Size of the database before maintenance, in bytes */
+  sizeBeforeBytes: number;
+  /** Size of the database after maintenance, in bytes */
+  sizeAfterBytes: number;
+  /** Space freed by maintenance, in bytes */
+  reclaimedBytes: number;
+}
+
+/** Records created by seeding demo data */
+export interface DemoDataSummary {
+  /** Number of demo animals admitted */
+  animalCount: number;
+  /** Number of demo adoption requests filed */
+  requestCount: number;
+}
+
+/** A record that references an animal which no longer exists */
+export interface OrphanedRecord {
+  /** Table holding the record */
+  table: string;
+  /** ID of the record */
+  id: string;
+  /** ID of the missing animal */
+  animalId: string;
+}
+
+/** An animal whose image file cannot be found */
+export interface MissingImage {
+  /** ID of the animal */
+  animalId: string;
+  /** Path to the missing image file */
+  imagePath: string;
+}
+
+/** Problems found by a database integrity check */
+export interface IntegrityReport {
+  /** Whether no problems were found */
+  healthy: boolean;
+  /** Corruption reported by SQLite's integrity check */
+  integrityErrors: string[];
+  /** Records that reference missing animals */
+  orphanedRecords: OrphanedRecord[];
+  /** Animals, in the shelter or in the trash, whose image file is missing */
+  missingImages: MissingImage[];
+  /** Uploaded files that no animal references */
+  unreferencedFiles: string[];
+}
+
+/** A deleted record waiting in the trash until it is restored or purged */
+export interface TrashItem {
+  /** Kind of the deleted record */
+  itemType: TrashItemType;
+  /** ID of the deleted record */
+  id: string;
+  /** Name of the animal, or of the applicant for adoption requests */
+  name: string;
+  /** ID of the animal the record belongs to */
+  animalId: string;
+  /** Timestamp when the record was deleted */
+  deletedTimestamp: number;
+  /** Timestamp after which the record is purged for good */
+  expiresTimestamp: number;
+}
+
+/** A queued, running or finished background job */
+export interface Job {
+  /** Unique identifier for the job */
+  id: string;
+  /** The kind of work the job does */
+  kind: JobKind;
+  /** Username of the staff member who queued the job */
+  username: string;
+  /** Where the job is in its lifecycle */
+  status: JobStatus;
+  /** How far along the job is, from 0 to 100 */
+  progress: number;
+  /** What the job is doing, such as "Rendering report" */
+  message: string;
+  /** The result of a completed job; its shape depends on the kind */
+  result: unknown;
+  /** Why the job failed, if it did */
+  error: string | null;
+  /** Timestamp when the job was queued */
+  createdTimestamp: number;
+  /** Timestamp when the job finished, null until it does */
+  finishedTimestamp: number | null;
+}
+
+/** Work that can be queued as a background job, with its parameters */
+export type JobRequest =
+  | {
+      type: JobKind.REPORT_EXPORT;
+      year: number;
+      month: number | null;
+      locationId: string | null;
+      format: ReportFormat;
+    }
+  | { type: JobKind.ANIMALS_EXPORT; filters: FilterSelections | null }
+  | {
+      type: JobKind.ADOPTION_REQUESTS_EXPORT;
+      filters: AdoptionRequestFilters;
+      anonymize?: boolean;
+    }
+  | { type: JobKind.CHANGES_EXPORT; sinceTimestamp: number | null }
+  | { type: JobKind.CHANGES_IMPORT }
+  | { type: JobKind.ANIMALS_IMPORT; animals: Animal[] };
+
+// ==================== ERROR FUNCTIONS ====================
+
+/**
+ * Checks whether a caught value is an error returned by a command.
+ * @param e - The caught value
+ * @param kind - Only match errors of this kind, or any kind if omitted
+ * @returns boolean - True if the value is a command error of the given kind
+ */
+export function isCommandError(
+  e: unknown,
+  kind?: CommandErrorKind,
+): e is CommandError {
+  if (
+    typeof e !== "object" ||
+    e === null ||
+    !("kind" in e) ||
+    !("message" in e)
+  ) {
+    return false;
+  }
+  return kind === undefined || (e as CommandError).kind === kind;
+}
+
+/**
+ * Gets a readable description of a caught value.
+ * @param e - The caught value, such as an error returned by a command
+ * @returns string - The localized summary and message of a command error, or the message of any other error
+ */
+export function describeError(e: unknown): string {
+  if (isCommandError(e)) {
+    return `${e.summary} (${e.message})`;
+  }
+  if (e instanceof Error) {
+    return e.message;
+  }
+  return String(e);
+}
+
+// ==================== ANIMAL FUNCTIONS ====================
+
+/**
+ * Retrieves animals from the database, with optional filtering.
+ *
+ * @param filters - Optional map of filter criteria and values
+ * @returns Promise<AnimalSummary[]> - List of animal summaries. Returns an empty array if the operation fails.
+ */
+export async function getAnimals(
+  filters: FilterSelections | null,
+): Promise<AnimalSummary[]> {
+  try {
+    return await invoke<AnimalSummary[]>("get_animals", { filters });
+  } catch (e) {
+    error(`Failed to get animals: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Retrieves an animal together with its adoption requests, latest medical note and age.
+ *
+ * @param animalId - The ID of the animal to retrieve
+ * @returns Promise<AnimalDetail | null> - The animal's details or null if not found. Returns null if the operation fails.
+ */
+export async function getAnimalDetail(
+  animalId: string,
+): Promise<AnimalDetail | null> {
+  try {
+    return await invoke<AnimalDetail | null>("get_animal_detail", {
+      animalId,
+    });
+  } catch (e) {
+    error(`Failed to get details for animal ${animalId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Counts animals in the database, with optional filtering.
+ *
+ * @param filters - Optional filter selections to apply
+ * @returns Promise<number> - The number of matching animals. Returns 0 if the operation fails.
+ */
+export async function getAnimalCount(
+  filters: FilterSelections | null,
+): Promise<number> {
+  try {
+    return await invoke<number>("get_animal_count", { filters });
+  } catch (e) {
+    error(`Failed to count animals: ${describeError(e)}`);
+    return 0;
+  }
+}
+
+/**
+ * Retrieves the distinct species of the animals in the database.
+ *
+ * @returns Promise<string[]> - Sorted list of species. Returns an empty array if the operation fails.
+ */
+export async function getDistinctSpecies(): Promise<string[]> {
+  try {
+    return await invoke<string[]>("get_distinct_species");
+  } catch (e) {
+    error(`Failed to get distinct species: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Retrieves the distinct breeds of the animals of a species in the database.
+ *
+ * @param specie - The species to retrieve breeds for
+ * @returns Promise<string[]> - Sorted list of breeds. Returns an empty array if the operation fails.
+ */
+export async function getDistinctBreeds(specie: string): Promise<string[]> {
+  try {
+    return await invoke<string[]>("get_distinct_breeds", { specie });
+  } catch (e) {
+    error(`Failed to get distinct breeds of ${specie}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Retrieves the distinct colors recorded for the animals in the database.
+ *
+ * @returns Promise<string[]> - Sorted list of colors. Returns an empty array if the operation fails.
+ */
+export async function getDistinctColors(): Promise<string[]> {
+  try {
+    return await invoke<string[]>("get_distinct_colors");
+  } catch (e) {
+    error(`Failed to get distinct colors: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Retrieves a specific animal by ID.
+ *
+ * @param animalId - The ID of the animal to retrieve
+ * @returns Promise<Animal | null> - The animal data if found, null if not found. Returns null if the operation fails.
+ */
+export async function getAnimalById(animalId: string): Promise<Animal | null> {
+  try {
+    return await invoke<Animal | null>("get_animal_by_id", { animalId });
+  } catch (e) {
+    error(`Failed to get animal by ID ${animalId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Finds the animal with a specific microchip number.
+ *
+ * @param microchipNumber - The scanned microchip number
+ * @returns Promise<Animal | null> - The animal with the microchip, null if there is none. Returns null if the operation fails.
+ */
+export async function findAnimalByMicrochip(
+  microchipNumber: string,
+): Promise<Animal | null> {
+  try {
+    return await invoke<Animal | null>("find_animal_by_microchip", {
+      microchipNumber,
+    });
+  } catch (e) {
+    error(`Failed to find animal with microchip ${microchipNumber}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves an animal and its associated accepted adoption in a single call.
+ *
+ * @param animalId - The ID of the animal.
+ * @param animalStatus - The status of the animal, to determine if a request should be fetched.
+ * @returns A promise that resolves to an object containing the animal and optional adopter details. Returns `{ animal: null, adopter: null }` if the operation fails.
+ */
+export async function getAnimalWithAcceptedAdoption(
+  animalId: string,
+  animalStatus: AnimalStatus,
+): Promise<{ animal: Animal | null; adopter: AdoptionRequest | null }> {
+  try {
+    const detail = await getAnimalDetail(animalId);
+    if (!detail) {
+      return { animal: null, adopter: null };
+    }
+
+    // Find the request with the APPROVED status
+    let adopter: AdoptionRequest | null = null;
+    if (animalStatus === AnimalStatus.ADOPTED) {
+      adopter =
+        detail.adoptionRequests
+          .filter(isFullAdoptionRequest)
+          .find((request) => request.status === RequestStatus.APPROVED) ??
+        null;
+    }
+    return { animal: detail.animal, adopter };
+  } catch (e) {
+    error(`Failed to get animal ${animalId} with adoption request: ${describeError(e)}`);
+    return { animal: null, adopter: null };
+  }
+}
+
+/**
+ * Checks an animal the way the backend does before storing it.
+ *
+ * @param animal - The animal data to check
+ * @returns Promise<FieldError[]> - The invalid fields, empty if the animal is valid. Returns an empty array if the operation fails.
+ */
+export async function validateAnimal(animal: Animal): Promise<FieldError[]> {
+  try {
+    return await invoke<FieldError[]>("validate_animal", { animal });
+  } catch (e) {
+    error(`Failed to validate animal: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Creates a new animal in the database.
+ *
+ * @param animal - The animal data to create
+ * @returns Promise<void> - A promise that resolves when the operation is complete. Logs an error if the operation fails.
+ */
+export async function createAnimal(animal: Animal): Promise<void> {
+  try {
+    await invoke("create_animal", { animal });
+  } catch (e) {
+    error(`Failed to create animal: ${describeError(e)}`);
+  }
+}
+
+/**
+ * Creates several animals in the database in a single transaction, as a background job.
+ * If any animal fails to insert, none of them are kept.
+ *
+ * @param animals - The animal data to create
+ * @returns Promise<string[] | null> - The IDs of the created animals, in the order given. Returns null if the operation fails.
+ */
+export async function createAnimalsBatch(
+  animals: Animal[],
+): Promise<string[] | null> {
+  try {
+    return await runJob<string[]>({ type: JobKind.ANIMALS_IMPORT, animals });
+  } catch (e) {
+    error(`Failed to create animals: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Updates an existing animal in the database.
+ *
+ * @param animal - The updated animal data, carrying the version it was read at
+ * @returns Promise<UpdateAnimalResult | null> - The updated animal, or the current animal if it was changed by someone else. Returns null if the operation fails.
+ */
+export async function updateAnimal(
+  animal: Animal,
+): Promise<UpdateAnimalResult | null> {
+  try {
+    return await invoke<UpdateAnimalResult>("update_animal", { animal });
+  } catch (e) {
+    error(`Failed to update animal: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Deletes an animal from the database, along with its adoption requests.
+ * The animal stays in the trash, and keeps its image file, until restored or purged.
+ *
+ * @param animal - The animal data to delete
+ * @returns Promise<boolean> - True if animal was found and deleted, false if not found. Returns false if the operation fails.
+ */
+export async function deleteAnimal(
+  animal: AnimalSummary | Animal,
+): Promise<boolean> {
+  try {
+    return await invoke<boolean>("delete_animal", {
+      animalId: animal.id,
+      cascade: true,
+    });
+  } catch (e) {
+    error(`Failed to delete animal with ID ${animal.id}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+// ==================== ADOPTION REQUEST FUNCTIONS ====================
+
+/**
+ * Lists adoption requests matching the given filters, newest first (staff only).
+ *
+ * @param filters - The status, submission date range and animal species to filter by
+ * @returns Promise<AdoptionRequestSummary[]> - List of matching requests. Returns an empty array if the operation fails.
+ */
+export async function getAdoptionRequests(
+  filters: AdoptionRequestFilters = {},
+): Promise<AdoptionRequestSummary[]> {
+  try {
+    return await invoke<AdoptionRequestSummary[]>("get_adoption_requests", {
+      filters,
+    });
+  } catch (e) {
+    error(`Failed to retrieve adoption requests: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Retrieves adoption requests together with the animals they are for, in a single call
+ * (staff only).
+ *
+ * @param filters - Optional filter selections applied to the animals
+ * @param status - Only include requests with this status, if given
+ * @returns Promise<AnimalAdoptionRequest[]> - Requests with their animal summaries. Returns an empty array if the operation fails.
+ */
+export async function getAdoptionRequestsWithAnimals(
+  filters: FilterSelections | null,
+  status: RequestStatus | null = null,
+): Promise<AnimalAdoptionRequest[]> {
+  try {
+    return await invoke<AnimalAdoptionRequest[]>(
+      "get_adoption_requests_with_animals",
+      { filters, status },
+    );
+  } catch (e) {
+    error(`Failed to retrieve adoption requests with animals: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Retrieves the distinct countries of the requesters in the database.
+ *
+ * @returns Promise<string[]> - Sorted list of countries. Returns an empty array if the operation fails.
+ */
+export async function getDistinctCountries(): Promise<string[]> {
+  try {
+    return await invoke<string[]>("get_distinct_countries");
+  } catch (e) {
+    error(`Failed to get distinct countries: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Counts the adoption requests that are still pending review.
+ *
+ * @returns Promise<number> - The number of pending adoption requests. Returns 0 if the operation fails.
+ */
+export async function getPendingRequestCount(): Promise<number> {
+  try {
+    return await invoke<number>("get_pending_request_count");
+  } catch (e) {
+    error(`Failed to count pending adoption requests: ${describeError(e)}`);
+    return 0;
+  }
+}
+
+/**
+ * Retrieves a specific adoption request by ID, redacted unless the current user is staff or the requester.
+ *
+ * @param requestId - The ID of the adoption request to retrieve
+ * @returns Promise<AdoptionRequestView | null> - The adoption request data if found, null if not found. Returns null if the operation fails.
+ */
+export async function getAdoptionRequestById(
+  requestId: string,
+): Promise<AdoptionRequestView | null> {
+  try {
+    return await invoke<AdoptionRequestView | null>("get_adoption_request_by_id", {
+      requestId,
+    });
+  } catch (e) {
+    error(`Failed to get adoption request by ID ${requestId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves all adoption requests for a specific animal ID. Only staff members see every
+ * applicant's personal details; other users only see their own.
+ *
+ * @param animalId - The ID of the animal to retrieve requests for
+ * @returns Promise<AdoptionRequestView[]> - List of adoption requests. Returns an empty array if the operation fails.
+ */
+export async function getAdoptionRequestsByAnimalId(
+  animalId: string,
+): Promise<AdoptionRequestView[]> {
+  try {
+    return await invoke<AdoptionRequestView[]>(
+      "get_adoption_requests_by_animal_id",
+      { animalId },
+    );
+  } catch (e) {
+    error(`Failed to get adoption requests for animal ID ${animalId}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Retrieves all adoption requests for a specific username.
+ *
+ * @param username - The username to retrieve requests for
+ * @returns Promise<AdoptionRequest[]> - List of adoption requests. Returns an empty array if the operation fails.
+ */
+export async function getAdoptionRequestsByUsername(
+  username: string,
+): Promise<AdoptionRequest[]> {
+  try {
+    return await invoke<AdoptionRequest[]>(
+      "get_adoption_requests_by_username",
+      { username },
+    );
+  } catch (e) {
+    error(`Failed to get adoption requests for username ${username}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Checks an adoption request the way the backend does before storing it.
+ *
+ * @param request - The adoption request data to check
+ * @returns Promise<FieldError[]> - The invalid fields, empty if the request is valid. Returns an empty array if the operation fails.
+ */
+export async function validateAdoptionRequest(
+  request: AdoptionRequest,
+): Promise<FieldError[]> {
+  try {
+    return await invoke<FieldError[]>("validate_adoption_request", {
+      request,
+    });
+  } catch (e) {
+    error(`Failed to validate adoption request: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Creates a new adoption request in the database.
+ *
+ * @param request - The adoption request data to create
+ * @param linkProfile - Whether to file the request under the logged-in user and fill its contact details from their profile
+ * @returns Promise<CreateRequestResult | null> - Whether the request was stored or rejected as a duplicate. Returns null if the operation fails.
+ */
+export async function createAdoptionRequest(
+  request: AdoptionRequest,
+  linkProfile: boolean = false,
+): Promise<CreateRequestResult | null> {
+  try {
+    return await invoke<CreateRequestResult>("create_adoption_request", {
+      request,
+      linkProfile,
+    });
+  } catch (e) {
+    error(`Failed to create adoption request: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Updates an existing adoption request in the database. Requests are approved or put
+ * on trial with approveAdoptionRequest, not here.
+ *
+ * @param request - The updated adoption request data
+ * @returns Promise<boolean> - True if request was found and updated, false if not found. Returns false if the operation fails.
+ */
+export async function updateAdoptionRequest(
+  request: AdoptionRequest,
+): Promise<boolean> {
+  try {
+    return await invoke<boolean>("update_adoption_request", { request });
+  } catch (e) {
+    error(`Failed to update adoption request: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Deletes an adoption request from the database.
+ *
+ * @param requestId - The ID of the adoption request to delete
+ * @returns Promise<boolean> - True if request was found and deleted, false if not found. Returns false if the operation fails.
+ */
+export async function deleteAdoptionRequest(
+  requestId: string,
+): Promise<boolean> {
+  try {
+    return await invoke<boolean>("delete_adoption_request", { requestId });
+  } catch (e) {
+    error(`Failed to delete adoption request with ID ${requestId}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Approves an adoption request, marks the animal as adopted and rejects its other pending requests (staff only).
+ * With a trial end, the request and the animal are put on trial until the adoption is finalized.
+ *
+ * @param requestId - The ID of the adoption request to approve
+ * @param allowSplitBond - Whether to approve even if a bonded partner would be left behind
+ * @param trialEndTimestamp - When the trial period ends, or null to adopt right away
+ * @returns Promise<ApproveRequestResult | null> - The outcome of the approval. Returns null if the operation fails.
+ */
+export async function approveAdoptionRequest(
+  requestId: string,
+  allowSplitBond: boolean = false,
+  trialEndTimestamp: number | null = null,
+): Promise<ApproveRequestResult | null> {
+  try {
+    return await invoke<ApproveRequestResult>("approve_adoption_request", {
+      requestId,
+      allowSplitBond,
+      trialEndTimestamp,
+    });
+  } catch (e) {
+    error(`Failed to approve adoption request with ID ${requestId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Scores every adoption request again, such as after the screening settings changed (staff only).
+ *
+ * @returns Promise<number | null> - The number of requests scored. Returns null if the operation fails.
+ */
+export async function recomputeScreeningScores(): Promise<number | null> {
+  try {
+    return await invoke<number>("recompute_screening_scores");
+  } catch (e) {
+    error(`Failed to recompute screening scores: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Records the return of an adopted animal, making it available again (staff only).
+ *
+ * @param adoptionRequestId - The ID of the approved or trial adoption request being returned
+ * @param reason - Why the animal was returned
+ * @returns Promise<ProcessReturnResult | null> - Whether the return was recorded, or why it could not be. Returns null if the operation fails.
+ */
+export async function processReturn(
+  adoptionRequestId: string,
+  reason: string,
+): Promise<ProcessReturnResult | null> {
+  try {
+    return await invoke<ProcessReturnResult>("process_return", {
+      adoptionRequestId,
+      reason,
+    });
+  } catch (e) {
+    error(`Failed to process return of adoption request: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Turns a trial adoption into a final adoption, marking its animal as adopted (staff only).
+ *
+ * @param adoptionRequestId - The ID of the adoption request on trial
+ * @returns Promise<FinalizeTrialResult | null> - Whether the adoption was finalized, or why it could not be. Returns null if the operation fails.
+ */
+export async function finalizeTrialAdoption(
+  adoptionRequestId: string,
+): Promise<FinalizeTrialResult | null> {
+  try {
+    return await invoke<FinalizeTrialResult>("finalize_trial_adoption", {
+      adoptionRequestId,
+    });
+  } catch (e) {
+    error(`Failed to finalize trial adoption of adoption request with ID ${adoptionRequestId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves the trial adoptions that are still ongoing, ending soonest first (staff only).
+ *
+ * @returns Promise<TrialAdoption[]> - List of ongoing trials. Returns an empty array if the operation fails.
+ */
+export async function getOngoingTrialAdoptions(): Promise<TrialAdoption[]> {
+  try {
+    return await invoke<TrialAdoption[]>("get_ongoing_trial_adoptions");
+  } catch (e) {
+    error(`Failed to retrieve ongoing trial adoptions: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Retrieves the returns of a specific animal, most recent first (staff only).
+ *
+ * @param animalId - The ID of the animal to retrieve returns for
+ * @returns Promise<AdoptionReturn[]> - List of returns. Returns an empty array if the operation fails.
+ */
+export async function getAdoptionReturnsByAnimalId(
+  animalId: string,
+): Promise<AdoptionReturn[]> {
+  try {
+    return await invoke<AdoptionReturn[]>(
+      "get_adoption_returns_by_animal_id",
+      { animalId },
+    );
+  } catch (e) {
+    error(`Failed to retrieve adoption returns for animal ID ${animalId}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+// ==================== REQUEST CHECK FUNCTIONS ====================
+
+/**
+ * Retrieves the phone interviews and reference checks recorded for an adoption request (staff only).
+ *
+ * @param requestId - The ID of the adoption request
+ * @returns Promise<RequestChecks | null> - Interviews, latest first, and reference checks. Returns null if the operation fails.
+ */
+export async function getRequestChecks(
+  requestId: string,
+): Promise<RequestChecks | null> {
+  try {
+    return await invoke<RequestChecks>("get_request_checks", { requestId });
+  } catch (e) {
+    error(`Failed to retrieve checks for adoption request with ID ${requestId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Records a phone interview the current staff member held with an applicant (staff only).
+ *
+ * @param requestId - The ID of the adoption request
+ * @param interviewTimestamp - Timestamp when the interview was held
+ * @param notes - What was discussed and the impression the applicant made
+ * @returns Promise<RecordCheckResult | null> - Recorded, or RequestNotFound. Returns null if the operation fails.
+ */
+export async function recordRequestInterview(
+  requestId: string,
+  interviewTimestamp: number,
+  notes: string,
+): Promise<RecordCheckResult | null> {
+  try {
+    return await invoke<RecordCheckResult>("record_request_interview", {
+      requestId,
+      interviewTimestamp,
+      notes,
+    });
+  } catch (e) {
+    error(`Failed to record interview for adoption request with ID ${requestId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Records a reference given by an applicant, checked by the current staff member (staff only).
+ *
+ * @param requestId - The ID of the adoption request
+ * @param refereeName - Name of the referee
+ * @param refereeContact - Email address or telephone number of the referee
+ * @param outcome - What the referee said, or pending if they were not reached yet
+ * @param notes - Anything worth knowing about what the referee said
+ * @returns Promise<RecordCheckResult | null> - Recorded, or RequestNotFound. Returns null if the operation fails.
+ */
+export async function recordReferenceCheck(
+  requestId: string,
+  refereeName: string,
+  refereeContact: string,
+  outcome: ReferenceOutcome = ReferenceOutcome.PENDING,
+  notes = "",
+): Promise<RecordCheckResult | null> {
+  try {
+    return await invoke<RecordCheckResult>("record_reference_check", {
+      requestId,
+      refereeName,
+      refereeContact,
+      outcome,
+      notes,
+    });
+  } catch (e) {
+    error(`Failed to record reference check for adoption request with ID ${requestId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Records the outcome of a reference check, such as once the referee was reached (staff only).
+ *
+ * @param checkId - The ID of the reference check
+ * @param outcome - What the referee said about the applicant
+ * @param notes - Anything worth knowing about what the referee said
+ * @returns Promise<ReferenceCheck | null> - The updated reference check. Returns null if the operation fails.
+ */
+export async function updateReferenceCheckOutcome(
+  checkId: string,
+  outcome: ReferenceOutcome,
+  notes = "",
+): Promise<ReferenceCheck | null> {
+  try {
+    return await invoke<ReferenceCheck>("update_reference_check_outcome", {
+      checkId,
+      outcome,
+      notes,
+    });
+  } catch (e) {
+    error(`Failed to update reference check with ID ${checkId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== ANIMAL NOTE FUNCTIONS ====================
+
+/**
+ * Retrieves the notes about a specific animal visible to the current user, most recent first.
+ *
+ * @param animalId - The ID of the animal to retrieve notes for
+ * @returns Promise<AnimalNote[]> - List of notes. Returns an empty array if the operation fails.
+ */
+export async function getAnimalNotes(animalId: string): Promise<AnimalNote[]> {
+  try {
+    return await invoke<AnimalNote[]>("get_animal_notes", { animalId });
+  } catch (e) {
+    error(`Failed to retrieve notes for animal ID ${animalId}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Adds a note about an animal as the logged-in staff member.
+ *
+ * @param animalId - The ID of the animal the note is about
+ * @param category - Category of the note
+ * @param content - Text of the note
+ * @param internal - Whether the note is for staff only
+ * @returns Promise<boolean> - True if the note was added. Returns false if the operation fails.
+ */
+export async function addAnimalNote(
+  animalId: string,
+  category: NoteCategory,
+  content: string,
+  internal: boolean,
+): Promise<boolean> {
+  try {
+    await invoke("add_animal_note", { animalId, category, content, internal });
+    return true;
+  } catch (e) {
+    error(`Failed to add note: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Deletes a note about an animal.
+ *
+ * @param noteId - The ID of the note to delete
+ * @returns Promise<boolean> - True if the note was found and deleted. Returns false if the operation fails.
+ */
+export async function deleteAnimalNote(noteId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("delete_animal_note", { noteId });
+  } catch (e) {
+    error(`Failed to delete note with ID ${noteId}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+// ==================== VACCINATION FUNCTIONS ====================
+
+/**
+ * Retrieves the vaccinations given to a specific animal, most recent first.
+ *
+ * @param animalId - The ID of the animal to retrieve vaccinations for
+ * @returns Promise<Vaccination[]> - List of vaccinations. Returns an empty array if the operation fails.
+ */
+export async function getVaccinations(
+  animalId: string,
+): Promise<Vaccination[]> {
+  try {
+    return await invoke<Vaccination[]>("get_vaccinations", { animalId });
+  } catch (e) {
+    error(`Failed to retrieve vaccinations for animal ID ${animalId}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Records a vaccination given to an animal as the logged-in staff member.
+ *
+ * @param animalId - The ID of the animal that was vaccinated
+ * @param vaccine - Name of the vaccine
+ * @param administeredTimestamp - Timestamp when the vaccination was given
+ * @param dueTimestamp - Timestamp when the next dose is due, or null if none is needed
+ * @param contactId - ID of the veterinarian who gave the vaccine, or null if unknown
+ * @returns Promise<string | null> - The ID of the vaccination. Returns null if the operation fails.
+ */
+export async function addVaccination(
+  animalId: string,
+  vaccine: string,
+  administeredTimestamp: number,
+  dueTimestamp: number | null,
+  contactId: string | null = null,
+): Promise<string | null> {
+  try {
+    return await invoke<string>("add_vaccination", {
+      animalId,
+      vaccine,
+      administeredTimestamp,
+      dueTimestamp,
+      contactId,
+    });
+  } catch (e) {
+    error(`Failed to add vaccination: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Deletes a vaccination record.
+ *
+ * @param vaccinationId - The ID of the vaccination to delete
+ * @returns Promise<boolean> - True if the vaccination was found and deleted. Returns false if the operation fails.
+ */
+export async function deleteVaccination(
+  vaccinationId: string,
+): Promise<boolean> {
+  try {
+    return await invoke<boolean>("delete_vaccination", { vaccinationId });
+  } catch (e) {
+    error(`Failed to delete vaccination with ID ${vaccinationId}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+// ==================== FOSTER PLACEMENT FUNCTIONS ====================
+
+/**
+ * Retrieves all foster placements for a specific animal, most recent first (staff only).
+ *
+ * @param animalId - The ID of the animal to retrieve placements for
+ * @returns Promise<FosterPlacement[]> - List of foster placements. Returns an empty array if the operation fails.
+ */
+export async function getFosterPlacementsByAnimalId(
+  animalId: string,
+): Promise<FosterPlacement[]> {
+  try {
+    return await invoke<FosterPlacement[]>(
+      "get_foster_placements_by_animal_id",
+      { animalId },
+    );
+  } catch (e) {
+    error(
+      `Failed to retrieve foster placements for animal ID ${animalId}: ${describeError(e)}`,
+    );
+    return [];
+  }
+}
+
+/**
+ * Starts a foster placement, marking the animal as fostered (staff only).
+ *
+ * @param placement - The foster placement to start
+ * @returns Promise<StartFosterResult | null> - Whether the placement was started, or why it could not be. Returns null if the operation fails.
+ */
+export async function startFosterPlacement(
+  placement: FosterPlacement,
+): Promise<StartFosterResult | null> {
+  try {
+    return await invoke<StartFosterResult>("start_foster_placement", {
+      placement,
+    });
+  } catch (e) {
+    error(`Failed to start foster placement: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Moves the planned end of an ongoing foster placement (staff only).
+ *
+ * @param placementId - The ID of the placement to extend
+ * @param endTimestamp - The new planned end of the placement
+ * @returns Promise<boolean> - True if an ongoing placement was found and extended. Returns false if the operation fails.
+ */
+export async function extendFosterPlacement(
+  placementId: string,
+  endTimestamp: number,
+): Promise<boolean> {
+  try {
+    return await invoke<boolean>("extend_foster_placement", {
+      placementId,
+      endTimestamp,
+    });
+  } catch (e) {
+    error(`Failed to extend foster placement with ID ${placementId}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Ends an ongoing foster placement, making the animal available again (staff only).
+ *
+ * @param placementId - The ID of the placement to end
+ * @returns Promise<boolean> - True if an ongoing placement was found and ended. Returns false if the operation fails.
+ */
+export async function endFosterPlacement(
+  placementId: string,
+): Promise<boolean> {
+  try {
+    return await invoke<boolean>("end_foster_placement", { placementId });
+  } catch (e) {
+    error(`Failed to end foster placement with ID ${placementId}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+// ==================== INTAKE FUNCTIONS ====================
+
+/**
+ * Records how an animal came into the shelter (staff only).
+ *
+ * @param record - The intake record to insert; the logged-in staff member is recorded as the one who took the animal in
+ * @returns Promise<boolean> - True if the intake was recorded. Returns false if the operation fails.
+ */
+export async function recordIntake(
+  record: Omit<IntakeRecord, "staffUsername">,
+): Promise<boolean> {
+  try {
+    await invoke("record_intake", { record });
+    return true;
+  } catch (e) {
+    error(`Failed to record intake: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Retrieves the intake history of a specific animal, most recent first (staff only).
+ *
+ * @param animalId - The ID of the animal to retrieve intake records for
+ * @returns Promise<IntakeRecord[]> - List of intake records. Returns an empty array if the operation fails.
+ */
+export async function getIntakeHistory(
+  animalId: string,
+): Promise<IntakeRecord[]> {
+  try {
+    return await invoke<IntakeRecord[]>("get_intake_history", { animalId });
+  } catch (e) {
+    error(`Failed to retrieve intake history for animal ID ${animalId}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Retrieves the strays whose hold ends within the next seven days, ending soonest first (staff only).
+ *
+ * @returns Promise<StrayHold[]> - The strays coming off hold this week. Returns an empty array if the operation fails.
+ */
+export async function getStrayHoldsEndingThisWeek(): Promise<StrayHold[]> {
+  try {
+    return await invoke<StrayHold[]>("get_stray_holds_ending_this_week");
+  } catch (e) {
+    error(`Failed to retrieve strays coming off hold: ${describeError(e)}`);
+    return [];
+  }
+}
+
+// ==================== KENNEL FUNCTIONS ====================
+
+/**
+ * Creates a new kennel (staff only).
+ *
+ * @param kennel - The kennel data to insert
+ * @returns Promise<boolean> - True if the kennel was created. Returns false if the operation fails.
+ */
+export async function createKennel(kennel: Kennel): Promise<boolean> {
+  try {
+    await invoke("create_kennel", { kennel });
+    return true;
+  } catch (e) {
+    error(`Failed to create kennel: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Retrieves every kennel with the animals currently assigned to it.
+ *
+ * @returns Promise<KennelOccupancy[]> - Occupancy of each kennel. Returns an empty array if the operation fails.
+ */
+export async function getKennelOccupancy(): Promise<KennelOccupancy[]> {
+  try {
+    return await invoke<KennelOccupancy[]>("get_kennel_occupancy");
+  } catch (e) {
+    error(`Failed to retrieve kennel occupancy: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Compares the capacity of the kennels with the number of animals at the shelter and in foster care.
+ *
+ * @returns Promise<CapacityReport | null> - Capacities, counts and utilization. Returns null if the operation fails.
+ */
+export async function getCapacityReport(): Promise<CapacityReport | null> {
+  try {
+    return await invoke<CapacityReport>("get_capacity_report");
+  } catch (e) {
+    error(`Failed to retrieve capacity report: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Assigns an animal to a kennel, moving it out of its current kennel (staff only).
+ *
+ * @param animalId - The ID of the animal to assign
+ * @param kennelId - The ID of the kennel to assign the animal to
+ * @returns Promise<AssignKennelResult | null> - Whether the animal was assigned, or why it was refused. Returns null if the operation fails.
+ */
+export async function assignAnimalToKennel(
+  animalId: string,
+  kennelId: string,
+): Promise<AssignKennelResult | null> {
+  try {
+    return await invoke<AssignKennelResult>("assign_animal_to_kennel", {
+      animalId,
+      kennelId,
+    });
+  } catch (e) {
+    error(`Failed to assign animal to kennel: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Removes an animal from the kennel it is currently assigned to (staff only).
+ *
+ * @param animalId - The ID of the animal to remove
+ * @returns Promise<boolean> - True if the animal was in a kennel and was removed. Returns false if the operation fails.
+ */
+export async function removeAnimalFromKennel(
+  animalId: string,
+): Promise<boolean> {
+  try {
+    return await invoke<boolean>("remove_animal_from_kennel", { animalId });
+  } catch (e) {
+    error(`Failed to remove animal with ID ${animalId} from kennel: ${describeError(e)}`);
+    return false;
+  }
+}
+
+// ==================== LOCATION FUNCTIONS ====================
+
+/**
+ * Creates a new location (staff only).
+ *
+ * @param location - The location data to insert
+ * @returns Promise<string | null> - The ID of the new location. Returns null if the operation fails.
+ */
+export async function createLocation(
+  location: Location,
+): Promise<string | null> {
+  try {
+    return await invoke<string>("create_location", { location });
+  } catch (e) {
+    error(`Failed to create location: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves every location, ordered by name.
+ *
+ * @returns Promise<Location[]> - The locations. Returns an empty array if the operation fails.
+ */
+export async function getLocations(): Promise<Location[]> {
+  try {
+    return await invoke<Location[]>("get_locations");
+  } catch (e) {
+    error(`Failed to retrieve locations: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Updates the name and address of a location (staff only).
+ *
+ * @param location - The updated location data
+ * @returns Promise<boolean> - True if the location was updated. Returns false if the operation fails.
+ */
+export async function updateLocation(location: Location): Promise<boolean> {
+  try {
+    return await invoke<boolean>("update_location", { location });
+  } catch (e) {
+    error(`Failed to update location ${location.id}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Deletes a location that no longer houses any animals (staff only).
+ *
+ * @param locationId - The ID of the location to delete
+ * @returns Promise<boolean> - True if the location was deleted. Returns false if the operation fails.
+ */
+export async function deleteLocation(locationId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("delete_location", { locationId });
+  } catch (e) {
+    error(`Failed to delete location ${locationId}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/** An external endpoint that receives signed event notifications */
+export interface Webhook {
+  /** Unique identifier for the webhook */
+  id: string;
+  /** URL the events are posted to */
+  url: string;
+  /** Secret used to sign the body of each event */
+  secret: string;
+  /** Events the webhook subscribes to */
+  eventTypes: WebhookEvent[];
+  /** Timestamp when the webhook was created */
+  createdTimestamp: number;
+}
+
+/** An event queued for a webhook, along with the outcome of its attempts */
+export interface WebhookDelivery {
+  /** Unique identifier for the delivery */
+  id: string;
+  /** ID of the webhook the event is sent to */
+  webhookId: string;
+  /** The event being sent */
+  event: WebhookEvent;
+  /** JSON body posted to the webhook */
+  payload: string;
+  /** Whether the event was delivered, is pending, or failed */
+  status: WebhookDeliveryStatus;
+  /** Number of attempts made so far */
+  attempts: number;
+  /** HTTP status code of the last response, if one was received */
+  responseCode: number | null;
+  /** Why the last attempt failed, if it did */
+  error: string | null;
+  /** Timestamp when the event was queued */
+  createdTimestamp: number;
+  /** Timestamp of the last attempt, if one was made */
+  attemptedTimestamp: number | null;
+  /** Timestamp after which the next attempt is due */
+  nextAttemptTimestamp: number;
+}
+
+/** An email telling an applicant their request changed status, with its send log */
+export interface EmailMessage {
+  /** Unique identifier for the email */
+  id: string;
+  /** ID of the adoption request the email is about */
+  requestId: string;
+  /** The status the request changed to, which decides the template used */
+  requestStatus: RequestStatus;
+  /** Email address of the applicant */
+  recipient: string;
+  /** Name of the applicant, as when the email was queued */
+  recipientName: string;
+  /** Name of the requested animal, as when the email was queued */
+  animalName: string;
+  /** Subject of the last attempt, if one was made */
+  subject: string | null;
+  /** Body of the last attempt, if one was made */
+  body: string | null;
+  /** Whether the email was sent, is pending, or failed */
+  status: EmailStatus;
+  /** Number of attempts made so far */
+  attempts: number;
+  /** Why the last attempt failed, if it did */
+  error: string | null;
+  /** Timestamp when the email was queued */
+  createdTimestamp: number;
+  /** Timestamp of the last attempt, if one was made */
+  attemptedTimestamp: number | null;
+}
+
+// ==================== TRANSFER FUNCTIONS ====================
+
+/**
+ * Records an animal moving between locations, or to or from a partner organization.
+ *
+ * @param transfer - The transfer to record
+ * @returns Promise<RecordTransferResult | null> - Whether the transfer was recorded, or why it could not be. Returns null if the operation fails.
+ */
+export async function recordTransfer(
+  transfer: Transfer,
+): Promise<RecordTransferResult | null> {
+  try {
+    return await invoke<RecordTransferResult>("record_transfer", { transfer });
+  } catch (e) {
+    error(`Failed to record transfer: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves the transfer history of a specific animal, most recent first.
+ *
+ * @param animalId - The ID of the animal to retrieve transfers for
+ * @returns Promise<Transfer[]> - List of transfers. Returns an empty array if the operation fails.
+ */
+export async function getTransferHistory(
+  animalId: string,
+): Promise<Transfer[]> {
+  try {
+    return await invoke<Transfer[]>("get_transfer_history", { animalId });
+  } catch (e) {
+    error(`Failed to retrieve transfer history for animal ID ${animalId}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+// ==================== DECEASED RECORD FUNCTIONS ====================
+
+/**
+ * Records that an animal passed away or was euthanized, marking it as passed away and
+ * rejecting its pending adoption requests, whose applicants are emailed (staff only).
+ *
+ * @param record - The date, cause, who authorized it and any notes
+ * @returns Promise<RecordDeathResult | null> - Whether the death was recorded, or why it could not be. Returns null if the operation fails.
+ */
+export async function recordDeath(
+  record: DeceasedRecord,
+): Promise<RecordDeathResult | null> {
+  try {
+    return await invoke<RecordDeathResult>("record_death", { record });
+  } catch (e) {
+    error(`Failed to record death of animal with ID ${record.animalId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== INVENTORY FUNCTIONS ====================
+
+/**
+ * Adds a supply to the inventory (staff only).
+ *
+ * @param item - The item to add, with its starting stock
+ * @returns Promise<string | null> - The ID of the created item. Returns null if the operation fails.
+ */
+export async function createInventoryItem(item: InventoryItem): Promise<string | null> {
+  try {
+    return await invoke<string>("create_inventory_item", { item });
+  } catch (e) {
+    error(`Failed to create inventory item: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves every supply in the inventory (staff only).
+ *
+ * @returns Promise<InventoryItem[]> - Items ordered by name. Returns an empty array if the operation fails.
+ */
+export async function getInventoryItems(): Promise<InventoryItem[]> {
+  try {
+    return await invoke<InventoryItem[]>("get_inventory_items");
+  } catch (e) {
+    error(`Failed to retrieve inventory items: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Updates the name, unit and reorder threshold of a supply (staff only). The quantity on
+ * hand only changes through adjustStock.
+ *
+ * @param item - The updated item
+ * @returns Promise<boolean> - True if the item was found and updated. Returns false if the operation fails.
+ */
+export async function updateInventoryItem(item: InventoryItem): Promise<boolean> {
+  try {
+    return await invoke<boolean>("update_inventory_item", { item });
+  } catch (e) {
+    error(`Failed to update inventory item with ID ${item.id}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Deletes a supply that is no longer stocked (staff only).
+ *
+ * @param itemId - The ID of the item to delete
+ * @returns Promise<boolean> - True if the item was found and deleted. Returns false if the operation fails.
+ */
+export async function deleteInventoryItem(itemId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("delete_inventory_item", { itemId });
+  } catch (e) {
+    error(`Failed to delete inventory item with ID ${itemId}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Adds to or takes from the stock of a supply, recording why (staff only).
+ *
+ * @param itemId - The ID of the item to adjust
+ * @param quantityChange - Quantity to add, negative to take stock away
+ * @param reason - Why the stock changed, such as a delivery or expired medicine
+ * @returns Promise<AdjustStockResult | null> - The adjusted item, or why the stock could not be adjusted. Returns null if the operation fails.
+ */
+export async function adjustStock(
+  itemId: string,
+  quantityChange: number,
+  reason: string,
+): Promise<AdjustStockResult | null> {
+  try {
+    return await invoke<AdjustStockResult>("adjust_stock", {
+      itemId,
+      quantityChange,
+      reason,
+    });
+  } catch (e) {
+    error(`Failed to adjust stock of inventory item with ID ${itemId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves the changes made to the stock of a supply (staff only).
+ *
+ * @param itemId - The ID of the item to retrieve the adjustments for
+ * @returns Promise<InventoryAdjustment[]> - Adjustments, most recent first. Returns an empty array if the operation fails.
+ */
+export async function getInventoryAdjustments(itemId: string): Promise<InventoryAdjustment[]> {
+  try {
+    return await invoke<InventoryAdjustment[]>("get_inventory_adjustments", { itemId });
+  } catch (e) {
+    error(`Failed to retrieve adjustments of inventory item with ID ${itemId}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Retrieves the low-stock report: the supplies at or below their reorder threshold (staff only).
+ *
+ * @returns Promise<InventoryItem[]> - Items to reorder, the most depleted first. Returns an empty array if the operation fails.
+ */
+export async function getLowStockItems(): Promise<InventoryItem[]> {
+  try {
+    return await invoke<InventoryItem[]>("get_low_stock_items");
+  } catch (e) {
+    error(`Failed to retrieve low stock items: ${describeError(e)}`);
+    return [];
+  }
+}
+
+// ==================== EXPENSE FUNCTIONS ====================
+
+/**
+ * Records money spent by the shelter (staff only).
+ *
+ * @param expense - The expense to record
+ * @returns Promise<string | null> - The ID of the created expense. Returns null if the operation fails.
+ */
+export async function createExpense(expense: Expense): Promise<string | null> {
+  try {
+    return await invoke<string>("create_expense", { expense });
+  } catch (e) {
+    error(`Failed to create expense: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves the expenses made during a period (staff only).
+ *
+ * @param fromTimestamp - Start of the period, inclusive
+ * @param toTimestamp - End of the period, exclusive
+ * @returns Promise<Expense[]> - Expenses, most recent first. Returns an empty array if the operation fails.
+ */
+export async function getExpenses(fromTimestamp: number, toTimestamp: number): Promise<Expense[]> {
+  try {
+    return await invoke<Expense[]>("get_expenses", { fromTimestamp, toTimestamp });
+  } catch (e) {
+    error(`Failed to retrieve expenses: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Updates an expense (staff only).
+ *
+ * @param expense - The updated expense
+ * @returns Promise<boolean> - True if the expense was found and updated. Returns false if the operation fails.
+ */
+export async function updateExpense(expense: Expense): Promise<boolean> {
+  try {
+    return await invoke<boolean>("update_expense", { expense });
+  } catch (e) {
+    error(`Failed to update expense with ID ${expense.id}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Deletes an expense recorded by mistake (staff only).
+ *
+ * @param expenseId - The ID of the expense to delete
+ * @returns Promise<boolean> - True if the expense was found and deleted. Returns false if the operation fails.
+ */
+export async function deleteExpense(expenseId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("delete_expense", { expenseId });
+  } catch (e) {
+    error(`Failed to delete expense with ID ${expenseId}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Generates the budget report: how much was spent in each month of a year, by category,
+ * against how much was donated (staff only).
+ *
+ * @param year - The year to report on
+ * @returns Promise<BudgetReport | null> - Expenses, donations and net of each of the twelve months. Returns null if the operation fails.
+ */
+export async function getBudgetReport(year: number): Promise<BudgetReport | null> {
+  try {
+    return await invoke<BudgetReport>("get_budget_report", { year });
+  } catch (e) {
+    error(`Failed to generate budget report for ${year}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== PAYMENT FUNCTIONS ====================
+
+/**
+ * Retrieves the fees charged for an adoption request (staff only).
+ *
+ * @param requestId - The ID of the adoption request
+ * @returns Promise<Payment[]> - Payments, in the order they were charged. Returns an empty array if the operation fails.
+ */
+export async function getPayments(requestId: string): Promise<Payment[]> {
+  try {
+    return await invoke<Payment[]>("get_payments", { requestId });
+  } catch (e) {
+    error(`Failed to retrieve payments for adoption request with ID ${requestId}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Charges a fee for an adoption request, such as the adoption fee (staff only).
+ *
+ * @param requestId - The ID of the adoption request
+ * @param amount - Amount charged, in minor units of the shelter's default currency, such as satang
+ * @param description - What the fee is for
+ * @returns Promise<RecordPaymentResult | null> - Recorded, or RequestNotFound. Returns null if the operation fails.
+ */
+export async function recordAdoptionFee(
+  requestId: string,
+  amount: number,
+  description: string,
+): Promise<RecordPaymentResult | null> {
+  try {
+    return await invoke<RecordPaymentResult>("record_adoption_fee", {
+      requestId,
+      amount,
+      description,
+    });
+  } catch (e) {
+    error(`Failed to record fee for adoption request with ID ${requestId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Records that a fee was paid, so a receipt can be generated for it (staff only).
+ *
+ * @param paymentId - The ID of the payment
+ * @param paidTimestamp - Timestamp when the fee was paid
+ * @returns Promise<Payment | null> - The updated payment. Returns null if the operation fails.
+ */
+export async function markPaymentPaid(
+  paymentId: string,
+  paidTimestamp: number,
+): Promise<Payment | null> {
+  try {
+    return await invoke<Payment>("mark_payment_paid", { paymentId, paidTimestamp });
+  } catch (e) {
+    error(`Failed to record payment with ID ${paymentId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== DONATION FUNCTIONS ====================
+
+/**
+ * Records a donation made by a donor in the contacts directory (staff only).
+ *
+ * @param donation - The donation to record
+ * @returns Promise<string | null> - The ID of the recorded donation. Returns null if the operation fails.
+ */
+export async function createDonation(donation: Donation): Promise<string | null> {
+  try {
+    return await invoke<string>("create_donation", { donation });
+  } catch (e) {
+    error(`Failed to record donation: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves the donations made by a donor (staff only).
+ *
+ * @param contactId - The ID of the donor's contact
+ * @returns Promise<Donation[]> - Donations, most recent first. Returns an empty array if the operation fails.
+ */
+export async function getDonations(contactId: string): Promise<Donation[]> {
+  try {
+    return await invoke<Donation[]>("get_donations", { contactId });
+  } catch (e) {
+    error(`Failed to retrieve donations from contact with ID ${contactId}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+// ==================== CONTACT FUNCTIONS ====================
+
+/**
+ * Adds a vet, rescue or transport partner to the contacts directory (staff only).
+ *
+ * @param contact - The contact to add
+ * @returns Promise<string | null> - The ID of the created contact. Returns null if the operation fails.
+ */
+export async function createContact(contact: Contact): Promise<string | null> {
+  try {
+    return await invoke<string>("create_contact", { contact });
+  } catch (e) {
+    error(`Failed to create contact: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves the contacts directory (staff only).
+ *
+ * @param kind - Only retrieve contacts of this kind, or null for every contact
+ * @returns Promise<Contact[]> - Contacts ordered by name. Returns an empty array if the operation fails.
+ */
+export async function getContacts(kind: ContactKind | null = null): Promise<Contact[]> {
+  try {
+    return await invoke<Contact[]>("get_contacts", { kind });
+  } catch (e) {
+    error(`Failed to retrieve contacts: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Updates the details of a contact (staff only).
+ *
+ * @param contact - The updated contact
+ * @returns Promise<boolean> - True if the contact was found and updated. Returns false if the operation fails.
+ */
+export async function updateContact(contact: Contact): Promise<boolean> {
+  try {
+    return await invoke<boolean>("update_contact", { contact });
+  } catch (e) {
+    error(`Failed to update contact with ID ${contact.id}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Deletes a contact, unlinking the vaccinations and transfers it took part in (staff only).
+ *
+ * @param contactId - The ID of the contact to delete
+ * @returns Promise<boolean> - True if the contact was found and deleted. Returns false if the operation fails.
+ */
+export async function deleteContact(contactId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("delete_contact", { contactId });
+  } catch (e) {
+    error(`Failed to delete contact with ID ${contactId}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Links a recorded vaccination to the veterinarian who gave it (staff only).
+ *
+ * @param vaccinationId - The ID of the vaccination
+ * @param contactId - The ID of the contact, or null to unlink the vaccination
+ * @returns Promise<boolean> - True if the vaccination was found and linked. Returns false if the operation fails.
+ */
+export async function linkVaccinationToContact(
+  vaccinationId: string,
+  contactId: string | null,
+): Promise<boolean> {
+  try {
+    return await invoke<boolean>("link_vaccination_to_contact", { vaccinationId, contactId });
+  } catch (e) {
+    error(`Failed to link vaccination with ID ${vaccinationId} to contact: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Links a recorded transfer to the contact at the partner organization or transport partner
+ * (staff only).
+ *
+ * @param transferId - The ID of the transfer
+ * @param contactId - The ID of the contact, or null to unlink the transfer
+ * @returns Promise<boolean> - True if the transfer was found and linked. Returns false if the operation fails.
+ */
+export async function linkTransferToContact(
+  transferId: string,
+  contactId: string | null,
+): Promise<boolean> {
+  try {
+    return await invoke<boolean>("link_transfer_to_contact", { transferId, contactId });
+  } catch (e) {
+    error(`Failed to link transfer with ID ${transferId} to contact: ${describeError(e)}`);
+    return false;
+  }
+}
+
+// ==================== APPOINTMENT FUNCTIONS ====================
+
+/**
+ * Books a slot for the logged-in user to meet an animal, waiting for staff to confirm it.
+ *
+ * @param animalId - The ID of the animal to meet
+ * @param slotTimestamp - Timestamp when the appointment starts
+ * @returns Promise<RequestAppointmentResult | null> - Requested, or why the slot could not be booked. Returns null if the operation fails.
+ */
+export async function requestAppointment(
+  animalId: string,
+  slotTimestamp: number,
+): Promise<RequestAppointmentResult | null> {
+  try {
+    return await invoke<RequestAppointmentResult>("request_appointment", {
+      animalId,
+      slotTimestamp,
+    });
+  } catch (e) {
+    error(`Failed to book appointment with animal ID ${animalId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves the appointments the logged-in user has booked.
+ *
+ * @returns Promise<Appointment[]> - Appointments, latest slot first. Returns an empty array if the operation fails.
+ */
+export async function getMyAppointments(): Promise<Appointment[]> {
+  try {
+    return await invoke<Appointment[]>("get_my_appointments");
+  } catch (e) {
+    error(`Failed to retrieve appointments: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Retrieves the appointments starting during a period (staff only).
+ *
+ * @param fromTimestamp - Start of the period, inclusive
+ * @param toTimestamp - End of the period, exclusive
+ * @returns Promise<Appointment[]> - Appointments, earliest slot first. Returns an empty array if the operation fails.
+ */
+export async function getAppointments(
+  fromTimestamp: number,
+  toTimestamp: number,
+): Promise<Appointment[]> {
+  try {
+    return await invoke<Appointment[]>("get_appointments", { fromTimestamp, toTimestamp });
+  } catch (e) {
+    error(`Failed to retrieve appointments: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Confirms a requested appointment and notifies the customer (staff only).
+ *
+ * @param appointmentId - The ID of the appointment
+ * @returns Promise<ConfirmAppointmentResult | null> - Confirmed, or why the appointment could not be confirmed. Returns null if the operation fails.
+ */
+export async function confirmAppointment(
+  appointmentId: string,
+): Promise<ConfirmAppointmentResult | null> {
+  try {
+    return await invoke<ConfirmAppointmentResult>("confirm_appointment", { appointmentId });
+  } catch (e) {
+    error(`Failed to confirm appointment with ID ${appointmentId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Cancels an appointment. Customers can only cancel their own appointments; when staff
+ * cancel one, the customer is notified.
+ *
+ * @param appointmentId - The ID of the appointment
+ * @returns Promise<CancelAppointmentResult | null> - Cancelled, or why the appointment could not be cancelled. Returns null if the operation fails.
+ */
+export async function cancelAppointment(
+  appointmentId: string,
+): Promise<CancelAppointmentResult | null> {
+  try {
+    return await invoke<CancelAppointmentResult>("cancel_appointment", { appointmentId });
+  } catch (e) {
+    error(`Failed to cancel appointment with ID ${appointmentId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== SURGERY FUNCTIONS ====================
+
+/**
+ * Books a spay or neuter surgery for an animal that is not neutered yet (staff only).
+ *
+ * @param animalId - The ID of the animal having the surgery
+ * @param scheduledTimestamp - Timestamp when the surgery is booked for
+ * @param contactId - The ID of the veterinarian performing the surgery, or null if not known
+ * @param notes - Anything worth knowing about the surgery
+ * @returns Promise<ScheduleSurgeryResult | null> - Scheduled, or why the surgery could not be booked. Returns null if the operation fails.
+ */
+export async function scheduleSurgery(
+  animalId: string,
+  scheduledTimestamp: number,
+  contactId: string | null = null,
+  notes = "",
+): Promise<ScheduleSurgeryResult | null> {
+  try {
+    return await invoke<ScheduleSurgeryResult>("schedule_surgery", {
+      animalId,
+      scheduledTimestamp,
+      contactId,
+      notes,
+    });
+  } catch (e) {
+    error(`Failed to schedule surgery for animal with ID ${animalId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves the surgeries booked for a specific animal (staff only).
+ *
+ * @param animalId - The ID of the animal
+ * @returns Promise<Surgery[]> - Surgeries, latest booking first. Returns an empty array if the operation fails.
+ */
+export async function getSurgeries(animalId: string): Promise<Surgery[]> {
+  try {
+    return await invoke<Surgery[]>("get_surgeries", { animalId });
+  } catch (e) {
+    error(`Failed to retrieve surgeries for animal with ID ${animalId}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Retrieves the report of surgeries that have not been performed yet (staff only).
+ *
+ * @returns Promise<Surgery[]> - Surgeries, earliest booking first so overdue ones lead. Returns an empty array if the operation fails.
+ */
+export async function getUpcomingSurgeries(): Promise<Surgery[]> {
+  try {
+    return await invoke<Surgery[]>("get_upcoming_surgeries");
+  } catch (e) {
+    error(`Failed to retrieve upcoming surgeries: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Records that a surgery was performed, marking the animal as neutered (staff only).
+ *
+ * @param surgeryId - The ID of the surgery
+ * @param completedTimestamp - Timestamp when the surgery was performed
+ * @returns Promise<CompleteSurgeryResult | null> - The completed surgery, or why it could not be recorded. Returns null if the operation fails.
+ */
+export async function completeSurgery(
+  surgeryId: string,
+  completedTimestamp: number,
+): Promise<CompleteSurgeryResult | null> {
+  try {
+    return await invoke<CompleteSurgeryResult>("complete_surgery", {
+      surgeryId,
+      completedTimestamp,
+    });
+  } catch (e) {
+    error(`Failed to complete surgery with ID ${surgeryId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Cancels a surgery that has not been performed yet (staff only).
+ *
+ * @param surgeryId - The ID of the surgery
+ * @returns Promise<boolean> - True if a surgery still to come was found and cancelled. Returns false if the operation fails.
+ */
+export async function cancelSurgery(surgeryId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("cancel_surgery", { surgeryId });
+  } catch (e) {
+    error(`Failed to cancel surgery with ID ${surgeryId}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+// ==================== INTEGRITY FUNCTIONS ====================
+
+/**
+ * Checks the database and image files for corruption and dangling references.
+ *
+ * @returns Promise<IntegrityReport | null> - The problems found. Returns null if the operation fails.
+ */
+export async function checkIntegrity(): Promise<IntegrityReport | null> {
+  try {
+    return await invoke<IntegrityReport>("check_integrity");
+  } catch (e) {
+    error(`Failed to check integrity: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Compacts the shelter and authentication databases and refreshes their statistics.
+ *
+ * @returns Promise<MaintenanceResult[]> - The space reclaimed from each database. Returns an empty array if the operation fails.
+ */
+export async function runDatabaseMaintenance(): Promise<MaintenanceResult[]> {
+  try {
+    return await invoke<MaintenanceResult[]>("run_database_maintenance");
+  } catch (e) {
+    error(`Failed to run database maintenance: ${describeError(e)}`);
+    return [];
+  }
+}
+
+// ==================== TRASH FUNCTIONS ====================
+
+/**
+ * Retrieves the deleted animals and adoption requests waiting in the trash.
+ *
+ * @returns Promise<TrashItem[]> - Deleted records, most recently deleted first. Returns an empty array if the operation fails.
+ */
+export async function getTrash(): Promise<TrashItem[]> {
+  try {
+    return await invoke<TrashItem[]>("get_trash");
+  } catch (e) {
+    error(`Failed to retrieve trash: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Undoes the deletion of an animal or adoption request.
+ *
+ * @param item - The record in the trash to restore
+ * @returns Promise<boolean> - True if the record was restored, false if it is no longer in the trash. Returns false if the operation fails.
+ */
+export async function restoreFromTrash(item: TrashItem): Promise<boolean> {
+  try {
+    return await invoke<boolean>("restore_from_trash", {
+      itemType: item.itemType,
+      id: item.id,
+    });
+  } catch (e) {
+    error(`Failed to restore record with ID ${item.id}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Permanently removes records that have expired from the trash, along with their files
+ * (staff only). The backend also does this on its own once a day.
+ *
+ * @returns Promise<number> - The number of records purged. Returns 0 if the operation fails.
+ */
+export async function purgeTrash(): Promise<number> {
+  try {
+    return await invoke<number>("purge_trash");
+  } catch (e) {
+    error(`Failed to purge trash: ${describeError(e)}`);
+    return 0;
+  }
+}
+
+// ==================== DEMO DATA FUNCTIONS ====================
+
+/**
+ * Populates an empty shelter with demo animals, accounts, and adoption requests.
+ * Only available in development and demo builds.
+ *
+ * @returns Promise<DemoDataSummary | null> - The records created. Returns null if the operation fails.
+ */
+export async function seedDemoData(): Promise<DemoDataSummary | null> {
+  try {
+    return await invoke<DemoDataSummary>("seed_demo_data");
+  } catch (e) {
+    error(`Failed to seed demo data: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== SYNC FUNCTIONS ====================
+
+/**
+ * Exports the changes made on this installation as a changelog file, saved where the user chooses.
+ * The file is written by a background job.
+ *
+ * @param sinceTimestamp - Only export changes made at or after this timestamp, or null for everything
+ * @returns Promise<string | null> - The path the changelog was saved to. Returns null if cancelled or if the operation fails.
+ */
+export async function exportChanges(
+  sinceTimestamp: number | null = null,
+): Promise<string | null> {
+  try {
+    return await runJob<string>({
+      type: JobKind.CHANGES_EXPORT,
+      sinceTimestamp,
+    });
+  } catch (e) {
+    error(`Failed to export changes: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Imports a changelog file exported by another installation, chosen by the user.
+ * The changes are applied by a background job.
+ *
+ * @returns Promise<ImportChangesResult | null> - How many changes were applied, skipped and deleted. Returns null if cancelled or if the operation fails.
+ */
+export async function importChanges(): Promise<ImportChangesResult | null> {
+  try {
+    return await runJob<ImportChangesResult>({ type: JobKind.CHANGES_IMPORT });
+  } catch (e) {
+    error(`Failed to import changes: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== WEBHOOK FUNCTIONS ====================
+
+/**
+ * Registers a webhook that receives signed notifications of the events it subscribes to.
+ *
+ * @param url - URL the events are posted to
+ * @param secret - Secret used to sign the body of each event
+ * @param eventTypes - Events the webhook subscribes to
+ * @returns Promise<string | null> - The ID of the created webhook. Returns null if the operation fails.
+ */
+export async function createWebhook(
+  url: string,
+  secret: string,
+  eventTypes: WebhookEvent[],
+): Promise<string | null> {
+  try {
+    return await invoke<string>("create_webhook", { url, secret, eventTypes });
+  } catch (e) {
+    error(`Failed to create webhook: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves every registered webhook.
+ *
+ * @returns Promise<Webhook[]> - Webhooks ordered by creation time. Returns an empty array if the operation fails.
+ */
+export async function getWebhooks(): Promise<Webhook[]> {
+  try {
+    return await invoke<Webhook[]>("get_webhooks");
+  } catch (e) {
+    error(`Failed to retrieve webhooks: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Deletes a webhook along with its delivery log.
+ *
+ * @param webhookId - The ID of the webhook to delete
+ * @returns Promise<boolean> - True if the webhook was deleted, false if not found or if the operation fails.
+ */
+export async function deleteWebhook(webhookId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("delete_webhook", { webhookId });
+  } catch (e) {
+    error(`Failed to delete webhook with ID ${webhookId}: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Retrieves the delivery log of a webhook, most recent first.
+ *
+ * @param webhookId - The ID of the webhook
+ * @returns Promise<WebhookDelivery[]> - List of deliveries. Returns an empty array if the operation fails.
+ */
+export async function getWebhookDeliveries(
+  webhookId: string,
+): Promise<WebhookDelivery[]> {
+  try {
+    return await invoke<WebhookDelivery[]>("get_webhook_deliveries", {
+      webhookId,
+    });
+  } catch (e) {
+    error(`Failed to retrieve deliveries for webhook ID ${webhookId}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+// ==================== EMAIL FUNCTIONS ====================
+
+/**
+ * Retrieves the send log of the emails to applicants, most recent first.
+ *
+ * @returns Promise<EmailMessage[]> - List of emails. Returns an empty array if the operation fails.
+ */
+export async function getEmails(): Promise<EmailMessage[]> {
+  try {
+    return await invoke<EmailMessage[]>("get_emails");
+  } catch (e) {
+    error(`Failed to retrieve emails: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Sends an email to an applicant again, with the current SMTP settings and templates.
+ *
+ * @param emailId - The ID of the email to resend
+ * @returns Promise<EmailMessage | null> - The email after the attempt, whose status tells whether it was sent. Returns null if the email does not exist.
+ * @throws The reason the attempt could not be made, such as the email still being queued
+ */
+export async function resendEmail(
+  emailId: string,
+): Promise<EmailMessage | null> {
+  try {
+    return await invoke<EmailMessage | null>("resend_email", { emailId });
+  } catch (e) {
+    error(`Failed to resend email ${emailId}: ${describeError(e)}`);
+    throw e;
+  }
+}
+
+// ==================== FAVORITE FUNCTIONS ====================
+
+/**
+ * Adds an animal to the logged-in user's favorites.
+ *
+ * @param animalId - The ID of the animal to favorite
+ * @returns Promise<boolean> - True if the favorite was added, false if it already existed. Returns false if the operation fails.
+ */
+export async function addFavorite(animalId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("add_favorite", { animalId });
+  } catch (e) {
+    error(`Failed to add animal ${animalId} to favorites: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Removes an animal from the logged-in user's favorites.
+ *
+ * @param animalId - The ID of the favorited animal
+ * @returns Promise<boolean> - True if the favorite was removed, false if not found. Returns false if the operation fails.
+ */
+export async function removeFavorite(animalId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("remove_favorite", { animalId });
+  } catch (e) {
+    error(`Failed to remove animal ${animalId} from favorites: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Retrieves the animals the logged-in user has favorited, most recent first.
+ *
+ * @returns Promise<AnimalSummary[]> - List of favorited animals. Returns an empty array if the operation fails.
+ */
+export async function getFavorites(): Promise<AnimalSummary[]> {
+  try {
+    return await invoke<AnimalSummary[]>("get_favorites");
+  } catch (e) {
+    error(`Failed to retrieve favorites: ${describeError(e)}`);
+    return [];
+  }
+}
+
+// ==================== PROFILE FUNCTIONS ====================
+
+/**
+ * Retrieves the logged-in user's profile.
+ *
+ * @returns Promise<UserProfile | null> - The profile. Returns null if the user has not saved one or the operation fails.
+ */
+export async function getProfile(): Promise<UserProfile | null> {
+  try {
+    return await invoke<UserProfile | null>("get_profile");
+  } catch (e) {
+    error(`Failed to retrieve profile: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Creates or replaces the logged-in user's profile.
+ *
+ * @param profile - The profile data; its username and update time are set by the backend
+ * @returns Promise<UserProfile | null> - The stored profile. Returns null if the operation fails.
+ */
+export async function updateProfile(
+  profile: UserProfile,
+): Promise<UserProfile | null> {
+  try {
+    return await invoke<UserProfile>("update_profile", { profile });
+  } catch (e) {
+    error(`Failed to update profile: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== PRIVACY FUNCTIONS ====================
+
+/**
+ * Saves everything stored about the logged-in user, their profile, adoption requests
+ * and favorites, as a JSON file where the user chooses.
+ *
+ * @returns Promise<string | null> - The path the data was saved to, or null if canceled or the operation fails.
+ */
+export async function exportMyData(): Promise<string | null> {
+  try {
+    return await invoke<string | null>("export_my_data");
+  } catch (e) {
+    error(`Failed to export your data: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Asks for the logged-in customer's personal data to be erased. Nothing is erased
+ * until an admin approves the request.
+ *
+ * @returns Promise<CreateErasureResult | null> - The stored request, or the one already waiting for review. Returns null if the operation fails.
+ */
+export async function requestErasure(): Promise<CreateErasureResult | null> {
+  try {
+    return await invoke<CreateErasureResult>("request_erasure");
+  } catch (e) {
+    error(`Failed to request erasure of your data: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves the customers' requests to erase their data, oldest first (admin only).
+ *
+ * @param status - Only retrieve requests with this status, or all requests if omitted
+ * @returns Promise<ErasureRequest[]> - List of erasure requests. Returns an empty array if the operation fails.
+ */
+export async function getErasureRequests(
+  status?: ErasureRequestStatus,
+): Promise<ErasureRequest[]> {
+  try {
+    return await invoke<ErasureRequest[]>("get_erasure_requests", {
+      status: status ?? null,
+    });
+  } catch (e) {
+    error(`Failed to retrieve erasure requests: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Approves a customer's request to erase their data, deleting their account, profile
+ * and favorites and anonymizing their adoption requests (admin only).
+ *
+ * @param requestId - The ID of the erasure request to approve
+ * @returns Promise<ReviewErasureResult | null> - The approved request, or why it could not be approved. Returns null if the operation fails.
+ */
+export async function approveErasureRequest(
+  requestId: string,
+): Promise<ReviewErasureResult | null> {
+  try {
+    return await invoke<ReviewErasureResult>("approve_erasure_request", {
+      requestId,
+    });
+  } catch (e) {
+    error(`Failed to approve erasure request ${requestId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Rejects a customer's request to erase their data (admin only).
+ *
+ * @param requestId - The ID of the erasure request to reject
+ * @returns Promise<ReviewErasureResult | null> - The rejected request, or why it could not be rejected. Returns null if the operation fails.
+ */
+export async function rejectErasureRequest(
+  requestId: string,
+): Promise<ReviewErasureResult | null> {
+  try {
+    return await invoke<ReviewErasureResult>("reject_erasure_request", {
+      requestId,
+    });
+  } catch (e) {
+    error(`Failed to reject erasure request ${requestId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== BANNED ADOPTER FUNCTIONS ====================
+
+/**
+ * Retrieves every banned adopter.
+ *
+ * @returns Promise<BannedAdopter[]> - List of banned adopters, most recently added first. Returns an empty array if the operation fails.
+ */
+export async function getBannedAdopters(): Promise<BannedAdopter[]> {
+  try {
+    return await invoke<BannedAdopter[]>("get_banned_adopters");
+  } catch (e) {
+    error(`Failed to retrieve banned adopters: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Bans a person from adopting.
+ *
+ * @param banned - The patterns and reason; the author and time are set by the backend
+ * @returns Promise<string | null> - The ID of the new entry. Returns null if the operation fails.
+ */
+export async function addBannedAdopter(
+  banned: BannedAdopter,
+): Promise<string | null> {
+  try {
+    return await invoke<string>("add_banned_adopter", { banned });
+  } catch (e) {
+    error(`Failed to add banned adopter: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Lifts a ban.
+ *
+ * @param bannedId - The ID of the entry to remove
+ * @returns Promise<boolean> - True if the entry was found and removed. Returns false if the operation fails.
+ */
+export async function removeBannedAdopter(bannedId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("remove_banned_adopter", { bannedId });
+  } catch (e) {
+    error(`Failed to remove banned adopter: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Checks an adoption request against the banned adopters.
+ *
+ * @param requestId - The ID of the adoption request to check
+ * @returns Promise<BannedAdopter[]> - The banned adopters matching the requester. Returns an empty array if the operation fails.
+ */
+export async function getBannedAdopterMatches(
+  requestId: string,
+): Promise<BannedAdopter[]> {
+  try {
+    return await invoke<BannedAdopter[]>("get_banned_adopter_matches", {
+      requestId,
+    });
+  } catch (e) {
+    error(`Failed to check banned adopters: ${describeError(e)}`);
+    return [];
+  }
+}
+
+// ==================== WAITLIST FUNCTIONS ====================
+
+/**
+ * Adds the logged-in user to the waitlist for a species, and optionally a breed.
+ *
+ * @param specie - Species to wait for
+ * @param breed - Breed to wait for, or null for any breed of the species
+ * @returns Promise<JoinWaitlistResult | null> - Whether the user joined or was already waiting. Returns null if the operation fails.
+ */
+export async function joinWaitlist(
+  specie: string,
+  breed: string | null,
+): Promise<JoinWaitlistResult | null> {
+  try {
+    return await invoke<JoinWaitlistResult>("join_waitlist", { specie, breed });
+  } catch (e) {
+    error(`Failed to join waitlist: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Removes one of the logged-in user's waitlist entries.
+ *
+ * @param entryId - The ID of the waitlist entry to remove
+ * @returns Promise<boolean> - True if the entry was found and removed. Returns false if the operation fails.
+ */
+export async function leaveWaitlist(entryId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("leave_waitlist", { entryId });
+  } catch (e) {
+    error(`Failed to leave waitlist: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Retrieves the logged-in user's waitlist entries, most recent first.
+ *
+ * @returns Promise<WaitlistEntry[]> - List of waitlist entries. Returns an empty array if the operation fails.
+ */
+export async function getWaitlist(): Promise<WaitlistEntry[]> {
+  try {
+    return await invoke<WaitlistEntry[]>("get_waitlist");
+  } catch (e) {
+    error(`Failed to retrieve waitlist: ${describeError(e)}`);
+    return [];
+  }
+}
+
+// ==================== DATA CHANGE FUNCTIONS ====================
+
+/**
+ * Calls a handler whenever one of the given records changes, so open pages can refresh.
+ *
+ * @param events - The data change events to listen for
+ * @param handler - Called with the event and the ID of the changed record
+ * @returns Promise<UnlistenFn> - Stops listening when called
+ */
+export async function onDataChange(
+  events: DataChangeEvent[],
+  handler: (event: DataChangeEvent, id: string) => void,
+): Promise<UnlistenFn> {
+  const unlisteners = await Promise.all(
+    events.map((event) =>
+      listen<string>(event, (changed) => handler(event, changed.payload)),
+    ),
+  );
+  return () => unlisteners.forEach((unlisten) => unlisten());
+}
+
+// ==================== NOTIFICATION FUNCTIONS ====================
+
+/** Name of the event emitted by the backend when notifications are created */
+export const NOTIFICATIONS_CREATED_EVENT = "notifications-created";
+
+/**
+ * Retrieves the logged-in user's notifications, most recent first.
+ *
+ * @returns Promise<Notification[]> - List of notifications. Returns an empty array if the operation fails.
+ */
+export async function getNotifications(): Promise<Notification[]> {
+  try {
+    return await invoke<Notification[]>("get_notifications");
+  } catch (e) {
+    error(`Failed to retrieve notifications: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Marks one of the logged-in user's notifications as read.
+ *
+ * @param notificationId - The ID of the notification to mark
+ * @returns Promise<boolean> - True if the notification was found and marked. Returns false if the operation fails.
+ */
+export async function markNotificationRead(
+  notificationId: string,
+): Promise<boolean> {
+  try {
+    return await invoke<boolean>("mark_notification_read", { notificationId });
+  } catch (e) {
+    error(`Failed to mark notification as read: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Checks the reminder rules right away instead of waiting for the scheduler.
+ *
+ * @returns Promise<number | null> - The number of notifications created. Returns null if the operation fails.
+ */
+export async function checkReminders(): Promise<number | null> {
+  try {
+    return await invoke<number>("check_reminders");
+  } catch (e) {
+    error(`Failed to check reminders: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves the available animals that have been in the shelter's care for a long time.
+ *
+ * @param minDays - Days in care an animal must have reached, or null for the length of stay set for reminders
+ * @returns Promise<LongStayAnimal[] | null> - The animals, longest staying first. Returns null if the operation fails.
+ */
+export async function getLongStayAnimals(
+  minDays: number | null = null,
+): Promise<LongStayAnimal[] | null> {
+  try {
+    return await invoke<LongStayAnimal[]>("get_long_stay_animals", { minDays });
+  } catch (e) {
+    error(`Failed to retrieve long-staying animals: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== BONDED ANIMAL FUNCTIONS ====================
+
+/**
+ * Retrieves the animals bonded to a specific animal.
+ *
+ * @param animalId - The ID of the animal to retrieve bonded animals for
+ * @returns Promise<AnimalSummary[]> - List of bonded animals. Returns an empty array if the operation fails.
+ */
+export async function getBondedAnimals(
+  animalId: string,
+): Promise<AnimalSummary[]> {
+  try {
+    return await invoke<AnimalSummary[]>("get_bonded_animals", { animalId });
+  } catch (e) {
+    error(`Failed to retrieve bonded animals for animal ID ${animalId}: ${describeError(e)}`);
+    return [];
+  }
+}
+
+/**
+ * Bonds two animals so they are adopted together (staff only).
+ *
+ * @param animalId - The ID of one animal
+ * @param otherAnimalId - The ID of the other animal
+ * @returns Promise<boolean> - True if the bond was created, false if it already existed. Returns false if the operation fails.
+ */
+export async function linkBondedAnimals(
+  animalId: string,
+  otherAnimalId: string,
+): Promise<boolean> {
+  try {
+    return await invoke<boolean>("link_bonded_animals", {
+      animalId,
+      otherAnimalId,
+    });
+  } catch (e) {
+    error(`Failed to bond animals: ${describeError(e)}`);
+    return false;
+  }
+}
+
+/**
+ * Removes the bond between two animals (staff only).
+ *
+ * @param animalId - The ID of one animal
+ * @param otherAnimalId - The ID of the other animal
+ * @returns Promise<boolean> - True if the bond was removed, false if not found. Returns false if the operation fails.
+ */
+export async function unlinkBondedAnimals(
+  animalId: string,
+  otherAnimalId: string,
+): Promise<boolean> {
+  try {
+    return await invoke<boolean>("unlink_bonded_animals", {
+      animalId,
+      otherAnimalId,
+    });
+  } catch (e) {
+    error(`Failed to remove bond between animals: ${describeError(e)}`);
+    return false;
+  }
+}
+
+// ==================== STATISTICS FUNCTIONS ====================
+
+/**
+ * Retrieves aggregate statistics about the shelter for the dashboard.
+ *
+ * @param locationId - ID of the location to limit the statistics to, or null for all locations
+ * @returns Promise<ShelterStatistics | null> - The shelter statistics. Returns null if the operation fails.
+ */
+export async function getShelterStatistics(
+  locationId: string | null = null,
+): Promise<ShelterStatistics | null> {
+  try {
+    return await invoke<ShelterStatistics>("get_shelter_statistics", {
+      locationId,
+    });
+  } catch (e) {
+    error(`Failed to retrieve shelter statistics: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Generates the report of the shelter's activity during a calendar month (staff only).
+ *
+ * @param year - The year of the month to report on
+ * @param month - The month to report on, from 1 to 12
+ * @param locationId - ID of the location to limit the report to, or null for all locations
+ * @returns Promise<MonthlyReport | null> - The report. Returns null if the operation fails.
+ */
+export async function generateMonthlyReport(
+  year: number,
+  month: number,
+  locationId: string | null = null,
+): Promise<MonthlyReport | null> {
+  try {
+    return await invoke<MonthlyReport>("generate_monthly_report", {
+      year,
+      month,
+      locationId,
+    });
+  } catch (e) {
+    error(`Failed to generate report for ${year}-${month}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Counts the animals taken in and adopted during each period of a time range, to draw
+ * as a line chart.
+ *
+ * @param granularity - Whether to count by day, week or month
+ * @param fromTimestamp - Start of the range
+ * @param toTimestamp - End of the range, excluded
+ * @param locationId - ID of the location to limit the counts to, or null for all locations
+ * @returns Promise<TrendPoint[] | null> - One point per period, oldest first. Returns null if the operation fails.
+ */
+export async function getAdoptionTrends(
+  granularity: TrendGranularity,
+  fromTimestamp: number,
+  toTimestamp: number,
+  locationId: string | null = null,
+): Promise<TrendPoint[] | null> {
+  try {
+    return await invoke<TrendPoint[]>("get_adoption_trends", {
+      granularity,
+      fromTimestamp,
+      toTimestamp,
+      locationId,
+    });
+  } catch (e) {
+    error(`Failed to retrieve adoption trends: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Exports the monthly or annual activity report as a PDF, saved where the user chooses.
+ * The report is rendered by a background job.
+ *
+ * @param year - The year to report on
+ * @param month - The month to report on, from 1 to 12, or null for the whole year
+ * @param locationId - ID of the location to limit the report to, or null for all locations
+ * @returns Promise<string | null> - The path the PDF was saved to. Returns null if cancelled or if the operation fails.
+ */
+export async function exportReportPdf(
+  year: number,
+  month: number | null = null,
+  locationId: string | null = null,
+): Promise<string | null> {
+  try {
+    return await runJob<string>({
+      type: JobKind.REPORT_EXPORT,
+      year,
+      month,
+      locationId,
+      format: ReportFormat.PDF,
+    });
+  } catch (e) {
+    error(`Failed to export report: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Exports the monthly or annual activity report as an Excel workbook, saved where the user chooses.
+ * The report is rendered by a background job.
+ *
+ * @param year - The year to report on
+ * @param month - The month to report on, from 1 to 12, or null for the whole year
+ * @param locationId - ID of the location to limit the report to, or null for all locations
+ * @returns Promise<string | null> - The path the workbook was saved to. Returns null if cancelled or if the operation fails.
+ */
+export async function exportReportXlsx(
+  year: number,
+  month: number | null = null,
+  locationId: string | null = null,
+): Promise<string | null> {
+  try {
+    return await runJob<string>({
+      type: JobKind.REPORT_EXPORT,
+      year,
+      month,
+      locationId,
+      format: ReportFormat.XLSX,
+    });
+  } catch (e) {
+    error(`Failed to export report: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Exports animals as an Excel workbook, saved where the user chooses.
+ * The workbook is rendered by a background job.
+ *
+ * @param filters - Optional filter criteria, as accepted by getAnimals
+ * @returns Promise<string | null> - The path the workbook was saved to. Returns null if cancelled or if the operation fails.
+ */
+export async function exportAnimalsXlsx(
+  filters: FilterSelections | null = null,
+): Promise<string | null> {
+  try {
+    return await runJob<string>({ type: JobKind.ANIMALS_EXPORT, filters });
+  } catch (e) {
+    error(`Failed to export animals: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Exports adoption requests as an Excel workbook, saved where the user chooses.
+ * The workbook is rendered by a background job.
+ *
+ * Anonymized workbooks, meant for research partners, replace each applicant with a
+ * hash and leave out their names, contact details, occupations and incomes.
+ *
+ * @param filters - Optional status, date range and species filters
+ * @param anonymize - Whether to hide who the applicants are
+ * @returns Promise<string | null> - The path the workbook was saved to. Returns null if cancelled or if the operation fails.
+ */
+export async function exportAdoptionRequestsXlsx(
+  filters: AdoptionRequestFilters = {},
+  anonymize: boolean = false,
+): Promise<string | null> {
+  try {
+    return await runJob<string>({
+      type: JobKind.ADOPTION_REQUESTS_EXPORT,
+      filters,
+      anonymize,
+    });
+  } catch (e) {
+    error(`Failed to export adoption requests: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== SEARCH FUNCTIONS ====================
+
+/**
+ * Searches animals, adoption requests and user accounts for some text at once.
+ *
+ * @param query - The text to look for, ignoring case; blank text finds nothing
+ * @param scopes - The kinds of record to search, or null for all of them
+ * @param animalFilters - Filters the animals must match, or null for none
+ * @param requestFilters - Filters the requests must match, or null for none
+ * @param limit - Most records to return of each kind, or null for the default
+ * @returns Promise<SearchResults | null> - The records found, grouped by kind. Returns null if the operation fails.
+ */
+export async function search(
+  query: string,
+  scopes: SearchScope[] | null = null,
+  animalFilters: FilterSelections | null = null,
+  requestFilters: AdoptionRequestFilters | null = null,
+  limit: number | null = null,
+): Promise<SearchResults | null> {
+  try {
+    return await invoke<SearchResults>("search", {
+      query,
+      scopes,
+      animalFilters,
+      requestFilters,
+      limit,
+    });
+  } catch (e) {
+    error(`Failed to search for "${query}": ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== ACTIVITY FUNCTIONS ====================
+
+/**
+ * Retrieves what staff members have recently done, for the team's activity panel (staff only).
+ *
+ * @param offset - Number of the most recent entries to skip
+ * @param limit - Most entries to return, or null for the default; at most 200 are returned
+ * @returns Promise<ActivityFeed | null> - A page of the feed, most recent first. Returns null if the operation fails.
+ */
+export async function getActivityFeed(
+  offset: number = 0,
+  limit: number | null = null,
+): Promise<ActivityFeed | null> {
+  try {
+    return await invoke<ActivityFeed>("get_activity_feed", { offset, limit });
+  } catch (e) {
+    error(`Failed to get activity feed: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== DOCUMENT FUNCTIONS ====================
+
+/**
+ * Renders the kennel card of an animal, a one-page profile to put up on its
+ * enclosure, and saves it where the user chooses (staff only).
+ *
+ * @param animalId - The ID of the animal
+ * @returns Promise<string | null> - The path the card was saved to, or null if canceled or the operation fails.
+ */
+export async function generateKennelCard(
+  animalId: string,
+): Promise<string | null> {
+  try {
+    return await invoke<string | null>("generate_kennel_card", { animalId });
+  } catch (e) {
+    error(`Failed to generate kennel card: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Renders the vaccination certificate of an animal, listing the vaccines it has been
+ * given for adopters to hand to their vet, and saves it where the user chooses (staff only).
+ *
+ * @param animalId - The ID of the animal
+ * @returns Promise<string | null> - The path the certificate was saved to, or null if canceled or the operation fails.
+ */
+export async function generateVaccinationCertificate(
+  animalId: string,
+): Promise<string | null> {
+  try {
+    return await invoke<string | null>("generate_vaccination_certificate", { animalId });
+  } catch (e) {
+    error(`Failed to generate vaccination certificate: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Renders the adoption certificate of an approved adoption request, with the animal's
+ * photo, the adopter's name and the adoption date, and saves it where the user chooses
+ * (staff only).
+ *
+ * @param requestId - The ID of the approved adoption request
+ * @returns Promise<string | null> - The path the certificate was saved to, or null if canceled or the operation fails.
+ */
+export async function generateAdoptionCertificate(
+  requestId: string,
+): Promise<string | null> {
+  try {
+    return await invoke<string | null>("generate_adoption_certificate", { requestId });
+  } catch (e) {
+    error(`Failed to generate adoption certificate: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Renders the invoice for a fee charged for an adoption request and saves it where
+ * the user chooses. The invoice is given the next invoice number the first time it
+ * is generated, and keeps it afterwards (staff only).
+ *
+ * @param paymentId - The ID of the payment
+ * @returns Promise<string | null> - The path the invoice was saved to, or null if canceled or the operation fails.
+ */
+export async function generateInvoice(paymentId: string): Promise<string | null> {
+  try {
+    return await invoke<string | null>("generate_invoice", { paymentId });
+  } catch (e) {
+    error(`Failed to generate invoice: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Renders the receipt for a paid fee and saves it where the user chooses. The receipt
+ * is given the next receipt number the first time it is generated, and keeps it
+ * afterwards (staff only).
+ *
+ * @param paymentId - The ID of the paid payment
+ * @returns Promise<string | null> - The path the receipt was saved to, or null if canceled or the operation fails.
+ */
+export async function generateReceipt(paymentId: string): Promise<string | null> {
+  try {
+    return await invoke<string | null>("generate_receipt", { paymentId });
+  } catch (e) {
+    error(`Failed to generate receipt: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Renders the receipt for a donation and saves it where the user chooses. The receipt
+ * is given the next donation receipt number the first time it is generated, and keeps
+ * it afterwards (staff only).
+ *
+ * @param donationId - The ID of the donation
+ * @returns Promise<string | null> - The path the receipt was saved to, or null if canceled or the operation fails.
+ */
+export async function generateDonationReceipt(donationId: string): Promise<string | null> {
+  try {
+    return await invoke<string | null>("generate_donation_receipt", { donationId });
+  } catch (e) {
+    error(`Failed to generate donation receipt: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Renders the statement of a donor's donations during a year, with their total for
+ * tax purposes, and saves it where the user chooses (staff only).
+ *
+ * @param contactId - The ID of the donor's contact
+ * @param year - The year to sum the donations of
+ * @returns Promise<string | null> - The path the statement was saved to, or null if canceled or the operation fails.
+ */
+export async function generateAnnualDonorStatement(
+  contactId: string,
+  year: number,
+): Promise<string | null> {
+  try {
+    return await invoke<string | null>("generate_annual_donor_statement", { contactId, year });
+  } catch (e) {
+    error(`Failed to generate donor statement: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Saves the vaccinations coming due, the animals due back from foster care, the
+ * meet-and-greet appointments and the spay/neuter surgeries within a period as an
+ * iCalendar (.ics) file, which calendar apps such as Outlook and Google Calendar
+ * can import (staff only).
+ *
+ * @param fromTimestamp - Start of the period in seconds, inclusive
+ * @param toTimestamp - End of the period in seconds, exclusive
+ * @returns Promise<string | null> - The path the calendar was saved to, or null if canceled or the operation fails.
+ */
+export async function exportCalendar(
+  fromTimestamp: number,
+  toTimestamp: number,
+): Promise<string | null> {
+  try {
+    return await invoke<string | null>("export_calendar", {
+      fromTimestamp,
+      toTimestamp,
+    });
+  } catch (e) {
+    error(`Failed to export calendar: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Saves the public listings feed, the animals available for adoption with only
+ * the fields safe to publish, for the website and adoption aggregators to read.
+ * Photos are copied to a directory next to the feed (staff only).
+ *
+ * @param format - The file format to write the feed in
+ * @returns Promise<string | null> - The path the feed was saved to, or null if canceled or the operation fails.
+ */
+export async function exportPublicListings(
+  format: ListingsFormat,
+): Promise<string | null> {
+  try {
+    return await invoke<string | null>("export_public_listings", { format });
+  } catch (e) {
+    error(`Failed to export public listings: ${describeError(e)}`);
+    return null;
+  }
+}
+
+// ==================== JOB FUNCTIONS ====================
+
+/** Name of the event emitted by the backend whenever a background job changes */
+export const JOB_UPDATED_EVENT = "job-updated";
+
+/**
+ * Checks whether a job has stopped, successfully or not.
+ *
+ * @param job - The job to check
+ * @returns boolean - True if the job completed or failed
+ */
+export function isJobFinished(job: Job): boolean {
+  return job.status === JobStatus.COMPLETED || job.status === JobStatus.FAILED;
 }
 
-// ==================== ANIMAL FUNCTIONS ====================
+/**
+ * Queues an export or bulk import to run in the background.
+ * Exports first ask where to save, and change imports which file to read.
+ *
+ * @param request - The work to do, with its parameters
+ * @returns Promise<Job | null> - The queued job. Returns null if the user cancels the file dialog.
+ * @throws The reason the job could not be queued
+ */
+export async function enqueueJob(request: JobRequest): Promise<Job | null> {
+  try {
+    return await invoke<Job | null>("enqueue_job", { request });
+  } catch (e) {
+    error(`Failed to queue job: ${describeError(e)}`);
+    throw e;
+  }
+}
 
 /**
- * Retrieves animals from the database, with optional filtering.
+ * Retrieves one of the logged-in staff member's background jobs.
  *
- * @param filters - Optional map of filter criteria and values
- * @returns Promise<AnimalSummary[]> - List of animal summaries. Returns an empty array if the operation fails.
+ * @param jobId - The ID of the job
+ * @returns Promise<Job | null> - The job. Returns null if it is unknown or if the operation fails.
  */
-export async function getAnimals(
-  filters: FilterSelections | null,
-): Promise<AnimalSummary[]> {
+export async function getJob(jobId: string): Promise<Job | null> {
   try {
-    return await invoke<AnimalSummary[]>("get_animals", { filters });
+    return await invoke<Job | null>("get_job", { jobId });
+  } catch (e) {
+    error(`Failed to retrieve job with ID ${jobId}: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Retrieves the logged-in staff member's background jobs, most recent first.
+ *
+ * @returns Promise<Job[]> - Jobs still queued or running, and those finished recently. Returns an empty array if the operation fails.
+ */
+export async function getJobs(): Promise<Job[]> {
+  try {
+    return await invoke<Job[]>("get_jobs");
   } catch (e) {
-    error(`Failed to get animals: ${e}`);
+    error(`Failed to retrieve jobs: ${describeError(e)}`);
     return [];
   }
 }
 
 /**
- * Retrieves a specific animal by ID.
+ * Calls a handler whenever a background job is queued, makes progress or finishes.
  *
- * @param animalId - The ID of the animal to retrieve
- * @returns Promise<Animal | null> - The animal data if found, null if not found. Returns null if the operation fails.
+ * @param handler - Called with the job as it is now
+ * @returns Promise<UnlistenFn> - Stops listening when called
  */
-export async function getAnimalById(animalId: string): Promise<Animal | null> {
+export async function onJobUpdated(
+  handler: (job: Job) => void,
+): Promise<UnlistenFn> {
+  return await listen<Job>(JOB_UPDATED_EVENT, (event) =>
+    handler(event.payload),
+  );
+}
+
+/**
+ * Waits for a background job to finish.
+ *
+ * @param jobId - The ID of the job
+ * @returns Promise<Job> - The finished job, with its result or error
+ * @throws If the job is unknown
+ */
+export async function waitForJob(jobId: string): Promise<Job> {
+  let resolveFinished: (job: Job) => void = () => {};
+  const finished = new Promise<Job>((resolve) => {
+    resolveFinished = resolve;
+  });
+  const unlisten = await onJobUpdated((job) => {
+    if (job.id === jobId && isJobFinished(job)) {
+      resolveFinished(job);
+    }
+  });
+
   try {
-    return await invoke<Animal | null>("get_animal_by_id", { animalId });
+    // The job may have finished before the listener started
+    const job = await getJob(jobId);
+    if (!job) {
+      throw new Error(`Job ${jobId} was not found`);
+    }
+    return isJobFinished(job) ? job : await finished;
+  } finally {
+    unlisten();
+  }
+}
+
+/**
+ * Queues a background job and waits for its result.
+ *
+ * @param request - The work to do, with its parameters
+ * @returns Promise<T | null> - The result of the job. Returns null if the user cancels the file dialog.
+ * @throws The reason the job could not be queued or failed
+ */
+export async function runJob<T>(request: JobRequest): Promise<T | null> {
+  const job = await enqueueJob(request);
+  if (!job) {
+    return null;
+  }
+  const finished = await waitForJob(job.id);
+  if (finished.status === JobStatus.FAILED) {
+    throw finished.error;
+  }
+  return finished.result as T;
+}
+
+// ==================== SETTINGS FUNCTIONS ====================
+
+/**
+ * Retrieves the current value of a setting.
+ *
+ * @param key - The setting to retrieve
+ * @returns Promise<Setting | null> - The setting, with its default value if it was never changed. Returns null if the operation fails.
+ */
+export async function getSetting(key: SettingKey): Promise<Setting | null> {
+  try {
+    return await invoke<Setting>("get_setting", { key });
   } catch (e) {
-    error(`Failed to get animal by ID ${animalId}: ${e}`);
+    error(`Failed to retrieve setting ${key}: ${describeError(e)}`);
     return null;
   }
 }
 
 /**
- * Retrieves an animal and its associated accepted adoption in parallel.
+ * Retrieves the current value of every setting.
  *
- * @param animalId - The ID of the animal.
- * @param animalStatus - The status of the animal, to determine if a request should be fetched.
- * @returns A promise that resolves to an object containing the animal and optional adopter details. Returns `{ animal: null, adopter: null }` if the operation fails.
+ * @returns Promise<Setting[]> - The settings. Returns an empty array if the operation fails.
  */
-export async function getAnimalWithAcceptedAdoption(
-  animalId: string,
-  animalStatus: AnimalStatus,
-): Promise<{ animal: Animal | null; adopter: AdoptionRequest | null }> {
+export async function getSettings(): Promise<Setting[]> {
   try {
-    const animalPromise = getAnimalById(animalId);
-    let adopterPromise: Promise<AdoptionRequest | null> = Promise.resolve(null);
+    return await invoke<Setting[]>("get_settings");
+  } catch (e) {
+    error(`Failed to retrieve settings: ${describeError(e)}`);
+    return [];
+  }
+}
 
-    if (animalStatus === AnimalStatus.ADOPTED) {
-      adopterPromise = getAdoptionRequestsByAnimalId(animalId).then(
-        (requests) => {
-          // Find the request with the APPROVED status
-          const foundApprovedRequest = requests.find(
-            (request) => request && request.status === RequestStatus.APPROVED,
-          );
-          return foundApprovedRequest || null;
-        },
-      );
-    }
+/**
+ * Changes a setting (admin only).
+ *
+ * @param key - The setting to change
+ * @param value - The new value
+ * @returns Promise<Setting> - The setting as stored, such as with the currency code in capitals.
+ * @throws The reason the change failed, such as the value not being allowed
+ */
+export async function setSetting(
+  key: SettingKey,
+  value: string,
+): Promise<Setting> {
+  try {
+    return await invoke<Setting>("set_setting", { key, value });
+  } catch (e) {
+    error(`Failed to change setting ${key}: ${describeError(e)}`);
+    throw e;
+  }
+}
 
-    const [animal, adopter] = await Promise.all([
-      animalPromise,
-      adopterPromise,
-    ]);
-    return { animal, adopter };
+/**
+ * Retrieves the text the backend writes, in the locale chosen in the settings.
+ *
+ * @returns Promise<Message[]> - Every message key with its text. Returns an empty array if the operation fails.
+ */
+export async function getMessages(): Promise<Message[]> {
+  try {
+    return await invoke<Message[]>("get_messages");
   } catch (e) {
-    error(`Failed to get animal ${animalId} with adoption request: ${e}`);
-    return { animal: null, adopter: null };
+    error(`Failed to retrieve messages: ${describeError(e)}`);
+    return [];
   }
 }
 
+// ==================== HEALTH FUNCTIONS ====================
+
 /**
- * Creates a new animal in the database.
+ * Checks the state of the application, such as whether the database answers,
+ * how much disk space is left and when the last backup was made (staff only).
  *
- * @param animal - The animal data to create
- * @returns Promise<void> - A promise that resolves when the operation is complete. Logs an error if the operation fails.
+ * @returns Promise<HealthReport | null> - The state of the application and its warnings. Returns null if the operation fails.
  */
-export async function createAnimal(animal: Animal): Promise<void> {
+export async function getHealth(): Promise<HealthReport | null> {
   try {
-    await invoke("create_animal", { animal });
+    return await invoke<HealthReport>("get_health");
   } catch (e) {
-    error(`Failed to create animal: ${e}`);
+    error(`Failed to check application health: ${describeError(e)}`);
+    return null;
   }
 }
 
+// ==================== LOG FUNCTIONS ====================
+
 /**
- * Updates an existing animal in the database.
+ * Retrieves which messages are currently written to the log.
  *
- * @param animal - The updated animal data
- * @returns Promise<boolean> - True if animal was found and updated, false if not found. Returns false if the operation fails.
+ * @returns Promise<LogLevel | null> - The least severe kind of message written. Returns null if logging is off or the operation fails.
  */
-export async function updateAnimal(animal: Animal): Promise<boolean> {
+export async function getLogLevel(): Promise<LogLevel | null> {
   try {
-    return await invoke<boolean>("update_animal", { animal });
+    return await invoke<LogLevel | null>("get_log_level");
   } catch (e) {
-    error(`Failed to update animal: ${e}`);
-    return false;
+    error(`Failed to retrieve log level: ${describeError(e)}`);
+    return null;
   }
 }
 
 /**
- * Deletes an animal from the database.
+ * Changes which messages are written to the log, straight away and after restarts (admin only).
  *
- * @param animal - The animal data to delete
- * @returns Promise<boolean> - True if animal was found and deleted, false if not found. Returns false if the operation fails.
+ * @param level - The least severe kind of message to write
+ * @throws The reason the change failed, such as the user not being an admin
  */
-export async function deleteAnimal(
-  animal: AnimalSummary | Animal,
-): Promise<boolean> {
+export async function setLogLevel(level: LogLevel): Promise<void> {
   try {
-    const animalId = animal.id;
+    await invoke("set_log_level", { level });
+  } catch (e) {
+    error(`Failed to change log level: ${describeError(e)}`);
+    throw e;
+  }
+}
 
-    // Delete animal from database
-    const deleteStatus = await invoke<boolean>("delete_animal", { animalId });
+/**
+ * Retrieves the last lines of the log, for attaching to bug reports.
+ *
+ * @param lineCount - How many lines to read, at most 1000
+ * @returns Promise<string[]> - The lines, oldest first. Returns an empty array if the operation fails.
+ */
+export async function getLogTail(lineCount: number): Promise<string[]> {
+  try {
+    return await invoke<string[]>("get_log_tail", { lineCount });
+  } catch (e) {
+    error(`Failed to read log: ${describeError(e)}`);
+    return [];
+  }
+}
 
-    // Remove file associated with the animal if the deletion was successful
-    if (deleteStatus) {
-      await invoke<boolean>("delete_animal", { animalId });
-      return true;
-    }
+/**
+ * Archives the current log file and starts it afresh (admin only).
+ *
+ * @returns Promise<string | null> - The path of the archived log, or null if nothing was logged since the last archive or the operation fails.
+ */
+export async function rotateLogs(): Promise<string | null> {
+  try {
+    return await invoke<string | null>("rotate_logs");
+  } catch (e) {
+    error(`Failed to rotate logs: ${describeError(e)}`);
+    return null;
+  }
+}
 
-    return false;
+/**
+ * Saves every log file as a single text file chosen by the user.
+ *
+ * @returns Promise<string | null> - The path the logs were saved to, or null if canceled or the operation fails.
+ */
+export async function exportLogs(): Promise<string | null> {
+  try {
+    return await invoke<string | null>("export_logs");
   } catch (e) {
-    error(`Failed to delete animal with ID ${animal.id}: ${e}`);
-    return false;
+    error(`Failed to export logs: ${describeError(e)}`);
+    return null;
   }
 }
 
-// ==================== ADOPTION REQUEST FUNCTIONS ====================
+// ==================== FILE FUNCTIONS ====================
 
 /**
- * Retrieves a specific adoption request by ID.
+ * Uploads a file selected by the user for animal images.
+ * Files that are not images, or are too large, are refused.
  *
- * @param requestId - The ID of the adoption request to retrieve
- * @returns Promise<AdoptionRequest | null> - The adoption request data if found, null if not found. Returns null if the operation fails.
+ * @param animalId - The ID of the animal, or null if it has not been saved yet
+ * @returns Promise<string | null> - The path of the uploaded file if successful, null if canceled.
+ * @throws The reason the upload failed, such as the file not being a valid image
  */
-export async function getAdoptionRequestById(
-  requestId: string,
-): Promise<AdoptionRequest | null> {
+export async function uploadAnimalImage(
+  animalId: string | null = null,
+): Promise<string | null> {
   try {
-    return await invoke<AdoptionRequest | null>("get_adoption_request_by_id", {
-      requestId,
+    const owner: StorageOwner | null = animalId
+      ? { type: "animal", id: animalId }
+      : null;
+    return await invoke<string | null>("upload_file", {
+      kind: UploadKind.ANIMAL_PHOTO,
+      owner,
     });
   } catch (e) {
-    error(`Failed to get adoption request by ID ${requestId}: ${e}`);
-    return null;
+    error(`Failed to upload animal image: ${describeError(e)}`);
+    throw e;
   }
 }
 
 /**
- * Retrieves all adoption requests for a specific animal ID.
+ * Replaces an animal's image with one selected by the user, saving it right
+ * away and deleting the previous image file (staff only).
  *
- * @param animalId - The ID of the animal to retrieve requests for
- * @returns Promise<AdoptionRequest[]> - List of adoption requests. Returns an empty array if the operation fails.
+ * @param animalId - The ID of the animal
+ * @returns Promise<Animal | null> - The animal with its new image and version, null if canceled.
+ * @throws The reason the replacement failed, such as the file not being a valid image
  */
-export async function getAdoptionRequestsByAnimalId(
+export async function replaceAnimalImage(
   animalId: string,
-): Promise<AdoptionRequest[]> {
+): Promise<Animal | null> {
   try {
-    return await invoke<AdoptionRequest[]>(
-      "get_adoption_requests_by_animal_id",
-      { animalId },
-    );
+    return await invoke<Animal | null>("replace_animal_image", { animalId });
   } catch (e) {
-    error(`Failed to get adoption requests for animal ID ${animalId}: ${e}`);
-    return [];
+    error(`Failed to replace animal image: ${describeError(e)}`);
+    throw e;
   }
 }
 
 /**
- * Retrieves all adoption requests for a specific username.
+ * Rotates an uploaded image, such as a photo taken sideways on a phone (staff only).
+ * The rotated image is written to a new file and the original is kept.
  *
- * @param username - The username to retrieve requests for
- * @returns Promise<AdoptionRequest[]> - List of adoption requests. Returns an empty array if the operation fails.
+ * @param filePath - The path of the uploaded image
+ * @param degrees - Angle to rotate clockwise by, a multiple of 90; negative angles rotate counterclockwise
+ * @returns Promise<string> - The path of the rotated image.
+ * @throws The reason the rotation failed, such as the file not being an image
  */
-export async function getAdoptionRequestsByUsername(
-  username: string,
-): Promise<AdoptionRequest[]> {
+export async function rotateImage(
+  filePath: string,
+  degrees: number,
+): Promise<string> {
   try {
-    return await invoke<AdoptionRequest[]>(
-      "get_adoption_requests_by_username",
-      { username },
-    );
+    return await invoke<string>("rotate_image", { filePath, degrees });
   } catch (e) {
-    error(`Failed to get adoption requests for username ${username}: ${e}`);
-    return [];
+    error(`Failed to rotate image: ${describeError(e)}`);
+    throw e;
   }
 }
 
 /**
- * Creates a new adoption request in the database.
+ * Crops an uploaded image to a rectangle, given in pixels (staff only).
+ * The cropped image is written to a new file and the original is kept.
  *
- * @param request - The adoption request data to create
- * @returns Promise<void> - A promise that resolves when the operation is complete. Logs an error if the operation fails.
+ * @param filePath - The path of the uploaded image
+ * @param x - Left edge of the rectangle
+ * @param y - Top edge of the rectangle
+ * @param width - Width of the rectangle
+ * @param height - Height of the rectangle
+ * @returns Promise<string> - The path of the cropped image.
+ * @throws The reason the crop failed, such as the rectangle not fitting within the image
  */
-export async function createAdoptionRequest(
-  request: AdoptionRequest,
-): Promise<void> {
+export async function cropImage(
+  filePath: string,
+  x: number,
+  y: number,
+  width: number,
+  height: number,
+): Promise<string> {
   try {
-    await invoke("create_adoption_request", { request });
+    return await invoke<string>("crop_image", {
+      filePath,
+      x,
+      y,
+      width,
+      height,
+    });
   } catch (e) {
-    error(`Failed to create adoption request: ${e}`);
+    error(`Failed to crop image: ${describeError(e)}`);
+    throw e;
   }
 }
 
 /**
- * Updates an existing adoption request in the database.
+ * Uploads several files selected by the user in one dialog.
+ * If any file does not suit the upload kind, none of them are uploaded.
  *
- * @param request - The updated adoption request data
- * @returns Promise<boolean> - True if request was found and updated, false if not found. Returns false if the operation fails.
+ * @param kind - What the files are going to be used for
+ * @param owner - The record the files belong to, or null if it has not been saved yet
+ * @returns Promise<string[]> - The paths of the uploaded files, in the order they were selected. Returns an empty array if canceled.
+ * @throws The reason the upload failed, such as a file not being in an allowed format
  */
-export async function updateAdoptionRequest(
-  request: AdoptionRequest,
-): Promise<boolean> {
+export async function uploadFiles(
+  kind: UploadKind,
+  owner: StorageOwner | null = null,
+): Promise<string[]> {
   try {
-    return await invoke<boolean>("update_adoption_request", { request });
+    return await invoke<string[]>("upload_files", { kind, owner });
   } catch (e) {
-    error(`Failed to update adoption request: ${e}`);
-    return false;
+    error(`Failed to upload files: ${describeError(e)}`);
+    throw e;
   }
 }
 
 /**
- * Deletes an adoption request from the database.
+ * Uploads a file that was dropped or pasted into the application, sending its
+ * contents directly instead of asking the user to pick it in a dialog.
  *
- * @param requestId - The ID of the adoption request to delete
- * @returns Promise<boolean> - True if request was found and deleted, false if not found. Returns false if the operation fails.
+ * @param file - The dropped or pasted file
+ * @param kind - What the file is going to be used for
+ * @param owner - The record the file belongs to, or null if it has not been saved yet
+ * @returns Promise<string> - The path of the uploaded file.
+ * @throws The reason the upload failed, such as the file not being in an allowed format
  */
-export async function deleteAdoptionRequest(
-  requestId: string,
-): Promise<boolean> {
+export async function uploadDroppedFile(
+  file: File,
+  kind: UploadKind,
+  owner: StorageOwner | null = null,
+): Promise<string> {
   try {
-    return await invoke<boolean>("delete_adoption_request", { requestId });
+    const contents = Array.from(new Uint8Array(await file.arrayBuffer()));
+    return await invoke<string>("upload_bytes", {
+      filenameHint: file.name,
+      contents,
+      kind,
+      owner,
+    });
   } catch (e) {
-    error(`Failed to delete adoption request with ID ${requestId}: ${e}`);
-    return false;
+    error(`Failed to upload dropped file: ${describeError(e)}`);
+    throw e;
   }
 }
 
-// ==================== FILE FUNCTIONS ====================
+/**
+ * Reads an uploaded file, such as an animal photo, so it can be displayed without
+ * going through the asset protocol (logged-in users only).
+ *
+ * @param filePath - The path of the stored file
+ * @returns Promise<string | null> - A data URL that can be used as an image source. Returns null if the file cannot be read.
+ */
+export async function readStoredFile(filePath: string): Promise<string | null> {
+  try {
+    return await invoke<string>("read_file", { filePath });
+  } catch (e) {
+    error(`Failed to read file: ${describeError(e)}`);
+    return null;
+  }
+}
 
 /**
- * Uploads a file selected by the user for animal images.
+ * Reports how much disk space the application's files take up.
+ *
+ * @returns Promise<StorageStats | null> - The disk space used, by category. Returns null if the operation fails.
+ */
+export async function getStorageStats(): Promise<StorageStats | null> {
+  try {
+    return await invoke<StorageStats>("get_storage_stats");
+  } catch (e) {
+    error(`Failed to retrieve storage statistics: ${describeError(e)}`);
+    return null;
+  }
+}
+
+/**
+ * Checks every stored file against the size and hash recorded when it was
+ * written, to find files damaged or removed outside the application.
  *
- * @returns Promise<string | null> - The path of the uploaded file if successful, null if canceled. Returns null if the upload fails.
+ * @returns Promise<FileVerificationReport | null> - The missing, damaged and unrecorded files. Returns null if the operation fails.
  */
-export async function uploadAnimalImage(): Promise<string | null> {
+export async function verifyFiles(): Promise<FileVerificationReport | null> {
   try {
-    return await invoke<string | null>("upload_file");
+    return await invoke<FileVerificationReport>("verify_files");
   } catch (e) {
-    error(`Failed to upload animal image: ${e}`);
+    error(`Failed to verify files: ${describeError(e)}`);
     return null;
   }
 }
@@ -385,7 +5121,7 @@ export async function deleteFile(filePath: string): Promise<void> {
   try {
     await invoke("delete_file", { filePath });
   } catch (e) {
-    error(`Failed to delete file: ${e}`);
+    error(`Failed to delete file: ${describeError(e)}`);
   }
 }
 
@@ -405,24 +5141,53 @@ export function formatTimestamp(timestamp: number): string {
   return `${day}/${month}/${year}`;
 }
 
+/** Number of minor currency units, such as satang or cents, in one unit of the currency */
+export const MINOR_UNITS_PER_UNIT = 100;
+
+/**
+ * Converts an amount of money entered in units of the currency into the minor units it is stored in.
+ *
+ * @param amount - Amount in units of the currency, such as 1500.5
+ * @returns number - The amount in minor units, rounded to the nearest one
+ */
+export function toMinorUnits(amount: number): number {
+  return Math.round(amount * MINOR_UNITS_PER_UNIT);
+}
+
+/**
+ * Formats an amount of money stored in minor currency units, for display.
+ *
+ * @param amount - Amount in minor units, such as satang
+ * @returns string - The amount in units of the currency with two decimals, such as "1500.50"
+ */
+export function formatAmount(amount: number): string {
+  return (amount / MINOR_UNITS_PER_UNIT).toFixed(2);
+}
+
 /**
  * Calculates age from birth year and month.
  *
- * @param birthYear - Birth year of the animal
- * @param birthMonth - Birth month of the animal (1-12)
- * @returns string - Age in years and months
+ * @param birthYear - Birth year of the animal, null if unknown
+ * @param birthMonth - Birth month of the animal (1-12), null if unknown
+ * @returns string | null - Age in years and months, an estimate in years if only the
+ *   birth year is known, or null if the age is unknown
  */
 export function calculateAge(
   birthYear: number | null,
   birthMonth: number | null,
 ): string | null {
-  if (!birthYear || !birthMonth) {
+  if (!birthYear) {
     return null;
   }
   const now = new Date();
   const currentYear = now.getFullYear();
   const currentMonth = now.getMonth() + 1;
 
+  if (!birthMonth) {
+    const years = currentYear - birthYear;
+    return years === 0 ? "under 1 y old" : `about ${years} y old`;
+  }
+
   let ageYears = currentYear - birthYear;
   let ageMonths = currentMonth - birthMonth;
 
@@ -440,6 +5205,26 @@ export function calculateAge(
   }
 }
 
+/**
+ * Formats the birth date of an animal with its age, for display.
+ *
+ * @param birthYear - Birth year of the animal, null if unknown
+ * @param birthMonth - Birth month of the animal (1-12), null if unknown
+ * @returns string - Such as "4/2021 (3 y 2 m old)", "2021 (about 3 y old)" or "Unknown"
+ */
+export function formatBirthDate(
+  birthYear: number | null,
+  birthMonth: number | null,
+): string {
+  const age = calculateAge(birthYear, birthMonth);
+  if (!birthYear || !age) {
+    return "Unknown";
+  }
+  return birthMonth
+    ? `${birthMonth}/${birthYear} (${age})`
+    : `${birthYear} (${age})`;
+}
+
 /**
  * Gets the display text for an animal status.
  *
@@ -454,8 +5239,12 @@ export function getStatusDisplayText(status: AnimalStatus): string {
       return "Requested";
     case AnimalStatus.ADOPTED:
       return "Adopted";
+    case AnimalStatus.FOSTERED:
+      return "Fostered";
     case AnimalStatus.PASSED_AWAY:
       return "Passed Away";
+    case AnimalStatus.TRANSFERRED:
+      return "Transferred";
     default:
       return "Unknown";
   }
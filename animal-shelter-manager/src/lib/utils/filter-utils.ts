@@ -12,6 +12,16 @@ export enum FilterCriteria {
   SPECIES_AND_BREEDS = "species-and-breeds",
   ADMISSION_DATE = "admission-date",
   ADOPTION_DATE = "adoption-date",
+  NEUTERED = "neutered",
+  HAS_IMAGE = "has-image",
+  LOCATION = "location",
+  GOOD_WITH_KIDS = "good-with-kids",
+  GOOD_WITH_DOGS = "good-with-dogs",
+  GOOD_WITH_CATS = "good-with-cats",
+  SPECIAL_NEEDS = "special-needs",
+  SIZE = "size",
+  COLOR = "color",
+  AGE = "age",
 }
 
 /** Types of filter components available */
@@ -66,6 +76,56 @@ export const FILTER_CRITERIA_CONFIGS: Record<
     type: FilterType.CHOOSE_ONE,
     displayName: "Adoption Date",
   },
+  [FilterCriteria.NEUTERED]: {
+    criteria: FilterCriteria.NEUTERED,
+    type: FilterType.CHOOSE_ONE,
+    displayName: "Neutered",
+  },
+  [FilterCriteria.HAS_IMAGE]: {
+    criteria: FilterCriteria.HAS_IMAGE,
+    type: FilterType.CHOOSE_ONE,
+    displayName: "Has Photo",
+  },
+  [FilterCriteria.LOCATION]: {
+    criteria: FilterCriteria.LOCATION,
+    type: FilterType.CHOOSE_MANY,
+    displayName: "Location",
+  },
+  [FilterCriteria.GOOD_WITH_KIDS]: {
+    criteria: FilterCriteria.GOOD_WITH_KIDS,
+    type: FilterType.CHOOSE_ONE,
+    displayName: "Good with Kids",
+  },
+  [FilterCriteria.GOOD_WITH_DOGS]: {
+    criteria: FilterCriteria.GOOD_WITH_DOGS,
+    type: FilterType.CHOOSE_ONE,
+    displayName: "Good with Dogs",
+  },
+  [FilterCriteria.GOOD_WITH_CATS]: {
+    criteria: FilterCriteria.GOOD_WITH_CATS,
+    type: FilterType.CHOOSE_ONE,
+    displayName: "Good with Cats",
+  },
+  [FilterCriteria.SPECIAL_NEEDS]: {
+    criteria: FilterCriteria.SPECIAL_NEEDS,
+    type: FilterType.CHOOSE_ONE,
+    displayName: "Special Needs",
+  },
+  [FilterCriteria.SIZE]: {
+    criteria: FilterCriteria.SIZE,
+    type: FilterType.CHOOSE_MANY,
+    displayName: "Size",
+  },
+  [FilterCriteria.COLOR]: {
+    criteria: FilterCriteria.COLOR,
+    type: FilterType.CHOOSE_MANY,
+    displayName: "Color",
+  },
+  [FilterCriteria.AGE]: {
+    criteria: FilterCriteria.AGE,
+    type: FilterType.CHOOSE_ONE,
+    displayName: "Age",
+  },
 };
 
 /**
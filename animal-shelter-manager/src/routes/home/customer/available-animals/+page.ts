@@ -15,6 +15,7 @@ import {
   getAnimals,
   AnimalStatus,
   getAdoptionRequestsByUsername,
+  describeError,
 } from "$lib/utils/data-utils";
 
 /**
@@ -50,7 +51,7 @@ export const load: PageLoad = async () => {
     };
   } catch (e) {
     // Authentication check failed, redirect to authentication
-    error(`Error during page load: ${e}`);
+    error(`Error during page load: ${describeError(e)}`);
     goto("/");
     return { currentUser: null, animals: [] };
   }
@@ -11,6 +11,7 @@ import {
 } from "$lib/utils/authentication-utils";
 import type { PageLoad } from "./$types";
 import { error } from "@tauri-apps/plugin-log";
+import { describeError } from "$lib/utils/data-utils";
 import {
   getMyAdoptionRequests,
   MyAdoptionRequest,
@@ -41,7 +42,7 @@ export const load: PageLoad = async () => {
       myAdoptionRequests,
     };
   } catch (e) {
-    error(`Error during page load: ${e}`);
+    error(`Error during page load: ${describeError(e)}`);
     goto("/");
     return { currentUser: null, myAdoptionRequests: [] };
   }
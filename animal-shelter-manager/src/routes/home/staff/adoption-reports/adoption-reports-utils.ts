@@ -13,6 +13,8 @@ import {
   RequestStatus,
   AnimalStatus,
   getAdoptionRequestsByAnimalId,
+  isFullAdoptionRequest,
+  describeError,
 } from "$lib/utils/data-utils";
 import { error } from "@tauri-apps/plugin-log";
 import { FilterSelections } from "$lib/utils/filter-utils";
@@ -47,8 +49,9 @@ export async function getAdoptionReports(
     let animalAdoptionRequests: AnimalAdoptionReport[] = [];
     for (const animal of adoptedAnimals) {
       // Get adoption requests for the animal
-      const adoptionRequest: AdoptionRequest[] =
-        await getAdoptionRequestsByAnimalId(animal.id);
+      const adoptionRequest: AdoptionRequest[] = (
+        await getAdoptionRequestsByAnimalId(animal.id)
+      ).filter(isFullAdoptionRequest);
 
       // Find the approved adoption request
       const approvedRequests = adoptionRequest.find(
@@ -65,7 +68,7 @@ export async function getAdoptionReports(
 
     return animalAdoptionRequests;
   } catch (e) {
-    error(`Error fetching adoption reports: ${e}`);
+    error(`Error fetching adoption reports: ${describeError(e)}`);
     return [];
   }
 }
@@ -11,6 +11,8 @@ import {
 } from "$lib/utils/authentication-utils";
 import type { PageLoad } from "./$types";
 import { error } from "@tauri-apps/plugin-log";
+import { describeError } from "$lib/utils/data-utils";
+import { getNavigationBadges } from "../navigation-utils";
 import {
   type AnimalAdoptionRequests,
   getAdoptionRequests,
@@ -37,11 +39,12 @@ export const load: PageLoad = async ({ url }) => {
 
     return {
       currentUser,
+      navigationBadges: await getNavigationBadges(),
       adoptionRequests,
     };
   } catch (e) {
     // Authentication check failed, redirect to authentication
-    error(`Error during page load: ${e}`);
+    error(`Error during page load: ${describeError(e)}`);
     goto("/");
   }
 };
@@ -5,16 +5,21 @@
  */
 
 import {
-  getAnimals,
+  getAdoptionRequestsWithAnimals,
   type AnimalSummary,
   type AdoptionRequest,
   RequestStatus,
   AnimalStatus,
   getAdoptionRequestsByAnimalId,
+  isFullAdoptionRequest,
   getAnimalById,
   updateAdoptionRequest,
   Animal,
   updateAnimal,
+  approveAdoptionRequest,
+  ApproveRequestStatus,
+  type ApproveRequestResult,
+  describeError,
 } from "$lib/utils/data-utils";
 import { info, error } from "@tauri-apps/plugin-log";
 import { FilterSelections } from "$lib/utils/filter-utils";
@@ -28,8 +33,8 @@ export type AnimalAdoptionRequests = {
 };
 
 /**
- * Fetches adoption reports for animals with the "REQUESTED" status.
- * Each report includes the animal's summary and the pending adoption requests.
+ * Fetches pending adoption requests together with the requested animals.
+ * Each entry includes the animal's summary and one pending adoption request.
  *
  * @param filterSeclections - The filter selections to apply when fetching adopted animals.
  * @param animalId - (Optional) Specific animal ID to fetch adoption requests for.
@@ -41,104 +46,71 @@ export async function getAdoptionRequests(
   animalId?: string | null,
 ): Promise<AnimalAdoptionRequests[]> {
   try {
-    // Fetch adopted animals based on whether an animalId is provided
-    let adoptedAnimals: AnimalSummary[];
-    if (animalId) {
-      // Fetch specific animal by ID
-      const animal: Animal | null = await getAnimalById(animalId);
-
-      // If animal not found, return empty array
-      if (!animal) {
-        return [];
-      }
-
-      // Construct AnimalSummary for the specific animal
-      const animalSummary: AnimalSummary = {
-        id: animal.id,
-        name: animal.name,
-        specie: animal.specie,
-        breed: animal.breed,
-        sex: animal.sex,
-        admissionTimestamp: animal.admissionTimestamp,
-        imagePath: animal.imagePath,
-        status: animal.status,
-      };
-      adoptedAnimals = [animalSummary];
-    } else {
-      // Fetch all adopted animals with status REQUESTED, applying filters if any
-      adoptedAnimals = await getAnimals({
-        ...filterSeclections,
-        status: AnimalStatus.REQUESTED,
-      });
+    // Without an animalId, fetch all pending requests with their animals in one call
+    if (!animalId) {
+      const animalAdoptionRequests = await getAdoptionRequestsWithAnimals(
+        filterSeclections,
+        RequestStatus.PENDING,
+      );
+      info(`${JSON.stringify(animalAdoptionRequests)}`);
+      return animalAdoptionRequests;
     }
 
-    // For each requested animal, get the associated adoption requests
-    let animalAdoptionRequests: AnimalAdoptionRequests[] = [];
-    for (const animal of adoptedAnimals) {
-      // Get adoption requests for the animal
-      const adoptionRequest: AdoptionRequest[] =
-        await getAdoptionRequestsByAnimalId(animal.id);
-
-      // Find the pending adoption request
-      const pendingRequests = adoptionRequest.filter(
-        (request) => request.status === RequestStatus.PENDING,
-      );
+    // Fetch specific animal by ID
+    const animal: Animal | null = await getAnimalById(animalId);
 
-      // Add each pending request to the result list
-      for (const request of pendingRequests) {
-        animalAdoptionRequests.push({
-          animal: animal,
-          request: request,
-        });
-      }
+    // If animal not found, return empty array
+    if (!animal) {
+      return [];
     }
 
+    // Construct AnimalSummary for the specific animal
+    const animalSummary: AnimalSummary = {
+      id: animal.id,
+      name: animal.name,
+      specie: animal.specie,
+      breed: animal.breed,
+      sex: animal.sex,
+      admissionTimestamp: animal.admissionTimestamp,
+      imagePath: animal.imagePath,
+      status: animal.status,
+    };
+
+    // Get adoption requests for the animal
+    const adoptionRequest: AdoptionRequest[] = (
+      await getAdoptionRequestsByAnimalId(animal.id)
+    ).filter(isFullAdoptionRequest);
+
+    // Add each pending request to the result list
+    const animalAdoptionRequests: AnimalAdoptionRequests[] = adoptionRequest
+      .filter((request) => request.status === RequestStatus.PENDING)
+      .map((request) => ({ animal: animalSummary, request }));
+
     info(`${JSON.stringify(animalAdoptionRequests)}`);
     return animalAdoptionRequests;
   } catch (e) {
-    error(`Error fetching adoption requests: ${e}`);
+    error(`Error fetching adoption requests: ${describeError(e)}`);
     return [];
   }
 }
 
 /**
- * Approves a pending adoption request.
+ * Approves a pending adoption request. The backend marks the animal as adopted
+ * and rejects all other pending requests for it.
  *
  * @param request - The adoption request to approve.
+ * @param allowSplitBond - Whether to approve even if a bonded partner would be left behind.
+ * @returns The outcome of the approval, or null if it failed.
  */
-export async function approveRequest(request: AdoptionRequest): Promise<void> {
-  try {
-    // Update the animal's status
-    let animal = await getAnimalById(request.animalId);
-    if (!animal) {
-      error(`Animal with ID ${request.animalId} not found.`);
-      return;
-    }
-    animal.status = AnimalStatus.ADOPTED;
-    await updateAnimal(animal);
-
-    // Update the status of the approved request
-    request.status = RequestStatus.APPROVED;
-    request.adoptionTimestamp = Math.floor(Date.now() / 1000);
-    await updateAdoptionRequest(request);
-
-    // Retrieve all other adoption requests for the same animal
-    const otherPendingRequests = await getAdoptionRequestsByAnimalId(
-      request.animalId,
-    ).then((requests) =>
-      requests.filter(
-        (r) => r.id !== request.id && r.status === RequestStatus.PENDING,
-      ),
-    );
-
-    // Update status of the other requests
-    for (const other of otherPendingRequests) {
-      other.status = RequestStatus.REJECTED;
-      await updateAdoptionRequest(other);
-    }
-  } catch (e) {
-    error(`Error approving adoption request: ${e}`);
+export async function approveRequest(
+  request: AdoptionRequest,
+  allowSplitBond: boolean = false,
+): Promise<ApproveRequestResult | null> {
+  const result = await approveAdoptionRequest(request.id, allowSplitBond);
+  if (result && result.status !== ApproveRequestStatus.APPROVED) {
+    info(`Adoption request ${request.id} not approved: ${result.status}`);
   }
+  return result;
 }
 
 /**
@@ -172,6 +144,6 @@ export async function rejectRequest(request: AdoptionRequest): Promise<void> {
       }
     }
   } catch (e) {
-    error(`Error rejecting adoption request: ${e}`);
+    error(`Error rejecting adoption request: ${describeError(e)}`);
   }
 }
@@ -11,7 +11,8 @@ import {
 } from "$lib/utils/authentication-utils";
 import type { PageLoad } from "./$types";
 import { error } from "@tauri-apps/plugin-log";
-import { getAnimals } from "$lib/utils/data-utils";
+import { getNavigationBadges } from "../navigation-utils";
+import { describeError, getAnimals } from "$lib/utils/data-utils";
 
 export const load: PageLoad = async () => {
   try {
@@ -27,11 +28,12 @@ export const load: PageLoad = async () => {
 
     return {
       currentUser,
+      navigationBadges: await getNavigationBadges(),
       animals: await getAnimals(null),
     };
   } catch (e) {
     // Authentication check failed, redirect to authentication
-    error(`Error during page load: ${e}`);
+    error(`Error during page load: ${describeError(e)}`);
     goto("/");
   }
 };
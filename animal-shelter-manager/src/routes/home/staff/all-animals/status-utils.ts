@@ -17,8 +17,14 @@ export function getStatusColor(status: string): string {
       return "#ffc107";
     case "adopted":
       return "#007bff";
+    case "fostered":
+      return "#9c27b0";
     case "passed-away":
       return "#6c757d";
+    case "transferred":
+      return "#17a2b8";
+    case "trial-adoption":
+      return "#fd7e14";
     default:
       return "#6c757d";
   }
@@ -5,8 +5,22 @@
  * such as the navigation map for the sidebar.
  */
 
+import { getPendingRequestCount } from "$lib/utils/data-utils";
+
 export const navigationMap: Record<string, string> = {
   "All Animals": "/home/staff/all-animals",
   "Adoption Requests": "/home/staff/adoption-requests",
   "Adoption Reports": "/home/staff/adoption-reports",
+  "User Accounts": "/home/staff/user-accounts",
 };
+
+/**
+ * Fetches the counts shown next to sidebar navigation items.
+ *
+ * @returns A map from navigation item to its count.
+ */
+export async function getNavigationBadges(): Promise<Record<string, number>> {
+  return {
+    "Adoption Requests": await getPendingRequestCount(),
+  };
+}
@@ -0,0 +1,72 @@
+/**
+ * routes/home/staff/user-accounts/+page.ts
+ *
+ * Data loading functions for staff/user-accounts page authentication and routing.
+ * Only admins can manage user accounts; other staff are sent back to the animal list.
+ */
+
+import { goto } from "$app/navigation";
+import {
+  getCurrentUser,
+  getSessions,
+  getUsers,
+  UserRole,
+  type CurrentUser,
+  type SessionInfo,
+  type UserAccount,
+} from "$lib/utils/authentication-utils";
+import {
+  ErasureRequestStatus,
+  getErasureRequests,
+  getSettings,
+  getStorageStats,
+  type ErasureRequest,
+  type Setting,
+  type StorageStats,
+  describeError,
+} from "$lib/utils/data-utils";
+import type { PageLoad } from "./$types";
+import { error } from "@tauri-apps/plugin-log";
+import { getNavigationBadges } from "../navigation-utils";
+
+export const load: PageLoad = async () => {
+  try {
+    // Check if user is authenticated
+    let currentUser: CurrentUser | null = await getCurrentUser();
+
+    if (!currentUser) {
+      // Redirect to authentication if not logged in
+      error("User not authenticated");
+      goto("/authentication");
+      return; // prevent returning data
+    }
+
+    if (currentUser.role !== UserRole.ADMIN) {
+      error("Only admins can manage user accounts");
+      goto("/home/staff/all-animals");
+      return; // prevent returning data
+    }
+
+    const users: UserAccount[] = await getUsers();
+    const sessions: SessionInfo[] = await getSessions();
+    const erasureRequests: ErasureRequest[] = await getErasureRequests(
+      ErasureRequestStatus.PENDING,
+    );
+    const storageStats: StorageStats | null = await getStorageStats();
+    const settings: Setting[] = await getSettings();
+
+    return {
+      currentUser,
+      navigationBadges: await getNavigationBadges(),
+      users,
+      sessions,
+      erasureRequests,
+      storageStats,
+      settings,
+    };
+  } catch (e) {
+    // Authentication check failed, redirect to authentication
+    error(`Error during page load: ${describeError(e)}`);
+    goto("/");
+  }
+};
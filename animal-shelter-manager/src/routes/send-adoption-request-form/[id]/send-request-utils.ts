@@ -7,40 +7,55 @@
 import { error } from "@tauri-apps/plugin-log";
 import {
   AnimalStatus,
+  CreateRequestResult,
+  UpdateAnimalStatus,
   createAdoptionRequest,
   getAnimalById,
   updateAnimal,
   type AdoptionRequest,
+  describeError,
 } from "$lib/utils/data-utils";
 
 /**
  * Sends an adoption request for a specific animal.
- * Updates the animal status to "REQUESTED".
+ * Updates the animal status to "REQUESTED" once the request is stored.
  *
  * @param adoptionRequest - The adoption request data to be sent.
+ * @param linkProfile - Whether to link the request to the profile of the logged-in user.
+ * @returns Promise<CreateRequestResult | null> - Whether the request was stored or rejected as a duplicate. Returns null if the operation fails.
  */
 export async function sendAdoptionRequest(
   adoptionRequest: AdoptionRequest,
-): Promise<void> {
+  linkProfile: boolean = false,
+): Promise<CreateRequestResult | null> {
   try {
     // Retrieve the animal by ID
     let animal = await getAnimalById(adoptionRequest.animalId);
     if (!animal) {
       error(`Animal with ID ${adoptionRequest.animalId} not found.`);
-      return;
+      return null;
+    }
+
+    // Create a new adoption request in the database
+    const result = await createAdoptionRequest(
+      adoptionRequest,
+      linkProfile,
+    );
+    if (result !== CreateRequestResult.SUCCESS) {
+      error(`Adoption request for animal ID ${animal.id} was not created.`);
+      return result;
     }
 
     // Update the animal status
     animal.status = AnimalStatus.REQUESTED;
-    const updateStatus = await updateAnimal(animal);
-    if (!updateStatus) {
+    const updateResult = await updateAnimal(animal);
+    if (updateResult?.status !== UpdateAnimalStatus.UPDATED) {
       error(`Failed to update status for animal ID ${animal.id}.`);
-      return;
     }
 
-    // Create a new adoption request in the database
-    await createAdoptionRequest(adoptionRequest);
+    return result;
   } catch (e) {
-    error(`Error sending adoption request: ${e}`);
+    error(`Error sending adoption request: ${describeError(e)}`);
+    return null;
   }
 }